@@ -141,6 +141,22 @@ fn main() {
         println!("cargo:rustc-env=DB_PASSWORD={}", db_password);
     }
     
+    // Ed25519 public key (base64) that release installers must be signed with
+    println!("cargo:rerun-if-env-changed=MOCKMATE_UPDATER_PUBKEY");
+    if let Ok(updater_pubkey) = env::var("MOCKMATE_UPDATER_PUBKEY") {
+        println!("cargo:rustc-env=MOCKMATE_UPDATER_PUBKEY={}", updater_pubkey);
+    } else {
+        println!("cargo:warning=MOCKMATE_UPDATER_PUBKEY not set - update installation will be disabled");
+    }
+    
+    // Ed25519 public key (base64) that offline session entitlements must be signed with
+    println!("cargo:rerun-if-env-changed=MOCKMATE_ENTITLEMENT_PUBKEY");
+    if let Ok(entitlement_pubkey) = env::var("MOCKMATE_ENTITLEMENT_PUBKEY") {
+        println!("cargo:rustc-env=MOCKMATE_ENTITLEMENT_PUBKEY={}", entitlement_pubkey);
+    } else {
+        println!("cargo:warning=MOCKMATE_ENTITLEMENT_PUBKEY not set - offline grace will be disabled");
+    }
+    
     // Only run tauri_build if we're building the main application, not test binaries
     let pkg_name = env::var("CARGO_PKG_NAME").unwrap_or_default();
     let bin_name = env::var("CARGO_BIN_NAME").unwrap_or_default();
@@ -1,10 +1,15 @@
 use anyhow::Result;
 use log::{info, debug, warn};
+#[cfg(windows)]
+use log::{trace, log_enabled, Level};
+use schemars::JsonSchema;
 use serde::{Serialize, Deserialize};
+use specta::Type;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tauri::{AppHandle, Emitter};
 use tokio::time::interval;
+#[cfg(windows)]
 use windows_sys::Win32::{
     Foundation::{HWND, BOOL, TRUE, FALSE},
     UI::{
@@ -14,17 +19,21 @@ use windows_sys::Win32::{
         },
     },
     System::{
-        Com::{CoInitialize, CoUninitialize},
         ProcessStatus::K32GetModuleBaseNameW,
         Threading::{OpenProcess, PROCESS_QUERY_INFORMATION, PROCESS_VM_READ},
     },
 };
+#[cfg(windows)]
 use winapi::um::{
     winuser::{GetWindowThreadProcessId, GetForegroundWindow},
 };
+use crate::envelope::{self, IntoResult, Response};
+use crate::guard::{self, Require};
+use crate::permissions::Permission;
+use crate::events;
 
 /// Configuration for accessibility text reading
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccessibilityConfig {
     /// Target applications to monitor (e.g., "Teams", "Zoom", "Chrome")
     pub target_apps: Vec<String>,
@@ -77,7 +86,7 @@ impl Default for AccessibilityConfig {
 }
 
 /// Result of text extraction from accessibility APIs
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
 pub struct AccessibilityTextResult {
     /// Extracted text content
     pub text: String,
@@ -101,7 +110,24 @@ pub struct AccessibilityTextResult {
     pub text_length: usize,
 }
 
-/// Windows-specific accessibility text reader
+/// Hand a detected question to the frontend as `accessibility-question-detected`, unless a script
+/// hook drops it (custom filters). Returns whether it was emitted.
+pub async fn emit_detected_question(app_handle: &AppHandle, result: &AccessibilityTextResult) -> bool {
+    if !crate::scripting::filter(crate::scripting::ScriptEvent::QuestionDetected, result).await {
+        return false;
+    }
+    info!("📝 Real-time question detected from {}: {}",
+          result.source_app,
+          result.text.chars().take(100).collect::<String>());
+    if let Err(e) = app_handle.emit(events::ACCESSIBILITY_QUESTION_DETECTED, result) {
+        warn!("Failed to emit question detection event: {}", e);
+    }
+    true
+}
+
+/// Windows-specific accessibility text reader; other platforms get the unavailable reader from
+/// `accessibility_worker`
+#[cfg(windows)]
 pub struct WindowsAccessibilityReader {
     config: AccessibilityConfig,
     last_seen_text: Option<String>,
@@ -112,13 +138,10 @@ pub struct WindowsAccessibilityReader {
     window_text_cache: std::collections::HashMap<HWND, String>,
 }
 
+#[cfg(windows)]
 impl WindowsAccessibilityReader {
+    /// COM is owned by the accessibility worker thread (see accessibility_worker), not the reader
     pub fn new(config: AccessibilityConfig) -> Result<Self> {
-        // Initialize COM for UI Automation
-        unsafe {
-            CoInitialize(std::ptr::null_mut());
-        }
-
         // Compile question detection patterns
         let question_patterns = vec![
             regex::Regex::new(r".*\?$").unwrap(),                           // Ends with question mark
@@ -143,7 +166,7 @@ impl WindowsAccessibilityReader {
 
     /// Read text from the currently focused/active window
     pub fn read_text_from_current_window(&mut self) -> Result<Option<AccessibilityTextResult>> {
-        info!("🎯 Reading text from current active window...");
+        trace!("🎯 Reading text from current active window...");
 
         let focused_hwnd = unsafe { GetForegroundWindow() };
         if focused_hwnd.is_null() {
@@ -161,14 +184,15 @@ impl WindowsAccessibilityReader {
             return Ok(None);
         }
 
-        info!("📖 Extracting from current window: {} ({})", app_name, window_title);
+        debug!("📖 Extracting from current window: {} ({})", app_name, window_title);
         
         match self.extract_text_from_window(focused_hwnd as isize) {
             Ok(Some(result)) => {
                 if self.is_new_content(&result.text) {
-                    info!("📝 New content detected from current window {}: {}", 
-                          result.source_app, 
-                          result.text.chars().take(100).collect::<String>());
+                    info!("📝 New content detected from current window {} ({} chars)", result.source_app, result.text.len());
+                    if log_enabled!(Level::Debug) {
+                        debug!("📝 Content preview: {}", result.text.chars().take(100).collect::<String>());
+                    }
                     Ok(Some(result))
                 } else {
                     debug!("Content hasn't changed in current window");
@@ -193,18 +217,18 @@ impl WindowsAccessibilityReader {
         let mut results = Vec::new();
         let windows = self.enumerate_windows()?;
         
-        info!("📊 Found {} total windows to examine", windows.len());
+        debug!("📊 Found {} total windows to examine", windows.len());
         
         for window_info in windows {
             if self.should_process_window(&window_info) {
-                info!("🔍 Processing window: {} ({})", window_info.title, window_info.hwnd);
+                debug!("🔍 Processing window: {} ({})", window_info.title, window_info.hwnd);
                 
                 match self.extract_text_from_window(window_info.hwnd) {
                     Ok(Some(result)) => {
-                        info!("✅ Extracted {} chars from {}: {}", 
-                              result.text.len(), 
-                              result.source_app,
-                              result.text.chars().take(100).collect::<String>());
+                        debug!("✅ Extracted {} chars from {}", result.text.len(), result.source_app);
+                        if log_enabled!(Level::Trace) {
+                            trace!("Extracted text preview: {}", result.text.chars().take(100).collect::<String>());
+                        }
                         results.push(result);
                     }
                     Ok(None) => {
@@ -377,6 +401,12 @@ impl WindowsAccessibilityReader {
         let window_title = self.get_window_title(hwnd)?;
         let app_name = self.get_application_name(hwnd)?;
 
+        // Reading another application's window content always needs the user's approval
+        if !crate::app_consent::check_or_request(&app_name, &window_title) {
+            debug!("⏸️ Skipping {} until consent is granted", app_name);
+            return Ok(None);
+        }
+
         debug!("📖 Extracting text from: {} ({})", app_name, window_title);
 
         // Try multiple text extraction methods
@@ -427,7 +457,7 @@ impl WindowsAccessibilityReader {
         let app_name = self.get_application_name(hwnd).unwrap_or_default();
         let window_title = self.get_window_title(hwnd).unwrap_or_default();
         
-        info!("🔍 Extracting text from: {} - {}", app_name, window_title);
+        debug!("🔍 Extracting text from: {} - {}", app_name, window_title);
         
         let is_browser = app_name.to_lowercase().contains("chrome") ||
                         app_name.to_lowercase().contains("firefox") ||
@@ -545,8 +575,8 @@ impl WindowsAccessibilityReader {
             }
         }
         
-        // Strategy 5: Clipboard monitoring (last resort)
-        if extracted_text.trim().is_empty() {
+        // Strategy 5: Clipboard monitoring (last resort), unless managed policy turns it off
+        if extracted_text.trim().is_empty() && crate::managed_policy::clipboard_monitoring_allowed() {
             info!("⚠️ All methods failed, trying clipboard monitoring");
             
             if let Ok(text) = self.extract_text_clipboard_monitoring(hwnd) {
@@ -560,7 +590,7 @@ impl WindowsAccessibilityReader {
         if extracted_text.trim().is_empty() {
             warn!("❌ All extraction methods failed for {} - {}", app_name, window_title);
         } else {
-            info!("✅ Final extracted text: {} chars from {}", extracted_text.len(), app_name);
+            debug!("✅ Final extracted text: {} chars from {}", extracted_text.len(), app_name);
         }
         
         Ok(extracted_text)
@@ -1873,8 +1903,13 @@ impl WindowsAccessibilityReader {
         std::cmp::min(score, 100)
     }
 
-    /// Enumerate all windows (including hidden ones if configured)
+    /// Enumerate all windows (including hidden ones if configured), reusing the cached list when fresh
     fn enumerate_windows(&self) -> Result<Vec<WindowInfo>> {
+        crate::window_cache::windows(self.config.monitor_hidden_windows, || self.enumerate_windows_uncached())
+    }
+
+    /// Walk every top-level window
+    fn enumerate_windows_uncached(&self) -> Result<Vec<WindowInfo>> {
         let mut windows: Vec<WindowInfo> = Vec::new();
         let monitor_hidden = self.config.monitor_hidden_windows;
         
@@ -2121,88 +2156,78 @@ impl WindowsAccessibilityReader {
     }
 }
 
-impl Drop for WindowsAccessibilityReader {
-    fn drop(&mut self) {
-        unsafe {
-            CoUninitialize();
-        }
-    }
-}
-
 /// Window information structure
+#[cfg(windows)]
 #[derive(Debug, Clone)]
-struct WindowInfo {
+pub(crate) struct WindowInfo {
     hwnd: HWND,
     title: String,
 }
 
-/// Initialize and create accessibility reader
-pub fn create_accessibility_reader() -> Result<WindowsAccessibilityReader> {
-    let config = AccessibilityConfig::default();
-    WindowsAccessibilityReader::new(config)
-}
-
 /// Tauri command to read text from the current active window
 #[tauri::command]
-pub async fn read_text_from_current_window() -> Result<Option<AccessibilityTextResult>, String> {
-    info!("🚀 Reading text from current active window...");
+#[specta::specta]
+pub async fn read_text_from_current_window() -> Response<Option<AccessibilityTextResult>> {
+    guard::respond("read_text_from_current_window", &[Require::Permission(Permission::Accessibility)], async move {
+        info!("🚀 Reading text from current active window...");
     
-    let mut reader = create_accessibility_reader()
-        .map_err(|e| format!("Failed to create accessibility reader: {}", e))?;
+        let result = crate::accessibility_worker::run(|reader| reader.read_text_from_current_window()).await?
+            .map_err(|e| format!("Failed to read current window: {}", e))?;
     
-    let result = reader.read_text_from_current_window()
-        .map_err(|e| format!("Failed to read current window: {}", e))?;
-    
-    match &result {
-        Some(text_result) => {
-            info!("✅ Current window text: {} chars from {}", 
-                  text_result.text.len(), text_result.source_app);
-        }
-        None => {
-            info!("ℹ️ No relevant text found in current window");
+        match &result {
+            Some(text_result) => {
+                info!("✅ Current window text: {} chars from {}", 
+                      text_result.text.len(), text_result.source_app);
+            }
+            None => {
+                info!("ℹ️ No relevant text found in current window");
+            }
         }
-    }
     
-    Ok(result)
+        Ok(result)
+    })
+    .await
 }
 
 /// Tauri command to read text from applications (now scans all target applications)
 #[tauri::command]
-pub async fn read_text_from_applications() -> Result<Vec<AccessibilityTextResult>, String> {
-    info!("🚀 Starting accessibility text reading from ALL target applications...");
-    
-    let mut reader = create_accessibility_reader()
-        .map_err(|e| format!("Failed to create accessibility reader: {}", e))?;
+#[specta::specta]
+pub async fn read_text_from_applications() -> Response<Vec<AccessibilityTextResult>> {
+    guard::respond("read_text_from_applications", &[Require::Permission(Permission::Accessibility)], async move {
+        info!("🚀 Starting accessibility text reading from ALL target applications...");
     
-    let results = reader.read_text_from_all_target_applications()
-        .map_err(|e| format!("Failed to read text from target applications: {}", e))?;
+        let results = crate::accessibility_worker::run(|reader| reader.read_text_from_all_target_applications()).await?
+            .map_err(|e| format!("Failed to read text from target applications: {}", e))?;
     
-    info!("✅ Text reading completed: {} results from target applications", results.len());
-    Ok(results)
+        info!("✅ Text reading completed: {} results from target applications", results.len());
+        Ok(results)
+    })
+    .await
 }
 
 /// Tauri command to read text from focused window
 #[tauri::command]
-pub async fn read_text_from_focused_window() -> Result<Option<AccessibilityTextResult>, String> {
-    info!("🎯 Reading text from focused window...");
-    
-    let mut reader = create_accessibility_reader()
-        .map_err(|e| format!("Failed to create accessibility reader: {}", e))?;
+#[specta::specta]
+pub async fn read_text_from_focused_window() -> Response<Option<AccessibilityTextResult>> {
+    guard::respond("read_text_from_focused_window", &[Require::Permission(Permission::Accessibility)], async move {
+        info!("🎯 Reading text from focused window...");
     
-    let result = reader.read_text_from_focused_window()
-        .map_err(|e| format!("Failed to read focused window: {}", e))?;
+        let result = crate::accessibility_worker::run(|reader| reader.read_text_from_focused_window()).await?
+            .map_err(|e| format!("Failed to read focused window: {}", e))?;
     
-    match &result {
-        Some(text_result) => {
-            info!("✅ Focused window text: {} chars from {}", 
-                  text_result.text.len(), text_result.source_app);
-        }
-        None => {
-            info!("ℹ️ No text found in focused window");
+        match &result {
+            Some(text_result) => {
+                info!("✅ Focused window text: {} chars from {}", 
+                      text_result.text.len(), text_result.source_app);
+            }
+            None => {
+                info!("ℹ️ No text found in focused window");
+            }
         }
-    }
     
-    Ok(result)
+        Ok(result)
+    })
+    .await
 }
 
 /// Real-time text monitoring service
@@ -2260,10 +2285,12 @@ impl RealtimeTextMonitor {
                 // Wait for next interval
                 interval.tick().await;
                 
-                // Read text from current window
-                if let Ok(mut reader) = create_accessibility_reader() {
-                    match reader.read_text_from_current_window() {
+                // Read text from current window on the accessibility thread
+                if let Ok(read) = crate::accessibility_worker::run(|reader| reader.read_text_from_current_window()).await {
+                    match read {
                         Ok(Some(result)) => {
+                            crate::auto_analysis::observe(&app_handle, &result);
+
                             // Check if this is new text
                             let key = format!("{}-{}", result.source_app, result.window_title);
                             let is_new = match last_seen_texts.get(&key) {
@@ -2271,16 +2298,10 @@ impl RealtimeTextMonitor {
                                 None => true,
                             };
                             
-                            if is_new && result.is_potential_question {
-                                info!("📝 Real-time question detected from current window {}: {}", 
-                                      result.source_app, 
-                                      result.text.chars().take(100).collect::<String>());
-                                
-                                // Emit event to frontend
-                                if let Err(e) = app_handle.emit("accessibility-question-detected", &result) {
-                                    warn!("Failed to emit question detection event: {}", e);
-                                }
-                                
+                            if is_new
+                                && result.is_potential_question
+                                && emit_detected_question(&app_handle, &result).await
+                            {
                                 // Update last seen text
                                 last_seen_texts.insert(key, result.text);
                             }
@@ -2322,6 +2343,7 @@ static GLOBAL_MONITOR: std::sync::OnceLock<Arc<Mutex<Option<RealtimeTextMonitor>
 
 /// Initialize real-time monitoring
 pub fn init_realtime_monitoring(app_handle: AppHandle) {
+    crate::app_consent::attach_app_handle(app_handle.clone());
     let config = AccessibilityConfig::default();
     let monitor = RealtimeTextMonitor::new(app_handle, config);
     
@@ -2332,99 +2354,125 @@ pub fn init_realtime_monitoring(app_handle: AppHandle) {
     info!("✅ Real-time accessibility monitoring initialized");
 }
 
+/// Configuration used by the global monitor (defaults if monitoring was never initialized)
+pub fn current_config() -> AccessibilityConfig {
+    GLOBAL_MONITOR
+        .get()
+        .and_then(|m| m.lock().ok().and_then(|g| g.as_ref().map(|monitor| monitor.config.clone())))
+        .unwrap_or_default()
+}
+
 /// Tauri command to start real-time monitoring
 #[tauri::command]
-pub async fn start_realtime_monitoring() -> Result<String, String> {
-    info!("🚀 Starting real-time monitoring via command...");
-    
-    let global_monitor = GLOBAL_MONITOR.get()
-        .ok_or("Monitoring not initialized")?;
-    
-    // Clone the monitor to avoid holding the lock across await
-    let monitor = {
-        let monitor_guard = global_monitor.lock().map_err(|e| e.to_string())?;
-        monitor_guard.as_ref()
-            .ok_or("Monitor not available")?
-            .clone()
-    };
+#[specta::specta]
+pub async fn start_realtime_monitoring() -> Response<String> {
+    guard::respond("start_realtime_monitoring", &[Require::Permission(Permission::Accessibility)], async move {
+        info!("🚀 Starting real-time monitoring via command...");
+        crate::platform::ensure_available(crate::platform::Capability::AccessibilityReader)?;
+    
+        let global_monitor = GLOBAL_MONITOR.get()
+            .ok_or("Monitoring not initialized")?;
+    
+        // Clone the monitor to avoid holding the lock across await
+        let monitor = {
+            let monitor_guard = global_monitor.lock().map_err(|e| e.to_string())?;
+            monitor_guard.as_ref()
+                .ok_or("Monitor not available")?
+                .clone()
+        };
     
-    monitor.start_monitoring().await?;
+        monitor.start_monitoring().await?;
     
-    Ok("Real-time monitoring started".to_string())
+        Ok("Real-time monitoring started".to_string())
+    })
+    .await
 }
 
 /// Tauri command to stop real-time monitoring
 #[tauri::command]
-pub async fn stop_realtime_monitoring() -> Result<String, String> {
-    info!("🛑 Stopping real-time monitoring via command...");
-    
-    let global_monitor = GLOBAL_MONITOR.get()
-        .ok_or("Monitoring not initialized")?;
-    
-    // Clone the monitor to avoid holding the lock
-    let monitor = {
-        let monitor_guard = global_monitor.lock().map_err(|e| e.to_string())?;
-        monitor_guard.as_ref()
-            .ok_or("Monitor not available")?
-            .clone()
-    };
+#[specta::specta]
+pub async fn stop_realtime_monitoring() -> Response<String> {
+    envelope::respond("stop_realtime_monitoring", async move {
+        info!("🛑 Stopping real-time monitoring via command...");
+    
+        let global_monitor = GLOBAL_MONITOR.get()
+            .ok_or("Monitoring not initialized")?;
+    
+        // Clone the monitor to avoid holding the lock
+        let monitor = {
+            let monitor_guard = global_monitor.lock().map_err(|e| e.to_string())?;
+            monitor_guard.as_ref()
+                .ok_or("Monitor not available")?
+                .clone()
+        };
     
-    monitor.stop_monitoring()?;
+        monitor.stop_monitoring()?;
     
-    Ok("Real-time monitoring stopped".to_string())
+        Ok("Real-time monitoring stopped".to_string())
+    })
+    .await
 }
 
 /// Tauri command to get monitoring status
 #[tauri::command]
-pub async fn get_monitoring_status() -> Result<serde_json::Value, String> {
-    let global_monitor = GLOBAL_MONITOR.get()
-        .ok_or("Monitoring not initialized")?;
-    
-    let is_active = {
-        let monitor_guard = global_monitor.lock().map_err(|e| e.to_string())?;
-        match monitor_guard.as_ref() {
-            Some(monitor) => monitor.is_monitoring(),
-            None => false,
-        }
-    };
-    
-    Ok(serde_json::json!({
-        "is_monitoring": is_active,
-        "interval_ms": AccessibilityConfig::default().monitoring_interval_ms,
-        "target_apps": AccessibilityConfig::default().target_apps
-    }))
+#[specta::specta]
+pub async fn get_monitoring_status() -> Response<serde_json::Value> {
+    envelope::respond("get_monitoring_status", async move {
+        let global_monitor = GLOBAL_MONITOR.get()
+            .ok_or("Monitoring not initialized")?;
+    
+        let is_active = {
+            let monitor_guard = global_monitor.lock().map_err(|e| e.to_string())?;
+            match monitor_guard.as_ref() {
+                Some(monitor) => monitor.is_monitoring(),
+                None => false,
+            }
+        };
+    
+        Ok(serde_json::json!({
+            "is_monitoring": is_active,
+            "interval_ms": AccessibilityConfig::default().monitoring_interval_ms,
+            "target_apps": AccessibilityConfig::default().target_apps
+        }))
+    })
+    .await
 }
 
 /// Tauri command for hybrid text extraction (Accessibility + OCR fallback)
 #[tauri::command]
-pub async fn extract_text_hybrid_approach() -> Result<Vec<AccessibilityTextResult>, String> {
-    info!("🔄 Starting hybrid text extraction (Accessibility + OCR fallback)...");
+#[specta::specta]
+pub async fn extract_text_hybrid_approach() -> Response<Vec<AccessibilityTextResult>> {
+    guard::respond("extract_text_hybrid_approach", &[Require::Permission(Permission::Accessibility)], async move {
+        info!("🔄 Starting hybrid text extraction (Accessibility + OCR fallback)...");
     
-    // First try accessibility API
-    let accessibility_results = match read_text_from_applications().await {
-        Ok(results) if !results.is_empty() => {
-            info!("✅ Accessibility API found {} results", results.len());
-            results
-        }
-        _ => {
-            info!("⚠️ Accessibility API found no results, trying OCR fallback...");
-            Vec::new()
-        }
-    };
+        // First try accessibility API
+        let accessibility_results = match read_text_from_applications().await.into_result() {
+            Ok(results) if !results.is_empty() => {
+                info!("✅ Accessibility API found {} results", results.len());
+                results
+            }
+            _ => {
+                info!("⚠️ Accessibility API found no results, trying OCR fallback...");
+                Vec::new()
+            }
+        };
     
-    // If no results from accessibility, try OCR as fallback
-    if accessibility_results.is_empty() {
-        // This would integrate with your existing screenshot + OCR functionality
-        // For now, we'll return a placeholder that indicates OCR should be used
-        info!("📸 Would trigger OCR screenshot analysis here...");
+        // If no results from accessibility, try OCR as fallback
+        if accessibility_results.is_empty() {
+            // This would integrate with your existing screenshot + OCR functionality
+            // For now, we'll return a placeholder that indicates OCR should be used
+            info!("📸 Would trigger OCR screenshot analysis here...");
         
-        // You can integrate this with your existing screenshot analysis
-        // by calling the screenshot module and OCR processing
-    }
+            // You can integrate this with your existing screenshot analysis
+            // by calling the screenshot module and OCR processing
+        }
     
-    Ok(accessibility_results)
+        Ok(accessibility_results)
+    })
+    .await
 }
 
+#[cfg(windows)]
 impl WindowsAccessibilityReader {
     /// Update the previously focused window
     pub fn update_previous_focused_window(&mut self) {
@@ -2589,63 +2637,67 @@ impl WindowsAccessibilityReader {
 
 /// Tauri command to read from background windows
 #[tauri::command]
-pub async fn read_text_from_background_windows() -> Result<Vec<AccessibilityTextResult>, String> {
-    info!("🔍 Reading text from background windows...");
-    
-    let mut reader = create_accessibility_reader()
-        .map_err(|e| format!("Failed to create accessibility reader: {}", e))?;
+#[specta::specta]
+pub async fn read_text_from_background_windows() -> Response<Vec<AccessibilityTextResult>> {
+    guard::respond("read_text_from_background_windows", &[Require::Permission(Permission::Accessibility)], async move {
+        info!("🔍 Reading text from background windows...");
     
-    let results = reader.read_background_windows()
-        .map_err(|e| format!("Failed to read background windows: {}", e))?;
+        let results = crate::accessibility_worker::run(|reader| reader.read_background_windows()).await?
+            .map_err(|e| format!("Failed to read background windows: {}", e))?;
     
-    info!("✅ Background window reading completed: {} results", results.len());
-    Ok(results)
+        info!("✅ Background window reading completed: {} results", results.len());
+        Ok(results)
+    })
+    .await
 }
 
 /// NEW: Tauri command to read text specifically from the window behind MockMate
 /// This is the key function for your use case - extracting text from the interviewer's window
 #[tauri::command]
-pub async fn read_text_from_window_behind_mockmate() -> Result<Option<AccessibilityTextResult>, String> {
-    info!("🎯 Reading text from window behind MockMate (interviewer's window)...");
+#[specta::specta]
+pub async fn read_text_from_window_behind_mockmate() -> Response<Option<AccessibilityTextResult>> {
+    guard::respond("read_text_from_window_behind_mockmate", &[Require::Permission(Permission::Accessibility)], async move {
+        info!("🎯 Reading text from window behind MockMate (interviewer's window)...");
     
-    let mut reader = create_accessibility_reader()
-        .map_err(|e| format!("Failed to create accessibility reader: {}", e))?;
+        let result = crate::accessibility_worker::run(|reader| reader.read_text_from_window_behind_mockmate()).await?
+            .map_err(|e| format!("Failed to read from window behind MockMate: {}", e))?;
     
-    let result = reader.read_text_from_window_behind_mockmate()
-        .map_err(|e| format!("Failed to read from window behind MockMate: {}", e))?;
-    
-    match &result {
-        Some(text_result) => {
-            info!("✅ Found text from window behind MockMate: {} chars from {} - '{}'", 
-                  text_result.text.len(), 
-                  text_result.source_app,
-                  text_result.text.chars().take(100).collect::<String>());
-        }
-        None => {
-            info!("ℹ️ No text found in window behind MockMate");
+        match &result {
+            Some(text_result) => {
+                info!("✅ Found text from window behind MockMate: {} chars from {} - '{}'", 
+                      text_result.text.len(), 
+                      text_result.source_app,
+                      text_result.text.chars().take(100).collect::<String>());
+            }
+            None => {
+                info!("ℹ️ No text found in window behind MockMate");
+            }
         }
-    }
     
-    Ok(result)
+        Ok(result)
+    })
+    .await
 }
 
 /// NEW: Tauri command to capture the current focused window before MockMate takes focus
 /// Call this before showing MockMate to remember which window to extract from
 #[tauri::command]
-pub async fn capture_previous_focused_window() -> Result<String, String> {
-    info!("📸 Capturing currently focused window before MockMate takes focus...");
-    
-    let mut reader = create_accessibility_reader()
-        .map_err(|e| format!("Failed to create accessibility reader: {}", e))?;
+#[specta::specta]
+pub async fn capture_previous_focused_window() -> Response<String> {
+    envelope::respond("capture_previous_focused_window", async move {
+        info!("📸 Capturing currently focused window before MockMate takes focus...");
     
-    reader.capture_previous_focused_window()
-        .map_err(|e| format!("Failed to capture previous focused window: {}", e))?;
+        crate::accessibility_worker::run(|reader| reader.capture_previous_focused_window()).await?
+            .map_err(|e| format!("Failed to capture previous focused window: {}", e))?;
     
-    Ok("Previous focused window captured successfully".to_string())
+        Ok("Previous focused window captured successfully".to_string())
+    })
+    .await
 }
 
 /// Configuration update command
 #[tauri::command]
+#[specta::specta]
 pub async fn update_accessibility_config(
     target_apps: Option<Vec<String>>,
     focused_only: Option<bool>,
@@ -2654,38 +2706,41 @@ pub async fn update_accessibility_config(
     track_previous_focus: Option<bool>,
     monitor_hidden_windows: Option<bool>,
     allow_window_activation: Option<bool>
-) -> Result<String, String> {
-    info!("⚙️ Updating accessibility configuration...");
+) -> Response<String> {
+    envelope::respond("update_accessibility_config", async move {
+        info!("⚙️ Updating accessibility configuration...");
     
-    // Note: In a real implementation, you'd want to store this config
-    // and apply it to the global monitor. For now, we'll just log the changes.
+        // Note: In a real implementation, you'd want to store this config
+        // and apply it to the global monitor. For now, we'll just log the changes.
     
-    if let Some(apps) = target_apps {
-        info!("📱 Updated target apps: {:?}", apps);
-    }
-    if let Some(focused) = focused_only {
-        info!("🎯 Updated focused_only: {}", focused);
-    }
-    if let Some(min_len) = min_question_length {
-        info!("📏 Updated min_question_length: {}", min_len);
-    }
-    if let Some(interval) = monitoring_interval_ms {
-        info!("⏱️ Updated monitoring_interval_ms: {}", interval);
-    }
-    if let Some(track_focus) = track_previous_focus {
-        info!("👁️ Updated track_previous_focus: {}", track_focus);
-    }
-    if let Some(monitor_hidden) = monitor_hidden_windows {
-        info!("👻 Updated monitor_hidden_windows: {}", monitor_hidden);
-    }
-    if let Some(allow_activation) = allow_window_activation {
-        info!("🎯 Updated allow_window_activation: {}", allow_activation);
-    }
+        if let Some(apps) = target_apps {
+            info!("📱 Updated target apps: {:?}", apps);
+        }
+        if let Some(focused) = focused_only {
+            info!("🎯 Updated focused_only: {}", focused);
+        }
+        if let Some(min_len) = min_question_length {
+            info!("📏 Updated min_question_length: {}", min_len);
+        }
+        if let Some(interval) = monitoring_interval_ms {
+            info!("⏱️ Updated monitoring_interval_ms: {}", interval);
+        }
+        if let Some(track_focus) = track_previous_focus {
+            info!("👁️ Updated track_previous_focus: {}", track_focus);
+        }
+        if let Some(monitor_hidden) = monitor_hidden_windows {
+            info!("👻 Updated monitor_hidden_windows: {}", monitor_hidden);
+        }
+        if let Some(allow_activation) = allow_window_activation {
+            info!("🎯 Updated allow_window_activation: {}", allow_activation);
+        }
     
-    Ok("Configuration updated successfully".to_string())
+        Ok("Configuration updated successfully".to_string())
+    })
+    .await
 }
 
-#[cfg(test)]
+#[cfg(all(test, windows))]
 mod tests {
     use super::*;
 
@@ -0,0 +1,78 @@
+// Dedicated COM apartment thread for accessibility work
+// COM is initialized once as a single-threaded apartment on a thread that lives for the whole
+// process. That thread owns the one WindowsAccessibilityReader, so COM/UIA objects never cross
+// apartments, and extraction never blocks a tokio worker.
+
+use std::sync::mpsc;
+use std::sync::OnceLock;
+
+use log::{error, info};
+use windows_sys::Win32::System::Com::{CoInitializeEx, CoUninitialize, COINIT_APARTMENTTHREADED};
+
+use crate::accessibility_reader::{AccessibilityConfig, WindowsAccessibilityReader};
+
+type Job = Box<dyn FnOnce(&mut WindowsAccessibilityReader) + Send>;
+
+static WORKER: OnceLock<mpsc::Sender<Job>> = OnceLock::new();
+
+/// Balances CoInitializeEx when the worker thread exits
+struct ApartmentGuard;
+
+impl Drop for ApartmentGuard {
+    fn drop(&mut self) {
+        unsafe {
+            CoUninitialize();
+        }
+    }
+}
+
+fn worker() -> &'static mpsc::Sender<Job> {
+    WORKER.get_or_init(|| {
+        let (tx, rx) = mpsc::channel::<Job>();
+        let spawned = std::thread::Builder::new()
+            .name("accessibility-sta".to_string())
+            .spawn(move || {
+                let hr = unsafe { CoInitializeEx(std::ptr::null(), COINIT_APARTMENTTHREADED as u32) };
+                // S_FALSE (1) means COM was already initialized on this thread; both need CoUninitialize
+                let _apartment = (hr >= 0).then_some(ApartmentGuard);
+                if hr < 0 {
+                    error!("Accessibility worker failed to initialize COM (HRESULT 0x{:08X})", hr);
+                }
+
+                let mut reader = match WindowsAccessibilityReader::new(AccessibilityConfig::default()) {
+                    Ok(reader) => reader,
+                    Err(e) => {
+                        error!("Accessibility worker failed to create reader: {}", e);
+                        return;
+                    }
+                };
+                info!("🧵 Accessibility STA worker started");
+
+                // Jobs run one at a time in arrival order
+                for job in rx {
+                    job(&mut reader);
+                }
+            });
+        if let Err(e) = spawned {
+            error!("Failed to spawn accessibility worker thread: {}", e);
+        }
+        tx
+    })
+}
+
+/// Run `job` on the accessibility thread with its reader and await the result
+pub async fn run<T, F>(job: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut WindowsAccessibilityReader) -> T + Send + 'static,
+{
+    let (reply_tx, reply_rx) = tokio::sync::oneshot::channel();
+    worker()
+        .send(Box::new(move |reader| {
+            let _ = reply_tx.send(job(reader));
+        }))
+        .map_err(|_| "Accessibility worker is not running".to_string())?;
+    reply_rx
+        .await
+        .map_err(|_| "Accessibility worker stopped before finishing the request".to_string())
+}
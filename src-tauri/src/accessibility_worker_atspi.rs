@@ -0,0 +1,330 @@
+// Accessibility worker for Linux over AT-SPI
+// Desktop applications (GTK, Qt, Chromium/Electron with accessibility enabled) publish their UI on
+// the accessibility bus. One thread owns the connection and an AtspiReader, and answers the same
+// jobs as the UI Automation worker on Windows: find the active or background frame, walk its tree
+// and join the text it shows.
+
+use std::sync::mpsc;
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, Result};
+use log::{debug, error, info};
+use zbus::blocking::Connection;
+use zbus::zvariant::{OwnedObjectPath, OwnedValue};
+
+use crate::accessibility_reader::{AccessibilityConfig, AccessibilityTextResult};
+
+type Job = Box<dyn FnOnce(&mut AtspiReader) + Send>;
+
+static WORKER: OnceLock<mpsc::Sender<Job>> = OnceLock::new();
+
+const REGISTRY: &str = "org.a11y.atspi.Registry";
+const ROOT_PATH: &str = "/org/a11y/atspi/accessible/root";
+const ACCESSIBLE: &str = "org.a11y.atspi.Accessible";
+const TEXT: &str = "org.a11y.atspi.Text";
+
+// Bit positions in the AT-SPI state set
+const STATE_ACTIVE: u32 = 1;
+const STATE_SHOWING: u32 = 25;
+
+/// Nodes visited per window before giving up on the rest of the tree
+const MAX_NODES: usize = 2000;
+const MAX_DEPTH: usize = 40;
+
+/// An accessible object: the owning bus name and its object path
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Node {
+    bus: String,
+    path: OwnedObjectPath,
+}
+
+/// A top-level frame and the application that owns it
+#[derive(Debug, Clone)]
+struct Frame {
+    node: Node,
+    app_name: String,
+    title: String,
+    process_id: u32,
+    active: bool,
+}
+
+pub struct AtspiReader {
+    config: AccessibilityConfig,
+    bus: Option<Connection>,
+    previous_focus: Option<Node>,
+}
+
+impl AtspiReader {
+    fn new(config: AccessibilityConfig) -> Self {
+        Self {
+            config,
+            bus: None,
+            previous_focus: None,
+        }
+    }
+
+    /// The accessibility bus, connected on first use and reconnected after a failure
+    fn bus(&mut self) -> Result<Connection> {
+        if let Some(bus) = &self.bus {
+            return Ok(bus.clone());
+        }
+        let session = Connection::session().map_err(|e| anyhow!("No D-Bus session bus: {}", e))?;
+        let address: String = session
+            .call_method(Some("org.a11y.Bus"), "/org/a11y/bus", Some("org.a11y.Bus"), "GetAddress", &())
+            .and_then(|reply| reply.body().deserialize())
+            .map_err(|e| anyhow!("AT-SPI bus is not running (is accessibility enabled?): {}", e))?;
+        let bus = zbus::blocking::connection::Builder::address(address.as_str())?.build()?;
+        info!("♿ Connected to the AT-SPI bus");
+        self.bus = Some(bus.clone());
+        Ok(bus)
+    }
+
+    fn call<B, R>(bus: &Connection, node: &Node, iface: &str, method: &str, body: &B) -> Result<R>
+    where
+        B: serde::Serialize + zbus::zvariant::DynamicType,
+        R: for<'d> zbus::zvariant::DynamicDeserialize<'d>,
+    {
+        let reply = bus.call_method(Some(node.bus.as_str()), node.path.as_str(), Some(iface), method, body)?;
+        Ok(reply.body().deserialize()?)
+    }
+
+    fn children(bus: &Connection, node: &Node) -> Vec<Node> {
+        Self::call::<_, Vec<(String, OwnedObjectPath)>>(bus, node, ACCESSIBLE, "GetChildren", &())
+            .map(|children| children.into_iter().map(|(bus, path)| Node { bus, path }).collect())
+            .unwrap_or_default()
+    }
+
+    fn has_state(bus: &Connection, node: &Node, state: u32) -> bool {
+        Self::call::<_, Vec<u32>>(bus, node, ACCESSIBLE, "GetState", &())
+            .map(|words| words.get((state / 32) as usize).is_some_and(|word| word & (1 << (state % 32)) != 0))
+            .unwrap_or(false)
+    }
+
+    fn name(bus: &Connection, node: &Node) -> String {
+        Self::call::<_, OwnedValue>(bus, node, "org.freedesktop.DBus.Properties", "Get", &(ACCESSIBLE, "Name"))
+            .ok()
+            .and_then(|value| String::try_from(value).ok())
+            .unwrap_or_default()
+    }
+
+    fn process_id(bus: &Connection, node: &Node) -> u32 {
+        bus.call_method(
+            Some("org.freedesktop.DBus"),
+            "/org/freedesktop/DBus",
+            Some("org.freedesktop.DBus"),
+            "GetConnectionUnixProcessID",
+            &(node.bus.as_str(),),
+        )
+        .and_then(|reply| reply.body().deserialize())
+        .unwrap_or(0)
+    }
+
+    /// Every top-level frame of every application except MockMate itself
+    fn frames(&mut self) -> Result<Vec<Frame>> {
+        let bus = self.bus()?;
+        let root = Node {
+            bus: REGISTRY.to_string(),
+            path: OwnedObjectPath::try_from(ROOT_PATH)?,
+        };
+        let applications = Self::call::<_, Vec<(String, OwnedObjectPath)>>(&bus, &root, ACCESSIBLE, "GetChildren", &());
+        let applications = match applications {
+            Ok(applications) => applications,
+            Err(e) => {
+                // The registry goes away when the session's accessibility bus restarts
+                self.bus = None;
+                return Err(anyhow!("Failed to list AT-SPI applications: {}", e));
+            }
+        };
+
+        let own_pid = std::process::id();
+        let mut frames = Vec::new();
+        for (app_bus, app_path) in applications {
+            let app = Node { bus: app_bus, path: app_path };
+            let process_id = Self::process_id(&bus, &app);
+            if process_id == own_pid {
+                continue;
+            }
+            let app_name = Self::name(&bus, &app);
+            for node in Self::children(&bus, &app) {
+                if !Self::has_state(&bus, &node, STATE_SHOWING) {
+                    continue;
+                }
+                frames.push(Frame {
+                    title: Self::name(&bus, &node),
+                    active: Self::has_state(&bus, &node, STATE_ACTIVE),
+                    app_name: app_name.clone(),
+                    process_id,
+                    node,
+                });
+            }
+        }
+        Ok(frames)
+    }
+
+    fn is_target(&self, frame: &Frame) -> bool {
+        let app = frame.app_name.to_lowercase();
+        let title = frame.title.to_lowercase();
+        self.config.target_apps.iter().any(|target| {
+            let target = target.to_lowercase();
+            app.contains(&target) || title.contains(&target)
+        })
+    }
+
+    /// Join the text of every showing node in the frame's tree
+    fn collect_text(&self, bus: &Connection, frame: &Node) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        let mut length = 0;
+        let mut stack = vec![(frame.clone(), 0usize)];
+        let mut visited = 0;
+        while let Some((node, depth)) = stack.pop() {
+            visited += 1;
+            if visited > MAX_NODES || length >= self.config.max_text_length {
+                break;
+            }
+            if depth > 0 && !Self::has_state(bus, &node, STATE_SHOWING) {
+                continue;
+            }
+            let interfaces = Self::call::<_, Vec<String>>(bus, &node, ACCESSIBLE, "GetInterfaces", &()).unwrap_or_default();
+            if interfaces.iter().any(|iface| iface == TEXT) {
+                if let Ok(text) = Self::call::<_, String>(bus, &node, TEXT, "GetText", &(0i32, -1i32)) {
+                    let text = text.trim();
+                    if !text.is_empty() && parts.last().map(String::as_str) != Some(text) {
+                        length += text.len();
+                        parts.push(text.to_string());
+                    }
+                }
+            }
+            if depth < MAX_DEPTH {
+                // Reverse so the stack visits children in reading order
+                stack.extend(Self::children(bus, &node).into_iter().rev().map(|child| (child, depth + 1)));
+            }
+        }
+        parts.join("\n")
+    }
+
+    fn extract(&mut self, frame: &Frame) -> Result<Option<AccessibilityTextResult>> {
+        // Reading another application's window content always needs the user's approval
+        if !crate::app_consent::check_or_request(&frame.app_name, &frame.title) {
+            debug!("⏸️ Skipping {} until consent is granted", frame.app_name);
+            return Ok(None);
+        }
+        let bus = self.bus()?;
+        let text = self.collect_text(&bus, &frame.node);
+        if text.len() < self.config.min_question_length {
+            debug!("Text too short or empty: {} chars", text.len());
+            return Ok(None);
+        }
+        let text: String = text.chars().take(self.config.max_text_length).collect();
+        let window_class = Self::call::<_, String>(&bus, &frame.node, ACCESSIBLE, "GetRoleName", &()).unwrap_or_default();
+        Ok(Some(AccessibilityTextResult {
+            is_potential_question: crate::headless::looks_like_question(&text),
+            source_app: frame.app_name.clone(),
+            window_title: frame.title.clone(),
+            confidence: 0.8,
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or_default(),
+            extraction_method: "AT_SPI".to_string(),
+            window_class,
+            process_id: frame.process_id,
+            text_length: text.len(),
+            text,
+        }))
+    }
+
+    fn extract_all(&mut self, frames: Vec<Frame>) -> Vec<AccessibilityTextResult> {
+        let mut results = Vec::new();
+        for frame in frames {
+            match self.extract(&frame) {
+                Ok(Some(result)) => results.push(result),
+                Ok(None) => {}
+                Err(e) => debug!("Failed to read {}: {}", frame.app_name, e),
+            }
+        }
+        results
+    }
+
+    pub fn read_text_from_current_window(&mut self) -> Result<Option<AccessibilityTextResult>> {
+        match self.frames()?.into_iter().find(|frame| frame.active) {
+            Some(frame) => self.extract(&frame),
+            None => Ok(None),
+        }
+    }
+
+    pub fn read_text_from_all_target_applications(&mut self) -> Result<Vec<AccessibilityTextResult>> {
+        let frames: Vec<Frame> = self.frames()?.into_iter().filter(|frame| self.is_target(frame)).collect();
+        Ok(self.extract_all(frames))
+    }
+
+    pub fn read_text_from_focused_window(&mut self) -> Result<Option<AccessibilityTextResult>> {
+        self.read_text_from_current_window()
+    }
+
+    /// The window captured by `capture_previous_focused_window`, else the first target application
+    /// that is not in the foreground
+    pub fn read_text_from_window_behind_mockmate(&mut self) -> Result<Option<AccessibilityTextResult>> {
+        let frames = self.frames()?;
+        let previous = self
+            .previous_focus
+            .as_ref()
+            .and_then(|node| frames.iter().find(|frame| &frame.node == node));
+        let candidate = previous.or_else(|| frames.iter().find(|frame| !frame.active && self.is_target(frame)));
+        match candidate.cloned() {
+            Some(frame) => self.extract(&frame),
+            None => Ok(None),
+        }
+    }
+
+    pub fn read_background_windows(&mut self) -> Result<Vec<AccessibilityTextResult>> {
+        let frames: Vec<Frame> = self.frames()?.into_iter().filter(|frame| !frame.active).collect();
+        Ok(self.extract_all(frames))
+    }
+
+    pub fn capture_previous_focused_window(&mut self) -> Result<()> {
+        let active = self.frames()?.into_iter().find(|frame| frame.active);
+        if let Some(frame) = &active {
+            info!("🎯 Captured previous focused window: {} ({})", frame.app_name, frame.title);
+        }
+        self.previous_focus = active.map(|frame| frame.node);
+        Ok(())
+    }
+}
+
+fn worker() -> &'static mpsc::Sender<Job> {
+    WORKER.get_or_init(|| {
+        let (tx, rx) = mpsc::channel::<Job>();
+        let spawned = std::thread::Builder::new()
+            .name("accessibility-atspi".to_string())
+            .spawn(move || {
+                let mut reader = AtspiReader::new(AccessibilityConfig::default());
+                info!("🧵 Accessibility AT-SPI worker started");
+
+                // Jobs run one at a time in arrival order
+                for job in rx {
+                    job(&mut reader);
+                }
+            });
+        if let Err(e) = spawned {
+            error!("Failed to spawn accessibility worker thread: {}", e);
+        }
+        tx
+    })
+}
+
+/// Run `job` on the accessibility thread with its reader and await the result
+pub async fn run<T, F>(job: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut AtspiReader) -> T + Send + 'static,
+{
+    let (reply_tx, reply_rx) = tokio::sync::oneshot::channel();
+    worker()
+        .send(Box::new(move |reader| {
+            let _ = reply_tx.send(job(reader));
+        }))
+        .map_err(|_| "Accessibility worker is not running".to_string())?;
+    reply_rx
+        .await
+        .map_err(|_| "Accessibility worker stopped before finishing the request".to_string())
+}
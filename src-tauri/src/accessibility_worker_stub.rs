@@ -0,0 +1,50 @@
+// Accessibility worker for platforms without UI Automation or AT-SPI
+// Reading other applications' windows is built on Win32/UIA. Here the worker hands jobs a reader
+// that answers every request with the "not available" error, so the commands fail with a clear
+// reason and the monitoring loop simply finds nothing.
+
+use anyhow::{anyhow, Result};
+
+use crate::accessibility_reader::AccessibilityTextResult;
+use crate::platform::{unavailable, Capability};
+
+pub struct UnavailableReader;
+
+impl UnavailableReader {
+    fn unavailable<T>() -> Result<T> {
+        Err(anyhow!(unavailable(Capability::AccessibilityReader)))
+    }
+
+    pub fn read_text_from_current_window(&mut self) -> Result<Option<AccessibilityTextResult>> {
+        Self::unavailable()
+    }
+
+    pub fn read_text_from_all_target_applications(&mut self) -> Result<Vec<AccessibilityTextResult>> {
+        Self::unavailable()
+    }
+
+    pub fn read_text_from_focused_window(&mut self) -> Result<Option<AccessibilityTextResult>> {
+        Self::unavailable()
+    }
+
+    pub fn read_text_from_window_behind_mockmate(&mut self) -> Result<Option<AccessibilityTextResult>> {
+        Self::unavailable()
+    }
+
+    pub fn read_background_windows(&mut self) -> Result<Vec<AccessibilityTextResult>> {
+        Self::unavailable()
+    }
+
+    pub fn capture_previous_focused_window(&mut self) -> Result<()> {
+        Self::unavailable()
+    }
+}
+
+/// Run `job` against the unavailable reader
+pub async fn run<T, F>(job: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut UnavailableReader) -> T + Send + 'static,
+{
+    Ok(job(&mut UnavailableReader))
+}
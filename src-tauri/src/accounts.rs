@@ -0,0 +1,230 @@
+// Account profiles for shared machines
+// A career coach working with several candidates on one laptop keeps one profile per candidate. Each
+// profile has its own data folder (settings, transcripts, labels, the signed-in account), its own
+// entries in the OS credential store and its own webview storage, so sessions and credits never mix.
+// The first profile keeps the original locations, so an existing install becomes the "default"
+// profile unchanged; others live under MockMate\profiles\<id>. The list and the active profile are
+// kept in MockMate\accounts.json in the machine-wide data folder. Most modules load their settings
+// once, so `switch_account` restarts the app into the other profile instead of swapping in place.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::AppHandle;
+
+use crate::envelope::{self, Response};
+use crate::error::{AppError, ErrorCode};
+
+pub const DEFAULT_PROFILE: &str = "default";
+const MAX_LABEL_CHARS: usize = 60;
+
+static REGISTRY: Lazy<Mutex<Registry>> = Lazy::new(|| Mutex::new(load_registry()));
+/// Profile this process runs as; fixed at startup because switching restarts the app
+static ACTIVE: Lazy<String> = Lazy::new(|| {
+    let registry = REGISTRY.lock();
+    registry
+        .active
+        .clone()
+        .filter(|id| registry.profiles.iter().any(|p| &p.id == id))
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+});
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AccountProfile {
+    pub id: String,
+    pub label: String,
+    /// Set when someone signs in within the profile
+    pub user_id: Option<String>,
+    pub email: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AccountSummary {
+    pub profile: AccountProfile,
+    pub active: bool,
+    /// Whether the profile has a signed-in account
+    pub signed_in: bool,
+    pub data_dir: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Registry {
+    active: Option<String>,
+    profiles: Vec<AccountProfile>,
+}
+
+fn registry_path() -> PathBuf {
+    crate::portable::base_root()
+        .join("MockMate")
+        .join("accounts.json")
+}
+
+fn load_registry() -> Registry {
+    let mut registry: Registry = std::fs::read(registry_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default();
+    if !registry.profiles.iter().any(|p| p.id == DEFAULT_PROFILE) {
+        registry.profiles.insert(
+            0,
+            AccountProfile {
+                id: DEFAULT_PROFILE.to_string(),
+                label: "Default".to_string(),
+                user_id: None,
+                email: None,
+                created_at: Utc::now(),
+                last_used_at: None,
+            },
+        );
+    }
+    registry
+}
+
+fn save_registry(registry: &Registry) -> Result<(), String> {
+    let path = registry_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(registry).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| e.to_string())
+}
+
+/// Folder holding the `MockMate` data directory of `profile`
+fn root_of(base: PathBuf, profile: &str) -> PathBuf {
+    if profile == DEFAULT_PROFILE {
+        base
+    } else {
+        base.join("MockMate").join("profiles").join(profile)
+    }
+}
+
+/// Id of the active profile. Resolving it locks the registry the first time, so callers read it
+/// before taking that lock themselves
+fn active() -> &'static str {
+    ACTIVE.as_str()
+}
+
+/// Folder holding the active profile's `MockMate` data directory (see `portable::data_root`)
+pub fn profile_root(base: PathBuf) -> PathBuf {
+    root_of(base, active())
+}
+
+pub fn is_default_active() -> bool {
+    active() == DEFAULT_PROFILE
+}
+
+/// Prefix for credential store entries of the active profile; None for the default profile, whose
+/// entries keep their original names
+pub fn secret_scope() -> Option<&'static str> {
+    (!is_default_active()).then(active)
+}
+
+/// Record who signed in within the active profile
+pub fn on_signed_in(user_id: Option<&str>, email: Option<&str>) {
+    let active = active();
+    let mut registry = REGISTRY.lock();
+    if let Some(user_id) = user_id {
+        if let Some(other) = registry.profiles.iter().find(|p| p.id != active && p.user_id.as_deref() == Some(user_id)) {
+            warn!("👥 This account is also signed in in profile \"{}\"", other.label);
+        }
+    }
+    let Some(profile) = registry.profiles.iter_mut().find(|p| p.id == active) else {
+        return;
+    };
+    profile.user_id = user_id.map(str::to_string);
+    profile.email = email.map(str::to_string);
+    if let Err(e) = save_registry(&registry) {
+        warn!("Failed to save account profiles: {}", e);
+    }
+}
+
+fn summary(profile: &AccountProfile, active: &str) -> AccountSummary {
+    let data_dir = root_of(crate::portable::base_root(), &profile.id).join("MockMate");
+    AccountSummary {
+        profile: profile.clone(),
+        active: profile.id == active,
+        signed_in: data_dir.join("auth.json").exists(),
+        data_dir: data_dir.display().to_string(),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_accounts() -> Response<Vec<AccountSummary>> {
+    envelope::respond("list_accounts", async move {
+        let active = active();
+        Ok(REGISTRY.lock().profiles.iter().map(|p| summary(p, active)).collect())
+    })
+    .await
+}
+
+/// Create an empty profile, e.g. for a new candidate; switch to it to sign in
+#[tauri::command]
+#[specta::specta]
+pub async fn add_account(label: String) -> Response<AccountSummary> {
+    envelope::respond("add_account", async move {
+        let label: String = label.trim().chars().take(MAX_LABEL_CHARS).collect();
+        if label.is_empty() {
+            return Err(AppError::new(ErrorCode::InvalidInput, "Give the profile a name"));
+        }
+        let active = active();
+        let mut registry = REGISTRY.lock();
+        if registry.profiles.iter().any(|p| p.label.eq_ignore_ascii_case(&label)) {
+            return Err(AppError::new(ErrorCode::InvalidInput, format!("A profile named \"{}\" already exists", label)));
+        }
+        let id = uuid::Uuid::new_v4().simple().to_string()[..12].to_string();
+        let profile = AccountProfile {
+            id: id.clone(),
+            label,
+            user_id: None,
+            email: None,
+            created_at: Utc::now(),
+            last_used_at: None,
+        };
+        std::fs::create_dir_all(root_of(crate::portable::base_root(), &id).join("MockMate")).map_err(|e| e.to_string())?;
+        registry.profiles.push(profile.clone());
+        save_registry(&registry)?;
+        info!("👥 Added profile \"{}\" ({})", profile.label, id);
+        Ok(summary(&profile, active))
+    })
+    .await
+}
+
+/// Make `account_id` the active profile and restart the app into it
+#[tauri::command]
+#[specta::specta]
+pub async fn switch_account(app_handle: AppHandle, account_id: String) -> Response<AccountSummary> {
+    envelope::respond("switch_account", async move {
+        let active = active();
+        let mut registry = REGISTRY.lock();
+        let Some(profile) = registry.profiles.iter_mut().find(|p| p.id == account_id) else {
+            return Err(AppError::new(ErrorCode::NotFound, format!("No profile {}", account_id)));
+        };
+        profile.last_used_at = Some(Utc::now());
+        let summary = summary(profile, active);
+        if account_id == active {
+            return Ok(summary);
+        }
+        registry.active = Some(account_id.clone());
+        save_registry(&registry)?;
+        drop(registry);
+        let summary = AccountSummary { active: true, ..summary };
+
+        info!("👥 Switching to profile \"{}\"; restarting", summary.profile.label);
+        // Give the response a moment to reach the window before the process goes away
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            app_handle.restart();
+        });
+        Ok(summary)
+    })
+    .await
+}
@@ -0,0 +1,211 @@
+// Event-based delivery of AI answers to the response window
+// Updates are emitted as structured `ai-response-update` events instead of eval'd JS, so no
+// escaping is involved. Tokens are batched so the window receives at most one token event per frame.
+// Upstream, TokenCoalescer sits between provider streaming callbacks and the UI and merges tokens
+// into ~30ms chunks delivered in order by a single task. Full-text updates are normalized to the same
+// markdown as the streamed chunks (see answer_markdown).
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::future::BoxFuture;
+use log::{debug, error};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter};
+use crate::answer_markdown::StreamNormalizer;
+use crate::events;
+
+pub const RESPONSE_WINDOW_LABEL: &str = "ai-response";
+/// One display frame at 60Hz
+const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+/// Tokens arriving within this window are delivered to the UI as one chunk
+const COALESCE_WINDOW: Duration = Duration::from_millis(30);
+
+static PENDING: Lazy<Mutex<PendingTokens>> = Lazy::new(|| Mutex::new(PendingTokens::default()));
+
+#[derive(Default)]
+struct PendingTokens {
+    text: String,
+    flush_scheduled: bool,
+}
+
+/// Payload of `ai-response-update`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct ResponseUpdate {
+    /// "stream", "stream-token", "complete" or "error"
+    pub message_type: String,
+    pub text: Option<String>,
+    pub error: Option<String>,
+}
+
+fn emit(app_handle: &AppHandle, update: &ResponseUpdate) -> Result<(), String> {
+    app_handle
+        .emit_to(RESPONSE_WINDOW_LABEL, events::AI_RESPONSE_UPDATE, update)
+        .map_err(|e| format!("Failed to emit {}: {}", events::AI_RESPONSE_UPDATE, e))
+}
+
+/// Emit buffered tokens; the lock is held while emitting so updates keep their order
+fn flush(app_handle: &AppHandle, pending: &mut PendingTokens) {
+    if pending.text.is_empty() {
+        return;
+    }
+    let update = ResponseUpdate {
+        message_type: "stream-token".to_string(),
+        text: Some(std::mem::take(&mut pending.text)),
+        error: None,
+    };
+    if let Err(e) = emit(app_handle, &update) {
+        error!("{}", e);
+    }
+}
+
+/// Queue a streamed token; everything queued within one frame goes out as a single event
+pub fn push_token(app_handle: &AppHandle, token: &str) {
+    let mut pending = PENDING.lock();
+    pending.text.push_str(token);
+    if pending.flush_scheduled {
+        return;
+    }
+    pending.flush_scheduled = true;
+
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(FRAME_INTERVAL).await;
+        let mut pending = PENDING.lock();
+        pending.flush_scheduled = false;
+        flush(&app_handle, &mut pending);
+    });
+}
+
+/// Send a non-token update, after any tokens still waiting for the next frame
+pub fn send_update(app_handle: &AppHandle, mut update: ResponseUpdate) -> Result<(), String> {
+    if update.message_type == "stream" || update.message_type == "complete" {
+        update.text = update.text.map(|text| crate::answer_markdown::normalize(&text));
+    }
+    let mut pending = PENDING.lock();
+    flush(app_handle, &mut pending);
+    emit(app_handle, &update)
+}
+
+struct CoalescerShared {
+    buffer: Mutex<String>,
+    notify: tokio::sync::Notify,
+    closed: AtomicBool,
+}
+
+/// Closes the stream when the last `TokenCoalescer` clone is dropped, so the task never leaks
+struct CoalescerHandle {
+    shared: Arc<CoalescerShared>,
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl Drop for CoalescerHandle {
+    fn drop(&mut self) {
+        self.shared.closed.store(true, Ordering::Release);
+        self.shared.notify.notify_one();
+    }
+}
+
+/// Merges streamed tokens into ~30ms chunks for a UI sink.
+///
+/// Chunks follow the markdown contract of `answer_markdown`: they are normalized and end on
+/// markdown-safe boundaries, and an unclosed code fence is closed when the stream finishes.
+///
+/// A single task delivers chunks in order and awaits the sink for each one; while the sink is
+/// busy (window not keeping up) new tokens accumulate into the next chunk instead of queueing
+/// one emit or task per token.
+#[derive(Clone)]
+pub struct TokenCoalescer {
+    handle: Arc<CoalescerHandle>,
+}
+
+impl TokenCoalescer {
+    pub fn spawn<F>(sink: F) -> Self
+    where
+        F: Fn(String) -> BoxFuture<'static, ()> + Send + Sync + 'static,
+    {
+        let shared = Arc::new(CoalescerShared {
+            buffer: Mutex::new(String::new()),
+            notify: tokio::sync::Notify::new(),
+            closed: AtomicBool::new(false),
+        });
+
+        let task_shared = shared.clone();
+        let task = tauri::async_runtime::spawn(async move {
+            let mut chunks = 0u64;
+            let mut markdown = StreamNormalizer::default();
+            loop {
+                if task_shared.buffer.lock().is_empty() {
+                    if task_shared.closed.load(Ordering::Acquire) {
+                        break;
+                    }
+                    task_shared.notify.notified().await;
+                    continue;
+                }
+
+                // Deliver the first token right away; afterwards give tokens a window to accumulate
+                if chunks > 0 && !task_shared.closed.load(Ordering::Acquire) {
+                    tokio::time::sleep(COALESCE_WINDOW).await;
+                }
+
+                let tokens = std::mem::take(&mut *task_shared.buffer.lock());
+                let chunk = markdown.push(&tokens);
+                if chunk.is_empty() {
+                    continue;
+                }
+                chunks += 1;
+                sink(chunk).await;
+            }
+            let tail = markdown.finish();
+            if !tail.is_empty() {
+                chunks += 1;
+                sink(tail).await;
+            }
+            debug!("Token coalescer delivered {} chunks", chunks);
+        });
+
+        Self {
+            handle: Arc::new(CoalescerHandle {
+                shared,
+                task: Mutex::new(Some(task)),
+            }),
+        }
+    }
+
+    /// Queue a token from a provider callback (never blocks the provider stream)
+    pub fn push(&self, token: &str) {
+        if token.is_empty() {
+            return;
+        }
+        let shared = &self.handle.shared;
+        let was_empty = {
+            let mut buffer = shared.buffer.lock();
+            let was_empty = buffer.is_empty();
+            buffer.push_str(token);
+            was_empty
+        };
+        if was_empty {
+            shared.notify.notify_one();
+        }
+    }
+
+    /// Deliver everything still buffered; call before sending the completion update
+    pub async fn finish(&self) {
+        let shared = &self.handle.shared;
+        shared.closed.store(true, Ordering::Release);
+        shared.notify.notify_one();
+
+        let task = self.handle.task.lock().take();
+        if let Some(task) = task {
+            if let Err(e) = task.await {
+                error!("Token coalescer task failed: {}", e);
+            }
+        }
+    }
+}
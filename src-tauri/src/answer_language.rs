@@ -0,0 +1,360 @@
+// Answer language and question translation
+// For interviews held in a second language. Answers are generated in the chosen output language,
+// whatever language the question came in. The interviewer's language is taken from the settings or
+// detected from final system-audio transcripts (script ranges for non-Latin languages, common words
+// for Latin ones). When a question is in a language other than the output language, it is translated
+// first and the model gets both texts, so it understands the question and answers in the right
+// language; the translation is also sent as `question-translated` for the UI. Once answers are not in
+// English, Deepgram is asked for multilingual transcription. Settings are in
+// %APPDATA%\MockMate\answer_language.json.
+
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter};
+
+use crate::envelope::{self, Response};
+use crate::error::{AppError, ErrorCode};
+use crate::events;
+
+/// ISO 639-1 codes that can be chosen, with their English names
+const LANGUAGES: &[(&str, &str)] = &[
+    ("en", "English"),
+    ("es", "Spanish"),
+    ("fr", "French"),
+    ("de", "German"),
+    ("pt", "Portuguese"),
+    ("it", "Italian"),
+    ("nl", "Dutch"),
+    ("ru", "Russian"),
+    ("ar", "Arabic"),
+    ("hi", "Hindi"),
+    ("ta", "Tamil"),
+    ("te", "Telugu"),
+    ("zh", "Chinese"),
+    ("ja", "Japanese"),
+    ("ko", "Korean"),
+];
+
+/// Frequent short words per Latin-script language; counted whole-word and case-insensitively
+const COMMON_WORDS: &[(&str, &[&str])] = &[
+    ("en", &["the", "and", "is", "you", "what", "how", "your", "that", "of", "to", "do", "can", "with", "about"]),
+    ("es", &["el", "la", "que", "y", "los", "por", "qué", "cómo", "usted", "tu", "es", "una", "con", "para"]),
+    ("fr", &["le", "les", "et", "est", "vous", "des", "une", "pour", "comment", "quel", "avez", "pourquoi", "dans"]),
+    ("de", &["der", "die", "das", "und", "ist", "sie", "nicht", "wie", "was", "ein", "eine", "haben", "mit"]),
+    ("pt", &["o", "que", "não", "você", "uma", "para", "com", "como", "é", "os", "em", "seu", "sua"]),
+    ("it", &["il", "che", "di", "non", "è", "per", "come", "sono", "lei", "hai", "gli", "della", "perché"]),
+    ("nl", &["het", "een", "en", "van", "je", "wat", "hoe", "niet", "dat", "u", "met", "waarom", "jouw"]),
+];
+
+/// Recent transcript detections the interviewer language is voted from
+const DETECTION_WINDOW: usize = 8;
+/// Votes a language needs before it counts as the interviewer's
+const MIN_VOTES: usize = 2;
+/// Transcripts shorter than this are not used for detection
+const MIN_DETECT_CHARS: usize = 12;
+const TRANSLATION_TIMEOUT: Duration = Duration::from_secs(8);
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+static SETTINGS: Lazy<Mutex<AnswerLanguageSettings>> = Lazy::new(|| Mutex::new(load()));
+static RECENT: Lazy<Mutex<VecDeque<&'static str>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AnswerLanguageSettings {
+    /// Language answers are written in (ISO 639-1 code)
+    pub output_language: String,
+    /// Translate questions that are not in the output language before answering
+    pub translate_questions: bool,
+    /// The interviewer's language; detected from transcripts when not set
+    pub interviewer_language: Option<String>,
+}
+
+impl Default for AnswerLanguageSettings {
+    fn default() -> Self {
+        Self { output_language: "en".to_string(), translate_questions: true, interviewer_language: None }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct AnswerLanguageStatus {
+    pub settings: AnswerLanguageSettings,
+    /// Language detected from recent interviewer transcripts
+    pub detected_language: Option<String>,
+    /// Codes `set_answer_language` accepts, with their names
+    pub supported: Vec<(String, String)>,
+}
+
+/// Payload of `question-translated`
+#[derive(Debug, Clone, Serialize, JsonSchema, Type)]
+pub struct QuestionTranslation {
+    pub original: String,
+    pub translated: String,
+    pub source_language: String,
+    pub target_language: String,
+}
+
+fn settings_path() -> PathBuf {
+    crate::portable::data_root().join("MockMate").join("answer_language.json")
+}
+
+fn load() -> AnswerLanguageSettings {
+    std::fs::read(settings_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn save(settings: &AnswerLanguageSettings) -> Result<(), String> {
+    let path = settings_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(settings).map_err(|e| e.to_string())?;
+    std::fs::write(path, body).map_err(|e| e.to_string())
+}
+
+fn language_name(code: &str) -> &str {
+    LANGUAGES.iter().find(|(c, _)| *c == code).map_or(code, |(_, name)| name)
+}
+
+/// Normalize "ES", "es-MX" or "Spanish" to a supported code
+fn parse_language(value: &str) -> Option<&'static str> {
+    let value = value.trim().to_lowercase();
+    let primary = value.split(['-', '_']).next().unwrap_or_default();
+    LANGUAGES
+        .iter()
+        .find(|(code, name)| *code == primary || name.to_lowercase() == value)
+        .map(|(code, _)| *code)
+}
+
+/// Best guess of the language of `text`, or `None` when it is too short or ambiguous
+pub fn detect(text: &str) -> Option<&'static str> {
+    if text.chars().count() < MIN_DETECT_CHARS {
+        return None;
+    }
+
+    // Non-Latin scripts identify the language on their own
+    let mut letters = 0usize;
+    let mut scripts = [0usize; 9];
+    for c in text.chars().filter(|c| c.is_alphabetic()) {
+        letters += 1;
+        let slot = match c as u32 {
+            0x0400..=0x04FF => 0,
+            0x0600..=0x06FF => 1,
+            0x0900..=0x097F => 2,
+            0x0B80..=0x0BFF => 3,
+            0x0C00..=0x0C7F => 4,
+            0x3040..=0x30FF => 5,
+            0xAC00..=0xD7AF | 0x1100..=0x11FF => 6,
+            0x4E00..=0x9FFF => 7,
+            _ => 8,
+        };
+        scripts[slot] += 1;
+    }
+    if letters == 0 {
+        return None;
+    }
+    // Japanese mixes kana with Han characters, so any kana wins over Chinese
+    if scripts[5] * 10 >= letters {
+        return Some("ja");
+    }
+    let codes = ["ru", "ar", "hi", "ta", "te", "ja", "ko", "zh"];
+    if let Some((slot, _)) = scripts[..8].iter().enumerate().filter(|(_, n)| **n * 3 >= letters).max_by_key(|(_, n)| **n) {
+        return Some(codes[slot]);
+    }
+
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    let mut scores: Vec<(&'static str, usize)> = COMMON_WORDS
+        .iter()
+        .map(|(code, common)| (*code, words.iter().filter(|word| common.contains(&word.as_str())).count()))
+        .collect();
+    scores.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+    match scores.as_slice() {
+        [(code, best), (_, second), ..] if *best >= 2 && best > second => Some(*code),
+        _ => None,
+    }
+}
+
+/// Language of the interviewer: the configured one, else the most frequent recent detection
+pub fn interviewer_language() -> Option<String> {
+    if let Some(language) = SETTINGS.lock().interviewer_language.clone() {
+        return Some(language);
+    }
+    detected_language()
+}
+
+fn detected_language() -> Option<String> {
+    let recent = RECENT.lock();
+    LANGUAGES
+        .iter()
+        .map(|(code, _)| (*code, recent.iter().filter(|detected| *detected == code).count()))
+        .filter(|(_, votes)| *votes >= MIN_VOTES)
+        .max_by_key(|(_, votes)| *votes)
+        .map(|(code, _)| code.to_string())
+}
+
+pub fn init(app_handle: AppHandle) {
+    let _ = APP_HANDLE.set(app_handle);
+}
+
+/// Called with every final system-audio transcript
+pub fn on_transcript(text: &str) {
+    let Some(language) = detect(text) else { return };
+    let before = detected_language();
+    {
+        let mut recent = RECENT.lock();
+        recent.push_back(language);
+        while recent.len() > DETECTION_WINDOW {
+            recent.pop_front();
+        }
+    }
+    let after = detected_language();
+    if after != before {
+        if let Some(after) = after {
+            info!("🌐 Interviewer language looks like {}", language_name(&after));
+        }
+    }
+}
+
+/// Deepgram `language` parameter: the interviewer's language when set, multilingual when answers
+/// are not in English
+pub fn transcription_language(model: &str) -> String {
+    let settings = SETTINGS.lock();
+    match settings.interviewer_language.as_deref() {
+        Some("en") => "en-US".to_string(),
+        Some(language) => language.to_string(),
+        None if settings.output_language != "en" && model.starts_with("nova-") => "multi".to_string(),
+        None => "en-US".to_string(),
+    }
+}
+
+/// Sentence added to answer system prompts, or `None` for English answers to English questions
+pub fn prompt_instruction() -> Option<String> {
+    let output = SETTINGS.lock().output_language.clone();
+    if output == "en" && interviewer_language().is_none_or(|language| language == "en") {
+        return None;
+    }
+    Some(format!(
+        " Always answer in {}, even when the question is asked in another language.",
+        language_name(&output)
+    ))
+}
+
+/// The question to send to the model: unchanged, or with a translation into the output language
+/// appended when it was asked in another language. A failed or slow translation keeps the original.
+pub(crate) async fn prepare_question(state: &crate::AppState, provider: &str, model: &str, question: &str) -> String {
+    let settings = SETTINGS.lock().clone();
+    if !settings.translate_questions {
+        return question.to_string();
+    }
+    let source = match detect(question) {
+        Some(language) => language.to_string(),
+        None => match interviewer_language() {
+            Some(language) => language,
+            None => return question.to_string(),
+        },
+    };
+    let target = settings.output_language;
+    if source == target {
+        return question.to_string();
+    }
+
+    let system_prompt = format!(
+        "Translate the user's text from {} to {}. Keep technical terms, names and code as they are. Reply with the translation only.",
+        language_name(&source),
+        language_name(&target)
+    );
+    let translated = match tokio::time::timeout(TRANSLATION_TIMEOUT, state.complete(provider, model, &system_prompt, question)).await {
+        Ok(Ok(translated)) if !translated.trim().is_empty() => translated.trim().to_string(),
+        Ok(Ok(_)) => return question.to_string(),
+        Ok(Err(e)) => {
+            warn!("Question translation failed, answering the original: {}", e);
+            return question.to_string();
+        }
+        Err(_) => {
+            warn!("Question translation timed out, answering the original");
+            return question.to_string();
+        }
+    };
+    info!("🌐 Translated question from {} to {}", language_name(&source), language_name(&target));
+
+    let translation = QuestionTranslation {
+        original: question.to_string(),
+        translated: translated.clone(),
+        source_language: source.clone(),
+        target_language: target.clone(),
+    };
+    if let Some(app_handle) = APP_HANDLE.get() {
+        if let Err(e) = app_handle.emit(events::QUESTION_TRANSLATED, &translation) {
+            warn!("Failed to emit {}: {}", events::QUESTION_TRANSLATED, e);
+        }
+    }
+    format!(
+        "{}\n\n(Asked in {}; {} translation: {})",
+        question.trim(),
+        language_name(&source),
+        language_name(&target),
+        translated
+    )
+}
+
+/// Answer language settings and the currently detected interviewer language
+#[tauri::command]
+#[specta::specta]
+pub fn get_answer_language() -> Response<AnswerLanguageStatus> {
+    envelope::respond_sync("get_answer_language", || {
+        Ok(AnswerLanguageStatus {
+            settings: SETTINGS.lock().clone(),
+            detected_language: detected_language(),
+            supported: LANGUAGES.iter().map(|(code, name)| (code.to_string(), name.to_string())).collect(),
+        })
+    })
+}
+
+/// Choose the answer language; `interviewer_language` of "auto" goes back to detection
+#[tauri::command]
+#[specta::specta]
+pub fn set_answer_language(
+    lang: String,
+    translate_questions: Option<bool>,
+    interviewer_language: Option<String>,
+) -> Response<AnswerLanguageSettings> {
+    envelope::respond_sync("set_answer_language", || {
+        let unsupported = |value: &str| AppError::new(ErrorCode::InvalidInput, format!("Unsupported language: {}", value));
+        let output = parse_language(&lang).ok_or_else(|| unsupported(&lang))?;
+        let interviewer = match interviewer_language.as_deref().map(str::trim) {
+            None => None,
+            Some("" | "auto") => Some(None),
+            Some(value) => Some(Some(parse_language(value).ok_or_else(|| unsupported(value))?.to_string())),
+        };
+
+        let mut settings = SETTINGS.lock();
+        settings.output_language = output.to_string();
+        if let Some(translate_questions) = translate_questions {
+            settings.translate_questions = translate_questions;
+        }
+        if let Some(interviewer) = interviewer {
+            settings.interviewer_language = interviewer;
+        }
+        save(&settings)?;
+        info!(
+            "🌐 Answers in {}, question translation {}, interviewer language {}",
+            language_name(&settings.output_language),
+            if settings.translate_questions { "on" } else { "off" },
+            settings.interviewer_language.as_deref().map_or("detected", language_name)
+        );
+        Ok(settings.clone())
+    })
+}
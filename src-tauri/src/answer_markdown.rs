@@ -0,0 +1,267 @@
+// Markdown contract for streamed AI answers
+// Everything the response window receives is CommonMark: list bullets are normalized to "-",
+// ordered markers keep their number but use "." ("3)" becomes "3."), "•" bullets become list
+// items, carriage returns are dropped, and code
+// fences are passed through verbatim (their contents are never rewritten) and closed if the model
+// stops inside one. `StreamNormalizer` applies this to a token stream and only releases text at
+// markdown-safe boundaries: a line start is held until it is clear whether it opens or closes a
+// fence or starts a list item, and a trailing run of `*`, `_`, `~` or backticks is held until the
+// next token shows how it continues. Streaming an answer and normalizing the complete text give the
+// same result, so the window can render each chunk and the final update without reflowing.
+
+/// Characters that open or close inline markup and may continue in the next token
+const INLINE_MARKERS: &[char] = &['*', '_', '~', '`'];
+
+#[derive(Debug, Clone)]
+struct Fence {
+    marker: char,
+    len: usize,
+    indent: String,
+}
+
+impl Fence {
+    fn closing(&self) -> String {
+        format!("{}{}\n", self.indent, self.marker.to_string().repeat(self.len))
+    }
+}
+
+/// Normalizes streamed answer text and aligns chunks to markdown tokens
+#[derive(Debug, Clone)]
+pub struct StreamNormalizer {
+    pending: String,
+    at_line_start: bool,
+    fence: Option<Fence>,
+    ends_with_newline: bool,
+}
+
+impl Default for StreamNormalizer {
+    fn default() -> Self {
+        Self { pending: String::new(), at_line_start: true, fence: None, ends_with_newline: true }
+    }
+}
+
+impl StreamNormalizer {
+    /// Queue a token; returns the normalized text that is safe to render (may be empty)
+    pub fn push(&mut self, token: &str) -> String {
+        self.pending.extend(token.chars().filter(|&c| c != '\r'));
+        let mut out = String::new();
+
+        loop {
+            if self.at_line_start {
+                let (line, complete) = match self.pending.find('\n') {
+                    Some(end) => (&self.pending[..end], true),
+                    None => (self.pending.as_str(), false),
+                };
+                if !complete && !self.line_start_decided(line) {
+                    break;
+                }
+                let line = line.to_string();
+                self.begin_line(&line, complete);
+                self.at_line_start = false;
+            }
+
+            if let Some(end) = self.pending.find('\n') {
+                let line: String = self.pending.drain(..=end).collect();
+                out.push_str(&line);
+                self.at_line_start = true;
+                continue;
+            }
+
+            let keep = if self.fence.is_some() {
+                0
+            } else {
+                self.pending.len() - self.pending.trim_end_matches(INLINE_MARKERS).len()
+            };
+            let release = self.pending.len() - keep;
+            out.extend(self.pending.drain(..release));
+            break;
+        }
+
+        if let Some(last) = out.chars().last() {
+            self.ends_with_newline = last == '\n';
+        }
+        out
+    }
+
+    /// Release everything still held and close a fence left open
+    pub fn finish(&mut self) -> String {
+        if self.at_line_start && !self.pending.is_empty() {
+            let line = self.pending.clone();
+            self.begin_line(&line, true);
+        }
+        let mut out = std::mem::take(&mut self.pending);
+        if let Some(last) = out.chars().last() {
+            self.ends_with_newline = last == '\n';
+        }
+        if let Some(fence) = self.fence.take() {
+            if !self.ends_with_newline {
+                out.push('\n');
+            }
+            out.push_str(&fence.closing());
+        }
+        self.at_line_start = true;
+        self.ends_with_newline = true;
+        out
+    }
+
+    /// Whether a line start without its newline yet can no longer turn into a fence, a closing
+    /// fence or a differently written list marker
+    fn line_start_decided(&self, line: &str) -> bool {
+        let rest = line.trim_start_matches([' ', '\t']);
+        let Some(first) = rest.chars().next() else {
+            return false;
+        };
+
+        if let Some(fence) = &self.fence {
+            return first != fence.marker || rest.chars().any(|c| c != fence.marker && c != ' ' && c != '\t');
+        }
+
+        match first {
+            '`' | '~' => {
+                let run = rest.chars().take_while(|&c| c == first).count();
+                run < 3 && rest.chars().count() > run
+            }
+            '*' | '+' | '-' | '_' => rest.chars().any(|c| !matches!(c, '*' | '+' | '-' | '_' | ' ' | '\t')),
+            '•' => rest.chars().count() > 1,
+            '0'..='9' => {
+                let digits = rest.chars().take_while(char::is_ascii_digit).count();
+                match rest.chars().nth(digits) {
+                    Some('.') | Some(')') => rest.chars().count() > digits + 1,
+                    Some(_) => true,
+                    None => false,
+                }
+            }
+            _ => true,
+        }
+    }
+
+    /// Track fences and rewrite the list marker at the start of the pending line
+    fn begin_line(&mut self, line: &str, complete: bool) {
+        let indent_len = line.len() - line.trim_start_matches([' ', '\t']).len();
+        let rest = &line[indent_len..];
+
+        if let Some(fence) = &self.fence {
+            if complete && closes_fence(rest, fence) {
+                self.fence = None;
+            }
+            return;
+        }
+
+        if complete {
+            if let Some((marker, len)) = opening_fence(rest) {
+                self.fence = Some(Fence { marker, len, indent: line[..indent_len].to_string() });
+                return;
+            }
+        }
+
+        if let Some((marker_len, replacement)) = list_marker(rest) {
+            self.pending.replace_range(indent_len..indent_len + marker_len, &replacement);
+        }
+    }
+}
+
+/// Normalize a complete answer the same way its streamed chunks are
+pub fn normalize(text: &str) -> String {
+    let mut normalizer = StreamNormalizer::default();
+    let mut out = normalizer.push(text);
+    out.push_str(&normalizer.finish());
+    out
+}
+
+/// Fence marker and length of a line opening a code block; indentation is allowed at any depth
+/// because models indent fences inside list items
+fn opening_fence(rest: &str) -> Option<(char, usize)> {
+    let marker = rest.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = rest.chars().take_while(|&c| c == marker).count();
+    if len < 3 {
+        return None;
+    }
+    // A backtick fence's info string may not contain backticks
+    if marker == '`' && rest[len..].contains('`') {
+        return None;
+    }
+    Some((marker, len))
+}
+
+fn closes_fence(rest: &str, fence: &Fence) -> bool {
+    let len = rest.chars().take_while(|&c| c == fence.marker).count();
+    len >= fence.len && rest[len..].trim().is_empty()
+}
+
+/// Byte length of a list marker at the start of `rest` and its normalized form
+fn list_marker(rest: &str) -> Option<(usize, String)> {
+    let first = rest.chars().next()?;
+    let followed_by_space = |at: usize| matches!(rest[at..].chars().next(), Some(' ') | Some('\t'));
+
+    match first {
+        '*' | '+' if followed_by_space(1) && !is_thematic_break(rest) => Some((1, "-".to_string())),
+        '•' if followed_by_space(first.len_utf8()) => Some((first.len_utf8(), "-".to_string())),
+        '•' => Some((first.len_utf8(), "- ".to_string())),
+        '0'..='9' => {
+            let digits = rest.chars().take_while(char::is_ascii_digit).count();
+            // CommonMark ordered list numbers have at most nine digits
+            if digits > 9 || !rest[digits..].starts_with(')') || !followed_by_space(digits + 1) {
+                return None;
+            }
+            Some((digits + 1, format!("{}.", &rest[..digits])))
+        }
+        _ => None,
+    }
+}
+
+/// "* * *" and similar lines are horizontal rules, not list items
+fn is_thematic_break(rest: &str) -> bool {
+    let marks: Vec<char> = rest.chars().filter(|c| !c.is_whitespace()).collect();
+    marks.len() >= 3 && marks.iter().all(|&c| c == marks[0]) && matches!(marks[0], '*' | '-' | '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ANSWERS: &[&str] = &[
+        "Two approaches:\r\n\r\n* **Hash map** lookups\r\n+ a `sorted` array\r\n\r\n1) count\r\n12) sort\r\n",
+        "Use this:\n\n```python\ndef f(x):\n    return x * 2\n* not a list\n```\nDone ~~maybe~~.",
+        "•First\n• Second\n  ~~~\n  1) raw\n  ~~~\n* * *\n__bold__ and _it_",
+        "Unclosed fence:\n````rust\nlet a = b**c;\n```\nstill code",
+    ];
+
+    fn streamed(chunks: &[&str]) -> String {
+        let mut normalizer = StreamNormalizer::default();
+        let mut out: String = chunks.iter().map(|chunk| normalizer.push(chunk)).collect();
+        out.push_str(&normalizer.finish());
+        out
+    }
+
+    #[test]
+    fn normalizes_markers_and_line_endings() {
+        assert_eq!(
+            normalize(ANSWERS[0]),
+            "Two approaches:\n\n- **Hash map** lookups\n- a `sorted` array\n\n1. count\n12. sort\n"
+        );
+        assert_eq!(
+            normalize(ANSWERS[2]),
+            "- First\n- Second\n  ~~~\n  1) raw\n  ~~~\n* * *\n__bold__ and _it_"
+        );
+        assert!(normalize(ANSWERS[3]).ends_with("still code\n````\n"));
+    }
+
+    #[test]
+    fn every_two_way_split_matches_the_whole_answer() {
+        for answer in ANSWERS {
+            let whole = normalize(answer);
+            for (at, _) in answer.char_indices().skip(1) {
+                assert_eq!(streamed(&[&answer[..at], &answer[at..]]), whole, "split at {} of {:?}", at, answer);
+            }
+        }
+    }
+
+    #[test]
+    fn char_by_char_matches_the_whole_answer() {
+        for answer in ANSWERS {
+            let chunks: Vec<String> = answer.chars().map(String::from).collect();
+            let chunks: Vec<&str> = chunks.iter().map(String::as_str).collect();
+            assert_eq!(streamed(&chunks), normalize(answer), "{:?}", answer);
+        }
+    }
+}
@@ -0,0 +1,34 @@
+// Version of the IPC API between the backend and the frontend
+// `API_VERSION` goes up whenever a command is removed or changes shape incompatibly, so a frontend
+// can tell it is talking to a backend it was not built for. Commands being phased out stay for one
+// version as aliases in `compat`; `get_api_version` lists them with their replacements.
+
+use serde::Serialize;
+use specta::Type;
+
+use crate::compat::{self, DeprecatedCommand};
+use crate::envelope::{self, Response};
+
+/// 2: every command resolves or rejects with an `Envelope` (1 returned bare values and strings)
+pub const API_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ApiVersion {
+    pub api_version: u32,
+    /// Version of the app itself, from Cargo.toml
+    pub app_version: &'static str,
+    /// Commands that still work but go away in a later API version
+    pub deprecated: Vec<DeprecatedCommand>,
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_api_version() -> Response<ApiVersion> {
+    envelope::respond_sync("get_api_version", || {
+        Ok(ApiVersion {
+            api_version: API_VERSION,
+            app_version: env!("CARGO_PKG_VERSION"),
+            deprecated: compat::DEPRECATED.to_vec(),
+        })
+    })
+}
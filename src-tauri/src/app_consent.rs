@@ -0,0 +1,182 @@
+// Per-application consent for accessibility text reading
+// Text is only extracted from applications the user explicitly approved. The first time an
+// unapproved application would be read, extraction is skipped and `consent-required` is emitted.
+
+use std::collections::{BTreeSet, HashSet};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use log::{error, info};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter};
+use crate::envelope::{self, IntoResult, Response};
+use crate::events;
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+static STATE: Lazy<Mutex<ConsentState>> = Lazy::new(|| Mutex::new(ConsentState::load()));
+
+struct ConsentState {
+    /// Approved permanently, persisted across restarts
+    remembered: BTreeSet<String>,
+    /// Approved until the app restarts
+    this_run: HashSet<String>,
+    /// Apps a `consent-required` event was already emitted for and not yet answered
+    pending: HashSet<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ConsentFile {
+    approved_apps: BTreeSet<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct ConsentRequest {
+    pub app: String,
+    pub window_title: String,
+    pub requested_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AppConsents {
+    pub remembered: Vec<String>,
+    pub this_session: Vec<String>,
+    pub pending: Vec<String>,
+}
+
+fn consent_path() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("app_consent.json")
+}
+
+/// Process names are compared case-insensitively ("Teams.exe" == "teams.exe")
+fn app_key(app: &str) -> String {
+    app.trim().to_lowercase()
+}
+
+impl ConsentState {
+    fn load() -> Self {
+        let remembered = std::fs::read(consent_path())
+            .ok()
+            .and_then(|body| serde_json::from_slice::<ConsentFile>(&body).ok())
+            .map(|file| file.approved_apps)
+            .unwrap_or_default();
+        Self {
+            remembered,
+            this_run: HashSet::new(),
+            pending: HashSet::new(),
+        }
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let path = consent_path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let file = ConsentFile {
+            approved_apps: self.remembered.clone(),
+        };
+        let body = serde_json::to_vec_pretty(&file).map_err(|e| e.to_string())?;
+        std::fs::write(&path, body).map_err(|e| e.to_string())
+    }
+}
+
+pub fn attach_app_handle(app_handle: AppHandle) {
+    let _ = APP_HANDLE.set(app_handle);
+}
+
+/// Whether text may be read from `app`; asks the user (once) when it may not
+pub fn check_or_request(app: &str, window_title: &str) -> bool {
+    let key = app_key(app);
+    let mut state = STATE.lock();
+    if state.remembered.contains(&key) || state.this_run.contains(&key) {
+        return true;
+    }
+
+    if state.pending.insert(key) {
+        info!("🔐 Consent required before reading text from {}", app);
+        let request = ConsentRequest {
+            app: app.to_string(),
+            window_title: window_title.to_string(),
+            requested_at: chrono::Utc::now().to_rfc3339(),
+        };
+        match APP_HANDLE.get() {
+            Some(app_handle) => {
+                if let Err(e) = app_handle.emit(events::CONSENT_REQUIRED, &request) {
+                    error!("Failed to emit consent-required event: {}", e);
+                }
+            }
+            None => error!("Consent required for {} but no app handle is attached", app),
+        }
+    }
+    false
+}
+
+/// Allow accessibility reading of `app`; with `remember` the approval survives restarts
+#[tauri::command]
+#[specta::specta]
+pub async fn grant_app_consent(app: String, remember: bool) -> Response<AppConsents> {
+    envelope::respond("grant_app_consent", async move {
+        let key = app_key(&app);
+        if key.is_empty() {
+            return Err("Application name is required".into());
+        }
+
+        {
+            let mut state = STATE.lock();
+            state.pending.remove(&key);
+            if remember {
+                state.remembered.insert(key);
+                state.save()?;
+            } else {
+                state.this_run.insert(key);
+            }
+        }
+
+        info!("🔐 Accessibility reading approved for {} ({})", app, if remember { "remembered" } else { "this session" });
+        get_app_consents().await.into_result()
+    })
+    .await
+}
+
+/// Withdraw a previous approval; the app will ask again next time it is read
+#[tauri::command]
+#[specta::specta]
+pub async fn revoke_app_consent(app: String) -> Response<AppConsents> {
+    envelope::respond("revoke_app_consent", async move {
+        let key = app_key(&app);
+        {
+            let mut state = STATE.lock();
+            state.this_run.remove(&key);
+            if state.remembered.remove(&key) {
+                state.save()?;
+            }
+        }
+
+        info!("🔐 Accessibility reading consent revoked for {}", app);
+        get_app_consents().await.into_result()
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_app_consents() -> Response<AppConsents> {
+    envelope::respond("get_app_consents", async move {
+        let state = STATE.lock();
+        let mut this_session: Vec<String> = state.this_run.iter().cloned().collect();
+        this_session.sort();
+        let mut pending: Vec<String> = state.pending.iter().cloned().collect();
+        pending.sort();
+        Ok(AppConsents {
+            remembered: state.remembered.iter().cloned().collect(),
+            this_session,
+            pending,
+        })
+    })
+    .await
+}
@@ -0,0 +1,290 @@
+// OS theme and accessibility preferences
+// Backend-created windows (the AI response overlay) are transparent and shadowless by default,
+// which is unreadable with high contrast on and ignores "reduce transparency". The preferences are
+// read per OS, cached, and re-read on a timer; a change is pushed as `os-appearance-changed` and
+// applied to the open overlay windows as far as the OS allows after creation.
+
+use log::{info, warn};
+use once_cell::sync::{Lazy, OnceCell};
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::Serialize;
+use specta::Type;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Theme};
+use crate::envelope::{self, Response};
+use crate::events;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema, Type)]
+pub struct AppearancePrefs {
+    pub dark: bool,
+    pub high_contrast: bool,
+    pub reduced_motion: bool,
+    pub reduced_transparency: bool,
+}
+
+/// How a backend-created overlay window should be built for the current preferences
+#[derive(Debug, Clone, Copy)]
+pub struct WindowStyle {
+    pub transparent: bool,
+    pub shadow: bool,
+    pub theme: Theme,
+}
+
+static APP_HANDLE: OnceCell<AppHandle> = OnceCell::new();
+static CURRENT: Lazy<Mutex<Option<AppearancePrefs>>> = Lazy::new(|| Mutex::new(None));
+
+#[cfg(windows)]
+mod os {
+    use std::process::Command;
+    use windows_sys::Win32::UI::Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTW};
+    use windows_sys::Win32::UI::WindowsAndMessaging::{
+        SystemParametersInfoW, SPI_GETCLIENTAREAANIMATION, SPI_GETHIGHCONTRAST,
+    };
+
+    fn personalize(value: &str) -> Option<bool> {
+        let output = Command::new("reg")
+            .args([
+                "query",
+                "HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize",
+                "/v",
+                value,
+            ])
+            .output()
+            .ok()?;
+        let text = String::from_utf8_lossy(&output.stdout);
+        let data = text.lines().find(|line| line.contains(value))?.split_whitespace().last()?;
+        Some(data != "0x0")
+    }
+
+    pub fn dark() -> Option<bool> {
+        personalize("AppsUseLightTheme").map(|light| !light)
+    }
+
+    pub fn high_contrast() -> bool {
+        let mut settings = HIGHCONTRASTW {
+            cbSize: std::mem::size_of::<HIGHCONTRASTW>() as u32,
+            dwFlags: 0,
+            lpszDefaultScheme: std::ptr::null_mut(),
+        };
+        let ok = unsafe {
+            SystemParametersInfoW(SPI_GETHIGHCONTRAST, settings.cbSize, &mut settings as *mut _ as *mut _, 0)
+        };
+        ok != 0 && settings.dwFlags & HCF_HIGHCONTRASTON != 0
+    }
+
+    /// "Show animations in Windows" off
+    pub fn reduced_motion() -> bool {
+        let mut animations = 1i32;
+        let ok = unsafe { SystemParametersInfoW(SPI_GETCLIENTAREAANIMATION, 0, &mut animations as *mut _ as *mut _, 0) };
+        ok != 0 && animations == 0
+    }
+
+    /// "Transparency effects" off
+    pub fn reduced_transparency() -> bool {
+        personalize("EnableTransparency") == Some(false)
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod os {
+    use std::ffi::c_void;
+    use std::os::raw::c_char;
+    use std::process::Command;
+
+    #[link(name = "AppKit", kind = "framework")]
+    extern "C" {}
+
+    #[link(name = "objc")]
+    extern "C" {
+        fn objc_getClass(name: *const c_char) -> *const c_void;
+        fn sel_registerName(name: *const c_char) -> *const c_void;
+        fn objc_msgSend();
+    }
+
+    /// Ask `[NSWorkspace sharedWorkspace]` a BOOL accessibility property
+    fn workspace_flag(property: &std::ffi::CStr) -> bool {
+        type SendId = unsafe extern "C" fn(*const c_void, *const c_void) -> *const c_void;
+        type SendBool = unsafe extern "C" fn(*const c_void, *const c_void) -> i8;
+        unsafe {
+            let class = objc_getClass(c"NSWorkspace".as_ptr());
+            if class.is_null() {
+                return false;
+            }
+            let send_id: SendId = std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+            let workspace = send_id(class, sel_registerName(c"sharedWorkspace".as_ptr()));
+            if workspace.is_null() {
+                return false;
+            }
+            let send_bool: SendBool = std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+            send_bool(workspace, sel_registerName(property.as_ptr())) != 0
+        }
+    }
+
+    pub fn dark() -> Option<bool> {
+        let output = Command::new("defaults").args(["read", "-g", "AppleInterfaceStyle"]).output().ok()?;
+        // The key is absent in light mode
+        Some(String::from_utf8_lossy(&output.stdout).trim() == "Dark")
+    }
+
+    pub fn high_contrast() -> bool {
+        workspace_flag(c"accessibilityDisplayShouldIncreaseContrast")
+    }
+
+    pub fn reduced_motion() -> bool {
+        workspace_flag(c"accessibilityDisplayShouldReduceMotion")
+    }
+
+    pub fn reduced_transparency() -> bool {
+        workspace_flag(c"accessibilityDisplayShouldReduceTransparency")
+    }
+}
+
+/// GNOME-compatible desktops, read through gsettings
+#[cfg(target_os = "linux")]
+mod os {
+    use std::process::Command;
+
+    fn gsettings(schema: &str, key: &str) -> Option<String> {
+        let output = Command::new("gsettings").args(["get", schema, key]).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(String::from_utf8_lossy(&output.stdout).trim().trim_matches('\'').to_string())
+    }
+
+    pub fn dark() -> Option<bool> {
+        if let Some(scheme) = gsettings("org.gnome.desktop.interface", "color-scheme") {
+            if scheme != "default" {
+                return Some(scheme == "prefer-dark");
+            }
+        }
+        gsettings("org.gnome.desktop.interface", "gtk-theme").map(|theme| theme.to_lowercase().contains("dark"))
+    }
+
+    pub fn high_contrast() -> bool {
+        gsettings("org.gnome.desktop.a11y.interface", "high-contrast").as_deref() == Some("true")
+    }
+
+    pub fn reduced_motion() -> bool {
+        gsettings("org.gnome.desktop.interface", "enable-animations").as_deref() == Some("false")
+    }
+
+    /// No desktop-wide setting for this on Linux
+    pub fn reduced_transparency() -> bool {
+        false
+    }
+}
+
+#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+mod os {
+    pub fn dark() -> Option<bool> {
+        None
+    }
+
+    pub fn high_contrast() -> bool {
+        false
+    }
+
+    pub fn reduced_motion() -> bool {
+        false
+    }
+
+    pub fn reduced_transparency() -> bool {
+        false
+    }
+}
+
+/// Read the preferences from the OS (spawns helper processes on some platforms; keep off the UI thread)
+pub fn detect() -> AppearancePrefs {
+    AppearancePrefs {
+        dark: os::dark().unwrap_or(false),
+        high_contrast: os::high_contrast(),
+        reduced_motion: os::reduced_motion(),
+        reduced_transparency: os::reduced_transparency(),
+    }
+}
+
+fn current() -> AppearancePrefs {
+    if let Some(prefs) = *CURRENT.lock() {
+        return prefs;
+    }
+    let prefs = detect();
+    *CURRENT.lock() = Some(prefs);
+    prefs
+}
+
+impl AppearancePrefs {
+    pub fn window_style(&self) -> WindowStyle {
+        // See-through overlays are hard to read in high contrast; give opaque ones a shadow so they
+        // stand out from what is behind them
+        let opaque = self.high_contrast || self.reduced_transparency;
+        WindowStyle {
+            transparent: !opaque,
+            shadow: opaque,
+            theme: if self.dark { Theme::Dark } else { Theme::Light },
+        }
+    }
+}
+
+/// Style for a backend-created overlay window under the current preferences
+pub fn window_style() -> WindowStyle {
+    current().window_style()
+}
+
+/// Transparency is fixed at creation, so open overlays only get the shadow and theme; the frontend
+/// paints an opaque background from the event
+fn apply_to_open_windows(app_handle: &AppHandle, prefs: &AppearancePrefs) {
+    let style = prefs.window_style();
+    for (label, window) in app_handle.webview_windows() {
+        if label == "main" {
+            continue;
+        }
+        if let Err(e) = window.set_shadow(style.shadow) {
+            warn!("Failed to update the shadow of '{}': {}", label, e);
+        }
+        if let Err(e) = window.set_theme(Some(style.theme)) {
+            warn!("Failed to update the theme of '{}': {}", label, e);
+        }
+    }
+}
+
+fn refresh(app_handle: &AppHandle, prefs: AppearancePrefs) {
+    let previous = CURRENT.lock().replace(prefs);
+    if previous.is_some_and(|previous| previous != prefs) {
+        info!("🎨 OS appearance changed: {:?}", prefs);
+        apply_to_open_windows(app_handle, &prefs);
+        let _ = app_handle.emit(events::OS_APPEARANCE_CHANGED, prefs);
+    }
+}
+
+/// Start watching the OS preferences for changes
+pub fn init(app_handle: AppHandle) {
+    if APP_HANDLE.set(app_handle).is_err() {
+        return;
+    }
+    tauri::async_runtime::spawn(async {
+        let mut ticker = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            ticker.tick().await;
+            let Ok(prefs) = tauri::async_runtime::spawn_blocking(detect).await else { continue };
+            if let Some(app_handle) = APP_HANDLE.get() {
+                refresh(app_handle, prefs);
+            }
+        }
+    });
+}
+
+/// Current OS theme, high-contrast, reduced-motion and reduced-transparency settings
+#[tauri::command]
+#[specta::specta]
+pub async fn get_os_appearance(app_handle: AppHandle) -> Response<AppearancePrefs> {
+    envelope::respond("get_os_appearance", async move {
+        let prefs = tauri::async_runtime::spawn_blocking(detect).await.map_err(|e| e.to_string())?;
+        refresh(&app_handle, prefs);
+        Ok(prefs)
+    })
+    .await
+}
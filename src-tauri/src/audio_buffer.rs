@@ -0,0 +1,83 @@
+// Shared audio buffers for the capture -> transcription -> disk pipeline
+// Captured chunks are published once as `Arc<[f32]>` and shared by every consumer instead of being
+// re-encoded (WAV -> base64 -> event -> decode) for each hop. Recordings are written straight to
+// disk with hound.
+
+use std::path::Path;
+use std::sync::Arc;
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+
+/// Chunks buffered per subscriber before the slowest one starts losing the oldest chunks
+const BUS_CAPACITY: usize = 256;
+
+static BUS: Lazy<broadcast::Sender<AudioChunk>> = Lazy::new(|| broadcast::channel(BUS_CAPACITY).0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioSource {
+    System,
+    Microphone,
+}
+
+/// Mono f32 samples shared between consumers without copying
+#[derive(Debug, Clone)]
+pub struct AudioChunk {
+    pub source: AudioSource,
+    pub samples: Arc<[f32]>,
+    pub sample_rate: u32,
+}
+
+/// Hand a captured chunk to every subscriber; a no-op when nobody is listening
+pub fn publish(source: AudioSource, samples: Arc<[f32]>, sample_rate: u32) {
+    let _ = BUS.send(AudioChunk {
+        source,
+        samples,
+        sample_rate,
+    });
+}
+
+pub fn subscribe() -> broadcast::Receiver<AudioChunk> {
+    BUS.subscribe()
+}
+
+/// Little-endian 16-bit PCM in a single pass, as expected by Deepgram's linear16 encoding
+pub fn pcm16_bytes(samples: &[f32]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(samples.len() * 2);
+    for &sample in samples {
+        let sample_i16 = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+        bytes.extend_from_slice(&sample_i16.to_le_bytes());
+    }
+    bytes
+}
+
+/// Decode packed little-endian f32 bytes as delivered by WASAPI, appending to `out`
+pub fn extend_f32_from_le_bytes(bytes: &[u8], out: &mut Vec<f32>) {
+    out.extend(
+        bytes
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+    );
+}
+
+/// Write samples as a 16-bit PCM WAV file without building the file in memory first
+pub fn write_wav(path: &Path, samples: &[f32], sample_rate: u32, channels: u16) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let spec = hound::WavSpec {
+        channels,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut writer = hound::WavWriter::create(path, spec).map_err(|e| e.to_string())?;
+    for &sample in samples {
+        writer
+            .write_sample((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
+            .map_err(|e| e.to_string())?;
+    }
+    writer.finalize().map_err(|e| e.to_string())
+}
@@ -0,0 +1,182 @@
+// Audio pipeline debug dump for MockMate
+// Captures what each active stream actually recorded (raw WASAPI samples) and what was
+// sent on to transcription (clamped 16-bit PCM), plus the pipeline configuration
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use hound::{SampleFormat, WavSpec, WavWriter};
+use log::{error, info};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use tauri::{AppHandle, Emitter};
+
+use crate::{pluely_audio, pluely_microphone};
+use crate::envelope::{self, IntoResult, Response};
+use crate::events;
+
+/// Longest dump a single command may request
+const MAX_DUMP_SECONDS: u64 = 120;
+
+/// Fast-path flag so the capture hot paths skip the lock when no dump is running
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+static SESSION: Lazy<Mutex<Option<DumpSession>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stage {
+    /// f32 samples as delivered by WASAPI, before any processing
+    Raw,
+    /// Clamped/quantized 16-bit PCM exactly as sent to transcription
+    Processed,
+}
+
+struct DumpSession {
+    dir: PathBuf,
+    writers: HashMap<(&'static str, Stage), WavWriter<BufWriter<File>>>,
+}
+
+impl DumpSession {
+    fn writer(&mut self, stream: &'static str, stage: Stage, sample_rate: u32) -> Option<&mut WavWriter<BufWriter<File>>> {
+        if !self.writers.contains_key(&(stream, stage)) {
+            let (suffix, spec) = match stage {
+                Stage::Raw => ("raw_f32", WavSpec {
+                    channels: 1,
+                    sample_rate,
+                    bits_per_sample: 32,
+                    sample_format: SampleFormat::Float,
+                }),
+                Stage::Processed => ("processed_i16", WavSpec {
+                    channels: 1,
+                    sample_rate,
+                    bits_per_sample: 16,
+                    sample_format: SampleFormat::Int,
+                }),
+            };
+            let path = self.dir.join(format!("{}_{}.wav", stream, suffix));
+            match WavWriter::create(&path, spec) {
+                Ok(writer) => {
+                    self.writers.insert((stream, stage), writer);
+                }
+                Err(e) => {
+                    error!("Failed to create audio dump file {}: {}", path.display(), e);
+                    return None;
+                }
+            }
+        }
+        self.writers.get_mut(&(stream, stage))
+    }
+}
+
+/// Whether a dump is currently recording
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::Relaxed)
+}
+
+/// Append samples from `stream` at `stage` to the running dump (no-op when inactive)
+pub fn tap(stream: &'static str, stage: Stage, samples: &[f32], sample_rate: u32) {
+    if !is_active() {
+        return;
+    }
+
+    let mut guard = SESSION.lock();
+    let Some(session) = guard.as_mut() else {
+        return;
+    };
+    let Some(writer) = session.writer(stream, stage, sample_rate) else {
+        return;
+    };
+
+    let result = match stage {
+        Stage::Raw => samples.iter().try_for_each(|&s| writer.write_sample(s)),
+        Stage::Processed => samples.iter().try_for_each(|&s| {
+            // Same conversion the stream modules use before sending to Deepgram
+            writer.write_sample((s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
+        }),
+    };
+    if let Err(e) = result {
+        error!("Failed to write audio dump samples for {}: {}", stream, e);
+    }
+}
+
+pub fn dump_root() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("audio-debug")
+}
+
+/// Finalize all WAV files of the running dump
+fn finish() -> Option<PathBuf> {
+    ACTIVE.store(false, Ordering::Relaxed);
+    let session = SESSION.lock().take()?;
+    for ((stream, stage), writer) in session.writers {
+        if let Err(e) = writer.finalize() {
+            error!("Failed to finalize audio dump for {} {:?}: {}", stream, stage, e);
+        }
+    }
+    Some(session.dir)
+}
+
+/// Record raw and processed PCM of each active audio stream for `duration` seconds
+#[tauri::command]
+#[specta::specta]
+pub async fn start_audio_debug_dump(app_handle: AppHandle, duration: u64) -> Response<String> {
+    envelope::respond("start_audio_debug_dump", async move {
+        if is_active() {
+            return Err("An audio debug dump is already running".into());
+        }
+        let duration = duration.clamp(1, MAX_DUMP_SECONDS);
+
+        let dir = dump_root().join(chrono::Local::now().format("%Y%m%d-%H%M%S").to_string());
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create dump directory {}: {}", dir.display(), e))?;
+
+        let pipeline = serde_json::json!({
+            "duration_seconds": duration,
+            "started_at": chrono::Utc::now().to_rfc3339(),
+            "system_audio": {
+                "active": pluely_audio::is_pluely_audio_active().await.into_result().unwrap_or(false),
+                "config": pluely_audio::pipeline_config(),
+            },
+            "microphone": {
+                "active": pluely_microphone::is_pluely_microphone_active().await.into_result().unwrap_or(false),
+                "config": pluely_microphone::pipeline_config(),
+            },
+            "deepgram": {
+                "model": crate::get_env_var("DEEPGRAM_MODEL").unwrap_or_else(|| "nova-3".to_string()),
+                "encoding": "linear16",
+                "sample_rate": 44100,
+                "channels": 1,
+            },
+            "files": {
+                "<stream>_raw_f32.wav": "32-bit float samples as delivered by WASAPI (pre-processing)",
+                "<stream>_processed_i16.wav": "16-bit PCM as sent to transcription (post-processing)",
+            },
+        });
+        let pipeline_body = serde_json::to_vec_pretty(&pipeline).map_err(|e| e.to_string())?;
+        std::fs::write(dir.join("pipeline.json"), pipeline_body).map_err(|e| e.to_string())?;
+
+        *SESSION.lock() = Some(DumpSession {
+            dir: dir.clone(),
+            writers: HashMap::new(),
+        });
+        ACTIVE.store(true, Ordering::Relaxed);
+        info!("🎙️ Audio debug dump started for {}s: {}", duration, dir.display());
+
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(duration)).await;
+            if let Some(dir) = finish() {
+                info!("✅ Audio debug dump complete: {}", dir.display());
+                let _ = app_handle.emit(events::AUDIO_DEBUG_DUMP_COMPLETE, events::AudioDebugDumpComplete {
+                    path: dir.display().to_string(),
+                });
+            }
+        });
+
+        Ok(dir.display().to_string())
+    })
+    .await
+}
@@ -0,0 +1,360 @@
+// Preferred audio devices
+// The microphone and the output recorded in loopback can be chosen instead of the OS defaults. The
+// choice is stored in %APPDATA%\MockMate\audio_devices.json as the endpoint ID plus its friendly
+// name. Endpoint IDs change when a USB headset moves to another port or its driver is reinstalled,
+// and Windows numbers duplicates ("Microphone (2- USB Audio Device)"), so a stored device is found
+// again by ID, then by exact name, then by the closest name. The endpoint list is polled; when the
+// device a role resolves to changes, `audio-devices-changed` carries the new selection and the next
+// capture start opens it. With no choice, or nothing matching, capture uses the default device.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter};
+
+use crate::envelope::{self, Response};
+use crate::error::{AppError, ErrorCode};
+use crate::events;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+/// Share of name words two endpoints must have in common to count as the same device
+const MIN_NAME_SIMILARITY: f32 = 0.5;
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+static PREFS: Lazy<Mutex<StoredPrefs>> = Lazy::new(|| Mutex::new(load()));
+/// Selection last reported, to notice changes
+static LAST_SELECTION: Lazy<Mutex<Option<AudioDeviceSelection>>> = Lazy::new(|| Mutex::new(None));
+/// "2- " that Windows puts in front of the second of two identically named endpoints
+static DUPLICATE_PREFIX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(^|\()\d+- ").expect("valid regex"));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum DeviceRole {
+    /// The microphone
+    Input,
+    /// The output device whose playback is captured as system audio
+    Loopback,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Type)]
+pub struct AudioEndpoint {
+    pub id: String,
+    pub name: String,
+    pub role: DeviceRole,
+    pub is_default: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Type)]
+pub struct StoredDevice {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct StoredPrefs {
+    input: Option<StoredDevice>,
+    loopback: Option<StoredDevice>,
+}
+
+impl StoredPrefs {
+    fn get(&self, role: DeviceRole) -> Option<&StoredDevice> {
+        match role {
+            DeviceRole::Input => self.input.as_ref(),
+            DeviceRole::Loopback => self.loopback.as_ref(),
+        }
+    }
+
+    fn set(&mut self, role: DeviceRole, device: Option<StoredDevice>) {
+        match role {
+            DeviceRole::Input => self.input = device,
+            DeviceRole::Loopback => self.loopback = device,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchKind {
+    Id,
+    Name,
+    /// Closest name; the stored ID is updated to the match
+    SimilarName,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema, Type)]
+pub struct RoleSelection {
+    pub role: DeviceRole,
+    /// What was chosen; none means the OS default
+    pub preferred: Option<StoredDevice>,
+    /// Endpoint capture opens; none means the OS default
+    pub resolved: Option<AudioEndpoint>,
+    pub matched_by: Option<MatchKind>,
+}
+
+/// Payload of `audio-devices-changed`
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema, Type)]
+pub struct AudioDeviceSelection {
+    pub input: RoleSelection,
+    pub loopback: RoleSelection,
+}
+
+fn prefs_path() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("audio_devices.json")
+}
+
+fn load() -> StoredPrefs {
+    std::fs::read(prefs_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn save(prefs: &StoredPrefs) -> Result<(), String> {
+    let path = prefs_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(prefs).map_err(|e| e.to_string())?;
+    std::fs::write(path, body).map_err(|e| e.to_string())
+}
+
+#[cfg(windows)]
+fn direction(role: DeviceRole) -> wasapi::Direction {
+    match role {
+        DeviceRole::Input => wasapi::Direction::Capture,
+        DeviceRole::Loopback => wasapi::Direction::Render,
+    }
+}
+
+/// Active endpoints for `role`
+#[cfg(windows)]
+fn endpoints(role: DeviceRole) -> Vec<AudioEndpoint> {
+    let _ = wasapi::initialize_mta();
+    let direction = direction(role);
+    let default_id = wasapi::get_default_device(&direction).and_then(|device| device.get_id()).ok();
+    let Ok(devices) = wasapi::DeviceCollection::new(&direction) else {
+        return Vec::new();
+    };
+    let count = devices.get_nbr_devices().unwrap_or(0);
+    (0..count)
+        .filter_map(|index| devices.get_device_at_index(index).ok())
+        .filter_map(|device| {
+            let id = device.get_id().ok()?;
+            let name = device.get_friendlyname().unwrap_or_else(|_| id.clone());
+            Some(AudioEndpoint {
+                is_default: default_id.as_deref() == Some(id.as_str()),
+                id,
+                name,
+                role,
+            })
+        })
+        .collect()
+}
+
+/// Device choice is Windows-only; PulseAudio and macOS capture follow the system default
+#[cfg(not(windows))]
+fn endpoints(_role: DeviceRole) -> Vec<AudioEndpoint> {
+    Vec::new()
+}
+
+fn normalize(name: &str) -> String {
+    let lower = name.to_lowercase();
+    DUPLICATE_PREFIX.replace_all(&lower, "$1").split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Share of words the two names have in common, 0-1
+fn name_similarity(a: &str, b: &str) -> f32 {
+    let words = |name: &str| -> HashSet<String> {
+        normalize(name)
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    let (a, b) = (words(a), words(b));
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f32 / union as f32
+}
+
+fn resolve(stored: &StoredDevice, endpoints: &[AudioEndpoint]) -> Option<(AudioEndpoint, MatchKind)> {
+    if let Some(endpoint) = endpoints.iter().find(|endpoint| endpoint.id == stored.id) {
+        return Some((endpoint.clone(), MatchKind::Id));
+    }
+    let name = normalize(&stored.name);
+    if let Some(endpoint) = endpoints.iter().find(|endpoint| normalize(&endpoint.name) == name) {
+        return Some((endpoint.clone(), MatchKind::Name));
+    }
+    endpoints
+        .iter()
+        .map(|endpoint| (endpoint, name_similarity(&stored.name, &endpoint.name)))
+        .filter(|(_, similarity)| *similarity >= MIN_NAME_SIMILARITY)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(endpoint, _)| (endpoint.clone(), MatchKind::SimilarName))
+}
+
+/// Resolve the stored choice for `role` against the current endpoints, remembering a new ID when
+/// the device came back under one
+fn select(role: DeviceRole) -> RoleSelection {
+    let mut prefs = PREFS.lock();
+    let Some(preferred) = prefs.get(role).cloned() else {
+        return RoleSelection { role, preferred: None, resolved: None, matched_by: None };
+    };
+    let resolved = resolve(&preferred, &endpoints(role));
+    if let Some((endpoint, MatchKind::Name | MatchKind::SimilarName)) = &resolved {
+        info!("🎧 Reconnected {:?} device \"{}\" as \"{}\"", role, preferred.name, endpoint.name);
+        prefs.set(role, Some(StoredDevice { id: endpoint.id.clone(), name: endpoint.name.clone() }));
+        if let Err(e) = save(&prefs) {
+            warn!("Failed to save audio device preferences: {}", e);
+        }
+    }
+    RoleSelection {
+        role,
+        preferred: prefs.get(role).cloned(),
+        matched_by: resolved.as_ref().map(|(_, kind)| *kind),
+        resolved: resolved.map(|(endpoint, _)| endpoint),
+    }
+}
+
+fn current_selection() -> AudioDeviceSelection {
+    AudioDeviceSelection {
+        input: select(DeviceRole::Input),
+        loopback: select(DeviceRole::Loopback),
+    }
+}
+
+/// The chosen device for `direction` if it is connected; `None` means use the default
+#[cfg(windows)]
+pub fn preferred_device(direction: &wasapi::Direction) -> Option<wasapi::Device> {
+    let role = match direction {
+        wasapi::Direction::Capture => DeviceRole::Input,
+        wasapi::Direction::Render => DeviceRole::Loopback,
+    };
+    let endpoint = select(role).resolved?;
+    let devices = wasapi::DeviceCollection::new(direction).ok()?;
+    (0..devices.get_nbr_devices().ok()?)
+        .filter_map(|index| devices.get_device_at_index(index).ok())
+        .find(|device| device.get_id().is_ok_and(|id| id == endpoint.id))
+}
+
+fn refresh(app_handle: &AppHandle, selection: AudioDeviceSelection) {
+    let previous = LAST_SELECTION.lock().replace(selection.clone());
+    match previous {
+        None => {
+            for role in [&selection.input, &selection.loopback] {
+                match (&role.preferred, &role.resolved) {
+                    (Some(_), Some(endpoint)) => info!("🎧 Using preferred {:?} device: {}", role.role, endpoint.name),
+                    (Some(preferred), None) => {
+                        warn!("🎧 Preferred {:?} device \"{}\" is not connected; using the default", role.role, preferred.name)
+                    }
+                    (None, _) => {}
+                }
+            }
+        }
+        // Only a different device counts; a reconnection by name re-matches by ID on the next poll
+        Some(previous) if previous.input.resolved != selection.input.resolved
+            || previous.loopback.resolved != selection.loopback.resolved =>
+        {
+            info!("🎧 Audio device selection changed");
+            let _ = app_handle.emit(events::AUDIO_DEVICES_CHANGED, &selection);
+        }
+        Some(_) => {}
+    }
+}
+
+/// Restore the preferred devices and watch for them to come and go
+pub fn init(app_handle: AppHandle) {
+    if APP_HANDLE.set(app_handle).is_err() {
+        return;
+    }
+    tauri::async_runtime::spawn(async {
+        let mut ticker = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            ticker.tick().await;
+            let Ok(selection) = tauri::async_runtime::spawn_blocking(current_selection).await else { continue };
+            if let Some(app_handle) = APP_HANDLE.get() {
+                refresh(app_handle, selection);
+            }
+        }
+    });
+}
+
+/// Connected microphones and outputs that can be captured in loopback
+#[tauri::command]
+#[specta::specta]
+pub async fn list_audio_endpoints() -> Response<Vec<AudioEndpoint>> {
+    envelope::respond("list_audio_endpoints", async move {
+        let endpoints = tauri::async_runtime::spawn_blocking(|| {
+            let mut all = endpoints(DeviceRole::Input);
+            all.extend(endpoints(DeviceRole::Loopback));
+            all
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+        Ok(endpoints)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_audio_device_selection() -> Response<AudioDeviceSelection> {
+    envelope::respond("get_audio_device_selection", async move {
+        Ok(tauri::async_runtime::spawn_blocking(current_selection).await.map_err(|e| e.to_string())?)
+    })
+    .await
+}
+
+/// Choose the device for `role` by endpoint ID, or go back to the OS default with `None`
+#[tauri::command]
+#[specta::specta]
+pub async fn set_preferred_audio_device(role: DeviceRole, device_id: Option<String>) -> Response<AudioDeviceSelection> {
+    envelope::respond("set_preferred_audio_device", async move {
+        let selection = tauri::async_runtime::spawn_blocking(move || -> Result<AudioDeviceSelection, AppError> {
+            let device = match device_id {
+                Some(device_id) => {
+                    let endpoints = endpoints(role);
+                    if endpoints.is_empty() {
+                        return Err(AppError::new(
+                            ErrorCode::Unsupported,
+                            "Choosing audio devices is not supported on this platform; the system default is used",
+                        ));
+                    }
+                    let endpoint = endpoints.into_iter().find(|endpoint| endpoint.id == device_id).ok_or_else(|| {
+                        AppError::new(ErrorCode::NotFound, format!("Audio device {} is not connected", device_id))
+                    })?;
+                    info!("🎧 Preferred {:?} device set to {}", role, endpoint.name);
+                    Some(StoredDevice { id: endpoint.id, name: endpoint.name })
+                }
+                None => {
+                    info!("🎧 {:?} device reset to the system default", role);
+                    None
+                }
+            };
+            {
+                let mut prefs = PREFS.lock();
+                prefs.set(role, device);
+                save(&prefs)?;
+            }
+            Ok(current_selection())
+        })
+        .await
+        .map_err(|e| e.to_string())??;
+        *LAST_SELECTION.lock() = Some(selection.clone());
+        Ok(selection)
+    })
+    .await
+}
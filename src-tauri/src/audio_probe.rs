@@ -0,0 +1,241 @@
+// Audio backend capability probing
+// What the capture backends can do differs by OS, OS build and even by device driver, so
+// `probe_audio_capabilities` asks the backend itself (WASAPI, PulseAudio/PipeWire, CoreAudio)
+// rather than guessing from the OS name. The frontend uses the result to hide options that
+// cannot work on this machine, with the reason shown where an option would have been.
+
+use log::info;
+use serde::Serialize;
+use specta::Type;
+use crate::envelope::{self, Response};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioFeature {
+    /// Recording what the speakers play
+    LoopbackCapture,
+    /// Recording a single application's audio instead of the whole mix
+    PerAppCapture,
+    /// Acoustic echo cancellation on the microphone stream
+    EchoCancellation,
+    /// Exclusive-mode access to the microphone
+    ExclusiveMode,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct AudioFeatureSupport {
+    pub feature: AudioFeature,
+    pub available: bool,
+    /// Why the feature is missing, or a note on how it is provided
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct AudioCapabilities {
+    pub os: String,
+    /// The capture backend in use, e.g. "wasapi", "pulseaudio", "coreaudio"
+    pub backend: String,
+    pub features: Vec<AudioFeatureSupport>,
+}
+
+fn support(feature: AudioFeature, result: Result<Option<String>, String>) -> AudioFeatureSupport {
+    match result {
+        Ok(note) => AudioFeatureSupport {
+            feature,
+            available: true,
+            reason: note,
+        },
+        Err(reason) => AudioFeatureSupport {
+            feature,
+            available: false,
+            reason: Some(reason),
+        },
+    }
+}
+
+#[cfg(windows)]
+mod backend {
+    use super::{support, AudioFeature, AudioFeatureSupport};
+    use wasapi::{get_default_device, AudioClient, Direction, SampleType, StreamMode, WaveFormat};
+
+    pub const NAME: &str = "wasapi";
+
+    fn loopback() -> Result<Option<String>, String> {
+        let device = get_default_device(&Direction::Render).map_err(|_| "No playback device is available".to_string())?;
+        device.get_iaudioclient().map_err(|e| format!("The playback device cannot be opened: {}", e))?;
+        Ok(device.get_friendlyname().ok())
+    }
+
+    /// Process loopback activation only exists on Windows 10 build 20348 and later
+    fn per_app() -> Result<Option<String>, String> {
+        AudioClient::new_application_loopback_client(std::process::id(), true)
+            .map(|_| None)
+            .map_err(|_| "Requires Windows 10 build 20348 or Windows 11".to_string())
+    }
+
+    /// The AEC control is only offered on an initialized communications capture stream (Windows 11 22H2+)
+    fn echo_cancellation() -> Result<Option<String>, String> {
+        let device = get_default_device(&Direction::Capture).map_err(|_| "No microphone is available".to_string())?;
+        let mut client = device.get_iaudioclient().map_err(|e| e.to_string())?;
+        let format = WaveFormat::new(32, 32, &SampleType::Float, 44100, 1, None);
+        let (_def_time, min_time) = client.get_device_period().map_err(|e| e.to_string())?;
+        let mode = StreamMode::EventsShared {
+            autoconvert: true,
+            buffer_duration_hns: min_time,
+        };
+        client.initialize_client(&format, &Direction::Capture, &mode).map_err(|e| e.to_string())?;
+        match client.is_aec_supported() {
+            Ok(true) => Ok(None),
+            Ok(false) => Err("The microphone driver offers no echo cancellation (needs Windows 11 22H2 or later)".to_string()),
+            Err(e) => Err(format!("Could not query echo cancellation: {}", e)),
+        }
+    }
+
+    /// Denied when the driver rejects the format or "Allow applications to take exclusive control" is off
+    fn exclusive_mode() -> Result<Option<String>, String> {
+        let device = get_default_device(&Direction::Capture).map_err(|_| "No microphone is available".to_string())?;
+        let client = device.get_iaudioclient().map_err(|e| e.to_string())?;
+        let format = client.get_mixformat().map_err(|e| e.to_string())?;
+        // Tries both the extensible and the plain WAVEFORMATEX form, as some drivers only accept one
+        client.is_supported_exclusive_with_quirks(&format).map(|_| None).map_err(|_| {
+            "The microphone does not allow exclusive control (Sound settings > Device properties > Advanced)".to_string()
+        })
+    }
+
+    pub fn probe() -> Vec<AudioFeatureSupport> {
+        let _ = wasapi::initialize_mta();
+        vec![
+            support(AudioFeature::LoopbackCapture, loopback()),
+            support(AudioFeature::PerAppCapture, per_app()),
+            support(AudioFeature::EchoCancellation, echo_cancellation()),
+            support(AudioFeature::ExclusiveMode, exclusive_mode()),
+        ]
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod backend {
+    use super::{support, AudioFeature, AudioFeatureSupport};
+    use std::process::Command;
+
+    pub const NAME: &str = "pulseaudio";
+
+    fn pactl(args: &[&str]) -> Result<String, String> {
+        let output = Command::new("pactl")
+            .args(args)
+            .output()
+            .map_err(|_| "pactl is not installed (pulseaudio-utils or pipewire-pulse)".to_string())?;
+        if !output.status.success() {
+            return Err("No PulseAudio or PipeWire server is running".to_string());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    fn loopback() -> Result<Option<String>, String> {
+        let sink = pactl(&["get-default-sink"])?;
+        let sink = sink.trim();
+        if sink.is_empty() {
+            return Err("No default output device".to_string());
+        }
+        Ok(Some(format!("{}.monitor", sink)))
+    }
+
+    /// module-echo-cancel provides an echo-cancelled source; it is used when it is the default source
+    fn echo_cancellation() -> Result<Option<String>, String> {
+        let modules = pactl(&["list", "short", "modules"])?;
+        if modules.lines().any(|line| line.contains("module-echo-cancel")) {
+            Ok(Some("Select the echo-cancelled source as the default input".to_string()))
+        } else {
+            Err("Load module-echo-cancel (pactl load-module module-echo-cancel) to enable it".to_string())
+        }
+    }
+
+    pub fn probe() -> Vec<AudioFeatureSupport> {
+        vec![
+            support(AudioFeature::LoopbackCapture, loopback()),
+            support(
+                AudioFeature::PerAppCapture,
+                Err("Only whole-device monitors are captured on PulseAudio/PipeWire".to_string()),
+            ),
+            support(AudioFeature::EchoCancellation, echo_cancellation()),
+            support(
+                AudioFeature::ExclusiveMode,
+                Err("PulseAudio and PipeWire streams are always shared".to_string()),
+            ),
+        ]
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod backend {
+    use super::{support, AudioFeature, AudioFeatureSupport};
+
+    pub const NAME: &str = "coreaudio";
+
+    fn loopback() -> Result<Option<String>, String> {
+        match crate::macos_loopback::find_loopback_device() {
+            Ok(Some(device)) => Ok(Some(device.name)),
+            Ok(None) => Err(crate::macos_loopback::SETUP_GUIDANCE.to_string()),
+            Err(e) => Err(format!("Could not list audio devices: {}", e)),
+        }
+    }
+
+    pub fn probe() -> Vec<AudioFeatureSupport> {
+        vec![
+            support(AudioFeature::LoopbackCapture, loopback()),
+            support(
+                AudioFeature::PerAppCapture,
+                Err("System audio comes from a loopback device, which carries the whole mix".to_string()),
+            ),
+            support(
+                AudioFeature::EchoCancellation,
+                Err("The microphone is recorded without the voice-processing unit".to_string()),
+            ),
+            support(
+                AudioFeature::ExclusiveMode,
+                Err("Core Audio devices are shared with other applications".to_string()),
+            ),
+        ]
+    }
+}
+
+#[cfg(not(any(windows, target_os = "linux", target_os = "macos")))]
+mod backend {
+    use super::{support, AudioFeature, AudioFeatureSupport};
+
+    pub const NAME: &str = "none";
+
+    pub fn probe() -> Vec<AudioFeatureSupport> {
+        [
+            AudioFeature::LoopbackCapture,
+            AudioFeature::PerAppCapture,
+            AudioFeature::EchoCancellation,
+            AudioFeature::ExclusiveMode,
+        ]
+        .into_iter()
+        .map(|feature| support(feature, Err(format!("Audio capture is not available on {}", std::env::consts::OS))))
+        .collect()
+    }
+}
+
+/// Which loopback, per-app capture, echo cancellation and exclusive-mode options work here
+#[tauri::command]
+#[specta::specta]
+pub async fn probe_audio_capabilities() -> Response<AudioCapabilities> {
+    envelope::respond("probe_audio_capabilities", async move {
+        let features = tauri::async_runtime::spawn_blocking(backend::probe)
+            .await
+            .map_err(|e| e.to_string())?;
+        info!(
+            "🔎 Audio capabilities ({}): {:?}",
+            backend::NAME,
+            features.iter().filter(|f| f.available).map(|f| f.feature).collect::<Vec<_>>()
+        );
+        Ok(AudioCapabilities {
+            os: std::env::consts::OS.to_string(),
+            backend: backend::NAME.to_string(),
+            features,
+        })
+    })
+    .await
+}
@@ -0,0 +1,110 @@
+// Real-time capture threads for WASAPI
+// Each capture loop runs on its own named thread registered with MMCSS as "Pro Audio", so the
+// scheduler keeps servicing audio events while AI streaming and window work load the process.
+
+use std::thread;
+
+use log::info;
+#[cfg(windows)]
+use log::warn;
+#[cfg(windows)]
+use wasapi::{get_default_device, Direction, SampleType, StreamMode, WaveFormat};
+#[cfg(windows)]
+use windows_sys::Win32::Foundation::HANDLE;
+#[cfg(windows)]
+use windows_sys::Win32::System::Threading::{
+    AvRevertMmThreadCharacteristics, AvSetMmThreadCharacteristicsW, AvSetMmThreadPriority, AVRT_PRIORITY_HIGH,
+};
+
+/// Bytes reserved up front for one WASAPI read (~90ms of 44.1kHz mono f32)
+pub const PACKET_BUFFER_BYTES: usize = 16 * 1024;
+/// Samples reserved up front for the shared capture queue (capped at 8192 plus one packet)
+pub const QUEUE_CAPACITY_SAMPLES: usize = 8192 + PACKET_BUFFER_BYTES / 4;
+
+/// Open the preferred device for `direction` (see `audio_devices`), else the default, as a started,
+/// event-driven 44.1kHz mono f32 stream.
+/// `Direction::Render` captures the speakers in loopback; WASAPI converts from the mix format.
+#[cfg(windows)]
+pub fn open_shared_stream(direction: Direction) -> anyhow::Result<(wasapi::Handle, wasapi::AudioCaptureClient)> {
+    match crate::audio_devices::preferred_device(&direction) {
+        Some(device) => open_device_stream(&device),
+        None => open_device_stream(&get_default_device(&direction)?),
+    }
+}
+
+/// `open_shared_stream` for a specific device, e.g. a Stereo Mix capture endpoint
+#[cfg(windows)]
+pub fn open_device_stream(device: &wasapi::Device) -> anyhow::Result<(wasapi::Handle, wasapi::AudioCaptureClient)> {
+    let mut audio_client = device.get_iaudioclient()?;
+
+    // Use Pluely's exact format configuration
+    let desired_format = WaveFormat::new(32, 32, &SampleType::Float, 44100, 1, None);
+
+    let (_def_time, min_time) = audio_client.get_device_period()?;
+
+    let mode = StreamMode::EventsShared {
+        autoconvert: true,
+        buffer_duration_hns: min_time,
+    };
+
+    // Loopback is a capture-direction client on a render device
+    audio_client.initialize_client(&desired_format, &Direction::Capture, &mode)?;
+
+    let h_event = audio_client.set_get_eventhandle()?;
+    let capture_client = audio_client.get_audiocaptureclient()?;
+
+    audio_client.start_stream()?;
+    Ok((h_event, capture_client))
+}
+
+/// MMCSS registration of the current thread; reverted on drop
+#[cfg(windows)]
+pub struct MmcssRegistration(HANDLE);
+
+#[cfg(windows)]
+impl Drop for MmcssRegistration {
+    fn drop(&mut self) {
+        unsafe {
+            AvRevertMmThreadCharacteristics(self.0);
+        }
+    }
+}
+
+/// Register the calling thread with the "Pro Audio" MMCSS task at high priority
+#[cfg(windows)]
+pub fn register_pro_audio() -> Option<MmcssRegistration> {
+    let task: Vec<u16> = "Pro Audio".encode_utf16().chain(std::iter::once(0)).collect();
+    let mut task_index = 0u32;
+    let handle = unsafe { AvSetMmThreadCharacteristicsW(task.as_ptr(), &mut task_index) };
+    if handle == 0 {
+        warn!("MMCSS Pro Audio registration failed (error {}); capturing at normal priority", std::io::Error::last_os_error());
+        return None;
+    }
+
+    let registration = MmcssRegistration(handle);
+    if unsafe { AvSetMmThreadPriority(handle, AVRT_PRIORITY_HIGH) } == 0 {
+        warn!("Failed to raise MMCSS thread priority: {}", std::io::Error::last_os_error());
+    }
+    Some(registration)
+}
+
+/// MMCSS is Windows-only; elsewhere capture threads keep the normal priority
+#[cfg(not(windows))]
+pub fn register_pro_audio() -> Option<()> {
+    None
+}
+
+/// Spawn a dedicated capture thread that runs `capture` under MMCSS Pro Audio scheduling
+pub fn spawn_capture_thread<F>(name: &str, capture: F) -> std::io::Result<thread::JoinHandle<()>>
+where
+    F: FnOnce() + Send + 'static,
+{
+    let thread_name = name.to_string();
+    thread::Builder::new().name(thread_name.clone()).spawn(move || {
+        let mmcss = register_pro_audio();
+        if mmcss.is_some() {
+            info!("🎚️ {} running with MMCSS Pro Audio priority", thread_name);
+        }
+        capture();
+    })
+}
@@ -4,18 +4,21 @@
 use log::{info, error, warn};
 use std::sync::{Arc, Mutex};
 use anyhow::{Result, anyhow};
-use std::collections::VecDeque;
-use std::time::SystemTime;
-use base64::prelude::*;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use crate::capture_buffer::{SpillingSampleBuffer, DEFAULT_MEMORY_CAP_BYTES};
 use crate::pluely_audio::{start_pluely_system_audio_capture, stop_pluely_system_audio_capture};
 use tauri::AppHandle;
+use crate::envelope::{self, IntoResult, Response};
 
 static AUDIO_STATE: std::sync::OnceLock<Arc<Mutex<AudioCaptureState>>> = std::sync::OnceLock::new();
 
 struct AudioCaptureState {
     is_recording: bool,
     config: AudioConfig,
-    captured_samples: VecDeque<f32>,
+    captured_samples: SpillingSampleBuffer,
+    /// Bumped on every start so a feeder from an earlier capture stops appending
+    recording_generation: u64,
     is_mic_recording: bool,
 }
 
@@ -24,7 +27,8 @@ fn get_audio_state() -> Arc<Mutex<AudioCaptureState>> {
         Arc::new(Mutex::new(AudioCaptureState {
             is_recording: false,
             config: AudioConfig::default(),
-            captured_samples: VecDeque::new(),
+            captured_samples: SpillingSampleBuffer::new(DEFAULT_MEMORY_CAP_BYTES),
+            recording_generation: 0,
             is_mic_recording: false,
         }))
     }).clone()
@@ -47,14 +51,6 @@ impl Default for AudioConfig {
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct AudioData {
-    pub samples: Vec<f32>,
-    pub sample_rate: u32,
-    pub channels: u16,
-    pub timestamp: SystemTime,
-}
-
 #[derive(Debug, Clone)]
 pub struct AudioDevice {
     pub name: String,
@@ -127,15 +123,70 @@ pub async fn start_system_audio_capture_with_app(app_handle: AppHandle) -> Resul
     
     // Use the new Pluely-style system audio capture
     start_pluely_system_audio_capture(app_handle)
-        .await
+        .await.into_result()
         .map_err(|e| anyhow!("Failed to start Pluely system audio capture: {}", e))?;
     
-    audio_state.is_recording = true;
+    begin_recording(&mut audio_state);
     
     info!("✅ WASAPI system audio capture started successfully");
     Ok(())
 }
 
+/// Record system audio into the capture buffer until `save_system_audio_file` writes it; only the
+/// `start_system_audio_capture` command records, not every capture
+pub fn begin_capture_recording() {
+    let state = get_audio_state();
+    let mut audio_state = state.lock().unwrap();
+    begin_recording(&mut audio_state);
+}
+
+/// The capture ended without being saved: drop the recording and its spill files
+pub fn discard_capture_recording() {
+    let state = get_audio_state();
+    let mut audio_state = state.lock().unwrap();
+    if audio_state.is_recording {
+        info!("🗑️ Discarding {} unsaved captured samples", audio_state.captured_samples.len());
+    }
+    audio_state.is_recording = false;
+    audio_state.captured_samples.clear();
+}
+
+/// Start recording system audio into the capture buffer, dropping any previous capture
+fn begin_recording(audio_state: &mut AudioCaptureState) {
+    audio_state.captured_samples.clear();
+    audio_state.is_recording = true;
+    audio_state.recording_generation += 1;
+    spawn_capture_feeder(audio_state.recording_generation);
+}
+
+/// Append published system audio to the capture buffer until this recording ends. Runs on its own
+/// thread because a push that spills writes to disk under the state lock
+fn spawn_capture_feeder(generation: u64) {
+    let mut chunks = crate::audio_buffer::subscribe();
+    std::thread::spawn(move || {
+        loop {
+            let chunk = match chunks.blocking_recv() {
+                Ok(chunk) => chunk,
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("Capture buffer fell behind, dropped {} chunks", skipped);
+                    continue;
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            };
+            if chunk.source != crate::audio_buffer::AudioSource::System {
+                continue;
+            }
+
+            let state = get_audio_state();
+            let mut audio_state = state.lock().unwrap();
+            if !audio_state.is_recording || audio_state.recording_generation != generation {
+                break;
+            }
+            audio_state.captured_samples.push(&chunk.samples);
+        }
+    });
+}
+
 pub async fn stop_system_audio_capture() -> Result<()> {
     info!("🛑 Stopping WASAPI system audio capture...");
     
@@ -151,7 +202,9 @@ pub async fn stop_system_audio_capture() -> Result<()> {
     // This is a compatibility layer, we'll handle this differently
     warn!("stop_system_audio_capture called from compatibility layer - Pluely system may need restart");
     
+    // Saving ends the recording itself, so a capture stopped here was not saved
     audio_state.is_recording = false;
+    audio_state.captured_samples.clear();
     
     info!("✅ WASAPI system audio capture stopped successfully");
     Ok(())
@@ -174,11 +227,6 @@ pub fn capture_audio_with_config(_config: AudioConfig) -> Result<()> {
     Err(anyhow!("This function requires an AppHandle for WASAPI integration"))
 }
 
-pub fn start_system_audio_capture() -> Result<()> {
-    warn!("start_system_audio_capture() called - this function requires an AppHandle for WASAPI. Use start_system_audio_capture_with_app() instead.");
-    Err(anyhow!("This function requires an AppHandle for WASAPI integration"))
-}
-
 pub fn start_microphone_capture() -> Result<()> {
     warn!("start_microphone_capture() - microphone support coming soon with WASAPI");
     Err(anyhow!("Microphone support with WASAPI coming soon"))
@@ -206,10 +254,69 @@ pub fn get_audio_config() -> AudioConfig {
     audio_state.config.clone()
 }
 
-pub fn get_captured_samples() -> Vec<f32> {
+pub fn captured_sample_count() -> usize {
     let state = get_audio_state();
     let audio_state = state.lock().unwrap();
-    audio_state.captured_samples.iter().cloned().collect()
+    audio_state.captured_samples.len()
+}
+
+/// End the recording and write what it captured to a WAV file, merging spilled audio without
+/// loading it all into memory. The buffer is taken out of the state first, so the feeder and other
+/// callers never wait on the write; its spill files are deleted once it is written.
+pub fn write_captured_wav(path: &std::path::Path) -> Result<usize, String> {
+    let (captured, config) = {
+        let state = get_audio_state();
+        let mut audio_state = state.lock().unwrap();
+        audio_state.is_recording = false;
+        let cap_bytes = audio_state.captured_samples.cap_bytes();
+        let captured = std::mem::replace(&mut audio_state.captured_samples, SpillingSampleBuffer::new(cap_bytes));
+        (captured, audio_state.config.clone())
+    };
+    captured.write_wav(path, config.sample_rate, config.channels)?;
+    Ok(captured.len())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CaptureMemoryStatus {
+    pub memory_limit_mb: u64,
+    pub captured_samples: usize,
+    pub spilled_samples: usize,
+}
+
+fn capture_memory_status(audio_state: &AudioCaptureState) -> CaptureMemoryStatus {
+    CaptureMemoryStatus {
+        memory_limit_mb: (audio_state.captured_samples.cap_bytes() / (1024 * 1024)) as u64,
+        captured_samples: audio_state.captured_samples.len(),
+        spilled_samples: audio_state.captured_samples.spilled_samples(),
+    }
+}
+
+/// Cap the RAM used by the system audio capture; older audio beyond it spills to temp files
+#[tauri::command]
+#[specta::specta]
+pub async fn set_capture_memory_limit(megabytes: u64) -> Response<CaptureMemoryStatus> {
+    envelope::respond("set_capture_memory_limit", async move {
+        if megabytes == 0 {
+            return Err("Memory limit must be at least 1 MB".into());
+        }
+        let state = get_audio_state();
+        let mut audio_state = state.lock().map_err(|e| e.to_string())?;
+        audio_state.captured_samples.set_cap_bytes((megabytes as usize).saturating_mul(1024 * 1024));
+        info!("💾 Capture memory limit set to {} MB", megabytes);
+        Ok(capture_memory_status(&audio_state))
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_capture_memory_status() -> Response<CaptureMemoryStatus> {
+    envelope::respond("get_capture_memory_status", async move {
+        let state = get_audio_state();
+        let audio_state = state.lock().map_err(|e| e.to_string())?;
+        Ok(capture_memory_status(&audio_state))
+    })
+    .await
 }
 
 pub fn cleanup_audio_capture() {
@@ -219,31 +326,3 @@ pub fn cleanup_audio_capture() {
     audio_state.captured_samples.clear();
     audio_state.is_recording = false;
 }
-
-pub fn audio_data_to_base64_wav(audio_data: &AudioData) -> Result<String> {
-    info!("🎵 Converting audio data to base64 WAV format...");
-    
-    use hound::{WavSpec, WavWriter};
-    use std::io::Cursor;
-    
-    let spec = WavSpec {
-        channels: audio_data.channels,
-        sample_rate: audio_data.sample_rate,
-        bits_per_sample: 16,
-        sample_format: hound::SampleFormat::Int,
-    };
-    
-    let mut cursor = Cursor::new(Vec::new());
-    let mut writer = WavWriter::new(&mut cursor, spec)?;
-    
-    for &sample in &audio_data.samples {
-        let clamped = sample.clamp(-1.0, 1.0);
-        let sample_i16 = (clamped * i16::MAX as f32) as i16;
-        writer.write_sample(sample_i16)?;
-    }
-    
-    writer.finalize()?;
-    let wav_data = cursor.into_inner();
-    
-    Ok(BASE64_STANDARD.encode(wav_data))
-}
\ No newline at end of file
@@ -0,0 +1,233 @@
+// Outbound-data audit log for MockMate
+// One line per payload that left the machine: who received it, why, what kind of data and how
+// many bytes. Contents are never written - only sizes and categories.
+
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+
+use chrono::{DateTime, FixedOffset};
+use log::error;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use crate::envelope::{self, Response};
+use crate::error::{AppError, ErrorCode};
+
+/// Default number of entries returned by get_audit_log
+const DEFAULT_LIMIT: usize = 500;
+
+static CURRENT_SESSION: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+/// Serializes appends so concurrent writers don't interleave lines
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadCategory {
+    /// Prompts, transcripts, answers
+    Text,
+    /// Raw PCM streamed for transcription
+    Audio,
+    /// Screenshots sent for analysis
+    Image,
+    /// Ids, status updates and counters without user content
+    Metadata,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AuditEntry {
+    pub timestamp: String,
+    pub provider: String,
+    pub purpose: String,
+    pub category: PayloadCategory,
+    pub bytes: u64,
+    pub session_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct AuditFilter {
+    pub provider: Option<String>,
+    pub purpose: Option<String>,
+    pub category: Option<PayloadCategory>,
+    pub session_id: Option<String>,
+    /// RFC 3339 timestamp; only entries at or after it are returned
+    pub since: Option<String>,
+    pub limit: Option<usize>,
+}
+
+impl AuditFilter {
+    fn since(&self) -> Result<Option<DateTime<FixedOffset>>, AppError> {
+        self.since
+            .as_deref()
+            .map(|since| {
+                DateTime::parse_from_rfc3339(since).map_err(|e| {
+                    AppError::new(ErrorCode::InvalidInput, format!("Invalid since timestamp '{}': {}", since, e))
+                })
+            })
+            .transpose()
+    }
+
+    /// `since` is the parsed `self.since`; timestamps are compared as instants, so offsets may differ
+    fn matches(&self, entry: &AuditEntry, since: Option<DateTime<FixedOffset>>) -> bool {
+        self.provider.as_ref().map(|p| &entry.provider == p).unwrap_or(true)
+            && self.purpose.as_ref().map(|p| &entry.purpose == p).unwrap_or(true)
+            && self.category.map(|c| entry.category == c).unwrap_or(true)
+            && self.session_id.as_ref().map(|s| entry.session_id.as_ref() == Some(s)).unwrap_or(true)
+            && since.is_none_or(|since| DateTime::parse_from_rfc3339(&entry.timestamp).is_ok_and(|at| at >= since))
+    }
+}
+
+pub fn audit_dir() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("audit")
+}
+
+fn audit_file() -> PathBuf {
+    audit_dir().join("outbound.jsonl")
+}
+
+/// Attribute subsequent entries to `session_id` (None when no session is active)
+pub fn set_current_session(session_id: Option<String>) {
+    *CURRENT_SESSION.lock() = session_id;
+}
+
+/// Session that new entries are attributed to
+pub fn current_session() -> Option<String> {
+    CURRENT_SESSION.lock().clone()
+}
+
+/// Append one outbound payload to the audit log, attributed to the current session
+pub fn record(provider: &str, purpose: &str, category: PayloadCategory, bytes: u64) {
+    record_for_session(provider, purpose, category, bytes, current_session());
+}
+
+/// Append one outbound payload that belongs to a known session
+pub fn record_for_session(provider: &str, purpose: &str, category: PayloadCategory, bytes: u64, session_id: Option<String>) {
+    let entry = AuditEntry {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        provider: provider.to_string(),
+        purpose: purpose.to_string(),
+        category,
+        bytes,
+        session_id,
+    };
+
+    let Ok(mut line) = serde_json::to_string(&entry) else {
+        return;
+    };
+    line.push('\n');
+
+    let _guard = WRITE_LOCK.lock();
+    let result = std::fs::create_dir_all(audit_dir()).and_then(|_| {
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(audit_file())?
+            .write_all(line.as_bytes())
+    });
+    if let Err(e) = result {
+        error!("Failed to write outbound audit entry: {}", e);
+    }
+}
+
+/// Provider name for an outbound HTTP host
+pub fn provider_for_host(host: &str) -> String {
+    let backend_host = std::env::var("MOCKMATE_BACKEND_URL")
+        .ok()
+        .and_then(|url| reqwest::Url::parse(&url).ok())
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| "mockmate-backend.onrender.com".to_string());
+
+    if host == backend_host {
+        "mockmate_backend".to_string()
+    } else if host.ends_with("openai.com") {
+        "openai".to_string()
+    } else if host.ends_with("pollinations.ai") {
+        "pollinations".to_string()
+    } else {
+        host.to_string()
+    }
+}
+
+/// Why an outbound HTTP request was made, inferred from its provider and path
+pub fn purpose_for_request(provider: &str, path: &str) -> &'static str {
+    match provider {
+        "mockmate_backend" if path.starts_with("/api/telemetry") => "telemetry",
+        "mockmate_backend" if path.contains("temp-token") => "authentication",
+        "mockmate_backend" if path.starts_with("/api/sessions") => "session_sync",
+        "openai" | "pollinations" if path.ends_with("/models") => "model_list",
+        "openai" | "pollinations" => "ai_answer",
+        _ => "request",
+    }
+}
+
+/// Entries matching `filter`, newest last
+#[tauri::command]
+#[specta::specta]
+pub async fn get_audit_log(filter: Option<AuditFilter>) -> Response<Vec<AuditEntry>> {
+    envelope::respond("get_audit_log", async move {
+        let filter = filter.unwrap_or_default();
+        let limit = filter.limit.unwrap_or(DEFAULT_LIMIT);
+        let since = filter.since()?;
+
+        Ok(tokio::task::spawn_blocking(move || {
+            let file = match std::fs::File::open(audit_file()) {
+                Ok(file) => file,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+                Err(e) => return Err(format!("Failed to open audit log: {}", e)),
+            };
+
+            let mut matching: std::collections::VecDeque<AuditEntry> = std::collections::VecDeque::new();
+            for line in BufReader::new(file).lines().map_while(Result::ok) {
+                let Ok(entry) = serde_json::from_str::<AuditEntry>(&line) else {
+                    continue;
+                };
+                if filter.matches(&entry, since) {
+                    matching.push_back(entry);
+                    if matching.len() > limit {
+                        matching.pop_front();
+                    }
+                }
+            }
+            Ok(matching.into())
+        })
+        .await
+        .map_err(|e| e.to_string())??)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(timestamp: &str) -> AuditEntry {
+        AuditEntry {
+            timestamp: timestamp.to_string(),
+            provider: "deepgram".to_string(),
+            purpose: "transcription".to_string(),
+            category: PayloadCategory::Audio,
+            bytes: 1,
+            session_id: None,
+        }
+    }
+
+    #[test]
+    fn since_compares_instants_across_offsets() {
+        let filter = AuditFilter { since: Some("2026-01-01T09:00:00Z".to_string()), ..Default::default() };
+        let since = filter.since().unwrap();
+        // 10:00 at +02:00 is 08:00 UTC, before the cutoff even though the string sorts after it
+        assert!(!filter.matches(&entry("2026-01-01T10:00:00+02:00"), since));
+        assert!(filter.matches(&entry("2026-01-01T05:30:00-04:00"), since));
+        assert!(filter.matches(&entry("2026-01-01T09:00:00.000001+00:00"), since));
+        assert!(!filter.matches(&entry("not a timestamp"), since));
+    }
+
+    #[test]
+    fn invalid_since_is_rejected() {
+        let filter = AuditFilter { since: Some("yesterday".to_string()), ..Default::default() };
+        assert_eq!(filter.since().unwrap_err().code, ErrorCode::InvalidInput);
+        assert_eq!(AuditFilter::default().since().unwrap(), None);
+    }
+}
@@ -0,0 +1,474 @@
+// Desktop sign-in with OAuth 2.0 authorization code + PKCE
+// `login` opens the system browser at the backend's authorize page with a fresh code verifier and
+// state, and waits for the redirect on a listener at http://127.0.0.1:<port>/callback (RFC 8252
+// loopback redirect). A mockmate:// redirect would start a second process that knows nothing of the
+// pending login, so it is not offered. The code is then exchanged for tokens. The refresh token is stored in the OS credential store (see secrets), the
+// account's id, email and name in %APPDATA%\MockMate\auth.json, and the access token only in memory;
+// `access_token` refreshes it when needed, so signing in survives restarts without a session ID.
+
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::sync::oneshot;
+
+use crate::envelope::{self, Response};
+use crate::error::{AppError, ErrorCode};
+use crate::events;
+use crate::net_trace::TracedSend;
+
+const REFRESH_TOKEN_SECRET: &str = "oauth_refresh_token";
+const DEFAULT_CLIENT_ID: &str = "mockmate-desktop";
+const SCOPES: &str = "openid profile email offline_access";
+/// How long `login` waits for the browser to come back
+const LOGIN_TIMEOUT: Duration = Duration::from_secs(300);
+/// Access tokens are refreshed this long before they expire
+const EXPIRY_MARGIN: chrono::Duration = chrono::Duration::seconds(60);
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+static ACCOUNT: Lazy<Mutex<Option<Account>>> = Lazy::new(|| Mutex::new(load_account()));
+static ACCESS_TOKEN: Mutex<Option<AccessToken>> = Mutex::new(None);
+static PENDING: Mutex<Option<PendingLogin>> = Mutex::new(None);
+/// Serializes refreshes so concurrent callers don't spend the same refresh token twice
+static REFRESH_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// The signed-in account, without any token
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct Account {
+    pub user_id: Option<String>,
+    pub email: Option<String>,
+    pub name: Option<String>,
+    pub signed_in_at: DateTime<Utc>,
+}
+
+/// Result of the auth commands and payload of `auth-state`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct AuthStatus {
+    pub signed_in: bool,
+    pub account: Option<Account>,
+    /// Expiry of the access token held in memory, if any
+    pub access_token_expires_at: Option<DateTime<Utc>>,
+    /// A browser sign-in is waiting for its callback
+    pub login_pending: bool,
+}
+
+#[derive(Clone)]
+struct AccessToken {
+    token: String,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+struct PendingLogin {
+    state: String,
+    callback: oneshot::Sender<Result<String, String>>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_in: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct UserInfo {
+    #[serde(default, alias = "sub")]
+    id: Option<String>,
+    #[serde(default)]
+    email: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
+}
+
+pub fn init(app_handle: AppHandle) {
+    let _ = APP_HANDLE.set(app_handle);
+}
+
+fn backend_url() -> String {
+    std::env::var("MOCKMATE_BACKEND_URL").unwrap_or_else(|_| "https://mockmate-backend.onrender.com".to_string())
+}
+
+fn client_id() -> String {
+    std::env::var("MOCKMATE_OAUTH_CLIENT_ID").unwrap_or_else(|_| DEFAULT_CLIENT_ID.to_string())
+}
+
+fn account_path() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("auth.json")
+}
+
+fn load_account() -> Option<Account> {
+    std::fs::read(account_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+}
+
+fn save_account(account: Option<&Account>) -> Result<(), String> {
+    let path = account_path();
+    let Some(account) = account else {
+        return match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+            _ => Ok(()),
+        };
+    };
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(account).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| e.to_string())
+}
+
+fn random_token() -> String {
+    use rand::Rng;
+    let bytes: [u8; 32] = rand::thread_rng().gen();
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// S256 code challenge of a PKCE verifier
+fn code_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(ring::digest::digest(&ring::digest::SHA256, verifier.as_bytes()))
+}
+
+pub fn status() -> AuthStatus {
+    let account = ACCOUNT.lock().clone();
+    AuthStatus {
+        signed_in: account.is_some(),
+        account,
+        access_token_expires_at: ACCESS_TOKEN.lock().as_ref().and_then(|t| t.expires_at),
+        login_pending: PENDING.lock().is_some(),
+    }
+}
+
+fn emit_status() {
+    if let Some(app_handle) = APP_HANDLE.get() {
+        if let Err(e) = app_handle.emit(events::AUTH_STATE, status()) {
+            warn!("Failed to emit {}: {}", events::AUTH_STATE, e);
+        }
+    }
+}
+
+/// What a request on the loopback listener amounted to
+enum Callback {
+    /// Matched the pending login, which now has its code or error
+    Delivered { ok: bool },
+    /// Not a redirect for the pending login; the listener keeps waiting for the real one
+    Rejected,
+}
+
+/// Deliver the loopback request target of an OAuth redirect to the waiting `login`. Only a request
+/// carrying the pending login's state and a code or error ends the login
+fn handle_callback(target: &str) -> Callback {
+    let Ok(parsed) = reqwest::Url::parse(&format!("http://127.0.0.1{}", target)) else {
+        warn!("Ignoring malformed sign-in callback");
+        return Callback::Rejected;
+    };
+    let param = |name: &str| parsed.query_pairs().find(|(key, _)| key == name).map(|(_, value)| value.into_owned());
+
+    let mut pending = PENDING.lock();
+    let Some(login) = pending.as_ref() else {
+        return Callback::Rejected;
+    };
+    if param("state").as_deref() != Some(login.state.as_str()) {
+        warn!("Ignoring sign-in callback that does not match the pending login");
+        return Callback::Rejected;
+    }
+    let result = match (param("code"), param("error")) {
+        (_, Some(error)) => Err(param("error_description").unwrap_or(error)),
+        (Some(code), None) => Ok(code),
+        (None, None) => {
+            warn!("Ignoring sign-in callback without a code");
+            return Callback::Rejected;
+        }
+    };
+    let ok = result.is_ok();
+    if let Some(login) = pending.take() {
+        let _ = login.callback.send(result);
+    }
+    Callback::Delivered { ok }
+}
+
+fn page(status: &str, title: &str, text: &str) -> String {
+    let body = format!("<html><body><h3>{}</h3><p>{}</p></body></html>", title, text);
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    )
+}
+
+/// Answer requests on the loopback listener until one delivers the redirect for the pending login;
+/// `login` aborts the task when it finishes or times out
+async fn serve_callback(listener: tokio::net::TcpListener) {
+    while let Ok((mut stream, _)) = listener.accept().await {
+        let mut request = Vec::new();
+        let mut buffer = [0u8; 1024];
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < 16 * 1024 {
+            match tokio::time::timeout(Duration::from_secs(5), stream.read(&mut buffer)).await {
+                Ok(Ok(n)) if n > 0 => request.extend_from_slice(&buffer[..n]),
+                _ => break,
+            }
+        }
+        let request = String::from_utf8_lossy(&request);
+        let target = request.lines().next().and_then(|line| line.split_whitespace().nth(1)).unwrap_or("/");
+        // Browsers also ask for /favicon.ico
+        if !target.starts_with("/callback") {
+            let _ = stream.write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n").await;
+            continue;
+        }
+        let (response, done) = match handle_callback(target) {
+            Callback::Delivered { ok: true } => (
+                page("200 OK", "MockMate sign-in complete", "You can close this tab and return to the app."),
+                true,
+            ),
+            Callback::Delivered { ok: false } => (
+                page("200 OK", "MockMate sign-in failed", "Return to the app and try signing in again."),
+                true,
+            ),
+            Callback::Rejected => (
+                page("400 Bad Request", "Invalid sign-in link", "This link does not belong to the sign-in MockMate is waiting for."),
+                false,
+            ),
+        };
+        let _ = stream.write_all(response.as_bytes()).await;
+        if done {
+            return;
+        }
+    }
+}
+
+async fn request_tokens(form: &[(&str, &str)]) -> Result<TokenResponse, AppError> {
+    let response = reqwest::Client::new()
+        .post(format!("{}/oauth/token", backend_url()))
+        .form(form)
+        .send_traced()
+        .await
+        .map_err(|e| e.context("Failed to reach the sign-in server"))?;
+    let status = response.status();
+    if status == reqwest::StatusCode::BAD_REQUEST || status == reqwest::StatusCode::UNAUTHORIZED {
+        let detail = response.text().await.unwrap_or_default();
+        return Err(AppError::new(ErrorCode::Unauthorized, format!("Sign-in was rejected: {}", detail)));
+    }
+    if !status.is_success() {
+        return Err(AppError::new(ErrorCode::from_status(status), format!("Sign-in server error: {}", status)));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| AppError::new(ErrorCode::Internal, format!("Failed to parse token response: {}", e)))
+}
+
+/// Keep the access token in memory and rotate the stored refresh token when a new one was issued
+fn store_tokens(tokens: &TokenResponse) -> Result<(), String> {
+    if let Some(refresh_token) = &tokens.refresh_token {
+        crate::secrets::set_secret(REFRESH_TOKEN_SECRET, refresh_token)?;
+    }
+    *ACCESS_TOKEN.lock() = Some(AccessToken {
+        token: tokens.access_token.clone(),
+        expires_at: tokens.expires_in.map(|secs| Utc::now() + chrono::Duration::seconds(secs)),
+    });
+    Ok(())
+}
+
+async fn fetch_user(access_token: &str) -> Option<UserInfo> {
+    let response = reqwest::Client::new()
+        .get(format!("{}/api/auth/me", backend_url()))
+        .bearer_auth(access_token)
+        .send_traced()
+        .await
+        .ok()?;
+    if !response.status().is_success() {
+        warn!("Failed to load the signed-in account: {}", response.status());
+        return None;
+    }
+    response.json().await.ok()
+}
+
+/// A valid access token for backend calls, refreshed with the stored refresh token when needed
+pub async fn access_token() -> Result<String, AppError> {
+    let _guard = REFRESH_LOCK.lock().await;
+    let current = ACCESS_TOKEN.lock().clone();
+    if let Some(current) = current {
+        if current.expires_at.is_none_or(|at| at - EXPIRY_MARGIN > Utc::now()) {
+            return Ok(current.token);
+        }
+    }
+
+    let refresh_token = crate::secrets::get_secret(REFRESH_TOKEN_SECRET)?
+        .ok_or_else(|| AppError::new(ErrorCode::Unauthorized, "Not signed in"))?;
+    let tokens = match request_tokens(&[
+        ("grant_type", "refresh_token"),
+        ("refresh_token", &refresh_token),
+        ("client_id", &client_id()),
+    ])
+    .await
+    {
+        Ok(tokens) => tokens,
+        Err(e) if e.code == ErrorCode::Unauthorized => {
+            // The refresh token was revoked or expired; signing in again is the only way back
+            warn!("🔑 Refresh token rejected; signing out");
+            sign_out_locally();
+            return Err(e);
+        }
+        Err(e) => return Err(e),
+    };
+    store_tokens(&tokens)?;
+    info!("🔑 Refreshed access token");
+    Ok(tokens.access_token)
+}
+
+fn sign_out_locally() {
+    if let Err(e) = crate::secrets::delete_secret(REFRESH_TOKEN_SECRET) {
+        warn!("Failed to delete refresh token: {}", e);
+    }
+    *ACCESS_TOKEN.lock() = None;
+    *ACCOUNT.lock() = None;
+    if let Err(e) = save_account(None) {
+        warn!("Failed to remove signed-in account: {}", e);
+    }
+    emit_status();
+}
+
+/// Sign in through the system browser. Resolves once the browser comes back, or fails after five
+/// minutes
+#[tauri::command]
+#[specta::specta]
+pub async fn login() -> Response<AuthStatus> {
+    envelope::respond("login", async move {
+        crate::privacy::ensure_cloud_allowed("Sign-in")?;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|e| format!("Failed to open the sign-in callback listener: {}", e))?;
+        let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+        let redirect_uri = format!("http://127.0.0.1:{}/callback", port);
+
+        let verifier = random_token();
+        let state = random_token();
+        let (callback, code) = oneshot::channel();
+        // A newer login replaces one still waiting; its caller gets "cancelled"
+        *PENDING.lock() = Some(PendingLogin { state: state.clone(), callback });
+
+        let mut authorize = reqwest::Url::parse(&format!("{}/oauth/authorize", backend_url())).map_err(|e| e.to_string())?;
+        authorize
+            .query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &client_id())
+            .append_pair("redirect_uri", &redirect_uri)
+            .append_pair("scope", SCOPES)
+            .append_pair("state", &state)
+            .append_pair("code_challenge", &code_challenge(&verifier))
+            .append_pair("code_challenge_method", "S256");
+        let server = tauri::async_runtime::spawn(serve_callback(listener));
+        if let Err(e) = tauri_plugin_opener::open_url(authorize.as_str(), None::<&str>) {
+            PENDING.lock().take();
+            server.abort();
+            return Err(format!("Failed to open browser: {}", e).into());
+        }
+        info!("🔑 Waiting for browser sign-in");
+        emit_status();
+
+        let outcome = tokio::time::timeout(LOGIN_TIMEOUT, code).await;
+        server.abort();
+        {
+            let mut pending = PENDING.lock();
+            if pending.as_ref().is_some_and(|p| p.state == state) {
+                pending.take();
+            }
+        }
+        emit_status();
+        let code = match outcome {
+            Ok(Ok(Ok(code))) => code,
+            Ok(Ok(Err(e))) => return Err(AppError::new(ErrorCode::Unauthorized, format!("Sign-in failed: {}", e))),
+            Ok(Err(_)) => return Err(AppError::new(ErrorCode::Cancelled, "Sign-in was replaced by a newer one")),
+            Err(_) => return Err(AppError::new(ErrorCode::Timeout, "Sign-in was not completed in the browser")),
+        };
+
+        let tokens = request_tokens(&[
+            ("grant_type", "authorization_code"),
+            ("code", &code),
+            ("redirect_uri", &redirect_uri),
+            ("client_id", &client_id()),
+            ("code_verifier", &verifier),
+        ])
+        .await?;
+        if tokens.refresh_token.is_none() {
+            warn!("🔑 Sign-in returned no refresh token; it will not survive a restart");
+        }
+        store_tokens(&tokens)?;
+
+        let user = fetch_user(&tokens.access_token).await;
+        let account = Account {
+            user_id: user.as_ref().and_then(|u| u.id.clone()),
+            email: user.as_ref().and_then(|u| u.email.clone()),
+            name: user.and_then(|u| u.name),
+            signed_in_at: Utc::now(),
+        };
+        save_account(Some(&account))?;
+        crate::accounts::on_signed_in(account.user_id.as_deref(), account.email.as_deref());
+        info!("🔑 Signed in as {}", account.email.as_deref().unwrap_or("unknown account"));
+        *ACCOUNT.lock() = Some(account);
+        emit_status();
+        Ok(status())
+    })
+    .await
+}
+
+/// Sign out: revoke the refresh token (best effort) and forget every token and the account
+#[tauri::command]
+#[specta::specta]
+pub async fn logout() -> Response<AuthStatus> {
+    envelope::respond("logout", async move {
+        PENDING.lock().take();
+        if let Ok(Some(refresh_token)) = crate::secrets::get_secret(REFRESH_TOKEN_SECRET) {
+            let revoked = reqwest::Client::new()
+                .post(format!("{}/oauth/revoke", backend_url()))
+                .form(&[("token", refresh_token.as_str()), ("token_type_hint", "refresh_token"), ("client_id", &client_id())])
+                .send_traced()
+                .await;
+            if let Err(e) = revoked {
+                warn!("Failed to revoke refresh token: {}", e);
+            }
+        }
+        sign_out_locally();
+        info!("🔑 Signed out");
+        Ok(status())
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_auth_status() -> Response<AuthStatus> {
+    envelope::respond("get_auth_status", async move {
+        Ok(status())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn code_challenge_matches_rfc7636_example() {
+        // RFC 7636 Appendix B
+        assert_eq!(
+            code_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+}
@@ -0,0 +1,238 @@
+// Automatic screen analysis on content change
+// Real-time monitoring already reads the text of the current target window every second. When a
+// large block of new text shows up there (a coding question pasted into CoderPad, a prompt shared
+// in the meeting chat), this runs the same accessibility analysis pipeline as the analyze hotkey
+// on the new text, so I do not have to press anything. The first read of a window only records a
+// baseline, a cooldown separates two runs and only one runs at a time. The AI provider and model
+// are the ones the frontend passes when turning it on. Off by default; settings are in
+// %APPDATA%\MockMate\auto_analysis.json.
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::accessibility_reader::AccessibilityTextResult;
+use crate::cancellation;
+use crate::envelope::{self, IntoResult, Response};
+use crate::error::{AppError, ErrorCode};
+use crate::events;
+
+const DEFAULT_COOLDOWN_SECS: u64 = 45;
+const DEFAULT_MIN_NEW_CHARS: usize = 200;
+/// Windows remembered for comparison; the oldest are dropped beyond this
+const MAX_TRACKED_WINDOWS: usize = 32;
+
+static SETTINGS: Lazy<Mutex<AutoAnalysisSettings>> = Lazy::new(|| Mutex::new(load()));
+/// Last text read per "app-window title"
+static SNAPSHOTS: Lazy<Mutex<HashMap<String, (Instant, String)>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+static LAST_RUN: Lazy<Mutex<Option<Instant>>> = Lazy::new(|| Mutex::new(None));
+static RUNNING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AutoAnalysisSettings {
+    pub enabled: bool,
+    /// Minimum seconds between two automatic runs
+    pub cooldown_secs: u64,
+    /// Characters of new text that count as a significant change
+    pub min_new_chars: usize,
+    pub provider: String,
+    pub model: String,
+}
+
+impl Default for AutoAnalysisSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            cooldown_secs: DEFAULT_COOLDOWN_SECS,
+            min_new_chars: DEFAULT_MIN_NEW_CHARS,
+            provider: "pollinations".to_string(),
+            model: "roblox-rp".to_string(),
+        }
+    }
+}
+
+/// Payload of `auto-analysis-triggered`
+#[derive(Debug, Clone, Serialize, JsonSchema, Type)]
+pub struct AutoAnalysisTriggered {
+    /// Progress and `cancel_request` use this ID
+    pub request_id: String,
+    pub source_app: String,
+    pub window_title: String,
+    pub new_chars: usize,
+    /// First characters of the new text
+    pub preview: String,
+}
+
+fn settings_path() -> PathBuf {
+    crate::portable::data_root().join("MockMate").join("auto_analysis.json")
+}
+
+fn load() -> AutoAnalysisSettings {
+    std::fs::read(settings_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn save(settings: &AutoAnalysisSettings) -> Result<(), String> {
+    let path = settings_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(settings).map_err(|e| e.to_string())?;
+    std::fs::write(path, body).map_err(|e| e.to_string())
+}
+
+/// Lines of `current` that were not in `previous`
+fn new_block(previous: &str, current: &str) -> String {
+    let seen: HashSet<&str> = previous.lines().map(str::trim).collect();
+    current
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !seen.contains(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Called by real-time monitoring for every window read; starts an analysis when enough new text appeared
+pub fn observe(app_handle: &AppHandle, result: &AccessibilityTextResult) {
+    let settings = SETTINGS.lock().clone();
+    if !settings.enabled {
+        return;
+    }
+    let key = format!("{}-{}", result.source_app, result.window_title);
+    let previous = {
+        let mut snapshots = SNAPSHOTS.lock();
+        let previous = snapshots.insert(key, (Instant::now(), result.text.clone())).map(|(_, text)| text);
+        if snapshots.len() > MAX_TRACKED_WINDOWS {
+            if let Some(oldest) = snapshots.iter().min_by_key(|(_, (seen, _))| *seen).map(|(key, _)| key.clone()) {
+                snapshots.remove(&oldest);
+            }
+        }
+        previous
+    };
+    // First read of this window: only a baseline
+    let Some(previous) = previous else { return };
+    if previous == result.text {
+        return;
+    }
+
+    let block = new_block(&previous, &result.text);
+    let new_chars = block.chars().count();
+    if new_chars < settings.min_new_chars {
+        return;
+    }
+    {
+        let mut last_run = LAST_RUN.lock();
+        if last_run.is_some_and(|at| at.elapsed() < Duration::from_secs(settings.cooldown_secs)) {
+            return;
+        }
+        if RUNNING.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        *last_run = Some(Instant::now());
+    }
+
+    let request_id = uuid::Uuid::new_v4().to_string();
+    info!("🔎 {} new characters in {}, analyzing automatically", new_chars, result.source_app);
+    let triggered = AutoAnalysisTriggered {
+        request_id: request_id.clone(),
+        source_app: result.source_app.clone(),
+        window_title: result.window_title.clone(),
+        new_chars,
+        preview: block.chars().take(200).collect(),
+    };
+    if let Err(e) = app_handle.emit(events::AUTO_ANALYSIS_TRIGGERED, &triggered) {
+        warn!("Failed to emit {}: {}", events::AUTO_ANALYSIS_TRIGGERED, e);
+    }
+
+    let app_handle = app_handle.clone();
+    let source = format!("Windows Accessibility API from {} (new content)", result.source_app);
+    tauri::async_runtime::spawn(async move {
+        let response = cancellation::run_with_progress("auto_analysis", Some(request_id), |progress| async move {
+            if let Err(e) = crate::show_ai_response_window(app_handle.clone()).into_result() {
+                warn!("Failed to show AI response window: {}", e);
+            }
+            progress.report("generating", Some(20.0));
+            let payload = crate::AnalyzeScreenWithAiPayload {
+                model: settings.model,
+                provider: settings.provider,
+                company: None,
+                position: None,
+                job_description: None,
+                system_prompt: None,
+            };
+            let state = app_handle.state::<crate::AppState>();
+            crate::generate_ai_analysis_from_text(&block, &source, payload, state, app_handle.clone()).await?;
+            Ok(())
+        })
+        .await;
+        if let Err(e) = response.into_result() {
+            warn!("Automatic analysis failed: {}", e);
+        }
+        RUNNING.store(false, Ordering::SeqCst);
+    });
+}
+
+/// Automatic analysis settings
+#[tauri::command]
+#[specta::specta]
+pub fn get_auto_analysis() -> Response<AutoAnalysisSettings> {
+    envelope::respond_sync("get_auto_analysis", || Ok(SETTINGS.lock().clone()))
+}
+
+/// Turn automatic analysis on or off; pass the provider and model the analysis should use
+#[tauri::command]
+#[specta::specta]
+pub fn set_auto_analysis(
+    enabled: bool,
+    cooldown_secs: Option<u64>,
+    min_new_chars: Option<usize>,
+    provider: Option<String>,
+    model: Option<String>,
+) -> Response<AutoAnalysisSettings> {
+    envelope::respond_sync("set_auto_analysis", || {
+        if cooldown_secs.is_some_and(|secs| secs < 5) {
+            return Err(AppError::new(ErrorCode::InvalidInput, "Cooldown must be at least 5 seconds"));
+        }
+        if min_new_chars.is_some_and(|chars| chars < 20) {
+            return Err(AppError::new(ErrorCode::InvalidInput, "Minimum new text must be at least 20 characters"));
+        }
+        let mut settings = SETTINGS.lock();
+        settings.enabled = enabled;
+        if let Some(cooldown_secs) = cooldown_secs {
+            settings.cooldown_secs = cooldown_secs;
+        }
+        if let Some(min_new_chars) = min_new_chars {
+            settings.min_new_chars = min_new_chars;
+        }
+        if let Some(provider) = provider.filter(|provider| !provider.trim().is_empty()) {
+            settings.provider = provider;
+        }
+        if let Some(model) = model.filter(|model| !model.trim().is_empty()) {
+            settings.model = model;
+        }
+        save(&settings)?;
+        if !enabled {
+            SNAPSHOTS.lock().clear();
+        }
+        info!(
+            "🔎 Automatic analysis {} ({}s cooldown, {} new characters, {} {})",
+            if enabled { "enabled" } else { "disabled" },
+            settings.cooldown_secs,
+            settings.min_new_chars,
+            settings.provider,
+            settings.model
+        );
+        Ok(settings.clone())
+    })
+}
@@ -7,7 +7,7 @@ use tokio::time::timeout;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    env_logger::init();
+    tracing_subscriber::fmt::init();
     
     info!("🧪 Testing all Pollinations models to identify working ones...");
     
@@ -36,6 +36,9 @@ async fn main() -> Result<()> {
         resume_content: None,
         user_experience_level: Some("mid-level".to_string()),
         interview_style: Some("technical".to_string()),
+        recent_transcript: None,
+        prior_qa: None,
+        custom_system_prompt: None,
     };
     
     let test_question = "What is your greatest strength?";
@@ -0,0 +1,78 @@
+// TypeScript bindings for the command surface
+// tauri-specta reads the argument and return types of every command registered in `commands()`
+// (lib.rs) and writes `src/bindings.ts` with a typed wrapper per command. Commands resolve with
+// their `Envelope` and reject with the failed one, so the wrappers throw; `src/lib/ipc.ts` turns
+// either side into data or a `CommandError`. Event names and payload types from `events` are
+// appended as `EVENTS` and `EventPayloads`.
+//
+// The file is committed. After changing a command or event, regenerate it with
+// `cargo run -- --headless bindings` in src-tauri; `committed_bindings_are_current` fails until then.
+
+use std::path::Path;
+
+use specta_typescript::{BigIntExportBehavior, ExportError, Typescript};
+use tauri::Wry;
+use tauri_specta::{Builder, Commands, ErrorHandlingMode, ExportContext, LanguageExt};
+
+use crate::events;
+
+/// Where the frontend imports the bindings from, relative to `src-tauri`
+pub const DEFAULT_PATH: &str = "../src/bindings.ts";
+
+pub fn builder(commands: Commands<Wry>) -> Builder<Wry> {
+    Builder::<Wry>::new()
+        .commands(commands)
+        .error_handling(ErrorHandlingMode::Throw)
+}
+
+/// The TypeScript exporter plus the event map
+struct WithEvents(Typescript);
+
+impl LanguageExt for WithEvents {
+    type Error = ExportError;
+
+    fn render(&self, cfg: &ExportContext) -> Result<String, ExportError> {
+        let mut cfg = cfg.clone();
+        // Payload types only used by events have to be in the type map before the types render
+        let payloads = events::payload_types(&mut cfg.type_map);
+        let mut bindings = self.0.render(&cfg)?;
+        bindings.push_str(&events::typescript(&self.0, &payloads, &cfg.type_map)?);
+        Ok(bindings)
+    }
+
+    fn format(&self, path: &Path) -> Result<(), ExportError> {
+        LanguageExt::format(&self.0, path)
+    }
+}
+
+pub fn export(builder: &Builder<Wry>, path: &Path) -> Result<(), String> {
+    // Timestamps and byte counts are i64/u64 but stay well inside the safe integer range
+    let typescript = Typescript::default()
+        .bigint(BigIntExportBehavior::Number)
+        // The generated helpers trip the frontend's unused-code checks
+        .header("// @ts-nocheck");
+    builder
+        .export(WithEvents(typescript), path)
+        .map_err(|e| format!("Failed to export bindings to {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// CI runs this; `cargo run -- --headless bindings` in src-tauri regenerates the file
+    #[test]
+    fn committed_bindings_are_current() {
+        let out = std::env::temp_dir().join(format!("mockmate-bindings-{}.ts", std::process::id()));
+        crate::export_bindings(&out).unwrap();
+        let generated = std::fs::read_to_string(&out).unwrap();
+        let _ = std::fs::remove_file(&out);
+        let committed = std::fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join(DEFAULT_PATH))
+            .unwrap_or_default()
+            .replace("\r\n", "\n");
+        assert!(
+            generated == committed,
+            "src/bindings.ts is out of date; run `cargo run -- --headless bindings` in src-tauri and commit it"
+        );
+    }
+}
@@ -0,0 +1,451 @@
+// Calendar integration for upcoming interviews
+// Google and Outlook accounts are linked through the backend, which owns the OAuth flow and tokens;
+// the desktop app only opens the consent page and polls the backend for the next day's events.
+// Events with a meeting link get a session pre-created with the company and position parsed from
+// the invite, and `interview-upcoming` fires shortly before each one starts. Optional and off by
+// default.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter};
+
+use crate::net_trace::TracedSend;
+use crate::envelope::{self, Response};
+use crate::error::{AppError, ErrorCode};
+use crate::events;
+
+/// How far ahead events are fetched
+const LOOKAHEAD_HOURS: i64 = 24;
+/// Backend poll interval; the reminder check runs every tick
+const SYNC_INTERVAL: Duration = Duration::from_secs(5 * 60);
+const TICK_INTERVAL: Duration = Duration::from_secs(30);
+
+static SETTINGS: Lazy<Mutex<CalendarSettings>> = Lazy::new(|| Mutex::new(load_settings()));
+/// Backend bearer token for the signed-in user; kept in memory only
+static TOKEN: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+/// Interviews found in the calendar, by event id
+static UPCOMING: Lazy<Mutex<HashMap<String, UpcomingInterview>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+static MEETING_LINK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"https://(?:[\w-]+\.)?(?:zoom\.us/(?:j|my|w)/\S+|teams\.microsoft\.com/l/meetup-join/\S+|teams\.live\.com/meet/\S+|meet\.google\.com/[a-z]{3}-[a-z]{4}-[a-z]{3}|[\w-]+\.webex\.com/\S+)",
+    )
+    .expect("valid meeting link pattern")
+});
+static INTERVIEW_WORDS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b(interview|screen(?:ing)?|onsite|technical round|hiring)\b").expect("valid pattern"));
+/// "Interview with Acme for Senior Engineer", "Acme - Senior Engineer Interview", "Senior Engineer @ Acme"
+static WITH_FOR: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)interview\s+with\s+(.+?)\s+(?:for|-|–|\|)\s+(?:the\s+)?(.+?)(?:\s+(?:role|position))?$").expect("valid pattern"));
+static SEPARATED: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(.+?)\s+(?:-|–|\||:)\s+(.+)$").expect("valid pattern"));
+static AT: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(.+?)\s+(?:@|at)\s+(.+)$").expect("valid pattern"));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum CalendarProvider {
+    Google,
+    Outlook,
+}
+
+impl CalendarProvider {
+    fn as_str(self) -> &'static str {
+        match self {
+            CalendarProvider::Google => "google",
+            CalendarProvider::Outlook => "outlook",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CalendarSettings {
+    pub enabled: bool,
+    pub providers: Vec<CalendarProvider>,
+    /// Minutes before start that `interview-upcoming` fires
+    pub reminder_minutes: u32,
+    /// Pre-create a backend session for each detected interview
+    pub auto_create_sessions: bool,
+    /// Also treat events with a meeting link but no interview wording as interviews
+    pub include_all_meetings: bool,
+}
+
+impl Default for CalendarSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            providers: Vec::new(),
+            reminder_minutes: 10,
+            auto_create_sessions: true,
+            include_all_meetings: false,
+        }
+    }
+}
+
+/// Event as returned by the backend calendar proxy
+#[derive(Debug, Clone, Deserialize)]
+struct CalendarEvent {
+    id: String,
+    provider: CalendarProvider,
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    location: Option<String>,
+    start: DateTime<Utc>,
+    end: Option<DateTime<Utc>>,
+    #[serde(default)]
+    organizer: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct UpcomingInterview {
+    pub event_id: String,
+    pub provider: CalendarProvider,
+    pub title: String,
+    pub company: Option<String>,
+    pub position: Option<String>,
+    pub meeting_url: String,
+    pub start: DateTime<Utc>,
+    pub end: Option<DateTime<Utc>>,
+    pub organizer: Option<String>,
+    pub session_id: Option<String>,
+    pub notified: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CalendarConnection {
+    pub provider: CalendarProvider,
+    pub auth_url: String,
+}
+
+fn settings_path() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("calendar.json")
+}
+
+fn load_settings() -> CalendarSettings {
+    std::fs::read(settings_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn save_settings(settings: &CalendarSettings) -> Result<(), String> {
+    let path = settings_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(settings).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| e.to_string())
+}
+
+fn backend_url() -> String {
+    std::env::var("MOCKMATE_BACKEND_URL").unwrap_or_else(|_| "https://mockmate-backend.onrender.com".to_string())
+}
+
+fn token() -> Result<String, String> {
+    TOKEN.lock().clone().ok_or_else(|| "Sign in to use calendar integration".to_string())
+}
+
+fn clean(part: &str) -> Option<String> {
+    let part = INTERVIEW_WORDS.replace_all(part, "");
+    let part = part.trim_matches(|c: char| c.is_whitespace() || "-–|:()[]".contains(c));
+    (!part.is_empty()).then(|| part.to_string())
+}
+
+/// Best-effort (company, position) from an invite title
+fn parse_title(title: &str) -> (Option<String>, Option<String>) {
+    let title = title.trim();
+    if let Some(caps) = WITH_FOR.captures(title) {
+        return (clean(&caps[1]), clean(&caps[2]));
+    }
+    if let Some(caps) = AT.captures(title) {
+        return (clean(&caps[2]), clean(&caps[1]));
+    }
+    if let Some(caps) = SEPARATED.captures(title) {
+        // "Acme - Senior Engineer Interview": the side with interview wording is usually the role
+        let (left, right) = (&caps[1], &caps[2]);
+        return if INTERVIEW_WORDS.is_match(left) && !INTERVIEW_WORDS.is_match(right) {
+            (clean(right), clean(left))
+        } else {
+            (clean(left), clean(right))
+        };
+    }
+    (None, clean(title))
+}
+
+fn meeting_url(event: &CalendarEvent) -> Option<String> {
+    [event.location.as_deref(), event.description.as_deref()]
+        .into_iter()
+        .flatten()
+        .find_map(|text| MEETING_LINK.find(text))
+        .map(|m| m.as_str().trim_end_matches(['>', ')', '"', '\'']).to_string())
+}
+
+async fn fetch_events(providers: &[CalendarProvider]) -> Result<Vec<CalendarEvent>, String> {
+    crate::privacy::ensure_cloud_allowed("Calendar sync")?;
+    let token = token()?;
+    let now = Utc::now();
+    let providers = providers.iter().map(|p| p.as_str()).collect::<Vec<_>>().join(",");
+    let response = reqwest::Client::new()
+        .get(format!("{}/api/calendar/events", backend_url()))
+        .bearer_auth(token)
+        .query(&[
+            ("from", now.to_rfc3339()),
+            ("to", (now + chrono::Duration::hours(LOOKAHEAD_HOURS)).to_rfc3339()),
+            ("providers", providers),
+        ])
+        .send_traced()
+        .await
+        .map_err(|e| format!("Failed to fetch calendar events: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Calendar events request failed: {}", response.status()));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse calendar events: {}", e))
+}
+
+/// Create a backend session for a detected interview and return its id
+async fn create_session(interview: &UpcomingInterview) -> Result<String, String> {
+    let token = token()?;
+    let response = reqwest::Client::new()
+        .post(format!("{}/api/sessions", backend_url()))
+        .bearer_auth(token)
+        .json(&serde_json::json!({
+            "job_title": interview.position.clone().unwrap_or_else(|| interview.title.clone()),
+            "company_name": interview.company,
+            "scheduled_at": interview.start.to_rfc3339(),
+            "source": "calendar",
+            "calendar_event_id": interview.event_id,
+            "calendar_provider": interview.provider.as_str(),
+            "meeting_url": interview.meeting_url,
+        }))
+        .send_traced()
+        .await
+        .map_err(|e| format!("Failed to create session: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Session creation failed: {}", response.status()));
+    }
+    let body: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    let id = [&body["session"]["id"], &body["session_id"], &body["id"]]
+        .into_iter()
+        .find_map(|id| id.as_str().map(str::to_string));
+    id.ok_or_else(|| "Session creation response did not include an id".to_string())
+}
+
+/// Fetch events, record new interviews and pre-create their sessions
+async fn sync(settings: &CalendarSettings) -> Result<(), String> {
+    let events = fetch_events(&settings.providers).await?;
+    let now = Utc::now();
+
+    let mut new = Vec::new();
+    {
+        let mut upcoming = UPCOMING.lock();
+        upcoming.retain(|_, interview| interview.end.unwrap_or(interview.start) > now);
+        for event in events {
+            let Some(meeting_url) = meeting_url(&event) else { continue };
+            let text = format!("{} {}", event.title, event.description.as_deref().unwrap_or_default());
+            if !settings.include_all_meetings && !INTERVIEW_WORDS.is_match(&text) {
+                continue;
+            }
+            if let Some(existing) = upcoming.get_mut(&event.id) {
+                // Rescheduled events keep their session but get a fresh reminder
+                if existing.start != event.start {
+                    existing.start = event.start;
+                    existing.end = event.end;
+                    existing.notified = false;
+                }
+                continue;
+            }
+            let (company, position) = parse_title(&event.title);
+            let interview = UpcomingInterview {
+                event_id: event.id.clone(),
+                provider: event.provider,
+                title: event.title,
+                company,
+                position,
+                meeting_url,
+                start: event.start,
+                end: event.end,
+                organizer: event.organizer,
+                session_id: None,
+                notified: false,
+            };
+            info!(
+                "📅 Detected interview '{}' at {} ({:?} / {:?})",
+                interview.title, interview.start, interview.company, interview.position
+            );
+            upcoming.insert(event.id, interview.clone());
+            new.push(interview);
+        }
+    }
+
+    if settings.auto_create_sessions {
+        for interview in new {
+            match create_session(&interview).await {
+                Ok(session_id) => {
+                    if let Some(entry) = UPCOMING.lock().get_mut(&interview.event_id) {
+                        entry.session_id = Some(session_id);
+                    }
+                }
+                Err(e) => warn!("Could not pre-create a session for '{}': {}", interview.title, e),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Emit `interview-upcoming` once for each interview entering the reminder window
+fn notify_due(app_handle: &AppHandle, reminder_minutes: u32) {
+    let now = Utc::now();
+    let window = chrono::Duration::minutes(i64::from(reminder_minutes));
+    let mut upcoming = UPCOMING.lock();
+    for interview in upcoming.values_mut() {
+        if !interview.notified && interview.start > now && interview.start - now <= window {
+            interview.notified = true;
+            info!("⏰ Interview '{}' starts at {}", interview.title, interview.start);
+            let _ = app_handle.emit(events::INTERVIEW_UPCOMING, &*interview);
+        }
+    }
+}
+
+pub fn start_background_sync(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut last_sync: Option<std::time::Instant> = None;
+        loop {
+            let settings = SETTINGS.lock().clone();
+            let signed_in = TOKEN.lock().is_some();
+            if settings.enabled && signed_in && !settings.providers.is_empty() && !crate::privacy::is_local_only() {
+                if last_sync.is_none_or(|at| at.elapsed() >= SYNC_INTERVAL) {
+                    if let Err(e) = sync(&settings).await {
+                        warn!("Calendar sync failed: {}", e);
+                    }
+                    last_sync = Some(std::time::Instant::now());
+                }
+                notify_due(&app_handle, settings.reminder_minutes);
+            }
+            tokio::time::sleep(TICK_INTERVAL).await;
+        }
+    });
+}
+
+/// Provide (or clear, on sign-out) the backend token used for calendar requests
+#[tauri::command]
+#[specta::specta]
+pub async fn set_calendar_token(token: Option<String>) -> Response<()> {
+    envelope::respond("set_calendar_token", async move {
+        if token.is_none() {
+            UPCOMING.lock().clear();
+        }
+        *TOKEN.lock() = token.filter(|t| !t.is_empty());
+        Ok(())
+    })
+    .await
+}
+
+/// Start linking a calendar: the backend returns its OAuth consent URL, which opens in the browser
+#[tauri::command]
+#[specta::specta]
+pub async fn connect_calendar(provider: CalendarProvider, token: String) -> Response<CalendarConnection> {
+    envelope::respond("connect_calendar", async move {
+        crate::privacy::ensure_cloud_allowed("Calendar")?;
+        *TOKEN.lock() = Some(token.clone());
+        let response = reqwest::Client::new()
+            .post(format!("{}/api/calendar/{}/connect", backend_url(), provider.as_str()))
+            .bearer_auth(token)
+            .send_traced()
+            .await
+            .map_err(|e| e.context("Failed to start calendar connection"))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(AppError::new(ErrorCode::from_status(status), format!("Calendar connection failed: {}", status)));
+        }
+        let body: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+        let auth_url = body["auth_url"]
+            .as_str()
+            .ok_or("Calendar connection response did not include an auth_url")?
+            .to_string();
+        tauri_plugin_opener::open_url(&auth_url, None::<&str>).map_err(|e| format!("Failed to open browser: {}", e))?;
+
+        let mut settings = SETTINGS.lock();
+        if !settings.providers.contains(&provider) {
+            settings.providers.push(provider);
+        }
+        settings.enabled = true;
+        save_settings(&settings)?;
+        info!("📅 Linking {} calendar", provider.as_str());
+        Ok(CalendarConnection { provider, auth_url })
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn disconnect_calendar(provider: CalendarProvider) -> Response<CalendarSettings> {
+    envelope::respond("disconnect_calendar", async move {
+        if let Ok(token) = token() {
+            let result = reqwest::Client::new()
+                .delete(format!("{}/api/calendar/{}", backend_url(), provider.as_str()))
+                .bearer_auth(token)
+                .send_traced()
+                .await;
+            if let Err(e) = result {
+                warn!("Failed to unlink {} calendar on the backend: {}", provider.as_str(), e);
+            }
+        }
+        UPCOMING.lock().retain(|_, interview| interview.provider != provider);
+        let mut settings = SETTINGS.lock();
+        settings.providers.retain(|p| *p != provider);
+        save_settings(&settings)?;
+        Ok(settings.clone())
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_calendar_settings() -> Response<CalendarSettings> {
+    envelope::respond("get_calendar_settings", async move {
+        Ok(SETTINGS.lock().clone())
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_calendar_settings(settings: CalendarSettings) -> Response<CalendarSettings> {
+    envelope::respond("set_calendar_settings", async move {
+        save_settings(&settings)?;
+        *SETTINGS.lock() = settings.clone();
+        Ok(settings)
+    })
+    .await
+}
+
+/// Interviews detected in the linked calendars, soonest first; `refresh` syncs right away
+#[tauri::command]
+#[specta::specta]
+pub async fn get_upcoming_interviews(refresh: Option<bool>) -> Response<Vec<UpcomingInterview>> {
+    envelope::respond("get_upcoming_interviews", async move {
+        if refresh.unwrap_or(false) {
+            let settings = SETTINGS.lock().clone();
+            sync(&settings).await?;
+        }
+        let mut interviews: Vec<UpcomingInterview> = UPCOMING.lock().values().cloned().collect();
+        interviews.sort_by_key(|i| i.start);
+        Ok(interviews)
+    })
+    .await
+}
@@ -0,0 +1,139 @@
+// Cancellation and progress for long-running commands
+// Long-running commands take an optional `request_id` from the caller (a fresh one is made when it
+// is missing) and run through `run` or `run_with_progress`. While they run, `cancel_request(id)`
+// stops them at their next await point and they fail with `ErrorCode::Cancelled`. Each one reports
+// `progress` events under its request ID: "started", any stages the command reports itself, and
+// finally "completed", "failed" or "cancelled". The envelope carries the same request ID.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::OnceLock;
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::watch;
+
+use crate::envelope::{self, Response};
+use crate::error::{AppError, ErrorCode};
+use crate::events;
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+/// Cancel switch of every running request, by request ID
+static RUNNING: Lazy<Mutex<HashMap<String, watch::Sender<bool>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Unregisters a request when its command finishes or its future is dropped by a caller
+struct Registration(String);
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        RUNNING.lock().remove(&self.0);
+    }
+}
+
+pub fn init(app_handle: AppHandle) {
+    let _ = APP_HANDLE.set(app_handle);
+}
+
+/// Handle a long-running command uses to report stages of its work
+#[derive(Clone)]
+pub struct Progress {
+    command: &'static str,
+    request_id: String,
+    cancelled: watch::Receiver<bool>,
+}
+
+impl Progress {
+    /// Report a stage; `percent` is 0-100 when the command can estimate it
+    pub fn report(&self, stage: &str, percent: Option<f32>) {
+        emit(self.command, &self.request_id, stage, percent, false);
+    }
+
+    /// For synchronous stretches of work that cannot be interrupted at an await point
+    pub fn is_cancelled(&self) -> bool {
+        *self.cancelled.borrow()
+    }
+}
+
+fn emit(command: &'static str, request_id: &str, stage: &str, percent: Option<f32>, done: bool) {
+    let Some(app_handle) = APP_HANDLE.get() else {
+        return;
+    };
+    let progress = events::RequestProgress {
+        request_id: request_id.to_string(),
+        command: command.to_string(),
+        stage: stage.to_string(),
+        percent,
+        done,
+    };
+    if let Err(e) = app_handle.emit(events::PROGRESS, &progress) {
+        warn!("Failed to emit progress for {}: {}", command, e);
+    }
+}
+
+/// Run a long-running command body that can be cancelled, reporting only start and finish
+pub async fn run<T, F>(command: &'static str, request_id: Option<String>, task: F) -> Response<T>
+where
+    F: Future<Output = Result<T, AppError>>,
+{
+    run_with_progress(command, request_id, |_| task).await
+}
+
+/// Run a long-running command body that can be cancelled and reports its own stages
+pub async fn run_with_progress<T, F, Fut>(command: &'static str, request_id: Option<String>, task: F) -> Response<T>
+where
+    F: FnOnce(Progress) -> Fut,
+    Fut: Future<Output = Result<T, AppError>>,
+{
+    let request_id = request_id
+        .filter(|id| !id.trim().is_empty())
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let (cancel, mut cancelled) = watch::channel(false);
+    RUNNING.lock().insert(request_id.clone(), cancel);
+    let registration = Registration(request_id.clone());
+
+    let progress = Progress {
+        command,
+        request_id: request_id.clone(),
+        cancelled: cancelled.clone(),
+    };
+    emit(command, &request_id, "started", Some(0.0), false);
+    let response = envelope::respond_as(command, request_id.clone(), async move {
+        tokio::select! {
+            result = task(progress) => result,
+            _ = cancelled.wait_for(|cancelled| *cancelled) => Err(AppError::new(
+                ErrorCode::Cancelled,
+                format!("{} was cancelled", command),
+            )),
+        }
+    })
+    .await;
+    drop(registration);
+
+    let stage = match &response {
+        Ok(_) => "completed",
+        Err(envelope) if envelope.error.as_ref().is_some_and(|e| e.code == ErrorCode::Cancelled) => "cancelled",
+        Err(_) => "failed",
+    };
+    emit(command, &request_id, stage, response.is_ok().then_some(100.0), true);
+    response
+}
+
+/// Cancel a running long-running command; false when no request with that ID is running
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_request(request_id: String) -> Response<bool> {
+    envelope::respond_sync("cancel_request", || {
+        let running = RUNNING.lock();
+        match running.get(&request_id) {
+            Some(cancel) => {
+                info!("🛑 Cancelling request {}", request_id);
+                let _ = cancel.send(true);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    })
+}
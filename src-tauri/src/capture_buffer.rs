@@ -0,0 +1,260 @@
+// Capped in-memory sample buffer that spills to disk
+// Long captures keep at most `cap` samples in RAM; older audio is appended to temp files as raw
+// little-endian f32 and merged back, in order, when the capture is saved.
+
+use std::fs::{File, TryLockError};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Once, OnceLock};
+
+use log::{info, warn};
+
+/// Default in-memory cap: 64 MB of f32 samples (~6 minutes of 44.1kHz mono)
+pub const DEFAULT_MEMORY_CAP_BYTES: usize = 64 * 1024 * 1024;
+/// Samples read back per step when merging spill files
+const MERGE_CHUNK_SAMPLES: usize = 64 * 1024;
+
+static NEXT_SPILL_ID: AtomicU64 = AtomicU64::new(0);
+static CLEAN_STALE: Once = Once::new();
+/// Held locked for the life of the process, so other instances can tell its spill files are in use
+static OWNER_LOCK: OnceLock<Option<File>> = OnceLock::new();
+
+pub fn spill_dir() -> PathBuf {
+    std::env::temp_dir().join("MockMate").join("capture_spill")
+}
+
+fn owner_lock_path(pid: u32) -> PathBuf {
+    spill_dir().join(format!("{}.lock", pid))
+}
+
+/// Take this process's owner lock before its first spill file is written
+fn hold_owner_lock() {
+    OWNER_LOCK.get_or_init(|| {
+        let path = owner_lock_path(std::process::id());
+        let locked = std::fs::create_dir_all(spill_dir())
+            .and_then(|()| File::create(&path))
+            .and_then(|file| file.try_lock().map(|()| file).map_err(std::io::Error::from));
+        match locked {
+            Ok(file) => Some(file),
+            Err(e) => {
+                warn!("Failed to lock {}: {}", path.display(), e);
+                None
+            }
+        }
+    });
+}
+
+/// Whether `path` in the spill folder was left by a process that is no longer running. Files are
+/// named `<pid>_<id>.f32` and `<pid>.lock`; the owner is alive while it holds its lock file.
+pub fn is_stale_spill(path: &Path) -> bool {
+    let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let Some(pid) = name.split(['_', '.']).next().and_then(|pid| pid.parse::<u32>().ok()) else {
+        return true;
+    };
+    if pid == std::process::id() {
+        return false;
+    }
+    // A lock nobody holds (or no lock file at all) means the owner exited or crashed
+    match File::open(owner_lock_path(pid)) {
+        Ok(file) => !matches!(file.try_lock(), Err(TryLockError::WouldBlock)),
+        Err(_) => true,
+    }
+}
+
+/// Remove spill files left behind by runs that didn't shut down cleanly, leaving other running
+/// instances' files alone
+fn clean_stale_spills() {
+    CLEAN_STALE.call_once(|| {
+        let Ok(entries) = std::fs::read_dir(spill_dir()) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            if is_stale_spill(&entry.path()) {
+                let _ = std::fs::remove_file(entry.path());
+            }
+        }
+    });
+}
+
+pub struct SpillingSampleBuffer {
+    memory: Vec<f32>,
+    cap_samples: usize,
+    spill_files: Vec<PathBuf>,
+    spilled_samples: usize,
+}
+
+impl SpillingSampleBuffer {
+    pub fn new(cap_bytes: usize) -> Self {
+        Self {
+            memory: Vec::new(),
+            cap_samples: (cap_bytes / std::mem::size_of::<f32>()).max(1),
+            spill_files: Vec::new(),
+            spilled_samples: 0,
+        }
+    }
+
+    pub fn set_cap_bytes(&mut self, cap_bytes: usize) {
+        self.cap_samples = (cap_bytes / std::mem::size_of::<f32>()).max(1);
+        if self.memory.len() > self.cap_samples {
+            self.spill();
+        }
+    }
+
+    pub fn cap_bytes(&self) -> usize {
+        self.cap_samples * std::mem::size_of::<f32>()
+    }
+
+    /// Total samples captured, in memory and on disk
+    pub fn len(&self) -> usize {
+        self.spilled_samples + self.memory.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn spilled_samples(&self) -> usize {
+        self.spilled_samples
+    }
+
+    pub fn push(&mut self, samples: &[f32]) {
+        self.memory.extend_from_slice(samples);
+        if self.memory.len() > self.cap_samples {
+            self.spill();
+        }
+    }
+
+    /// Move everything held in memory to a new spill file
+    fn spill(&mut self) {
+        clean_stale_spills();
+        hold_owner_lock();
+        let path = spill_dir().join(format!(
+            "{}_{}.f32",
+            std::process::id(),
+            NEXT_SPILL_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        match write_raw(&path, &self.memory) {
+            Ok(()) => {
+                self.spilled_samples += self.memory.len();
+                info!("💾 Spilled {} captured samples to {}", self.memory.len(), path.display());
+                self.spill_files.push(path);
+                self.memory.clear();
+                self.memory.shrink_to(self.cap_samples);
+            }
+            // Keep the audio in memory rather than lose it; the next push retries
+            Err(e) => warn!("Failed to spill captured audio to disk: {}", e),
+        }
+    }
+
+    /// Visit all samples oldest first without loading spilled audio into memory at once
+    pub fn for_each_chunk(&self, mut visit: impl FnMut(&[f32]) -> Result<(), String>) -> Result<(), String> {
+        let mut chunk = Vec::with_capacity(MERGE_CHUNK_SAMPLES);
+        let mut bytes = vec![0u8; MERGE_CHUNK_SAMPLES * std::mem::size_of::<f32>()];
+        for path in &self.spill_files {
+            let mut reader = BufReader::new(File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?);
+            loop {
+                let read = read_full(&mut reader, &mut bytes).map_err(|e| e.to_string())?;
+                if read == 0 {
+                    break;
+                }
+                chunk.clear();
+                crate::audio_buffer::extend_f32_from_le_bytes(&bytes[..read], &mut chunk);
+                visit(&chunk)?;
+            }
+        }
+        visit(&self.memory)
+    }
+
+    /// Write everything captured as a 16-bit PCM WAV, streaming spill files from disk
+    pub fn write_wav(&self, path: &Path, sample_rate: u32, channels: u16) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let spec = hound::WavSpec {
+            channels,
+            sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(path, spec).map_err(|e| e.to_string())?;
+        self.for_each_chunk(|chunk| {
+            for &sample in chunk {
+                writer
+                    .write_sample((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
+                    .map_err(|e| e.to_string())?;
+            }
+            Ok(())
+        })?;
+        writer.finalize().map_err(|e| e.to_string())
+    }
+
+    pub fn clear(&mut self) {
+        for path in self.spill_files.drain(..) {
+            let _ = std::fs::remove_file(path);
+        }
+        self.spilled_samples = 0;
+        self.memory = Vec::new();
+    }
+}
+
+impl Drop for SpillingSampleBuffer {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+fn write_raw(path: &Path, samples: &[f32]) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut writer = BufWriter::new(File::create(path)?);
+    for &sample in samples {
+        writer.write_all(&sample.to_le_bytes())?;
+    }
+    writer.flush()
+}
+
+/// Fill `buf` unless the reader ends first; returns the number of bytes read
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn own_spill_files_are_in_use() {
+        let pid = std::process::id();
+        assert!(!is_stale_spill(&spill_dir().join(format!("{}_0.f32", pid))));
+        assert!(!is_stale_spill(&spill_dir().join(format!("{}.lock", pid))));
+    }
+
+    #[test]
+    fn unlocked_and_unnamed_spill_files_are_stale() {
+        // No process holds a lock file for this pid
+        assert!(is_stale_spill(&spill_dir().join(format!("{}_3.f32", u32::MAX))));
+        assert!(is_stale_spill(&spill_dir().join("leftover.tmp")));
+    }
+
+    #[test]
+    fn spill_held_by_a_live_lock_is_in_use() {
+        let pid = u32::MAX - 1;
+        std::fs::create_dir_all(spill_dir()).unwrap();
+        let lock = File::create(owner_lock_path(pid)).unwrap();
+        lock.try_lock().unwrap();
+        assert!(!is_stale_spill(&spill_dir().join(format!("{}_0.f32", pid))));
+        lock.unlock().unwrap();
+        assert!(is_stale_spill(&spill_dir().join(format!("{}_0.f32", pid))));
+        drop(lock);
+        let _ = std::fs::remove_file(owner_lock_path(pid));
+    }
+}
@@ -0,0 +1,162 @@
+// Hiding MockMate's windows from screen capture and screen sharing
+// Windows uses SetWindowDisplayAffinity(WDA_EXCLUDEFROMCAPTURE) and macOS sets the NSWindow
+// sharing type to NSWindowSharingNone. Linux compositors offer no equivalent, so windows there are
+// recorded as unsupported. What was asked for and what actually happened is kept per window label
+// so `get_capture_protection_status` can tell the user whether a share would show MockMate.
+
+use std::collections::HashMap;
+
+use log::{error, info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
+use specta::Type;
+use tauri::{AppHandle, Manager, WebviewWindow};
+use crate::envelope::{self, Response};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtectionState {
+    /// Screen capture and screen shares see nothing where the window is
+    Protected,
+    Unprotected,
+    /// This OS cannot hide windows from capture
+    Unsupported,
+    /// Protection was requested but the OS call failed (e.g. Windows older than 10 2004)
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct WindowProtection {
+    pub label: String,
+    /// Whether protection was last asked for; None when it never was for this window
+    pub requested: Option<bool>,
+    pub state: ProtectionState,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Outcome {
+    requested: bool,
+    error: Option<String>,
+}
+
+static OUTCOMES: Lazy<Mutex<HashMap<String, Outcome>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[cfg(windows)]
+fn set_protected(window: &WebviewWindow, protect: bool) -> Result<(), String> {
+    use windows_sys::Win32::Foundation::{GetLastError, HWND};
+    use windows_sys::Win32::UI::WindowsAndMessaging::{SetWindowDisplayAffinity, WDA_EXCLUDEFROMCAPTURE, WDA_NONE};
+
+    let hwnd = window.hwnd().map_err(|e| e.to_string())?.0 as HWND;
+    let affinity = if protect { WDA_EXCLUDEFROMCAPTURE } else { WDA_NONE };
+    if unsafe { SetWindowDisplayAffinity(hwnd, affinity) } == 0 {
+        let error_code = unsafe { GetLastError() };
+        return Err(format!("Failed to set window display affinity: {}", error_code));
+    }
+    Ok(())
+}
+
+/// NSWindowSharingNone when protected, NSWindowSharingReadOnly otherwise
+#[cfg(target_os = "macos")]
+fn set_protected(window: &WebviewWindow, protect: bool) -> Result<(), String> {
+    window
+        .set_content_protected(protect)
+        .map_err(|e| format!("Failed to set the window sharing type: {}", e))
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+fn set_protected(_window: &WebviewWindow, _protect: bool) -> Result<(), String> {
+    Ok(())
+}
+
+/// Protect or unprotect `window` and remember the outcome. Unsupported platforms are not an error;
+/// the status command reports them.
+pub fn apply(window: &WebviewWindow, protect: bool) -> Result<(), String> {
+    let result = if crate::platform::Capability::CaptureProtection.available() {
+        set_protected(window, protect)
+    } else {
+        if protect {
+            warn!("{}", crate::platform::unavailable(crate::platform::Capability::CaptureProtection));
+        }
+        Ok(())
+    };
+    match &result {
+        Ok(()) => info!("🛡️ Capture protection for '{}' set to {}", window.label(), protect),
+        Err(e) => error!("Failed to set capture protection for '{}': {}", window.label(), e),
+    }
+    OUTCOMES.lock().insert(
+        window.label().to_string(),
+        Outcome {
+            requested: protect,
+            error: result.as_ref().err().cloned(),
+        },
+    );
+    result
+}
+
+/// What the OS reports right now, where it can be asked
+#[cfg(windows)]
+fn queried_state(window: &WebviewWindow) -> Option<ProtectionState> {
+    use windows_sys::Win32::Foundation::HWND;
+    use windows_sys::Win32::UI::WindowsAndMessaging::{GetWindowDisplayAffinity, WDA_NONE};
+
+    let hwnd = window.hwnd().ok()?.0 as HWND;
+    let mut affinity = 0u32;
+    if unsafe { GetWindowDisplayAffinity(hwnd, &mut affinity) } == 0 {
+        return None;
+    }
+    // WDA_MONITOR (shown black) counts as protected too
+    Some(if affinity == WDA_NONE {
+        ProtectionState::Unprotected
+    } else {
+        ProtectionState::Protected
+    })
+}
+
+#[cfg(not(windows))]
+fn queried_state(_window: &WebviewWindow) -> Option<ProtectionState> {
+    None
+}
+
+fn window_status(window: &WebviewWindow) -> WindowProtection {
+    let outcome = OUTCOMES.lock().get(window.label()).cloned();
+    let requested = outcome.as_ref().map(|outcome| outcome.requested);
+    let error = outcome.and_then(|outcome| outcome.error);
+    let (state, detail) = if !crate::platform::Capability::CaptureProtection.available() {
+        (
+            ProtectionState::Unsupported,
+            Some(crate::platform::unavailable(crate::platform::Capability::CaptureProtection)),
+        )
+    } else {
+        let state = match (queried_state(window), requested, &error) {
+            (Some(ProtectionState::Unprotected), Some(true), _) | (None, Some(true), Some(_)) => ProtectionState::Failed,
+            (Some(state), _, _) => state,
+            (None, Some(true), None) => ProtectionState::Protected,
+            (None, _, _) => ProtectionState::Unprotected,
+        };
+        let detail = match state {
+            ProtectionState::Failed => error.or_else(|| Some("Protection was turned off outside MockMate".to_string())),
+            _ => error,
+        };
+        (state, detail)
+    };
+    WindowProtection {
+        label: window.label().to_string(),
+        requested,
+        state,
+        detail,
+    }
+}
+
+/// Effective capture protection of every open window
+#[tauri::command]
+#[specta::specta]
+pub async fn get_capture_protection_status(app_handle: AppHandle) -> Response<Vec<WindowProtection>> {
+    envelope::respond("get_capture_protection_status", async move {
+        let mut windows: Vec<WindowProtection> = app_handle.webview_windows().values().map(window_status).collect();
+        windows.sort_by(|a, b| a.label.cmp(&b.label));
+        Ok(windows)
+    })
+    .await
+}
@@ -0,0 +1,252 @@
+// Slack/Discord summary posting
+// Sends an end-of-session summary to a team channel through an incoming webhook, so coaches can
+// review several candidates' sessions in one place. Channels are named by the user; their webhook
+// URLs are kept in the Windows Credential Manager and only the names in chat_channels.json.
+
+use std::path::PathBuf;
+
+use log::info;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use serde_json::json;
+
+use crate::net_trace::TracedSend;
+use crate::session_summary::SessionSummary;
+use crate::envelope::{self, Response};
+use crate::error::{AppError, ErrorCode};
+
+/// Discord embed field values are capped at 1024 characters, descriptions at 4096
+const DISCORD_FIELD_CHARS: usize = 1024;
+const DISCORD_DESCRIPTION_CHARS: usize = 4096;
+/// Slack section text is capped at 3000 characters
+const SLACK_SECTION_CHARS: usize = 3000;
+
+static CHANNELS: Lazy<Mutex<ChatChannels>> = Lazy::new(|| Mutex::new(load_channels()));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ChatTarget {
+    Slack,
+    Discord,
+}
+
+impl ChatTarget {
+    fn as_str(self) -> &'static str {
+        match self {
+            ChatTarget::Slack => "slack",
+            ChatTarget::Discord => "discord",
+        }
+    }
+
+    fn service(self) -> &'static str {
+        match self {
+            ChatTarget::Slack => "Slack",
+            ChatTarget::Discord => "Discord",
+        }
+    }
+
+    fn accepts(self, url: &url::Url) -> bool {
+        let host = url.host_str().unwrap_or_default();
+        url.scheme() == "https"
+            && match self {
+                ChatTarget::Slack => host == "hooks.slack.com",
+                ChatTarget::Discord => {
+                    matches!(host, "discord.com" | "discordapp.com" | "ptb.discord.com" | "canary.discord.com")
+                        && url.path().starts_with("/api/webhooks/")
+                }
+            }
+    }
+}
+
+/// Configured channel names; the first one of each target is the default
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct ChatChannels {
+    pub slack: Vec<String>,
+    pub discord: Vec<String>,
+}
+
+impl ChatChannels {
+    fn list_mut(&mut self, target: ChatTarget) -> &mut Vec<String> {
+        match target {
+            ChatTarget::Slack => &mut self.slack,
+            ChatTarget::Discord => &mut self.discord,
+        }
+    }
+}
+
+fn channels_path() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("chat_channels.json")
+}
+
+fn load_channels() -> ChatChannels {
+    std::fs::read(channels_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn save_channels(channels: &ChatChannels) -> Result<(), String> {
+    let path = channels_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(channels).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| e.to_string())
+}
+
+fn secret_name(target: ChatTarget, channel: &str) -> String {
+    format!("{}_webhook/{}", target.as_str(), channel)
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+fn bullets(items: &[String]) -> String {
+    if items.is_empty() {
+        return "—".to_string();
+    }
+    items.iter().map(|item| format!("• {}", item)).collect::<Vec<_>>().join("\n")
+}
+
+fn score_line(summary: &SessionSummary) -> String {
+    let score = summary
+        .overall_score
+        .map(|s| format!("Score {:.1}/10 · ", s))
+        .unwrap_or_default();
+    format!("{}{} questions", score, summary.questions)
+}
+
+fn slack_payload(summary: &SessionSummary) -> serde_json::Value {
+    let section = |text: String| json!({ "type": "section", "text": { "type": "mrkdwn", "text": truncate(&text, SLACK_SECTION_CHARS) } });
+    json!({
+        "text": format!("{}: {}", summary.title, summary.summary),
+        "blocks": [
+            { "type": "header", "text": { "type": "plain_text", "text": truncate(&summary.title, 150) } },
+            section(summary.summary.clone()),
+            section(format!("*Strengths*\n{}", bullets(&summary.strengths))),
+            section(format!("*To improve*\n{}", bullets(&summary.improvements))),
+            { "type": "context", "elements": [{ "type": "mrkdwn", "text": format!("MockMate · {}", score_line(summary)) }] }
+        ]
+    })
+}
+
+fn discord_payload(summary: &SessionSummary) -> serde_json::Value {
+    json!({
+        "username": "MockMate",
+        "embeds": [{
+            "title": truncate(&summary.title, 256),
+            "description": truncate(&summary.summary, DISCORD_DESCRIPTION_CHARS),
+            "color": 0x5865F2,
+            "fields": [
+                { "name": "Strengths", "value": truncate(&bullets(&summary.strengths), DISCORD_FIELD_CHARS) },
+                { "name": "To improve", "value": truncate(&bullets(&summary.improvements), DISCORD_FIELD_CHARS) }
+            ],
+            "footer": { "text": score_line(summary) },
+            "timestamp": summary.generated_at.to_rfc3339()
+        }]
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_chat_channels() -> Response<ChatChannels> {
+    envelope::respond("get_chat_channels", async move {
+        Ok(CHANNELS.lock().clone())
+    })
+    .await
+}
+
+/// Add or replace a named channel's webhook URL, or remove the channel when `webhook_url` is None
+#[tauri::command]
+#[specta::specta]
+pub async fn set_chat_channel(target: ChatTarget, channel: String, webhook_url: Option<String>) -> Response<ChatChannels> {
+    envelope::respond("set_chat_channel", async move {
+        let channel = channel.trim().to_string();
+        if channel.is_empty() {
+            return Err("Channel name is required".into());
+        }
+        match webhook_url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty()) {
+            Some(webhook_url) => {
+                let url = url::Url::parse(&webhook_url).map_err(|e| format!("Invalid webhook URL: {}", e))?;
+                if !target.accepts(&url) {
+                    return Err(format!("That is not a {} incoming webhook URL", target.service()).into());
+                }
+                crate::secrets::set_secret(&secret_name(target, &channel), &webhook_url)?;
+                let mut channels = CHANNELS.lock();
+                let list = channels.list_mut(target);
+                if !list.contains(&channel) {
+                    list.push(channel);
+                }
+                save_channels(&channels)?;
+                Ok(channels.clone())
+            }
+            None => {
+                crate::secrets::delete_secret(&secret_name(target, &channel))?;
+                let mut channels = CHANNELS.lock();
+                channels.list_mut(target).retain(|c| *c != channel);
+                save_channels(&channels)?;
+                Ok(channels.clone())
+            }
+        }
+    })
+    .await
+}
+
+/// Post a session summary to a configured channel. Without `channel` the target's first channel is
+/// used; without `session_id` the most recently generated summary is sent.
+#[tauri::command]
+#[specta::specta]
+pub async fn post_session_summary(target: ChatTarget, channel: Option<String>, session_id: Option<String>) -> Response<()> {
+    envelope::respond("post_session_summary", async move {
+        crate::privacy::ensure_cloud_allowed(target.service())?;
+        let channel = match channel {
+            Some(channel) => channel,
+            None => CHANNELS
+                .lock()
+                .list_mut(target)
+                .first()
+                .cloned()
+                .ok_or_else(|| format!("No {} channel is configured", target.service()))?,
+        };
+        let webhook_url = crate::secrets::get_secret(&secret_name(target, &channel))?
+            .ok_or_else(|| format!("{} channel '{}' is not configured", target.service(), channel))?;
+
+        let summary = match &session_id {
+            Some(id) => crate::session_summary::load(id)
+                .ok_or_else(|| format!("No summary has been generated for session {}", id))?,
+            None => crate::session_summary::latest().ok_or("No session summary has been generated yet")?,
+        };
+        let payload = match target {
+            ChatTarget::Slack => slack_payload(&summary),
+            ChatTarget::Discord => discord_payload(&summary),
+        };
+
+        let response = reqwest::Client::new()
+            .post(&webhook_url)
+            .json(&payload)
+            .send_traced()
+            .await
+            .map_err(|e| e.context(&format!("Failed to post to {}", target.service())))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(AppError::new(
+                ErrorCode::from_status(status),
+                format!("{} rejected the summary: {} {}", target.service(), status, body),
+            ));
+        }
+        info!("💬 Posted summary for session {} to {} channel '{}'", summary.session_id, target.service(), channel);
+        Ok(())
+    })
+    .await
+}
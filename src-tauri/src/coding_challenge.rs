@@ -0,0 +1,310 @@
+// Coding-challenge detection and structured solutions
+// Extracted screen text and transcribed questions are checked for coding problems (problem
+// statements, examples, constraints, function signatures). Detected problems go to a dedicated
+// prompt that returns the approach, complexity, code in the preferred language and test cases as
+// one typed record. Records are kept per session in %APPDATA%\MockMate\coding and the answer is
+// shown in the AI window as Markdown sections.
+
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use log::info;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::State;
+
+use crate::question_topic::QuestionTopic;
+use crate::AppState;
+use crate::envelope::{self, Response};
+
+/// Detection score needed before text is treated as a coding problem
+const DETECTION_THRESHOLD: u32 = 4;
+/// Problem characters sent to the model
+const MAX_PROBLEM_CHARS: usize = 8000;
+/// Records kept for text captured outside a session
+const NO_SESSION: &str = "no_session";
+
+/// Signals of a coding problem and how strongly each one counts
+const SIGNALS: [(&str, u32); 22] = [
+    ("given an array", 3),
+    ("given a string", 3),
+    ("given the root", 3),
+    ("given a linked list", 3),
+    ("given an integer", 3),
+    ("write a function", 3),
+    ("implement a function", 3),
+    ("example 1", 3),
+    ("constraints:", 3),
+    ("input:", 2),
+    ("output:", 2),
+    ("return the", 2),
+    ("returns the", 2),
+    ("time complexity", 2),
+    ("o(n", 2),
+    ("def ", 2),
+    ("class solution", 3),
+    ("public int", 2),
+    ("nums", 1),
+    ("target", 1),
+    ("subarray", 2),
+    ("1 <=", 2),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CodingSettings {
+    /// Route detected coding problems to the structured prompt
+    pub enabled: bool,
+    /// Language solutions are written in unless a request names another
+    pub language: String,
+}
+
+impl Default for CodingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            language: "python".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct TestCase {
+    pub input: String,
+    pub expected: String,
+    #[serde(default)]
+    pub explanation: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct CodingSolution {
+    pub id: String,
+    pub session_id: Option<String>,
+    /// Where the problem came from, e.g. "transcript" or the accessibility source
+    pub source: String,
+    pub problem: String,
+    pub language: String,
+    pub approach: String,
+    pub time_complexity: String,
+    pub space_complexity: String,
+    pub code: String,
+    pub test_cases: Vec<TestCase>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl CodingSolution {
+    /// Markdown shown in the AI window, one section per part of the solution
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!(
+            "## Approach\n\n{}\n\n## Complexity\n\n- **Time:** {}\n- **Space:** {}\n\n## Code\n\n```{}\n{}\n```\n",
+            self.approach.trim(),
+            self.time_complexity.trim(),
+            self.space_complexity.trim(),
+            self.language,
+            self.code.trim_end()
+        );
+        if !self.test_cases.is_empty() {
+            markdown.push_str("\n## Test cases\n\n");
+            for (i, case) in self.test_cases.iter().enumerate() {
+                markdown.push_str(&format!(
+                    "{}. **Input:** `{}` → **Expected:** `{}`",
+                    i + 1,
+                    case.input.trim(),
+                    case.expected.trim()
+                ));
+                if !case.explanation.trim().is_empty() {
+                    markdown.push_str(&format!(" — {}", case.explanation.trim()));
+                }
+                markdown.push('\n');
+            }
+        }
+        markdown
+    }
+}
+
+#[derive(Deserialize)]
+struct SolutionReply {
+    approach: String,
+    time_complexity: String,
+    space_complexity: String,
+    code: String,
+    #[serde(default)]
+    test_cases: Vec<TestCase>,
+}
+
+static SETTINGS: Lazy<Mutex<CodingSettings>> = Lazy::new(|| Mutex::new(load_settings()));
+
+fn mockmate_dir() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+}
+
+fn settings_path() -> PathBuf {
+    mockmate_dir().join("coding_challenge.json")
+}
+
+fn solutions_path(session_id: Option<&str>) -> PathBuf {
+    mockmate_dir().join("coding").join(format!("{}.json", session_id.unwrap_or(NO_SESSION)))
+}
+
+fn load_settings() -> CodingSettings {
+    std::fs::read(settings_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+pub fn settings() -> CodingSettings {
+    SETTINGS.lock().clone()
+}
+
+/// Confidence (0-1) that `text` is a coding problem, or `None` when it does not look like one
+pub fn detect(text: &str) -> Option<f32> {
+    let lower = text.to_lowercase();
+    let mut score: u32 = SIGNALS.iter().filter(|(signal, _)| lower.contains(signal)).map(|(_, weight)| weight).sum();
+    if QuestionTopic::classify(text) == Some(QuestionTopic::Coding) {
+        score += 2;
+    }
+    (score >= DETECTION_THRESHOLD).then(|| (score as f32 / 12.0).min(1.0))
+}
+
+/// `detect`, unless routing to the coding prompt is switched off
+pub fn detect_enabled(text: &str) -> Option<f32> {
+    if !SETTINGS.lock().enabled {
+        return None;
+    }
+    detect(text)
+}
+
+/// Stored solutions for a session (or for text captured outside one), oldest first
+pub fn solutions(session_id: Option<&str>) -> Vec<CodingSolution> {
+    std::fs::read(solutions_path(session_id))
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn store(solution: &CodingSolution) -> Result<(), String> {
+    let mut all = solutions(solution.session_id.as_deref());
+    all.push(solution.clone());
+    let path = solutions_path(solution.session_id.as_deref());
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(&all).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| e.to_string())
+}
+
+/// Solve a coding problem with the structured prompt and store the record
+pub(crate) async fn solve(
+    state: &AppState,
+    provider: &str,
+    model: &str,
+    problem: &str,
+    language: Option<&str>,
+    source: &str,
+) -> Result<CodingSolution, String> {
+    let language = language
+        .map(str::to_string)
+        .filter(|l| !l.trim().is_empty())
+        .unwrap_or_else(|| settings().language);
+    let problem: String = problem.trim().chars().take(MAX_PROBLEM_CHARS).collect();
+    let prompt = format!(
+        "Solve this coding interview problem in {language}.\n\n---\n{problem}\n---\n\n\
+         Reply only with JSON in exactly this format:\n\
+         {{\"approach\": \"<how to solve it and why, as a short explanation a candidate can say out loud>\", \
+         \"time_complexity\": \"<e.g. O(n log n) with a one-line justification>\", \
+         \"space_complexity\": \"<e.g. O(n) with a one-line justification>\", \
+         \"code\": \"<complete, runnable {language} solution>\", \
+         \"test_cases\": [{{\"input\": \"<input>\", \"expected\": \"<expected output>\", \"explanation\": \"<what it covers>\"}}]}}\n\n\
+         Include 3-5 test cases covering the examples and edge cases."
+    );
+    let reply = state
+        .complete(
+            provider,
+            model,
+            "You are an expert software engineer helping a candidate in a coding interview. Prefer the optimal \
+             solution and keep code idiomatic and commented where it helps.",
+            &prompt,
+        )
+        .await?;
+    let parsed: SolutionReply = crate::practice::extract_json(&reply, '{', '}')?;
+
+    let solution = CodingSolution {
+        id: uuid::Uuid::new_v4().to_string(),
+        session_id: crate::audit::current_session(),
+        source: source.to_string(),
+        problem,
+        language,
+        approach: parsed.approach,
+        time_complexity: parsed.time_complexity,
+        space_complexity: parsed.space_complexity,
+        code: parsed.code,
+        test_cases: parsed.test_cases,
+        created_at: Utc::now(),
+    };
+    store(&solution)?;
+    info!(
+        "🧩 Solved coding problem from {} in {} ({} test cases)",
+        solution.source,
+        solution.language,
+        solution.test_cases.len()
+    );
+    Ok(solution)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_coding_settings() -> Response<CodingSettings> {
+    envelope::respond("get_coding_settings", async move {
+        Ok(settings())
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_coding_settings(settings: CodingSettings) -> Response<CodingSettings> {
+    envelope::respond("set_coding_settings", async move {
+        if settings.language.trim().is_empty() {
+            return Err("A solution language is required".into());
+        }
+        let path = settings_path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let body = serde_json::to_vec_pretty(&settings).map_err(|e| e.to_string())?;
+        std::fs::write(&path, body).map_err(|e| e.to_string())?;
+        *SETTINGS.lock() = settings.clone();
+        Ok(settings)
+    })
+    .await
+}
+
+/// Solve a problem on request, e.g. one pasted by the user, without showing it in the AI window
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn solve_coding_challenge(
+    state: State<'_, AppState>,
+    problem: String,
+    language: Option<String>,
+    provider: String,
+    model: String,
+) -> Response<CodingSolution> {
+    envelope::respond("solve_coding_challenge", async move {
+        Ok(solve(&state, &provider, &model, &problem, language.as_deref(), "manual").await?)
+    })
+    .await
+}
+
+/// Solutions stored for a session; without a session id, those captured outside any session
+#[tauri::command]
+#[specta::specta]
+pub async fn list_coding_solutions(session_id: Option<String>) -> Response<Vec<CodingSolution>> {
+    envelope::respond("list_coding_solutions", async move {
+        Ok(solutions(session_id.as_deref()))
+    })
+    .await
+}
@@ -0,0 +1,70 @@
+// Deprecated command aliases
+// Renamed or merged commands keep their old name here for one API version so an older frontend
+// keeps working, so an alias also keeps the old return type: the plain value or error message
+// rather than an `Envelope`. Every call logs a warning and emits `deprecated-command` naming the
+// replacement; once `removed_in` is reached the alias and its `DEPRECATED` entry are deleted.
+
+use log::warn;
+use schemars::JsonSchema;
+use serde::Serialize;
+use specta::Type;
+use tauri::{AppHandle, Emitter};
+
+use crate::api::API_VERSION;
+use crate::envelope::IntoResult;
+use crate::events;
+
+/// Payload of `deprecated-command`
+#[derive(Debug, Clone, Serialize, JsonSchema, Type)]
+pub struct DeprecatedCommand {
+    pub command: &'static str,
+    pub replacement: &'static str,
+    /// API version that no longer has the command
+    pub removed_in: u32,
+}
+
+pub const DEPRECATED: &[DeprecatedCommand] = &[
+    DeprecatedCommand {
+        command: "activate_session",
+        replacement: "activate_session_cmd",
+        removed_in: API_VERSION + 1,
+    },
+    DeprecatedCommand {
+        command: "disconnect_session",
+        replacement: "disconnect_session_cmd",
+        removed_in: API_VERSION + 1,
+    },
+];
+
+fn warn_deprecated(app_handle: &AppHandle, command: &str) {
+    let Some(deprecation) = DEPRECATED.iter().find(|d| d.command == command) else {
+        return;
+    };
+    warn!(
+        "⚠️ Deprecated command {} was called; use {} (removed in API version {})",
+        deprecation.command, deprecation.replacement, deprecation.removed_in
+    );
+    if let Err(e) = app_handle.emit(events::DEPRECATED_COMMAND, deprecation) {
+        warn!("Failed to emit deprecation warning for {}: {}", command, e);
+    }
+}
+
+/// Deprecated: use `activate_session_cmd`
+#[tauri::command]
+#[specta::specta]
+pub async fn activate_session(app_handle: AppHandle, session_id: String, idempotency_key: Option<String>) -> Result<bool, String> {
+    warn_deprecated(&app_handle, "activate_session");
+    crate::activate_session_cmd(app_handle, session_id, idempotency_key, None)
+        .await
+        .into_result()
+        .map(|_| true)
+        .map_err(|e| e.message)
+}
+
+/// Deprecated: use `disconnect_session_cmd`
+#[tauri::command]
+#[specta::specta]
+pub async fn disconnect_session(app_handle: AppHandle, session_id: String) -> Result<String, String> {
+    warn_deprecated(&app_handle, "disconnect_session");
+    crate::disconnect_session_cmd(app_handle, session_id).await.into_result().map_err(|e| e.message)
+}
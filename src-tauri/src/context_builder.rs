@@ -0,0 +1,309 @@
+// Prompt context assembly
+// Every answer command used to clone the interview context and patch company, position and job
+// description from its payload on its own. `ContextBuilder` does that in one place and also decides
+// how much of each source goes into the prompt: the job description, the resume chunks that best
+// match the question, the recent transcript and the last questions and answers of this session,
+// in that priority, under a token budget that depends on the provider (Pollinations prompts go in a
+// GET URL, so they get much less). `preview_prompt_context` returns exactly what would be sent.
+
+use std::collections::{HashSet, VecDeque};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use specta::Type;
+use tauri::State;
+
+use crate::envelope::{self, Response};
+use crate::openai::InterviewContext;
+use crate::AppState;
+
+/// Prompt tokens for context, by provider; one token is estimated as four characters
+const OPENAI_BUDGET_TOKENS: usize = 3000;
+const POLLINATIONS_BUDGET_TOKENS: usize = 500;
+/// Share of the budget each source may take at most, in priority order
+const JOB_DESCRIPTION_SHARE: f32 = 0.3;
+const RESUME_SHARE: f32 = 0.35;
+const TRANSCRIPT_SHARE: f32 = 0.2;
+const PRIOR_QA_SHARE: f32 = 0.15;
+/// Resume paragraphs longer than this are split into chunks of about this size
+const RESUME_CHUNK_CHARS: usize = 600;
+const MAX_TRANSCRIPT_LINES: usize = 40;
+const MAX_EXCHANGES: usize = 5;
+
+/// Final interviewer transcript segments, newest last
+static TRANSCRIPT: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+/// Questions answered in this run of the app with their answers, newest last
+static EXCHANGES: Mutex<VecDeque<(String, String)>> = Mutex::new(VecDeque::new());
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ContextSection {
+    /// "job_description", "resume", "recent_transcript" or "prior_qa"
+    pub name: String,
+    pub content: String,
+    /// Estimated tokens of `content`
+    pub tokens: usize,
+    /// Whether part of the source was left out to fit the budget
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ContextPreview {
+    pub budget_tokens: usize,
+    pub used_tokens: usize,
+    pub company: Option<String>,
+    pub position: Option<String>,
+    pub sections: Vec<ContextSection>,
+    /// The system prompt the provider would be sent with this context
+    pub system_prompt: String,
+}
+
+fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// `text` cut to about `tokens`, at a character boundary
+fn truncate_to_tokens(text: &str, tokens: usize) -> (String, bool) {
+    let max_chars = tokens * 4;
+    if text.chars().count() <= max_chars {
+        return (text.to_string(), false);
+    }
+    (text.chars().take(max_chars).collect(), true)
+}
+
+fn keywords(text: &str) -> HashSet<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric() && c != '+' && c != '#')
+        .filter(|word| word.len() > 2)
+        .map(str::to_string)
+        .collect()
+}
+
+fn resume_chunks(resume: &str) -> Vec<String> {
+    let mut chunks = Vec::new();
+    for paragraph in resume.split("\n\n").map(str::trim).filter(|p| !p.is_empty()) {
+        let mut chunk = String::new();
+        for line in paragraph.lines() {
+            if !chunk.is_empty() && chunk.len() + line.len() > RESUME_CHUNK_CHARS {
+                chunks.push(std::mem::take(&mut chunk));
+            }
+            if !chunk.is_empty() {
+                chunk.push('\n');
+            }
+            chunk.push_str(line.trim());
+        }
+        if !chunk.is_empty() {
+            chunks.push(chunk);
+        }
+    }
+    chunks
+}
+
+/// Called with every final interviewer transcript segment
+pub fn on_transcript(text: &str) {
+    let text = text.trim();
+    if text.is_empty() {
+        return;
+    }
+    let mut transcript = TRANSCRIPT.lock();
+    transcript.push_back(text.to_string());
+    while transcript.len() > MAX_TRANSCRIPT_LINES {
+        transcript.pop_front();
+    }
+}
+
+/// Remember an answered question so follow-ups are answered consistently
+pub fn record_exchange(question: &str, answer: &str) {
+    if question.trim().is_empty() || answer.trim().is_empty() {
+        return;
+    }
+    let mut exchanges = EXCHANGES.lock();
+    exchanges.push_back((question.trim().to_string(), answer.trim().to_string()));
+    while exchanges.len() > MAX_EXCHANGES {
+        exchanges.pop_front();
+    }
+}
+
+/// Forget the transcript and answers kept for context, e.g. when a session ends
+pub fn clear() {
+    TRANSCRIPT.lock().clear();
+    EXCHANGES.lock().clear();
+}
+
+struct Budget {
+    total: usize,
+    remaining: usize,
+    sections: Vec<ContextSection>,
+}
+
+impl Budget {
+    /// Tokens a source with `share` of the total may use now
+    fn allowance(&self, share: f32) -> usize {
+        ((self.total as f32 * share) as usize).min(self.remaining)
+    }
+
+    /// Fit `text` into its allowance and record it as a section; `None` when nothing fits
+    fn take(&mut self, name: &str, text: &str, share: f32, already_truncated: bool) -> Option<String> {
+        let (content, truncated) = truncate_to_tokens(text.trim(), self.allowance(share));
+        if content.trim().is_empty() {
+            return None;
+        }
+        let tokens = estimate_tokens(&content);
+        self.remaining = self.remaining.saturating_sub(tokens);
+        self.sections.push(ContextSection {
+            name: name.to_string(),
+            content: content.clone(),
+            tokens,
+            truncated: truncated || already_truncated,
+        });
+        Some(content)
+    }
+}
+
+pub struct ContextBuilder {
+    context: InterviewContext,
+    question: Option<String>,
+    budget_tokens: usize,
+}
+
+impl ContextBuilder {
+    /// Start from the stored interview context
+    pub(crate) fn new(state: &AppState) -> Self {
+        Self {
+            context: state.interview_context.lock().clone(),
+            question: None,
+            budget_tokens: OPENAI_BUDGET_TOKENS,
+        }
+    }
+
+    /// Per-request company, position and job description; `None` keeps the stored value
+    pub fn overrides(mut self, company: Option<String>, position: Option<String>, job_description: Option<String>) -> Self {
+        if let Some(company) = company {
+            self.context.company = Some(company);
+        }
+        if let Some(position) = position {
+            self.context.position = Some(position);
+        }
+        if let Some(job_description) = job_description {
+            self.context.job_description = Some(job_description);
+        }
+        self
+    }
+
+    /// The question being answered; resume chunks are picked by how well they match it
+    pub fn question(mut self, question: &str) -> Self {
+        self.question = Some(question.to_string());
+        self
+    }
+
+    /// Use the token budget of `provider` ("openai" or "pollinations")
+    pub fn provider(mut self, provider: &str) -> Self {
+        self.budget_tokens = if provider.eq_ignore_ascii_case("openai") { OPENAI_BUDGET_TOKENS } else { POLLINATIONS_BUDGET_TOKENS };
+        self
+    }
+
+    fn assemble(self) -> (InterviewContext, Vec<ContextSection>, usize) {
+        let ContextBuilder { mut context, question, budget_tokens } = self;
+        let mut budget = Budget { total: budget_tokens, remaining: budget_tokens, sections: Vec::new() };
+
+        context.job_description = context
+            .job_description
+            .take()
+            .and_then(|jd| budget.take("job_description", &jd, JOB_DESCRIPTION_SHARE, false));
+
+        // Best-matching resume chunks, kept in resume order
+        context.resume_content = context.resume_content.take().and_then(|resume| {
+            let chunks = resume_chunks(&resume);
+            let query = keywords(&format!(
+                "{} {}",
+                question.as_deref().unwrap_or_default(),
+                context.position.as_deref().unwrap_or_default()
+            ));
+            let mut ranked: Vec<(usize, usize)> = chunks
+                .iter()
+                .enumerate()
+                .map(|(i, chunk)| (i, keywords(chunk).intersection(&query).count()))
+                .collect();
+            ranked.sort_by_key(|(i, score)| (std::cmp::Reverse(*score), *i));
+            let allowance = budget.allowance(RESUME_SHARE) * 4;
+            let mut picked = Vec::new();
+            let mut used = 0;
+            for (i, _) in ranked {
+                if used + chunks[i].len() <= allowance {
+                    used += chunks[i].len() + 2;
+                    picked.push(i);
+                }
+            }
+            picked.sort_unstable();
+            let selected = picked.iter().map(|i| chunks[*i].as_str()).collect::<Vec<_>>().join("\n\n");
+            budget.take("resume", &selected, RESUME_SHARE, picked.len() < chunks.len())
+        });
+
+        // Newest transcript lines that fit, oldest first
+        let transcript: Vec<String> = TRANSCRIPT.lock().iter().cloned().collect();
+        let allowance = budget.allowance(TRANSCRIPT_SHARE) * 4;
+        let mut lines = Vec::new();
+        let mut used = 0;
+        for line in transcript.iter().rev() {
+            if used + line.len() + 1 > allowance {
+                break;
+            }
+            used += line.len() + 1;
+            lines.push(line.as_str());
+        }
+        lines.reverse();
+        context.recent_transcript =
+            budget.take("recent_transcript", &lines.join("\n"), TRANSCRIPT_SHARE, lines.len() < transcript.len());
+
+        let prior_qa: String = EXCHANGES
+            .lock()
+            .iter()
+            .rev()
+            .map(|(q, a)| format!("Q: {}\nA: {}\n", q, a))
+            .collect();
+        context.prior_qa = budget.take("prior_qa", &prior_qa, PRIOR_QA_SHARE, false);
+
+        let used_tokens = budget.total - budget.remaining;
+        (context, budget.sections, used_tokens)
+    }
+
+    pub fn build(self) -> InterviewContext {
+        self.assemble().0
+    }
+
+    pub fn preview(self, provider: &str) -> ContextPreview {
+        let budget_tokens = self.budget_tokens;
+        let (context, sections, used_tokens) = self.assemble();
+        let system_prompt = if provider.eq_ignore_ascii_case("openai") {
+            crate::openai::OpenAIClient::build_system_prompt(&context)
+        } else {
+            crate::pollinations::PollinationsClient::build_system_prompt(&context)
+        };
+        ContextPreview {
+            budget_tokens,
+            used_tokens,
+            company: context.company,
+            position: context.position,
+            sections,
+            system_prompt,
+        }
+    }
+}
+
+/// The context and system prompt an answer to `question` would get right now
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn preview_prompt_context(
+    state: State<'_, AppState>,
+    question: Option<String>,
+    provider: Option<String>,
+) -> Response<ContextPreview> {
+    envelope::respond("preview_prompt_context", async move {
+        let provider = provider.unwrap_or_else(|| "pollinations".to_string());
+        let mut builder = ContextBuilder::new(&state).provider(&provider);
+        if let Some(question) = &question {
+            builder = builder.question(question);
+        }
+        Ok(builder.preview(&provider))
+    })
+    .await
+}
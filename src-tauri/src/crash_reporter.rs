@@ -0,0 +1,137 @@
+// Crash reporting for MockMate
+// Panic hook that persists a crash report (backtrace, version, recent logs) to disk
+// Panics inside tokio tasks go through the same hook, so background failures are captured too
+
+use std::backtrace::Backtrace;
+use std::fs;
+use std::path::PathBuf;
+
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::logging;
+use crate::envelope::{self, Response};
+
+/// Number of recent log entries stored with each crash report
+const CRASH_LOG_LINES: usize = 200;
+/// Number of crash reports kept on disk
+const MAX_CRASH_REPORTS: usize = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CrashReport {
+    pub timestamp: String,
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub thread: String,
+    /// True when the panic happened on a tokio runtime thread (e.g. a spawned task)
+    pub in_async_task: bool,
+    pub message: String,
+    pub location: Option<String>,
+    pub backtrace: String,
+    pub recent_logs: Vec<logging::LogEntry>,
+    #[serde(default)]
+    pub file_path: Option<String>,
+}
+
+/// Directory holding crash reports (%APPDATA%\MockMate\crashes)
+pub fn crash_dir() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("crashes")
+}
+
+/// Install the panic hook; the default hook still runs afterwards so stderr output is unchanged
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |panic_info| {
+        let message = if let Some(s) = panic_info.payload().downcast_ref::<&str>() {
+            s.to_string()
+        } else if let Some(s) = panic_info.payload().downcast_ref::<String>() {
+            s.clone()
+        } else {
+            "Unknown panic payload".to_string()
+        };
+
+        let report = CrashReport {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            thread: std::thread::current().name().unwrap_or("<unnamed>").to_string(),
+            in_async_task: tokio::runtime::Handle::try_current().is_ok(),
+            message,
+            location: panic_info
+                .location()
+                .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+            backtrace: Backtrace::force_capture().to_string(),
+            recent_logs: logging::recent_entries(CRASH_LOG_LINES),
+            file_path: None,
+        };
+
+        match write_crash_report(&report) {
+            Ok(path) => error!("💥 Panic captured, crash report written to {}", path.display()),
+            Err(e) => eprintln!("Failed to write crash report: {}", e),
+        }
+
+        default_hook(panic_info);
+    }));
+
+    info!("✅ Crash reporter installed");
+}
+
+fn write_crash_report(report: &CrashReport) -> std::io::Result<PathBuf> {
+    let dir = crash_dir();
+    fs::create_dir_all(&dir)?;
+
+    let stamp = chrono::Utc::now().format("%Y%m%d-%H%M%S%.3f");
+    let path = dir.join(format!("crash-{}.json", stamp));
+    let body = serde_json::to_vec_pretty(report)?;
+    fs::write(&path, body)?;
+
+    prune_old_reports();
+    Ok(path)
+}
+
+/// Crash report files, newest first
+fn crash_report_files() -> Vec<PathBuf> {
+    let mut files: Vec<(PathBuf, std::time::SystemTime)> = fs::read_dir(crash_dir())
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_name().to_string_lossy().starts_with("crash-"))
+                .filter_map(|e| Some((e.path(), e.metadata().and_then(|m| m.modified()).ok()?)))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+    files.into_iter().map(|(p, _)| p).collect()
+}
+
+fn prune_old_reports() {
+    for path in crash_report_files().into_iter().skip(MAX_CRASH_REPORTS) {
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// Return the most recent crash report, if any, so the UI can offer to send it
+#[tauri::command]
+#[specta::specta]
+pub async fn get_last_crash_report() -> Response<Option<CrashReport>> {
+    envelope::respond("get_last_crash_report", async move {
+        let Some(path) = crash_report_files().into_iter().next() else {
+            return Ok(None);
+        };
+
+        let body = fs::read(&path)
+            .map_err(|e| format!("Failed to read crash report {}: {}", path.display(), e))?;
+        let mut report: CrashReport = serde_json::from_slice(&body)
+            .map_err(|e| format!("Failed to parse crash report {}: {}", path.display(), e))?;
+        report.file_path = Some(path.display().to_string());
+
+        Ok(Some(report))
+    })
+    .await
+}
@@ -0,0 +1,248 @@
+// In-app credit top-up
+// `open_purchase_flow` asks the backend for a checkout page for the session's user and opens it in
+// the browser; the checkout returns to mockmate://credits/complete (or /cancelled) with the session
+// id. A second launch does not reach this process, so besides handling those links the app polls the
+// balance after opening the checkout until it changes, and `refresh_credit_balance` re-reads it on
+// demand. Every new balance is emitted as `credit-balance`, so the interview keeps running and the
+// UI shows the top-up without reconnecting.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::envelope::{self, Response};
+use crate::error::{AppError, ErrorCode};
+use crate::events;
+use crate::net_trace::TracedSend;
+
+pub const DEEP_LINK_PREFIX: &str = "mockmate://credits/";
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Polling stops after this long without a change; a late purchase is picked up by a refresh
+const POLL_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+
+/// Last known balance per session
+static BALANCES: Lazy<Mutex<HashMap<String, i32>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+/// Bumped for every purchase flow; a poll loop ends once a newer flow or a deep link supersedes it
+static PURCHASE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Payload of `credit-balance`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct CreditBalance {
+    pub session_id: String,
+    pub credits: i32,
+    /// Balance before this refresh, when one was known
+    pub previous_credits: Option<i32>,
+    /// "manual", "purchase_poll" or "deep_link"
+    pub source: String,
+    pub refreshed_at: DateTime<Utc>,
+}
+
+/// Payload of `credit-purchase`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct CreditPurchase {
+    pub session_id: String,
+    /// "opened", "completed" or "cancelled"
+    pub status: String,
+    pub checkout_url: Option<String>,
+}
+
+fn backend_url() -> String {
+    std::env::var("MOCKMATE_BACKEND_URL").unwrap_or_else(|_| "https://mockmate-backend.onrender.com".to_string())
+}
+
+fn session_or_current(session_id: Option<String>) -> Result<String, AppError> {
+    session_id
+        .filter(|s| !s.trim().is_empty())
+        .or_else(crate::audit::current_session)
+        .ok_or_else(|| AppError::new(ErrorCode::InvalidInput, "No session given and no session is active"))
+}
+
+async fn fetch_credits(session_id: &str) -> Result<i32, String> {
+    crate::database::initialize_database().await?;
+    Ok(crate::database::get_session_with_user_info(session_id).await?.credits_available)
+}
+
+/// Note a balance reported by the backend, e.g. remaining credits after activation
+pub fn remember(session_id: &str, credits: i32) {
+    BALANCES.lock().insert(session_id.to_string(), credits);
+}
+
+/// Re-read the balance of the session's user and emit it
+pub async fn refresh(app_handle: &AppHandle, session_id: &str, source: &str) -> Result<CreditBalance, String> {
+    let credits = fetch_credits(session_id).await?;
+    let previous_credits = BALANCES.lock().insert(session_id.to_string(), credits);
+    let balance = CreditBalance {
+        session_id: session_id.to_string(),
+        credits,
+        previous_credits,
+        source: source.to_string(),
+        refreshed_at: Utc::now(),
+    };
+    if previous_credits != Some(credits) {
+        info!("💳 Credits for session {}: {:?} -> {}", session_id, previous_credits, credits);
+    }
+    if let Err(e) = app_handle.emit(events::CREDIT_BALANCE, balance.clone()) {
+        warn!("Failed to emit {}: {}", events::CREDIT_BALANCE, e);
+    }
+    Ok(balance)
+}
+
+fn emit_purchase(app_handle: &AppHandle, session_id: &str, status: &str, checkout_url: Option<String>) {
+    let purchase = CreditPurchase { session_id: session_id.to_string(), status: status.to_string(), checkout_url };
+    if let Err(e) = app_handle.emit(events::CREDIT_PURCHASE, purchase) {
+        warn!("Failed to emit {}: {}", events::CREDIT_PURCHASE, e);
+    }
+}
+
+/// Poll until the balance changes from `before`, the flow is superseded or the timeout passes
+fn spawn_balance_poll(app_handle: AppHandle, session_id: String, mut before: Option<i32>) {
+    let generation = PURCHASE_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    tauri::async_runtime::spawn(async move {
+        let started = std::time::Instant::now();
+        while started.elapsed() < POLL_TIMEOUT {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            if PURCHASE_GENERATION.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            match fetch_credits(&session_id).await {
+                Ok(credits) if before.is_some_and(|before| credits > before) => {
+                    if let Err(e) = refresh(&app_handle, &session_id, "purchase_poll").await {
+                        warn!("Failed to refresh credits after purchase: {}", e);
+                    }
+                    emit_purchase(&app_handle, &session_id, "completed", None);
+                    return;
+                }
+                Ok(credits) => {
+                    before.get_or_insert(credits);
+                }
+                Err(e) => warn!("Credit balance poll failed: {}", e),
+            }
+        }
+        info!("💳 Stopped waiting for a credit purchase for session {}", session_id);
+    });
+}
+
+/// Handle a mockmate://credits/<action>?session_id=... launch: "complete" refreshes the balance,
+/// "cancelled" reports the cancelled checkout and "refresh" only re-reads the balance
+pub fn handle_deep_link(app_handle: &AppHandle, url: &str) {
+    let Ok(parsed) = reqwest::Url::parse(url) else {
+        warn!("Ignoring malformed credits link: {}", url);
+        return;
+    };
+    let action = parsed.path().trim_matches('/').to_string();
+    let session_id = parsed
+        .query_pairs()
+        .find(|(key, _)| key == "session_id")
+        .map(|(_, value)| value.into_owned())
+        .or_else(crate::audit::current_session);
+    let Some(session_id) = session_id else {
+        warn!("Ignoring credits link without a session: {}", url);
+        return;
+    };
+    info!("💳 Credits link: {} for session {}", action, session_id);
+
+    if let Some(main_window) = app_handle.get_webview_window("main") {
+        let _ = main_window.show();
+        let _ = main_window.set_focus();
+    }
+
+    match action.as_str() {
+        "complete" | "success" | "cancelled" | "cancel" => {
+            PURCHASE_GENERATION.fetch_add(1, Ordering::SeqCst);
+        }
+        "refresh" => {}
+        _ => {
+            warn!("Unknown credits link action: {}", action);
+            return;
+        }
+    }
+
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        match action.as_str() {
+            "cancelled" | "cancel" => emit_purchase(&app_handle, &session_id, "cancelled", None),
+            _ => {
+                if let Err(e) = refresh(&app_handle, &session_id, "deep_link").await {
+                    warn!("Failed to refresh credits from link: {}", e);
+                }
+                if action != "refresh" {
+                    emit_purchase(&app_handle, &session_id, "completed", None);
+                }
+            }
+        }
+    });
+}
+
+/// Open the credit checkout for the session's user in the browser and watch for the new balance.
+/// `token` is the session token from the web app; `pack` selects a credit pack on the checkout page
+#[tauri::command]
+#[specta::specta]
+pub async fn open_purchase_flow(
+    app_handle: AppHandle,
+    token: String,
+    session_id: Option<String>,
+    pack: Option<String>,
+) -> Response<CreditPurchase> {
+    envelope::respond("open_purchase_flow", async move {
+        let session_id = session_or_current(session_id)?;
+        let return_url = |action: &str| format!("{}{}?session_id={}", DEEP_LINK_PREFIX, action, urlencoding::encode(&session_id));
+
+        let response = reqwest::Client::new()
+            .post(format!("{}/api/credits/checkout", backend_url()))
+            .bearer_auth(&token)
+            .json(&serde_json::json!({
+                "session_id": session_id,
+                "pack": pack,
+                "success_url": return_url("complete"),
+                "cancel_url": return_url("cancelled"),
+            }))
+            .send_traced()
+            .await
+            .map_err(|e| e.context("Failed to start credit purchase"))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(AppError::new(ErrorCode::from_status(status), format!("Credit purchase failed: {}", status)));
+        }
+        let body: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+        let checkout_url = body["checkout_url"]
+            .as_str()
+            .ok_or("Credit purchase response did not include a checkout_url")?
+            .to_string();
+        tauri_plugin_opener::open_url(&checkout_url, None::<&str>).map_err(|e| format!("Failed to open browser: {}", e))?;
+
+        let known = BALANCES.lock().get(&session_id).copied();
+        let before = match known {
+            Some(credits) => Some(credits),
+            None => fetch_credits(&session_id).await.ok(),
+        };
+        if let Some(credits) = before {
+            BALANCES.lock().insert(session_id.clone(), credits);
+        }
+        spawn_balance_poll(app_handle.clone(), session_id.clone(), before);
+
+        info!("💳 Opened credit checkout for session {}", session_id);
+        emit_purchase(&app_handle, &session_id, "opened", Some(checkout_url.clone()));
+        Ok(CreditPurchase { session_id, status: "opened".to_string(), checkout_url: Some(checkout_url) })
+    })
+    .await
+}
+
+/// Re-read the credit balance of a session's user (the active session when none is given)
+#[tauri::command]
+#[specta::specta]
+pub async fn refresh_credit_balance(app_handle: AppHandle, session_id: Option<String>) -> Response<CreditBalance> {
+    envelope::respond("refresh_credit_balance", async move {
+        let session_id = session_or_current(session_id)?;
+        Ok(refresh(&app_handle, &session_id, "manual").await?)
+    })
+    .await
+}
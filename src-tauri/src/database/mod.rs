@@ -6,7 +6,7 @@ pub mod shared;
 pub use postgres::DatabaseManager;
 pub use models::{InterviewQuestion, InterviewAnswer};
 pub use sync::DatabaseSync;
-pub use shared::{Session, User, InterviewConfig, SessionWithUser, UserInfo, get_session_with_user_info, activate_session, disconnect_session, initialize_database};
+pub use shared::{Session, User, InterviewConfig, SessionWithUser, UserInfo, get_session_with_user_info, activate_session, activation_charged, disconnect_session, initialize_database};
 
 use thiserror::Error;
 
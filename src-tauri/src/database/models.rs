@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
+use specta::Type;
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct Session {
     pub id: Uuid,
     pub user_id: Uuid,
@@ -19,7 +20,7 @@ pub struct Session {
     pub credits_used: Option<i32>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct User {
     pub id: Uuid,
     pub email: String,
@@ -30,20 +31,110 @@ pub struct User {
     pub last_active: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct InterviewQuestion {
     pub id: Uuid,
     pub session_id: Uuid,
     pub question_number: i32,
     pub question_text: String,
     pub category: String,
+    /// Behavioral / coding / system design / domain, classified when the question is saved
+    #[serde(default)]
+    pub topic: Option<crate::question_topic::QuestionTopic>,
     pub difficulty_level: String,
     pub expected_duration: i32,
     pub asked_at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// One entry of a `save_interview_items` batch
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum InterviewItem {
+    Question {
+        /// Lets answers later in the same batch refer to this question before it has an ID
+        #[serde(default)]
+        client_ref: Option<String>,
+        question_number: i32,
+        question_text: String,
+        category: String,
+        difficulty_level: String,
+        expected_duration: i32,
+    },
+    Answer {
+        /// A saved question's ID, or the `client_ref` of a question earlier in the batch
+        question_id: String,
+        answer_text: String,
+        #[serde(default)]
+        response_time: Option<i32>,
+        #[serde(default)]
+        ai_feedback: Option<String>,
+        #[serde(default)]
+        ai_score: Option<i32>,
+    },
+    TranscriptSegment {
+        /// "system_audio" or "zoom_captions"
+        source: String,
+        #[serde(default)]
+        speaker: Option<String>,
+        text: String,
+        timestamp: DateTime<Utc>,
+    },
+}
+
+/// Sort direction of a paged query, by timestamp
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Paging, ordering and filters for session question/answer queries. Every field is optional;
+/// the default returns the whole session, oldest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+#[serde(default)]
+pub struct SessionItemQuery {
+    /// No limit when unset
+    pub limit: Option<u32>,
+    pub offset: u32,
+    pub order: SortOrder,
+    /// Questions only: the category the question was saved with
+    pub category: Option<String>,
+    /// Questions only
+    pub topic: Option<crate::question_topic::QuestionTopic>,
+    /// Only items at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only items before this time
+    pub until: Option<DateTime<Utc>>,
+    /// Questions only: true for answered questions, false for unanswered ones
+    pub answered: Option<bool>,
+}
+
+/// One page of a paged query
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Matching items across all pages
+    pub total: i64,
+    pub offset: u32,
+    pub has_more: bool,
+}
+
+impl<T> Page<T> {
+    pub(crate) fn new(items: Vec<T>, total: i64, query: &SessionItemQuery) -> Self {
+        let has_more = i64::from(query.offset) + (items.len() as i64) < total;
+        Page {
+            items,
+            total,
+            offset: query.offset,
+            has_more,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct InterviewAnswer {
     pub id: Uuid,
     pub question_id: Uuid,
@@ -105,7 +196,7 @@ pub struct InterviewConfig {
     pub resume_content: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct SessionInfo {
     pub id: String,
     pub job_title: String,
@@ -1,22 +1,72 @@
 use deadpool_postgres::{Config, Pool, Runtime};
+use tokio_postgres::types::ToSql;
 use tokio_postgres::NoTls;
 use uuid::Uuid;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use log::{info, error};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::HashMap;
 use std::str::FromStr;
 use serde::{Serialize, Deserialize};
+use specta::Type;
 
 use super::{DatabaseError, Result};
 use super::models::*;
 use crate::database::models::SessionInfo;
+use crate::envelope::{self, Response};
+use crate::error::{AppError, ErrorCode};
+use crate::guard::{self, Require};
+use crate::audit;
+use crate::question_topic::QuestionTopic;
 
 #[derive(Debug)]
 pub struct DatabaseManager {
     pool: Pool,
 }
 
+/// Metadata JSON stored with a question message
+fn question_metadata(
+    question_number: i32,
+    question_text: &str,
+    category: &str,
+    difficulty_level: &str,
+    expected_duration: i32,
+    now: DateTime<Utc>,
+) -> serde_json::Value {
+    serde_json::json!({
+        "questionNumber": question_number,
+        "category": category,
+        "topic": QuestionTopic::categorize(category, question_text).as_str(),
+        "difficulty": difficulty_level,
+        "expectedDuration": expected_duration,
+        "source": "desktop_app",
+        "timestamp": now.to_rfc3339()
+    })
+}
+
+/// Metadata JSON stored with an answer message
+fn answer_metadata(
+    question_id: &Uuid,
+    response_time: Option<i32>,
+    ai_feedback: Option<&str>,
+    ai_score: Option<i32>,
+    now: DateTime<Utc>,
+) -> serde_json::Value {
+    serde_json::json!({
+        "questionId": question_id,
+        "responseTime": response_time,
+        "aiFeedback": ai_feedback,
+        "aiScore": ai_score,
+        "source": "desktop_app",
+        "timestamp": now.to_rfc3339()
+    })
+}
+
 impl DatabaseManager {
     pub async fn new() -> Result<Self> {
+        crate::privacy::ensure_cloud_allowed("Postgres").map_err(DatabaseError::ConnectionFailed)?;
+
         // Read database configuration from compile-time embedded variables (build.rs)
         // Fall back to runtime env vars for development
         let host = if let Some(value) = option_env!("DB_HOST") {
@@ -245,15 +295,7 @@ impl DatabaseManager {
         let message_id = Uuid::new_v4();
         let now = Utc::now();
 
-        // Create metadata JSON with question details
-        let metadata = serde_json::json!({
-            "questionNumber": question_number,
-            "category": category,
-            "difficulty": difficulty_level,
-            "expectedDuration": expected_duration,
-            "source": "desktop_app",
-            "timestamp": now.to_rfc3339()
-        });
+        let metadata = question_metadata(question_number, question_text, category, difficulty_level, expected_duration, now);
 
         client
             .execute(
@@ -277,6 +319,13 @@ impl DatabaseManager {
             })?;
 
         info!("Inserted interview question {} for session {}", message_id, session_id);
+        audit::record_for_session(
+            "postgres",
+            "save_question",
+            audit::PayloadCategory::Text,
+            (question_text.len() + metadata.to_string().len()) as u64,
+            Some(session_id.to_string()),
+        );
         Ok(message_id)
     }
 
@@ -298,15 +347,7 @@ impl DatabaseManager {
         let message_id = Uuid::new_v4();
         let now = Utc::now();
 
-        // Create metadata JSON with answer details
-        let metadata = serde_json::json!({
-            "questionId": question_id,
-            "responseTime": response_time,
-            "aiFeedback": ai_feedback,
-            "aiScore": ai_score,
-            "source": "desktop_app",
-            "timestamp": now.to_rfc3339()
-        });
+        let metadata = answer_metadata(question_id, response_time, ai_feedback, ai_score, now);
 
         // Use answer_text or default to empty string if None
         let content = answer_text.unwrap_or("");
@@ -334,9 +375,101 @@ impl DatabaseManager {
             })?;
 
         info!("Inserted interview answer {} for session {}", message_id, session_id);
+        audit::record_for_session(
+            "postgres",
+            "save_answer",
+            audit::PayloadCategory::Text,
+            (content.len() + metadata.to_string().len()) as u64,
+            Some(session_id.to_string()),
+        );
         Ok(message_id)
     }
 
+    /// Write questions, answers and transcript segments in one transaction. Returns the new
+    /// message IDs in batch order; nothing is written if any item fails
+    pub async fn insert_interview_items(&self, session_id: &str, items: &[InterviewItem]) -> Result<Vec<Uuid>> {
+        let mut client = self.pool.get().await
+            .map_err(|e| DatabaseError::ConnectionFailed(e.to_string()))?;
+        
+        let session_uuid = Uuid::from_str(session_id)
+            .map_err(|_| DatabaseError::SessionNotFound("Invalid session ID format".to_string()))?;
+
+        let failed = |e: tokio_postgres::Error| {
+            error!("Failed to save interview items for session {}: {}", session_id, e);
+            DatabaseError::QueryFailed(format!("Failed to save interview items: {}", e))
+        };
+        let transaction = client.transaction().await.map_err(failed)?;
+        let insert_sql = |message_type: &str| {
+            format!(
+                r#"
+                INSERT INTO interview_messages 
+                (id, session_id, message_type, content, metadata, timestamp, parent_message_id)
+                VALUES ($1, $2, '{}', $3, $4, $5, $6)
+                "#,
+                message_type
+            )
+        };
+        let insert_question = transaction.prepare(&insert_sql("question")).await.map_err(failed)?;
+        let insert_answer = transaction.prepare(&insert_sql("answer")).await.map_err(failed)?;
+        let insert_transcript = transaction.prepare(&insert_sql("transcript")).await.map_err(failed)?;
+
+        let mut ids = Vec::with_capacity(items.len());
+        // Questions saved earlier in this batch, by client_ref
+        let mut refs: HashMap<&str, Uuid> = HashMap::new();
+        let mut bytes = 0;
+        for item in items {
+            let message_id = Uuid::new_v4();
+            let now = Utc::now();
+            let (statement, content, metadata, timestamp, parent) = match item {
+                InterviewItem::Question { client_ref, question_number, question_text, category, difficulty_level, expected_duration } => {
+                    if let Some(client_ref) = client_ref {
+                        refs.insert(client_ref, message_id);
+                    }
+                    let metadata = question_metadata(*question_number, question_text, category, difficulty_level, *expected_duration, now);
+                    (&insert_question, question_text, metadata, now, None)
+                }
+                InterviewItem::Answer { question_id, answer_text, response_time, ai_feedback, ai_score } => {
+                    let question_uuid = match refs.get(question_id.as_str()) {
+                        Some(id) => *id,
+                        None => Uuid::from_str(question_id)
+                            .map_err(|_| DatabaseError::QueryFailed(format!("Unknown question reference: {}", question_id)))?,
+                    };
+                    let metadata = answer_metadata(&question_uuid, *response_time, ai_feedback.as_deref(), *ai_score, now);
+                    (&insert_answer, answer_text, metadata, now, Some(question_uuid))
+                }
+                InterviewItem::TranscriptSegment { source, speaker, text, timestamp } => {
+                    let metadata = serde_json::json!({
+                        "transcriptSource": source,
+                        "speaker": speaker,
+                        "source": "desktop_app",
+                        "timestamp": timestamp.to_rfc3339()
+                    });
+                    (&insert_transcript, text, metadata, *timestamp, None)
+                }
+            };
+            transaction
+                .execute(
+                    statement,
+                    &[&message_id, &session_uuid, content, &metadata, &timestamp.naive_utc(), &parent],
+                )
+                .await
+                .map_err(failed)?;
+            bytes += (content.len() + metadata.to_string().len()) as u64;
+            ids.push(message_id);
+        }
+        transaction.commit().await.map_err(failed)?;
+
+        info!("Inserted {} interview items for session {}", ids.len(), session_id);
+        audit::record_for_session(
+            "postgres",
+            "save_items",
+            audit::PayloadCategory::Text,
+            bytes,
+            Some(session_id.to_string()),
+        );
+        Ok(ids)
+    }
+
     pub async fn update_session_duration_and_credits(
         &self, 
         session_id: &str, 
@@ -493,37 +626,62 @@ impl DatabaseManager {
     }
 
     // Session data access methods - now reading from interview_messages table
-    pub async fn get_session_questions(&self, session_id: &str) -> Result<Vec<InterviewQuestion>> {
+    pub async fn get_session_questions(&self, session_id: &str, query: &SessionItemQuery) -> Result<Page<InterviewQuestion>> {
         let client = self.pool.get().await
             .map_err(|e| DatabaseError::ConnectionFailed(e.to_string()))?;
         
         let session_uuid = Uuid::from_str(session_id)
             .map_err(|_| DatabaseError::SessionNotFound("Invalid session ID format".to_string()))?;
 
+        let mut filter = MessageFilter::new(session_uuid, "question", query);
+        if let Some(category) = &query.category {
+            filter.push("COALESCE(metadata->>'category', 'general') = {}", category.clone());
+        }
+        if let Some(answered) = query.answered {
+            let exists = "EXISTS (SELECT 1 FROM interview_messages a WHERE a.parent_message_id = interview_messages.id AND a.message_type = 'answer')";
+            filter.clauses.push(if answered { exists.to_string() } else { format!("NOT {}", exists) });
+        }
+
+        // The topic of older questions is only known after classifying their text, so a topic
+        // filter pages in memory; everything else pages in SQL
+        let page_in_sql = query.topic.is_none();
         let rows = client
             .query(
-                r#"
-                SELECT id, session_id, content, metadata, timestamp
-                FROM interview_messages
-                WHERE session_id = $1 AND message_type = 'question'
-                ORDER BY timestamp ASC
-                "#,
-                &[&session_uuid]
+                &format!(
+                    r#"
+                    SELECT id, session_id, content, metadata, timestamp
+                    FROM interview_messages
+                    WHERE {}
+                    {}
+                    "#,
+                    filter.where_sql(),
+                    filter.order_sql(page_in_sql),
+                ),
+                &filter.params(),
             )
             .await
             .map_err(|e| {
                 error!("Failed to fetch questions for session {}: {}", session_id, e);
                 DatabaseError::QueryFailed(format!("Failed to fetch questions: {}", e))
             })?;
+        let total = if page_in_sql {
+            filter.count(&client).await?
+        } else {
+            rows.len() as i64
+        };
 
         let mut questions = Vec::new();
         for (index, row) in rows.iter().enumerate() {
+            let position = match query.order {
+                SortOrder::Asc => i64::from(query.offset) + index as i64 + 1,
+                SortOrder::Desc => total - i64::from(query.offset) - index as i64,
+            };
             let metadata: Option<serde_json::Value> = row.get(3);
             let question_number = metadata
                 .as_ref()
                 .and_then(|m| m.get("questionNumber"))
                 .and_then(|n| n.as_i64())
-                .unwrap_or((index + 1) as i64) as i32;
+                .unwrap_or(position) as i32;
             
             let category = metadata
                 .as_ref()
@@ -531,6 +689,17 @@ impl DatabaseManager {
                 .and_then(|c| c.as_str())
                 .unwrap_or("general");
                 
+            // Questions saved before categorization have no topic; classify them on the way out
+            let topic = metadata
+                .as_ref()
+                .and_then(|m| m.get("topic"))
+                .and_then(|t| t.as_str())
+                .and_then(QuestionTopic::parse)
+                .unwrap_or_else(|| {
+                    let text: String = row.get(2);
+                    QuestionTopic::categorize(category, &text)
+                });
+
             let difficulty = metadata
                 .as_ref()
                 .and_then(|m| m.get("difficulty"))
@@ -549,6 +718,7 @@ impl DatabaseManager {
                 question_number,
                 question_text: row.get(2),
                 category: category.to_string(),
+                topic: Some(topic),
                 difficulty_level: difficulty.to_string(),
                 expected_duration,
                 asked_at: row.get(4),
@@ -556,31 +726,47 @@ impl DatabaseManager {
             });
         }
 
-        Ok(questions)
+        if page_in_sql {
+            return Ok(Page::new(questions, total, query));
+        }
+        questions.retain(|q| q.topic == query.topic);
+        let total = questions.len() as i64;
+        let questions = questions
+            .into_iter()
+            .skip(query.offset as usize)
+            .take(query.limit.map_or(usize::MAX, |limit| limit as usize))
+            .collect();
+        Ok(Page::new(questions, total, query))
     }
 
-    pub async fn get_session_answers(&self, session_id: &str) -> Result<Vec<InterviewAnswer>> {
+    pub async fn get_session_answers(&self, session_id: &str, query: &SessionItemQuery) -> Result<Page<InterviewAnswer>> {
         let client = self.pool.get().await
             .map_err(|e| DatabaseError::ConnectionFailed(e.to_string()))?;
         
         let session_uuid = Uuid::from_str(session_id)
             .map_err(|_| DatabaseError::SessionNotFound("Invalid session ID format".to_string()))?;
 
+        let filter = MessageFilter::new(session_uuid, "answer", query);
         let rows = client
             .query(
-                r#"
-                SELECT id, parent_message_id, session_id, content, metadata, timestamp
-                FROM interview_messages
-                WHERE session_id = $1 AND message_type = 'answer'
-                ORDER BY timestamp ASC
-                "#,
-                &[&session_uuid]
+                &format!(
+                    r#"
+                    SELECT id, parent_message_id, session_id, content, metadata, timestamp
+                    FROM interview_messages
+                    WHERE {}
+                    {}
+                    "#,
+                    filter.where_sql(),
+                    filter.order_sql(true),
+                ),
+                &filter.params(),
             )
             .await
             .map_err(|e| {
                 error!("Failed to fetch answers for session {}: {}", session_id, e);
                 DatabaseError::QueryFailed(format!("Failed to fetch answers: {}", e))
             })?;
+        let total = filter.count(&client).await?;
 
         let mut answers = Vec::new();
         for row in rows {
@@ -616,14 +802,14 @@ impl DatabaseManager {
             });
         }
 
-        Ok(answers)
+        Ok(Page::new(answers, total, query))
     }
 
     pub async fn get_session_report(&self, session_id: &str) -> Result<SessionReport> {
         let session = self.get_session_by_id(session_id).await?;
         let user = self.get_user_by_id(&session.user_id.to_string()).await?;
-        let questions = self.get_session_questions(session_id).await?;
-        let answers = self.get_session_answers(session_id).await?;
+        let questions = self.get_session_questions(session_id, &SessionItemQuery::default()).await?.items;
+        let answers = self.get_session_answers(session_id, &SessionItemQuery::default()).await?.items;
 
         let total_questions = questions.len() as i32;
         let total_answers = answers.len() as i32;
@@ -658,6 +844,8 @@ impl DatabaseManager {
             total_answers,
             average_response_time,
             average_score,
+            pacing: crate::pacing::report(session_id),
+            evaluations: crate::rubric::evaluations(session_id),
         })
     }
 
@@ -697,7 +885,7 @@ impl DatabaseManager {
 }
 
 // Additional data structures for reports
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct SessionReport {
     pub session: Session,
     pub user: User,
@@ -707,50 +895,137 @@ pub struct SessionReport {
     pub total_answers: i32,
     pub average_response_time: f64,
     pub average_score: f64,
+    /// Talk time, pace and filler words per answer, when measured locally
+    #[serde(default)]
+    pub pacing: Option<crate::pacing::PacingReport>,
+    /// Rubric scores and comments per answer, when evaluated
+    #[serde(default)]
+    pub evaluations: Vec<crate::rubric::AnswerEvaluation>,
 }
 
-// Tauri commands for database operations
-#[tauri::command]
-pub async fn test_database_connection() -> std::result::Result<String, String> {
-    match DatabaseManager::new().await {
-        Ok(db) => {
-            db.test_connection().await
-                .map_err(|e| e.to_string())
+/// WHERE clause, parameters and paging shared by the question and answer queries
+struct MessageFilter {
+    clauses: Vec<String>,
+    params: Vec<Box<dyn ToSql + Sync + Send>>,
+    order: SortOrder,
+    limit: Option<u32>,
+    offset: u32,
+}
+
+impl MessageFilter {
+    fn new(session_uuid: Uuid, message_type: &'static str, query: &SessionItemQuery) -> Self {
+        let mut filter = MessageFilter {
+            clauses: vec![format!("message_type = '{}'", message_type)],
+            params: Vec::new(),
+            order: query.order,
+            limit: query.limit,
+            offset: query.offset,
+        };
+        filter.push("session_id = {}", session_uuid);
+        // Cast so the comparison works whether the column has a time zone or not
+        if let Some(since) = query.since {
+            filter.push("timestamp >= {}::timestamptz", since);
         }
-        Err(e) => {
-            log::warn!("Database connection test failed: {}", e);
-            Ok(format!("Database unavailable: {}", e))
+        if let Some(until) = query.until {
+            filter.push("timestamp < {}::timestamptz", until);
+        }
+        filter
+    }
+
+    /// Add a condition; `{}` in the clause stands for the value's placeholder
+    fn push<T: ToSql + Sync + Send + 'static>(&mut self, clause: &str, value: T) {
+        self.params.push(Box::new(value));
+        self.clauses.push(clause.replace("{}", &format!("${}", self.params.len())));
+    }
+
+    fn where_sql(&self) -> String {
+        self.clauses.join(" AND ")
+    }
+
+    fn order_sql(&self, paged: bool) -> String {
+        let direction = match self.order {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        };
+        let mut sql = format!("ORDER BY timestamp {}", direction);
+        if paged {
+            if let Some(limit) = self.limit {
+                sql.push_str(&format!(" LIMIT {}", limit));
+            }
+            if self.offset > 0 {
+                sql.push_str(&format!(" OFFSET {}", self.offset));
+            }
         }
+        sql
+    }
+
+    fn params(&self) -> Vec<&(dyn ToSql + Sync)> {
+        self.params.iter().map(|param| &**param as &(dyn ToSql + Sync)).collect()
+    }
+
+    async fn count(&self, client: &deadpool_postgres::Client) -> Result<i64> {
+        let row = client
+            .query_one(
+                &format!("SELECT COUNT(*) FROM interview_messages WHERE {}", self.where_sql()),
+                &self.params(),
+            )
+            .await
+            .map_err(|e| DatabaseError::QueryFailed(format!("Failed to count session items: {}", e)))?;
+        Ok(row.get(0))
     }
 }
 
+// Tauri commands for database operations
 #[tauri::command]
-pub async fn get_db_session_info(session_id: String) -> std::result::Result<SessionInfo, String> {
-    let db = DatabaseManager::new().await
-        .map_err(|e| e.to_string())?;
+#[specta::specta]
+pub async fn test_database_connection() -> Response<String> {
+    envelope::respond("test_database_connection", async move {
+        match DatabaseManager::new().await {
+            Ok(db) => {
+                db.test_connection().await
+                    .map_err(|e| AppError::from(e.to_string()))
+            }
+            Err(e) => {
+                log::warn!("Database connection test failed: {}", e);
+                Ok(format!("Database unavailable: {}", e))
+            }
+        }
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_db_session_info(session_id: String) -> Response<SessionInfo> {
+    envelope::respond("get_db_session_info", async move {
+        let db = DatabaseManager::new().await
+            .map_err(|e| e.to_string())?;
     
-    let session = db.get_session_by_id(&session_id).await
-        .map_err(|e| e.to_string())?;
+        let session = db.get_session_by_id(&session_id).await
+            .map_err(|e| e.to_string())?;
     
-    let user = db.get_user_by_id(&session.user_id.to_string()).await
-        .map_err(|e| e.to_string())?;
+        let user = db.get_user_by_id(&session.user_id.to_string()).await
+            .map_err(|e| e.to_string())?;
 
-    let user_name = match user.last_name {
-        Some(last_name) => format!("{} {}", user.first_name, last_name),
-        None => user.first_name,
-    };
+        let user_name = match user.last_name {
+            Some(last_name) => format!("{} {}", user.first_name, last_name),
+            None => user.first_name,
+        };
 
-    Ok(SessionInfo {
-        id: session.id.to_string(),
-        job_title: session.job_title,
-        user_name,
-        difficulty: session.difficulty,
-        credits_available: user.credits,
-        status: session.status,
+        Ok(SessionInfo {
+            id: session.id.to_string(),
+            job_title: session.job_title,
+            user_name,
+            difficulty: session.difficulty,
+            credits_available: user.credits,
+            status: session.status,
+        })
     })
+    .await
 }
 
 #[tauri::command]
+#[specta::specta]
 pub async fn save_interview_question(
     session_id: String,
     question_number: i32,
@@ -758,170 +1033,378 @@ pub async fn save_interview_question(
     category: String,
     difficulty_level: String,
     expected_duration: i32
-) -> std::result::Result<String, String> {
-    info!("💾 Attempting to save interview question {} for session {}", question_number, session_id);
+) -> Response<String> {
+    guard::respond("save_interview_question", &[Require::Session(session_id.clone())], async move {
+        info!("💾 Attempting to save interview question {} for session {}", question_number, session_id);
+        // A repeat of a question saved moments ago from another source links to that record
+        if let Some(question_id) = crate::question_dedup::saved_question_id(&question_text) {
+            info!("🔁 Question is a duplicate of saved question {}, not saving it again", question_id);
+            return Ok(question_id);
+        }
+        let topic = QuestionTopic::categorize(&category, &question_text);
+        info!("🏷️ Question categorized as {}", topic.as_str());
+        crate::question_bank::record_asked(&question_text, &category, topic, &difficulty_level);
     
-    match DatabaseManager::new().await {
-        Ok(db) => {
-            match db.insert_interview_question(
-                &session_id,
-                question_number,
-                &question_text,
-                &category,
-                &difficulty_level,
-                expected_duration
-            ).await {
-                Ok(question_id) => {
-                    info!("✅ Question saved with ID: {}", question_id);
-                    Ok(question_id.to_string())
-                }
-                Err(e) => {
-                    log::warn!("❌ Failed to save question to database: {}", e);
-                    // Generate a fallback UUID for the question
-                    let fallback_id = uuid::Uuid::new_v4();
-                    log::info!("💡 Using fallback question ID: {}", fallback_id);
-                    Ok(fallback_id.to_string())
+        let question_id = match DatabaseManager::new().await {
+            Ok(db) => {
+                match db.insert_interview_question(
+                    &session_id,
+                    question_number,
+                    &question_text,
+                    &category,
+                    &difficulty_level,
+                    expected_duration
+                ).await {
+                    Ok(question_id) => {
+                        info!("✅ Question saved with ID: {}", question_id);
+                        question_id.to_string()
+                    }
+                    Err(e) => {
+                        log::warn!("❌ Failed to save question to database: {}", e);
+                        // Generate a fallback UUID for the question
+                        let fallback_id = uuid::Uuid::new_v4();
+                        log::info!("💡 Using fallback question ID: {}", fallback_id);
+                        fallback_id.to_string()
+                    }
                 }
             }
-        }
-        Err(e) => {
-            log::warn!("❌ Database unavailable for saving question: {}", e);
-            log::info!("💡 Database features disabled - generating fallback question ID");
-            // Generate a fallback UUID for the question
-            let fallback_id = uuid::Uuid::new_v4();
-            log::info!("💡 Using fallback question ID: {}", fallback_id);
-            Ok(fallback_id.to_string())
-        }
-    }
+            Err(e) => {
+                log::warn!("❌ Database unavailable for saving question: {}", e);
+                log::info!("💡 Database features disabled - generating fallback question ID");
+                // Generate a fallback UUID for the question
+                let fallback_id = uuid::Uuid::new_v4();
+                log::info!("💡 Using fallback question ID: {}", fallback_id);
+                fallback_id.to_string()
+            }
+        };
+
+        crate::question_dedup::link_saved(&question_text, &question_id);
+        crate::pacing::on_question(&session_id, &question_id, &question_text);
+        crate::webhooks::dispatch(
+            crate::webhooks::WebhookEvent::QuestionDetected,
+            serde_json::json!({
+                "session_id": session_id,
+                "question_id": question_id,
+                "question_number": question_number,
+                "question_text": question_text,
+                "category": category,
+                "topic": topic.as_str(),
+                "difficulty": difficulty_level,
+            }),
+        );
+        Ok(question_id)
+    })
+    .await
 }
 
 #[tauri::command]
+#[specta::specta]
 pub async fn save_interview_answer(
+    app: tauri::AppHandle,
     session_id: String,
     question_id: String,
     answer_text: String,
     response_time: i32,
     ai_feedback: Option<String>,
     ai_score: Option<i32>
-) -> std::result::Result<String, String> {
-    info!("🔥🔥🔥 BACKEND: save_interview_answer called with params:");
-    info!("  📋 session_id: {}", session_id);
-    info!("  🆔 question_id: {}", question_id);
-    info!("  📝 answer_text length: {}", answer_text.len());
-    info!("  📝 answer_text preview (first 200 chars): {}", answer_text.chars().take(200).collect::<String>());
-    info!("  ⏱️ response_time: {}", response_time);
+) -> Response<String> {
+    guard::respond("save_interview_answer", &[Require::Session(session_id.clone())], async move {
+        info!("🔥🔥🔥 BACKEND: save_interview_answer called with params:");
+        info!("  📋 session_id: {}", session_id);
+        info!("  🆔 question_id: {}", question_id);
+        info!("  📝 answer_text length: {}", answer_text.len());
+        info!("  📝 answer_text preview (first 200 chars): {}", answer_text.chars().take(200).collect::<String>());
+        info!("  ⏱️ response_time: {}", response_time);
     
-    match DatabaseManager::new().await {
-        Ok(db) => {
-            info!("✅ Database connection established successfully");
+        match DatabaseManager::new().await {
+            Ok(db) => {
+                info!("✅ Database connection established successfully");
             
-            match Uuid::from_str(&question_id) {
-                Ok(question_uuid) => {
-                    info!("✅ Question UUID parsed successfully: {}", question_uuid);
+                match Uuid::from_str(&question_id) {
+                    Ok(question_uuid) => {
+                        info!("✅ Question UUID parsed successfully: {}", question_uuid);
                     
-                    match db.insert_interview_answer(
-                        &question_uuid,
-                        &session_id,
-                        Some(&answer_text),
-                        Some(response_time),
-                        ai_feedback.as_deref(),
-                        ai_score
-                    ).await {
-                        Ok(answer_id) => {
-                            info!("✅✅✅ SUCCESS! Answer saved with ID: {}", answer_id);
-                            info!("✅ Saved answer length: {} characters", answer_text.len());
-                            Ok(answer_id.to_string())
-                        },
-                        Err(e) => {
-                            log::error!("❌❌❌ FAILED to insert answer into database: {}", e);
-                            log::error!("❌ Failed answer details: session_id={}, question_id={}, answer_length={}", session_id, question_id, answer_text.len());
-                            Err(format!("Database insert failed: {}", e))
+                        match db.insert_interview_answer(
+                            &question_uuid,
+                            &session_id,
+                            Some(&answer_text),
+                            Some(response_time),
+                            ai_feedback.as_deref(),
+                            ai_score
+                        ).await {
+                            Ok(answer_id) => {
+                                info!("✅✅✅ SUCCESS! Answer saved with ID: {}", answer_id);
+                                info!("✅ Saved answer length: {} characters", answer_text.len());
+                                crate::webhooks::dispatch(
+                                    crate::webhooks::WebhookEvent::AnswerGenerated,
+                                    serde_json::json!({
+                                        "session_id": session_id,
+                                        "question_id": question_id,
+                                        "answer_id": answer_id.to_string(),
+                                        "answer_text": answer_text,
+                                        "response_time": response_time,
+                                        "ai_score": ai_score,
+                                    }),
+                                );
+                                crate::rubric::on_answer_saved(&app, &session_id, &question_id, &answer_id.to_string(), &answer_text);
+                                Ok(answer_id.to_string())
+                            },
+                            Err(e) => {
+                                log::error!("❌❌❌ FAILED to insert answer into database: {}", e);
+                                log::error!("❌ Failed answer details: session_id={}, question_id={}, answer_length={}", session_id, question_id, answer_text.len());
+                                Err(format!("Database insert failed: {}", e).into())
+                            }
                         }
+                    },
+                    Err(_) => {
+                        log::error!("❌ Invalid question ID format: {}", question_id);
+                        Err("Invalid question ID format".into())
                     }
-                },
-                Err(_) => {
-                    log::error!("❌ Invalid question ID format: {}", question_id);
-                    Err("Invalid question ID format".to_string())
                 }
+            },
+            Err(e) => {
+                log::error!("❌❌❌ FAILED to connect to database: {}", e);
+                Err(format!("Database connection failed: {}", e).into())
             }
+        }
+    })
+    .await
+}
+
+/// Save several questions, answers and transcript segments in one transaction; returns the new
+/// IDs in batch order. Answers may refer to a question earlier in the batch by its `client_ref`
+#[tauri::command]
+#[specta::specta]
+pub async fn save_interview_items(
+    app: tauri::AppHandle,
+    session_id: String,
+    items: Vec<InterviewItem>,
+) -> Response<Vec<String>> {
+    guard::respond("save_interview_items", &[Require::Session(session_id.clone())], async move {
+        info!("💾 Saving {} interview items for session {}", items.len(), session_id);
+        if items.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut client_refs = Vec::new();
+        for item in &items {
+            match item {
+                InterviewItem::Question { client_ref: Some(client_ref), .. } => client_refs.push(client_ref.as_str()),
+                InterviewItem::Answer { question_id, .. }
+                    if !client_refs.contains(&question_id.as_str()) && Uuid::from_str(question_id).is_err() =>
+                {
+                    return Err(AppError::new(
+                        ErrorCode::InvalidInput,
+                        format!("Answer refers to unknown question '{}'", question_id),
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        let db = DatabaseManager::new().await
+            .map_err(|e| e.to_string())?;
+        let ids = db.insert_interview_items(&session_id, &items).await
+            .map_err(|e| e.to_string())?;
+        info!("✅ Saved {} interview items", ids.len());
+
+        // Same follow-up as saving the items one by one
+        let mut saved_questions: HashMap<&str, String> = HashMap::new();
+        for (item, id) in items.iter().zip(&ids) {
+            let id = id.to_string();
+            match item {
+                InterviewItem::Question { client_ref, question_number, question_text, category, difficulty_level, .. } => {
+                    let topic = QuestionTopic::categorize(category, question_text);
+                    crate::question_bank::record_asked(question_text, category, topic, difficulty_level);
+                    crate::pacing::on_question(&session_id, &id, question_text);
+                    crate::webhooks::dispatch(
+                        crate::webhooks::WebhookEvent::QuestionDetected,
+                        serde_json::json!({
+                            "session_id": session_id,
+                            "question_id": id,
+                            "question_number": question_number,
+                            "question_text": question_text,
+                            "category": category,
+                            "topic": topic.as_str(),
+                            "difficulty": difficulty_level,
+                        }),
+                    );
+                    if let Some(client_ref) = client_ref {
+                        saved_questions.insert(client_ref, id);
+                    }
+                }
+                InterviewItem::Answer { question_id, answer_text, response_time, ai_score, .. } => {
+                    let question_id = saved_questions.get(question_id.as_str()).unwrap_or(question_id);
+                    crate::webhooks::dispatch(
+                        crate::webhooks::WebhookEvent::AnswerGenerated,
+                        serde_json::json!({
+                            "session_id": session_id,
+                            "question_id": question_id,
+                            "answer_id": id,
+                            "answer_text": answer_text,
+                            "response_time": response_time,
+                            "ai_score": ai_score,
+                        }),
+                    );
+                    crate::rubric::on_answer_saved(&app, &session_id, question_id, &id, answer_text);
+                }
+                InterviewItem::TranscriptSegment { .. } => {}
+            }
+        }
+        Ok(ids.iter().map(Uuid::to_string).collect())
+    })
+    .await
+}
+
+/// Final transcript segments waiting to be written, per session
+static PENDING_SEGMENTS: Lazy<Mutex<Vec<(String, InterviewItem)>>> = Lazy::new(|| Mutex::new(Vec::new()));
+/// Segments arriving within this window after the first are written in one transaction
+const SEGMENT_FLUSH_DELAY: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Queue a final transcript segment from the streaming pipelines for the session's history
+pub fn queue_transcript_segment(session_id: &str, source: &str, speaker: Option<&str>, text: &str) {
+    let mut pending = PENDING_SEGMENTS.lock();
+    let first = pending.is_empty();
+    pending.push((
+        session_id.to_string(),
+        InterviewItem::TranscriptSegment {
+            source: source.to_string(),
+            speaker: speaker.map(str::to_string),
+            text: text.to_string(),
+            timestamp: Utc::now(),
         },
+    ));
+    if first {
+        tauri::async_runtime::spawn(async {
+            tokio::time::sleep(SEGMENT_FLUSH_DELAY).await;
+            flush_transcript_segments().await;
+        });
+    }
+}
+
+async fn flush_transcript_segments() {
+    let pending = std::mem::take(&mut *PENDING_SEGMENTS.lock());
+    let mut by_session: Vec<(String, Vec<InterviewItem>)> = Vec::new();
+    for (session_id, item) in pending {
+        match by_session.iter_mut().find(|(id, _)| *id == session_id) {
+            Some((_, items)) => items.push(item),
+            None => by_session.push((session_id, vec![item])),
+        }
+    }
+
+    let db = match DatabaseManager::new().await {
+        Ok(db) => db,
         Err(e) => {
-            log::error!("❌❌❌ FAILED to connect to database: {}", e);
-            Err(format!("Database connection failed: {}", e))
+            log::debug!("Transcript segments kept locally only, database unavailable: {}", e);
+            return;
+        }
+    };
+    for (session_id, items) in by_session {
+        if let Err(e) = db.insert_interview_items(&session_id, &items).await {
+            log::warn!("Failed to save {} transcript segments for session {}: {}", items.len(), session_id, e);
         }
     }
 }
 
+/// One page of a session's questions; `topic` is kept for callers that predate `query`
 #[tauri::command]
-pub async fn get_session_questions(session_id: String) -> std::result::Result<Vec<InterviewQuestion>, String> {
-    info!("📋 Retrieving questions for session: {}", session_id);
+#[specta::specta]
+pub async fn get_session_questions(
+    session_id: String,
+    topic: Option<String>,
+    query: Option<SessionItemQuery>,
+) -> Response<Page<InterviewQuestion>> {
+    envelope::respond("get_session_questions", async move {
+        info!("📋 Retrieving questions for session: {}", session_id);
+        let mut query = query.unwrap_or_default();
+        if let Some(name) = topic.as_deref().filter(|t| !t.trim().is_empty()) {
+            query.topic = Some(QuestionTopic::parse(name).ok_or_else(|| format!("Unknown question topic: {}", name))?);
+        }
     
-    let db = DatabaseManager::new().await
-        .map_err(|e| e.to_string())?;
+        let db = DatabaseManager::new().await
+            .map_err(|e| e.to_string())?;
     
-    let questions = db.get_session_questions(&session_id).await
-        .map_err(|e| e.to_string())?;
+        let questions = db.get_session_questions(&session_id, &query).await
+            .map_err(|e| e.to_string())?;
     
-    info!("✅ Retrieved {} questions", questions.len());
-    Ok(questions)
+        info!("✅ Retrieved {} of {} questions", questions.items.len(), questions.total);
+        Ok(questions)
+    })
+    .await
 }
 
+/// One page of a session's answers
 #[tauri::command]
-pub async fn get_session_answers(session_id: String) -> std::result::Result<Vec<InterviewAnswer>, String> {
-    info!("📝 Retrieving answers for session: {}", session_id);
+#[specta::specta]
+pub async fn get_session_answers(session_id: String, query: Option<SessionItemQuery>) -> Response<Page<InterviewAnswer>> {
+    envelope::respond("get_session_answers", async move {
+        info!("📝 Retrieving answers for session: {}", session_id);
     
-    let db = DatabaseManager::new().await
-        .map_err(|e| e.to_string())?;
+        let db = DatabaseManager::new().await
+            .map_err(|e| e.to_string())?;
     
-    let answers = db.get_session_answers(&session_id).await
-        .map_err(|e| e.to_string())?;
+        let answers = db.get_session_answers(&session_id, &query.unwrap_or_default()).await
+            .map_err(|e| e.to_string())?;
     
-    info!("✅ Retrieved {} answers", answers.len());
-    Ok(answers)
+        info!("✅ Retrieved {} of {} answers", answers.items.len(), answers.total);
+        Ok(answers)
+    })
+    .await
 }
 
 #[tauri::command]
-pub async fn get_interview_report(session_id: String) -> std::result::Result<SessionReport, String> {
-    info!("📊 Generating interview report for session: {}", session_id);
+#[specta::specta]
+pub async fn get_interview_report(session_id: String) -> Response<SessionReport> {
+    envelope::respond("get_interview_report", async move {
+        info!("📊 Generating interview report for session: {}", session_id);
     
-    let db = DatabaseManager::new().await
-        .map_err(|e| e.to_string())?;
+        let db = DatabaseManager::new().await
+            .map_err(|e| e.to_string())?;
     
-    let report = db.get_session_report(&session_id).await
-        .map_err(|e| e.to_string())?;
+        let report = db.get_session_report(&session_id).await
+            .map_err(|e| e.to_string())?;
     
-    info!("✅ Generated report with {} questions, {} answers, avg score: {:.1}", 
-          report.total_questions, report.total_answers, report.average_score);
-    Ok(report)
+        info!("✅ Generated report with {} questions, {} answers, avg score: {:.1}", 
+              report.total_questions, report.total_answers, report.average_score);
+        Ok(report)
+    })
+    .await
 }
 
 #[tauri::command]
+#[specta::specta]
 pub async fn finalize_session_duration(
     session_id: String, 
     total_minutes: i32
-) -> std::result::Result<String, String> {
-    info!("🏁 Finalizing session {} with duration: {} minutes", session_id, total_minutes);
+) -> Response<String> {
+    envelope::respond("finalize_session_duration", async move {
+        info!("🏁 Finalizing session {} with duration: {} minutes", session_id, total_minutes);
     
-    let db = DatabaseManager::new().await
-        .map_err(|e| e.to_string())?;
+        let db = DatabaseManager::new().await
+            .map_err(|e| e.to_string())?;
     
-    db.update_session_final_duration(&session_id, total_minutes).await
-        .map_err(|e| e.to_string())?;
+        db.update_session_final_duration(&session_id, total_minutes).await
+            .map_err(|e| e.to_string())?;
     
-    info!("✅ Session duration finalized");
-    Ok("Session duration finalized successfully".to_string())
+        info!("✅ Session duration finalized");
+        Ok("Session duration finalized successfully".to_string())
+    })
+    .await
 }
 
 #[tauri::command]
-pub async fn mark_session_started(session_id: String) -> std::result::Result<String, String> {
-    info!("🚀 Marking session {} as started", session_id);
+#[specta::specta]
+pub async fn mark_session_started(session_id: String) -> Response<String> {
+    envelope::respond("mark_session_started", async move {
+        info!("🚀 Marking session {} as started", session_id);
     
-    let db = DatabaseManager::new().await
-        .map_err(|e| e.to_string())?;
+        let db = DatabaseManager::new().await
+            .map_err(|e| e.to_string())?;
     
-    db.mark_session_started(&session_id).await
-        .map_err(|e| e.to_string())?;
+        db.mark_session_started(&session_id).await
+            .map_err(|e| e.to_string())?;
     
-    info!("✅ Session marked as started");
-    Ok("Session marked as started successfully".to_string())
+        info!("✅ Session marked as started");
+        Ok("Session marked as started successfully".to_string())
+    })
+    .await
 }
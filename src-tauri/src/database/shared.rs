@@ -2,8 +2,10 @@ use once_cell::sync::Lazy;
 use deadpool_postgres::{Config, Pool, Runtime};
 use tokio_postgres::NoTls;
 use serde::{Deserialize, Serialize};
+use specta::Type;
 use uuid::Uuid;
 use chrono::{DateTime, Utc, NaiveDateTime, TimeZone};
+use crate::error::{AppError, ErrorCode};
 use crate::get_env_var;
 
 // Database connection pool - shared globally
@@ -28,6 +30,20 @@ pub static DATABASE_POOL: Lazy<Pool> = Lazy::new(|| {
     cfg.create_pool(Some(Runtime::Tokio1), NoTls).expect("Failed to create database pool")
 });
 
+/// Shared pool, unless local-only mode forbids reaching the database
+pub fn pool() -> Result<&'static Pool, String> {
+    crate::privacy::ensure_cloud_allowed("Postgres")?;
+    Ok(&DATABASE_POOL)
+}
+
+/// Connection from the shared pool; failing to get one is an outage, so the error is retryable
+async fn connect() -> Result<deadpool_postgres::Object, AppError> {
+    let pool = pool().map_err(|e| AppError::new(ErrorCode::PrivacyBlocked, e))?;
+    pool.get()
+        .await
+        .map_err(|e| AppError::new(ErrorCode::Unavailable, format!("Database connection error: {}", e)))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Session {
     pub id: Uuid,
@@ -61,7 +77,7 @@ pub struct User {
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct SessionWithUser {
     // Session fields
     pub session_id: Uuid,
@@ -84,14 +100,14 @@ pub struct SessionWithUser {
     pub credits_available: i32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct UserInfo {
     pub name: String,
     pub email: String,
     pub avatar_url: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct InterviewConfig {
     pub job_title: String,
     pub company_name: Option<String>,
@@ -99,7 +115,7 @@ pub struct InterviewConfig {
 }
 
 pub async fn get_session_with_user_info(session_id: &str) -> Result<SessionWithUser, String> {
-    let pool = &*DATABASE_POOL;
+    let pool = pool()?;
     let client = pool.get().await.map_err(|e| format!("Database connection error: {}", e))?;
     
     // Parse session ID as UUID
@@ -183,9 +199,8 @@ pub async fn get_session_with_user_info(session_id: &str) -> Result<SessionWithU
     Ok(session_with_user)
 }
 
-pub async fn activate_session(session_id: &str) -> Result<(), String> {
-    let pool = &*DATABASE_POOL;
-    let mut client = pool.get().await.map_err(|e| format!("Database connection error: {}", e))?;
+pub async fn activate_session(session_id: &str) -> Result<(), AppError> {
+    let mut client = connect().await?;
     
     let session_uuid = Uuid::parse_str(session_id)
         .map_err(|_| "Invalid session ID format".to_string())?;
@@ -206,7 +221,7 @@ pub async fn activate_session(session_id: &str) -> Result<(), String> {
     let user_id: Uuid = session_row.get("user_id");
     
     if current_status != "created" && current_status != "active" {
-        return Err("Session cannot be activated".to_string());
+        return Err("Session cannot be activated".into());
     }
     
     // Check if user has sufficient credits
@@ -217,7 +232,7 @@ pub async fn activate_session(session_id: &str) -> Result<(), String> {
     let current_credits: i32 = user_row.get("credits");
     
     if current_credits < 1 {
-        return Err("Insufficient credits to activate session".to_string());
+        return Err(AppError::new(ErrorCode::InvalidInput, "Insufficient credits to activate session"));
     }
     
     // Update session status to active and set started_at
@@ -252,8 +267,33 @@ pub async fn activate_session(session_id: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Whether the session is active and its activation credit has been recorded, i.e. an
+/// `activate_session` whose response was lost went through
+pub async fn activation_charged(session_id: &str) -> Result<bool, AppError> {
+    let client = connect().await?;
+    
+    let session_uuid = Uuid::parse_str(session_id)
+        .map_err(|_| "Invalid session ID format".to_string())?;
+    
+    let query = r#"
+        SELECT EXISTS (
+            SELECT 1 FROM sessions s
+            JOIN credit_transactions ct ON ct.session_id = s.id
+            WHERE s.id = $1
+              AND s.status = 'active'
+              AND ct.transaction_type = 'usage'
+              AND ct.description = 'Session activation'
+        ) AS charged
+    "#;
+    
+    let row = client.query_one(query, &[&session_uuid]).await
+        .map_err(|e| format!("Failed to check session activation: {}", e))?;
+    
+    Ok(row.get("charged"))
+}
+
 pub async fn disconnect_session(session_id: &str) -> Result<(), String> {
-    let pool = &*DATABASE_POOL;
+    let pool = pool()?;
     let client = pool.get().await.map_err(|e| format!("Database connection error: {}", e))?;
     
     let session_uuid = Uuid::parse_str(session_id)
@@ -273,7 +313,7 @@ pub async fn disconnect_session(session_id: &str) -> Result<(), String> {
 }
 
 pub async fn get_session_info(session_id: &str) -> Result<Session, String> {
-    let pool = &*DATABASE_POOL;
+    let pool = pool()?;
     let client = pool.get().await.map_err(|e| format!("Database connection error: {}", e))?;
     
     let session_uuid = Uuid::parse_str(session_id)
@@ -317,7 +357,7 @@ pub async fn initialize_database() -> Result<(), String> {
     dotenvy::dotenv().ok(); // Don't fail if .env doesn't exist
     
     // Test the connection - but make it optional for development
-    let pool = &*DATABASE_POOL;
+    let pool = pool()?;
     match pool.get().await {
         Ok(client) => {
             // Try a simple ping query
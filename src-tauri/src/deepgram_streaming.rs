@@ -3,14 +3,32 @@
 // Using Nova-3 model for maximum accuracy and lowest latency
 
 use anyhow::Result;
-use log::{info, error};
-use tauri::{AppHandle, Emitter, Listener};
-use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
+use log::{info, error, debug, warn, trace};
+use tauri::{AppHandle, Emitter};
+use tokio_tungstenite::{connect_async, tungstenite::protocol::Message, MaybeTlsStream, WebSocketStream};
 use futures_util::{StreamExt, SinkExt};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use specta::Type;
 use std::sync::Arc;
+use tokio::net::TcpStream;
 use tokio::sync::Mutex;
 
+use crate::{audit, metrics, telemetry};
+use crate::net_trace::TracedSend;
+use crate::logging::LogSampler;
+use crate::envelope::{self, Response};
+use crate::guard::{self, Require};
+use crate::events;
+
+/// Audio packets go out every few milliseconds; only a sample of them is logged
+static PACKET_LOG: LogSampler = LogSampler::new(200);
+/// Domain terms boosted in recognition (company, role, technologies); applied on the next connect
+static KEYWORDS: once_cell::sync::Lazy<parking_lot::Mutex<Vec<String>>> =
+    once_cell::sync::Lazy::new(|| parking_lot::Mutex::new(Vec::new()));
+/// Deepgram rejects very long query strings; keep the boost list short
+const MAX_KEYWORDS: usize = 50;
+
 // Deepgram API configuration from environment
 fn get_deepgram_api_key() -> String {
     // Try build-time embedded key first, then runtime env var
@@ -19,13 +37,31 @@ fn get_deepgram_api_key() -> String {
         .to_string()
 }
 
-fn get_deepgram_model() -> String {
+/// Resolve Deepgram and validate the API key before the first stream opens
+pub async fn warm_up() -> Result<()> {
+    let api_key = get_deepgram_api_key();
+    if api_key.is_empty() {
+        return Err(anyhow::anyhow!("DEEPGRAM_API_KEY not set"));
+    }
+
+    let response = reqwest::Client::new()
+        .get("https://api.deepgram.com/v1/projects")
+        .header("Authorization", format!("Token {}", api_key))
+        .send_traced()
+        .await?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!("Deepgram warm-up returned {}", response.status()));
+    }
+    Ok(())
+}
+
+pub(crate) fn get_deepgram_model() -> String {
     option_env!("DEEPGRAM_MODEL")
         .unwrap_or("nova-3")
         .to_string()
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Type)]
 pub struct DeepgramTranscriptionResult {
     pub text: String,
     pub is_final: bool,
@@ -39,6 +75,43 @@ struct DeepgramResponse {
     channel: Option<DeepgramChannel>,
     #[serde(default)]
     is_final: bool,
+    /// Offset of this result in the audio stream (seconds)
+    #[serde(default)]
+    start: f64,
+    #[serde(default)]
+    duration: f64,
+}
+
+/// Tracks when each stretch of audio was sent so transcript latency can be measured
+/// against the stream position Deepgram reports back
+#[derive(Default)]
+struct AudioSendClock {
+    sent_seconds: f64,
+    sent_bytes: u64,
+    marks: std::collections::VecDeque<(f64, std::time::Instant)>,
+}
+
+impl AudioSendClock {
+    /// PCM sent is 16-bit mono at 44.1kHz
+    fn record_sent(&mut self, pcm_bytes: usize) {
+        self.sent_seconds += pcm_bytes as f64 / 2.0 / 44100.0;
+        self.sent_bytes += pcm_bytes as u64;
+        self.marks.push_back((self.sent_seconds, std::time::Instant::now()));
+        if self.marks.len() > 2000 {
+            self.marks.pop_front();
+        }
+    }
+
+    /// Time since the audio ending at `stream_position` was sent
+    fn latency_for(&mut self, stream_position: f64) -> Option<std::time::Duration> {
+        while let Some(&(end, sent_at)) = self.marks.front() {
+            if end >= stream_position {
+                return Some(sent_at.elapsed());
+            }
+            self.marks.pop_front();
+        }
+        None
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -53,6 +126,77 @@ struct DeepgramAlternative {
     confidence: f64,
 }
 
+/// Open a live transcription socket for 44.1kHz mono linear16 audio
+pub(crate) async fn connect(model: &str) -> Result<WebSocketStream<MaybeTlsStream<TcpStream>>> {
+    let api_key = get_deepgram_api_key();
+    if api_key.is_empty() {
+        return Err(anyhow::anyhow!("DEEPGRAM_API_KEY not set"));
+    }
+
+    info!("📡 Using Deepgram model: {}", model);
+
+    // Build Deepgram WebSocket URL with absolute minimum parameters to avoid 400 error
+    // Start with basic working connection, then we can add optimizations later
+    let tuning = crate::transcription_preset::current();
+    let mut ws_url = format!(
+        "wss://api.deepgram.com/v1/listen?model={}&language={}&encoding=linear16&sample_rate=44100&channels=1&interim_results={}&endpointing={}&smart_format={}",
+        model,
+        crate::answer_language::transcription_language(model),
+        tuning.interim_results,
+        tuning.endpointing_ms,
+        tuning.smart_format
+    );
+    if let Some(utterance_end_ms) = tuning.utterance_end_ms {
+        ws_url.push_str(&format!("&utterance_end_ms={}", utterance_end_ms));
+    }
+    // Nova-3 takes key terms; older models take boosted keywords
+    let keyword_param = if model.starts_with("nova-3") { "keyterm" } else { "keywords" };
+    for keyword in boosted_keywords() {
+        ws_url.push_str(&format!("&{}={}", keyword_param, urlencoding::encode(&keyword)));
+    }
+    // Misheard forms of glossary terms are replaced by Deepgram before results come back
+    for (alias, term) in crate::glossary::replacements() {
+        ws_url.push_str(&format!("&replace={}", urlencoding::encode(&format!("{}:{}", alias, term))));
+    }
+
+    info!("🔗 Connecting to Deepgram: {}", ws_url);
+
+    // Connect to Deepgram WebSocket with proper headers
+    let request = tungstenite::http::Request::builder()
+        .method("GET")
+        .uri(&ws_url)
+        .header("Host", "api.deepgram.com")
+        .header("Connection", "Upgrade")
+        .header("Upgrade", "websocket")
+        .header("Sec-WebSocket-Version", "13")
+        .header("Sec-WebSocket-Key", tungstenite::handshake::client::generate_key())
+        .header("Authorization", format!("Token {}", api_key))
+        .body(())
+        .map_err(|e| anyhow::anyhow!("Failed to build request: {}", e))?;
+
+    let (ws_stream, _) = connect_async(request)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to connect to Deepgram: {}", e))?;
+
+    Ok(ws_stream)
+}
+
+/// Transcript carried by a Deepgram message, if it has non-empty text
+pub(crate) fn parse_transcript(message: &str) -> Option<DeepgramTranscriptionResult> {
+    let response = serde_json::from_str::<DeepgramResponse>(message).ok()?;
+    let alternative = response.channel?.alternatives.into_iter().next()?;
+    let text = alternative.transcript.trim();
+    if text.is_empty() {
+        return None;
+    }
+    Some(DeepgramTranscriptionResult {
+        text: text.to_string(),
+        is_final: response.is_final,
+        confidence: alternative.confidence as f32,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
 /// Deepgram streaming transcription manager with deduplication
 pub struct DeepgramStreamer {
     app_handle: AppHandle,
@@ -60,6 +204,7 @@ pub struct DeepgramStreamer {
     stop_flag: Arc<std::sync::atomic::AtomicBool>,
     last_interim: Arc<Mutex<String>>,
     last_final: Arc<Mutex<String>>,
+    send_clock: Arc<std::sync::Mutex<AudioSendClock>>,
 }
 
 impl DeepgramStreamer {
@@ -70,6 +215,7 @@ impl DeepgramStreamer {
             stop_flag: Arc::new(std::sync::atomic::AtomicBool::new(false)),
             last_interim: Arc::new(Mutex::new(String::new())),
             last_final: Arc::new(Mutex::new(String::new())),
+            send_clock: Arc::new(std::sync::Mutex::new(AudioSendClock::default())),
         }
     }
 
@@ -77,49 +223,19 @@ impl DeepgramStreamer {
     pub async fn start_streaming(&mut self) -> Result<()> {
         info!("🎙️ Starting Deepgram Nova-3 streaming transcription...");
 
-        let api_key = get_deepgram_api_key();
-        if api_key.is_empty() {
-            return Err(anyhow::anyhow!("DEEPGRAM_API_KEY not set"));
-        }
+        crate::privacy::ensure_cloud_allowed("Deepgram").map_err(|e| anyhow::anyhow!(e))?;
 
         let model = get_deepgram_model();
-        info!("📡 Using Deepgram model: {}", model);
-
-        // Build Deepgram WebSocket URL with absolute minimum parameters to avoid 400 error
-        // Start with basic working connection, then we can add optimizations later
-        let ws_url = format!(
-            "wss://api.deepgram.com/v1/listen?model={}&language=en-US&encoding=linear16&sample_rate=44100&channels=1&interim_results=true",
-            model
-        );
-
-        info!("🔗 Connecting to Deepgram: {}", ws_url);
-
-        // Connect to Deepgram WebSocket with proper headers
-        let request = tungstenite::http::Request::builder()
-            .method("GET")
-            .uri(&ws_url)
-            .header("Host", "api.deepgram.com")
-            .header("Connection", "Upgrade")
-            .header("Upgrade", "websocket")
-            .header("Sec-WebSocket-Version", "13")
-            .header("Sec-WebSocket-Key", tungstenite::handshake::client::generate_key())
-            .header("Authorization", format!("Token {}", api_key))
-            .body(())
-            .map_err(|e| anyhow::anyhow!("Failed to build request: {}", e))?;
-
-        let (ws_stream, _) = connect_async(request)
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to connect to Deepgram: {}", e))?;
-
+        let ws_stream = connect(&model).await?;
         info!("✅ Connected to Deepgram WebSocket");
         self.is_connected.store(true, std::sync::atomic::Ordering::Relaxed);
 
         // Emit connection status
-        let _ = self.app_handle.emit("deepgram-status", serde_json::json!({
-            "status": "connected",
-            "model": model,
-            "timestamp": chrono::Utc::now().timestamp_millis()
-        }));
+        let _ = self.app_handle.emit(events::DEEPGRAM_STATUS, events::DeepgramStatus {
+            status: "connected".to_string(),
+            model: Some(model.to_string()),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        });
 
         let (write, mut read) = ws_stream.split();
 
@@ -128,6 +244,7 @@ impl DeepgramStreamer {
         let is_connected = self.is_connected.clone();
         let last_interim = self.last_interim.clone();
         let last_final = self.last_final.clone();
+        let send_clock = self.send_clock.clone();
 
         // Spawn task to handle incoming transcription results with deduplication
         tokio::spawn(async move {
@@ -146,6 +263,14 @@ impl DeepgramStreamer {
                                     let transcript = alternative.transcript.trim();
                                     
                                     if !transcript.is_empty() {
+                                        let latency = send_clock
+                                            .lock()
+                                            .ok()
+                                            .and_then(|mut clock| clock.latency_for(response.start + response.duration));
+                                        if let Some(latency) = latency {
+                                            metrics::record(metrics::AUDIO_TO_TRANSCRIPT, latency);
+                                        }
+
                                         let should_emit = if response.is_final {
                                             // For final results, check against last final to prevent duplicates
                                             let mut last_final_guard = last_final.lock().await;
@@ -171,12 +296,11 @@ impl DeepgramStreamer {
                                             };
 
                                             // Emit transcription result to frontend
-                                            let _ = app_clone.emit("transcription-result", &result);
+                                            let _ = app_clone.emit(events::TRANSCRIPTION_RESULT, &result);
 
                                             if response.is_final {
-                                                info!("📝 FINAL: \"{}\" ({:.1}%)", transcript, alternative.confidence * 100.0);
-                                            } else {
-                                                info!("⏳ INTERIM: \"{}\"", transcript);
+                                                on_final_transcript(&result, response.duration);
+                                                debug!("Final transcript: {} chars ({:.1}%)", transcript.len(), alternative.confidence * 100.0);
                                             }
                                         }
                                     }
@@ -187,116 +311,105 @@ impl DeepgramStreamer {
                     Ok(Message::Close(_)) => {
                         info!("🔌 Deepgram connection closed");
                         is_connected.store(false, std::sync::atomic::Ordering::Relaxed);
-                        let _ = app_clone.emit("deepgram-status", serde_json::json!({
-                            "status": "disconnected",
-                            "timestamp": chrono::Utc::now().timestamp_millis()
-                        }));
+                        let _ = app_clone.emit(events::DEEPGRAM_STATUS, events::DeepgramStatus {
+                            status: "disconnected".to_string(),
+                            model: None,
+                            timestamp: chrono::Utc::now().timestamp_millis(),
+                        });
                         break;
                     }
                     Err(e) => {
                         error!("❌ Deepgram WebSocket error: {}", e);
                         is_connected.store(false, std::sync::atomic::Ordering::Relaxed);
-                        let _ = app_clone.emit("transcription-error", serde_json::json!({
-                            "error": e.to_string()
-                        }));
+                        let _ = app_clone.emit(events::TRANSCRIPTION_ERROR, events::ErrorPayload {
+                            error: e.to_string(),
+                        });
                         break;
                     }
                     _ => {}
                 }
             }
             is_connected.store(false, std::sync::atomic::Ordering::Relaxed);
+            // A drop we didn't ask for: keep transcribing locally until Deepgram is back
+            if !stop_flag.load(std::sync::atomic::Ordering::Relaxed) {
+                crate::vosk_fallback::engage(&app_clone, "Deepgram connection lost");
+            }
             info!("🛑 Deepgram reader task ended");
         });
 
         // Store the write half for sending audio
         let write_arc = Arc::new(Mutex::new(write));
         
-        // Listen for audio chunks from Pluely capture
-        let app_handle = self.app_handle.clone();
+        // Stream audio chunks published by the Pluely capture processors
         let stop_flag_clone = self.stop_flag.clone();
         let is_connected_clone = self.is_connected.clone();
-        
+        let send_clock_for_audio = self.send_clock.clone();
+        let send_clock_for_audit = self.send_clock.clone();
+
+        // Subscribe before spawning so no chunk published after connect is missed
+        let mut audio_rx = crate::audio_buffer::subscribe();
+        let write_for_audio = write_arc.clone();
+        let stop_flag_for_audio = stop_flag_clone.clone();
         tokio::spawn(async move {
-            // Listen for system audio chunks
-            let app_handle_clone = app_handle.clone();
-            app_handle_clone.listen("audio-chunk", {
-                let write_clone = write_arc.clone();
-                let stop_flag = stop_flag_clone.clone();
-                let is_connected = is_connected_clone.clone();
-                move |event| {
-                    if stop_flag.load(std::sync::atomic::Ordering::Relaxed) {
-                        return;
-                    }
-                    
-                    // Check if WebSocket is still connected before sending
-                    if !is_connected.load(std::sync::atomic::Ordering::Relaxed) {
-                        return;
-                    }
-                    
-                    if let Ok(payload_str) = serde_json::from_str::<String>(event.payload()) {
-                        // Payload is base64 encoded WAV
-                        if let Ok(wav_data) = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, &payload_str) {
-                            // Extract raw PCM data from WAV (skip header)
-                            if wav_data.len() > 44 {
-                                let pcm_data = wav_data[44..].to_vec();
-                                
-                                let write_clone2 = write_clone.clone();
-                                tokio::spawn(async move {
-                                    let mut write_guard = write_clone2.lock().await;
-                                    if let Err(e) = write_guard.send(Message::Binary(pcm_data)).await {
-                                        error!("Failed to send audio to Deepgram: {}", e);
-                                    }
-                                });
-                            }
-                        }
+            // One sender task keeps chunks in capture order
+            while !stop_flag_for_audio.load(std::sync::atomic::Ordering::Relaxed) {
+                let chunk = match tokio::time::timeout(tokio::time::Duration::from_millis(100), audio_rx.recv()).await {
+                    Ok(Ok(chunk)) => chunk,
+                    Ok(Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped))) => {
+                        warn!("Deepgram audio sender fell behind, dropped {} chunks", skipped);
+                        continue;
                     }
+                    Ok(Err(tokio::sync::broadcast::error::RecvError::Closed)) => break,
+                    Err(_) => continue,
+                };
+
+                // Check if WebSocket is still connected before sending
+                if !is_connected_clone.load(std::sync::atomic::Ordering::Relaxed) {
+                    continue;
                 }
-            });
-
-            // Listen for microphone audio chunks
-            let app_handle_clone2 = app_handle.clone();
-            app_handle_clone2.listen("mic-audio-chunk", {
-                let write_clone = write_arc.clone();
-                let stop_flag = stop_flag_clone.clone();
-                let is_connected = is_connected_clone.clone();
-                move |event| {
-                    if stop_flag.load(std::sync::atomic::Ordering::Relaxed) {
-                        return;
-                    }
-                    
-                    // Check if WebSocket is still connected before sending
-                    if !is_connected.load(std::sync::atomic::Ordering::Relaxed) {
-                        return;
-                    }
-                    
-                    if let Ok(payload_str) = serde_json::from_str::<String>(event.payload()) {
-                        // Payload is base64 encoded WAV
-                        if let Ok(wav_data) = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, &payload_str) {
-                            // Extract raw PCM data from WAV (skip header)
-                            if wav_data.len() > 44 {
-                                let pcm_data = wav_data[44..].to_vec();
-                                
-                                let write_clone2 = write_clone.clone();
-                                tokio::spawn(async move {
-                                    let mut write_guard = write_clone2.lock().await;
-                                    if let Err(e) = write_guard.send(Message::Binary(pcm_data)).await {
-                                        error!("Failed to send mic audio to Deepgram: {}", e);
-                                    }
-                                });
-                            }
-                        }
+
+                let pcm_data = crate::audio_buffer::pcm16_bytes(&chunk.samples);
+                let pcm_len = pcm_data.len();
+                let mut write_guard = write_for_audio.lock().await;
+                if let Err(e) = write_guard.send(Message::Binary(pcm_data)).await {
+                    let source = match chunk.source {
+                        crate::audio_buffer::AudioSource::System => "audio",
+                        crate::audio_buffer::AudioSource::Microphone => "mic audio",
+                    };
+                    error!("Failed to send {} to Deepgram: {}", source, e);
+                } else if let Ok(mut clock) = send_clock_for_audio.lock() {
+                    clock.record_sent(pcm_len);
+                    if let Some(packets) = PACKET_LOG.sample() {
+                        trace!("Sent {} audio packets to Deepgram ({} bytes total)", packets, clock.sent_bytes);
                     }
                 }
-            });
+            }
+        });
 
-            // Wait for stop signal
+        tokio::spawn(async move {
+            // Wait for stop signal, logging streamed audio to the outbound audit log every ~10s
+            let mut audited_bytes = 0u64;
+            let mut ticks_since_audit = 0u32;
+            let audit_audio = |audited_bytes: &mut u64| {
+                let sent = send_clock_for_audit.lock().map(|c| c.sent_bytes).unwrap_or(*audited_bytes);
+                if sent > *audited_bytes {
+                    audit::record("deepgram", "transcription", audit::PayloadCategory::Audio, sent - *audited_bytes);
+                    *audited_bytes = sent;
+                }
+            };
             loop {
                 if stop_flag_clone.load(std::sync::atomic::Ordering::Relaxed) {
                     info!("🛑 Deepgram audio sender stopping");
                     break;
                 }
                 tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+                ticks_since_audit += 1;
+                if ticks_since_audit >= 100 {
+                    ticks_since_audit = 0;
+                    audit_audio(&mut audited_bytes);
+                }
             }
+            audit_audio(&mut audited_bytes);
 
             // Send close frame to Deepgram
             let mut write_guard = write_arc.lock().await;
@@ -315,10 +428,11 @@ impl DeepgramStreamer {
         self.is_connected.store(false, std::sync::atomic::Ordering::Relaxed);
 
         // Emit disconnection status
-        let _ = self.app_handle.emit("deepgram-status", serde_json::json!({
-            "status": "stopped",
-            "timestamp": chrono::Utc::now().timestamp_millis()
-        }));
+        let _ = self.app_handle.emit(events::DEEPGRAM_STATUS, events::DeepgramStatus {
+            status: "stopped".to_string(),
+            model: None,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        });
 
         // Wait a bit for tasks to stop
         tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
@@ -340,69 +454,167 @@ fn get_deepgram_streamer() -> Arc<tokio::sync::Mutex<Option<DeepgramStreamer>>>
     DEEPGRAM_STATE.get_or_init(|| Arc::new(tokio::sync::Mutex::new(None))).clone()
 }
 
+/// Hand a final transcript segment to everything that follows the interview
+pub(crate) fn on_final_transcript(result: &DeepgramTranscriptionResult, duration_seconds: f64) {
+    crate::scripting::dispatch(crate::scripting::ScriptEvent::TranscriptSegment, result);
+    crate::practice::on_transcript(&result.text);
+    crate::session_transcript::append("system_audio", None, &result.text);
+    crate::pacing::on_transcript(&result.text, duration_seconds);
+    crate::answer_language::on_transcript(&result.text);
+    crate::context_builder::on_transcript(&result.text);
+}
+
+/// Try to replace a lost stream with a new one; true when Deepgram is streaming again
+pub(crate) async fn reconnect(app: &AppHandle) -> bool {
+    let mut streamer = DeepgramStreamer::new(app.clone());
+    if let Err(e) = streamer.start_streaming().await {
+        debug!("Deepgram still unavailable: {}", e);
+        return false;
+    }
+    let streamer_arc = get_deepgram_streamer();
+    let mut streamer_guard = streamer_arc.lock().await;
+    if let Some(mut previous) = streamer_guard.replace(streamer) {
+        let _ = previous.stop_streaming().await;
+    }
+    info!("✅ Deepgram streaming restored");
+    true
+}
+
+/// Reconnect a running stream, e.g. so new connection parameters take effect
+pub(crate) async fn restart_if_streaming(app: &AppHandle) {
+    let streaming = get_deepgram_streamer().lock().await.as_ref().is_some_and(|streamer| streamer.is_connected());
+    if streaming {
+        reconnect(app).await;
+    }
+}
+
 /// Tauri command to start Deepgram streaming transcription
 #[tauri::command]
-pub async fn start_deepgram_streaming(app: AppHandle) -> Result<(), String> {
-    info!("🚀 Starting Deepgram streaming transcription...");
+#[specta::specta]
+pub async fn start_deepgram_streaming(app: AppHandle) -> Response<()> {
+    guard::respond("start_deepgram_streaming", &[Require::Cloud("Deepgram")], async move {
+        info!("🚀 Starting Deepgram streaming transcription...");
 
-    let streamer_arc = get_deepgram_streamer();
+        let streamer_arc = get_deepgram_streamer();
     
-    // Stop existing streamer if running
-    {
-        let mut streamer_guard = streamer_arc.lock().await;
-        if let Some(mut existing) = streamer_guard.take() {
-            info!("Stopping existing Deepgram streamer...");
-            let _ = existing.stop_streaming().await;
+        // Stop existing streamer if running
+        {
+            let mut streamer_guard = streamer_arc.lock().await;
+            if let Some(mut existing) = streamer_guard.take() {
+                info!("Stopping existing Deepgram streamer...");
+                let _ = existing.stop_streaming().await;
+            }
         }
-    }
     
-    // Create new streamer
-    let mut streamer = DeepgramStreamer::new(app.clone());
+        // Create new streamer
+        let mut streamer = DeepgramStreamer::new(app.clone());
     
-    // Start streaming
-    if let Err(e) = streamer.start_streaming().await {
-        error!("Failed to start Deepgram streaming: {}", e);
-        return Err(e.to_string());
-    }
+        // Start streaming
+        telemetry::record_feature("deepgram_streaming");
+        if let Err(e) = streamer.start_streaming().await {
+            error!("Failed to start Deepgram streaming: {}", e);
+            telemetry::record_error("transcription_connect_failed");
+            if crate::vosk_fallback::engage(&app, &format!("Deepgram unreachable: {}", e)) {
+                return Ok(());
+            }
+            return Err(e.to_string().into());
+        }
+        crate::vosk_fallback::disengage(&app, "Deepgram connected");
     
-    // Store the streamer
-    {
-        let mut streamer_guard = streamer_arc.lock().await;
-        *streamer_guard = Some(streamer);
-    }
+        // Store the streamer
+        {
+            let mut streamer_guard = streamer_arc.lock().await;
+            *streamer_guard = Some(streamer);
+        }
     
-    info!("✅ Deepgram streaming started successfully");
-    Ok(())
+        info!("✅ Deepgram streaming started successfully");
+        Ok(())
+    })
+    .await
 }
 
 /// Tauri command to stop Deepgram streaming
 #[tauri::command]
-pub async fn stop_deepgram_streaming(_app: AppHandle) -> Result<(), String> {
-    info!("🛑 Stopping Deepgram streaming...");
+#[specta::specta]
+pub async fn stop_deepgram_streaming(app: AppHandle) -> Response<()> {
+    envelope::respond("stop_deepgram_streaming", async move {
+        info!("🛑 Stopping Deepgram streaming...");
+        crate::vosk_fallback::disengage(&app, "Transcription stopped");
 
-    let streamer_arc = get_deepgram_streamer();
-    let mut streamer_guard = streamer_arc.lock().await;
+        let streamer_arc = get_deepgram_streamer();
+        let mut streamer_guard = streamer_arc.lock().await;
     
-    if let Some(mut streamer) = streamer_guard.take() {
-        if let Err(e) = streamer.stop_streaming().await {
-            error!("Failed to stop Deepgram streaming: {}", e);
-            return Err(e.to_string());
+        if let Some(mut streamer) = streamer_guard.take() {
+            if let Err(e) = streamer.stop_streaming().await {
+                error!("Failed to stop Deepgram streaming: {}", e);
+                return Err(e.to_string().into());
+            }
         }
-    }
     
-    info!("✅ Deepgram streaming stopped");
-    Ok(())
+        info!("✅ Deepgram streaming stopped");
+        Ok(())
+    })
+    .await
 }
 
 /// Check if Deepgram streaming is active
 #[tauri::command]
-pub async fn is_deepgram_streaming_active() -> Result<bool, String> {
-    let streamer_arc = get_deepgram_streamer();
-    let streamer_guard = streamer_arc.lock().await;
+#[specta::specta]
+pub async fn is_deepgram_streaming_active() -> Response<bool> {
+    envelope::respond("is_deepgram_streaming_active", async move {
+        let streamer_arc = get_deepgram_streamer();
+        let streamer_guard = streamer_arc.lock().await;
     
-    if let Some(streamer) = streamer_guard.as_ref() {
-        Ok(streamer.is_connected())
-    } else {
-        Ok(false)
+        // Transcription continues locally while the offline fallback stands in for Deepgram
+        if let Some(streamer) = streamer_guard.as_ref() {
+            Ok(streamer.is_connected() || crate::vosk_fallback::is_active())
+        } else {
+            Ok(crate::vosk_fallback::is_active())
+        }
+    })
+    .await
+}
+
+/// Glossary terms first, then the imported keywords, without duplicates and within the limit
+fn boosted_keywords() -> Vec<String> {
+    let mut keywords = crate::glossary::terms();
+    for keyword in KEYWORDS.lock().iter() {
+        if !keywords.iter().any(|k| k.eq_ignore_ascii_case(keyword)) {
+            keywords.push(keyword.clone());
+        }
+    }
+    keywords.truncate(MAX_KEYWORDS);
+    keywords
+}
+
+/// Replace the recognition keywords; takes effect when the next stream connects
+pub fn set_keywords(keywords: Vec<String>) {
+    let mut cleaned: Vec<String> = Vec::new();
+    for keyword in keywords.into_iter().map(|k| k.trim().to_string()).filter(|k| !k.is_empty()) {
+        if !cleaned.iter().any(|k| k.eq_ignore_ascii_case(&keyword)) {
+            cleaned.push(keyword);
+        }
     }
+    cleaned.truncate(MAX_KEYWORDS);
+    info!("🔤 Transcription keywords set: {}", cleaned.len());
+    *KEYWORDS.lock() = cleaned;
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_transcription_keywords() -> Response<Vec<String>> {
+    envelope::respond("get_transcription_keywords", async move {
+        Ok(KEYWORDS.lock().clone())
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_transcription_keywords(keywords: Vec<String>) -> Response<Vec<String>> {
+    envelope::respond("set_transcription_keywords", async move {
+        set_keywords(keywords);
+        Ok(KEYWORDS.lock().clone())
+    })
+    .await
 }
@@ -0,0 +1,204 @@
+// Diagnostics bundle export for MockMate support tickets
+// Collects logs, database/audio/monitor state and sanitized config into one zip
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use log::{info, warn};
+use serde_json::json;
+use tauri::AppHandle;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::{accessibility_reader, audio, logging, window_manager};
+use crate::cancellation;
+use crate::envelope::{IntoResult, Response};
+use crate::error::{AppError, ErrorCode};
+
+/// Only the tail of each log file is bundled to keep the archive small
+const MAX_LOG_BYTES_PER_FILE: u64 = 2 * 1024 * 1024;
+/// Number of rotated log files included alongside the active one
+const ROTATED_LOGS_IN_BUNDLE: usize = 2;
+
+/// Keys whose values must never leave the machine - only presence and length are reported
+const SECRET_ENV_KEYS: &[&str] = &[
+    "DEEPGRAM_API_KEY",
+    "OPENAI_API_KEY",
+    "POLLINATIONS_API_KEY",
+    "DB_PASSWORD",
+];
+
+/// Non-secret configuration keys whose values are useful for support
+const CONFIG_ENV_KEYS: &[&str] = &[
+    "DEEPGRAM_MODEL",
+    "DEEPGRAM_LANGUAGE",
+    "DEEPGRAM_ENDPOINTING",
+    "DEEPGRAM_INTERIM_RESULTS",
+    "DEEPGRAM_SMART_FORMAT",
+    "POLLINATIONS_REFERER",
+    "DB_HOST",
+    "DB_PORT",
+    "DB_NAME",
+    "BACKEND_URL",
+    "MOCKMATE_BACKEND_URL",
+    "MOCKMATE_LOG",
+];
+
+/// Environment status with secrets reduced to "set/missing" and their length
+pub fn sanitized_env_status() -> serde_json::Value {
+    let mut status = serde_json::Map::new();
+
+    for key in SECRET_ENV_KEYS {
+        let value = match crate::get_env_var(key) {
+            Some(v) => json!({ "set": true, "length": v.len() }),
+            None => json!({ "set": false }),
+        };
+        status.insert(key.to_string(), value);
+    }
+
+    for key in CONFIG_ENV_KEYS {
+        let value = match crate::get_env_var(key) {
+            Some(v) => json!({ "set": true, "value": v }),
+            None => json!({ "set": false }),
+        };
+        status.insert(key.to_string(), value);
+    }
+
+    serde_json::Value::Object(status)
+}
+
+fn system_info(app_handle: &AppHandle) -> serde_json::Value {
+    json!({
+        "app_version": app_handle.package_info().version.to_string(),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "generated_at": chrono::Utc::now().to_rfc3339(),
+        "log_dir": logging::log_dir().display().to_string(),
+    })
+}
+
+fn audio_devices_info() -> serde_json::Value {
+    match audio::list_all_audio_devices() {
+        Ok(devices) => json!(devices
+            .iter()
+            .map(|d| json!({
+                "name": d.name,
+                "is_default": d.is_default,
+                "device_type": d.device_type,
+                "supports_loopback": d.supports_loopback,
+            }))
+            .collect::<Vec<_>>()),
+        Err(e) => json!({ "error": e.to_string() }),
+    }
+}
+
+fn monitors_info(app_handle: &AppHandle) -> serde_json::Value {
+    match window_manager::get_monitors_info(app_handle) {
+        Ok(monitors) => json!(monitors),
+        Err(e) => json!({ "error": e }),
+    }
+}
+
+/// Read at most the last `MAX_LOG_BYTES_PER_FILE` bytes of a log file
+fn read_log_tail(path: &Path) -> std::io::Result<Vec<u8>> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    if len > MAX_LOG_BYTES_PER_FILE {
+        file.seek(SeekFrom::Start(len - MAX_LOG_BYTES_PER_FILE))?;
+    }
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+/// Active log file first, then the newest rotated files
+fn recent_log_files() -> Vec<PathBuf> {
+    let dir = logging::log_dir();
+    let mut files = vec![logging::current_log_file()];
+
+    let mut rotated = logging::rotated_log_files(&dir);
+    rotated.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+    files.extend(rotated.into_iter().take(ROTATED_LOGS_IN_BUNDLE).map(|(p, _)| p));
+
+    files.into_iter().filter(|p| p.exists()).collect()
+}
+
+fn write_json_entry(
+    zip: &mut ZipWriter<File>,
+    name: &str,
+    value: &serde_json::Value,
+    options: FileOptions,
+) -> Result<(), String> {
+    zip.start_file(name, options).map_err(|e| e.to_string())?;
+    let body = serde_json::to_vec_pretty(value).map_err(|e| e.to_string())?;
+    zip.write_all(&body).map_err(|e| e.to_string())
+}
+
+/// Export a zip with recent logs and system state for support tickets
+#[tauri::command]
+#[specta::specta]
+pub async fn export_diagnostics(app_handle: AppHandle, path: String, request_id: Option<String>) -> Response<String> {
+    cancellation::run_with_progress("export_diagnostics", request_id, |progress| async move {
+        info!("📦 Exporting diagnostics bundle to: {}", path);
+        crate::telemetry::record_feature("diagnostics_export");
+
+        let mut target = PathBuf::from(&path);
+        if target.is_dir() {
+            let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+            target = target.join(format!("mockmate-diagnostics-{}.zip", stamp));
+        }
+
+        // Gather everything that needs async/app access before touching the file
+        progress.report("collecting", Some(10.0));
+        let database = match crate::diagnose_database().await.into_result() {
+            Ok(diagnostic) => serde_json::to_value(diagnostic).unwrap_or_default(),
+            Err(e) => json!({ "error": e.to_string() }),
+        };
+        let accessibility = serde_json::to_value(accessibility_reader::current_config())
+            .unwrap_or_default();
+        let system = system_info(&app_handle);
+        let monitors = monitors_info(&app_handle);
+        let audio_devices = audio_devices_info();
+        let env_status = sanitized_env_status();
+
+        progress.report("writing", Some(50.0));
+        let file = File::create(&target)
+            .map_err(|e| format!("Failed to create {}: {}", target.display(), e))?;
+        let mut zip = ZipWriter::new(file);
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        write_json_entry(&mut zip, "system.json", &system, options)?;
+        write_json_entry(&mut zip, "database.json", &database, options)?;
+        write_json_entry(&mut zip, "audio_devices.json", &audio_devices, options)?;
+        write_json_entry(&mut zip, "monitors.json", &monitors, options)?;
+        write_json_entry(&mut zip, "accessibility_config.json", &accessibility, options)?;
+        write_json_entry(&mut zip, "environment.json", &env_status, options)?;
+
+        progress.report("adding_logs", Some(70.0));
+        for log_path in recent_log_files() {
+            if progress.is_cancelled() {
+                return Err(AppError::new(ErrorCode::Cancelled, "export_diagnostics was cancelled"));
+            }
+            let name = log_path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| logging::LOG_FILE_NAME.to_string());
+            match read_log_tail(&log_path) {
+                Ok(contents) => {
+                    zip.start_file(format!("logs/{}", name), options)
+                        .map_err(|e| e.to_string())?;
+                    zip.write_all(&contents).map_err(|e| e.to_string())?;
+                }
+                Err(e) => warn!("Skipping log file {}: {}", log_path.display(), e),
+            }
+        }
+
+        zip.finish().map_err(|e| format!("Failed to finalize diagnostics bundle: {}", e))?;
+
+        let written = target.display().to_string();
+        info!("✅ Diagnostics bundle written: {}", written);
+        Ok(written)
+    })
+    .await
+}
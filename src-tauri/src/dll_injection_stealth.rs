@@ -5,6 +5,7 @@ use std::ffi::{CString, CStr};
 use std::ptr;
 use std::mem;
 use serde::{Serialize, Deserialize};
+use specta::Type;
 use log::{info, warn, error};
 use anyhow::Result;
 
@@ -22,8 +23,10 @@ use winapi::{
         minwindef::{DWORD, LPVOID, BOOL, HMODULE, MAX_PATH, FALSE, TRUE},
     },
 };
+use crate::envelope::{self, Response};
+use crate::error::AppError;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct DLLInjectionStatus {
     pub target_processes: Vec<u32>,
     pub injected_count: u32,
@@ -348,25 +351,34 @@ fn get_dll_injection_manager() -> Option<&'static mut DLLInjectionManager> {
 
 // Tauri commands for DLL injection stealth
 #[tauri::command]
-pub fn activate_dll_injection_stealth() -> Result<String, String> {
-    match get_dll_injection_manager() {
-        Some(manager) => manager.activate_dll_injection_stealth().map_err(|e| e.to_string()),
-        None => Err("DLL injection stealth system not initialized".to_string()),
-    }
+#[specta::specta]
+pub fn activate_dll_injection_stealth() -> Response<String> {
+    envelope::respond_sync("activate_dll_injection_stealth", || {
+        match get_dll_injection_manager() {
+            Some(manager) => manager.activate_dll_injection_stealth().map_err(|e| AppError::from(e.to_string())),
+            None => Err("DLL injection stealth system not initialized".into()),
+        }
+    })
 }
 
 #[tauri::command]
-pub fn deactivate_dll_injection_stealth() -> Result<String, String> {
-    match get_dll_injection_manager() {
-        Some(manager) => manager.deactivate_dll_injection_stealth().map_err(|e| e.to_string()),
-        None => Err("DLL injection stealth system not initialized".to_string()),
-    }
+#[specta::specta]
+pub fn deactivate_dll_injection_stealth() -> Response<String> {
+    envelope::respond_sync("deactivate_dll_injection_stealth", || {
+        match get_dll_injection_manager() {
+            Some(manager) => manager.deactivate_dll_injection_stealth().map_err(|e| AppError::from(e.to_string())),
+            None => Err("DLL injection stealth system not initialized".into()),
+        }
+    })
 }
 
 #[tauri::command]
-pub fn get_dll_injection_stealth_status() -> Result<DLLInjectionStatus, String> {
-    match get_dll_injection_manager() {
-        Some(manager) => Ok(manager.get_status()),
-        None => Err("DLL injection stealth system not initialized".to_string()),
-    }
+#[specta::specta]
+pub fn get_dll_injection_stealth_status() -> Response<DLLInjectionStatus> {
+    envelope::respond_sync("get_dll_injection_stealth_status", || {
+        match get_dll_injection_manager() {
+            Some(manager) => Ok(manager.get_status()),
+            None => Err("DLL injection stealth system not initialized".into()),
+        }
+    })
 }
\ No newline at end of file
@@ -0,0 +1,30 @@
+// DLL injection only exists on Windows; other platforms report it as unavailable
+
+use crate::platform::{unavailable, Capability};
+use crate::envelope::{self, Response};
+
+pub fn initialize_dll_injection_stealth() {}
+
+#[tauri::command]
+#[specta::specta]
+pub fn activate_dll_injection_stealth() -> Response<String> {
+    envelope::respond_sync("activate_dll_injection_stealth", || {
+        Err(unavailable(Capability::Stealth).into())
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn deactivate_dll_injection_stealth() -> Response<String> {
+    envelope::respond_sync("deactivate_dll_injection_stealth", || {
+        Err(unavailable(Capability::Stealth).into())
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_dll_injection_stealth_status() -> Response<serde_json::Value> {
+    envelope::respond_sync("get_dll_injection_stealth_status", || {
+        Err(unavailable(Capability::Stealth).into())
+    })
+}
@@ -0,0 +1,228 @@
+// Post-interview documents: thank-you emails and cover letters
+// Drafts are written by the configured AI provider from the interview context, the session's
+// questions and transcript, and the resume, and returned as Markdown. When asked, a draft is also
+// saved next to the session in %APPDATA%\MockMate\documents\<session id>\.
+
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use log::info;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::State;
+
+use crate::AppState;
+use crate::cancellation;
+use crate::envelope::{IntoResult, Response};
+
+/// Resume and job description characters included in a prompt
+const MAX_RESUME_CHARS: usize = 6000;
+const MAX_JOB_CHARS: usize = 4000;
+/// Transcript characters offered to the model as highlights
+const MAX_TRANSCRIPT_CHARS: usize = 3000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentKind {
+    ThankYouEmail,
+    CoverLetter,
+}
+
+impl DocumentKind {
+    fn file_name(self) -> &'static str {
+        match self {
+            DocumentKind::ThankYouEmail => "thank_you_email.md",
+            DocumentKind::CoverLetter => "cover_letter.md",
+        }
+    }
+}
+
+/// Candidate details; anything left out falls back to the interview context
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct CandidateProfile {
+    pub name: Option<String>,
+    pub resume: Option<String>,
+    pub experience_level: Option<String>,
+    /// Anything else worth mentioning (achievements, motivation)
+    pub notes: Option<String>,
+}
+
+/// Target role; anything left out falls back to the interview context
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct JobTarget {
+    pub title: Option<String>,
+    pub company: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GeneratedDocument {
+    pub kind: DocumentKind,
+    pub session_id: Option<String>,
+    pub markdown: String,
+    pub saved_path: Option<String>,
+    pub generated_at: DateTime<Utc>,
+}
+
+fn documents_dir(session_id: &str) -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("documents")
+        .join(session_id)
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    text.chars().take(max).collect()
+}
+
+/// Models often wrap Markdown in a fence; return just the document
+fn strip_fence(reply: &str) -> String {
+    let trimmed = reply.trim();
+    trimmed
+        .strip_prefix("```markdown")
+        .or_else(|| trimmed.strip_prefix("```md"))
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|body| body.trim_end().strip_suffix("```"))
+        .unwrap_or(trimmed)
+        .trim()
+        .to_string()
+}
+
+fn finish(kind: DocumentKind, session_id: Option<String>, reply: &str, save: bool) -> Result<GeneratedDocument, String> {
+    let markdown = strip_fence(reply);
+    let saved_path = match (&session_id, save) {
+        (Some(session_id), true) => {
+            let dir = documents_dir(session_id);
+            std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+            let path = dir.join(kind.file_name());
+            std::fs::write(&path, &markdown).map_err(|e| e.to_string())?;
+            Some(path.display().to_string())
+        }
+        _ => None,
+    };
+    info!("✉️ Drafted {:?} ({} chars, saved: {})", kind, markdown.len(), saved_path.is_some());
+    Ok(GeneratedDocument {
+        kind,
+        session_id,
+        markdown,
+        saved_path,
+        generated_at: Utc::now(),
+    })
+}
+
+/// Draft a thank-you email for the interviewer(s) of a finished session
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn generate_thank_you_email(
+    state: State<'_, AppState>,
+    session_id: String,
+    provider: String,
+    model: String,
+    save: Option<bool>,
+    request_id: Option<String>,
+) -> Response<GeneratedDocument> {
+    cancellation::run("generate_thank_you_email", request_id, async move {
+        let report = crate::database::postgres::get_interview_report(session_id.clone()).await.into_result()?;
+        let context = state.interview_context.lock().clone();
+        let session = &report.session;
+
+        let mut questions: Vec<_> = report.questions.iter().collect();
+        questions.sort_by_key(|q| q.question_number);
+        let topics = questions.iter().map(|q| format!("- {}", q.question_text)).collect::<Vec<_>>().join("\n");
+        let transcript: String = crate::session_transcript::load(&session_id)
+            .iter()
+            .map(|line| match &line.speaker {
+                Some(speaker) => format!("{}: {}\n", speaker, line.text),
+                None => format!("{}\n", line.text),
+            })
+            .collect();
+        let summary = crate::session_summary::load(&session_id);
+
+        let mut prompt = format!(
+            "Write a short, warm, professional thank-you email to send after a job interview.\n\nRole: {}\nCompany: {}\nCandidate: {}\nInterview date: {}\n",
+            session.job_title,
+            context.company.as_deref().unwrap_or("(not given - use a neutral greeting)"),
+            context.user_name.as_deref().unwrap_or(&report.user.first_name),
+            session.session_started_at.unwrap_or(session.created_at).format("%B %-d, %Y"),
+        );
+        if !topics.is_empty() {
+            prompt.push_str(&format!("\nTopics discussed:\n{}\n", topics));
+        }
+        if let Some(summary) = &summary {
+            prompt.push_str(&format!("\nHow it went: {}\nStrengths shown: {}\n", summary.summary, summary.strengths.join("; ")));
+        }
+        if !transcript.is_empty() {
+            prompt.push_str(&format!(
+                "\nTranscript excerpt (pick one or two specific moments to reference):\n{}\n",
+                truncate(&transcript, MAX_TRANSCRIPT_CHARS)
+            ));
+        }
+        if let Some(resume) = context.resume_content.as_deref().or(session.resume_content.as_deref()) {
+            prompt.push_str(&format!("\nCandidate resume:\n{}\n", truncate(resume, MAX_RESUME_CHARS)));
+        }
+        prompt.push_str(
+            "\nReply with only the email in Markdown: a **Subject:** line, then the body (under 200 words), \
+             referencing specific discussion points and restating interest in the role. Do not invent facts.",
+        );
+
+        let reply = state
+            .complete(&provider, &model, "You are a career coach who writes concise, genuine professional correspondence.", &prompt)
+            .await?;
+        Ok(finish(DocumentKind::ThankYouEmail, Some(session_id), &reply, save.unwrap_or(false))?)
+    })
+    .await
+}
+
+/// Draft a cover letter for a role from the candidate profile and resume
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn generate_cover_letter(
+    state: State<'_, AppState>,
+    profile: CandidateProfile,
+    job: JobTarget,
+    provider: String,
+    model: String,
+    session_id: Option<String>,
+    request_id: Option<String>,
+) -> Response<GeneratedDocument> {
+    cancellation::run("generate_cover_letter", request_id, async move {
+        let context = state.interview_context.lock().clone();
+        let title = job.title.or(context.position).ok_or("A job title is required for a cover letter")?;
+        let company = job.company.or(context.company);
+        let description = job.description.or(context.job_description);
+        let resume = profile.resume.or(context.resume_content);
+
+        let mut prompt = format!(
+            "Write a tailored cover letter.\n\nRole: {}\nCompany: {}\nCandidate: {}\nExperience level: {}\n",
+            title,
+            company.as_deref().unwrap_or("(not given)"),
+            profile.name.or(context.user_name).as_deref().unwrap_or("(not given - leave a [Your Name] placeholder)"),
+            profile
+                .experience_level
+                .or(context.user_experience_level)
+                .as_deref()
+                .unwrap_or("(not given)"),
+        );
+        if let Some(description) = &description {
+            prompt.push_str(&format!("\nJob description:\n{}\n", truncate(description, MAX_JOB_CHARS)));
+        }
+        match &resume {
+            Some(resume) => prompt.push_str(&format!("\nResume:\n{}\n", truncate(resume, MAX_RESUME_CHARS))),
+            None => prompt.push_str("\nNo resume was provided; keep claims general and add [placeholders] for specifics.\n"),
+        }
+        if let Some(notes) = &profile.notes {
+            prompt.push_str(&format!("\nAdditional notes from the candidate:\n{}\n", notes));
+        }
+        prompt.push_str(
+            "\nReply with only the letter in Markdown (3-4 paragraphs, under 400 words). Match the candidate's \
+             real experience to the role's requirements; do not invent employers, titles or numbers.",
+        );
+
+        let reply = state
+            .complete(&provider, &model, "You are a career coach who writes specific, honest cover letters.", &prompt)
+            .await?;
+        let save = session_id.is_some();
+        Ok(finish(DocumentKind::CoverLetter, session_id, &reply, save)?)
+    })
+    .await
+}
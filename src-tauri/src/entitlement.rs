@@ -0,0 +1,346 @@
+// Offline grace for paid sessions
+// Activating a session needs the backend, so a short outage at the scheduled interview time used to
+// lock the candidate out of a session they had already paid for. The backend now attaches a signed
+// entitlement to successful temp-token authentications and activations: base64 JSON claims (session,
+// user, credits, issue time, optional expiry) and an Ed25519 signature over them, checked against a
+// key embedded at build time. The last entitlement per session is kept in
+// %APPDATA%\MockMate\entitlements.json. When the backend cannot be reached, the session is let in
+// from that entitlement as long as it was issued within the grace window (24 hours by default,
+// configurable up to a week, 0 turns it off) and has not expired. Entitlements are verified again on
+// every use, so editing the file grants nothing. Credits are not charged offline; the backend settles
+// the session when it sees it next.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::envelope::{self, Response};
+use crate::error::{AppError, ErrorCode};
+
+const DEFAULT_GRACE_HOURS: u32 = 24;
+const MAX_GRACE_HOURS: u32 = 7 * 24;
+
+static SETTINGS: Lazy<Mutex<GraceSettings>> = Lazy::new(|| Mutex::new(load_settings()));
+static CACHE: Lazy<Mutex<BTreeMap<String, SignedEntitlement>>> = Lazy::new(|| Mutex::new(load_cache()));
+
+/// Entitlement as the backend sends it
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SignedEntitlement {
+    /// Base64 JSON claims
+    pub payload: String,
+    /// Base64 Ed25519 signature over the decoded payload
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct EntitlementClaims {
+    session_id: String,
+    #[serde(default)]
+    user_id: Option<String>,
+    #[serde(default)]
+    credits_available: Option<i32>,
+    issued_at: DateTime<Utc>,
+    #[serde(default)]
+    expires_at: Option<DateTime<Utc>>,
+}
+
+/// A session let in without the backend
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct OfflineGrant {
+    pub session_id: String,
+    pub user_id: Option<String>,
+    /// Balance when the entitlement was issued
+    pub credits_available: Option<i32>,
+    /// When the backend last confirmed the session
+    pub issued_at: DateTime<Utc>,
+    /// End of the grace window
+    pub usable_until: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GraceSettings {
+    grace_hours: u32,
+}
+
+impl Default for GraceSettings {
+    fn default() -> Self {
+        Self { grace_hours: DEFAULT_GRACE_HOURS }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct OfflineGraceStatus {
+    pub grace_hours: u32,
+    /// The session could be let in offline right now
+    pub grant: Option<OfflineGrant>,
+    /// Why it could not, when a session was given
+    pub unavailable_reason: Option<String>,
+}
+
+fn settings_path() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("offline_grace.json")
+}
+
+fn cache_path() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("entitlements.json")
+}
+
+fn load_settings() -> GraceSettings {
+    std::fs::read(settings_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn load_cache() -> BTreeMap<String, SignedEntitlement> {
+    std::fs::read(cache_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn save_json<T: Serialize>(path: PathBuf, value: &T) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(value).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| e.to_string())
+}
+
+/// Ed25519 key embedded at build time; never read at runtime so it can't be swapped
+fn public_key() -> Result<Vec<u8>, String> {
+    let encoded = option_env!("MOCKMATE_ENTITLEMENT_PUBKEY")
+        .filter(|key| !key.is_empty())
+        .ok_or("This build has no entitlement signing key; offline grace is unavailable")?;
+    base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .map_err(|e| format!("Invalid entitlement signing key: {}", e))
+}
+
+fn verify(signed: &SignedEntitlement) -> Result<EntitlementClaims, String> {
+    verify_with(&public_key()?, signed)
+}
+
+fn verify_with(key: &[u8], signed: &SignedEntitlement) -> Result<EntitlementClaims, String> {
+    let engine = base64::engine::general_purpose::STANDARD;
+    let payload = engine
+        .decode(signed.payload.trim())
+        .map_err(|e| format!("Invalid entitlement encoding: {}", e))?;
+    let signature = engine
+        .decode(signed.signature.trim())
+        .map_err(|e| format!("Invalid entitlement signature encoding: {}", e))?;
+    ring::signature::UnparsedPublicKey::new(&ring::signature::ED25519, key)
+        .verify(&payload, &signature)
+        .map_err(|_| "Entitlement signature verification failed".to_string())?;
+    serde_json::from_slice(&payload).map_err(|e| format!("Invalid entitlement claims: {}", e))
+}
+
+/// Keep the entitlement from a successful backend check of `session_id`; ignored when it does not
+/// verify or belongs to another session
+pub fn remember(session_id: &str, signed: Option<&SignedEntitlement>) {
+    let Some(signed) = signed else {
+        return;
+    };
+    match verify(signed) {
+        Ok(claims) if claims.session_id == session_id => {
+            let oldest = Utc::now() - chrono::Duration::hours(MAX_GRACE_HOURS as i64);
+            let mut cache = CACHE.lock();
+            cache.insert(session_id.to_string(), signed.clone());
+            // Entitlements past the longest possible window can never be used again
+            cache.retain(|_, cached| verify(cached).is_ok_and(|claims| claims.issued_at > oldest));
+            if let Err(e) = save_json(cache_path(), &*cache) {
+                warn!("Failed to save entitlement cache: {}", e);
+            }
+        }
+        Ok(claims) => warn!("Ignoring entitlement for session {} sent with session {}", claims.session_id, session_id),
+        Err(e) => warn!("Ignoring entitlement for session {}: {}", session_id, e),
+    }
+}
+
+/// Let `session_id` in without the backend if its cached entitlement is still within the grace
+/// window; callers use this only when the backend could not be reached
+pub fn offline_grant(session_id: &str) -> Result<OfflineGrant, String> {
+    let grace_hours = SETTINGS.lock().grace_hours;
+    if grace_hours == 0 {
+        return Err("Offline grace is turned off".to_string());
+    }
+    let signed = CACHE
+        .lock()
+        .get(session_id)
+        .cloned()
+        .ok_or("The backend has not confirmed this session on this device yet")?;
+    let claims = verify(&signed)?;
+    if claims.session_id != session_id {
+        return Err("Cached entitlement belongs to another session".to_string());
+    }
+    grant(claims, grace_hours, Utc::now())
+}
+
+/// Grant for verified `claims` at `now`: usable until the grace window or the entitlement's own
+/// expiry ends, whichever comes first
+fn grant(claims: EntitlementClaims, grace_hours: u32, now: DateTime<Utc>) -> Result<OfflineGrant, String> {
+    let grace_end = claims.issued_at + chrono::Duration::hours(grace_hours as i64);
+    let usable_until = claims.expires_at.map_or(grace_end, |expires_at| expires_at.min(grace_end));
+    if now > usable_until {
+        return Err(format!("The offline grace window for this session ended at {}", usable_until.to_rfc3339()));
+    }
+    Ok(OfflineGrant {
+        session_id: claims.session_id,
+        user_id: claims.user_id,
+        credits_available: claims.credits_available,
+        issued_at: claims.issued_at,
+        usable_until,
+    })
+}
+
+/// `offline_grant`, logged; None when the session cannot be let in
+pub fn try_offline(session_id: &str, backend_error: &str) -> Option<OfflineGrant> {
+    match offline_grant(session_id) {
+        Ok(grant) => {
+            info!(
+                "📴 Backend unreachable ({}); continuing session {} offline until {}",
+                backend_error, session_id, grant.usable_until
+            );
+            Some(grant)
+        }
+        Err(reason) => {
+            info!("📴 No offline grace for session {}: {}", session_id, reason);
+            None
+        }
+    }
+}
+
+/// Grace window setting, and whether `session_id` could be let in offline right now
+#[tauri::command]
+#[specta::specta]
+pub async fn get_offline_grace(session_id: Option<String>) -> Response<OfflineGraceStatus> {
+    envelope::respond("get_offline_grace", async move {
+        let grace_hours = SETTINGS.lock().grace_hours;
+        let (grant, unavailable_reason) = match session_id.as_deref().map(offline_grant) {
+            Some(Ok(grant)) => (Some(grant), None),
+            Some(Err(reason)) => (None, Some(reason)),
+            None => (None, None),
+        };
+        Ok(OfflineGraceStatus { grace_hours, grant, unavailable_reason })
+    })
+    .await
+}
+
+/// How many hours after the backend last confirmed a session it may still be used offline; 0 turns
+/// offline grace off
+#[tauri::command]
+#[specta::specta]
+pub async fn set_offline_grace_hours(hours: u32) -> Response<u32> {
+    envelope::respond("set_offline_grace_hours", async move {
+        if hours > MAX_GRACE_HOURS {
+            return Err(AppError::new(
+                ErrorCode::InvalidInput,
+                format!("The offline grace window can be at most {} hours", MAX_GRACE_HOURS),
+            ));
+        }
+        let mut settings = SETTINGS.lock();
+        let updated = GraceSettings { grace_hours: hours };
+        save_json(settings_path(), &updated)?;
+        *settings = updated;
+        info!("📴 Offline grace window set to {} hours", hours);
+        Ok(hours)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    fn key_pair() -> Ed25519KeyPair {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&ring::rand::SystemRandom::new()).unwrap();
+        Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap()
+    }
+
+    fn sign(key: &Ed25519KeyPair, claims: &serde_json::Value) -> SignedEntitlement {
+        let payload = serde_json::to_vec(claims).unwrap();
+        let engine = base64::engine::general_purpose::STANDARD;
+        SignedEntitlement {
+            payload: engine.encode(&payload),
+            signature: engine.encode(key.sign(&payload).as_ref()),
+        }
+    }
+
+    fn claims(issued_at: DateTime<Utc>, expires_at: Option<DateTime<Utc>>) -> EntitlementClaims {
+        EntitlementClaims {
+            session_id: "session-1".to_string(),
+            user_id: Some("user-1".to_string()),
+            credits_available: Some(3),
+            issued_at,
+            expires_at,
+        }
+    }
+
+    #[test]
+    fn verify_accepts_signed_claims() {
+        let key = key_pair();
+        let signed = sign(&key, &serde_json::json!({ "session_id": "session-1", "issued_at": "2026-10-17T09:00:00Z" }));
+        let claims = verify_with(key.public_key().as_ref(), &signed).unwrap();
+        assert_eq!(claims.session_id, "session-1");
+        assert_eq!(claims.issued_at, "2026-10-17T09:00:00Z".parse::<DateTime<Utc>>().unwrap());
+    }
+
+    #[test]
+    fn verify_rejects_tampered_payload() {
+        let key = key_pair();
+        let mut signed = sign(&key, &serde_json::json!({ "session_id": "session-1", "issued_at": "2026-10-17T09:00:00Z" }));
+        let forged = serde_json::json!({ "session_id": "session-1", "issued_at": "2030-01-01T00:00:00Z" });
+        signed.payload = base64::engine::general_purpose::STANDARD.encode(serde_json::to_vec(&forged).unwrap());
+        assert!(verify_with(key.public_key().as_ref(), &signed).is_err());
+    }
+
+    #[test]
+    fn verify_rejects_other_key() {
+        let signed = sign(&key_pair(), &serde_json::json!({ "session_id": "session-1", "issued_at": "2026-10-17T09:00:00Z" }));
+        assert!(verify_with(key_pair().public_key().as_ref(), &signed).is_err());
+    }
+
+    #[test]
+    fn grant_within_grace_window() {
+        let now = Utc::now();
+        let grant = grant(claims(now - chrono::Duration::hours(2), None), 24, now).unwrap();
+        assert_eq!(grant.usable_until, grant.issued_at + chrono::Duration::hours(24));
+        assert_eq!(grant.credits_available, Some(3));
+    }
+
+    #[test]
+    fn grant_refused_after_grace_window() {
+        let now = Utc::now();
+        assert!(grant(claims(now - chrono::Duration::hours(25), None), 24, now).is_err());
+    }
+
+    #[test]
+    fn grant_refused_after_entitlement_expiry() {
+        let now = Utc::now();
+        let expired = claims(now - chrono::Duration::hours(2), Some(now - chrono::Duration::minutes(1)));
+        assert!(grant(expired, 24, now).is_err());
+    }
+
+    #[test]
+    fn grant_clamped_to_grace_hours() {
+        let now = Utc::now();
+        let issued_at = now - chrono::Duration::hours(1);
+        let long_lived = claims(issued_at, Some(issued_at + chrono::Duration::days(30)));
+        assert_eq!(grant(long_lived, 4, now).unwrap().usable_until, issued_at + chrono::Duration::hours(4));
+
+        let short_lived = claims(issued_at, Some(issued_at + chrono::Duration::hours(2)));
+        assert_eq!(grant(short_lived, 4, now).unwrap().usable_until, issued_at + chrono::Duration::hours(2));
+    }
+}
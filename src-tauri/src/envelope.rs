@@ -0,0 +1,107 @@
+// Uniform response envelope for Tauri commands
+// Every command goes through `respond` (async) or `respond_sync`, which give the call a request ID,
+// time it, record the latency in `metrics` under the command name and log failures with the ID.
+// The frontend always receives `{ ok, data, error, request_id, duration_ms }`: resolved when the
+// command succeeded, rejected with `ok: false` and the `AppError` when it failed. Rejecting rather
+// than resolving failures keeps async commands with borrowed arguments (which Tauri requires to
+// return a `Result`) on the same path as the rest.
+
+use std::future::Future;
+use std::time::Instant;
+
+use log::{debug, warn};
+use serde::Serialize;
+use specta::Type;
+
+use crate::error::{AppError, ErrorCode};
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct Envelope<T> {
+    pub ok: bool,
+    pub data: Option<T>,
+    pub error: Option<AppError>,
+    /// Appears in the backend log next to a failure, so a UI error can be matched to its log line
+    pub request_id: String,
+    pub duration_ms: f64,
+}
+
+/// What every command returns
+pub type Response<T> = Result<Envelope<T>, Envelope<()>>;
+
+fn finish<T>(command: &'static str, request_id: String, started: Instant, result: Result<T, AppError>) -> Response<T> {
+    let elapsed = started.elapsed();
+    crate::metrics::record(command, elapsed);
+    let duration_ms = elapsed.as_secs_f64() * 1000.0;
+    match result {
+        Ok(data) => {
+            debug!("{} [{}] ok in {:.1}ms", command, request_id, duration_ms);
+            Ok(Envelope {
+                ok: true,
+                data: Some(data),
+                error: None,
+                request_id,
+                duration_ms,
+            })
+        }
+        Err(error) => {
+            warn!(
+                "⚠️ {} [{}] failed in {:.1}ms ({:?}, {}): {}",
+                command, request_id, duration_ms, error.code, error.subsystem, error.message
+            );
+            Err(Envelope {
+                ok: false,
+                data: None,
+                error: Some(error),
+                request_id,
+                duration_ms,
+            })
+        }
+    }
+}
+
+/// Run an async command body and wrap its result
+pub async fn respond<T, F>(command: &'static str, task: F) -> Response<T>
+where
+    F: Future<Output = Result<T, AppError>>,
+{
+    respond_as(command, uuid::Uuid::new_v4().to_string(), task).await
+}
+
+/// `respond` under a request ID chosen by the caller
+pub async fn respond_as<T, F>(command: &'static str, request_id: String, task: F) -> Response<T>
+where
+    F: Future<Output = Result<T, AppError>>,
+{
+    let started = Instant::now();
+    let result = task.await;
+    finish(command, request_id, started, result)
+}
+
+/// Run a synchronous command body and wrap its result
+pub fn respond_sync<T, F>(command: &'static str, task: F) -> Response<T>
+where
+    F: FnOnce() -> Result<T, AppError>,
+{
+    let request_id = uuid::Uuid::new_v4().to_string();
+    let started = Instant::now();
+    let result = task();
+    finish(command, request_id, started, result)
+}
+
+/// Unwraps a command's response when another part of the backend calls the command directly
+pub trait IntoResult<T> {
+    fn into_result(self) -> Result<T, AppError>;
+}
+
+impl<T> IntoResult<T> for Response<T> {
+    fn into_result(self) -> Result<T, AppError> {
+        match self {
+            Ok(envelope) => envelope
+                .data
+                .ok_or_else(|| AppError::new(ErrorCode::Internal, "Command returned no data")),
+            Err(envelope) => Err(envelope
+                .error
+                .unwrap_or_else(|| AppError::new(ErrorCode::Internal, "Command failed without an error"))),
+        }
+    }
+}
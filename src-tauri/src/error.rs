@@ -0,0 +1,293 @@
+// Typed errors returned by every Tauri command
+// Commands used to fail with a bare String, so the frontend could only show the prose. `AppError`
+// carries a stable code, whether retrying can help, the subsystem it came from and a remediation
+// hint, and serializes to `{ code, message, retryable, subsystem, remediation }`.
+//
+// Most internal helpers still return `Result<T, String>`; `?` converts those at the command
+// boundary, classifying the message and taking the subsystem from the module the `?` is in.
+// Code that knows the failure mode builds the error directly with `AppError::new`. Whether an error
+// is retryable drives idempotency keys and the offline fallback, so the retryable codes (network,
+// timeout, unavailable) are only ever set at the source: `SendError::context` for requests,
+// `ErrorCode::from_status` for HTTP statuses. Classifying a message never yields one.
+
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use specta::datatype::{reference::Reference, DataType};
+use specta::{Generics, Type, TypeCollection};
+use std::panic::Location;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    NotFound,
+    InvalidInput,
+    /// The feature does not exist on this OS or in this mode
+    Unsupported,
+    /// The OS or the user refused access (microphone, screen recording, files)
+    PermissionDenied,
+    /// Local-only mode forbids the cloud request
+    PrivacyBlocked,
+    /// Sign-in is missing or expired
+    Unauthorized,
+    Network,
+    Timeout,
+    /// A service or device is busy, not configured or not running
+    Unavailable,
+    /// Stopped by `cancel_request`
+    Cancelled,
+    /// The command needs an activated session and there is none (or it is another one)
+    NoActiveSession,
+    Internal,
+}
+
+impl ErrorCode {
+    pub fn retryable(self) -> bool {
+        matches!(self, ErrorCode::Network | ErrorCode::Timeout | ErrorCode::Unavailable)
+    }
+
+    fn remediation(self) -> Option<&'static str> {
+        match self {
+            ErrorCode::PermissionDenied => Some("Grant the permission in the OS privacy settings, then try again"),
+            ErrorCode::PrivacyBlocked => Some("Turn off local-only mode in Privacy settings to use this feature"),
+            ErrorCode::Unauthorized => Some("Sign in again"),
+            ErrorCode::Network => Some("Check your internet connection and try again"),
+            ErrorCode::Timeout => Some("The service is slow to respond; try again in a moment"),
+            ErrorCode::Unavailable => Some("Try again shortly; if it keeps failing, restart MockMate"),
+            ErrorCode::NoActiveSession => Some("Connect to your interview session and activate it first"),
+            ErrorCode::NotFound | ErrorCode::InvalidInput | ErrorCode::Unsupported | ErrorCode::Cancelled | ErrorCode::Internal => None,
+        }
+    }
+
+    /// Code for a non-success HTTP status
+    pub fn from_status(status: reqwest::StatusCode) -> ErrorCode {
+        match status.as_u16() {
+            401 | 403 => ErrorCode::Unauthorized,
+            404 | 410 => ErrorCode::NotFound,
+            408 | 504 => ErrorCode::Timeout,
+            429 | 500..=599 => ErrorCode::Unavailable,
+            400..=499 => ErrorCode::InvalidInput,
+            _ => ErrorCode::Internal,
+        }
+    }
+
+    /// Last-resort guess from a legacy error message. Never a retryable code: wording like
+    /// "network policy denied" or "timeout must be positive" must not make an error retryable
+    fn classify(message: &str) -> ErrorCode {
+        let lower = message.to_lowercase();
+        let has = |needles: &[&str]| needles.iter().any(|needle| lower.contains(needle));
+        if lower.contains(crate::privacy::BLOCKED_MESSAGE) {
+            ErrorCode::PrivacyBlocked
+        } else if has(&["not available on", "not available in portable mode", "not supported", "unsupported"]) {
+            ErrorCode::Unsupported
+        } else if has(&["unauthorized", "401", "not signed in", "token expired", "expired token", "sign in"]) {
+            ErrorCode::Unauthorized
+        } else if has(&["permission", "access denied", "access is denied", "not granted", "denied"]) {
+            ErrorCode::PermissionDenied
+        } else if has(&["not found", "no such", "does not exist", "unknown session", "no active"]) {
+            ErrorCode::NotFound
+        } else if has(&["invalid", "must be", "is required", "cannot be empty", "too long", "too large", "malformed"]) {
+            ErrorCode::InvalidInput
+        } else {
+            ErrorCode::Internal
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AppError {
+    pub code: ErrorCode,
+    pub message: String,
+    /// Module that raised the error, e.g. "secrets" or "pluely_audio"
+    pub subsystem: &'static str,
+}
+
+/// Module name of a source path such as `src/database/mod.rs` or `src\secrets.rs`
+fn subsystem_of(location: &'static Location<'static>) -> &'static str {
+    let file = location.file();
+    // Conversions that pass through the standard library have no useful caller
+    if file.contains("/rustc/") || file.contains("library") || file.contains(".cargo") {
+        return "app";
+    }
+    let mut parts = file.rsplit(['/', '\\']);
+    // Non-Windows stubs report as the module they stand in for
+    let stem = parts.next().unwrap_or(file).trim_end_matches(".rs").trim_end_matches("_stub");
+    match stem {
+        "mod" => parts.next().unwrap_or("app"),
+        "lib" | "main" => "app",
+        stem => stem,
+    }
+}
+
+impl AppError {
+    #[track_caller]
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        AppError {
+            code,
+            message: message.into(),
+            subsystem: subsystem_of(Location::caller()),
+        }
+    }
+
+    pub fn retryable(&self) -> bool {
+        self.code.retryable()
+    }
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AppError", 5)?;
+        state.serialize_field("code", &self.code)?;
+        state.serialize_field("message", &self.message)?;
+        state.serialize_field("retryable", &self.retryable())?;
+        state.serialize_field("subsystem", self.subsystem)?;
+        state.serialize_field("remediation", &self.code.remediation())?;
+        state.end()
+    }
+}
+
+/// Serialized form of `AppError`, which only exists for the TypeScript bindings
+#[derive(Type)]
+#[specta(rename = "AppError")]
+#[allow(dead_code)]
+struct AppErrorShape {
+    code: ErrorCode,
+    message: String,
+    retryable: bool,
+    subsystem: String,
+    remediation: Option<String>,
+}
+
+impl Type for AppError {
+    fn inline(types: &mut TypeCollection, generics: Generics) -> DataType {
+        AppErrorShape::inline(types, generics)
+    }
+
+    fn reference(types: &mut TypeCollection, generics: &[DataType]) -> Reference {
+        AppErrorShape::reference(types, generics)
+    }
+}
+
+impl From<String> for AppError {
+    #[track_caller]
+    fn from(message: String) -> Self {
+        let code = ErrorCode::classify(&message);
+        AppError {
+            code,
+            message,
+            subsystem: subsystem_of(Location::caller()),
+        }
+    }
+}
+
+impl From<&str> for AppError {
+    #[track_caller]
+    fn from(message: &str) -> Self {
+        AppError::from(message.to_string())
+    }
+}
+
+impl From<anyhow::Error> for AppError {
+    #[track_caller]
+    fn from(error: anyhow::Error) -> Self {
+        // A failed request anywhere in the chain decides the code
+        let code = error.chain().find_map(|cause| {
+            cause
+                .downcast_ref::<crate::net_trace::SendError>()
+                .map(crate::net_trace::SendError::code)
+                .or_else(|| cause.downcast_ref::<reqwest::Error>().map(request_code))
+        });
+        match code {
+            Some(code) => AppError::new(code, error.to_string()),
+            None => AppError::from(error.to_string()),
+        }
+    }
+}
+
+/// Code of a request that failed before or without a response
+pub(crate) fn request_code(error: &reqwest::Error) -> ErrorCode {
+    if error.is_timeout() {
+        ErrorCode::Timeout
+    } else if error.is_builder() {
+        ErrorCode::InvalidInput
+    } else if let Some(status) = error.status() {
+        ErrorCode::from_status(status)
+    } else if error.is_decode() {
+        ErrorCode::Internal
+    } else {
+        ErrorCode::Network
+    }
+}
+
+impl From<std::io::Error> for AppError {
+    #[track_caller]
+    fn from(error: std::io::Error) -> Self {
+        let code = match error.kind() {
+            std::io::ErrorKind::NotFound => ErrorCode::NotFound,
+            std::io::ErrorKind::PermissionDenied => ErrorCode::PermissionDenied,
+            std::io::ErrorKind::TimedOut => ErrorCode::Timeout,
+            std::io::ErrorKind::InvalidInput | std::io::ErrorKind::InvalidData => ErrorCode::InvalidInput,
+            _ => ErrorCode::Internal,
+        };
+        AppError::new(code, error.to_string())
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    #[track_caller]
+    fn from(error: serde_json::Error) -> Self {
+        AppError::new(ErrorCode::InvalidInput, error.to_string())
+    }
+}
+
+impl From<tauri::Error> for AppError {
+    #[track_caller]
+    fn from(error: tauri::Error) -> Self {
+        AppError::new(ErrorCode::Internal, error.to_string())
+    }
+}
+
+impl From<reqwest::Error> for AppError {
+    #[track_caller]
+    fn from(error: reqwest::Error) -> Self {
+        let code = if error.is_timeout() {
+            ErrorCode::Timeout
+        } else if error.status().is_some_and(|status| status.as_u16() == 401 || status.as_u16() == 403) {
+            ErrorCode::Unauthorized
+        } else {
+            ErrorCode::Network
+        };
+        AppError::new(code, error.to_string())
+    }
+}
+
+impl From<crate::net_trace::SendError> for AppError {
+    #[track_caller]
+    fn from(error: crate::net_trace::SendError) -> Self {
+        match error {
+            crate::net_trace::SendError::Blocked(_) => AppError::new(ErrorCode::PrivacyBlocked, error.to_string()),
+            crate::net_trace::SendError::Http(e) => AppError::from(e),
+        }
+    }
+}
+
+impl From<crate::database::DatabaseError> for AppError {
+    #[track_caller]
+    fn from(error: crate::database::DatabaseError) -> Self {
+        use crate::database::DatabaseError;
+        let code = match &error {
+            DatabaseError::ConnectionFailed(_) => ErrorCode::Unavailable,
+            DatabaseError::SessionNotFound(_) | DatabaseError::UserNotFound(_) => ErrorCode::NotFound,
+            DatabaseError::InsufficientCredits | DatabaseError::InvalidSessionStatus(_) => ErrorCode::InvalidInput,
+            DatabaseError::QueryFailed(_) => ErrorCode::Internal,
+        };
+        AppError::new(code, error.to_string())
+    }
+}
@@ -0,0 +1,399 @@
+// Names and payloads of every event the backend emits to the frontend
+// Emit sites use the constants below instead of string literals, and payloads that used to be
+// ad-hoc `json!` objects have a struct here. `list_events` returns each name with a description
+// and the JSON schema of its payload, for the frontend and devtools to discover what to listen to.
+
+use schemars::schema::RootSchema;
+use schemars::{schema_for, JsonSchema};
+use serde::Serialize;
+use specta::datatype::{DataType, FunctionResultVariant};
+use specta::{Type, TypeCollection};
+use specta_typescript::{ExportError, Typescript};
+
+use crate::envelope::{self, Response};
+
+// AI answers
+pub const AI_STREAM_START: &str = "ai-stream-start";
+pub const AI_STREAM_TOKEN: &str = "ai-stream-token";
+pub const AI_STREAM_COMPLETE: &str = "ai-stream-complete";
+pub const AI_STREAM_ERROR: &str = "ai-stream-error";
+pub const AI_RESPONSE_UPDATE: &str = "ai-response-update";
+pub const AI_RESPONSE_GEOMETRY: &str = "ai-response-geometry";
+pub const CODING_SOLUTION: &str = "coding-solution";
+pub const STAR_ANSWER: &str = "star-answer";
+pub const SYSTEM_DESIGN_SECTION: &str = "system-design-section";
+pub const SYSTEM_DESIGN_ANSWER: &str = "system-design-answer";
+pub const STORE_AI_QUESTION: &str = "store-ai-question";
+pub const ACCESSIBILITY_QUESTION_DETECTED: &str = "accessibility-question-detected";
+
+// Audio capture and transcription
+pub const AUDIO_CHUNK: &str = "audio-chunk";
+pub const AUDIO_LEVEL: &str = "audio-level";
+pub const SPEECH_START: &str = "speech-start";
+pub const SPEECH_DETECTED: &str = "speech-detected";
+pub const MIC_AUDIO_CHUNK: &str = "mic-audio-chunk";
+pub const MIC_SPEECH_START: &str = "mic-speech-start";
+pub const MIC_SPEECH_DETECTED: &str = "mic-speech-detected";
+pub const PLUELY_AUDIO_DEBUG: &str = "pluely-audio-debug";
+pub const PLUELY_MICROPHONE_DEBUG: &str = "pluely-microphone-debug";
+pub const DEEPGRAM_STATUS: &str = "deepgram-status";
+pub const TRANSCRIPTION_STATUS: &str = "transcription-status";
+pub const TRANSCRIPTION_RESULT: &str = "transcription-result";
+pub const TRANSCRIPTION_ERROR: &str = "transcription-error";
+pub const AUDIO_DEBUG_DUMP_COMPLETE: &str = "audio-debug-dump-complete";
+pub const ZOOM_CAPTION: &str = "zoom-caption";
+pub const ZOOM_CAPTION_STATUS: &str = "zoom-caption-status";
+
+// Sessions
+pub const SESSION_LAUNCH: &str = "session-launch";
+pub const TEMP_TOKEN_AUTH_RESULT: &str = "temp-token-auth-result";
+pub const SESSION_RECORDING_STATE: &str = "session-recording-state";
+pub const PACING_FEEDBACK: &str = "pacing-feedback";
+pub const PRACTICE_UPDATE: &str = "practice-update";
+pub const INTERVIEW_UPCOMING: &str = "interview-upcoming";
+pub const PROVIDERS_WARMED_UP: &str = "providers-warmed-up";
+
+// App
+pub const STEALTH_HOTKEY: &str = "stealth-hotkey";
+pub const PERMISSION_REVOKED: &str = "permission-revoked";
+pub const CONSENT_REQUIRED: &str = "consent-required";
+pub const OS_APPEARANCE_CHANGED: &str = "os-appearance-changed";
+pub const UPDATE_READY: &str = "update-ready";
+pub const METRICS_SNAPSHOT: &str = "metrics-snapshot";
+pub const LOG_EVENT: &str = "log-event";
+pub const SCRIPT_NOTIFICATION: &str = "script-notification";
+pub const SCRIPT_EVENT: &str = "script-event";
+pub const WEBSOCKET_MESSAGE: &str = "websocket-message";
+pub const WEBSOCKET_ERROR: &str = "websocket-error";
+pub const WEBSOCKET_CLOSED: &str = "websocket-closed";
+pub const PROGRESS: &str = "progress";
+pub const DEPRECATED_COMMAND: &str = "deprecated-command";
+pub const AUDIO_DEVICES_CHANGED: &str = "audio-devices-changed";
+pub const PTT_STATE: &str = "ptt-state";
+pub const WAKE_WORD_DETECTED: &str = "wake-word-detected";
+pub const MEETING_STARTED: &str = "meeting-started";
+pub const MEETING_ENDED: &str = "meeting-ended";
+pub const AUTO_ANALYSIS_TRIGGERED: &str = "auto-analysis-triggered";
+pub const TELEPROMPTER_STATE: &str = "teleprompter-state";
+pub const QUESTION_TRANSLATED: &str = "question-translated";
+pub const QUESTION_DEDUPLICATED: &str = "question-deduplicated";
+pub const TRANSCRIPTION_FALLBACK: &str = "transcription-fallback";
+pub const CREDIT_BALANCE: &str = "credit-balance";
+pub const CREDIT_PURCHASE: &str = "credit-purchase";
+pub const SESSION_AUTH: &str = "session-auth";
+pub const AUTH_STATE: &str = "auth-state";
+
+/// Payload of `ai-stream-token`; `token` repeats `text` for older listeners
+#[derive(Debug, Clone, Serialize, JsonSchema, Type)]
+pub struct AiStreamToken {
+    pub text: String,
+    pub token: String,
+    /// Unix milliseconds
+    pub timestamp: u64,
+}
+
+/// Payload of `ai-stream-complete`: the finished Markdown answer, or the analysis result when the
+/// answer came from the accessibility-based screen analysis
+#[derive(Serialize, JsonSchema, Type)]
+#[serde(untagged)]
+pub enum AiStreamComplete {
+    Markdown(String),
+    ScreenAnalysis(crate::AiAnalysisResult),
+}
+
+/// Payload of `store-ai-question`
+#[derive(Debug, Clone, Serialize, JsonSchema, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreAiQuestion {
+    pub question_text: String,
+    pub question_number: u32,
+    pub category: String,
+    pub difficulty_level: String,
+    pub source: String,
+    pub metadata: StoreAiQuestionMetadata,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreAiQuestionMetadata {
+    pub timestamp: String,
+    pub ai_provider: String,
+    pub ai_model: String,
+    pub confidence: f32,
+    pub analysis_type: String,
+    /// Minutes
+    pub expected_duration: u32,
+    pub source_description: String,
+    pub extracted_text_length: usize,
+}
+
+/// Payload of `audio-level`, sent about once a second while system audio is captured
+#[derive(Debug, Clone, Serialize, JsonSchema, Type)]
+pub struct AudioLevel {
+    pub rms: f32,
+    pub peak: f32,
+    pub speech: bool,
+    /// Unix milliseconds
+    pub timestamp: i64,
+}
+
+/// Payload of `deepgram-status`
+#[derive(Debug, Clone, Serialize, JsonSchema, Type)]
+pub struct DeepgramStatus {
+    /// "connected", "disconnected" or "stopped"
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Unix milliseconds
+    pub timestamp: i64,
+}
+
+/// Payload of `transcription-status`
+#[derive(Debug, Clone, Serialize, JsonSchema, Type)]
+pub struct TranscriptionStatus {
+    /// "starting", "streaming", "partial", "stopping", "disconnected" or "error"
+    pub status: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Unix milliseconds
+    pub timestamp: i64,
+}
+
+/// Payload of `transcription-error` and `websocket-error`
+#[derive(Debug, Clone, Serialize, JsonSchema, Type)]
+pub struct ErrorPayload {
+    pub error: String,
+}
+
+/// Payload of `audio-debug-dump-complete`
+#[derive(Debug, Clone, Serialize, JsonSchema, Type)]
+pub struct AudioDebugDumpComplete {
+    /// Directory holding the WAV files
+    pub path: String,
+}
+
+/// Payload of `session-launch`. A launch from the web app's deep link carries `token` and
+/// `user_id`; a temp-token launch that is not authenticated automatically carries `temp_token`
+/// and `auto_connect` for the frontend to finish
+#[derive(Debug, Clone, Default, Serialize, JsonSchema, Type)]
+pub struct SessionLaunch {
+    /// "session-launch" on temp-token launches
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    pub session_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temp_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_connect: Option<bool>,
+    pub launched_at: String,
+}
+
+/// Payload of `temp-token-auth-result`
+#[derive(Debug, Clone, Default, Serialize, JsonSchema, Type)]
+pub struct TempTokenAuthResult {
+    /// "temp-token-auth-success", "temp-token-auth-failed" or "temp-token-auth-error"
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub session_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session: Option<crate::SessionData>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining_credits: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub auto_authenticated: bool,
+    pub launched_at: String,
+}
+
+/// Payload of `script-event`, raised by a user script's `emit_ui`
+#[derive(Debug, Clone, Serialize, JsonSchema, Type)]
+pub struct ScriptUiEvent {
+    pub name: String,
+    pub payload: serde_json::Value,
+}
+
+/// Payload of `progress`, reported by long-running commands under their request ID
+#[derive(Debug, Clone, Serialize, JsonSchema, Type)]
+pub struct RequestProgress {
+    pub request_id: String,
+    pub command: String,
+    /// "started", a command-specific stage, then "completed", "failed" or "cancelled"
+    pub stage: String,
+    /// 0-100 when the command can estimate it
+    pub percent: Option<f32>,
+    /// True on the final event
+    pub done: bool,
+}
+
+/// An emitted event as reported by `list_events`
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct EventDescriptor {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// JSON schema of the payload; the `null` type for events that carry none
+    pub payload_schema: serde_json::Value,
+}
+
+struct EventSpec {
+    name: &'static str,
+    description: &'static str,
+    schema: fn() -> RootSchema,
+    /// Payload type for the generated TypeScript bindings
+    payload: fn(&mut TypeCollection) -> DataType,
+}
+
+impl EventSpec {
+    const fn of<T: JsonSchema + Type>(name: &'static str, description: &'static str) -> Self {
+        EventSpec {
+            name,
+            description,
+            schema: schema::<T>,
+            payload: payload::<T>,
+        }
+    }
+}
+
+fn schema<T: JsonSchema>() -> RootSchema {
+    schema_for!(T)
+}
+
+fn payload<T: Type>(types: &mut TypeCollection) -> DataType {
+    T::reference(types, &[]).inner
+}
+
+const REGISTRY: &[EventSpec] = &[
+    EventSpec::of::<()>(AI_STREAM_START, "An AI answer started streaming"),
+    EventSpec::of::<AiStreamToken>(AI_STREAM_TOKEN, "A chunk of a streaming AI answer (~30ms of tokens)"),
+    EventSpec::of::<AiStreamComplete>(AI_STREAM_COMPLETE, "An AI answer finished"),
+    EventSpec::of::<String>(AI_STREAM_ERROR, "An AI answer failed; the payload is the error message"),
+    EventSpec::of::<crate::ai_stream::ResponseUpdate>(AI_RESPONSE_UPDATE, "Stream, token, completion or error update for the AI response window"),
+    EventSpec::of::<crate::resize_coordinator::SettledGeometry>(AI_RESPONSE_GEOMETRY, "The AI response window settled at a new size and position"),
+    EventSpec::of::<crate::coding_challenge::CodingSolution>(CODING_SOLUTION, "Typed coding solution for the response window, next to its Markdown rendering"),
+    EventSpec::of::<crate::experience_bank::StarAnswer>(STAR_ANSWER, "Typed STAR answer for the response window, next to its Markdown rendering"),
+    EventSpec::of::<crate::system_design::SectionChunk>(SYSTEM_DESIGN_SECTION, "One streamed piece of a system design answer section"),
+    EventSpec::of::<crate::system_design::SystemDesignAnswer>(SYSTEM_DESIGN_ANSWER, "The finished, typed system design answer"),
+    EventSpec::of::<StoreAiQuestion>(STORE_AI_QUESTION, "A question generated from screen analysis, for the frontend to save"),
+    EventSpec::of::<crate::accessibility_reader::AccessibilityTextResult>(ACCESSIBILITY_QUESTION_DETECTED, "A question was read from another app's window"),
+    EventSpec::of::<String>(AUDIO_CHUNK, "Base64 PCM chunk of system audio"),
+    EventSpec::of::<AudioLevel>(AUDIO_LEVEL, "System audio level for visualization"),
+    EventSpec::of::<()>(SPEECH_START, "Speech started in system audio"),
+    EventSpec::of::<String>(SPEECH_DETECTED, "Base64 PCM of a system audio speech segment"),
+    EventSpec::of::<String>(MIC_AUDIO_CHUNK, "Base64 PCM chunk of microphone audio"),
+    EventSpec::of::<()>(MIC_SPEECH_START, "Speech started on the microphone"),
+    EventSpec::of::<String>(MIC_SPEECH_DETECTED, "Base64 PCM of a microphone speech segment"),
+    EventSpec::of::<serde_json::Value>(PLUELY_AUDIO_DEBUG, "Free-form system audio capture diagnostics"),
+    EventSpec::of::<serde_json::Value>(PLUELY_MICROPHONE_DEBUG, "Free-form microphone capture diagnostics"),
+    EventSpec::of::<DeepgramStatus>(DEEPGRAM_STATUS, "Deepgram streaming connected, disconnected or stopped"),
+    EventSpec::of::<TranscriptionStatus>(TRANSCRIPTION_STATUS, "Progress of the combined capture and transcription pipeline"),
+    EventSpec::of::<crate::deepgram_streaming::DeepgramTranscriptionResult>(TRANSCRIPTION_RESULT, "Interim or final transcript of system audio"),
+    EventSpec::of::<ErrorPayload>(TRANSCRIPTION_ERROR, "The transcription connection failed"),
+    EventSpec::of::<AudioDebugDumpComplete>(AUDIO_DEBUG_DUMP_COMPLETE, "An audio debug dump finished writing"),
+    EventSpec::of::<crate::zoom_captions::ZoomCaption>(ZOOM_CAPTION, "A caption received from the Zoom caption bridge"),
+    EventSpec::of::<crate::zoom_captions::ZoomCaptionStatus>(ZOOM_CAPTION_STATUS, "Zoom caption bridge connection status"),
+    EventSpec::of::<SessionLaunch>(SESSION_LAUNCH, "The app was launched for a session from a deep link"),
+    EventSpec::of::<TempTokenAuthResult>(TEMP_TOKEN_AUTH_RESULT, "Outcome of authenticating a deep-link launch with its temporary token"),
+    EventSpec::of::<crate::session_recorder::RecordingStatus>(SESSION_RECORDING_STATE, "Session recording started or stopped"),
+    EventSpec::of::<crate::pacing::PacingFeedback>(PACING_FEEDBACK, "Speaking pace feedback on the current answer"),
+    EventSpec::of::<crate::practice::PracticeUpdate<'static>>(PRACTICE_UPDATE, "A practice session changed state or moved to a new turn"),
+    EventSpec::of::<crate::calendar::UpcomingInterview>(INTERVIEW_UPCOMING, "A calendar interview is about to start"),
+    EventSpec::of::<crate::warmup::WarmUpReport>(PROVIDERS_WARMED_UP, "AI and transcription providers were warmed up for a session"),
+    EventSpec::of::<crate::stealth_hotkeys_simple::StealthHotkeyEvent>(STEALTH_HOTKEY, "A stealth hotkey was pressed"),
+    EventSpec::of::<crate::permissions::PermissionRevokedEvent>(PERMISSION_REVOKED, "An OS permission was revoked and dependent capture paused"),
+    EventSpec::of::<crate::app_consent::ConsentRequest>(CONSENT_REQUIRED, "Reading text from an app needs the user's consent"),
+    EventSpec::of::<crate::appearance::AppearancePrefs>(OS_APPEARANCE_CHANGED, "OS dark mode, contrast, motion or transparency preferences changed"),
+    EventSpec::of::<crate::updater::UpdateReady>(UPDATE_READY, "An update was downloaded and is ready to install"),
+    EventSpec::of::<Vec<crate::metrics::MetricSummary>>(METRICS_SNAPSHOT, "Periodic latency percentiles per metric"),
+    EventSpec::of::<crate::logging::LogEntry>(LOG_EVENT, "A backend log record"),
+    EventSpec::of::<crate::scripting::ScriptNotification>(SCRIPT_NOTIFICATION, "A user script asked to show a notification"),
+    EventSpec::of::<ScriptUiEvent>(SCRIPT_EVENT, "A user script emitted a custom UI event"),
+    EventSpec::of::<serde_json::Value>(WEBSOCKET_MESSAGE, "A JSON message from the backend WebSocket"),
+    EventSpec::of::<ErrorPayload>(WEBSOCKET_ERROR, "The backend WebSocket failed"),
+    EventSpec::of::<serde_json::Value>(WEBSOCKET_CLOSED, "The backend WebSocket closed; the payload is an empty object"),
+    EventSpec::of::<RequestProgress>(PROGRESS, "Progress of a long-running command, which `cancel_request` can stop"),
+    EventSpec::of::<crate::compat::DeprecatedCommand>(DEPRECATED_COMMAND, "A deprecated command alias was called"),
+    EventSpec::of::<crate::audio_devices::AudioDeviceSelection>(
+        AUDIO_DEVICES_CHANGED,
+        "A preferred microphone or loopback device was connected, disconnected or reconnected under a new ID",
+    ),
+    EventSpec::of::<crate::push_to_talk::PttState>(PTT_STATE, "The push-to-talk gate opened or closed, or the capture mode changed"),
+    EventSpec::of::<crate::wake_word::WakeWordDetected>(WAKE_WORD_DETECTED, "The wake phrase was heard on the microphone"),
+    EventSpec::of::<crate::meeting_detection::MeetingEvent>(MEETING_STARTED, "A Teams, Zoom or Meet call became active"),
+    EventSpec::of::<crate::meeting_detection::MeetingEvent>(MEETING_ENDED, "A detected call ended"),
+    EventSpec::of::<crate::auto_analysis::AutoAnalysisTriggered>(
+        AUTO_ANALYSIS_TRIGGERED,
+        "A large block of new text appeared in the monitored window and is being analyzed",
+    ),
+    EventSpec::of::<crate::teleprompter::TeleprompterState>(
+        TELEPROMPTER_STATE,
+        "Teleprompter mode was turned on or off, or its speed, pause or font size changed",
+    ),
+    EventSpec::of::<crate::answer_language::QuestionTranslation>(
+        QUESTION_TRANSLATED,
+        "A question asked in another language was translated into the answer language",
+    ),
+    EventSpec::of::<crate::question_dedup::QuestionDeduplicated>(
+        QUESTION_DEDUPLICATED,
+        "A question matched one seen moments ago and reuses its answer instead of a new AI call",
+    ),
+    EventSpec::of::<crate::vosk_fallback::TranscriptionFallback>(
+        TRANSCRIPTION_FALLBACK,
+        "Transcription switched to the offline Vosk fallback (reduced accuracy) or back to Deepgram",
+    ),
+    EventSpec::of::<crate::credits::CreditBalance>(CREDIT_BALANCE, "The credit balance of the session's user was re-read"),
+    EventSpec::of::<crate::credits::CreditPurchase>(CREDIT_PURCHASE, "A credit checkout was opened, completed or cancelled"),
+    EventSpec::of::<crate::session_auth::SessionAuthStatus>(
+        SESSION_AUTH,
+        "A session's temp token was authenticated or refreshed, failed to refresh, or expired",
+    ),
+    EventSpec::of::<crate::auth::AuthStatus>(AUTH_STATE, "Desktop sign-in started, completed or ended"),
+];
+
+/// Every event the backend emits, with the JSON schema of its payload
+#[tauri::command]
+#[specta::specta]
+pub fn list_events() -> Response<Vec<EventDescriptor>> {
+    envelope::respond_sync("list_events", || {
+        REGISTRY
+            .iter()
+            .map(|spec| {
+                Ok(EventDescriptor {
+                    name: spec.name,
+                    description: spec.description,
+                    payload_schema: serde_json::to_value((spec.schema)())?,
+                })
+            })
+            .collect()
+    })
+}
+
+/// Add every event payload type to `types`, by event name
+pub(crate) fn payload_types(types: &mut TypeCollection) -> Vec<(&'static str, DataType)> {
+    REGISTRY.iter().map(|spec| (spec.name, (spec.payload)(types))).collect()
+}
+
+/// `EVENTS` (constant name to event name) and `EventPayloads` (event name to payload type) for
+/// the generated TypeScript bindings
+pub(crate) fn typescript(
+    ts: &Typescript,
+    payloads: &[(&'static str, DataType)],
+    types: &TypeCollection,
+) -> Result<String, ExportError> {
+    let mut names = String::new();
+    let mut payload_map = String::new();
+    for (spec, (name, payload)) in REGISTRY.iter().zip(payloads) {
+        let payload = specta_typescript::datatype(ts, &FunctionResultVariant::Value(payload.clone()), types)?;
+        names.push_str(&format!("\t/** {} */\n\t{}: \"{}\",\n", spec.description, name.to_uppercase().replace('-', "_"), name));
+        payload_map.push_str(&format!("\t\"{}\": {};\n", name, payload));
+    }
+    Ok(format!(
+        "\n/** backend events **/\n\nexport const EVENTS = {{\n{}}} as const;\n\nexport type EventPayloads = {{\n{}}};\n",
+        names, payload_map
+    ))
+}
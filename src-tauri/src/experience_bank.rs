@@ -0,0 +1,341 @@
+// Experience bank and STAR answer builder
+// The user keeps their real stories (situation, task, action, result) with theme tags. When a
+// behavioral question comes up, the best-matching stories are retrieved and the answer is built
+// from the selected story in STAR form, so the AI retells the user's experience instead of
+// inventing a generic anecdote. The bank is a single JSON file in %APPDATA%\MockMate.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use log::info;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::State;
+
+use crate::AppState;
+use crate::cancellation;
+use crate::envelope::{self, Response};
+
+/// Matches scoring below this are not offered for a question
+const MIN_MATCH_SCORE: f32 = 0.15;
+const DEFAULT_MATCH_LIMIT: usize = 3;
+
+/// Words too common to say anything about a story's fit
+const STOPWORDS: [&str; 40] = [
+    "a", "an", "the", "and", "or", "but", "to", "of", "in", "on", "for", "with", "at", "by", "from", "about",
+    "you", "your", "me", "my", "i", "we", "our", "it", "is", "was", "were", "are", "be", "had", "have", "how",
+    "what", "when", "tell", "time", "describe", "give", "example", "did",
+];
+
+static BANK: Lazy<Mutex<ExperienceBank>> = Lazy::new(|| Mutex::new(load_bank()));
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct Story {
+    pub id: String,
+    pub title: String,
+    pub situation: String,
+    pub task: String,
+    pub action: String,
+    pub result: String,
+    /// Themes the story demonstrates, e.g. "conflict", "leadership", "failure"
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub times_used: u32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Story {
+    fn keywords(&self) -> HashSet<String> {
+        let text = format!("{} {} {} {} {}", self.title, self.situation, self.task, self.action, self.result);
+        keywords(&text)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ExperienceBank {
+    stories: Vec<Story>,
+}
+
+/// New or edited story; on update, only provided fields change
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct StoryInput {
+    pub title: Option<String>,
+    pub situation: Option<String>,
+    pub task: Option<String>,
+    pub action: Option<String>,
+    pub result: Option<String>,
+    pub tags: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct StoryMatch {
+    pub story: Story,
+    /// 0-1; tag hits count more than words from the story body
+    pub score: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct StarAnswer {
+    pub question: String,
+    pub story_id: String,
+    pub story_title: String,
+    /// Spoken-style answer in Markdown with Situation / Task / Action / Result sections
+    pub answer: String,
+    /// Other stories that would also fit, best first
+    pub alternatives: Vec<StoryMatch>,
+}
+
+fn bank_path() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("experience_bank.json")
+}
+
+fn load_bank() -> ExperienceBank {
+    std::fs::read(bank_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn save_bank(bank: &ExperienceBank) -> Result<(), String> {
+    let path = bank_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(bank).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| e.to_string())
+}
+
+fn keywords(text: &str) -> HashSet<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.len() > 2 && !STOPWORDS.contains(word))
+        // Crude stemming: truncating long words lets variants like "deadline"/"deadlines" meet
+        .map(|word| word.chars().take(6).collect())
+        .collect()
+}
+
+fn clean_tags(tags: &[String]) -> Vec<String> {
+    let mut tags: Vec<String> = tags
+        .iter()
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+fn non_empty(value: &Option<String>) -> Option<String> {
+    value.as_ref().map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+/// Whether a question asks for a past experience (and so should be answered from a story)
+pub fn is_behavioral(question: &str) -> bool {
+    crate::question_topic::QuestionTopic::classify(question) == Some(crate::question_topic::QuestionTopic::Behavioral)
+}
+
+/// Stories ranked by how well they fit `question`
+pub fn find_matches(question: &str, limit: usize) -> Vec<StoryMatch> {
+    let question_words = keywords(question);
+    if question_words.is_empty() {
+        return Vec::new();
+    }
+    let bank = BANK.lock();
+    let mut matches: Vec<StoryMatch> = bank
+        .stories
+        .iter()
+        .filter_map(|story| {
+            let tag_hits = story
+                .tags
+                .iter()
+                .filter(|tag| keywords(tag).iter().any(|word| question_words.contains(word)))
+                .count();
+            let word_hits = story.keywords().intersection(&question_words).count();
+            let score = ((tag_hits as f32 * 2.0 + word_hits as f32) / (question_words.len() as f32 + 1.0)).min(1.0);
+            (score >= MIN_MATCH_SCORE).then(|| StoryMatch { story: story.clone(), score })
+        })
+        .collect();
+    matches.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| b.story.times_used.cmp(&a.story.times_used)));
+    matches.truncate(limit);
+    matches
+}
+
+fn mark_used(id: &str) {
+    let mut bank = BANK.lock();
+    if let Some(story) = bank.stories.iter_mut().find(|s| s.id == id) {
+        story.times_used += 1;
+        if let Err(e) = save_bank(&bank) {
+            log::warn!("Failed to save experience bank: {}", e);
+        }
+    }
+}
+
+/// Build a STAR answer from `story_id`, or from the best-matching story when none is given.
+/// Returns `Ok(None)` when no story fits the question.
+pub(crate) async fn build_star_answer(
+    state: &AppState,
+    provider: &str,
+    model: &str,
+    question: &str,
+    story_id: Option<&str>,
+) -> Result<Option<StarAnswer>, String> {
+    let mut matches = find_matches(question, DEFAULT_MATCH_LIMIT + 1);
+    let story = match story_id {
+        Some(id) => {
+            matches.retain(|m| m.story.id != id);
+            let bank = BANK.lock();
+            bank.stories.iter().find(|s| s.id == id).cloned().ok_or_else(|| format!("Story {} not found", id))?
+        }
+        None if matches.is_empty() => return Ok(None),
+        None => matches.remove(0).story,
+    };
+    matches.truncate(DEFAULT_MATCH_LIMIT);
+
+    let role = state.interview_context.lock().position.clone();
+    let prompt = format!(
+        "Answer this behavioral interview question using ONLY the candidate's story below. Do not add \
+         events, numbers, people or outcomes that are not in the story; you may rephrase and tighten it.\n\n\
+         Question: {}\n{}\nStory \"{}\":\n- Situation: {}\n- Task: {}\n- Action: {}\n- Result: {}\n\n\
+         Reply with only the answer in Markdown, as the candidate speaking in first person, using the \
+         headings **Situation**, **Task**, **Action** and **Result**. Keep it under 250 words, put most \
+         of the detail in Action, and end Result with what the candidate learned if the story says so.",
+        question.trim(),
+        role.map(|r| format!("Role: {}\n", r)).unwrap_or_default(),
+        story.title,
+        story.situation,
+        story.task,
+        story.action,
+        story.result,
+    );
+    let answer = state
+        .complete(provider, model, "You are an interview coach who helps candidates tell their own stories well.", &prompt)
+        .await?;
+    mark_used(&story.id);
+    info!("⭐ Built STAR answer from story '{}'", story.title);
+    Ok(Some(StarAnswer {
+        question: question.trim().to_string(),
+        story_id: story.id,
+        story_title: story.title,
+        answer: answer.trim().to_string(),
+        alternatives: matches,
+    }))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_stories() -> Response<Vec<Story>> {
+    envelope::respond("list_stories", async move {
+        Ok(BANK.lock().stories.clone())
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn add_story(input: StoryInput) -> Response<Story> {
+    envelope::respond("add_story", async move {
+        let title = non_empty(&input.title).ok_or("A story title is required")?;
+        let action = non_empty(&input.action).ok_or("Describe what you did (the Action) for this story")?;
+        let now = Utc::now();
+        let story = Story {
+            id: uuid::Uuid::new_v4().to_string(),
+            title,
+            situation: non_empty(&input.situation).unwrap_or_default(),
+            task: non_empty(&input.task).unwrap_or_default(),
+            action,
+            result: non_empty(&input.result).unwrap_or_default(),
+            tags: clean_tags(input.tags.as_deref().unwrap_or_default()),
+            times_used: 0,
+            created_at: now,
+            updated_at: now,
+        };
+        let mut bank = BANK.lock();
+        bank.stories.push(story.clone());
+        save_bank(&bank)?;
+        Ok(story)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn update_story(id: String, input: StoryInput) -> Response<Story> {
+    envelope::respond("update_story", async move {
+        let mut bank = BANK.lock();
+        let story = bank
+            .stories
+            .iter_mut()
+            .find(|s| s.id == id)
+            .ok_or_else(|| format!("Story {} not found", id))?;
+        for (field, value) in [
+            (&mut story.title, &input.title),
+            (&mut story.situation, &input.situation),
+            (&mut story.task, &input.task),
+            (&mut story.action, &input.action),
+            (&mut story.result, &input.result),
+        ] {
+            if let Some(value) = non_empty(value) {
+                *field = value;
+            }
+        }
+        if let Some(tags) = &input.tags {
+            story.tags = clean_tags(tags);
+        }
+        story.updated_at = Utc::now();
+        let story = story.clone();
+        save_bank(&bank)?;
+        Ok(story)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_story(id: String) -> Response<()> {
+    envelope::respond("delete_story", async move {
+        let mut bank = BANK.lock();
+        let before = bank.stories.len();
+        bank.stories.retain(|s| s.id != id);
+        if bank.stories.len() == before {
+            return Err(format!("Story {} not found", id).into());
+        }
+        Ok(save_bank(&bank)?)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn find_matching_stories(question: String, limit: Option<usize>) -> Response<Vec<StoryMatch>> {
+    envelope::respond("find_matching_stories", async move {
+        Ok(find_matches(&question, limit.unwrap_or(DEFAULT_MATCH_LIMIT)))
+    })
+    .await
+}
+
+/// Build a STAR answer for a behavioral question from a chosen story (or the best match)
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn generate_star_answer(
+    state: State<'_, AppState>,
+    question: String,
+    story_id: Option<String>,
+    provider: String,
+    model: String,
+    request_id: Option<String>,
+) -> Response<StarAnswer> {
+    cancellation::run("generate_star_answer", request_id, async move {
+        Ok(build_star_answer(&state, &provider, &model, &question, story_id.as_deref())
+            .await?
+            .ok_or_else(|| "No story in the experience bank fits this question; add one or pick a story".to_string())?)
+    })
+    .await
+}
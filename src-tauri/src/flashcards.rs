@@ -0,0 +1,334 @@
+// Flashcards generated from completed sessions, reviewed on an SM-2 schedule
+// Each card has the question on the front and, on the back, the answer given in the session next to
+// a model answer written by the configured AI provider. Cards come from backend interview sessions
+// or local practice reports and are stored in %APPDATA%\MockMate\flashcards.json.
+
+use std::path::PathBuf;
+
+use chrono::{DateTime, Duration, Utc};
+use log::info;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::State;
+
+use crate::AppState;
+use crate::cancellation;
+use crate::envelope::{self, IntoResult, Response};
+
+/// SM-2 starting ease factor and its floor
+const INITIAL_EASE: f32 = 2.5;
+const MIN_EASE: f32 = 1.3;
+
+static CARDS: Lazy<Mutex<Vec<Flashcard>>> = Lazy::new(|| Mutex::new(load_cards()));
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(tag = "kind", content = "id", rename_all = "snake_case")]
+pub enum FlashcardSource {
+    /// Backend interview session id
+    Session(String),
+    /// Local practice session id
+    Practice(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ReviewSchedule {
+    pub ease_factor: f32,
+    pub interval_days: u32,
+    pub repetitions: u32,
+    pub due_at: DateTime<Utc>,
+    pub last_grade: Option<u8>,
+    pub last_reviewed_at: Option<DateTime<Utc>>,
+}
+
+impl ReviewSchedule {
+    fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            ease_factor: INITIAL_EASE,
+            interval_days: 0,
+            repetitions: 0,
+            due_at: now,
+            last_grade: None,
+            last_reviewed_at: None,
+        }
+    }
+
+    /// SM-2: grades below 3 restart the card, otherwise the interval grows by the ease factor,
+    /// which itself drifts with how easy the recall was
+    fn grade(&mut self, grade: u8, now: DateTime<Utc>) {
+        if grade < 3 {
+            self.repetitions = 0;
+            self.interval_days = 1;
+        } else {
+            self.repetitions += 1;
+            self.interval_days = match self.repetitions {
+                1 => 1,
+                2 => 6,
+                _ => (self.interval_days as f32 * self.ease_factor).round() as u32,
+            };
+        }
+        let miss = f32::from(5 - grade);
+        self.ease_factor = (self.ease_factor + 0.1 - miss * (0.08 + miss * 0.02)).max(MIN_EASE);
+        self.last_grade = Some(grade);
+        self.last_reviewed_at = Some(now);
+        self.due_at = now + Duration::days(i64::from(self.interval_days));
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Flashcard {
+    pub id: String,
+    pub source: FlashcardSource,
+    pub question: String,
+    pub my_answer: Option<String>,
+    pub model_answer: String,
+    pub created_at: DateTime<Utc>,
+    pub schedule: ReviewSchedule,
+}
+
+fn cards_path() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("flashcards.json")
+}
+
+fn load_cards() -> Vec<Flashcard> {
+    std::fs::read(cards_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn save_cards(cards: &[Flashcard]) -> Result<(), String> {
+    let path = cards_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(cards).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| e.to_string())
+}
+
+/// (question, answer given) pairs from a finished session
+async fn session_items(source: &FlashcardSource) -> Result<Vec<(String, Option<String>)>, String> {
+    match source {
+        FlashcardSource::Session(session_id) => {
+            let questions = crate::database::postgres::get_session_questions(session_id.clone(), None, None).await.into_result().map_err(|e| e.to_string())?.items;
+            let answers = crate::database::postgres::get_session_answers(session_id.clone(), None).await.into_result().map_err(|e| e.to_string())?.items;
+            Ok(questions
+                .into_iter()
+                .map(|q| {
+                    let answer = answers
+                        .iter()
+                        .filter(|a| a.question_id == q.id)
+                        .max_by_key(|a| a.answered_at)
+                        .and_then(|a| a.answer_text.clone());
+                    (q.question_text, answer)
+                })
+                .collect())
+        }
+        FlashcardSource::Practice(session_id) => Ok(crate::practice::load_report(session_id)?
+            .turns
+            .into_iter()
+            .map(|turn| (turn.question, turn.answer))
+            .collect()),
+    }
+}
+
+async fn model_answers(state: &AppState, provider: &str, model: &str, questions: &[&str]) -> Result<Vec<String>, String> {
+    let numbered = questions
+        .iter()
+        .enumerate()
+        .map(|(i, q)| format!("{}. {}", i + 1, q))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let user_prompt = format!(
+        "Write a strong model answer for each interview question below, suitable for memorizing as a \
+         flashcard: concise, structured and specific (under 120 words each).\n\n{}\n\nReply with a JSON \
+         array of {} strings, one answer per question in the same order.",
+        numbered,
+        questions.len()
+    );
+    let reply = state
+        .complete(
+            provider,
+            model,
+            "You are an expert interview coach preparing study material.",
+            &user_prompt,
+        )
+        .await?;
+    let answers: Vec<String> = crate::practice::extract_json(&reply, '[', ']')?;
+    if answers.len() != questions.len() {
+        return Err(format!(
+            "Expected {} model answers but the AI returned {}",
+            questions.len(),
+            answers.len()
+        ));
+    }
+    Ok(answers)
+}
+
+/// Create cards for the questions of a completed session that do not have one yet
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn generate_flashcards(
+    state: State<'_, AppState>,
+    source: FlashcardSource,
+    provider: String,
+    model: String,
+    request_id: Option<String>,
+) -> Response<Vec<Flashcard>> {
+    cancellation::run("generate_flashcards", request_id, async move {
+        let items: Vec<(String, Option<String>)> = {
+            let items = session_items(&source).await?;
+            let cards = CARDS.lock();
+            items
+                .into_iter()
+                .filter(|(question, _)| !question.trim().is_empty())
+                .filter(|(question, _)| !cards.iter().any(|c| c.source == source && c.question == *question))
+                .collect()
+        };
+        if items.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let questions: Vec<&str> = items.iter().map(|(q, _)| q.as_str()).collect();
+        let answers = model_answers(&state, &provider, &model, &questions).await?;
+
+        let now = Utc::now();
+        let created: Vec<Flashcard> = items
+            .into_iter()
+            .zip(answers)
+            .map(|((question, my_answer), model_answer)| Flashcard {
+                id: uuid::Uuid::new_v4().to_string(),
+                source: source.clone(),
+                question,
+                my_answer: my_answer.filter(|a| !a.trim().is_empty()),
+                model_answer,
+                created_at: now,
+                schedule: ReviewSchedule::new(now),
+            })
+            .collect();
+
+        let mut cards = CARDS.lock();
+        cards.extend(created.iter().cloned());
+        save_cards(&cards)?;
+        info!("🃏 Created {} flashcards from {:?}", created.len(), source);
+        Ok(created)
+    })
+    .await
+}
+
+/// Cards due now, most overdue first
+#[tauri::command]
+#[specta::specta]
+pub async fn get_due_flashcards(limit: Option<usize>) -> Response<Vec<Flashcard>> {
+    envelope::respond("get_due_flashcards", async move {
+        let now = Utc::now();
+        let mut due: Vec<Flashcard> = CARDS.lock().iter().filter(|c| c.schedule.due_at <= now).cloned().collect();
+        due.sort_by_key(|c| c.schedule.due_at);
+        if let Some(limit) = limit {
+            due.truncate(limit);
+        }
+        Ok(due)
+    })
+    .await
+}
+
+/// Grade a review from 0 (blackout) to 5 (perfect recall) and reschedule the card
+#[tauri::command]
+#[specta::specta]
+pub async fn grade_flashcard(id: String, grade: u8) -> Response<Flashcard> {
+    envelope::respond("grade_flashcard", async move {
+        if grade > 5 {
+            return Err("Grade must be between 0 and 5".into());
+        }
+        let mut cards = CARDS.lock();
+        let card = cards
+            .iter_mut()
+            .find(|c| c.id == id)
+            .ok_or_else(|| format!("Flashcard {} not found", id))?;
+        card.schedule.grade(grade, Utc::now());
+        let card = card.clone();
+        save_cards(&cards)?;
+        Ok(card)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_flashcard(id: String) -> Response<()> {
+    envelope::respond("delete_flashcard", async move {
+        let mut cards = CARDS.lock();
+        let before = cards.len();
+        cards.retain(|c| c.id != id);
+        if cards.len() == before {
+            return Err(format!("Flashcard {} not found", id).into());
+        }
+        Ok(save_cards(&cards)?)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 1, 9, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn intervals_grow_by_the_ease_factor() {
+        let mut schedule = ReviewSchedule::new(now());
+        let intervals: Vec<u32> = (0..4)
+            .map(|_| {
+                schedule.grade(4, now());
+                schedule.interval_days
+            })
+            .collect();
+        // A grade of 4 leaves the ease at 2.5
+        assert_eq!(intervals, [1, 6, 15, 38]);
+        assert!((schedule.ease_factor - 2.5).abs() < 1e-5);
+        assert_eq!(schedule.due_at, now() + Duration::days(38));
+    }
+
+    #[test]
+    fn ease_follows_the_grade() {
+        let mut schedule = ReviewSchedule::new(now());
+        schedule.grade(5, now());
+        assert!((schedule.ease_factor - 2.6).abs() < 1e-5);
+        schedule.grade(3, now());
+        assert!((schedule.ease_factor - 2.46).abs() < 1e-5);
+    }
+
+    #[test]
+    fn ease_never_drops_below_the_floor() {
+        let mut schedule = ReviewSchedule::new(now());
+        schedule.grade(0, now());
+        assert!((schedule.ease_factor - 1.7).abs() < 1e-5);
+        for _ in 0..3 {
+            schedule.grade(0, now());
+            assert_eq!(schedule.ease_factor, MIN_EASE);
+        }
+    }
+
+    #[test]
+    fn low_grade_restarts_the_card() {
+        let mut schedule = ReviewSchedule::new(now());
+        for _ in 0..3 {
+            schedule.grade(5, now());
+        }
+        assert_eq!(schedule.repetitions, 3);
+        schedule.grade(2, now());
+        assert_eq!(schedule.repetitions, 0);
+        assert_eq!(schedule.interval_days, 1);
+        assert_eq!(schedule.due_at, now() + Duration::days(1));
+        assert_eq!(schedule.last_grade, Some(2));
+        // The next pass starts over at one day
+        schedule.grade(4, now());
+        assert_eq!(schedule.interval_days, 1);
+    }
+}
@@ -0,0 +1,264 @@
+// Company and domain glossary
+// Niche product names and internal acronyms ("Kestrel", "PLT", "SRE on-call") are what transcription
+// and the AI get wrong most often. The user keeps a glossary of terms, each with an optional
+// definition, the phrasing they prefer in answers, and the ways it tends to be misheard. The active
+// profile's glossary is boosted in Deepgram recognition (misheard forms are replaced with the term)
+// and added to answer system prompts. Glossaries are kept per profile, one JSON file each in
+// %APPDATA%\MockMate\glossary.
+
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use log::info;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::envelope::{self, Response};
+use crate::practice_profile::DEFAULT_PROFILE;
+
+static ACTIVE: Lazy<Mutex<Glossary>> = Lazy::new(|| Mutex::new(load(&load_active_profile())));
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GlossaryEntry {
+    pub id: String,
+    pub term: String,
+    /// What the term means, for the AI's context
+    #[serde(default)]
+    pub definition: Option<String>,
+    /// How answers should say it, e.g. "Kestrel (our payments platform)"
+    #[serde(default)]
+    pub preferred_phrasing: Option<String>,
+    /// Ways transcription tends to mishear the term; replaced with the term
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Glossary {
+    pub profile: String,
+    pub entries: Vec<GlossaryEntry>,
+}
+
+/// New or edited glossary entry; on update, only provided fields change
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct GlossaryEntryInput {
+    pub term: Option<String>,
+    pub definition: Option<String>,
+    pub preferred_phrasing: Option<String>,
+    pub aliases: Option<Vec<String>>,
+}
+
+fn glossary_dir() -> PathBuf {
+    crate::portable::data_root().join("MockMate").join("glossary")
+}
+
+fn glossary_path(profile: &str) -> PathBuf {
+    let file: String = profile
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    glossary_dir().join(format!("{}.json", file))
+}
+
+fn load_active_profile() -> String {
+    std::fs::read_to_string(glossary_dir().join("active_profile"))
+        .ok()
+        .map(|profile| profile.trim().to_string())
+        .filter(|profile| !profile.is_empty())
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+}
+
+fn load(profile: &str) -> Glossary {
+    std::fs::read(glossary_path(profile))
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_else(|| Glossary { profile: profile.to_string(), entries: Vec::new() })
+}
+
+fn save(glossary: &Glossary) -> Result<(), String> {
+    let path = glossary_path(&glossary.profile);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(glossary).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| e.to_string())
+}
+
+/// Run `f` on a profile's glossary (the active one when `None`) and save it; the cached active
+/// glossary stays in sync
+fn with_glossary<T>(profile: Option<String>, f: impl FnOnce(&mut Glossary) -> Result<T, String>) -> Result<T, String> {
+    let mut active = ACTIVE.lock();
+    match profile.filter(|profile| *profile != active.profile) {
+        Some(profile) => {
+            let mut glossary = load(&profile);
+            let result = f(&mut glossary)?;
+            save(&glossary)?;
+            Ok(result)
+        }
+        None => {
+            let result = f(&mut active)?;
+            save(&active)?;
+            Ok(result)
+        }
+    }
+}
+
+fn non_empty(value: &Option<String>) -> Option<String> {
+    value.as_ref().map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+fn clean_aliases(aliases: &[String], term: &str) -> Vec<String> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for alias in aliases.iter().map(|a| a.trim()).filter(|a| !a.is_empty() && !a.eq_ignore_ascii_case(term)) {
+        if !cleaned.iter().any(|a| a.eq_ignore_ascii_case(alias)) {
+            cleaned.push(alias.to_string());
+        }
+    }
+    cleaned
+}
+
+/// Terms of the active glossary, for recognition boosting
+pub fn terms() -> Vec<String> {
+    ACTIVE.lock().entries.iter().map(|entry| entry.term.clone()).collect()
+}
+
+/// (misheard form, term) pairs of the active glossary, for transcript replacement
+pub fn replacements() -> Vec<(String, String)> {
+    ACTIVE
+        .lock()
+        .entries
+        .iter()
+        .flat_map(|entry| entry.aliases.iter().map(|alias| (alias.clone(), entry.term.clone())))
+        .collect()
+}
+
+/// Glossary lines for answer system prompts, cut at `max_chars`; `None` when the glossary is empty
+pub fn prompt_section(max_chars: usize) -> Option<String> {
+    let active = ACTIVE.lock();
+    let mut section = String::new();
+    for entry in &active.entries {
+        let mut line = format!("- {}", entry.term);
+        if let Some(definition) = &entry.definition {
+            line.push_str(&format!(": {}", definition));
+        }
+        if let Some(phrasing) = &entry.preferred_phrasing {
+            line.push_str(&format!(" (say \"{}\")", phrasing));
+        }
+        if section.len() + line.len() + 1 > max_chars {
+            break;
+        }
+        section.push('\n');
+        section.push_str(&line);
+    }
+    (!section.is_empty()).then_some(section)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_glossary(profile: Option<String>) -> Response<Glossary> {
+    envelope::respond("list_glossary", async move {
+        let active = ACTIVE.lock();
+        Ok(match profile.filter(|profile| *profile != active.profile) {
+            Some(profile) => load(&profile),
+            None => active.clone(),
+        })
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn add_glossary_term(profile: Option<String>, input: GlossaryEntryInput) -> Response<GlossaryEntry> {
+    envelope::respond("add_glossary_term", async move {
+        let term = non_empty(&input.term).ok_or("A glossary term is required")?;
+        Ok(with_glossary(profile, |glossary| {
+            if glossary.entries.iter().any(|entry| entry.term.eq_ignore_ascii_case(&term)) {
+                return Err(format!("\"{}\" is already in the glossary", term));
+            }
+            let now = Utc::now();
+            let entry = GlossaryEntry {
+                id: uuid::Uuid::new_v4().to_string(),
+                aliases: clean_aliases(input.aliases.as_deref().unwrap_or_default(), &term),
+                term,
+                definition: non_empty(&input.definition),
+                preferred_phrasing: non_empty(&input.preferred_phrasing),
+                created_at: now,
+                updated_at: now,
+            };
+            glossary.entries.push(entry.clone());
+            info!("📖 Added glossary term '{}' to profile {}", entry.term, glossary.profile);
+            Ok(entry)
+        })?)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn update_glossary_term(profile: Option<String>, id: String, input: GlossaryEntryInput) -> Response<GlossaryEntry> {
+    envelope::respond("update_glossary_term", async move {
+        Ok(with_glossary(profile, |glossary| {
+            let entry = glossary
+                .entries
+                .iter_mut()
+                .find(|entry| entry.id == id)
+                .ok_or_else(|| format!("Glossary term {} not found", id))?;
+            if let Some(term) = non_empty(&input.term) {
+                entry.term = term;
+            }
+            // An empty string clears the optional fields
+            if let Some(definition) = &input.definition {
+                entry.definition = non_empty(&Some(definition.clone()));
+            }
+            if let Some(phrasing) = &input.preferred_phrasing {
+                entry.preferred_phrasing = non_empty(&Some(phrasing.clone()));
+            }
+            if let Some(aliases) = &input.aliases {
+                entry.aliases = clean_aliases(aliases, &entry.term);
+            }
+            entry.updated_at = Utc::now();
+            Ok(entry.clone())
+        })?)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_glossary_term(profile: Option<String>, id: String) -> Response<()> {
+    envelope::respond("delete_glossary_term", async move {
+        Ok(with_glossary(profile, |glossary| {
+            let before = glossary.entries.len();
+            glossary.entries.retain(|entry| entry.id != id);
+            if glossary.entries.len() == before {
+                return Err(format!("Glossary term {} not found", id));
+            }
+            Ok(())
+        })?)
+    })
+    .await
+}
+
+/// Use `profile`'s glossary for transcription and prompts; recognition picks it up on the next connect
+#[tauri::command]
+#[specta::specta]
+pub async fn set_active_glossary_profile(profile: String) -> Response<Glossary> {
+    envelope::respond("set_active_glossary_profile", async move {
+        let profile = profile.trim();
+        if profile.is_empty() {
+            return Err("A profile name is required".into());
+        }
+        let dir = glossary_dir();
+        std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        std::fs::write(dir.join("active_profile"), profile).map_err(|e| e.to_string())?;
+        let glossary = load(profile);
+        info!("📖 Active glossary profile {} ({} terms)", glossary.profile, glossary.entries.len());
+        *ACTIVE.lock() = glossary.clone();
+        Ok(glossary)
+    })
+    .await
+}
@@ -0,0 +1,83 @@
+// Preconditions checked before a command body runs
+// Commands that only make sense in an activated session, that need an OS permission or that call a
+// cloud service declare it with `Require` and run through `respond` (or call `check` first thing
+// in a `cancellation::run` body). A failed check rejects with the same typed error everywhere:
+// `NoActiveSession`, `PermissionDenied` carrying the OS remediation, or `PrivacyBlocked`.
+
+use std::future::Future;
+
+use log::warn;
+
+use crate::envelope::{self, Response};
+use crate::error::{AppError, ErrorCode};
+use crate::permissions::{self, Permission};
+
+#[derive(Debug, Clone)]
+pub enum Require {
+    /// A session has been activated and not disconnected
+    ActiveSession,
+    /// The given session ID is the activated session
+    Session(String),
+    /// The OS grants the permission, or will ask on first use
+    Permission(Permission),
+    /// Local-only mode is off; names the service in the error
+    Cloud(&'static str),
+}
+
+impl Require {
+    fn check(&self) -> Result<(), AppError> {
+        match self {
+            Require::ActiveSession => match crate::audit::current_session() {
+                Some(_) => Ok(()),
+                None => Err(AppError::new(ErrorCode::NoActiveSession, "No session is active")),
+            },
+            Require::Session(session_id) => match crate::audit::current_session() {
+                Some(active) if active == *session_id => Ok(()),
+                Some(active) => Err(AppError::new(
+                    ErrorCode::NoActiveSession,
+                    format!("Session {} is not the active session ({})", session_id, active),
+                )),
+                None => Err(AppError::new(
+                    ErrorCode::NoActiveSession,
+                    format!("Session {} is not active", session_id),
+                )),
+            },
+            Require::Permission(permission) => {
+                let status = permissions::permission_status(*permission);
+                if status.usable {
+                    return Ok(());
+                }
+                let mut message = format!("{:?} access is {:?}", permission, status.state);
+                if let Some(remediation) = status.remediation {
+                    message = format!("{}: {}", message, remediation.instructions);
+                }
+                Err(AppError::new(ErrorCode::PermissionDenied, message))
+            }
+            Require::Cloud(service) => crate::privacy::ensure_cloud_allowed(service)
+                .map_err(|message| AppError::new(ErrorCode::PrivacyBlocked, message)),
+        }
+    }
+}
+
+/// Fail with the first requirement that does not hold
+pub fn check(requirements: &[Require]) -> Result<(), AppError> {
+    for requirement in requirements {
+        if let Err(error) = requirement.check() {
+            warn!("🚧 {:?} not met: {}", requirement, error.message);
+            return Err(error);
+        }
+    }
+    Ok(())
+}
+
+/// `envelope::respond` that runs the body only when every requirement holds
+pub async fn respond<T, F>(command: &'static str, requirements: &[Require], task: F) -> Response<T>
+where
+    F: Future<Output = Result<T, AppError>>,
+{
+    envelope::respond(command, async move {
+        check(requirements)?;
+        task.await
+    })
+    .await
+}
@@ -0,0 +1,363 @@
+// Headless command-line mode
+// `mockmate --headless transcribe --device system|microphone [--out file.jsonl] [--duration secs]
+// [--answer] [--model name]` runs capture + Deepgram transcription, and optionally AI answers,
+// without building the Tauri app, so no webview windows are created. Results are written as JSON
+// lines to the output file or stdout; logs still go to the rotating log file.
+// `mockmate --headless bindings [--out file]` writes the frontend's TypeScript bindings.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures_util::{SinkExt, Stream, StreamExt};
+use log::{error, info, warn};
+use parking_lot::Mutex;
+use serde::Serialize;
+use tokio_tungstenite::tungstenite::protocol::Message;
+
+use crate::openai::InterviewContext;
+use crate::pollinations::{PollinationsClient, PollinationsModel};
+
+const USAGE: &str = "\
+Usage: mockmate --headless transcribe [options]
+       mockmate --headless bindings [--out <file.ts>]
+
+Options:
+  --device <system|microphone>  Audio to capture (default: system)
+  --out <file.jsonl>            Write results to a file instead of stdout
+  --duration <seconds>          Stop after this long (default: until Ctrl+C)
+  --answer                      Generate an AI answer for each detected question
+  --model <name>                Pollinations model used with --answer (default: openai)
+
+bindings writes the TypeScript bindings for every command and event (default: ../src/bindings.ts)
+";
+
+/// Samples per Deepgram packet (100ms at 44.1kHz)
+const PACKET_SAMPLES: usize = 4410;
+/// How long to wait for Deepgram's last results after the audio stops
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(3);
+
+const QUESTION_STARTERS: &[&str] = &[
+    "what", "why", "how", "when", "where", "who", "which", "can", "could", "would", "should", "do",
+    "does", "did", "is", "are", "have", "has", "tell me", "describe", "explain", "walk me through",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CaptureDevice {
+    System,
+    Microphone,
+}
+
+#[derive(Debug)]
+struct TranscribeOptions {
+    device: CaptureDevice,
+    out: Option<PathBuf>,
+    duration: Option<Duration>,
+    answer: bool,
+    model: String,
+}
+
+/// One line of output
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum OutputLine<'a> {
+    Transcript {
+        text: &'a str,
+        is_final: bool,
+        confidence: f32,
+        timestamp: &'a str,
+    },
+    Answer {
+        question: &'a str,
+        answer: &'a str,
+        model: &'a str,
+        timestamp: String,
+    },
+    Error {
+        message: &'a str,
+    },
+}
+
+type Output = Arc<Mutex<Box<dyn Write + Send>>>;
+type SampleStream = Pin<Box<dyn Stream<Item = f32> + Send>>;
+
+fn write_line(output: &Output, line: &OutputLine) {
+    let mut output = output.lock();
+    let written = serde_json::to_writer(&mut *output, line)
+        .map_err(std::io::Error::from)
+        .and_then(|_| output.write_all(b"\n"))
+        .and_then(|_| output.flush());
+    if let Err(e) = written {
+        error!("Failed to write headless output: {}", e);
+    }
+}
+
+/// Attach to the console of the launching shell; release builds use the Windows GUI subsystem.
+/// Elsewhere the process already writes to the terminal it was started from.
+pub fn attach_console() {
+    #[cfg(windows)]
+    unsafe {
+        use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+/// Whether the process was started in headless mode
+pub fn requested(args: &[String]) -> bool {
+    args.iter().any(|arg| arg == "--headless")
+}
+
+/// Run the headless command in `args` and return the process exit code
+pub fn run(args: &[String]) -> i32 {
+    let rest: Vec<&str> = args
+        .iter()
+        .skip(1)
+        .map(String::as_str)
+        .filter(|arg| *arg != "--headless")
+        .collect();
+
+    let options = match rest.split_first() {
+        Some((&"transcribe", options)) => match parse_transcribe(options) {
+            Ok(options) => options,
+            Err(e) => {
+                eprintln!("{}\n\n{}", e, USAGE);
+                return 2;
+            }
+        },
+        Some((&"bindings", options)) => return export_bindings(options),
+        Some((&"--help", _)) | Some((&"help", _)) => {
+            println!("{}", USAGE);
+            return 0;
+        }
+        Some((command, _)) => {
+            eprintln!("Unknown headless command '{}'\n\n{}", command, USAGE);
+            return 2;
+        }
+        None => {
+            eprintln!("{}", USAGE);
+            return 2;
+        }
+    };
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("Failed to start async runtime: {}", e);
+            return 1;
+        }
+    };
+    match runtime.block_on(transcribe(options)) {
+        Ok(()) => 0,
+        Err(e) => {
+            error!("Headless transcription failed: {}", e);
+            eprintln!("Error: {}", e);
+            1
+        }
+    }
+}
+
+fn export_bindings(args: &[&str]) -> i32 {
+    let path = match args {
+        [] => PathBuf::from(crate::bindings::DEFAULT_PATH),
+        ["--out", path] => PathBuf::from(path),
+        _ => {
+            eprintln!("{}", USAGE);
+            return 2;
+        }
+    };
+    match crate::export_bindings(&path) {
+        Ok(()) => {
+            println!("Wrote {}", path.display());
+            0
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            1
+        }
+    }
+}
+
+fn parse_transcribe(args: &[&str]) -> Result<TranscribeOptions, String> {
+    let mut options = TranscribeOptions {
+        device: CaptureDevice::System,
+        out: None,
+        duration: None,
+        answer: false,
+        model: "openai".to_string(),
+    };
+
+    let mut args = args.iter();
+    while let Some(&arg) = args.next() {
+        let mut value = || args.next().copied().ok_or_else(|| format!("Missing value for {}", arg));
+        match arg {
+            "--device" => {
+                options.device = match value()?.to_lowercase().as_str() {
+                    "system" | "speaker" | "loopback" => CaptureDevice::System,
+                    "microphone" | "mic" => CaptureDevice::Microphone,
+                    other => return Err(format!("Unknown device '{}' (expected system or microphone)", other)),
+                }
+            }
+            "--out" => options.out = Some(PathBuf::from(value()?)),
+            "--duration" => {
+                let seconds: f64 = value()?.parse().map_err(|_| "--duration expects a number of seconds".to_string())?;
+                if seconds <= 0.0 {
+                    return Err("--duration must be positive".to_string());
+                }
+                options.duration = Some(Duration::from_secs_f64(seconds));
+            }
+            "--answer" => options.answer = true,
+            "--model" => options.model = value()?.to_string(),
+            other => return Err(format!("Unknown option '{}'", other)),
+        }
+    }
+    Ok(options)
+}
+
+/// Cheap heuristic for transcribed speech: ends with "?" or opens like a question
+pub(crate) fn looks_like_question(text: &str) -> bool {
+    let lower = text.trim().to_lowercase();
+    lower.ends_with('?')
+        || QUESTION_STARTERS
+            .iter()
+            .any(|starter| lower.starts_with(starter) && lower[starter.len()..].starts_with(' '))
+}
+
+fn open_capture(device: CaptureDevice) -> Result<SampleStream, String> {
+    match device {
+        CaptureDevice::System => {
+            let input = crate::pluely_audio::PluelySpeakerInput::new().map_err(|e| e.to_string())?;
+            Ok(Box::pin(input.stream()))
+        }
+        CaptureDevice::Microphone => {
+            let input = crate::pluely_microphone::PluelyMicrophoneInput::new().map_err(|e| e.to_string())?;
+            Ok(Box::pin(input.stream().map_err(|e| e.to_string())?))
+        }
+    }
+}
+
+fn answer_client() -> Result<PollinationsClient, String> {
+    let api_key = crate::get_env_var("POLLINATIONS_API_KEY")
+        .ok_or_else(|| "POLLINATIONS_API_KEY environment variable not set".to_string())?;
+    let referer = crate::get_env_var("POLLINATIONS_REFERER").unwrap_or_else(|| "mockmate".to_string());
+    Ok(PollinationsClient::new(api_key, referer))
+}
+
+async fn transcribe(options: TranscribeOptions) -> Result<(), String> {
+    crate::privacy::ensure_cloud_allowed("Deepgram")?;
+
+    let output: Output = Arc::new(Mutex::new(match &options.out {
+        Some(path) => Box::new(std::io::BufWriter::new(
+            std::fs::File::create(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?,
+        )),
+        None => Box::new(std::io::stdout()),
+    }));
+    let answers = if options.answer {
+        crate::privacy::ensure_cloud_allowed("Pollinations")?;
+        Some(Arc::new(answer_client()?))
+    } else {
+        None
+    };
+
+    let model = crate::deepgram_streaming::get_deepgram_model();
+    let socket = crate::deepgram_streaming::connect(&model).await.map_err(|e| e.to_string())?;
+    let (mut write, mut read) = socket.split();
+
+    let samples = open_capture(options.device)?;
+    info!("🖥️ Headless transcription started ({:?}, Deepgram {})", options.device, model);
+
+    let (stop_tx, mut stop_rx) = tokio::sync::watch::channel(false);
+    let sender = tokio::spawn(async move {
+        let mut packets = samples.ready_chunks(PACKET_SAMPLES);
+        loop {
+            tokio::select! {
+                packet = packets.next() => {
+                    let Some(packet) = packet else { break };
+                    if let Err(e) = write.send(Message::Binary(crate::audio_buffer::pcm16_bytes(&packet))).await {
+                        warn!("Failed to send audio to Deepgram: {}", e);
+                        break;
+                    }
+                }
+                _ = stop_rx.changed() => break,
+            }
+        }
+        // Ask Deepgram to flush its last results and close the stream
+        let _ = write.send(Message::Text(r#"{"type":"CloseStream"}"#.to_string())).await;
+    });
+
+    let reader_output = output.clone();
+    let answer_model = options.model.clone();
+    let reader = tokio::spawn(async move {
+        let mut pending_answers = Vec::new();
+        while let Some(message) = read.next().await {
+            let text = match message {
+                Ok(Message::Text(text)) => text,
+                Ok(Message::Close(_)) => break,
+                Ok(_) => continue,
+                Err(e) => {
+                    let message = e.to_string();
+                    write_line(&reader_output, &OutputLine::Error { message: &message });
+                    break;
+                }
+            };
+            let Some(result) = crate::deepgram_streaming::parse_transcript(&text) else {
+                continue;
+            };
+            write_line(&reader_output, &OutputLine::Transcript {
+                text: &result.text,
+                is_final: result.is_final,
+                confidence: result.confidence,
+                timestamp: &result.timestamp,
+            });
+
+            if let Some(client) = answers.as_ref().filter(|_| result.is_final && looks_like_question(&result.text)) {
+                let client = client.clone();
+                let output = reader_output.clone();
+                let model = answer_model.clone();
+                pending_answers.push(tokio::spawn(async move {
+                    let question = result.text;
+                    match client
+                        .generate_answer(&question, &InterviewContext::new(), PollinationsModel::Custom(model.clone()))
+                        .await
+                    {
+                        Ok(answer) => write_line(&output, &OutputLine::Answer {
+                            question: &question,
+                            answer: &answer,
+                            model: &model,
+                            timestamp: chrono::Utc::now().to_rfc3339(),
+                        }),
+                        Err(e) => {
+                            let message = format!("Answer failed for '{}': {}", question, e);
+                            write_line(&output, &OutputLine::Error { message: &message });
+                        }
+                    }
+                }));
+            }
+        }
+        // Answers still in flight are part of the output
+        for answer in pending_answers {
+            let _ = answer.await;
+        }
+    });
+
+    let duration = options.duration;
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => info!("🛑 Headless transcription interrupted"),
+        _ = async {
+            match duration {
+                Some(duration) => tokio::time::sleep(duration).await,
+                None => std::future::pending::<()>().await,
+            }
+        } => info!("🛑 Headless transcription reached its duration"),
+    }
+
+    let _ = stop_tx.send(true);
+    let _ = sender.await;
+    if tokio::time::timeout(DRAIN_TIMEOUT, reader).await.is_err() {
+        warn!("Deepgram did not close the stream in time; some final results may be missing");
+    }
+    output.lock().flush().map_err(|e| e.to_string())?;
+    info!("✅ Headless transcription finished");
+    Ok(())
+}
@@ -0,0 +1,225 @@
+// Aggregated health check for MockMate
+// One call that reports per-subsystem readiness for the pre-interview checklist
+
+use std::time::{Duration, Instant};
+
+use log::info;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{accessibility_reader, deepgram_streaming, pluely_audio, pluely_microphone};
+use crate::pollinations::PollinationsClient;
+use crate::net_trace::TracedSend;
+use crate::envelope::{self, IntoResult, Response};
+
+/// Upper bound for any single network/database probe
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthState {
+    /// Component is running and responding
+    Ok,
+    /// Component works but something is off (slow, missing optional config)
+    Degraded,
+    /// Component is expected to work but failed its check
+    Down,
+    /// Component is not running right now (e.g. capture not started yet)
+    Inactive,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ComponentHealth {
+    pub component: String,
+    pub status: HealthState,
+    pub detail: String,
+    pub latency_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct HealthReport {
+    /// Worst status among components that are expected to be running
+    pub overall: HealthState,
+    pub components: Vec<ComponentHealth>,
+    pub checked_at: String,
+}
+
+fn component(name: &str, status: HealthState, detail: impl Into<String>, latency: Option<Duration>) -> ComponentHealth {
+    ComponentHealth {
+        component: name.to_string(),
+        status,
+        detail: detail.into(),
+        latency_ms: latency.map(|d| d.as_millis() as u64),
+    }
+}
+
+/// Cloud components are switched off, not broken, while local-only mode is on
+fn blocked_by_local_only(name: &str) -> Option<ComponentHealth> {
+    crate::privacy::is_local_only()
+        .then(|| component(name, HealthState::Inactive, "Disabled by local-only mode", None))
+}
+
+async fn check_system_audio() -> ComponentHealth {
+    match pluely_audio::is_pluely_audio_active().await.into_result() {
+        Ok(true) => component("system_audio_capture", HealthState::Ok, "Capturing system audio", None),
+        Ok(false) => component("system_audio_capture", HealthState::Inactive, "System audio capture not started", None),
+        Err(e) => component("system_audio_capture", HealthState::Down, e.to_string(), None),
+    }
+}
+
+async fn check_microphone() -> ComponentHealth {
+    match pluely_microphone::is_pluely_microphone_active().await.into_result() {
+        Ok(true) => component("microphone_capture", HealthState::Ok, "Capturing microphone audio", None),
+        Ok(false) => component("microphone_capture", HealthState::Inactive, "Microphone capture not started", None),
+        Err(e) => component("microphone_capture", HealthState::Down, e.to_string(), None),
+    }
+}
+
+async fn check_deepgram() -> ComponentHealth {
+    if let Some(blocked) = blocked_by_local_only("deepgram") {
+        return blocked;
+    }
+    if crate::get_env_var("DEEPGRAM_API_KEY").is_none() {
+        return component("deepgram", HealthState::Down, "DEEPGRAM_API_KEY not configured", None);
+    }
+    match deepgram_streaming::is_deepgram_streaming_active().await.into_result() {
+        Ok(true) => component("deepgram", HealthState::Ok, "Streaming socket open", None),
+        Ok(false) => component("deepgram", HealthState::Inactive, "Streaming socket not connected", None),
+        Err(e) => component("deepgram", HealthState::Down, e.to_string(), None),
+    }
+}
+
+async fn check_database() -> ComponentHealth {
+    if let Some(blocked) = blocked_by_local_only("database") {
+        return blocked;
+    }
+    let started = Instant::now();
+    let probe = async {
+        let client = crate::database::shared::DATABASE_POOL
+            .get()
+            .await
+            .map_err(|e| format!("Pool connection failed: {}", e))?;
+        client
+            .query_one("SELECT 1", &[])
+            .await
+            .map_err(|e| format!("Query failed: {}", e))?;
+        Ok::<_, String>(())
+    };
+
+    match tokio::time::timeout(PROBE_TIMEOUT, probe).await {
+        Ok(Ok(())) => {
+            let status = crate::database::shared::DATABASE_POOL.status();
+            component(
+                "database",
+                HealthState::Ok,
+                format!("Pool healthy ({} connections, {} available)", status.size, status.available),
+                Some(started.elapsed()),
+            )
+        }
+        Ok(Err(e)) => component("database", HealthState::Down, e, Some(started.elapsed())),
+        Err(_) => component("database", HealthState::Down, "Database check timed out", Some(started.elapsed())),
+    }
+}
+
+async fn check_pollinations() -> ComponentHealth {
+    if let Some(blocked) = blocked_by_local_only("pollinations") {
+        return blocked;
+    }
+    let Some(api_key) = crate::get_env_var("POLLINATIONS_API_KEY") else {
+        return component("pollinations", HealthState::Down, "POLLINATIONS_API_KEY not configured", None);
+    };
+    let referer = crate::get_env_var("POLLINATIONS_REFERER").unwrap_or_else(|| "mockmate".to_string());
+    let client = PollinationsClient::new(api_key, referer);
+
+    let started = Instant::now();
+    if client.health_check().await {
+        component("pollinations", HealthState::Ok, "Reachable", Some(started.elapsed()))
+    } else {
+        component("pollinations", HealthState::Down, "Unreachable or returning server errors", Some(started.elapsed()))
+    }
+}
+
+async fn check_openai() -> ComponentHealth {
+    if let Some(blocked) = blocked_by_local_only("openai") {
+        return blocked;
+    }
+    let Some(api_key) = crate::get_env_var("OPENAI_API_KEY") else {
+        // OpenAI is the fallback provider, so a missing key only degrades the app
+        return component("openai", HealthState::Degraded, "OPENAI_API_KEY not configured (fallback unavailable)", None);
+    };
+
+    let started = Instant::now();
+    let request = reqwest::Client::new()
+        .get("https://api.openai.com/v1/models")
+        .bearer_auth(api_key)
+        .timeout(PROBE_TIMEOUT)
+        .send_traced();
+
+    match request.await {
+        Ok(response) if response.status().is_success() => {
+            component("openai", HealthState::Ok, "Reachable", Some(started.elapsed()))
+        }
+        Ok(response) if response.status().as_u16() == 401 => {
+            component("openai", HealthState::Down, "API key rejected", Some(started.elapsed()))
+        }
+        Ok(response) => component(
+            "openai",
+            HealthState::Degraded,
+            format!("Unexpected status {}", response.status()),
+            Some(started.elapsed()),
+        ),
+        Err(e) => component("openai", HealthState::Down, format!("Unreachable: {}", e), Some(started.elapsed())),
+    }
+}
+
+async fn check_accessibility_monitoring() -> ComponentHealth {
+    match accessibility_reader::get_monitoring_status().await.into_result() {
+        Ok(status) => {
+            if status.get("is_monitoring").and_then(|v| v.as_bool()).unwrap_or(false) {
+                component("accessibility_monitoring", HealthState::Ok, "Monitoring thread running", None)
+            } else {
+                component("accessibility_monitoring", HealthState::Inactive, "Monitoring not started", None)
+            }
+        }
+        Err(e) => component("accessibility_monitoring", HealthState::Down, e.to_string(), None),
+    }
+}
+
+/// Check every subsystem and return a per-component readiness report
+#[tauri::command]
+#[specta::specta]
+pub async fn get_health_status() -> Response<HealthReport> {
+    envelope::respond("get_health_status", async move {
+        info!("🏥 Running aggregated health check...");
+
+        let (system_audio, microphone, deepgram, database, pollinations, openai, monitoring) = tokio::join!(
+            check_system_audio(),
+            check_microphone(),
+            check_deepgram(),
+            check_database(),
+            check_pollinations(),
+            check_openai(),
+            check_accessibility_monitoring(),
+        );
+
+        let components = vec![system_audio, microphone, deepgram, database, pollinations, openai, monitoring];
+
+        // Inactive components are not failures - they just haven't been started yet
+        let overall = if components.iter().any(|c| c.status == HealthState::Down) {
+            HealthState::Down
+        } else if components.iter().any(|c| c.status == HealthState::Degraded) {
+            HealthState::Degraded
+        } else {
+            HealthState::Ok
+        };
+
+        info!("🏥 Health check complete: {:?}", overall);
+
+        Ok(HealthReport {
+            overall,
+            components,
+            checked_at: chrono::Utc::now().to_rfc3339(),
+        })
+    })
+    .await
+}
@@ -0,0 +1,247 @@
+// Idempotency keys for session and credit operations
+// Activating a session spends a credit, so a retry after a dropped response must not charge twice.
+// Commands that connect to, activate or otherwise spend credits on a session take an optional
+// `idempotency_key`; the frontend makes one per user action and reuses it when retrying. `run`
+// records the operation as pending before its body runs, and the response is kept once the
+// operation succeeds so a retry with the same key gets that response without running it again.
+// Bodies that call the web backend forward the key in `Idempotency-Key`. `activate_session_cmd`
+// writes to the database directly, which has nowhere to store the key; a resumed attempt there
+// checks `database::activation_charged` before charging instead. An operation whose outcome is
+// unknown (network error, timeout, app closed mid-request) stays pending in
+// %APPDATA%\MockMate\pending_operations.json; the next attempt with its key is `resumed`, so the
+// body can check whether the first attempt already went through. Calls without a key are not
+// recorded at all.
+
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Duration, Utc};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::envelope::{self, Response};
+use crate::error::{AppError, ErrorCode};
+
+/// Header that carries the key on backend requests
+pub const HEADER: &str = "Idempotency-Key";
+/// Succeeded operations are replayed for this long; pending ones are kept until they resolve
+const REPLAY_WINDOW_HOURS: i64 = 24;
+
+static OPERATIONS: Lazy<Mutex<HashMap<String, Operation>>> = Lazy::new(|| Mutex::new(load()));
+/// Keys whose body is running right now
+static IN_FLIGHT: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationStatus {
+    /// Sent, but no response has been acknowledged yet
+    Pending,
+    Succeeded,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Operation {
+    pub key: String,
+    pub command: String,
+    pub session_id: String,
+    pub status: OperationStatus,
+    pub started_at: DateTime<Utc>,
+    pub attempts: u32,
+    pub acknowledged_at: Option<DateTime<Utc>>,
+    /// What the command returned, replayed to retries
+    pub response: Option<serde_json::Value>,
+}
+
+/// What the body of `run` gets to work with
+pub struct Attempt {
+    pub key: String,
+    /// An earlier attempt with this key never resolved and may have gone through
+    pub resumed: bool,
+}
+
+/// Removes the key from `IN_FLIGHT` even when the body is dropped (e.g. by `cancel_request`)
+struct InFlight(String);
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        IN_FLIGHT.lock().remove(&self.0);
+    }
+}
+
+fn operations_path() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("pending_operations.json")
+}
+
+fn load() -> HashMap<String, Operation> {
+    let operations: HashMap<String, Operation> = std::fs::read(operations_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default();
+    let cutoff = Utc::now() - Duration::hours(REPLAY_WINDOW_HOURS);
+    operations
+        .into_iter()
+        .filter(|(_, operation)| {
+            operation.status == OperationStatus::Pending || operation.acknowledged_at.is_some_and(|at| at > cutoff)
+        })
+        .collect()
+}
+
+fn save(operations: &HashMap<String, Operation>) {
+    let path = operations_path();
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| serde_json::to_vec_pretty(operations).map_err(std::io::Error::other))
+        .and_then(|body| std::fs::write(&path, body));
+    if let Err(e) = result {
+        warn!("Failed to save pending operations to {}: {}", path.display(), e);
+    }
+}
+
+/// Run `task` at most once per key: a key that already succeeded replays its response, one still
+/// running is rejected, and one left pending by an earlier attempt runs again as `resumed`.
+/// Without a key nothing is recorded; the body runs once with a fresh key for the backend.
+pub async fn run<T, F, Fut>(command: &'static str, key: Option<String>, session_id: &str, task: F) -> Result<T, AppError>
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce(Attempt) -> Fut,
+    Fut: Future<Output = Result<T, AppError>>,
+{
+    let Some(key) = key else {
+        // A retry could not be recognised, so a pending entry would never be resolved
+        return task(Attempt { key: uuid::Uuid::new_v4().to_string(), resumed: false }).await;
+    };
+    if key.trim().is_empty() {
+        return Err(AppError::new(ErrorCode::InvalidInput, "Idempotency key cannot be empty"));
+    }
+
+    let (resumed, _in_flight) = {
+        let mut operations = OPERATIONS.lock();
+        let mut in_flight = IN_FLIGHT.lock();
+        let resumed = match operations.get_mut(&key) {
+            Some(operation) if operation.command != command || operation.session_id != session_id => {
+                return Err(AppError::new(
+                    ErrorCode::InvalidInput,
+                    format!(
+                        "Idempotency key {} was already used for {} on session {}",
+                        key, operation.command, operation.session_id
+                    ),
+                ));
+            }
+            Some(_) if in_flight.contains(&key) => {
+                return Err(AppError::new(
+                    ErrorCode::Unavailable,
+                    format!("{} with idempotency key {} is already in progress", command, key),
+                ));
+            }
+            Some(Operation { status: OperationStatus::Succeeded, response: Some(response), .. }) => {
+                info!("↩️ Replaying {} for session {} (key {})", command, session_id, key);
+                return Ok(serde_json::from_value(response.clone())?);
+            }
+            Some(operation) => {
+                operation.attempts += 1;
+                true
+            }
+            None => {
+                operations.insert(
+                    key.clone(),
+                    Operation {
+                        key: key.clone(),
+                        command: command.to_string(),
+                        session_id: session_id.to_string(),
+                        status: OperationStatus::Pending,
+                        started_at: Utc::now(),
+                        attempts: 1,
+                        acknowledged_at: None,
+                        response: None,
+                    },
+                );
+                false
+            }
+        };
+        in_flight.insert(key.clone());
+        save(&operations);
+        (resumed, InFlight(key.clone()))
+    };
+
+    if resumed {
+        info!("🔁 Resuming pending {} for session {} (key {})", command, session_id, key);
+    }
+    let result = task(Attempt { key: key.clone(), resumed }).await;
+
+    let mut operations = OPERATIONS.lock();
+    match &result {
+        Ok(value) => {
+            if let Some(operation) = operations.get_mut(&key) {
+                operation.status = OperationStatus::Succeeded;
+                operation.acknowledged_at = Some(Utc::now());
+                operation.response = serde_json::to_value(value).ok();
+            }
+        }
+        // The request may have reached the backend; keep the key so a retry cannot double-charge
+        Err(error) if error.retryable() => {
+            warn!("⏳ Outcome of {} for session {} is unknown, keeping key {} pending: {}", command, session_id, key, error.message);
+        }
+        Err(_) => {
+            operations.remove(&key);
+        }
+    }
+    save(&operations);
+    result
+}
+
+/// Operations sent but never acknowledged, oldest first
+#[tauri::command]
+#[specta::specta]
+pub fn list_pending_operations() -> Response<Vec<Operation>> {
+    envelope::respond_sync("list_pending_operations", || {
+        let mut pending: Vec<Operation> = OPERATIONS
+            .lock()
+            .values()
+            .filter(|operation| operation.status == OperationStatus::Pending)
+            .cloned()
+            .collect();
+        pending.sort_by_key(|operation| operation.started_at);
+        Ok(pending)
+    })
+}
+
+/// Forget a pending operation, e.g. once the user confirmed it on the web dashboard
+#[tauri::command]
+#[specta::specta]
+pub fn discard_pending_operation(key: String) -> Response<bool> {
+    envelope::respond_sync("discard_pending_operation", || {
+        let mut operations = OPERATIONS.lock();
+        if IN_FLIGHT.lock().contains(&key) {
+            return Err(AppError::new(ErrorCode::Unavailable, format!("Operation {} is still in progress", key)));
+        }
+        let removed = operations.remove(&key).is_some();
+        if removed {
+            save(&operations);
+        }
+        Ok(removed)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn keyless_calls_are_not_recorded() {
+        let result: Result<String, AppError> = run("keyless_test", None, "session", |attempt| async move {
+            assert!(!attempt.resumed);
+            Err(AppError::new(ErrorCode::Network, "connection reset"))
+        })
+        .await;
+        assert!(result.is_err());
+        assert!(!OPERATIONS.lock().values().any(|operation| operation.command == "keyless_test"));
+    }
+}
@@ -0,0 +1,323 @@
+// Job description import from a posting URL
+// Fetches a LinkedIn, Greenhouse, Lever (or any) job page and pulls out the title, company and
+// description. Structured schema.org JobPosting data is preferred when the page has it; otherwise
+// site-specific markup and a readability-style pass over the page text are used. The result fills
+// the interview context and the transcription keyword list.
+
+use std::collections::HashMap;
+
+use log::info;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::State;
+
+use crate::net_trace::TracedSend;
+use crate::AppState;
+use crate::envelope::{self, Response};
+use crate::error::{AppError, ErrorCode};
+
+/// Browsers get the full server-rendered posting; bare clients often get a login wall
+const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0 Safari/537.36";
+/// Keywords handed to transcription
+const MAX_KEYWORDS: usize = 25;
+/// Lines shorter than this are treated as navigation/boilerplate by the readability pass
+const MIN_CONTENT_LINE: usize = 40;
+
+static JSON_LD: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?is)<script[^>]*type=["']application/ld\+json["'][^>]*>(.*?)</script>"#).expect("valid pattern"));
+static META: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?is)<meta[^>]+(?:property|name)=["']([^"']+)["'][^>]*content=["']([^"']*)["']"#).expect("valid pattern")
+});
+static TITLE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?is)<title[^>]*>(.*?)</title>").expect("valid pattern"));
+static NOISE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?is)<(script|style|noscript|nav|header|footer|aside|form|svg)\b[^>]*>.*?</(script|style|noscript|nav|header|footer|aside|form|svg)>")
+        .expect("valid pattern")
+});
+static BLOCK_TAG: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)<(br|/p|/div|/li|/h[1-6]|/section|/article|/ul|/ol|/tr)\b[^>]*>").expect("valid pattern"));
+static LIST_ITEM: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)<li\b[^>]*>").expect("valid pattern"));
+static TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<[^>]+>").expect("valid pattern"));
+static ENTITY: Lazy<Regex> = Lazy::new(|| Regex::new(r"&(#x[0-9a-fA-F]+|#[0-9]+|[a-zA-Z]+);").expect("valid pattern"));
+/// Technology-looking terms: acronyms, CamelCase, and names like C++, C#, Node.js, .NET
+static TECH_TERM: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:\b[A-Za-z]+(?:\+\+|#)|\.NET\b|\b[A-Za-z]+\.js\b|\b[A-Z][a-z]+[A-Z][A-Za-z]*\b|\b[a-z]+[A-Z][A-Za-z]*\b|\b[A-Z]{2,}[0-9]*s?\b)")
+        .expect("valid pattern")
+});
+/// Site-specific title/company markup: (host fragment, title pattern, company pattern)
+static SITE_PATTERNS: Lazy<Vec<(&'static str, Regex, Regex)>> = Lazy::new(|| {
+    let re = |pattern: &str| Regex::new(pattern).expect("valid pattern");
+    vec![
+        (
+            "linkedin.com",
+            re(r#"(?is)<h1[^>]*top-card-layout__title[^>]*>(.*?)</h1>"#),
+            re(r#"(?is)<a[^>]*topcard__org-name-link[^>]*>(.*?)</a>"#),
+        ),
+        (
+            "greenhouse.io",
+            re(r#"(?is)<h1[^>]*(?:app-title|section-header)[^>]*>(.*?)</h1>"#),
+            re(r#"(?is)<(?:span|div)[^>]*company-name[^>]*>(.*?)</(?:span|div)>"#),
+        ),
+        (
+            "lever.co",
+            re(r#"(?is)<div[^>]*posting-headline[^>]*>\s*<h2[^>]*>(.*?)</h2>"#),
+            re(r#"(?is)<div[^>]*main-footer-text[^>]*>.*?<p[^>]*>(.*?)</p>"#),
+        ),
+    ]
+});
+/// Acronyms common in postings that are not skills
+const NOT_KEYWORDS: &[&str] = &[
+    "EEO", "EOE", "USA", "US", "UK", "EU", "OR", "AND", "THE", "WE", "YOU", "PTO", "FAQ", "CEO", "CTO", "HR", "LLC", "INC",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ImportedJobDescription {
+    pub url: String,
+    pub title: Option<String>,
+    pub company: Option<String>,
+    pub location: Option<String>,
+    pub description: String,
+    pub keywords: Vec<String>,
+    /// "json_ld" when structured data was found, otherwise "html"
+    pub extraction: String,
+}
+
+fn decode_entities(text: &str) -> String {
+    ENTITY
+        .replace_all(text, |caps: &regex::Captures| {
+            let entity = &caps[1];
+            let decoded = if let Some(hex) = entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X")) {
+                u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+            } else if let Some(decimal) = entity.strip_prefix('#') {
+                decimal.parse().ok().and_then(char::from_u32)
+            } else {
+                match entity {
+                    "amp" => Some('&'),
+                    "lt" => Some('<'),
+                    "gt" => Some('>'),
+                    "quot" => Some('"'),
+                    "apos" => Some('\''),
+                    "nbsp" => Some(' '),
+                    "ndash" => Some('–'),
+                    "mdash" => Some('—'),
+                    "rsquo" | "lsquo" => Some('\''),
+                    "bull" => Some('•'),
+                    _ => None,
+                }
+            };
+            decoded.map(String::from).unwrap_or_else(|| caps[0].to_string())
+        })
+        .into_owned()
+}
+
+/// HTML fragment to plain text, keeping paragraph and list structure as lines
+fn html_to_text(html: &str) -> String {
+    let html = NOISE.replace_all(html, "\n");
+    let html = BLOCK_TAG.replace_all(&html, "\n");
+    let html = LIST_ITEM.replace_all(&html, "\n• ");
+    let text = decode_entities(&TAG.replace_all(&html, " "));
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty() && line != "•")
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn clean(text: &str) -> Option<String> {
+    let text = decode_entities(&TAG.replace_all(text, " "));
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+/// schema.org JobPosting objects, including ones nested in arrays or @graph
+fn find_job_posting(value: &serde_json::Value) -> Option<&serde_json::Value> {
+    match value {
+        serde_json::Value::Array(items) => items.iter().find_map(find_job_posting),
+        serde_json::Value::Object(object) => {
+            let is_posting = match &object.get("@type") {
+                Some(serde_json::Value::String(kind)) => kind == "JobPosting",
+                Some(serde_json::Value::Array(kinds)) => kinds.iter().any(|k| k == "JobPosting"),
+                _ => false,
+            };
+            if is_posting {
+                Some(value)
+            } else {
+                object.get("@graph").and_then(find_job_posting)
+            }
+        }
+        _ => None,
+    }
+}
+
+struct PostingFields {
+    title: Option<String>,
+    company: Option<String>,
+    location: Option<String>,
+    description: String,
+}
+
+fn from_json_ld(html: &str) -> Option<PostingFields> {
+    JSON_LD.captures_iter(html).find_map(|caps| {
+        let value: serde_json::Value = serde_json::from_str(caps[1].trim()).ok()?;
+        let posting = find_job_posting(&value)?;
+        let description = html_to_text(&decode_entities(posting["description"].as_str()?));
+        let company = match &posting["hiringOrganization"] {
+            serde_json::Value::String(name) => clean(name),
+            organization => organization["name"].as_str().and_then(clean),
+        };
+        let location = posting["jobLocation"]
+            .as_array()
+            .and_then(|locations| locations.first())
+            .unwrap_or(&posting["jobLocation"])["address"]
+            .as_object()
+            .map(|address| {
+                ["addressLocality", "addressRegion", "addressCountry"]
+                    .iter()
+                    .filter_map(|key| address.get(*key).and_then(|v| v.as_str().or_else(|| v["name"].as_str())))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .filter(|location| !location.is_empty());
+        Some(PostingFields {
+            title: posting["title"].as_str().and_then(clean),
+            company,
+            location,
+            description,
+        })
+    })
+}
+
+/// Keep the densest stretch of text: lines long enough to be content, plus the bullets among them
+fn readable_text(html: &str) -> String {
+    let body = html
+        .find("<main")
+        .or_else(|| html.find("<article"))
+        .map(|start| &html[start..])
+        .unwrap_or(html);
+    html_to_text(body)
+        .lines()
+        .filter(|line| line.len() >= MIN_CONTENT_LINE || (line.starts_with('•') && line.len() > 10))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn extract_keywords(description: &str, title: Option<&str>, company: Option<&str>) -> Vec<String> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for term in TECH_TERM.find_iter(description).map(|m| m.as_str()) {
+        if !NOT_KEYWORDS.contains(&term.to_uppercase().as_str()) {
+            *counts.entry(term).or_default() += 1;
+        }
+    }
+    let mut terms: Vec<(&str, usize)> = counts.into_iter().collect();
+    terms.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+
+    let mut keywords: Vec<String> = company.into_iter().map(str::to_string).collect();
+    keywords.extend(title.into_iter().map(str::to_string));
+    for (term, _) in terms {
+        if keywords.len() >= MAX_KEYWORDS {
+            break;
+        }
+        if !keywords.iter().any(|k| k.eq_ignore_ascii_case(term)) {
+            keywords.push(term.to_string());
+        }
+    }
+    keywords
+}
+
+fn extract(url: &url::Url, html: &str) -> ImportedJobDescription {
+    let meta: HashMap<String, String> = META
+        .captures_iter(html)
+        .map(|caps| (caps[1].to_lowercase(), decode_entities(&caps[2])))
+        .collect();
+    let host = url.host_str().unwrap_or_default();
+
+    let (title, company, location, description, extraction) = match from_json_ld(html) {
+        Some(posting) => (posting.title, posting.company, posting.location, posting.description, "json_ld"),
+        None => {
+            let site = SITE_PATTERNS.iter().find(|(site, _, _)| host.ends_with(site));
+            let title = site
+                .and_then(|(_, title, _)| title.captures(html))
+                .and_then(|caps| clean(&caps[1]))
+                .or_else(|| meta.get("og:title").and_then(|t| clean(t)))
+                .or_else(|| TITLE.captures(html).and_then(|caps| clean(&caps[1])));
+            let company = site
+                .and_then(|(_, _, company)| company.captures(html))
+                .and_then(|caps| clean(&caps[1]))
+                .or_else(|| meta.get("og:site_name").and_then(|t| clean(t)));
+            (title, company, None, readable_text(html), "html")
+        }
+    };
+    let description = if description.trim().is_empty() {
+        meta.get("og:description").or_else(|| meta.get("description")).cloned().unwrap_or_default()
+    } else {
+        description
+    };
+    let keywords = extract_keywords(&description, title.as_deref(), company.as_deref());
+
+    ImportedJobDescription {
+        url: url.to_string(),
+        title,
+        company,
+        location,
+        description,
+        keywords,
+        extraction: extraction.to_string(),
+    }
+}
+
+/// Fetch a job posting and load it into the interview context and transcription keywords
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn import_job_description(state: State<'_, AppState>, url: String) -> Response<ImportedJobDescription> {
+    envelope::respond("import_job_description", async move {
+        let url = url::Url::parse(url.trim()).map_err(|e| format!("Invalid job posting URL: {}", e))?;
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err("Job posting URL must be http(s)".into());
+        }
+        crate::privacy::ensure_cloud_allowed("Job posting import")?;
+
+        let response = reqwest::Client::new()
+            .get(url.clone())
+            .header("User-Agent", USER_AGENT)
+            .header("Accept", "text/html,application/xhtml+xml")
+            .header("Accept-Language", "en-US,en;q=0.9")
+            .send_traced()
+            .await
+            .map_err(|e| e.context("Failed to fetch job posting"))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(AppError::new(ErrorCode::from_status(status), format!("Job posting request failed: {}", status)));
+        }
+        let html = response.text().await.map_err(|e| e.to_string())?;
+
+        let job = extract(&url, &html);
+        if job.description.trim().is_empty() {
+            return Err("No job description could be found on that page (it may require signing in)".into());
+        }
+
+        {
+            let mut context = state.interview_context.lock();
+            if let Some(company) = &job.company {
+                context.company = Some(company.clone());
+            }
+            if let Some(title) = &job.title {
+                context.position = Some(title.clone());
+            }
+            context.job_description = Some(job.description.clone());
+        }
+        crate::deepgram_streaming::set_keywords(job.keywords.clone());
+
+        info!(
+            "📄 Imported job description from {} via {}: {:?} at {:?}, {} chars, {} keywords",
+            url.host_str().unwrap_or_default(),
+            job.extraction,
+            job.title,
+            job.company,
+            job.description.len(),
+            job.keywords.len()
+        );
+        Ok(job)
+    })
+    .await
+}
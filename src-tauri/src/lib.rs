@@ -2,8 +2,8 @@
 
 use tauri::{Builder, AppHandle, Window, State, Manager, Emitter};
 use serde::{Serialize, Deserialize};
+use specta::Type;
 use log::{info, error, warn};
-use base64::Engine;
 use anyhow::Result;
 use std::sync::Arc;
 use parking_lot::Mutex;
@@ -26,11 +26,114 @@ pub mod accessibility_reader; // Windows Accessibility API text reader
 pub mod window_manager; // DPI-aware window management
 pub mod permissions; // Permission management for audio access
 pub mod stereo_mix_manager; // Windows Stereo Mix automatic enablement
+pub mod logging; // Structured logging with rotating log files
+pub mod diagnostics; // Diagnostics bundle export for support
+pub mod crash_reporter; // Panic hook and persisted crash reports
+pub mod metrics; // End-to-end latency metrics
+pub mod health; // Aggregated subsystem health check
+pub mod telemetry; // Opt-in anonymized usage telemetry
+pub mod audio_debug; // Raw/processed PCM dumps for debugging capture issues
+pub mod net_trace; // Redacted tracing of outbound HTTP requests
+pub mod startup; // Startup phase profiling and deferred services
+pub mod privacy; // Local-only mode that blocks all cloud calls
+pub mod retention; // Retention policy, janitor and data purge
+pub mod audit; // Local audit log of data sent off-machine
+pub mod app_consent; // Per-application consent for accessibility reading
+#[cfg_attr(target_os = "linux", path = "accessibility_worker_atspi.rs")]
+#[cfg_attr(not(any(windows, target_os = "linux")), path = "accessibility_worker_stub.rs")]
+pub mod accessibility_worker; // STA COM thread that owns accessibility/UIA work
+#[cfg(windows)]
+pub mod window_cache; // Cached window enumeration invalidated by WinEvent hooks
+pub mod ai_stream; // Event-based streaming of AI answers to the response window
+pub mod answer_markdown; // CommonMark normalization and token-aligned chunking of streamed answers
+pub mod audio_buffer; // Shared zero-copy audio chunks and direct-to-disk WAV writing
+pub mod audio_thread; // MMCSS Pro Audio capture threads with pre-allocated buffers
+pub mod capture_buffer; // Memory-capped capture buffer that spills to temp files
+mod warmup; // Provider client warm-up at session start
+pub mod resize_coordinator; // Debounced, serialized resizes of the AI response window
+pub mod updater; // Channel-based auto-update with staged rollout and signed installers
+pub mod scripting; // Rhai hook scripts for user automations
+pub mod headless; // Windowless CLI for capture, transcription and answers
+#[cfg_attr(not(windows), path = "screenshot_stub.rs")]
+pub mod screenshot; // Windows Graphics Capture screenshots of monitors, windows and regions
+#[cfg_attr(not(windows), path = "session_recorder_stub.rs")]
+pub mod session_recorder; // Opt-in MP4 recording of the screen and audio during a session
+pub mod practice; // Practice interviews with the AI acting as interviewer
+pub mod question_bank; // Local question bank with tags and spaced-repetition rehearsal
+pub mod flashcards; // SM-2 flashcards built from completed sessions
+pub mod calendar; // Google/Outlook calendar sync for upcoming interviews
+pub mod zoom_captions; // Zoom caption bridge feeding question detection
+pub mod teams_graph; // Teams transcripts via Microsoft Graph merged into interview reports
+pub mod webhooks; // Signed webhook notifications for session events
+pub mod secrets; // Integration tokens in the OS credential store
+pub mod session_transcript; // Per-session transcript store used by exports
+pub mod notion; // Notion export of interview notes
+pub mod markdown_export; // Obsidian/Logseq vault export of sessions
+pub mod session_summary; // End-of-session AI summaries
+pub mod chat_share; // Slack/Discord summary posting
+pub mod job_import; // Job posting import into the interview context
+pub mod documents; // AI-drafted thank-you emails and cover letters
+pub mod pacing; // Answer talk time, pace and filler-word feedback
+pub mod question_topic; // Question topics shared by practice and categorization
+pub mod practice_profile; // Adaptive per-topic practice difficulty
+pub mod rubric; // Rubric-based answer evaluation
+pub mod coding_challenge; // Coding-problem detection and structured solutions
+pub mod experience_bank; // User stories and STAR answers for behavioral questions
+pub mod report_card; // Cross-session scores, pacing and topic trends per profile
+pub mod system_design; // Sectioned system design answers with text diagrams
+pub mod platform; // Per-OS feature availability for the Windows-only modules
+#[cfg(target_os = "linux")]
+pub mod pulse_capture; // PulseAudio/PipeWire capture through parec
+#[cfg(target_os = "macos")]
+pub mod macos_loopback; // System audio on macOS through a BlackHole-style loopback device
+pub mod capture_protection; // Per-window screen capture protection and its effective state
+pub mod selftest; // Runtime self-test of the window handle and audio FFI paths
+pub mod appearance; // OS theme, high-contrast and reduced-motion preferences for overlay windows
+pub mod portable; // Portable mode: data beside the executable, no registry or protocol registration
+pub mod audio_probe; // Per-platform probing of loopback, per-app capture, AEC and exclusive mode
+pub mod error; // Typed command errors with codes, retryability and remediation hints
+pub mod envelope; // Request ID, timing and ok/data/error envelope around every command result
+pub mod events; // Event names, payload types and the list_events discovery command
+pub mod cancellation; // cancel_request and progress events for long-running commands
+pub mod guard; // Session, permission and local-only checks run before command bodies
+pub mod bindings; // TypeScript bindings generated from the command and event types
+pub mod api; // IPC API version and get_api_version
+mod compat; // Deprecated command aliases kept for one API version
+pub mod idempotency; // Idempotency keys and pending operations for credit-spending commands
+pub mod mic_selftest; // Record, play back and score a microphone test clip
+pub mod audio_devices; // Preferred microphone/loopback devices, found again by ID or name
+pub mod push_to_talk; // Push-to-talk/push-to-mute gating of the microphone
+pub mod wake_word; // "Hey MockMate" keyword spotter that triggers actions hands-free
+pub mod meeting_detection; // Teams/Zoom/Meet call detection from audio sessions and windows
+pub mod auto_analysis; // Runs screen analysis when a large block of new text appears
+pub mod teleprompter; // AI response window as an auto-scrolling teleprompter on another display
+pub mod answer_language; // Answer language choice and translation of questions asked in another language
+pub mod glossary; // Per-profile glossary of domain terms for transcription boosting and prompts
+pub mod context_builder; // Token-budgeted prompt context from resume, job description, transcript and prior answers
+pub mod question_dedup; // Links repeated and reworded questions to one canonical question and answer
+pub mod session_labels; // Tags, favorites and saved filters for sessions and answers
+pub mod session_timeline; // Ordered event stream of a session for replay alongside its recording
+pub mod local_server; // Token, settings and restart helpers shared by the local servers
+pub mod live_server; // Opt-in token-authenticated WebSocket feed of transcripts, questions and answers
+pub mod rest_api; // Opt-in token-authenticated localhost HTTP API for Stream Deck and scripts
+pub mod vosk_fallback; // Offline Vosk transcription when Deepgram is unreachable
+pub mod transcription_preset; // Latency/accuracy presets for capture chunking, Deepgram and the local model
+pub mod session_prompt; // Per-session custom system prompt applied to every generation
+pub mod credits; // Credit top-up checkout, mockmate://credits links and balance refresh
+pub mod session_auth; // Temp token expiry tracking, refresh and re-authentication
+pub mod auth; // Browser sign-in with OAuth PKCE; refresh token in the OS credential store
+pub mod accounts; // Account profiles with separate data folders and credential store entries
+pub mod managed_policy; // Administrator policy from the registry, managed plists or a JSON file
+pub mod entitlement; // Signed session entitlements cached for offline grace
+use net_trace::TracedSend;
 // Stealth mode modules for secure interview operation
 mod stealth_hotkeys_simple;
+#[cfg_attr(not(windows), path = "task_manager_stealth_stub.rs")]
 mod task_manager_stealth_simple;
+#[cfg_attr(not(windows), path = "real_stealth_stub.rs")]
 mod real_stealth; // REAL stealth implementation for actual process hiding
 // mod advanced_stealth; // REMOVED - Advanced stealth module removed
+#[cfg_attr(not(windows), path = "dll_injection_stealth_stub.rs")]
 mod dll_injection_stealth; // DLL injection stealth for maximum hiding
 mod taskbar_manager; // Windows taskbar hiding functionality
 // Re-export simplified modules with original names
@@ -46,205 +149,440 @@ pub mod model_optimizer; // Advanced model selection and optimization
 
 use openai::{OpenAIClient, InterviewContext};
 use pollinations::{PollinationsClient, AIProvider};
+use error::AppError;
+use envelope::{IntoResult, Response};
+use guard::Require;
+use permissions::Permission;
+use context_builder::ContextBuilder;
 // use database::shared::*; // Import shared database types and functions - commented out to avoid unused import warning
 
+/// Every command the frontend can invoke
+fn commands() -> tauri_specta::Commands<tauri::Wry> {
+    tauri_specta::collect_commands![
+        start_audio_stream,
+        stop_audio_stream,
+        start_system_audio_capture,
+        start_microphone_capture,
+        test_microphone_capture,
+        get_audio_devices,
+        check_audio_status,
+        start_audio_with_config,
+        test_audio_capture,
+        test_native_windows_system_audio_capture,
+        send_manual_question,
+        connect_to_session,
+        close_application,
+        minimize_window,
+        toggle_always_on_top,
+        create_ai_response_window,
+        close_ai_response_window,
+        debug_main_window_dimensions,
+        fix_main_window_invisible_boundary,
+        force_window_exact_content_size,
+        nuclear_fix_webview_padding,
+        resize_ai_response_window,
+        show_ai_response_window,
+        hide_ai_response_window,
+        send_ai_response_data,
+        reset_ai_response_window_size,
+        create_ai_response_window_enhanced_below,
+        reset_ai_response_window_enhanced_below_size,
+        // Real-time transcription commands - DISABLED (using JavaScript SDK now)
+        // realtime_transcription::start_microphone_transcription,
+        // realtime_transcription::start_system_audio_transcription,
+        // realtime_transcription::stop_transcription,
+        // realtime_transcription::get_transcription_status,
+        // realtime_transcription::get_deepgram_config,
+        // realtime_transcription::test_deepgram_connection,
+        // Pluely audio capture commands
+        pluely_audio::start_pluely_system_audio_capture,
+        pluely_audio::stop_pluely_system_audio_capture,
+        pluely_audio::is_pluely_audio_active,
+        pluely_audio::test_pluely_system_audio_capture,
+        // Pluely microphone capture commands
+        pluely_microphone::start_pluely_microphone_capture,
+        pluely_microphone::stop_pluely_microphone_capture,
+        pluely_microphone::is_pluely_microphone_active,
+        // Deepgram Nova-3 streaming transcription commands
+        deepgram_streaming::start_deepgram_streaming,
+        deepgram_streaming::stop_deepgram_streaming,
+        deepgram_streaming::is_deepgram_streaming_active,
+        deepgram_streaming::get_transcription_keywords,
+        deepgram_streaming::set_transcription_keywords,
+        generate_ai_answer,
+        analyze_screen_content,
+        update_interview_context,
+        get_available_models,
+        get_ai_providers,
+        save_microphone_file,
+        save_system_audio_file,
+        audio_wasapi::set_capture_memory_limit,
+        audio_wasapi::get_capture_memory_status,
+        warmup::warm_up_providers,
+        pollinations_generate_answer,
+        pollinations_generate_answer_streaming,
+        pollinations_generate_answer_post_streaming,
+        // AI Analysis commands (accessibility-based)
+        analyze_screen_with_ai,
+        analyze_screen_with_ai_streaming,
+        // Windows Accessibility API commands (Primary text reading solution)
+        accessibility_reader::read_text_from_applications,
+        accessibility_reader::read_text_from_focused_window,
+        accessibility_reader::read_text_from_background_windows,
+        accessibility_reader::read_text_from_current_window,
+        // NEW: Commands for targeting window behind MockMate (interviewer's window)
+        accessibility_reader::read_text_from_window_behind_mockmate,
+        accessibility_reader::capture_previous_focused_window,
+        // Real-time monitoring commands
+        accessibility_reader::start_realtime_monitoring,
+        accessibility_reader::stop_realtime_monitoring,
+        accessibility_reader::get_monitoring_status,
+        // Hybrid approach commands
+        accessibility_reader::extract_text_hybrid_approach,
+        accessibility_reader::update_accessibility_config,
+        // Accessibility-based AI analysis commands
+        analyze_applications_with_ai_streaming,
+        analyze_focused_window_with_ai_streaming,
+        // Screenshot and vision analysis commands
+        capture_screenshot,
+        screenshot::list_capture_monitors,
+        screenshot::capture_monitor_screenshot,
+        screenshot::capture_window_screenshot,
+        screenshot::capture_region_screenshot,
+        session_recorder::get_recording_settings,
+        session_recorder::set_recording_settings,
+        session_recorder::start_session_recording,
+        session_recorder::stop_session_recording,
+        session_recorder::get_recording_status,
+        practice::start_practice_session,
+        practice::submit_practice_answer,
+        practice::skip_practice_question,
+        practice::get_practice_session,
+        practice::end_practice_session,
+        practice::list_practice_reports,
+        question_bank::add_bank_question,
+        question_bank::update_bank_question,
+        question_bank::delete_bank_question,
+        question_bank::query_bank_questions,
+        question_bank::list_bank_tags,
+        question_bank::import_question_bank,
+        question_bank::export_question_bank,
+        question_bank::get_rehearsal_queue,
+        question_bank::record_rehearsal,
+        flashcards::generate_flashcards,
+        flashcards::get_due_flashcards,
+        flashcards::grade_flashcard,
+        flashcards::delete_flashcard,
+        calendar::set_calendar_token,
+        calendar::connect_calendar,
+        calendar::disconnect_calendar,
+        calendar::get_calendar_settings,
+        calendar::set_calendar_settings,
+        calendar::get_upcoming_interviews,
+        zoom_captions::get_zoom_caption_settings,
+        zoom_captions::set_zoom_caption_settings,
+        zoom_captions::connect_zoom_captions,
+        zoom_captions::disconnect_zoom_captions,
+        zoom_captions::get_zoom_caption_status,
+        teams_graph::import_teams_meeting_data,
+        teams_graph::get_teams_meeting_report,
+        webhooks::list_webhooks,
+        webhooks::save_webhook,
+        webhooks::delete_webhook,
+        webhooks::test_webhook,
+        webhooks::get_webhook_deliveries,
+        session_transcript::get_session_transcript,
+        notion::set_notion_token,
+        notion::get_notion_status,
+        notion::export_session_to_notion,
+        markdown_export::export_session_markdown,
+        session_summary::generate_session_summary,
+        session_summary::get_session_summary,
+        chat_share::get_chat_channels,
+        chat_share::set_chat_channel,
+        chat_share::post_session_summary,
+        job_import::import_job_description,
+        documents::generate_thank_you_email,
+        documents::generate_cover_letter,
+        pacing::get_pacing_report,
+        practice_profile::get_practice_profile,
+        practice_profile::reset_practice_profile,
+        rubric::get_rubric,
+        rubric::set_rubric,
+        rubric::get_answer_evaluations,
+        rubric::evaluate_session_answers,
+        coding_challenge::get_coding_settings,
+        coding_challenge::set_coding_settings,
+        coding_challenge::solve_coding_challenge,
+        coding_challenge::list_coding_solutions,
+        experience_bank::list_stories,
+        experience_bank::add_story,
+        experience_bank::update_story,
+        experience_bank::delete_story,
+        experience_bank::find_matching_stories,
+        experience_bank::generate_star_answer,
+        report_card::generate_report_card,
+        system_design::get_system_design_settings,
+        system_design::set_system_design_settings,
+        platform::get_platform_capabilities,
+        capture_protection::get_capture_protection_status,
+        selftest::run_platform_selftest,
+        appearance::get_os_appearance,
+        portable::get_portable_status,
+        window_manager::get_positioning_strategy,
+        audio_probe::probe_audio_capabilities,
+        events::list_events,
+        database::postgres::save_interview_items,
+        cancellation::cancel_request,
+        api::get_api_version,
+        idempotency::list_pending_operations,
+        idempotency::discard_pending_operation,
+        mic_selftest::run_mic_selftest,
+        audio_devices::list_audio_endpoints,
+        audio_devices::get_audio_device_selection,
+        audio_devices::set_preferred_audio_device,
+        push_to_talk::get_push_to_talk,
+        push_to_talk::set_push_to_talk,
+        push_to_talk::set_ptt_held,
+        wake_word::get_wake_word,
+        wake_word::set_wake_word,
+        wake_word::record_wake_word_sample,
+        wake_word::clear_wake_word_samples,
+        meeting_detection::get_meeting_detection,
+        meeting_detection::set_meeting_detection,
+        auto_analysis::get_auto_analysis,
+        auto_analysis::set_auto_analysis,
+        teleprompter::list_displays,
+        teleprompter::get_teleprompter_state,
+        teleprompter::start_teleprompter,
+        teleprompter::stop_teleprompter,
+        teleprompter::set_teleprompter_speed,
+        teleprompter::set_teleprompter_paused,
+        answer_language::get_answer_language,
+        answer_language::set_answer_language,
+        glossary::list_glossary,
+        glossary::add_glossary_term,
+        glossary::update_glossary_term,
+        glossary::delete_glossary_term,
+        glossary::set_active_glossary_profile,
+        context_builder::preview_prompt_context,
+        question_dedup::list_recent_questions,
+        question_dedup::clear_recent_questions,
+        session_labels::get_session_labels,
+        session_labels::set_session_labels,
+        session_labels::set_answer_labels,
+        session_labels::find_labeled_items,
+        session_labels::list_label_tags,
+        session_labels::list_saved_filters,
+        session_labels::save_label_filter,
+        session_labels::delete_saved_filter,
+        session_timeline::get_session_timeline,
+        live_server::get_live_server_settings,
+        live_server::set_live_server_settings,
+        live_server::regenerate_live_server_token,
+        live_server::get_live_server_status,
+        rest_api::get_rest_api_settings,
+        rest_api::set_rest_api_settings,
+        rest_api::regenerate_rest_api_token,
+        rest_api::get_rest_api_status,
+        vosk_fallback::get_offline_transcription_status,
+        transcription_preset::get_transcription_preset,
+        transcription_preset::set_transcription_preset,
+        session_prompt::get_session_system_prompt,
+        session_prompt::set_session_system_prompt,
+        credits::open_purchase_flow,
+        credits::refresh_credit_balance,
+        session_auth::get_session_auth_status,
+        session_auth::reauthenticate_session,
+        auth::login,
+        auth::logout,
+        auth::get_auth_status,
+        accounts::list_accounts,
+        accounts::add_account,
+        accounts::switch_account,
+        managed_policy::get_effective_policy,
+        entitlement::get_offline_grace,
+        entitlement::set_offline_grace_hours,
+        answer_screenshot_questions_streaming,
+        enhanced_qa_with_vision_streaming,
+        // Session management commands (existing)
+        connect_to_web_session,
+        activate_web_session,
+        get_session_info,
+        handle_protocol_launch,
+        // Temporary token authentication
+        connect_with_temp_token,
+        // New shared database session commands
+        connect_session,
+        activate_session_cmd,
+        disconnect_session_cmd,
+        // Frontend compatibility commands
+        validate_session_id,
+        compat::activate_session,
+        compat::disconnect_session,
+        // Timer management
+        update_session_timer,
+        // Database operations
+        database::postgres::test_database_connection,
+        database::postgres::get_db_session_info,
+        database::postgres::save_interview_question,
+        database::postgres::save_interview_answer,
+        database::postgres::get_session_questions,
+        database::postgres::get_session_answers,
+        database::postgres::get_interview_report,
+        database::postgres::finalize_session_duration,
+        database::postgres::mark_session_started,
+        // Window management
+        resize_main_window,
+        move_window_relative,
+        resize_window_scale,
+        show_main_window,
+        hide_main_window,
+        // DPI-aware window management
+        setup_dpi_aware_positioning,
+        get_window_info,
+        get_monitors_info,
+        lock_window_size,
+        ensure_window_visible,
+        // Database diagnostics
+        diagnose_database,
+        test_session_query,
+        diagnostics::export_diagnostics,
+        logging::tail_logs,
+        crash_reporter::get_last_crash_report,
+        metrics::get_performance_metrics,
+        health::get_health_status,
+        telemetry::set_telemetry_enabled,
+        telemetry::get_telemetry_preview,
+        audio_debug::start_audio_debug_dump,
+        net_trace::get_recent_network_activity,
+        net_trace::set_network_tracing_enabled,
+        startup::get_startup_report,
+        privacy::set_local_only_mode,
+        privacy::get_local_only_mode,
+        retention::get_retention_policy,
+        retention::set_retention_policy,
+        retention::get_storage_usage,
+        retention::purge_all_data,
+        audit::get_audit_log,
+        app_consent::grant_app_consent,
+        app_consent::revoke_app_consent,
+        app_consent::get_app_consents,
+        updater::check_for_updates,
+        updater::install_update,
+        scripting::get_scripting_status,
+        scripting::set_scripting_enabled,
+        scripting::reload_scripts,
+        // Permission management
+        permissions::check_permissions,
+        permissions::request_permissions,
+        permissions::initialize_first_run,
+        permissions::reopen_privacy_settings,
+        // Stereo Mix management
+        stereo_mix_manager::check_stereo_mix_enabled,
+        stereo_mix_manager::enable_stereo_mix,
+        stereo_mix_manager::open_recording_devices,
+        stereo_mix_manager::get_stereo_mix_capabilities,
+        stereo_mix_manager::get_stereo_mix_instructions,
+        // Universal Audio Capture commands - DISABLED
+        // detect_universal_audio_capabilities,
+        // start_universal_system_audio_capture,
+        // stop_universal_audio_capture,
+        // get_universal_audio_status,
+        // get_universal_audio_samples,
+        // Stealth Mode commands for secure interview operation
+        stealth_hotkeys::activate_stealth_mode,
+        stealth_hotkeys::deactivate_stealth_mode,
+        stealth_hotkeys::get_stealth_status,
+        stealth_hotkeys::test_stealth_hotkey,
+        // Task Manager stealth commands
+        task_manager_stealth::enable_task_manager_stealth,
+        task_manager_stealth::disable_task_manager_stealth,
+        task_manager_stealth::apply_advanced_stealth,
+        task_manager_stealth::get_task_manager_stealth_status,
+        // Taskbar management commands
+        taskbar_manager::hide_from_taskbar,
+        taskbar_manager::show_in_taskbar,
+        taskbar_manager::toggle_taskbar_visibility,
+        taskbar_manager::get_taskbar_status,
+        taskbar_manager::is_hidden_from_taskbar,
+        // REAL stealth commands for actual process hiding
+        real_stealth::activate_real_stealth,
+        real_stealth::deactivate_real_stealth,
+        real_stealth::get_real_stealth_status,
+        // ADVANCED stealth commands for ultimate process hiding - REMOVED
+        // advanced_stealth::activate_ultimate_stealth,
+        // advanced_stealth::deactivate_ultimate_stealth,
+        // advanced_stealth::get_ultimate_stealth_status,
+        // DLL injection stealth commands for maximum hiding
+        dll_injection_stealth::activate_dll_injection_stealth,
+        dll_injection_stealth::deactivate_dll_injection_stealth,
+        dll_injection_stealth::get_dll_injection_stealth_status,
+    ]
+}
+
+/// Write the TypeScript bindings for every command and event to `path`
+pub fn export_bindings(path: &std::path::Path) -> Result<(), String> {
+    bindings::export(&bindings::builder(commands()), path)
+}
+
 pub fn run() -> Result<()> {
     // Environment variables are now embedded at build time via build.rs
     // We'll use env!() macro to access them, with fallbacks to runtime env::var() for development
     info!("MockMate starting with embedded environment configuration...");
-    
-    // Log which environment variables are available
-    log_environment_status();
+
+    let bindings = bindings::builder(commands());
 
     Builder::default()
-        .invoke_handler(tauri::generate_handler![
-            start_audio_stream,
-            stop_audio_stream,
-            start_system_audio_capture,
-            start_microphone_capture,
-            test_microphone_capture,
-            get_audio_devices,
-            check_audio_status,
-            start_audio_with_config,
-            test_audio_capture,
-            test_native_windows_system_audio_capture,
-            send_manual_question,
-            connect_to_session,
-            close_application,
-            minimize_window,
-            toggle_always_on_top,
-            create_ai_response_window,
-            close_ai_response_window,
-            debug_main_window_dimensions,
-            fix_main_window_invisible_boundary,
-            force_window_exact_content_size,
-            nuclear_fix_webview_padding,
-            resize_ai_response_window,
-            show_ai_response_window,
-            hide_ai_response_window,
-            send_ai_response_data,
-            reset_ai_response_window_size,
-            create_ai_response_window_enhanced_below,
-            reset_ai_response_window_enhanced_below_size,
-            // Real-time transcription commands - DISABLED (using JavaScript SDK now)
-            // realtime_transcription::start_microphone_transcription,
-            // realtime_transcription::start_system_audio_transcription,
-            // realtime_transcription::stop_transcription,
-            // realtime_transcription::get_transcription_status,
-            // realtime_transcription::get_deepgram_config,
-            // realtime_transcription::test_deepgram_connection,
-            // Pluely audio capture commands
-            pluely_audio::start_pluely_system_audio_capture,
-            pluely_audio::stop_pluely_system_audio_capture,
-            pluely_audio::is_pluely_audio_active,
-            pluely_audio::test_pluely_system_audio_capture,
-            // Pluely microphone capture commands
-            pluely_microphone::start_pluely_microphone_capture,
-            pluely_microphone::stop_pluely_microphone_capture,
-            pluely_microphone::is_pluely_microphone_active,
-            // Deepgram Nova-3 streaming transcription commands
-            deepgram_streaming::start_deepgram_streaming,
-            deepgram_streaming::stop_deepgram_streaming,
-            deepgram_streaming::is_deepgram_streaming_active,
-            generate_ai_answer,
-            analyze_screen_content,
-            update_interview_context,
-            get_available_models,
-            get_ai_providers,
-            save_microphone_file,
-            save_system_audio_file,
-            pollinations_generate_answer,
-            pollinations_generate_answer_streaming,
-            pollinations_generate_answer_post_streaming,
-            // AI Analysis commands (accessibility-based)
-            analyze_screen_with_ai,
-            analyze_screen_with_ai_streaming,
-            // Windows Accessibility API commands (Primary text reading solution)
-            accessibility_reader::read_text_from_applications,
-            accessibility_reader::read_text_from_focused_window,
-            accessibility_reader::read_text_from_background_windows,
-            accessibility_reader::read_text_from_current_window,
-            // NEW: Commands for targeting window behind MockMate (interviewer's window)
-            accessibility_reader::read_text_from_window_behind_mockmate,
-            accessibility_reader::capture_previous_focused_window,
-            // Real-time monitoring commands
-            accessibility_reader::start_realtime_monitoring,
-            accessibility_reader::stop_realtime_monitoring,
-            accessibility_reader::get_monitoring_status,
-            // Hybrid approach commands
-            accessibility_reader::extract_text_hybrid_approach,
-            accessibility_reader::update_accessibility_config,
-            // Accessibility-based AI analysis commands
-            analyze_applications_with_ai_streaming,
-            analyze_focused_window_with_ai_streaming,
-            // Screenshot and vision analysis commands
-            capture_screenshot,
-            answer_screenshot_questions_streaming,
-            enhanced_qa_with_vision_streaming,
-            // Session management commands (existing)
-            connect_to_web_session,
-            activate_web_session,
-            get_session_info,
-            handle_protocol_launch,
-            // Temporary token authentication
-            connect_with_temp_token,
-            // New shared database session commands
-            connect_session,
-            activate_session_cmd,
-            disconnect_session_cmd,
-            // Frontend compatibility commands
-            validate_session_id,
-            activate_session,
-            disconnect_session,
-            // Timer management
-            update_session_timer,
-            // Database operations
-            database::postgres::test_database_connection,
-            database::postgres::get_db_session_info,
-            database::postgres::save_interview_question,
-            database::postgres::save_interview_answer,
-            database::postgres::get_session_questions,
-            database::postgres::get_session_answers,
-            database::postgres::get_interview_report,
-            database::postgres::finalize_session_duration,
-            database::postgres::mark_session_started,
-            // Window management
-            resize_main_window,
-            move_window_relative,
-            resize_window_scale,
-            show_main_window,
-            hide_main_window,
-            // DPI-aware window management
-            setup_dpi_aware_positioning,
-            get_window_info,
-            get_monitors_info,
-            lock_window_size,
-            ensure_window_visible,
-            // Database diagnostics
-            diagnose_database,
-            test_session_query,
-            // Permission management
-            permissions::check_permissions,
-            permissions::request_permissions,
-            permissions::initialize_first_run,
-            // Stereo Mix management
-            stereo_mix_manager::check_stereo_mix_enabled,
-            stereo_mix_manager::enable_stereo_mix,
-            stereo_mix_manager::open_recording_devices,
-            stereo_mix_manager::get_stereo_mix_capabilities,
-            stereo_mix_manager::get_stereo_mix_instructions,
-            // Universal Audio Capture commands - DISABLED
-            // detect_universal_audio_capabilities,
-            // start_universal_system_audio_capture,
-            // stop_universal_audio_capture,
-            // get_universal_audio_status,
-            // get_universal_audio_samples,
-            // Stealth Mode commands for secure interview operation
-            stealth_hotkeys::activate_stealth_mode,
-            stealth_hotkeys::deactivate_stealth_mode,
-            stealth_hotkeys::get_stealth_status,
-            stealth_hotkeys::test_stealth_hotkey,
-            // Task Manager stealth commands
-            task_manager_stealth::enable_task_manager_stealth,
-            task_manager_stealth::disable_task_manager_stealth,
-            task_manager_stealth::apply_advanced_stealth,
-            task_manager_stealth::get_task_manager_stealth_status,
-            // Taskbar management commands
-            taskbar_manager::hide_from_taskbar,
-            taskbar_manager::show_in_taskbar,
-            taskbar_manager::toggle_taskbar_visibility,
-            taskbar_manager::get_taskbar_status,
-            taskbar_manager::is_hidden_from_taskbar,
-            // REAL stealth commands for actual process hiding
-            real_stealth::activate_real_stealth,
-            real_stealth::deactivate_real_stealth,
-            real_stealth::get_real_stealth_status,
-            // ADVANCED stealth commands for ultimate process hiding - REMOVED
-            // advanced_stealth::activate_ultimate_stealth,
-            // advanced_stealth::deactivate_ultimate_stealth,
-            // advanced_stealth::get_ultimate_stealth_status,
-            // DLL injection stealth commands for maximum hiding
-            dll_injection_stealth::activate_dll_injection_stealth,
-            dll_injection_stealth::deactivate_dll_injection_stealth,
-            dll_injection_stealth::get_dll_injection_stealth_status
-        ])
+        .invoke_handler(bindings.invoke_handler())
         .manage(AppState::new())
         .setup(|app| {
             info!("MockMate application starting up...");
+            startup::mark_setup_start();
             
-            // Initialize stealth systems
-            stealth_hotkeys::initialize_stealth_hotkeys(app.handle().clone());
-            task_manager_stealth::initialize_task_manager_stealth();
-            info!("🕵️ Stealth systems initialized");
+            // Stream backend log entries to the in-app debug console
+            startup::time_phase("background_services", || {
+                logging::attach_app_handle(app.handle().clone());
+                metrics::start_snapshot_emitter(app.handle().clone());
+                telemetry::init();
+                retention::start_janitor();
+                updater::start_background_checks(app.handle().clone());
+                scripting::init(app.handle().clone());
+                calendar::start_background_sync(app.handle().clone());
+                zoom_captions::init(app.handle().clone());
+                pacing::init(app.handle().clone());
+                appearance::init(app.handle().clone());
+                cancellation::init(app.handle().clone());
+                audio_devices::init(app.handle().clone());
+                push_to_talk::init(app.handle().clone());
+                wake_word::init(app.handle().clone());
+                meeting_detection::init(app.handle().clone());
+                teleprompter::init(app.handle().clone());
+                answer_language::init(app.handle().clone());
+                question_dedup::init(app.handle().clone());
+                live_server::init(app.handle().clone());
+                rest_api::init(app.handle().clone());
+                transcription_preset::init();
+                session_auth::init(app.handle().clone());
+                auth::init(app.handle().clone());
+            });
             
             // Handle command line arguments for protocol URLs
             let args: Vec<String> = std::env::args().collect();
             info!("Command line args: {:?}", args);
             
-            // Check if launched with a mockmate:// URL
-            if let Some(protocol_url) = args.iter().find(|arg| arg.starts_with("mockmate://")) {
+            // Check if launched with a mockmate:// URL; portable copies take a typed session ID instead
+            let protocol_url = args.iter().find(|arg| arg.starts_with("mockmate://"));
+            if protocol_url.is_some() && portable::enabled() {
+                warn!("Ignoring mockmate:// launch in portable mode; enter the session ID instead");
+            }
+            if let Some(protocol_url) = protocol_url.filter(|_| !portable::enabled()) {
                 info!("Detected protocol launch: {}", protocol_url);
                 
+                if protocol_url.starts_with(credits::DEEP_LINK_PREFIX) {
+                    credits::handle_deep_link(app.handle(), protocol_url);
+                }
+                
                 // Parse the protocol URL
                 if let Some(session_part) = protocol_url.strip_prefix("mockmate://session/") {
                     // Extract session ID and any query parameters
@@ -292,10 +630,13 @@ pub fn run() -> Result<()> {
             
             
             // Initialize the real-time accessibility monitoring service
-            accessibility_reader::init_realtime_monitoring(app.handle().clone());
+            startup::time_phase("accessibility_monitoring", || {
+                accessibility_reader::init_realtime_monitoring(app.handle().clone());
+            });
             info!("✅ Real-time accessibility monitoring service initialized");
             
             // Initialize stealth mode systems for secure interview operation
+            let stealth_started = std::time::Instant::now();
             stealth_hotkeys::initialize_stealth_hotkeys(app.handle().clone());
             info!("✅ Stealth hotkey system initialized");
             
@@ -316,12 +657,14 @@ pub fn run() -> Result<()> {
             // Initialize DLL injection stealth system for maximum hiding
             dll_injection_stealth::initialize_dll_injection_stealth();
             info!("💩 DLL INJECTION stealth system initialized for maximum process hiding");
+            startup::record_phase("stealth_systems", stealth_started);
             
             // Get the main window and set capture protection + DPI-aware positioning
+            let main_window_started = std::time::Instant::now();
             match app.get_webview_window("main") {
                 Some(main_window) => {
                     info!("Main window found. Attempting to set capture protection.");
-                    if let Err(e) = set_window_capture_protection(&main_window, true) {
+                    if let Err(e) = set_window_capture_protection(&main_window, true).into_result() {
                         error!("Failed to set window capture protection on startup: {}", e);
                     }
                     
@@ -371,36 +714,41 @@ pub fn run() -> Result<()> {
                 }
             }
             
-            // Create AI response window at startup (hidden by default)
-            if let Err(e) = create_ai_response_window_at_startup(app.handle().clone()) {
-                error!("Failed to create AI response window at startup: {}", e);
-            } else {
-                info!("✅ AI response window created at startup");
-            }
+            startup::record_phase("main_window_protection_dpi", main_window_started);
             
-            // List available audio devices on startup
-            audio::list_all_devices();
+            // Everything below is not needed for the first frame - run it after setup returns
+            // AI response window (hidden by default); send_ai_response_data also creates it on demand
+            let ai_window_handle = app.handle().clone();
+            startup::spawn_deferred("ai_response_window", async move {
+                ensure_ai_response_window(&ai_window_handle).await
+            });
             
-            // Initialize permissions on first run - defer to avoid runtime context issues
-            let _app_handle_perms = app.handle().clone();
-            tauri::async_runtime::spawn(async move {
-                if let Err(e) = permissions::PermissionManager::initialize_permissions_on_first_run() {
-                    error!("Failed to initialize first-run permissions: {}", e);
-                } else {
-                    info!("✅ First-run permission initialization completed");
-                }
+            // List available audio devices (COM enumeration is slow on some drivers)
+            startup::spawn_deferred("audio_device_enumeration", async {
+                tokio::task::spawn_blocking(audio::list_all_devices)
+                    .await
+                    .map_err(|e| e.to_string())
             });
             
-            // Initialize environment variables if needed
-            match get_env_var("DEEPGRAM_API_KEY") {
-                Some(_) => info!("✅ DEEPGRAM_API_KEY loaded successfully"),
-                None => warn!("❌ DEEPGRAM_API_KEY not set in environment - transcription will not work")
-            }
-            match get_env_var("OPENAI_API_KEY") {
-                Some(_) => info!("✅ OPENAI_API_KEY loaded successfully"),
-                None => warn!("❌ OPENAI_API_KEY not set in environment - AI answers will not work")
-            }
+            // Initialize permissions on first run
+            startup::spawn_deferred("first_run_permissions", async {
+                tokio::task::spawn_blocking(|| {
+                    permissions::PermissionManager::initialize_permissions_on_first_run().map_err(|e| e.to_string())
+                })
+                .await
+                .map_err(|e| e.to_string())??;
+                info!("✅ First-run permission initialization completed");
+                Ok(())
+            });
             
+            // Log which environment variables are available
+            startup::spawn_deferred("environment_check", async {
+                tokio::task::spawn_blocking(log_environment_status)
+                    .await
+                    .map_err(|e| e.to_string())
+            });
+            
+            startup::mark_setup_complete();
             Ok(())
         })
         .run(tauri::generate_context!())
@@ -409,13 +757,13 @@ pub fn run() -> Result<()> {
     Ok(())
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Type)]
 struct QuestionPayload {
     session_id: String,
     question: String,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Type)]
 struct GenerateAnswerPayload {
     question: String,
     model: String,
@@ -423,9 +771,12 @@ struct GenerateAnswerPayload {
     company: Option<String>,
     position: Option<String>,
     job_description: Option<String>,
+    /// Where the question came from ("transcript", "manual", "accessibility"), for deduplication
+    #[serde(default)]
+    source: Option<String>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Type)]
 struct AnalyzeScreenPayload {
     screen_content: String,
     model: String,
@@ -434,16 +785,19 @@ struct AnalyzeScreenPayload {
     job_description: Option<String>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Type)]
 struct InterviewContextPayload {
     company: Option<String>,
     position: Option<String>,
     job_description: Option<String>,
+    /// Custom system prompt for the active session; an empty string clears it
+    #[serde(default)]
+    system_prompt: Option<String>,
 }
 
 // Session Management Structures
-#[derive(Serialize, Deserialize, Clone, Debug)]
-struct SessionData {
+#[derive(Serialize, Deserialize, Clone, Debug, schemars::JsonSchema, Type)]
+pub struct SessionData {
     id: String,
     job_title: String,
     job_description: Option<String>,
@@ -455,38 +809,59 @@ struct SessionData {
     desktop_connected: Option<bool>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Type)]
 struct SessionConnectionPayload {
     session_id: String,
     token: String,
     user_id: String,
+    /// Custom system prompt applied to every generation in the session; an empty string clears it
+    #[serde(default)]
+    system_prompt: Option<String>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Type)]
 struct SessionActivationResponse {
     success: bool,
     message: String,
     session: Option<SessionData>,
     remaining_credits: Option<u32>,
+    /// Signed by the backend; kept for offline grace
+    #[serde(default)]
+    entitlement: Option<entitlement::SignedEntitlement>,
+    /// Let in from the cached entitlement because the backend could not be reached
+    #[serde(default)]
+    offline: bool,
 }
 
 // Temporary token authentication structures
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Type)]
 struct TempTokenAuthPayload {
     session_id: String,
     temp_token: String,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Type)]
 struct TempTokenAuthResponse {
     success: bool,
     message: String,
     user_id: Option<String>,
     session: Option<SessionData>,
     remaining_credits: Option<u32>,
+    /// When the temp token stops working (RFC 3339), if the backend reports it
+    #[serde(default)]
+    expires_at: Option<String>,
+    /// Seconds the temp token stays valid, if the backend reports it
+    #[serde(default)]
+    expires_in: Option<u64>,
+    /// Signed by the backend; kept for offline grace
+    #[serde(default)]
+    entitlement: Option<entitlement::SignedEntitlement>,
+    /// Let in from the cached entitlement because the backend could not be reached
+    #[serde(default)]
+    offline: bool,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Type)]
 struct AudioConfigPayload {
     sample_rate: u32,
     channels: u16,
@@ -527,564 +902,668 @@ impl AppState {
         }
         Ok(())
     }
+
+    /// Run a free-form prompt on the given provider ("openai" or "pollinations") and model
+    async fn complete(&self, provider: &str, model: &str, system_prompt: &str, user_prompt: &str) -> Result<String, String> {
+        match AIProvider::from_str(provider).unwrap_or(AIProvider::Pollinations) {
+            AIProvider::OpenAI => {
+                self.ensure_openai_client()?;
+                let client = self.openai_client.lock().clone().ok_or("OpenAI client unavailable")?;
+                let model = openai::OpenAIModel::from_string(model).map_err(|e| format!("Invalid OpenAI model: {}", e))?;
+                client.complete(system_prompt, user_prompt, model).await.map_err(|e| e.to_string())
+            }
+            AIProvider::Pollinations => {
+                self.ensure_pollinations_client()?;
+                let client = self.pollinations_client.lock().clone().ok_or("Pollinations client unavailable")?;
+                let model = pollinations::PollinationsModel::from_string(model)
+                    .map_err(|e| format!("Invalid Pollinations model: {}", e))?;
+                client.complete(system_prompt, user_prompt, model).await.map_err(|e| e.to_string())
+            }
+        }
+    }
 }
 
 #[tauri::command]
-fn start_audio_stream() -> Result<String, String> {
-    info!("Starting audio stream...");
-    match audio::capture_audio() {
-        Ok(_) => Ok("Audio capture started successfully".to_string()),
-        Err(e) => {
-            error!("Failed to start audio capture: {}", e);
-            Err(format!("Failed to start audio capture: {}", e))
+#[specta::specta]
+fn start_audio_stream() -> Response<String> {
+    envelope::respond_sync("start_audio_stream", || {
+        info!("Starting audio stream...");
+        match audio::capture_audio() {
+            Ok(_) => Ok("Audio capture started successfully".to_string()),
+            Err(e) => {
+                error!("Failed to start audio capture: {}", e);
+                Err(format!("Failed to start audio capture: {}", e).into())
+            }
         }
-    }
+    })
 }
 
 #[tauri::command]
-fn stop_audio_stream() -> Result<String, String> {
-    info!("Stopping audio stream...");
-    match audio::stop_capture() {
-        Ok(_) => Ok("Audio capture stopped successfully".to_string()),
-        Err(e) => {
-            error!("Failed to stop audio capture: {}", e);
-            Err(format!("Failed to stop audio capture: {}", e))
+#[specta::specta]
+fn stop_audio_stream() -> Response<String> {
+    envelope::respond_sync("stop_audio_stream", || {
+        info!("Stopping audio stream...");
+        match audio::stop_capture() {
+            Ok(_) => Ok("Audio capture stopped successfully".to_string()),
+            Err(e) => {
+                error!("Failed to stop audio capture: {}", e);
+                Err(format!("Failed to stop audio capture: {}", e).into())
+            }
         }
-    }
+    })
 }
 
 #[tauri::command]
-fn send_manual_question(payload: QuestionPayload) {
-    info!("Sending manual question: {}", payload.question);
-    websocket::send_question(payload);
+#[specta::specta]
+fn send_manual_question(payload: QuestionPayload) -> Response<()> {
+    envelope::respond_sync("send_manual_question", || {
+        info!("Sending manual question: {}", payload.question);
+        websocket::send_question(payload);
+        Ok(())
+    })
 }
 
 #[tauri::command]
-fn connect_to_session(session_id: String) {
-    info!("Connecting to session: {}", session_id);
-    websocket::connect(session_id);
+#[specta::specta]
+fn connect_to_session(session_id: String) -> Response<()> {
+    envelope::respond_sync("connect_to_session", || {
+        info!("Connecting to session: {}", session_id);
+        websocket::connect(session_id);
+        Ok(())
+    })
 }
 
 #[tauri::command]
-fn close_application(app_handle: AppHandle) {
-    info!("Closing application...");
-    app_handle.exit(0);
+#[specta::specta]
+fn close_application(app_handle: AppHandle) -> Response<()> {
+    envelope::respond_sync("close_application", || {
+        info!("Closing application...");
+        app_handle.exit(0);
+        Ok(())
+    })
 }
 
 #[tauri::command]
-fn minimize_window(window: Window) {
-    info!("Minimizing window...");
-    if let Err(e) = window.minimize() {
-        error!("Failed to minimize window: {}", e);
-    }
+#[specta::specta]
+fn minimize_window(window: Window) -> Response<()> {
+    envelope::respond_sync("minimize_window", || {
+        info!("Minimizing window...");
+        if let Err(e) = window.minimize() {
+            error!("Failed to minimize window: {}", e);
+        }
+        Ok(())
+    })
 }
 
 #[tauri::command]
-fn toggle_always_on_top(window: Window) -> Result<bool, String> {
-    info!("Toggling always on top...");
-    let is_always_on_top = window.is_always_on_top().map_err(|e| e.to_string())?;
-    window.set_always_on_top(!is_always_on_top).map_err(|e| e.to_string())?;
-    Ok(!is_always_on_top)
+#[specta::specta]
+fn toggle_always_on_top(window: Window) -> Response<bool> {
+    envelope::respond_sync("toggle_always_on_top", || {
+        info!("Toggling always on top...");
+        let is_always_on_top = window.is_always_on_top().map_err(|e| e.to_string())?;
+        window.set_always_on_top(!is_always_on_top).map_err(|e| e.to_string())?;
+        Ok(!is_always_on_top)
+    })
 }
 
 #[tauri::command]
+#[specta::specta]
 async fn generate_ai_answer(
     payload: GenerateAnswerPayload,
-    state: State<'_, AppState>
-) -> Result<String, String> {
-    info!("Generating AI answer for question: {}", payload.question);
-    
-    // Determine which provider to use
-    let provider = AIProvider::from_str(&payload.provider)
-        .unwrap_or(AIProvider::OpenAI); // Default to OpenAI if invalid
-    
-    let mut context = {
-        let context_guard = state.interview_context.lock();
-        context_guard.clone()
-    };
-    
-    // Update context with payload data if provided
-    if let Some(company) = payload.company {
-        context.company = Some(company);
-    }
-    if let Some(position) = payload.position {
-        context.position = Some(position);
-    }
-    if let Some(job_description) = payload.job_description {
-        context.job_description = Some(job_description);
-    }
-    
-    match provider {
-        AIProvider::OpenAI => {
-            info!("Using OpenAI provider");
-            state.ensure_openai_client()?;
+    state: State<'_, AppState>,
+    request_id: Option<String>,
+) -> Response<String> {
+    cancellation::run("generate_ai_answer", request_id, async move {
+        info!("Generating AI answer for question: {}", payload.question);
+        // The same question from another source gets the first arrival's answer
+        let mut ticket = match question_dedup::claim(&payload.question, payload.source.as_deref().unwrap_or("unknown")) {
+            question_dedup::Claim::New(ticket) => ticket,
+            question_dedup::Claim::Duplicate(duplicate) => return duplicate.answer().await,
+        };
+        let mut payload = payload;
+        payload.question = answer_language::prepare_question(&state, &payload.provider, &payload.model, &payload.question).await;
+    
+        // Determine which provider to use
+        let provider = AIProvider::from_str(&payload.provider)
+            .unwrap_or(AIProvider::OpenAI); // Default to OpenAI if invalid
+    
+        let context = ContextBuilder::new(&state)
+            .overrides(payload.company, payload.position, payload.job_description)
+            .provider(&payload.provider)
+            .question(&payload.question)
+            .build();
+    
+        match provider {
+            AIProvider::OpenAI => {
+                info!("Using OpenAI provider");
+                state.ensure_openai_client()?;
             
-            let client = {
-                let client_guard = state.openai_client.lock();
-                client_guard.as_ref().unwrap().clone()
-            };
+                let client = {
+                    let client_guard = state.openai_client.lock();
+                    client_guard.as_ref().unwrap().clone()
+                };
             
-            let model = openai::OpenAIModel::from_string(&payload.model)
-                .map_err(|e| format!("Invalid OpenAI model: {}", e))?;
+                let model = openai::OpenAIModel::from_string(&payload.model)
+                    .map_err(|e| format!("Invalid OpenAI model: {}", e))?;
             
-            client.generate_answer(&payload.question, &context, model)
-                .await
-                .map_err(|e| e.to_string())
-        },
-        AIProvider::Pollinations => {
-            info!("Using Pollinations provider");
-            state.ensure_pollinations_client()?;
+                client.generate_answer(&payload.question, &context, model)
+                    .await
+                    .map_err(|e| AppError::from(e.to_string()))
+                    .inspect(|answer| {
+                        context_builder::record_exchange(&payload.question, answer);
+                        ticket.finish(answer);
+                    })
+            },
+            AIProvider::Pollinations => {
+                info!("Using Pollinations provider");
+                state.ensure_pollinations_client()?;
             
-            let client = {
-                let client_guard = state.pollinations_client.lock();
-                client_guard.as_ref().unwrap().clone()
-            };
+                let client = {
+                    let client_guard = state.pollinations_client.lock();
+                    client_guard.as_ref().unwrap().clone()
+                };
             
-            let model = pollinations::PollinationsModel::from_string(&payload.model)
-                .map_err(|e| format!("Invalid Pollinations model: {}", e))?;
+                let model = pollinations::PollinationsModel::from_string(&payload.model)
+                    .map_err(|e| format!("Invalid Pollinations model: {}", e))?;
             
-            client.generate_answer(&payload.question, &context, model)
-                .await
-                .map_err(|e| e.to_string())
+                client.generate_answer(&payload.question, &context, model)
+                    .await
+                    .map_err(|e| AppError::from(e.to_string()))
+                    .inspect(|answer| {
+                        context_builder::record_exchange(&payload.question, answer);
+                        ticket.finish(answer);
+                    })
+            }
         }
-    }
+    })
+    .await
 }
 
 // New command: generate answer via Pollinations using backend (adds required headers)
 #[tauri::command]
+#[specta::specta]
 async fn pollinations_generate_answer(
     payload: GenerateAnswerPayload,
-    state: State<'_, AppState>
-) -> Result<String, String> {
-    if payload.provider.to_lowercase() != "pollinations" {
-        return Err("Provider must be 'pollinations' for this command".to_string());
-    }
-    info!("Generating Pollinations answer (backend) for: {}", payload.question);
-    state.ensure_pollinations_client()?;
-
-    let client = {
-        let client_guard = state.pollinations_client.lock();
-        client_guard.as_ref().unwrap().clone()
-    };
-
-    let mut context = {
-        let context_guard = state.interview_context.lock();
-        context_guard.clone()
-    };
-    if let Some(company) = payload.company { context.company = Some(company); }
-    if let Some(position) = payload.position { context.position = Some(position); }
-    if let Some(job_description) = payload.job_description { context.job_description = Some(job_description); }
+    state: State<'_, AppState>,
+    request_id: Option<String>,
+) -> Response<String> {
+    cancellation::run("pollinations_generate_answer", request_id, async move {
+        guard::check(&[Require::Cloud("Pollinations")])?;
+        if payload.provider.to_lowercase() != "pollinations" {
+            return Err("Provider must be 'pollinations' for this command".into());
+        }
+        info!("Generating Pollinations answer (backend) for: {}", payload.question);
+        // The same question from another source gets the first arrival's answer
+        let mut ticket = match question_dedup::claim(&payload.question, payload.source.as_deref().unwrap_or("unknown")) {
+            question_dedup::Claim::New(ticket) => ticket,
+            question_dedup::Claim::Duplicate(duplicate) => return duplicate.answer().await,
+        };
+        let mut payload = payload;
+        payload.question = answer_language::prepare_question(&state, &payload.provider, &payload.model, &payload.question).await;
+        state.ensure_pollinations_client()?;
 
-    let model = pollinations::PollinationsModel::from_string(&payload.model)
-        .map_err(|e| format!("Invalid Pollinations model: {}", e))?;
+        let client = {
+            let client_guard = state.pollinations_client.lock();
+            client_guard.as_ref().unwrap().clone()
+        };
 
-    client.generate_answer(&payload.question, &context, model)
-        .await
-        .map_err(|e| e.to_string())
+        let context = ContextBuilder::new(&state)
+            .overrides(payload.company, payload.position, payload.job_description)
+            .provider(&payload.provider)
+            .question(&payload.question)
+            .build();
+
+        let model = pollinations::PollinationsModel::from_string(&payload.model)
+            .map_err(|e| format!("Invalid Pollinations model: {}", e))?;
+
+        client.generate_answer(&payload.question, &context, model)
+            .await
+            .map_err(|e| AppError::from(e.to_string()))
+            .inspect(|answer| {
+                context_builder::record_exchange(&payload.question, answer);
+                ticket.finish(answer);
+            })
+    })
+    .await
 }
 
 // New command: Generate streaming answer via Pollinations GET endpoint
 #[tauri::command]
+#[specta::specta]
 async fn pollinations_generate_answer_streaming(
     payload: GenerateAnswerPayload,
     state: State<'_, AppState>,
     app_handle: AppHandle,
-) -> Result<String, String> {
-    if payload.provider.to_lowercase() != "pollinations" {
-        return Err("Provider must be 'pollinations' for this command".to_string());
-    }
-    info!("Generating Pollinations streaming answer (GET) for: {}", payload.question);
-    state.ensure_pollinations_client()?;
+    request_id: Option<String>,
+) -> Response<String> {
+    cancellation::run("pollinations_generate_answer_streaming", request_id, async move {
+        guard::check(&[Require::Cloud("Pollinations")])?;
+        if payload.provider.to_lowercase() != "pollinations" {
+            return Err("Provider must be 'pollinations' for this command".into());
+        }
+        info!("Generating Pollinations streaming answer (GET) for: {}", payload.question);
+        telemetry::record_feature("ai_answer_streaming");
+        // The same question from another source gets the first arrival's answer
+        let mut ticket = match question_dedup::claim(&payload.question, payload.source.as_deref().unwrap_or("unknown")) {
+            question_dedup::Claim::New(ticket) => ticket,
+            question_dedup::Claim::Duplicate(duplicate) => {
+                let answer = duplicate.answer().await?;
+                show_reused_answer(&app_handle, &answer).await;
+                return Ok(answer);
+            }
+        };
+        let mut payload = payload;
+        payload.question = answer_language::prepare_question(&state, &payload.provider, &payload.model, &payload.question).await;
+        state.ensure_pollinations_client()?;
 
-    let client = {
-        let client_guard = state.pollinations_client.lock();
-        client_guard.as_ref().unwrap().clone()
-    };
+        let client = {
+            let client_guard = state.pollinations_client.lock();
+            client_guard.as_ref().unwrap().clone()
+        };
 
-    let mut context = {
-        let context_guard = state.interview_context.lock();
-        context_guard.clone()
-    };
-    if let Some(company) = payload.company { context.company = Some(company); }
-    if let Some(position) = payload.position { context.position = Some(position); }
-    if let Some(job_description) = payload.job_description { context.job_description = Some(job_description); }
-
-    let model = pollinations::PollinationsModel::from_string(&payload.model)
-        .map_err(|e| format!("Invalid Pollinations model: {}", e))?;
-    let model_clone = model.clone(); // Clone for fallback use
-
-    // Immediately show the AI response window for faster response (no await)
-    let app_handle_show = app_handle.clone();
-    tokio::spawn(async move {
-        if let Err(e) = show_ai_response_window_async(app_handle_show).await {
-            warn!("Failed to show AI response window: {}", e);
+        let context = ContextBuilder::new(&state)
+            .overrides(payload.company, payload.position, payload.job_description)
+            .provider(&payload.provider)
+            .question(&payload.question)
+            .build();
+
+        let model = pollinations::PollinationsModel::from_string(&payload.model)
+            .map_err(|e| format!("Invalid Pollinations model: {}", e))?;
+        let model_clone = model.clone(); // Clone for fallback use
+
+        // Coding problems get the structured solution prompt instead of a spoken-style answer
+        if coding_challenge::detect_enabled(&payload.question).is_some() {
+            info!("🧩 Question looks like a coding problem, using the structured solution prompt");
+            let _ = app_handle.emit(events::AI_STREAM_START, ());
+            let solution = coding_challenge::solve(&state, &payload.provider, &payload.model, &payload.question, None, "transcript").await?;
+            let answer = show_structured_answer(&app_handle, events::CODING_SOLUTION, &solution, solution.to_markdown()).await;
+            ticket.finish(&answer);
+            return Ok(answer);
         }
-    });
 
-    // Initialize streaming state and timing
-    let stream_start_time = std::time::Instant::now();
-    info!("⚡ SPEED OPTIMIZED: Starting progressive streaming for AI response window");
-    let _ = app_handle.emit("ai-stream-start", ());
-    
-    // Send immediate ready signal to AI response window to prepare for tokens
-    let ready_data = AiResponseData {
-        message_type: "stream-token".to_string(),
-        text: Some("".to_string()), // Empty token to initialize
-        error: None,
-    };
-    let app_handle_ready = app_handle.clone();
-    tokio::spawn(async move {
-        if let Err(e) = send_ai_response_data(app_handle_ready, ready_data).await {
-            warn!("Failed to send ready signal to AI response window: {}", e);
+        // Behavioral questions are answered from the user's own stories when one fits
+        if experience_bank::is_behavioral(&payload.question) {
+            if let Some(star) = experience_bank::build_star_answer(&state, &payload.provider, &payload.model, &payload.question, None).await? {
+                info!("⭐ Behavioral question answered from story '{}'", star.story_title);
+                let _ = app_handle.emit(events::AI_STREAM_START, ());
+                let answer = show_structured_answer(&app_handle, events::STAR_ANSWER, &star, star.answer.clone()).await;
+                ticket.finish(&answer);
+                return Ok(answer);
+            }
         }
-    });
 
-    // Stream the response with callback to update UI progressively
-    let app_handle_clone = app_handle.clone();
-    let result = client.generate_answer_streaming(
-        &payload.question, 
-        &context, 
-        model_clone.clone(),
-        move |token: &str| {
-            // Optimize: reduce logging for better streaming performance
-            if token.len() <= 20 {
-                info!("📝 Token: '{}' ({})", token.chars().take(10).collect::<String>(), token.len());
-            } else {
-                info!("📝 Token: {}chars", token.len());
+        // Immediately show the AI response window for faster response (no await)
+        let app_handle_show = app_handle.clone();
+        tokio::spawn(async move {
+            if let Err(e) = show_ai_response_window_async(app_handle_show).await {
+                warn!("Failed to show AI response window: {}", e);
             }
-            
-            // Emit token event for progressive display with optimized payload
-            let token_payload = serde_json::json!({
-                "text": token,
-                "token": token,
-                "timestamp": std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_millis()
-            });
-            if let Err(e) = app_handle_clone.emit("ai-stream-token", &token_payload) {
-                warn!("Failed to emit streaming token: {}", e);
+        });
+
+        // Initialize streaming state and timing
+        let stream_start_time = std::time::Instant::now();
+        info!("⚡ SPEED OPTIMIZED: Starting progressive streaming for AI response window");
+        let _ = app_handle.emit(events::AI_STREAM_START, ());
+    
+        // Send immediate ready signal to AI response window to prepare for tokens
+        let ready_data = AiResponseData {
+            message_type: "stream-token".to_string(),
+            text: Some("".to_string()), // Empty token to initialize
+            error: None,
+        };
+        let app_handle_ready = app_handle.clone();
+        tokio::spawn(async move {
+            if let Err(e) = send_ai_response_data(app_handle_ready, ready_data).await.into_result() {
+                warn!("Failed to send ready signal to AI response window: {}", e);
             }
-            
-            // ALSO send token to AI response window for display (IMMEDIATE for first tokens)
-            let ai_response_data = AiResponseData {
-                message_type: "stream-token".to_string(),
-                text: Some(token.to_string()),
-                error: None,
-            };
-            let app_handle_for_ai_window = app_handle_clone.clone();
-            
-            // Use immediate task spawning with high priority for first tokens
-            tokio::task::spawn_blocking(move || {
-                // Use blocking spawn for immediate execution
-                tokio::runtime::Handle::current().block_on(async move {
-                    if let Err(e) = send_ai_response_data(app_handle_for_ai_window, ai_response_data).await {
-                        warn!("Failed to send streaming token to AI response window: {}", e);
-                    }
-                });
-            });
+        });
+
+        // System design questions stream as separate overview/diagram/components/talking-point sections
+        if system_design::detect_enabled(&payload.question) {
+            info!("🏗️ Question looks like system design, streaming sectioned answer");
+            let answer = system_design::stream_answer(&app_handle, &client, &context, model_clone.clone(), &payload.question).await?;
+            let answer = show_structured_answer(&app_handle, events::SYSTEM_DESIGN_ANSWER, &answer, answer.to_markdown()).await;
+            ticket.finish(&answer);
+            return Ok(answer);
         }
-    ).await;
-    
-    // If Pollinations fails, try to fallback to non-streaming fallback or provide helpful error
-    let final_result = match result {
-        Ok(response) => Ok(response),
-        Err(pollinations_error) => {
-            let error_str = pollinations_error.to_string();
-            let is_infrastructure_issue = error_str.contains("HTTP 530") || 
-                error_str.contains("infrastructure issues") ||
-                error_str.contains("health check failed");
+
+        // Stream the response with callback to update UI progressively
+        let tokens = ui_token_coalescer(&app_handle, true);
+        let token_sink = tokens.clone();
+        let mut first_token_seen = false;
+        let result = client.generate_answer_streaming(
+            &payload.question, 
+            &context, 
+            model_clone.clone(),
+            move |token: &str| {
+                if !first_token_seen {
+                    first_token_seen = true;
+                    metrics::record(metrics::QUESTION_TO_FIRST_TOKEN, stream_start_time.elapsed());
+                }
+                token_sink.push(token);
+            }
+        ).await;
+        tokens.finish().await;
+    
+        // If Pollinations fails, try to fallback to non-streaming fallback or provide helpful error
+        let final_result = match result {
+            Ok(response) => Ok(response),
+            Err(pollinations_error) => {
+                let error_str = pollinations_error.to_string();
+                let is_infrastructure_issue = error_str.contains("HTTP 530") || 
+                    error_str.contains("infrastructure issues") ||
+                    error_str.contains("health check failed");
             
-            warn!("Pollinations streaming failed: {} (infrastructure issue: {})", pollinations_error, is_infrastructure_issue);
+                warn!("Pollinations streaming failed: {} (infrastructure issue: {})", pollinations_error, is_infrastructure_issue);
+                telemetry::record_error(if is_infrastructure_issue { "ai_provider_unavailable" } else { "ai_provider_error" });
             
-            // Send helpful error message to UI
-            let error_message = if is_infrastructure_issue {
-                format!("❌ Pollinations service is temporarily unavailable due to infrastructure issues (HTTP 530 errors). This usually resolves within a few minutes.\n\n🔄 Suggestions:\n• Try again in 2-3 minutes\n• Switch to OpenAI in settings\n• Use the manual input mode\n\nTechnical details: {}", error_str)
-            } else {
-                format!("❌ Pollinations streaming failed: {}\n\n💡 Try switching to OpenAI or check your internet connection.", error_str)
-            };
+                // Send helpful error message to UI
+                let error_message = if is_infrastructure_issue {
+                    format!("❌ Pollinations service is temporarily unavailable due to infrastructure issues (HTTP 530 errors). This usually resolves within a few minutes.\n\n🔄 Suggestions:\n• Try again in 2-3 minutes\n• Switch to OpenAI in settings\n• Use the manual input mode\n\nTechnical details: {}", error_str)
+                } else {
+                    format!("❌ Pollinations streaming failed: {}\n\n💡 Try switching to OpenAI or check your internet connection.", error_str)
+                };
             
-            Err(error_message)
-        }
-    };
+                Err(error_message)
+            }
+        };
 
-    let elapsed_time = stream_start_time.elapsed();
+        let elapsed_time = stream_start_time.elapsed();
     
-    // Handle streaming result and send to UI
-    match final_result {
-        Ok(full_response) => {
-            info!("✅ Pollinations streaming completed successfully in {:.2?}. Response length: {}", elapsed_time, full_response.len());
+        // Handle streaming result and send to UI
+        match final_result {
+            Ok(full_response) => {
+                info!("✅ Pollinations streaming completed successfully in {:.2?}. Response length: {}", elapsed_time, full_response.len());
             
-            // Check if response is empty and try fallback if needed
-            if full_response.trim().is_empty() {
-                warn!("Streaming returned empty response, trying non-streaming fallback...");
-                match client.generate_answer(&payload.question, &context, model_clone).await {
-                    Ok(fallback_response) => {
-                        info!("✅ Non-streaming fallback successful");
-                        let data = AiResponseData {
-                            message_type: "complete".to_string(),
-                            text: Some(fallback_response.clone()),
-                            error: None,
-                        };
-                        let app_handle_fallback_clone = app_handle.clone();
-                        tokio::spawn(async move {
-                            if let Err(e) = send_ai_response_data(app_handle_fallback_clone, data).await {
-                                error!("Failed to send fallback response: {}", e);
-                            }
-                        });
-                        let _ = app_handle.emit("ai-stream-complete", fallback_response.clone());
-                        return Ok(fallback_response);
-                    },
-                    Err(e) => {
-                        error!("❌ Non-streaming fallback also failed: {}", e);
-                        let error_msg = format!("Both streaming and fallback failed: {}", e);
-                        let data = AiResponseData {
-                            message_type: "error".to_string(),
-                            text: None,
-                            error: Some(error_msg.clone()),
-                        };
-                        let app_handle_error_clone = app_handle.clone();
-                        tokio::spawn(async move {
-                            if let Err(e) = send_ai_response_data(app_handle_error_clone, data).await {
-                                error!("Failed to send error: {}", e);
-                            }
-                        });
-                        return Err(error_msg);
+                // Check if response is empty and try fallback if needed
+                if full_response.trim().is_empty() {
+                    warn!("Streaming returned empty response, trying non-streaming fallback...");
+                    match client.generate_answer(&payload.question, &context, model_clone).await {
+                        Ok(fallback_response) => {
+                            info!("✅ Non-streaming fallback successful");
+                            let data = AiResponseData {
+                                message_type: "complete".to_string(),
+                                text: Some(fallback_response.clone()),
+                                error: None,
+                            };
+                            let app_handle_fallback_clone = app_handle.clone();
+                            tokio::spawn(async move {
+                                if let Err(e) = send_ai_response_data(app_handle_fallback_clone, data).await.into_result() {
+                                    error!("Failed to send fallback response: {}", e);
+                                }
+                            });
+                            let _ = app_handle.emit(events::AI_STREAM_COMPLETE, fallback_response.clone());
+                            scripting::dispatch(scripting::ScriptEvent::AnswerComplete, serde_json::json!({ "answer": &fallback_response }));
+                            context_builder::record_exchange(&payload.question, &fallback_response);
+                            ticket.finish(&fallback_response);
+                            return Ok(fallback_response);
+                        },
+                        Err(e) => {
+                            error!("❌ Non-streaming fallback also failed: {}", e);
+                            let error_msg = format!("Both streaming and fallback failed: {}", e);
+                            let data = AiResponseData {
+                                message_type: "error".to_string(),
+                                text: None,
+                                error: Some(error_msg.clone()),
+                            };
+                            let app_handle_error_clone = app_handle.clone();
+                            tokio::spawn(async move {
+                                if let Err(e) = send_ai_response_data(app_handle_error_clone, data).await.into_result() {
+                                    error!("Failed to send error: {}", e);
+                                }
+                            });
+                            return Err(error_msg.into());
+                        }
                     }
                 }
-            }
             
-            // Send successful completion signal
-            let data = AiResponseData {
-                message_type: "complete".to_string(),
-                text: Some(full_response.clone()),
-                error: None,
-            };
-            let app_handle_complete = app_handle.clone();
-            tokio::spawn(async move {
-                if let Err(e) = send_ai_response_data(app_handle_complete, data).await {
-                    error!("Failed to send completion signal to UI: {}", e);
-                }
-            });
+                // Send successful completion signal
+                let data = AiResponseData {
+                    message_type: "complete".to_string(),
+                    text: Some(full_response.clone()),
+                    error: None,
+                };
+                let app_handle_complete = app_handle.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = send_ai_response_data(app_handle_complete, data).await.into_result() {
+                        error!("Failed to send completion signal to UI: {}", e);
+                    }
+                });
             
-            let _ = app_handle.emit("ai-stream-complete", full_response.clone());
-            Ok(full_response)
-        },
-        Err(error_message) => {
-            error!("❌ Pollinations streaming failed after {:.2?}: {}", elapsed_time, error_message);
+                let _ = app_handle.emit(events::AI_STREAM_COMPLETE, full_response.clone());
+                scripting::dispatch(scripting::ScriptEvent::AnswerComplete, serde_json::json!({ "answer": &full_response }));
+                context_builder::record_exchange(&payload.question, &full_response);
+                ticket.finish(&full_response);
+                Ok(full_response)
+            },
+            Err(error_message) => {
+                error!("❌ Pollinations streaming failed after {:.2?}: {}", elapsed_time, error_message);
             
-            // Send error details to the UI with structured error data
-            let data = AiResponseData {
-                message_type: "error".to_string(),
-                text: None,
-                error: Some(error_message.clone()),
-            };
-            let app_handle_error = app_handle.clone();
-            tokio::spawn(async move {
-                if let Err(e) = send_ai_response_data(app_handle_error, data).await {
-                    error!("Failed to send error signal to UI: {}", e);
-                }
-            });
+                // Send error details to the UI with structured error data
+                let data = AiResponseData {
+                    message_type: "error".to_string(),
+                    text: None,
+                    error: Some(error_message.clone()),
+                };
+                let app_handle_error = app_handle.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = send_ai_response_data(app_handle_error, data).await.into_result() {
+                        error!("Failed to send error signal to UI: {}", e);
+                    }
+                });
             
-            let _ = app_handle.emit("ai-stream-error", error_message.clone());
-            Err(error_message)
+                let _ = app_handle.emit(events::AI_STREAM_ERROR, error_message.clone());
+                Err(error_message.into())
+            }
         }
-    }
+    })
+    .await
 }
 
 // New command: Generate streaming answer via Pollinations POST endpoint
 #[tauri::command]
+#[specta::specta]
 async fn pollinations_generate_answer_post_streaming(
     payload: GenerateAnswerPayload,
     state: State<'_, AppState>,
     app_handle: AppHandle,
-) -> Result<String, String> {
-    if payload.provider.to_lowercase() != "pollinations" {
-        return Err("Provider must be 'pollinations' for this command".to_string());
-    }
-    info!("Generating Pollinations streaming answer (POST) for: {}", payload.question);
-    state.ensure_pollinations_client()?;
+    request_id: Option<String>,
+) -> Response<String> {
+    cancellation::run("pollinations_generate_answer_post_streaming", request_id, async move {
+        guard::check(&[Require::Cloud("Pollinations")])?;
+        if payload.provider.to_lowercase() != "pollinations" {
+            return Err("Provider must be 'pollinations' for this command".into());
+        }
+        info!("Generating Pollinations streaming answer (POST) for: {}", payload.question);
+        telemetry::record_feature("ai_answer_post_streaming");
+        // The same question from another source gets the first arrival's answer
+        let mut ticket = match question_dedup::claim(&payload.question, payload.source.as_deref().unwrap_or("unknown")) {
+            question_dedup::Claim::New(ticket) => ticket,
+            question_dedup::Claim::Duplicate(duplicate) => {
+                let answer = duplicate.answer().await?;
+                show_reused_answer(&app_handle, &answer).await;
+                return Ok(answer);
+            }
+        };
+        let mut payload = payload;
+        payload.question = answer_language::prepare_question(&state, &payload.provider, &payload.model, &payload.question).await;
+        state.ensure_pollinations_client()?;
 
-    let client = {
-        let client_guard = state.pollinations_client.lock();
-        client_guard.as_ref().unwrap().clone()
-    };
+        let client = {
+            let client_guard = state.pollinations_client.lock();
+            client_guard.as_ref().unwrap().clone()
+        };
 
-    let mut context = {
-        let context_guard = state.interview_context.lock();
-        context_guard.clone()
-    };
-    if let Some(company) = payload.company { context.company = Some(company); }
-    if let Some(position) = payload.position { context.position = Some(position); }
-    if let Some(job_description) = payload.job_description { context.job_description = Some(job_description); }
+        let context = ContextBuilder::new(&state)
+            .overrides(payload.company, payload.position, payload.job_description)
+            .provider(&payload.provider)
+            .question(&payload.question)
+            .build();
 
-    let model = pollinations::PollinationsModel::from_string(&payload.model)
-        .map_err(|e| format!("Invalid Pollinations model: {}", e))?;
+        let model = pollinations::PollinationsModel::from_string(&payload.model)
+            .map_err(|e| format!("Invalid Pollinations model: {}", e))?;
 
-    // Immediately show the AI response window for faster response (no await)
-    let app_handle_show = app_handle.clone();
-    tokio::spawn(async move {
-        if let Err(e) = show_ai_response_window_async(app_handle_show).await {
-            warn!("Failed to show AI response window: {}", e);
-        }
-    });
+        // Immediately show the AI response window for faster response (no await)
+        let app_handle_show = app_handle.clone();
+        tokio::spawn(async move {
+            if let Err(e) = show_ai_response_window_async(app_handle_show).await {
+                warn!("Failed to show AI response window: {}", e);
+            }
+        });
 
-    // Initialize streaming state and timing
-    let stream_start_time = std::time::Instant::now();
-    info!("⚡ SPEED OPTIMIZED: Starting progressive streaming (POST) for AI response window");
-    let _ = app_handle.emit("ai-stream-start", ());
+        // Initialize streaming state and timing
+        let stream_start_time = std::time::Instant::now();
+        info!("⚡ SPEED OPTIMIZED: Starting progressive streaming (POST) for AI response window");
+        let _ = app_handle.emit(events::AI_STREAM_START, ());
     
-    // Send immediate status to show window is ready
-    let ready_data = AiResponseData {
-        message_type: "stream-token".to_string(),
-        text: Some("[READY] AI response window ready, generating answer...".to_string()),
-        error: None,
-    };
-    let app_handle_ready = app_handle.clone();
-    tokio::spawn(async move {
-        if let Err(e) = send_ai_response_data(app_handle_ready, ready_data).await {
-            warn!("Failed to send ready signal: {}", e);
-        }
-    });
+        // Send immediate status to show window is ready
+        let ready_data = AiResponseData {
+            message_type: "stream-token".to_string(),
+            text: Some("[READY] AI response window ready, generating answer...".to_string()),
+            error: None,
+        };
+        let app_handle_ready = app_handle.clone();
+        tokio::spawn(async move {
+            if let Err(e) = send_ai_response_data(app_handle_ready, ready_data).await.into_result() {
+                warn!("Failed to send ready signal: {}", e);
+            }
+        });
 
-    // Stream the response with callback to update UI progressively
-    let app_handle_clone = app_handle.clone();
-    let model_clone = model.clone(); // Clone model to avoid ownership issues
-    let result = client.generate_answer_post_streaming(
-        &payload.question, 
-        &context, 
-        model_clone,
-        move |token: &str| {
-            info!("📝 Streaming token (POST): '{}' (length: {})", token.chars().take(50).collect::<String>(), token.len());
-            
-            // Emit token event for progressive display with structured payload for frontend
-            let token_payload = serde_json::json!({
-                "text": token,
-                "token": token,
-                "timestamp": std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_millis()
-            });
-            let _ = app_handle_clone.emit("ai-stream-token", token_payload);
-            
-            // Also send via direct window communication for immediate display
-            let data = AiResponseData {
-                message_type: "stream-token".to_string(),
-                text: Some(token.to_string()),
-                error: None,
-            };
-            let app_handle_for_async = app_handle_clone.clone();
-            tokio::spawn(async move {
-                if let Err(e) = send_ai_response_data(app_handle_for_async, data).await {
-                    error!("Failed to send streaming token to UI: {}", e);
+        // Stream the response with callback to update UI progressively
+        let tokens = ui_token_coalescer(&app_handle, true);
+        let token_sink = tokens.clone();
+        let model_clone = model.clone(); // Clone model to avoid ownership issues
+        let mut first_token_seen = false;
+        let result = client.generate_answer_post_streaming(
+            &payload.question, 
+            &context, 
+            model_clone,
+            move |token: &str| {
+                if !first_token_seen {
+                    first_token_seen = true;
+                    metrics::record(metrics::QUESTION_TO_FIRST_TOKEN, stream_start_time.elapsed());
                 }
-            });
-        }
-    ).await;
+                token_sink.push(token);
+            }
+        ).await;
+        tokens.finish().await;
 
-    match result {
-        Ok(full_response) => {
-            let elapsed_time = stream_start_time.elapsed();
-            info!("✅ Streaming (POST) completed. Full response length: {}, elapsed time: {:.2?}", full_response.len(), elapsed_time);
+        match result {
+            Ok(full_response) => {
+                let elapsed_time = stream_start_time.elapsed();
+                info!("✅ Streaming (POST) completed. Full response length: {}, elapsed time: {:.2?}", full_response.len(), elapsed_time);
             
-            // Emit completion event
-            let _ = app_handle.emit("ai-stream-complete", full_response.clone());
+                // Emit completion event
+                let _ = app_handle.emit(events::AI_STREAM_COMPLETE, full_response.clone());
+                scripting::dispatch(scripting::ScriptEvent::AnswerComplete, serde_json::json!({ "answer": &full_response }));
+                context_builder::record_exchange(&payload.question, &full_response);
+                ticket.finish(&full_response);
             
-            // Send completion signal
-            let data = AiResponseData {
-                message_type: "complete".to_string(),
-                text: Some(full_response.clone()),
-                error: None,
-            };
-            let app_handle_for_complete = app_handle.clone();
-            tokio::spawn(async move {
-                if let Err(e) = send_ai_response_data(app_handle_for_complete, data).await {
-                    error!("Failed to send completion signal to UI: {}", e);
-                }
-            });
-            Ok(full_response)
-        },
-        Err(e) => {
-            error!("❌ Streaming (POST) failed: {}", e);
+                // Send completion signal
+                let data = AiResponseData {
+                    message_type: "complete".to_string(),
+                    text: Some(full_response.clone()),
+                    error: None,
+                };
+                let app_handle_for_complete = app_handle.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = send_ai_response_data(app_handle_for_complete, data).await.into_result() {
+                        error!("Failed to send completion signal to UI: {}", e);
+                    }
+                });
+                Ok(full_response)
+            },
+            Err(e) => {
+                error!("❌ Streaming (POST) failed: {}", e);
             
-            // Emit error event
-            let _ = app_handle.emit("ai-stream-error", e.to_string());
+                // Emit error event
+                let _ = app_handle.emit(events::AI_STREAM_ERROR, e.to_string());
             
-            // Send error signal
-            let data = AiResponseData {
-                message_type: "error".to_string(),
-                text: None,
-                error: Some(e.to_string()),
-            };
-            let app_handle_for_error = app_handle.clone();
-            tokio::spawn(async move {
-                if let Err(send_err) = send_ai_response_data(app_handle_for_error, data).await {
-                    error!("Failed to send error signal to UI: {}", send_err);
-                }
-            });
-            Err(e.to_string())
+                // Send error signal
+                let data = AiResponseData {
+                    message_type: "error".to_string(),
+                    text: None,
+                    error: Some(e.to_string()),
+                };
+                let app_handle_for_error = app_handle.clone();
+                tokio::spawn(async move {
+                    if let Err(send_err) = send_ai_response_data(app_handle_for_error, data).await.into_result() {
+                        error!("Failed to send error signal to UI: {}", send_err);
+                    }
+                });
+                Err(e.to_string().into())
+            }
         }
-    }
+    })
+    .await
 }
 
 #[tauri::command]
+#[specta::specta]
 async fn analyze_screen_content(
     payload: AnalyzeScreenPayload,
-    state: State<'_, AppState>
-) -> Result<String, String> {
-    info!("Analyzing screen content");
-    
-    state.ensure_openai_client()?;
+    state: State<'_, AppState>,
+    request_id: Option<String>,
+) -> Response<String> {
+    cancellation::run("analyze_screen_content", request_id, async move {
+        info!("Analyzing screen content");
     
-    let client = {
-        let client_guard = state.openai_client.lock();
-        client_guard.as_ref().unwrap().clone()
-    };
+        state.ensure_openai_client()?;
     
-    let mut context = {
-        let context_guard = state.interview_context.lock();
-        context_guard.clone()
-    };
+        let client = {
+            let client_guard = state.openai_client.lock();
+            client_guard.as_ref().unwrap().clone()
+        };
     
-    // Update context with payload data if provided
-    if let Some(company) = payload.company {
-        context.company = Some(company);
-    }
-    if let Some(position) = payload.position {
-        context.position = Some(position);
-    }
-    if let Some(job_description) = payload.job_description {
-        context.job_description = Some(job_description);
-    }
+        let context = ContextBuilder::new(&state)
+            .overrides(payload.company, payload.position, payload.job_description)
+            .provider("openai")
+            .question(&payload.screen_content)
+            .build();
     
-    let model = openai::OpenAIModel::from_string(&payload.model)
-        .map_err(|e| format!("Invalid model: {}", e))?;
+        let model = openai::OpenAIModel::from_string(&payload.model)
+            .map_err(|e| format!("Invalid model: {}", e))?;
     
-    let analysis = client.analyze_screen_content(&payload.screen_content, &context, model)
-        .await
-        .map_err(|e| e.to_string())?;
+        let analysis = client.analyze_screen_content(&payload.screen_content, &context, model)
+            .await
+            .map_err(|e| e.to_string())?;
     
-    Ok(analysis)
+        Ok(analysis)
+    })
+    .await
 }
 
 #[tauri::command]
+#[specta::specta]
 fn update_interview_context(
     payload: InterviewContextPayload,
     state: State<'_, AppState>
-) -> Result<String, String> {
-    info!("Updating interview context");
+) -> Response<String> {
+    envelope::respond_sync("update_interview_context", || {
+        info!("Updating interview context");
     
-    let mut context_guard = state.interview_context.lock();
+        let mut context_guard = state.interview_context.lock();
     
-    if let Some(company) = payload.company {
-        context_guard.company = Some(company);
-    }
-    if let Some(position) = payload.position {
-        context_guard.position = Some(position);
-    }
-    if let Some(job_description) = payload.job_description {
-        context_guard.job_description = Some(job_description);
-    }
+        if let Some(company) = payload.company {
+            context_guard.company = Some(company);
+        }
+        if let Some(position) = payload.position {
+            context_guard.position = Some(position);
+        }
+        if let Some(job_description) = payload.job_description {
+            context_guard.job_description = Some(job_description);
+        }
+        drop(context_guard);
+
+        if let Some(system_prompt) = payload.system_prompt {
+            session_prompt::update_current(&state, &system_prompt)?;
+        }
     
-    Ok("Interview context updated".to_string())
+        Ok("Interview context updated".to_string())
+    })
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Type)]
 struct ModelInfo {
     id: String,
     name: String,
@@ -1092,7 +1571,7 @@ struct ModelInfo {
     icon: String,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Type)]
 struct ProviderInfo {
     id: String,
     name: String,
@@ -1100,235 +1579,283 @@ struct ProviderInfo {
 }
 
 #[tauri::command]
-async fn get_available_models(state: State<'_, AppState>) -> Result<Vec<ModelInfo>, String> {
-    info!("Getting available models...");
-    
-    let mut models = Vec::new();
-    
-    // OpenAI models
-    models.push(ModelInfo {
-        id: "gpt-4-turbo".to_string(),
-        name: "GPT-4 Turbo".to_string(),
-        provider: "openai".to_string(),
-        icon: "🤖".to_string(),
-    });
-    models.push(ModelInfo {
-        id: "gpt-4".to_string(),
-        name: "GPT-4".to_string(),
-        provider: "openai".to_string(),
-        icon: "🤖".to_string(),
-    });
-    models.push(ModelInfo {
-        id: "gpt-3.5-turbo".to_string(),
-        name: "GPT-3.5 Turbo".to_string(),
-        provider: "openai".to_string(),
-        icon: "🤖".to_string(),
-    });
-    
-    // Pollinations models - only include verified working models
-    // Based on test results from test_pollinations_models.rs
-    const WORKING_MODELS: &[&str] = &[
-        "roblox-rp",    // Llama 3.1 8B Instruct (Cross-Region Bedrock) - our default
-        "gemini",       // Gemini 2.5 Flash Lite (api.navy)
-        "mistral",      // Mistral Small 3.1 24B
-    ];
-    
-    if let Err(e) = state.ensure_pollinations_client() {
-        warn!("Pollinations client not available: {}", e);
-    } else {
-        info!("Adding {} verified working Pollinations models", WORKING_MODELS.len());
-        for model_id in WORKING_MODELS {
-            if let Some(model) = pollinations::PollinationsModel::from_str(model_id) {
-                models.push(ModelInfo {
-                    id: model.as_str().to_string(),
-                    name: model.display_name().to_string(),
-                    provider: "pollinations".to_string(),
-                    icon: "🧠".to_string(),
-                });
+#[specta::specta]
+async fn get_available_models(state: State<'_, AppState>) -> Response<Vec<ModelInfo>> {
+    envelope::respond("get_available_models", async move {
+        info!("Getting available models...");
+    
+        let mut models = Vec::new();
+    
+        // OpenAI models
+        models.push(ModelInfo {
+            id: "gpt-4-turbo".to_string(),
+            name: "GPT-4 Turbo".to_string(),
+            provider: "openai".to_string(),
+            icon: "🤖".to_string(),
+        });
+        models.push(ModelInfo {
+            id: "gpt-4".to_string(),
+            name: "GPT-4".to_string(),
+            provider: "openai".to_string(),
+            icon: "🤖".to_string(),
+        });
+        models.push(ModelInfo {
+            id: "gpt-3.5-turbo".to_string(),
+            name: "GPT-3.5 Turbo".to_string(),
+            provider: "openai".to_string(),
+            icon: "🤖".to_string(),
+        });
+    
+        // Pollinations models - only include verified working models
+        // Based on test results from test_pollinations_models.rs
+        const WORKING_MODELS: &[&str] = &[
+            "roblox-rp",    // Llama 3.1 8B Instruct (Cross-Region Bedrock) - our default
+            "gemini",       // Gemini 2.5 Flash Lite (api.navy)
+            "mistral",      // Mistral Small 3.1 24B
+        ];
+    
+        if let Err(e) = state.ensure_pollinations_client() {
+            warn!("Pollinations client not available: {}", e);
+        } else {
+            info!("Adding {} verified working Pollinations models", WORKING_MODELS.len());
+            for model_id in WORKING_MODELS {
+                if let Some(model) = pollinations::PollinationsModel::from_str(model_id) {
+                    models.push(ModelInfo {
+                        id: model.as_str().to_string(),
+                        name: model.display_name().to_string(),
+                        provider: "pollinations".to_string(),
+                        icon: "🧠".to_string(),
+                    });
+                }
             }
         }
-    }
     
-    Ok(models)
+        Ok(models)
+    })
+    .await
 }
 
 #[tauri::command]
-fn get_ai_providers() -> Result<Vec<ProviderInfo>, String> {
-    info!("Getting AI providers...");
-    
-    let providers = vec![
-        ProviderInfo {
-            id: "openai".to_string(),
-            name: "OpenAI".to_string(),
-            description: "Official OpenAI models including GPT-4 and GPT-3.5".to_string(),
-        },
-        ProviderInfo {
-            id: "pollinations".to_string(),
-            name: "Pollinations (Self AI)".to_string(),
-            description: "Free and open AI models via Pollinations API".to_string(),
-        },
-    ];
+#[specta::specta]
+fn get_ai_providers() -> Response<Vec<ProviderInfo>> {
+    envelope::respond_sync("get_ai_providers", || {
+        info!("Getting AI providers...");
+    
+        let providers = vec![
+            ProviderInfo {
+                id: "openai".to_string(),
+                name: "OpenAI".to_string(),
+                description: "Official OpenAI models including GPT-4 and GPT-3.5".to_string(),
+            },
+            ProviderInfo {
+                id: "pollinations".to_string(),
+                name: "Pollinations (Self AI)".to_string(),
+                description: "Free and open AI models via Pollinations API".to_string(),
+            },
+        ];
     
-    Ok(providers)
+        Ok(providers)
+    })
 }
 
 // New Audio Commands
 
 #[tauri::command]
-fn get_audio_devices() -> Result<Vec<String>, String> {
-    info!("Getting audio devices...");
-    match audio::list_input_devices() {
-        Ok(devices) => {
-            info!("Found {} audio devices", devices.len());
-            Ok(devices)
-        }
-        Err(e) => {
-            error!("Failed to get audio devices: {}", e);
-            Err(format!("Failed to get audio devices: {}", e))
+#[specta::specta]
+fn get_audio_devices() -> Response<Vec<String>> {
+    envelope::respond_sync("get_audio_devices", || {
+        info!("Getting audio devices...");
+        match audio::list_input_devices() {
+            Ok(devices) => {
+                info!("Found {} audio devices", devices.len());
+                Ok(devices)
+            }
+            Err(e) => {
+                error!("Failed to get audio devices: {}", e);
+                Err(format!("Failed to get audio devices: {}", e).into())
+            }
         }
-    }
+    })
 }
 
 #[tauri::command]
-fn check_audio_status() -> Result<serde_json::Value, String> {
-    info!("Checking audio status...");
-    let is_recording = audio::is_recording();
-    let config = audio::get_audio_config();
-    
-    let status = serde_json::json!({
-        "is_recording": is_recording,
-        "config": {
-            "sample_rate": config.sample_rate,
-            "channels": config.channels,
-            "buffer_size": config.buffer_size
-        }
-    });
+#[specta::specta]
+fn check_audio_status() -> Response<serde_json::Value> {
+    envelope::respond_sync("check_audio_status", || {
+        info!("Checking audio status...");
+        let is_recording = audio::is_recording();
+        let config = audio::get_audio_config();
+    
+        let status = serde_json::json!({
+            "is_recording": is_recording,
+            "config": {
+                "sample_rate": config.sample_rate,
+                "channels": config.channels,
+                "buffer_size": config.buffer_size
+            }
+        });
     
-    Ok(status)
+        Ok(status)
+    })
 }
 
 #[tauri::command]
-fn start_audio_with_config(config: AudioConfigPayload) -> Result<String, String> {
-    info!("Starting audio with custom config: {} Hz, {} channels, {} buffer", 
-          config.sample_rate, config.channels, config.buffer_size);
-    
-    let audio_config = audio::AudioConfig {
-        sample_rate: config.sample_rate,
-        channels: config.channels,
-        buffer_size: config.buffer_size,
-    };
+#[specta::specta]
+fn start_audio_with_config(config: AudioConfigPayload) -> Response<String> {
+    envelope::respond_sync("start_audio_with_config", || {
+        info!("Starting audio with custom config: {} Hz, {} channels, {} buffer", 
+              config.sample_rate, config.channels, config.buffer_size);
+    
+        let audio_config = audio::AudioConfig {
+            sample_rate: config.sample_rate,
+            channels: config.channels,
+            buffer_size: config.buffer_size,
+        };
     
-    match audio::capture_audio_with_config(audio_config) {
-        Ok(_) => Ok("Audio capture started with custom config".to_string()),
-        Err(e) => {
-            error!("Failed to start audio capture with config: {}", e);
-            Err(format!("Failed to start audio capture: {}", e))
+        match audio::capture_audio_with_config(audio_config) {
+            Ok(_) => Ok("Audio capture started with custom config".to_string()),
+            Err(e) => {
+                error!("Failed to start audio capture with config: {}", e);
+                Err(format!("Failed to start audio capture: {}", e).into())
+            }
         }
-    }
+    })
 }
 
 #[tauri::command]
-async fn test_audio_capture(duration: u64) -> Result<String, String> {
-    info!("Starting test audio capture for {} seconds", duration);
+#[specta::specta]
+async fn test_audio_capture(duration: u64) -> Response<String> {
+    envelope::respond("test_audio_capture", async move {
+        info!("Starting test audio capture for {} seconds", duration);
     
-    match audio::test_capture_audio(duration) {
-        Ok(_) => Ok(format!("Test audio capture completed for {} seconds", duration)),
-        Err(e) => {
-            error!("Test audio capture failed: {}", e);
-            Err(format!("Test audio capture failed: {}", e))
+        match audio::test_capture_audio(duration) {
+            Ok(_) => Ok(format!("Test audio capture completed for {} seconds", duration)),
+            Err(e) => {
+                error!("Test audio capture failed: {}", e);
+                Err(format!("Test audio capture failed: {}", e).into())
+            }
         }
-    }
+    })
+    .await
 }
 
 #[tauri::command]
-fn test_native_windows_system_audio_capture(duration: u64) -> Result<String, String> {
-    info!("🧪 Testing native Windows WASAPI system audio capture for {} seconds", duration);
-    
-    match audio::test_native_windows_system_audio_capture(duration) {
-        Ok(result) => {
-            info!("✅ Native Windows WASAPI test completed successfully");
-            Ok(result)
-        }
-        Err(e) => {
-            error!("❌ Native Windows WASAPI test failed: {}", e);
-            Err(format!("❌ Native Windows WASAPI test failed: {}", e))
+#[specta::specta]
+fn test_native_windows_system_audio_capture(duration: u64) -> Response<String> {
+    envelope::respond_sync("test_native_windows_system_audio_capture", || {
+        info!("🧪 Testing native Windows WASAPI system audio capture for {} seconds", duration);
+    
+        match audio::test_native_windows_system_audio_capture(duration) {
+            Ok(result) => {
+                info!("✅ Native Windows WASAPI test completed successfully");
+                Ok(result)
+            }
+            Err(e) => {
+                error!("❌ Native Windows WASAPI test failed: {}", e);
+                Err(format!("❌ Native Windows WASAPI test failed: {}", e).into())
+            }
         }
-    }
+    })
 }
 
 #[tauri::command]
-fn start_system_audio_capture() -> Result<String, String> {
-    info!("Starting system audio capture...");
-    match audio::start_system_audio_capture() {
-        Ok(_) => Ok("System audio capture started successfully".to_string()),
-        Err(e) => {
-            error!("Failed to start system audio capture: {}", e);
-            Err(format!("Failed to start system audio capture: {}", e))
+#[specta::specta]
+async fn start_system_audio_capture(app_handle: AppHandle) -> Response<String> {
+    envelope::respond("start_system_audio_capture", async move {
+        info!("Starting system audio capture...");
+        // Same pipeline as the Pluely command: WASAPI loopback, PulseAudio monitor or macOS loopback device
+        match pluely_audio::start_pluely_system_audio_capture(app_handle).await.into_result() {
+            Ok(_) => {
+                // Recorded for `save_system_audio_file`, as this command always was
+                audio::begin_capture_recording();
+                Ok("System audio capture started successfully".to_string())
+            }
+            Err(e) => {
+                error!("Failed to start system audio capture: {}", e);
+                Err(format!("Failed to start system audio capture: {}", e).into())
+            }
         }
-    }
+    })
+    .await
 }
 
 #[tauri::command]
-fn test_microphone_capture() -> Result<String, String> {
-    info!("Testing microphone capture...");
-    
-    // List all audio devices first
-    match audio::list_all_audio_devices() {
-        Ok(devices) => {
-            let input_devices: Vec<_> = devices.into_iter()
-                .filter(|d| d.device_type == "input")
-                .collect();
+#[specta::specta]
+fn test_microphone_capture() -> Response<String> {
+    envelope::respond_sync("test_microphone_capture", || {
+        info!("Testing microphone capture...");
+    
+        // List all audio devices first
+        match audio::list_all_audio_devices() {
+            Ok(devices) => {
+                let input_devices: Vec<_> = devices.into_iter()
+                    .filter(|d| d.device_type == "input")
+                    .collect();
             
-            info!("Found {} input devices:", input_devices.len());
-            for device in &input_devices {
-                info!("  - {} (default: {})", device.name, device.is_default);
-            }
-            
-            if input_devices.is_empty() {
-                return Err("No input devices found for microphone capture".to_string());
-            }
+                info!("Found {} input devices:", input_devices.len());
+                for device in &input_devices {
+                    info!("  - {} (default: {})", device.name, device.is_default);
+                }
             
-            // Try to start microphone capture
-            match audio::start_microphone_capture() {
-                Ok(_) => {
-                    info!("Microphone capture test started successfully");
-                    Ok(format!("Microphone test started. Found {} input devices", input_devices.len()))
+                if input_devices.is_empty() {
+                    return Err("No input devices found for microphone capture".into());
                 }
-                Err(e) => {
-                    error!("Microphone capture test failed: {}", e);
-                    Err(format!("Microphone test failed: {}", e))
+            
+                // Try to start microphone capture
+                match audio::start_microphone_capture() {
+                    Ok(_) => {
+                        info!("Microphone capture test started successfully");
+                        Ok(format!("Microphone test started. Found {} input devices", input_devices.len()))
+                    }
+                    Err(e) => {
+                        error!("Microphone capture test failed: {}", e);
+                        Err(format!("Microphone test failed: {}", e).into())
+                    }
                 }
             }
+            Err(e) => {
+                error!("Failed to list audio devices: {}", e);
+                Err(format!("Failed to list audio devices: {}", e).into())
+            }
         }
-        Err(e) => {
-            error!("Failed to list audio devices: {}", e);
-            Err(format!("Failed to list audio devices: {}", e))
-        }
-    }
+    })
 }
 
 #[tauri::command]
-fn start_microphone_capture() -> Result<String, String> {
-    info!("Starting microphone capture...");
-    match audio::start_microphone_capture() {
-        Ok(_) => Ok("Microphone capture started successfully".to_string()),
-        Err(e) => {
-            error!("Failed to start microphone capture: {}", e);
-            Err(format!("Failed to start microphone capture: {}", e))
+#[specta::specta]
+fn start_microphone_capture() -> Response<String> {
+    envelope::respond_sync("start_microphone_capture", || {
+        info!("Starting microphone capture...");
+        match audio::start_microphone_capture() {
+            Ok(_) => Ok("Microphone capture started successfully".to_string()),
+            Err(e) => {
+                error!("Failed to start microphone capture: {}", e);
+                Err(format!("Failed to start microphone capture: {}", e).into())
+            }
         }
-    }
+    })
 }
 
 #[tauri::command]
-fn save_microphone_file() -> Result<String, String> {
-    info!("Saving microphone audio file...");
-    save_audio_file_impl(true)
+#[specta::specta]
+async fn save_microphone_file() -> Response<String> {
+    envelope::respond("save_microphone_file", async move {
+        info!("Saving microphone audio file...");
+        // Long captures merge spilled audio from disk; keep that off the async workers
+        Ok(tokio::task::spawn_blocking(|| save_audio_file_impl(true)).await.map_err(|e| e.to_string())??)
+    })
+    .await
 }
 
 #[tauri::command]
-fn save_system_audio_file() -> Result<String, String> {
-    info!("Saving system audio file...");
-    save_audio_file_impl(false)
+#[specta::specta]
+async fn save_system_audio_file() -> Response<String> {
+    envelope::respond("save_system_audio_file", async move {
+        info!("Saving system audio file...");
+        // Long captures merge spilled audio from disk; keep that off the async workers
+        Ok(tokio::task::spawn_blocking(|| save_audio_file_impl(false)).await.map_err(|e| e.to_string())??)
+    })
+    .await
 }
 
 fn save_audio_file_impl(is_mic: bool) -> Result<String, String> {
@@ -1342,60 +1869,33 @@ fn save_audio_file_impl(is_mic: bool) -> Result<String, String> {
 
     let prefix = if is_mic { "mic" } else { "Sound" };
     let filename_prefix = if is_mic { "mic_capture" } else { "audio_capture" };
-    let filename = format!("recordings/{}/{}_{}.wav", prefix, filename_prefix, timestamp);
-    
-    // Get captured audio samples
-    let captured_samples = audio::get_captured_samples();
-    let audio_config = audio::get_audio_config();
-    
-    if captured_samples.is_empty() {
+    let path = retention::DataCategory::Recordings
+        .dir()
+        .join(prefix)
+        .join(format!("{}_{}.wav", filename_prefix, timestamp));
+    let filename = path.display().to_string();
+    
+    let captured_samples = audio::captured_sample_count();
+    if captured_samples == 0 {
         warn!("No audio samples captured");
         return Err("No audio samples available to save".to_string());
     }
     
-    info!("Saving {} audio samples", captured_samples.len());
-    
-    let audio_data = audio::AudioData {
-        samples: captured_samples,
-        sample_rate: audio_config.sample_rate,
-        channels: audio_config.channels,
-        timestamp: std::time::SystemTime::now(),
-    };
+    info!("Saving {} audio samples", captured_samples);
     
-    // Convert to WAV format
-    match audio::audio_data_to_base64_wav(&audio_data) {
-        Ok(base64_wav) => {
-            // Decode base64 and write to file
-            match base64::prelude::BASE64_STANDARD.decode(base64_wav) {
-                Ok(wav_data) => {
-                    match std::fs::write(&filename, wav_data) {
-                        Ok(_) => {
-                            info!("Audio file saved successfully: {}", filename);
-                            // Clean up the WASAPI loopback instance after successful save
-                            audio::cleanup_audio_capture();
-                            Ok(format!("Audio file saved: {} ({} samples)", filename, audio_data.samples.len()))
-                        }
-                        Err(e) => {
-                            error!("Failed to write audio file: {}", e);
-                            // Clean up even on error
-                            audio::cleanup_audio_capture();
-                            Err(format!("Failed to write audio file: {}", e))
-                        }
-                    }
-                }
-                Err(e) => {
-                    error!("Failed to decode base64 audio data: {}", e);
-                    // Clean up on error
-                    audio::cleanup_audio_capture();
-                    Err(format!("Failed to decode audio data: {}", e))
-                }
-            }
+    // Write straight to disk, merging any audio spilled to temp files during a long capture
+    let result = audio::write_captured_wav(&path);
+    // Clean up the WASAPI loopback instance whether or not the write succeeded
+    audio::cleanup_audio_capture();
+
+    match result {
+        Ok(samples) => {
+            info!("Audio file saved successfully: {}", filename);
+            Ok(format!("Audio file saved: {} ({} samples)", filename, samples))
         }
         Err(e) => {
-            error!("Failed to convert audio to WAV format: {}", e);
-            // Clean up on error
-            audio::cleanup_audio_capture();
-            Err(format!("Failed to convert audio to WAV: {}", e))
+            error!("Failed to write audio file: {}", e);
+            Err(format!("Failed to write audio file: {}", e))
         }
     }
 }
@@ -1514,176 +2014,184 @@ fn save_audio_file_impl(is_mic: bool) -> Result<String, String> {
 // }
 
 #[tauri::command]
-fn create_ai_response_window(app_handle: AppHandle) -> Result<String, String> {
-    info!("Creating AI response window...");
-    
-    let main_window = match app_handle.get_webview_window("main") {
-        Some(window) => window,
-        None => {
-            error!("Main window not found");
-            return Err("Main window not found".to_string());
-        }
-    };
-    
-    // Get DPI scale factor first for proper scaling
-    let monitor = main_window.current_monitor().map_err(|e| e.to_string())?
-        .ok_or_else(|| "No monitor found".to_string())?;
-    let scale_factor = monitor.scale_factor();
+#[specta::specta]
+fn create_ai_response_window(app_handle: AppHandle) -> Response<String> {
+    envelope::respond_sync("create_ai_response_window", || {
+        info!("Creating AI response window...");
+    
+        let main_window = match app_handle.get_webview_window("main") {
+            Some(window) => window,
+            None => {
+                error!("Main window not found");
+                return Err("Main window not found".into());
+            }
+        };
     
-    info!("🔍 DEBUG: DPI Scale factor: {}", scale_factor);
+        // Get DPI scale factor first for proper scaling
+        let monitor = main_window.current_monitor().map_err(|e| e.to_string())?
+            .ok_or_else(|| "No monitor found".to_string())?;
+        let scale_factor = monitor.scale_factor();
     
-    // Get main window measurements - use OUTER for positioning alignment
-    let main_outer_position = main_window.outer_position().map_err(|e| e.to_string())?;
-    let main_outer_size = main_window.outer_size().map_err(|e| e.to_string())?;
-    let main_inner_size = main_window.inner_size().map_err(|e| e.to_string())?;
+        info!("🔍 DEBUG: DPI Scale factor: {}", scale_factor);
     
-    info!("🔍 DEBUG: Main window - outer: {}x{} at ({}, {}), inner: {}x{}", 
-          main_outer_size.width, main_outer_size.height, main_outer_position.x, main_outer_position.y,
-          main_inner_size.width, main_inner_size.height);
+        // Get main window measurements - use OUTER for positioning alignment
+        let main_outer_position = main_window.outer_position().map_err(|e| e.to_string())?;
+        let main_outer_size = main_window.outer_size().map_err(|e| e.to_string())?;
+        let main_inner_size = main_window.inner_size().map_err(|e| e.to_string())?;
     
-    // Calculate AI response window size - SAME WIDTH as main window's OUTER size
-    let _max_height = 550u32; // Maximum height constraint
+        info!("🔍 DEBUG: Main window - outer: {}x{} at ({}, {}), inner: {}x{}", 
+              main_outer_size.width, main_outer_size.height, main_outer_position.x, main_outer_position.y,
+              main_inner_size.width, main_inner_size.height);
     
-    // Use main window OUTER width for exact width match
-    let ai_response_width = main_outer_size.width;
-    let ai_response_height = 550u32; // Fixed default height of 550px
+        // Calculate AI response window size - SAME WIDTH as main window's OUTER size
+        let _max_height = 550u32; // Maximum height constraint
     
-    info!("🔍 DEBUG: AI Response size calculated: {}x{} (SAME OUTER WIDTH as main)", ai_response_width, ai_response_height);
+        // Use main window OUTER width for exact width match
+        let ai_response_width = main_outer_size.width;
+        let ai_response_height = 550u32; // Fixed default height of 550px
     
-    // Get screen size first for positioning calculations
-    let screen_size = monitor.size();
+        info!("🔍 DEBUG: AI Response size calculated: {}x{} (SAME OUTER WIDTH as main)", ai_response_width, ai_response_height);
     
-    // WINDOW CONTENT-AWARE POSITIONING: Account for difference between outer and inner window
-    // Calculate the difference between outer and inner window (window decorations)
-    let window_chrome_height = main_outer_size.height as i32 - main_inner_size.height as i32;
+        // Get screen size first for positioning calculations
+        let screen_size = monitor.size();
     
-    // Position AI window below main window with 5px gap
-    let response_x = main_outer_position.x;
-    let main_bottom_y = main_outer_position.y + main_outer_size.height as i32;
+        // WINDOW CONTENT-AWARE POSITIONING: Account for difference between outer and inner window
+        // Calculate the difference between outer and inner window (window decorations)
+        let window_chrome_height = main_outer_size.height as i32 - main_inner_size.height as i32;
     
-    // CRITICAL FIX: Add DPI-aware gap between main window and AI response window
-    let base_gap = 5.0; // 5px base gap
-    let dpi_aware_gap = (base_gap * scale_factor) as i32; // Scale gap with DPI
-    let final_response_y = main_bottom_y + dpi_aware_gap;
+        // Position AI window below main window with 5px gap
+        let response_x = main_outer_position.x;
+        let main_bottom_y = main_outer_position.y + main_outer_size.height as i32;
     
-    info!("🔍 DPI-AWARE GAP: {}px base * {:.2} scale = {}px physical gap", base_gap, scale_factor, dpi_aware_gap);
+        // CRITICAL FIX: Add DPI-aware gap between main window and AI response window
+        let base_gap = 5.0; // 5px base gap
+        let dpi_aware_gap = (base_gap * scale_factor) as i32; // Scale gap with DPI
+        let final_response_y = main_bottom_y + dpi_aware_gap;
     
-    info!("📏 Using consistent -1px overlap for ALL scale factors");
+        info!("🔍 DPI-AWARE GAP: {}px base * {:.2} scale = {}px physical gap", base_gap, scale_factor, dpi_aware_gap);
     
-    // Use original window sizes - no additional DPI scaling needed
-    let physical_width = ai_response_width;
-    let physical_height = ai_response_height;
+        info!("📏 Using consistent -1px overlap for ALL scale factors");
     
-    info!("🔍 DEBUG: CONTENT-AWARE AI Response Window Alignment:");
-    info!("  - Screen size (physical): {}x{}", screen_size.width, screen_size.height);
-    info!("  - DPI Scale factor: {:.2}", scale_factor);
-    info!("  - Main window outer: {}x{} at ({}, {})", main_outer_size.width, main_outer_size.height, main_outer_position.x, main_outer_position.y);
-    info!("  - Main window inner: {}x{}", main_inner_size.width, main_inner_size.height);
-    info!("  - Window chrome height: {}px (NOT USED FOR POSITIONING)", window_chrome_height);
-    info!("  - Main bottom Y: {} + DPI gap: {}px = final Y: {}", main_bottom_y, dpi_aware_gap, final_response_y);
-    info!("  - Content-aware positioning: Consistent -1px overlap for ALL scale factors");
+        // Use original window sizes - no additional DPI scaling needed
+        let physical_width = ai_response_width;
+        let physical_height = ai_response_height;
     
-    info!("📎 AI Response Window Position: main_content={}x{}, AI={}x{} at ({}, {}) [CONTENT-AWARE ALIGNED]", 
-          main_inner_size.width, main_inner_size.height, ai_response_width, ai_response_height, response_x, final_response_y);
+        info!("🔍 DEBUG: CONTENT-AWARE AI Response Window Alignment:");
+        info!("  - Screen size (physical): {}x{}", screen_size.width, screen_size.height);
+        info!("  - DPI Scale factor: {:.2}", scale_factor);
+        info!("  - Main window outer: {}x{} at ({}, {})", main_outer_size.width, main_outer_size.height, main_outer_position.x, main_outer_position.y);
+        info!("  - Main window inner: {}x{}", main_inner_size.width, main_inner_size.height);
+        info!("  - Window chrome height: {}px (NOT USED FOR POSITIONING)", window_chrome_height);
+        info!("  - Main bottom Y: {} + DPI gap: {}px = final Y: {}", main_bottom_y, dpi_aware_gap, final_response_y);
+        info!("  - Content-aware positioning: Consistent -1px overlap for ALL scale factors");
     
-    // Screen bounds check for safety (screen_size already obtained above)
-    info!("🔍 DEBUG: Screen size: {}x{} (scale: {})", screen_size.width, screen_size.height, scale_factor);
+        info!("📎 AI Response Window Position: main_content={}x{}, AI={}x{} at ({}, {}) [CONTENT-AWARE ALIGNED]", 
+              main_inner_size.width, main_inner_size.height, ai_response_width, ai_response_height, response_x, final_response_y);
     
-    // Ensure AI response window fits on screen (positioned below main window)
-    if response_x + ai_response_width as i32 > screen_size.width as i32 ||
-       final_response_y + ai_response_height as i32 > screen_size.height as i32 {
-        warn!("⚠️ AI response window would go off-screen when positioned below main window");
-    }
+        // Screen bounds check for safety (screen_size already obtained above)
+        info!("🔍 DEBUG: Screen size: {}x{} (scale: {})", screen_size.width, screen_size.height, scale_factor);
     
-    // Create response window configuration
-    // Use a full URL to ensure it bypasses any React routing
-    let window_url = if cfg!(debug_assertions) {
-        // Development mode - serve from localhost with proper path
-        tauri::WebviewUrl::External("http://localhost:1420/ai-response.html".parse().unwrap())
-    } else {
-        // Production mode - use app protocol with proper path
-        tauri::WebviewUrl::App("ai-response.html".into())
-    };
+        // Ensure AI response window fits on screen (positioned below main window)
+        if response_x + ai_response_width as i32 > screen_size.width as i32 ||
+           final_response_y + ai_response_height as i32 > screen_size.height as i32 {
+            warn!("⚠️ AI response window would go off-screen when positioned below main window");
+        }
     
-    let window_config = tauri::WebviewWindowBuilder::new(
-        &app_handle,
-        "ai-response",
-        window_url
-    )
-    .title("AI Response")
-    .inner_size(physical_width as f64, physical_height as f64)
-    .min_inner_size(200.0, 100.0)  // Conservative minimum size
-    .max_inner_size(physical_width as f64, 800.0 * scale_factor)  // Allow up to 800px max height with DPI scaling
-    // Remove max size constraint to allow dynamic resizing
-    .position(response_x as f64, final_response_y as f64)
-    .resizable(true) // Make resizable for programmatic resizing
-    .fullscreen(false)
-    .always_on_top(true)
-    .skip_taskbar(true)
-    .visible(true)
-    .decorations(false)
-    .transparent(true)
-    .shadow(false)
-    .focused(true);
+        // Create response window configuration
+        // Use a full URL to ensure it bypasses any React routing
+        let window_url = if cfg!(debug_assertions) {
+            // Development mode - serve from localhost with proper path
+            tauri::WebviewUrl::External("http://localhost:1420/ai-response.html".parse().unwrap())
+        } else {
+            // Production mode - use app protocol with proper path
+            tauri::WebviewUrl::App("ai-response.html".into())
+        };
     
-    match window_config.build() {
-        Ok(window) => {
-            info!("AI response window created successfully");
+        let style = appearance::window_style();
+        let window_config = tauri::WebviewWindowBuilder::new(
+            &app_handle,
+            "ai-response",
+            window_url
+        )
+        .title("AI Response")
+        .inner_size(physical_width as f64, physical_height as f64)
+        .min_inner_size(200.0, 100.0)  // Conservative minimum size
+        .max_inner_size(physical_width as f64, 800.0 * scale_factor)  // Allow up to 800px max height with DPI scaling
+        // Remove max size constraint to allow dynamic resizing
+        .resizable(true) // Make resizable for programmatic resizing
+        .fullscreen(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .visible(true)
+        .decorations(false)
+        .transparent(style.transparent)
+        .shadow(style.shadow)  // Off when transparent to prevent visual gaps
+        .theme(Some(style.theme))
+        .focused(true);
+        let window_config = window_manager::anchor_to_main(window_config, &main_window, response_x as f64, final_response_y as f64)?;
+    
+        match window_config.build() {
+            Ok(window) => {
+                info!("AI response window created successfully");
             
-            // Set window capture protection for the response window too
-            if let Err(e) = set_window_capture_protection(&window, true) {
-                error!("Failed to set window capture protection on AI response window: {}", e);
-            }
+                // Set window capture protection for the response window too
+                if let Err(e) = set_window_capture_protection(&window, true).into_result() {
+                    error!("Failed to set window capture protection on AI response window: {}", e);
+                }
             
-            Ok("AI response window created".to_string())
-        }
-        Err(e) => {
-            error!("Failed to create AI response window: {}", e);
-            Err(format!("Failed to create AI response window: {}", e))
+                Ok("AI response window created".to_string())
+            }
+            Err(e) => {
+                error!("Failed to create AI response window: {}", e);
+                Err(format!("Failed to create AI response window: {}", e).into())
+            }
         }
-    }
+    })
 }
 
 #[tauri::command]
-fn debug_main_window_dimensions(app_handle: AppHandle) -> Result<String, String> {
-    info!("🔍 DEBUG: Analyzing main window dimensions for invisible boundary...");
+#[specta::specta]
+fn debug_main_window_dimensions(app_handle: AppHandle) -> Response<String> {
+    envelope::respond_sync("debug_main_window_dimensions", || {
+        info!("🔍 DEBUG: Analyzing main window dimensions for invisible boundary...");
     
-    if let Some(main_window) = app_handle.get_webview_window("main") {
-        // Get all dimension measurements
-        let outer_position = main_window.outer_position().map_err(|e| e.to_string())?;
-        let outer_size = main_window.outer_size().map_err(|e| e.to_string())?;
-        let inner_position = main_window.inner_position().map_err(|e| e.to_string())?;
-        let inner_size = main_window.inner_size().map_err(|e| e.to_string())?;
+        if let Some(main_window) = app_handle.get_webview_window("main") {
+            // Get all dimension measurements
+            let outer_position = main_window.outer_position().map_err(|e| e.to_string())?;
+            let outer_size = main_window.outer_size().map_err(|e| e.to_string())?;
+            let inner_position = main_window.inner_position().map_err(|e| e.to_string())?;
+            let inner_size = main_window.inner_size().map_err(|e| e.to_string())?;
         
-        // Get monitor info
-        let monitor = main_window.current_monitor().map_err(|e| e.to_string())?
-            .ok_or_else(|| "No monitor found".to_string())?;
-        let scale_factor = monitor.scale_factor();
+            // Get monitor info
+            let monitor = main_window.current_monitor().map_err(|e| e.to_string())?
+                .ok_or_else(|| "No monitor found".to_string())?;
+            let scale_factor = monitor.scale_factor();
         
-        // Calculate chrome/padding differences
-        let chrome_width = outer_size.width as i32 - inner_size.width as i32;
-        let chrome_height = outer_size.height as i32 - inner_size.height as i32;
-        let position_offset_x = outer_position.x - inner_position.x;
-        let position_offset_y = outer_position.y - inner_position.y;
+            // Calculate chrome/padding differences
+            let chrome_width = outer_size.width as i32 - inner_size.width as i32;
+            let chrome_height = outer_size.height as i32 - inner_size.height as i32;
+            let position_offset_x = outer_position.x - inner_position.x;
+            let position_offset_y = outer_position.y - inner_position.y;
         
-        info!("🔍 MAIN WINDOW DIMENSIONS DEBUG:");
-        info!("  - Scale factor: {:.2}", scale_factor);
-        info!("  - Outer size: {}x{} at ({}, {})", outer_size.width, outer_size.height, outer_position.x, outer_position.y);
-        info!("  - Inner size: {}x{} at ({}, {})", inner_size.width, inner_size.height, inner_position.x, inner_position.y);
-        info!("  - Chrome/padding: width={}px, height={}px", chrome_width, chrome_height);
-        info!("  - Position offset: x={}, y={}", position_offset_x, position_offset_y);
+            info!("🔍 MAIN WINDOW DIMENSIONS DEBUG:");
+            info!("  - Scale factor: {:.2}", scale_factor);
+            info!("  - Outer size: {}x{} at ({}, {})", outer_size.width, outer_size.height, outer_position.x, outer_position.y);
+            info!("  - Inner size: {}x{} at ({}, {})", inner_size.width, inner_size.height, inner_position.x, inner_position.y);
+            info!("  - Chrome/padding: width={}px, height={}px", chrome_width, chrome_height);
+            info!("  - Position offset: x={}, y={}", position_offset_x, position_offset_y);
         
-        if chrome_height > 0 {
-            info!("  ⚠️ INVISIBLE BOUNDARY DETECTED: {}px height difference between outer and inner!", chrome_height);
-        }
+            if chrome_height > 0 {
+                info!("  ⚠️ INVISIBLE BOUNDARY DETECTED: {}px height difference between outer and inner!", chrome_height);
+            }
         
-        let debug_info = format!("Main window debug - Outer: {}x{}, Inner: {}x{}, Chrome height: {}px (INVISIBLE BOUNDARY: {}px)", 
-            outer_size.width, outer_size.height, inner_size.width, inner_size.height, chrome_height, chrome_height);
+            let debug_info = format!("Main window debug - Outer: {}x{}, Inner: {}x{}, Chrome height: {}px (INVISIBLE BOUNDARY: {}px)", 
+                outer_size.width, outer_size.height, inner_size.width, inner_size.height, chrome_height, chrome_height);
         
-        Ok(debug_info)
-    } else {
-        Err("Main window not found".to_string())
-    }
+            Ok(debug_info)
+        } else {
+            Err("Main window not found".into())
+        }
+    })
 }
 
 // Windows API fix for WebView2 invisible padding
@@ -1696,339 +2204,341 @@ use windows_sys::Win32::UI::WindowsAndMessaging::{
 use windows_sys::Win32::Foundation::HWND;
 
 #[tauri::command]
-fn nuclear_fix_webview_padding(app_handle: AppHandle) -> Result<String, String> {
-    info!("💥 NUCLEAR FIX: Direct WebView2 window size control to eliminate ALL padding...");
-    
-    #[cfg(windows)]
-    {
-        use windows_sys::Win32::UI::WindowsAndMessaging::{
-            GetWindowLongPtrW, SetWindowLongPtrW, SetWindowPos,
-            GWL_STYLE, GWL_EXSTYLE, WS_BORDER, WS_DLGFRAME, WS_THICKFRAME, 
-            WS_MINIMIZEBOX, WS_MAXIMIZEBOX, WS_SYSMENU,
-            WS_EX_CLIENTEDGE, WS_EX_STATICEDGE, WS_EX_WINDOWEDGE, WS_EX_DLGMODALFRAME,
-            SWP_NOMOVE, SWP_NOZORDER, SWP_NOACTIVATE, SWP_FRAMECHANGED
-        };
-        use windows_sys::Win32::Foundation::HWND;
+#[specta::specta]
+fn nuclear_fix_webview_padding(app_handle: AppHandle) -> Response<String> {
+    envelope::respond_sync("nuclear_fix_webview_padding", || {
+        info!("💥 NUCLEAR FIX: Direct WebView2 window size control to eliminate ALL padding...");
+    
+        #[cfg(windows)]
+        {
+            use windows_sys::Win32::UI::WindowsAndMessaging::{
+                GetWindowLongPtrW, SetWindowLongPtrW, SetWindowPos,
+                GWL_STYLE, GWL_EXSTYLE, WS_BORDER, WS_DLGFRAME, WS_THICKFRAME, 
+                WS_MINIMIZEBOX, WS_MAXIMIZEBOX, WS_SYSMENU,
+                WS_EX_CLIENTEDGE, WS_EX_STATICEDGE, WS_EX_WINDOWEDGE, WS_EX_DLGMODALFRAME,
+                SWP_NOMOVE, SWP_NOZORDER, SWP_NOACTIVATE, SWP_FRAMECHANGED
+            };
+            use windows_sys::Win32::Foundation::HWND;
         
-        if let Some(main_window) = app_handle.get_webview_window("main") {
-            match main_window.hwnd() {
-                Ok(hwnd) => {
-                    let hwnd = hwnd.0 as HWND;
+            if let Some(main_window) = app_handle.get_webview_window("main") {
+                match main_window.hwnd() {
+                    Ok(hwnd) => {
+                        let hwnd = hwnd.0 as HWND;
                     
-                    match main_window.inner_size() {
-                        Ok(inner_size) => {
-                            info!("🎨 NUCLEAR: Removing ALL window styles that cause padding...");
+                        match main_window.inner_size() {
+                            Ok(inner_size) => {
+                                info!("🎨 NUCLEAR: Removing ALL window styles that cause padding...");
                             
-                            unsafe {
-                                // Get current styles
-                                let current_style = GetWindowLongPtrW(hwnd, GWL_STYLE) as u32;
-                                let current_ex_style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE) as u32;
+                                unsafe {
+                                    // Get current styles
+                                    let current_style = GetWindowLongPtrW(hwnd, GWL_STYLE) as u32;
+                                    let current_ex_style = GetWindowLongPtrW(hwnd, GWL_EXSTYLE) as u32;
                                 
-                                info!("🔍 Current styles: style=0x{:X}, ex_style=0x{:X}", current_style, current_ex_style);
+                                    info!("🔍 Current styles: style=0x{:X}, ex_style=0x{:X}", current_style, current_ex_style);
                                 
-                                // Remove ALL styles that could cause padding/chrome
-                                let clean_style = current_style & !(
-                                    WS_BORDER | WS_DLGFRAME | WS_THICKFRAME | 
-                                    WS_MAXIMIZEBOX | WS_MINIMIZEBOX | WS_SYSMENU
-                                );
+                                    // Remove ALL styles that could cause padding/chrome
+                                    let clean_style = current_style & !(
+                                        WS_BORDER | WS_DLGFRAME | WS_THICKFRAME | 
+                                        WS_MAXIMIZEBOX | WS_MINIMIZEBOX | WS_SYSMENU
+                                    );
                                 
-                                let clean_ex_style = current_ex_style & !(
-                                    WS_EX_CLIENTEDGE | WS_EX_STATICEDGE | 
-                                    WS_EX_WINDOWEDGE | WS_EX_DLGMODALFRAME
-                                );
+                                    let clean_ex_style = current_ex_style & !(
+                                        WS_EX_CLIENTEDGE | WS_EX_STATICEDGE | 
+                                        WS_EX_WINDOWEDGE | WS_EX_DLGMODALFRAME
+                                    );
                                 
-                                info!("🧙 NUCLEAR: Setting clean styles: style=0x{:X}, ex_style=0x{:X}", clean_style, clean_ex_style);
+                                    info!("🧙 NUCLEAR: Setting clean styles: style=0x{:X}, ex_style=0x{:X}", clean_style, clean_ex_style);
                                 
-                                // Apply the cleaned styles
-                                SetWindowLongPtrW(hwnd, GWL_STYLE, clean_style as isize);
-                                SetWindowLongPtrW(hwnd, GWL_EXSTYLE, clean_ex_style as isize);
+                                    // Apply the cleaned styles
+                                    SetWindowLongPtrW(hwnd, GWL_STYLE, clean_style as isize);
+                                    SetWindowLongPtrW(hwnd, GWL_EXSTYLE, clean_ex_style as isize);
                                 
-                                // Force the window to exact content size
-                                let result = SetWindowPos(
-                                    hwnd,
-                                    0, // HWND_TOP as isize
-                                    0, 0,
-                                    inner_size.width as i32,
-                                    inner_size.height as i32,
-                                    SWP_NOMOVE | SWP_NOZORDER | SWP_NOACTIVATE | SWP_FRAMECHANGED
-                                );
+                                    // Force the window to exact content size
+                                    let result = SetWindowPos(
+                                        hwnd,
+                                        0, // HWND_TOP as isize
+                                        0, 0,
+                                        inner_size.width as i32,
+                                        inner_size.height as i32,
+                                        SWP_NOMOVE | SWP_NOZORDER | SWP_NOACTIVATE | SWP_FRAMECHANGED
+                                    );
                                 
-                                if result != 0 {
-                                    info!("💥 NUCLEAR FIX: Applied clean window styles + exact sizing");
+                                    if result != 0 {
+                                        info!("💥 NUCLEAR FIX: Applied clean window styles + exact sizing");
                                     
-                                    // Verify the nuclear fix
-                                    std::thread::sleep(std::time::Duration::from_millis(200));
-                                    if let (Ok(new_outer), Ok(new_inner)) = (main_window.outer_size(), main_window.inner_size()) {
-                                        let final_chrome = new_outer.height as i32 - new_inner.height as i32;
-                                        info!("🔭 NUCLEAR VERIFICATION: outer={}x{}, inner={}x{}, chrome={}px", 
-                                              new_outer.width, new_outer.height, new_inner.width, new_inner.height, final_chrome);
+                                        // Verify the nuclear fix
+                                        std::thread::sleep(std::time::Duration::from_millis(200));
+                                        if let (Ok(new_outer), Ok(new_inner)) = (main_window.outer_size(), main_window.inner_size()) {
+                                            let final_chrome = new_outer.height as i32 - new_inner.height as i32;
+                                            info!("🔭 NUCLEAR VERIFICATION: outer={}x{}, inner={}x{}, chrome={}px", 
+                                                  new_outer.width, new_outer.height, new_inner.width, new_inner.height, final_chrome);
                                         
-                                        if final_chrome == 0 {
-                                            Ok(format!("💥 NUCLEAR SUCCESS: ALL invisible padding eliminated! (chrome: {}px)", final_chrome))
-                                        } else if final_chrome < 5 {
-                                            Ok(format!("✅ NUCLEAR PARTIAL: Minimal chrome remaining ({}px - WebView2 core limitation)", final_chrome))
+                                            if final_chrome == 0 {
+                                                Ok(format!("💥 NUCLEAR SUCCESS: ALL invisible padding eliminated! (chrome: {}px)", final_chrome))
+                                            } else if final_chrome < 5 {
+                                                Ok(format!("✅ NUCLEAR PARTIAL: Minimal chrome remaining ({}px - WebView2 core limitation)", final_chrome))
+                                            } else {
+                                                Ok(format!("⚠️ NUCLEAR REDUCED: Chrome reduced to {}px (Windows/WebView2 enforced minimum)", final_chrome))
+                                            }
                                         } else {
-                                            Ok(format!("⚠️ NUCLEAR REDUCED: Chrome reduced to {}px (Windows/WebView2 enforced minimum)", final_chrome))
+                                            Ok("💥 Nuclear fix applied - verification failed".to_string())
                                         }
                                     } else {
-                                        Ok("💥 Nuclear fix applied - verification failed".to_string())
+                                        Err("Nuclear fix: SetWindowPos failed".into())
                                     }
-                                } else {
-                                    Err("Nuclear fix: SetWindowPos failed".to_string())
                                 }
                             }
+                            Err(e) => Err(format!("Nuclear fix: Failed to get inner size: {}", e).into())
                         }
-                        Err(e) => Err(format!("Nuclear fix: Failed to get inner size: {}", e))
                     }
+                    Err(e) => Err(format!("Nuclear fix: Failed to get HWND: {}", e).into())
                 }
-                Err(e) => Err(format!("Nuclear fix: Failed to get HWND: {}", e))
+            } else {
+                Err("Nuclear fix: Main window not found".into())
             }
-        } else {
-            Err("Nuclear fix: Main window not found".to_string())
         }
-    }
-    #[cfg(not(windows))]
-    {
-        Err("Nuclear fix is only available on Windows".to_string())
-    }
+        #[cfg(not(windows))]
+        {
+            let _ = app_handle;
+            Err("Nuclear fix is only available on Windows".into())
+        }
+    })
 }
 
 #[tauri::command]
-fn force_window_exact_content_size(app_handle: AppHandle) -> Result<String, String> {
-    info!("🔥 FORCE FIX: Using Windows API to eliminate invisible WebView2 padding...");
-    
-    #[cfg(windows)]
-    {
-        if let Some(main_window) = app_handle.get_webview_window("main") {
-            // Get the native Windows HWND
-            match main_window.hwnd() {
-                Ok(hwnd) => {
-                    let hwnd = hwnd.0 as HWND;
+#[specta::specta]
+fn force_window_exact_content_size(app_handle: AppHandle) -> Response<String> {
+    envelope::respond_sync("force_window_exact_content_size", || {
+        info!("🔥 FORCE FIX: Using Windows API to eliminate invisible WebView2 padding...");
+    
+        #[cfg(windows)]
+        {
+            if let Some(main_window) = app_handle.get_webview_window("main") {
+                // Get the native Windows HWND
+                match main_window.hwnd() {
+                    Ok(hwnd) => {
+                        let hwnd = hwnd.0 as HWND;
                     
-                    // Get current inner content size
-                    match main_window.inner_size() {
-                        Ok(inner_size) => {
-                            info!("🎯 FORCE FIX: Setting window size to exact content: {}x{}", inner_size.width, inner_size.height);
+                        // Get current inner content size
+                        match main_window.inner_size() {
+                            Ok(inner_size) => {
+                                info!("🎯 FORCE FIX: Setting window size to exact content: {}x{}", inner_size.width, inner_size.height);
                             
-                            unsafe {
-                                // Force the window to exact content size using Windows API
-                                let result = SetWindowPos(
-                                    hwnd,
-                                    0, // HWND_TOP as isize
-                                    0, 0,
-                                    inner_size.width as i32,
-                                    inner_size.height as i32,
-                                    SWP_NOMOVE | SWP_NOZORDER | SWP_NOACTIVATE
-                                );
+                                unsafe {
+                                    // Force the window to exact content size using Windows API
+                                    let result = SetWindowPos(
+                                        hwnd,
+                                        0, // HWND_TOP as isize
+                                        0, 0,
+                                        inner_size.width as i32,
+                                        inner_size.height as i32,
+                                        SWP_NOMOVE | SWP_NOZORDER | SWP_NOACTIVATE
+                                    );
                                 
-                                if result != 0 {
-                                    info!("✅ FORCE FIX: Windows API SetWindowPos successful");
+                                    if result != 0 {
+                                        info!("✅ FORCE FIX: Windows API SetWindowPos successful");
                                     
-                                    // Verify the fix worked
-                                    std::thread::sleep(std::time::Duration::from_millis(100));
-                                    if let (Ok(new_outer), Ok(new_inner)) = (main_window.outer_size(), main_window.inner_size()) {
-                                        let remaining_chrome = new_outer.height as i32 - new_inner.height as i32;
-                                        info!("🔍 VERIFICATION: New outer: {}x{}, inner: {}x{}, chrome: {}px", 
-                                              new_outer.width, new_outer.height, new_inner.width, new_inner.height, remaining_chrome);
+                                        // Verify the fix worked
+                                        std::thread::sleep(std::time::Duration::from_millis(100));
+                                        if let (Ok(new_outer), Ok(new_inner)) = (main_window.outer_size(), main_window.inner_size()) {
+                                            let remaining_chrome = new_outer.height as i32 - new_inner.height as i32;
+                                            info!("🔍 VERIFICATION: New outer: {}x{}, inner: {}x{}, chrome: {}px", 
+                                                  new_outer.width, new_outer.height, new_inner.width, new_inner.height, remaining_chrome);
                                         
-                                        if remaining_chrome <= 1 {
-                                            Ok(format!("✅ SUCCESS: Invisible padding eliminated using Windows API (chrome: {}px)", remaining_chrome))
+                                            if remaining_chrome <= 1 {
+                                                Ok(format!("✅ SUCCESS: Invisible padding eliminated using Windows API (chrome: {}px)", remaining_chrome))
+                                            } else {
+                                                Ok(format!("⚠️ PARTIAL: Reduced to {}px chrome (WebView2 limitation)", remaining_chrome))
+                                            }
                                         } else {
-                                            Ok(format!("⚠️ PARTIAL: Reduced to {}px chrome (WebView2 limitation)", remaining_chrome))
+                                            Ok("✅ Windows API call succeeded".to_string())
                                         }
                                     } else {
-                                        Ok("✅ Windows API call succeeded".to_string())
+                                        Err("Failed to call Windows API SetWindowPos".into())
                                     }
-                                } else {
-                                    Err("Failed to call Windows API SetWindowPos".to_string())
                                 }
                             }
+                            Err(e) => Err(format!("Failed to get inner size: {}", e).into())
                         }
-                        Err(e) => Err(format!("Failed to get inner size: {}", e))
                     }
+                    Err(e) => Err(format!("Failed to get HWND: {}", e).into())
                 }
-                Err(e) => Err(format!("Failed to get HWND: {}", e))
+            } else {
+                Err("Main window not found".into())
             }
-        } else {
-            Err("Main window not found".to_string())
         }
-    }
-    #[cfg(not(windows))]
-    {
-        Err("This fix is only available on Windows".to_string())
-    }
+        #[cfg(not(windows))]
+        {
+            let _ = app_handle;
+            Err("This fix is only available on Windows".into())
+        }
+    })
 }
 
 #[tauri::command]
-fn fix_main_window_invisible_boundary(app_handle: AppHandle) -> Result<String, String> {
-    info!("🔧 FIXING: Main window invisible boundary by adjusting size to match content...");
+#[specta::specta]
+fn fix_main_window_invisible_boundary(app_handle: AppHandle) -> Response<String> {
+    envelope::respond_sync("fix_main_window_invisible_boundary", || {
+        info!("🔧 FIXING: Main window invisible boundary by adjusting size to match content...");
     
-    if let Some(main_window) = app_handle.get_webview_window("main") {
-        // Get current dimensions
-        let outer_size = main_window.outer_size().map_err(|e| e.to_string())?;
-        let inner_size = main_window.inner_size().map_err(|e| e.to_string())?;
-        let _outer_position = main_window.outer_position().map_err(|e| e.to_string())?;
+        if let Some(main_window) = app_handle.get_webview_window("main") {
+            // Get current dimensions
+            let outer_size = main_window.outer_size().map_err(|e| e.to_string())?;
+            let inner_size = main_window.inner_size().map_err(|e| e.to_string())?;
+            let _outer_position = main_window.outer_position().map_err(|e| e.to_string())?;
         
-        // Calculate chrome/padding
-        let chrome_height = outer_size.height as i32 - inner_size.height as i32;
-        let chrome_width = outer_size.width as i32 - inner_size.width as i32;
+            // Calculate chrome/padding
+            let chrome_height = outer_size.height as i32 - inner_size.height as i32;
+            let chrome_width = outer_size.width as i32 - inner_size.width as i32;
         
-        info!("🔍 Current main window state:");
-        info!("  - Outer size: {}x{}", outer_size.width, outer_size.height);
-        info!("  - Inner size: {}x{}", inner_size.width, inner_size.height);
-        info!("  - Chrome padding: width={}px, height={}px", chrome_width, chrome_height);
+            info!("🔍 Current main window state:");
+            info!("  - Outer size: {}x{}", outer_size.width, outer_size.height);
+            info!("  - Inner size: {}x{}", inner_size.width, inner_size.height);
+            info!("  - Chrome padding: width={}px, height={}px", chrome_width, chrome_height);
         
-        if chrome_height > 0 {
-            info!("🎯 FIXING: Reducing window outer height by {}px to eliminate invisible boundary", chrome_height);
+            if chrome_height > 0 {
+                info!("🎯 FIXING: Reducing window outer height by {}px to eliminate invisible boundary", chrome_height);
             
-            // Set the outer size to match inner size (eliminate chrome padding)
-            let fixed_height = inner_size.height;
-            let fixed_width = inner_size.width;
+                // Set the outer size to match inner size (eliminate chrome padding)
+                let fixed_height = inner_size.height;
+                let fixed_width = inner_size.width;
             
-            match main_window.set_size(tauri::Size::Physical(tauri::PhysicalSize {
-                width: fixed_width,
-                height: fixed_height,
-            })) {
-                Ok(_) => {
-                    info!("✅ Main window size fixed: {}x{} (eliminated {}px invisible boundary)", 
-                          fixed_width, fixed_height, chrome_height);
+                match main_window.set_size(tauri::Size::Physical(tauri::PhysicalSize {
+                    width: fixed_width,
+                    height: fixed_height,
+                })) {
+                    Ok(_) => {
+                        info!("✅ Main window size fixed: {}x{} (eliminated {}px invisible boundary)", 
+                              fixed_width, fixed_height, chrome_height);
                     
-                    // Verify the fix worked
-                    match main_window.outer_size() {
-                        Ok(new_outer_size) => {
-                            let new_chrome_height = new_outer_size.height as i32 - inner_size.height as i32;
-                            info!("🔍 Post-fix verification: new outer size {}x{}, remaining chrome: {}px", 
-                                  new_outer_size.width, new_outer_size.height, new_chrome_height);
+                        // Verify the fix worked
+                        match main_window.outer_size() {
+                            Ok(new_outer_size) => {
+                                let new_chrome_height = new_outer_size.height as i32 - inner_size.height as i32;
+                                info!("🔍 Post-fix verification: new outer size {}x{}, remaining chrome: {}px", 
+                                      new_outer_size.width, new_outer_size.height, new_chrome_height);
                             
-                            if new_chrome_height <= 1 { // Allow 1px tolerance
-                                Ok(format!("✅ FIXED: Invisible boundary eliminated (was {}px, now {}px)", chrome_height, new_chrome_height))
-                            } else {
-                                Ok(format!("⚠️ PARTIAL FIX: Reduced from {}px to {}px chrome", chrome_height, new_chrome_height))
+                                if new_chrome_height <= 1 { // Allow 1px tolerance
+                                    Ok(format!("✅ FIXED: Invisible boundary eliminated (was {}px, now {}px)", chrome_height, new_chrome_height))
+                                } else {
+                                    Ok(format!("⚠️ PARTIAL FIX: Reduced from {}px to {}px chrome", chrome_height, new_chrome_height))
+                                }
+                            }
+                            Err(e) => {
+                                warn!("❌ Failed to verify fix: {}", e);
+                                Ok(format!("✅ Fix attempted: eliminated {}px chrome", chrome_height))
                             }
-                        }
-                        Err(e) => {
-                            warn!("❌ Failed to verify fix: {}", e);
-                            Ok(format!("✅ Fix attempted: eliminated {}px chrome", chrome_height))
                         }
                     }
+                    Err(e) => {
+                        error!("❌ Failed to fix main window size: {}", e);
+                        Err(format!("Failed to fix main window size: {}", e).into())
+                    }
                 }
-                Err(e) => {
-                    error!("❌ Failed to fix main window size: {}", e);
-                    Err(format!("Failed to fix main window size: {}", e))
-                }
+            } else {
+                info!("✅ No invisible boundary detected - main window size is already correct");
+                Ok("Main window size is already correct - no invisible boundary".to_string())
             }
         } else {
-            info!("✅ No invisible boundary detected - main window size is already correct");
-            Ok("Main window size is already correct - no invisible boundary".to_string())
+            Err("Main window not found".into())
         }
-    } else {
-        Err("Main window not found".to_string())
-    }
+    })
 }
 
 #[tauri::command]
-fn close_ai_response_window(app_handle: AppHandle) -> Result<String, String> {
-    info!("Closing AI response window...");
+#[specta::specta]
+fn close_ai_response_window(app_handle: AppHandle) -> Response<String> {
+    envelope::respond_sync("close_ai_response_window", || {
+        info!("Closing AI response window...");
     
-    if let Some(window) = app_handle.get_webview_window("ai-response") {
-        match window.close() {
-            Ok(_) => {
-                info!("AI response window closed successfully");
-                Ok("AI response window closed".to_string())
-            }
-            Err(e) => {
-                error!("Failed to close AI response window: {}", e);
-                Err(format!("Failed to close AI response window: {}", e))
+        if let Some(window) = app_handle.get_webview_window("ai-response") {
+            match window.close() {
+                Ok(_) => {
+                    info!("AI response window closed successfully");
+                    Ok("AI response window closed".to_string())
+                }
+                Err(e) => {
+                    error!("Failed to close AI response window: {}", e);
+                    Err(format!("Failed to close AI response window: {}", e).into())
+                }
             }
+        } else {
+            info!("AI response window not found - may already be closed");
+            Ok("AI response window not found".to_string())
         }
-    } else {
-        info!("AI response window not found - may already be closed");
-        Ok("AI response window not found".to_string())
-    }
+    })
 }
 
 #[tauri::command]
-fn resize_ai_response_window(app_handle: AppHandle, height: u32) -> Result<String, String> {
-    info!("🔧 RESIZE REQUEST: height={}, timestamp={}", height, std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_millis());
-    
-    if let Some(window) = app_handle.get_webview_window("ai-response") {
-        // Get current size first for debugging
-        let current_size = window.outer_size().map_err(|e| {
-            error!("❌ Failed to get current window size: {}", e);
-            e.to_string()
-        })?;
-        
-        // Get screen dimensions for dynamic max height
-        let monitor = window.current_monitor()
-            .map_err(|e| {
-                error!("❌ Failed to get monitor info: {}", e);
+#[specta::specta]
+fn resize_ai_response_window(app_handle: AppHandle, height: u32) -> Response<String> {
+    envelope::respond_sync("resize_ai_response_window", || {
+        info!("🔧 RESIZE REQUEST: height={}, timestamp={}", height, std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_millis());
+    
+        if let Some(window) = app_handle.get_webview_window("ai-response") {
+            // Get current size first for debugging
+            let current_size = window.outer_size().map_err(|e| {
+                error!("❌ Failed to get current window size: {}", e);
                 e.to_string()
-            })?
-            .ok_or_else(|| {
-                error!("❌ No monitor found");
-                "No monitor found".to_string()
             })?;
         
-        let max_height = monitor.size();
-        let calculated_max = (max_height.height as f64 * 0.6) as u32;
-        info!("📐 Screen size: {}x{}, calculated max height: {}", max_height.width, max_height.height, calculated_max);
+            // Get screen dimensions for dynamic max height
+            let monitor = window.current_monitor()
+                .map_err(|e| {
+                    error!("❌ Failed to get monitor info: {}", e);
+                    e.to_string()
+                })?
+                .ok_or_else(|| {
+                    error!("❌ No monitor found");
+                    "No monitor found".to_string()
+                })?;
         
-        // Get main window size to calculate centered AI response width
-        let main_window = app_handle.get_webview_window("main")
-            .ok_or_else(|| "Main window not found for width calculation".to_string())?;
-        let main_outer_size = main_window.outer_size().map_err(|e| e.to_string())?;
-        let ai_response_width = main_outer_size.width; // Use SAME WIDTH as main window outer size
+            let max_height = monitor.size();
+            let calculated_max = (max_height.height as f64 * 0.6) as u32;
+            info!("📐 Screen size: {}x{}, calculated max height: {}", max_height.width, max_height.height, calculated_max);
         
-        info!("🔍 DEBUG (resize): Main outer size: {}x{}, AI width: {} (SAME WIDTH)", main_outer_size.width, main_outer_size.height, ai_response_width);
+            // Get main window size to calculate centered AI response width
+            let main_window = app_handle.get_webview_window("main")
+                .ok_or_else(|| "Main window not found for width calculation".to_string())?;
+            let main_outer_size = main_window.outer_size().map_err(|e| e.to_string())?;
+            let ai_response_width = main_outer_size.width; // Use SAME WIDTH as main window outer size
         
-        // Lower minimum height for auto-sizing content, max height 550px
-        let clamped_height = height.max(80).min(550); // Use 550px as max height for content-based sizing
-        let size_diff = (current_size.height as i32 - clamped_height as i32).abs();
+            info!("🔍 DEBUG (resize): Main outer size: {}x{}, AI width: {} (SAME WIDTH)", main_outer_size.width, main_outer_size.height, ai_response_width);
         
-        info!("📊 RESIZE DEBUG: current={}px, requested={}px, clamped={}px, max={}px, diff={}px", 
-              current_size.height, height, clamped_height, calculated_max, size_diff);
+            // Lower minimum height for auto-sizing content, max height 550px
+            let clamped_height = height.max(80).min(550); // Use 550px as max height for content-based sizing
+            let size_diff = (current_size.height as i32 - clamped_height as i32).abs();
         
-        // Always try to resize if there's any difference - remove the 5px threshold
-        if current_size.height != clamped_height {
-            info!("🎯 Attempting resize from {}px to {}px...", current_size.height, clamped_height);
-            
-            match window.set_size(tauri::Size::Physical(tauri::PhysicalSize {
-                width: ai_response_width,  // Use fixed AI response window width
-                height: clamped_height,
-            })) {
-                Ok(_) => {
-                    info!("✅ AI response window successfully resized: {}px -> {}px (diff: {}px)", current_size.height, clamped_height, size_diff);
-                    
-                    // Verify the resize worked by checking the new size
-                    match window.outer_size() {
-                        Ok(new_size) => {
-                            info!("🔍 Post-resize verification: actual new size is {}px", new_size.height);
-                            if new_size.height == clamped_height {
-                                Ok(format!("✅ Resized successfully: {} -> {}", current_size.height, new_size.height))
-                            } else {
-                                warn!("⚠️ Resize mismatch: expected {}px but got {}px", clamped_height, new_size.height);
-                                Ok(format!("⚠️ Partial resize: {} -> {} (expected {})", current_size.height, new_size.height, clamped_height))
-                            }
-                        }
-                        Err(e) => {
-                            warn!("❌ Failed to verify resize: {}", e);
-                            Ok(format!("✅ Resize attempted: {} -> {}", current_size.height, clamped_height))
-                        }
-                    }
-                }
-                Err(e) => {
-                    error!("❌ Failed to resize AI response window: {}", e);
-                    Err(format!("❌ Resize failed: {}", e))
-                }
-            }
+            info!("📊 RESIZE DEBUG: current={}px, requested={}px, clamped={}px, max={}px, diff={}px", 
+                  current_size.height, height, clamped_height, calculated_max, size_diff);
+        
+            // Debounced and serialized with other resizes; the settled size is emitted as `ai-response-geometry`
+            resize_coordinator::request(&app_handle, resize_coordinator::GeometryRequest {
+                width: Some(ai_response_width),
+                height: Some(clamped_height),
+                ..Default::default()
+            });
+            Ok(format!("Resize to {}px scheduled (current {}px)", clamped_height, current_size.height))
         } else {
-            info!("➡️ No resize needed: window already at target height {}px", current_size.height);
-            Ok(format!("➡️ Already correct size: {}px", current_size.height))
+            error!("❌ AI response window 'ai-response' not found for resize");
+            Err("AI response window not found".into())
         }
-    } else {
-        error!("❌ AI response window 'ai-response' not found for resize");
-        Err("AI response window not found".to_string())
+    })
+}
+
+// Serializes lazy creation so concurrent callers don't build the window twice
+static AI_RESPONSE_WINDOW_INIT: once_cell::sync::Lazy<tokio::sync::Mutex<()>> =
+    once_cell::sync::Lazy::new(|| tokio::sync::Mutex::new(()));
+
+// Create the (hidden) AI response window if it doesn't exist yet
+async fn ensure_ai_response_window(app_handle: &AppHandle) -> Result<(), String> {
+    let _guard = AI_RESPONSE_WINDOW_INIT.lock().await;
+    if app_handle.get_webview_window("ai-response").is_some() {
+        return Ok(());
     }
+    create_ai_response_window_at_startup(app_handle.clone())?;
+    info!("✅ AI response window created");
+    Ok(())
 }
 
 // Function to create AI response window at startup (hidden by default)
@@ -2115,6 +2625,7 @@ fn create_ai_response_window_at_startup(app_handle: AppHandle) -> Result<String,
         tauri::WebviewUrl::App("ai-response.html".into())
     };
     
+    let style = appearance::window_style();
     let window_config = tauri::WebviewWindowBuilder::new(
         &app_handle,
         "ai-response",
@@ -2125,23 +2636,24 @@ fn create_ai_response_window_at_startup(app_handle: AppHandle) -> Result<String,
     .min_inner_size(200.0, 100.0)  // Conservative minimum size (startup)
     .max_inner_size(ai_response_width as f64, max_height as f64)  // Respect max height constraint
     // Remove max size constraint to allow dynamic resizing
-    .position(response_x as f64, final_response_y as f64)
     .resizable(true) // Make resizable for programmatic resizing
     .fullscreen(false)
     .always_on_top(true)
     .skip_taskbar(true)
     .visible(false) // Start hidden
     .decorations(false)
-    .transparent(true)
-    .shadow(false)
+    .transparent(style.transparent)
+    .shadow(style.shadow)  // Off when transparent to prevent visual gaps
+    .theme(Some(style.theme))
     .focused(false);
+    let window_config = window_manager::anchor_to_main(window_config, &main_window, response_x as f64, final_response_y as f64)?;
     
     match window_config.build() {
         Ok(window) => {
             info!("AI response window created at startup (hidden)");
             
             // Set window capture protection for the response window too
-            if let Err(e) = set_window_capture_protection(&window, true) {
+            if let Err(e) = set_window_capture_protection(&window, true).into_result() {
                 error!("Failed to set window capture protection on AI response window: {}", e);
             }
             
@@ -2155,27 +2667,30 @@ fn create_ai_response_window_at_startup(app_handle: AppHandle) -> Result<String,
 }
 
 #[tauri::command]
-fn show_ai_response_window(app_handle: AppHandle) -> Result<String, String> {
-    info!("⚡ FAST SHOW: AI response window...");
-    
-    if let Some(window) = app_handle.get_webview_window("ai-response") {
-        // Use concurrent operations for faster display
-        match window.show() {
-            Ok(_) => {
-                // Ensure window is focused and visible for immediate use
-                let _ = window.set_focus();
-                info!("✅ FAST SHOW: AI response window shown and focused successfully");
-                Ok("AI response window shown".to_string())
-            }
-            Err(e) => {
-                error!("❌ FAST SHOW: Failed to show AI response window: {}", e);
-                Err(format!("Failed to show AI response window: {}", e))
+#[specta::specta]
+fn show_ai_response_window(app_handle: AppHandle) -> Response<String> {
+    envelope::respond_sync("show_ai_response_window", || {
+        info!("⚡ FAST SHOW: AI response window...");
+    
+        if let Some(window) = app_handle.get_webview_window("ai-response") {
+            // Use concurrent operations for faster display
+            match window.show() {
+                Ok(_) => {
+                    // Ensure window is focused and visible for immediate use
+                    let _ = window.set_focus();
+                    info!("✅ FAST SHOW: AI response window shown and focused successfully");
+                    Ok("AI response window shown".to_string())
+                }
+                Err(e) => {
+                    error!("❌ FAST SHOW: Failed to show AI response window: {}", e);
+                    Err(format!("Failed to show AI response window: {}", e).into())
+                }
             }
+        } else {
+            warn!("⚠️ FAST SHOW: AI response window not found - creating new one immediately");
+            create_ai_response_window(app_handle).into_result()
         }
-    } else {
-        warn!("⚠️ FAST SHOW: AI response window not found - creating new one immediately");
-        create_ai_response_window(app_handle)
-    }
+    })
 }
 
 // Async version for non-blocking show operations
@@ -2197,654 +2712,747 @@ async fn show_ai_response_window_async(app_handle: AppHandle) -> Result<String,
         }
     } else {
         warn!("⚠️ ASYNC FAST SHOW: Window not found - creating new one");
-        create_ai_response_window(app_handle)
+        create_ai_response_window(app_handle).into_result().map_err(|e| e.to_string())
     }
 }
 
 #[tauri::command]
-fn hide_ai_response_window(app_handle: AppHandle) -> Result<String, String> {
-    info!("Hiding AI response window...");
+#[specta::specta]
+fn hide_ai_response_window(app_handle: AppHandle) -> Response<String> {
+    envelope::respond_sync("hide_ai_response_window", || {
+        info!("Hiding AI response window...");
     
-    if let Some(window) = app_handle.get_webview_window("ai-response") {
-        match window.hide() {
-            Ok(_) => {
-                info!("AI response window hidden successfully");
-                Ok("AI response window hidden".to_string())
-            }
-            Err(e) => {
-                error!("Failed to hide AI response window: {}", e);
-                Err(format!("Failed to hide AI response window: {}", e))
+        if let Some(window) = app_handle.get_webview_window("ai-response") {
+            match window.hide() {
+                Ok(_) => {
+                    info!("AI response window hidden successfully");
+                    Ok("AI response window hidden".to_string())
+                }
+                Err(e) => {
+                    error!("Failed to hide AI response window: {}", e);
+                    Err(format!("Failed to hide AI response window: {}", e).into())
+                }
             }
+        } else {
+            info!("AI response window not found - already hidden or not created");
+            Ok("AI response window not found".to_string())
         }
-    } else {
-        info!("AI response window not found - already hidden or not created");
-        Ok("AI response window not found".to_string())
-    }
+    })
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Type)]
 struct AiResponseData {
     message_type: String, // "stream", "complete", "error"
     text: Option<String>,
     error: Option<String>,
 }
 
-#[tauri::command]
-async fn send_ai_response_data(app_handle: AppHandle, data: AiResponseData) -> Result<String, String> {
-    info!("🚀 RUST DEBUG: send_ai_response_data called with message_type: {:?}", data.message_type);
-    
-    // Check if AI response window exists
-    if let Some(window) = app_handle.get_webview_window("ai-response") {
-        info!("✅ RUST DEBUG: AI response window found, attempting to show and send data");
-        
-        // First ensure the window is visible
-        if let Err(e) = window.show() {
-            error!("❌ RUST DEBUG: Failed to show AI response window: {}", e);
-        } else {
-            info!("✅ RUST DEBUG: AI response window shown successfully");
-        }
-        
-        // Send data to the AI response window via JavaScript evaluation
-        let js_code = match data.message_type.as_str() {
-            "stream" => {
-                let text = data.text.as_ref().map(|t| t.clone()).unwrap_or_default();
-                let escaped_text = text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
-                format!(r#"
-                    if (window.updateContent) {{
-                        window.updateContent('stream', {{ text: "{}" }});
-                    }} else {{
-                        console.log('updateContent function not found');
-                    }}
-                "#, escaped_text)
+// Coalesce provider tokens into ~30ms chunks before they reach the response window;
+// with emit_global each chunk is also broadcast as `ai-stream-token`
+fn ui_token_coalescer(app_handle: &AppHandle, emit_global: bool) -> ai_stream::TokenCoalescer {
+    let app_handle = app_handle.clone();
+    ai_stream::TokenCoalescer::spawn(move |chunk: String| {
+        let app_handle = app_handle.clone();
+        Box::pin(async move {
+            if emit_global {
+                let token_payload = events::AiStreamToken {
+                    text: chunk.clone(),
+                    token: chunk.clone(),
+                    timestamp: std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_millis() as u64,
+                };
+                let _ = app_handle.emit(events::AI_STREAM_TOKEN, token_payload);
             }
-            "stream-token" => {
-                let token = data.text.as_ref().map(|t| t.clone()).unwrap_or_default();
-                let escaped_token = token.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
-                format!(r#"
-                    if (window.updateContent) {{
-                        console.log('🎯 AI RESPONSE WINDOW: Received stream token:', '{}');
-                        window.updateContent('stream-token', {{ token: "{}" }});
-                    }} else {{
-                        console.log('updateContent function not found for stream-token');
-                    }}
-                "#, escaped_token.chars().take(50).collect::<String>(), escaped_token)
+
+            let data = AiResponseData {
+                message_type: "stream-token".to_string(),
+                text: Some(chunk),
+                error: None,
+            };
+            if let Err(e) = send_ai_response_data(app_handle, data).await.into_result() {
+                error!("Failed to send streaming token to UI: {}", e);
             }
-            "complete" => {
-                let text = data.text.as_ref().map(|t| t.clone()).unwrap_or_default();
-                let escaped_text = text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
-                format!(r#"
-                    if (window.updateContent) {{
-                        window.updateContent('complete', {{ text: "{}" }});
-                    }}
-                "#, escaped_text)
+        })
+    })
+}
+
+// Show a structured answer (coding solution, STAR answer): Markdown sections for the response
+// window plus the typed record, under its own event, for UIs that render it themselves
+async fn show_structured_answer<T: Serialize>(app_handle: &AppHandle, event: &str, record: &T, markdown: String) -> String {
+    if let Err(e) = app_handle.emit_to(ai_stream::RESPONSE_WINDOW_LABEL, event, record) {
+        warn!("Failed to emit {}: {}", event, e);
+    }
+    let data = AiResponseData {
+        message_type: "complete".to_string(),
+        text: Some(markdown.clone()),
+        error: None,
+    };
+    if let Err(e) = send_ai_response_data(app_handle.clone(), data).await.into_result() {
+        error!("Failed to send structured answer: {}", e);
+    }
+    let _ = app_handle.emit(events::AI_STREAM_COMPLETE, markdown.clone());
+    markdown
+}
+
+/// Show an answer reused for a duplicate question in the AI response window
+async fn show_reused_answer(app_handle: &AppHandle, answer: &str) {
+    let data = AiResponseData {
+        message_type: "complete".to_string(),
+        text: Some(answer.to_string()),
+        error: None,
+    };
+    if let Err(e) = send_ai_response_data(app_handle.clone(), data).await.into_result() {
+        error!("Failed to send reused answer: {}", e);
+    }
+    let _ = app_handle.emit(events::AI_STREAM_COMPLETE, answer.to_string());
+}
+
+#[tauri::command]
+#[specta::specta]
+async fn send_ai_response_data(app_handle: AppHandle, data: AiResponseData) -> Response<String> {
+    envelope::respond("send_ai_response_data", async move {
+        log::trace!("send_ai_response_data: message_type={}", data.message_type);
+    
+        // The window is created lazily after startup; make sure it exists before the first answer
+        if app_handle.get_webview_window(ai_stream::RESPONSE_WINDOW_LABEL).is_none() {
+            if let Err(e) = ensure_ai_response_window(&app_handle).await {
+                warn!("AI response window unavailable: {}", e);
             }
-            "error" => {
-                let error_msg = data.error.as_ref().map(|e| e.clone()).unwrap_or_default();
-                let escaped_error = error_msg.replace('\\', "\\\\").replace('"', "\\\"");
-                format!(r#"
-                    if (window.updateContent) {{
-                        window.updateContent('error', {{ error: "{}" }});
-                    }}
-                "#, escaped_error)
+        }
+    
+        let Some(window) = app_handle.get_webview_window(ai_stream::RESPONSE_WINDOW_LABEL) else {
+            warn!("AI response window not found");
+            return Err("AI response window not found".into());
+        };
+    
+        // Make sure the window is visible (cheap check, runs for every token)
+        if !window.is_visible().unwrap_or(false) {
+            if let Err(e) = window.show() {
+                error!("Failed to show AI response window: {}", e);
             }
-            _ => {
-                return Err("Invalid message type".to_string());
+        }
+    
+        match data.message_type.as_str() {
+            "stream-token" => {
+                ai_stream::push_token(&app_handle, data.text.as_deref().unwrap_or_default());
             }
-        };
-        
-        info!("🚀 RUST DEBUG: About to evaluate JavaScript code: {}", js_code.chars().take(200).collect::<String>() + "...");
-        
-        match window.eval(&js_code) {
-            Ok(_) => {
-                info!("✅ RUST DEBUG: JavaScript evaluation successful - AI response data sent successfully");
-                
+            "stream" | "complete" | "error" => {
+                let is_complete = data.message_type == "complete";
+                ai_stream::send_update(&app_handle, ai_stream::ResponseUpdate {
+                    message_type: data.message_type,
+                    text: data.text,
+                    error: data.error,
+                })?;
+            
                 // Keep window at fixed 550px height - no automatic content-based resizing
-                if data.message_type == "complete" {
-                    info!("🔄 RUST DEBUG: Content complete - ensuring window stays at 550px height");
-                    
-                    // Always maintain 550px height for consistent experience
+                if is_complete {
                     let fixed_height = 550u32;
-                    
-                    info!("📏 RUST DEBUG: Maintaining fixed height: {}px (no content-based auto-resize)", fixed_height);
-                    
-                    // Only resize if current height is different from 550px
-                    if let Some(window) = app_handle.get_webview_window("ai-response") {
-                        if let Ok(current_size) = window.outer_size() {
-                            if current_size.height != fixed_height {
-                                if let Err(resize_err) = resize_ai_response_window(app_handle.clone(), fixed_height) {
-                                    warn!("❌ RUST DEBUG: Failed to maintain 500px height: {}", resize_err);
-                                } else {
-                                    info!("✅ RUST DEBUG: Window height maintained at 550px");
-                                }
+                    if let Ok(current_size) = window.outer_size() {
+                        if current_size.height != fixed_height {
+                            if let Err(resize_err) = resize_ai_response_window(app_handle.clone(), fixed_height).into_result() {
+                                warn!("Failed to maintain {}px AI response window height: {}", fixed_height, resize_err);
                             }
                         }
                     }
                 }
-                
-                Ok("Data sent to AI response window".to_string())
-            }
-            Err(e) => {
-                error!("❌ RUST DEBUG: JavaScript evaluation failed - Failed to send data to AI response window: {}", e);
-                Err(format!("Failed to send data: {}", e))
             }
+            _ => return Err("Invalid message type".into()),
         }
-    } else {
-        warn!("AI response window not found");
-        Err("AI response window not found".to_string())
-    }
+    
+        Ok("Data sent to AI response window".to_string())
+    })
+    .await
 }
 
 /// NEW: Create AI response window positioned below main window with proper DPI-aware centering
 #[tauri::command]
-fn create_ai_response_window_enhanced_below(app_handle: AppHandle) -> Result<String, String> {
-    info!("✨ Creating AI response window positioned below main (enhanced DPI-aware)...");
-    
-    let main_window = match app_handle.get_webview_window("main") {
-        Some(window) => window,
-        None => {
-            error!("Main window not found");
-            return Err("Main window not found".to_string());
-        }
-    };
-    
-    // Get DPI scale factor for proper scaling
-    let monitor = main_window.current_monitor().map_err(|e| e.to_string())?
-        .ok_or_else(|| "No monitor found".to_string())?;
-    let scale_factor = monitor.scale_factor();
+#[specta::specta]
+fn create_ai_response_window_enhanced_below(app_handle: AppHandle) -> Response<String> {
+    envelope::respond_sync("create_ai_response_window_enhanced_below", || {
+        info!("✨ Creating AI response window positioned below main (enhanced DPI-aware)...");
+    
+        let main_window = match app_handle.get_webview_window("main") {
+            Some(window) => window,
+            None => {
+                error!("Main window not found");
+                return Err("Main window not found".into());
+            }
+        };
     
-    info!("🔍 DPI Scale factor: {:.2}", scale_factor);
+        // Get DPI scale factor for proper scaling
+        let monitor = main_window.current_monitor().map_err(|e| e.to_string())?
+            .ok_or_else(|| "No monitor found".to_string())?;
+        let scale_factor = monitor.scale_factor();
     
-    // Get main window measurements in physical pixels
-    let main_outer_position = main_window.outer_position().map_err(|e| e.to_string())?;
-    let main_outer_size = main_window.outer_size().map_err(|e| e.to_string())?;
+        info!("🔍 DPI Scale factor: {:.2}", scale_factor);
     
-    info!("🔍 Main window: {}x{} at ({}, {})", 
-          main_outer_size.width, main_outer_size.height, 
-          main_outer_position.x, main_outer_position.y);
+        // Get main window measurements in physical pixels
+        let main_outer_position = main_window.outer_position().map_err(|e| e.to_string())?;
+        let main_outer_size = main_window.outer_size().map_err(|e| e.to_string())?;
     
-    // AI response window dimensions - SAME WIDTH as main window
-    let ai_width = main_outer_size.width;
-    let ai_height = 550u32; // Increased default height for better visibility
+        info!("🔍 Main window: {}x{} at ({}, {})", 
+              main_outer_size.width, main_outer_size.height, 
+              main_outer_position.x, main_outer_position.y);
     
-    // CRITICAL FIX: Position calculation for proper centering with DPI awareness
-    // Calculate center X position of main window in logical coordinates first
-    let main_center_x_logical = (main_outer_position.x as f64 / scale_factor) + (main_outer_size.width as f64 / scale_factor / 2.0);
-    let ai_width_logical = ai_width as f64 / scale_factor;
+        // AI response window dimensions - SAME WIDTH as main window
+        let ai_width = main_outer_size.width;
+        let ai_height = 550u32; // Increased default height for better visibility
     
-    // Calculate AI window position to center it below main window (logical coordinates)
-    let ai_x_logical = main_center_x_logical - (ai_width_logical / 2.0);
+        // CRITICAL FIX: Position calculation for proper centering with DPI awareness
+        // Calculate center X position of main window in logical coordinates first
+        let main_center_x_logical = (main_outer_position.x as f64 / scale_factor) + (main_outer_size.width as f64 / scale_factor / 2.0);
+        let ai_width_logical = ai_width as f64 / scale_factor;
     
-    // CRITICAL FIX: Add proper DPI-aware gap between main window and AI response window
-    let base_gap_logical = 5.0; // 5px gap in logical pixels (visual consistency)
-    let ai_y_logical = (main_outer_position.y as f64 / scale_factor) + (main_outer_size.height as f64 / scale_factor) + base_gap_logical;
+        // Calculate AI window position to center it below main window (logical coordinates)
+        let ai_x_logical = main_center_x_logical - (ai_width_logical / 2.0);
     
-    info!("🔍 ENHANCED DPI GAP: {}px logical gap for visual consistency", base_gap_logical);
+        // CRITICAL FIX: Add proper DPI-aware gap between main window and AI response window
+        let base_gap_logical = 5.0; // 5px gap in logical pixels (visual consistency)
+        let ai_y_logical = (main_outer_position.y as f64 / scale_factor) + (main_outer_size.height as f64 / scale_factor) + base_gap_logical;
     
-    // Convert back to physical coordinates ONLY ONCE
-    let ai_x_physical = (ai_x_logical * scale_factor) as i32;
-    let ai_y_physical = (ai_y_logical * scale_factor) as i32;
+        info!("🔍 ENHANCED DPI GAP: {}px logical gap for visual consistency", base_gap_logical);
     
-    info!("🎯 DPI-FIXED Positioning (WITH 5PX LOGICAL GAP):");
-    info!("  - Scale factor: {:.2} (gap: {:.1}px logical)", scale_factor, base_gap_logical);
-    info!("  - Main center logical: {:.1}", main_center_x_logical);
-    info!("  - AI window logical: {:.1}x{:.1} at ({:.1}, {:.1})", ai_width_logical, ai_height as f64 / scale_factor, ai_x_logical, ai_y_logical);
-    info!("  - AI window physical: {}x{} at ({}, {})", ai_width, ai_height, ai_x_physical, ai_y_physical);
-    info!("  - Positioning: Directly below main window with no gap");
+        // Convert back to physical coordinates ONLY ONCE
+        let ai_x_physical = (ai_x_logical * scale_factor) as i32;
+        let ai_y_physical = (ai_y_logical * scale_factor) as i32;
     
-    // Create response window configuration
-    let window_url = if cfg!(debug_assertions) {
-        tauri::WebviewUrl::External("http://localhost:1420/ai-response.html".parse().unwrap())
-    } else {
-        tauri::WebviewUrl::App("ai-response.html".into())
-    };
+        info!("🎯 DPI-FIXED Positioning (WITH 5PX LOGICAL GAP):");
+        info!("  - Scale factor: {:.2} (gap: {:.1}px logical)", scale_factor, base_gap_logical);
+        info!("  - Main center logical: {:.1}", main_center_x_logical);
+        info!("  - AI window logical: {:.1}x{:.1} at ({:.1}, {:.1})", ai_width_logical, ai_height as f64 / scale_factor, ai_x_logical, ai_y_logical);
+        info!("  - AI window physical: {}x{} at ({}, {})", ai_width, ai_height, ai_x_physical, ai_y_physical);
+        info!("  - Positioning: Directly below main window with no gap");
     
-    let window_config = tauri::WebviewWindowBuilder::new(
-        &app_handle,
-        "ai-response",
-        window_url
-    )
-    .title("AI Response")
-    .inner_size(ai_width as f64, ai_height as f64)
-    .min_inner_size(200.0, 100.0)
-    .position(ai_x_physical as f64, ai_y_physical as f64)
-    .resizable(true)
-    .fullscreen(false)
-    .always_on_top(true)
-    .skip_taskbar(true)
-    .visible(true)
-    .decorations(false)
-    .transparent(true)
-    .shadow(false)  // Disable shadow to prevent visual gaps
-    .focused(true);
+        // Create response window configuration
+        let window_url = if cfg!(debug_assertions) {
+            tauri::WebviewUrl::External("http://localhost:1420/ai-response.html".parse().unwrap())
+        } else {
+            tauri::WebviewUrl::App("ai-response.html".into())
+        };
     
-    match window_config.build() {
-        Ok(window) => {
-            info!("✅ AI response window created below main with DPI-aware centering");
+        let style = appearance::window_style();
+        let window_config = tauri::WebviewWindowBuilder::new(
+            &app_handle,
+            "ai-response",
+            window_url
+        )
+        .title("AI Response")
+        .inner_size(ai_width as f64, ai_height as f64)
+        .min_inner_size(200.0, 100.0)
+        .resizable(true)
+        .fullscreen(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .visible(true)
+        .decorations(false)
+        .transparent(style.transparent)
+        .shadow(style.shadow)  // Off when transparent to prevent visual gaps
+        .theme(Some(style.theme))
+        .focused(true);
+        let window_config = window_manager::anchor_to_main(window_config, &main_window, ai_x_physical as f64, ai_y_physical as f64)?;
+    
+        match window_config.build() {
+            Ok(window) => {
+                info!("✅ AI response window created below main with DPI-aware centering");
             
-            // Set window capture protection
-            if let Err(e) = set_window_capture_protection(&window, true) {
-                error!("Failed to set window capture protection: {}", e);
-            }
+                // Set window capture protection
+                if let Err(e) = set_window_capture_protection(&window, true).into_result() {
+                    error!("Failed to set window capture protection: {}", e);
+                }
             
-            Ok("AI response window created below main".to_string())
-        }
-        Err(e) => {
-            error!("Failed to create AI response window: {}", e);
-            Err(format!("Failed to create AI response window: {}", e))
+                Ok("AI response window created below main".to_string())
+            }
+            Err(e) => {
+                error!("Failed to create AI response window: {}", e);
+                Err(format!("Failed to create AI response window: {}", e).into())
+            }
         }
-    }
+    })
 }
 
 /// NEW: Reset AI response window size and position it below main window with DPI-aware centering
 #[tauri::command]
-fn reset_ai_response_window_enhanced_below_size(app_handle: AppHandle) -> Result<String, String> {
-    info!("🔄 Resetting AI response window below main with DPI-aware centering...");
-    
-    if let Some(ai_window) = app_handle.get_webview_window("ai-response") {
-        if let Some(main_window) = app_handle.get_webview_window("main") {
-            // Get DPI scale factor
-            let monitor = main_window.current_monitor().map_err(|e| e.to_string())?
-                .ok_or_else(|| "No monitor found".to_string())?;
-            let scale_factor = monitor.scale_factor();
+#[specta::specta]
+fn reset_ai_response_window_enhanced_below_size(app_handle: AppHandle) -> Response<String> {
+    envelope::respond_sync("reset_ai_response_window_enhanced_below_size", || {
+        info!("🔄 Resetting AI response window below main with DPI-aware centering...");
+    
+        if app_handle.get_webview_window("ai-response").is_some() {
+            if let Some(main_window) = app_handle.get_webview_window("main") {
+                // Get DPI scale factor
+                let monitor = main_window.current_monitor().map_err(|e| e.to_string())?
+                    .ok_or_else(|| "No monitor found".to_string())?;
+                let scale_factor = monitor.scale_factor();
             
-            // Get main window measurements
-            let main_outer_position = main_window.outer_position().map_err(|e| e.to_string())?;
-            let main_outer_size = main_window.outer_size().map_err(|e| e.to_string())?;
+                // Get main window measurements
+                let main_outer_position = main_window.outer_position().map_err(|e| e.to_string())?;
+                let main_outer_size = main_window.outer_size().map_err(|e| e.to_string())?;
             
-            // AI response window dimensions - SAME WIDTH as main window
-            let ai_width = main_outer_size.width;
-            let ai_height = 500u32; // Reset to default height of 500px
+                // AI response window dimensions - SAME WIDTH as main window
+                let ai_width = main_outer_size.width;
+                let ai_height = 500u32; // Reset to default height of 500px
             
-            // CRITICAL FIX: Position calculation for proper centering with DPI awareness
-            // Calculate center X position of main window in logical coordinates first
-            let main_center_x_logical = (main_outer_position.x as f64 / scale_factor) + (main_outer_size.width as f64 / scale_factor / 2.0);
-            let ai_width_logical = ai_width as f64 / scale_factor;
+                // CRITICAL FIX: Position calculation for proper centering with DPI awareness
+                // Calculate center X position of main window in logical coordinates first
+                let main_center_x_logical = (main_outer_position.x as f64 / scale_factor) + (main_outer_size.width as f64 / scale_factor / 2.0);
+                let ai_width_logical = ai_width as f64 / scale_factor;
             
-            // Calculate AI window position to center it below main window (logical coordinates)
-            let ai_x_logical = main_center_x_logical - (ai_width_logical / 2.0);
-            // CRITICAL FIX: Add proper DPI-aware gap between main window and AI response window (reset)
-            let base_gap_logical = 5.0; // 5px gap in logical pixels (visual consistency)
-            let ai_y_logical = (main_outer_position.y as f64 / scale_factor) + (main_outer_size.height as f64 / scale_factor) + base_gap_logical;
+                // Calculate AI window position to center it below main window (logical coordinates)
+                let ai_x_logical = main_center_x_logical - (ai_width_logical / 2.0);
+                // CRITICAL FIX: Add proper DPI-aware gap between main window and AI response window (reset)
+                let base_gap_logical = 5.0; // 5px gap in logical pixels (visual consistency)
+                let ai_y_logical = (main_outer_position.y as f64 / scale_factor) + (main_outer_size.height as f64 / scale_factor) + base_gap_logical;
             
-            info!("🔍 RESET DPI GAP: {}px logical gap for visual consistency", base_gap_logical);
+                info!("🔍 RESET DPI GAP: {}px logical gap for visual consistency", base_gap_logical);
             
-            // Convert back to physical coordinates ONLY ONCE
-            let ai_x_physical = (ai_x_logical * scale_factor) as i32;
-            let ai_y_physical = (ai_y_logical * scale_factor) as i32;
+                // Convert back to physical coordinates ONLY ONCE
+                let ai_x_physical = (ai_x_logical * scale_factor) as i32;
+                let ai_y_physical = (ai_y_logical * scale_factor) as i32;
             
-            info!("🔄 DPI-FIXED Reset Positioning:");
-            info!("  - Scale factor: {:.2} (gap: {:.1}px logical - 5px visual gap)", scale_factor, base_gap_logical);
-            info!("  - Main center logical: {:.1}", main_center_x_logical);
-            info!("  - AI reset physical: {}x{} at ({}, {})", ai_width, ai_height, ai_x_physical, ai_y_physical);
-            info!("  - Gap calculation: 5px gap = {:.2}px logical", base_gap_logical);
+                info!("🔄 DPI-FIXED Reset Positioning:");
+                info!("  - Scale factor: {:.2} (gap: {:.1}px logical - 5px visual gap)", scale_factor, base_gap_logical);
+                info!("  - Main center logical: {:.1}", main_center_x_logical);
+                info!("  - AI reset physical: {}x{} at ({}, {})", ai_width, ai_height, ai_x_physical, ai_y_physical);
+                info!("  - Gap calculation: 5px gap = {:.2}px logical", base_gap_logical);
             
-            // Apply size and position in one operation
-            match ai_window.set_size(tauri::Size::Physical(tauri::PhysicalSize {
-                width: ai_width,
-                height: ai_height,
-            })) {
-                Ok(_) => {
-                    match ai_window.set_position(tauri::Position::Physical(tauri::PhysicalPosition {
-                        x: ai_x_physical,
-                        y: ai_y_physical,
-                    })) {
-                        Ok(_) => {
-                            info!("✅ AI response window reset below main with DPI-aware centering");
-                            Ok(format!("AI window reset: {}x{} at ({}, {})", ai_width, ai_height, ai_x_physical, ai_y_physical))
-                        }
-                        Err(e) => {
-                            error!("Failed to reset AI window position: {}", e);
-                            Err(format!("Failed to reset position: {}", e))
-                        }
-                    }
-                }
-                Err(e) => {
-                    error!("Failed to reset AI window size: {}", e);
-                    Err(format!("Failed to reset size: {}", e))
-                }
+                // Size and position are applied together by the resize coordinator
+                resize_coordinator::request(&app_handle, resize_coordinator::GeometryRequest {
+                    width: Some(ai_width),
+                    height: Some(ai_height),
+                    x: Some(ai_x_physical),
+                    y: Some(ai_y_physical),
+                });
+                info!("✅ AI response window reset below main with DPI-aware centering");
+                Ok(format!("AI window reset: {}x{} at ({}, {})", ai_width, ai_height, ai_x_physical, ai_y_physical))
+            } else {
+                error!("Main window not found for AI window reset");
+                Err("Main window not found".into())
             }
         } else {
-            error!("Main window not found for AI window reset");
-            Err("Main window not found".to_string())
+            warn!("AI response window not found for reset - creating new one");
+            create_ai_response_window_enhanced_below(app_handle).into_result()
         }
-    } else {
-        warn!("AI response window not found for reset - creating new one");
-        create_ai_response_window_enhanced_below(app_handle)
-    }
+    })
 }
 
 #[tauri::command]
-fn reset_ai_response_window_size(app_handle: AppHandle) -> Result<String, String> {
-    info!("🔄 Resetting AI response window to match main window width...");
-    
-    if let Some(ai_window) = app_handle.get_webview_window("ai-response") {
-        if let Some(main_window) = app_handle.get_webview_window("main") {
-            // Get main window size for width matching
-            let _main_size = main_window.outer_size().map_err(|e| {
-                error!("❌ Failed to get main window size: {}", e);
-                e.to_string()
-            })?;
+#[specta::specta]
+fn reset_ai_response_window_size(app_handle: AppHandle) -> Response<String> {
+    envelope::respond_sync("reset_ai_response_window_size", || {
+        info!("🔄 Resetting AI response window to match main window width...");
+    
+        if let Some(ai_window) = app_handle.get_webview_window("ai-response") {
+            if let Some(main_window) = app_handle.get_webview_window("main") {
+                // Get main window size for width matching
+                let _main_size = main_window.outer_size().map_err(|e| {
+                    error!("❌ Failed to get main window size: {}", e);
+                    e.to_string()
+                })?;
             
-            // Get monitor info for proper sizing
-            let monitor = ai_window.current_monitor().map_err(|e| e.to_string())?
-                .ok_or_else(|| "No monitor found".to_string())?;
-            let max_height = monitor.size();
-            let default_height = (max_height.height as f64 * 0.4) as u32; // 40% of screen height
+                // Get monitor info for proper sizing
+                let monitor = ai_window.current_monitor().map_err(|e| e.to_string())?
+                    .ok_or_else(|| "No monitor found".to_string())?;
+                let max_height = monitor.size();
+                let default_height = (max_height.height as f64 * 0.4) as u32; // 40% of screen height
             
-            // Calculate centered AI response window size (80% of main window width)
-            let main_inner_size = main_window.inner_size().map_err(|e| e.to_string())?;
-            let reset_width = (main_inner_size.width as f64 * 0.8) as u32; // Maintain 80% for centering
-            let reset_height = default_height.min(500).max(200); // Between 200-500px
+                // Calculate centered AI response window size (80% of main window width)
+                let main_inner_size = main_window.inner_size().map_err(|e| e.to_string())?;
+                let reset_width = (main_inner_size.width as f64 * 0.8) as u32; // Maintain 80% for centering
+                let reset_height = default_height.min(500).max(200); // Between 200-500px
             
-            info!("📐 Resetting AI response window: main_width={}px, reset_height={}px", reset_width, reset_height);
+                info!("📐 Resetting AI response window: main_width={}px, reset_height={}px", reset_width, reset_height);
             
-            match ai_window.set_size(tauri::Size::Physical(tauri::PhysicalSize {
-                width: reset_width,
-                height: reset_height,
-            })) {
-                Ok(_) => {
-                    info!("✅ AI response window reset to {}x{}", reset_width, reset_height);
-                    Ok(format!("AI response window reset to {}x{}", reset_width, reset_height))
-                }
-                Err(e) => {
-                    error!("❌ Failed to reset AI response window size: {}", e);
-                    Err(format!("Failed to reset window size: {}", e))
-                }
+                resize_coordinator::request(&app_handle, resize_coordinator::GeometryRequest {
+                    width: Some(reset_width),
+                    height: Some(reset_height),
+                    ..Default::default()
+                });
+                info!("✅ AI response window reset to {}x{}", reset_width, reset_height);
+                Ok(format!("AI response window reset to {}x{}", reset_width, reset_height))
+            } else {
+                error!("❌ Main window not found for width reference");
+                Err("Main window not found".into())
             }
         } else {
-            error!("❌ Main window not found for width reference");
-            Err("Main window not found".to_string())
+            error!("❌ AI response window not found for reset");
+            Err("AI response window not found".into())
         }
-    } else {
-        error!("❌ AI response window not found for reset");
-        Err("AI response window not found".to_string())
-    }
+    })
 }
 
 #[tauri::command]
-fn set_window_capture_protection(window: &tauri::WebviewWindow, protect: bool) -> Result<(), String> {
-    info!("Setting window capture protection to: {}", protect);
-    #[cfg(target_os = "windows")]
-    {
-        use windows_sys::Win32::Foundation::HWND;
-        let hwnd = window.hwnd().map_err(|e| e.to_string())?.0 as HWND;
-        let affinity = if protect { windows_sys::Win32::UI::WindowsAndMessaging::WDA_EXCLUDEFROMCAPTURE } else { windows_sys::Win32::UI::WindowsAndMessaging::WDA_NONE };
-        unsafe {
-            if windows_sys::Win32::UI::WindowsAndMessaging::SetWindowDisplayAffinity(hwnd, affinity) == 0 {
-                let error_code = windows_sys::Win32::Foundation::GetLastError();
-                error!("Failed to set window display affinity: {}", error_code);
-                return Err(format!("Failed to set window display affinity: {}", error_code));
-            }
-        }
-    }
-    #[cfg(not(target_os = "windows"))]
-    {
-        warn!("Window capture protection is only supported on Windows.");
-    }
-    Ok(())
+#[specta::specta]
+fn set_window_capture_protection(window: &tauri::WebviewWindow, protect: bool) -> Response<()> {
+    envelope::respond_sync("set_window_capture_protection", || {
+        info!("Setting window capture protection to: {}", protect);
+        Ok(capture_protection::apply(window, protect)?)
+    })
 }
 
 // Session Management Commands
 
 #[tauri::command]
-async fn connect_to_web_session(payload: SessionConnectionPayload) -> Result<SessionData, String> {
-    info!("Connecting to web session: {}", payload.session_id);
-    
-    let backend_url = std::env::var("MOCKMATE_BACKEND_URL")
-        .unwrap_or_else(|_| "https://mockmate-backend.onrender.com".to_string());
-    
-    let client = reqwest::Client::new();
-    
-    // Notify backend about desktop connection
-    let connection_response = client
-        .post(format!("{}/api/sessions/{}/connect-desktop", backend_url, payload.session_id))
-        .header("Authorization", format!("Bearer {}", payload.token))
-        .header("Content-Type", "application/json")
-        .json(&serde_json::json!({
-            "user_id": payload.user_id,
-            "desktop_version": env!("CARGO_PKG_VERSION"),
-            "platform": std::env::consts::OS
-        }))
-        .send()
-        .await
-        .map_err(|e| format!("Failed to connect to session: {}", e))?;
-    
-    if !connection_response.status().is_success() {
-        return Err(format!("Session connection failed: {}", connection_response.status()));
-    }
+#[specta::specta]
+async fn connect_to_web_session(payload: SessionConnectionPayload, idempotency_key: Option<String>) -> Response<SessionData> {
+    envelope::respond("connect_to_web_session", async move {
+        info!("Connecting to web session: {}", payload.session_id);
+    
+        let backend_url = std::env::var("MOCKMATE_BACKEND_URL")
+            .unwrap_or_else(|_| "https://mockmate-backend.onrender.com".to_string());
+    
+        let client = reqwest::Client::new();
+    
+        // Notify backend about desktop connection, once per idempotency key
+        let session_data: SessionData = idempotency::run("connect_to_web_session", idempotency_key, &payload.session_id, |attempt| async {
+            let connection_response = client
+                .post(format!("{}/api/sessions/{}/connect-desktop", backend_url, payload.session_id))
+                .header("Authorization", format!("Bearer {}", payload.token))
+                .header("Content-Type", "application/json")
+                .header(idempotency::HEADER, attempt.key)
+                .json(&serde_json::json!({
+                    "user_id": payload.user_id,
+                    "desktop_version": env!("CARGO_PKG_VERSION"),
+                    "platform": std::env::consts::OS
+                }))
+                .send_traced()
+                .await
+                .map_err(|e| e.context("Failed to connect to session"))?;
+        
+            if !connection_response.status().is_success() {
+                let status = connection_response.status();
+                return Err(AppError::new(error::ErrorCode::from_status(status), format!("Session connection failed: {}", status)));
+            }
+        
+            Ok(connection_response
+                .json()
+                .await
+                .map_err(|e| format!("Failed to parse session data: {}", e))?)
+        })
+        .await?;
     
-    let session_data: SessionData = connection_response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse session data: {}", e))?;
+        if let Some(system_prompt) = &payload.system_prompt {
+            session_prompt::set(&payload.session_id, system_prompt)?;
+        }
     
-    info!("Successfully connected to session: {} - {}", session_data.id, session_data.job_title);
-    Ok(session_data)
+        info!("Successfully connected to session: {} - {}", session_data.id, session_data.job_title);
+        Ok(session_data)
+    })
+    .await
 }
 
 #[tauri::command]
-async fn activate_web_session(payload: SessionConnectionPayload) -> Result<SessionActivationResponse, String> {
-    info!("Activating session with credit check: {}", payload.session_id);
-    
-    let backend_url = std::env::var("MOCKMATE_BACKEND_URL")
-        .unwrap_or_else(|_| "https://mockmate-backend.onrender.com".to_string());
-    
-    let client = reqwest::Client::new();
-    
-    // Activate session with credit deduction
-    let activation_response = client
-        .post(format!("{}/api/sessions/{}/activate", backend_url, payload.session_id))
-        .header("Authorization", format!("Bearer {}", payload.token))
-        .header("Content-Type", "application/json")
-        .json(&serde_json::json!({
-            "user_id": payload.user_id
-        }))
-        .send()
-        .await
-        .map_err(|e| format!("Failed to activate session: {}", e))?;
-    
-    let activation_result: SessionActivationResponse = activation_response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse activation response: {}", e))?;
-    
-    if activation_result.success {
-        info!("Session activated successfully. Credits remaining: {:?}", activation_result.remaining_credits);
-    } else {
-        warn!("Session activation failed: {}", activation_result.message);
-    }
+#[specta::specta]
+async fn activate_web_session(app_handle: AppHandle, payload: SessionConnectionPayload, idempotency_key: Option<String>) -> Response<SessionActivationResponse> {
+    envelope::respond("activate_web_session", async move {
+        info!("Activating session with credit check: {}", payload.session_id);
+    
+        let backend_url = std::env::var("MOCKMATE_BACKEND_URL")
+            .unwrap_or_else(|_| "https://mockmate-backend.onrender.com".to_string());
+    
+        let client = reqwest::Client::new();
+    
+        // Activate session with credit deduction, once per idempotency key
+        let activation: Result<SessionActivationResponse, AppError> = idempotency::run("activate_web_session", idempotency_key, &payload.session_id, |attempt| async {
+            let activation_response = client
+                .post(format!("{}/api/sessions/{}/activate", backend_url, payload.session_id))
+                .header("Authorization", format!("Bearer {}", payload.token))
+                .header("Content-Type", "application/json")
+                .header(idempotency::HEADER, attempt.key)
+                .json(&serde_json::json!({
+                    "user_id": payload.user_id
+                }))
+                .send_traced()
+                .await
+                .map_err(|e| e.context("Failed to activate session"))?;
+        
+            // Refusals (bad token, no credits) must not be mistaken for an outage
+            let status = activation_response.status();
+            if !status.is_success() {
+                let detail = activation_response.text().await.unwrap_or_default();
+                return Err(AppError::new(error::ErrorCode::from_status(status), format!("Session activation failed: {} {}", status, detail)));
+            }
+        
+            Ok(activation_response
+                .json()
+                .await
+                .map_err(|e| format!("Failed to parse activation response: {}", e))?)
+        })
+        .await;
+        let activation_result: SessionActivationResponse = match activation {
+            Ok(result) => {
+                if result.success {
+                    entitlement::remember(&payload.session_id, result.entitlement.as_ref());
+                }
+                result
+            }
+            // A session the backend confirmed recently stays usable through a short outage
+            Err(e) if e.retryable() => match entitlement::try_offline(&payload.session_id, &e.message) {
+                Some(grant) => SessionActivationResponse {
+                    success: true,
+                    message: format!("Backend unreachable; activated offline until {}", grant.usable_until.to_rfc3339()),
+                    session: None,
+                    remaining_credits: grant.credits_available.map(|credits| credits.max(0) as u32),
+                    entitlement: None,
+                    offline: true,
+                },
+                None => return Err(e),
+            },
+            Err(e) => return Err(e),
+        };
+    
+        if activation_result.success {
+            info!("Session activated successfully. Credits remaining: {:?}", activation_result.remaining_credits);
+            if let Some(credits) = activation_result.remaining_credits {
+                credits::remember(&payload.session_id, credits as i32);
+            }
+            audit::set_current_session(Some(payload.session_id.clone()));
+            session_prompt::activate(&app_handle.state::<AppState>(), &payload.session_id, payload.system_prompt.as_deref());
+            warmup::spawn_warm_up(&app_handle);
+            session_timeline::mark(&payload.session_id, session_timeline::MarkKind::SessionStarted, "Session started");
+            session_recorder::on_session_activated(&app_handle, &payload.session_id);
+            webhooks::dispatch(
+                webhooks::WebhookEvent::SessionStarted,
+                serde_json::json!({ "session_id": payload.session_id, "remaining_credits": activation_result.remaining_credits }),
+            );
+        } else {
+            warn!("Session activation failed: {}", activation_result.message);
+        }
     
-    Ok(activation_result)
+        Ok(activation_result)
+    })
+    .await
 }
 
 #[tauri::command]
-async fn get_session_info(session_id: String, token: String) -> Result<SessionData, String> {
-    info!("Getting session info: {}", session_id);
+#[specta::specta]
+async fn get_session_info(session_id: String, token: String) -> Response<SessionData> {
+    envelope::respond("get_session_info", async move {
+        info!("Getting session info: {}", session_id);
     
-    let backend_url = std::env::var("MOCKMATE_BACKEND_URL")
-        .unwrap_or_else(|_| "https://mockmate-backend.onrender.com".to_string());
+        let backend_url = std::env::var("MOCKMATE_BACKEND_URL")
+            .unwrap_or_else(|_| "https://mockmate-backend.onrender.com".to_string());
     
-    let client = reqwest::Client::new();
+        let client = reqwest::Client::new();
     
-    let response = client
-        .get(format!("{}/api/sessions/{}", backend_url, session_id))
-        .header("Authorization", format!("Bearer {}", token))
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch session info: {}", e))?;
+        let response = client
+            .get(format!("{}/api/sessions/{}", backend_url, session_id))
+            .header("Authorization", format!("Bearer {}", token))
+            .send_traced()
+            .await
+            .map_err(|e| e.context("Failed to fetch session info"))?;
     
-    if !response.status().is_success() {
-        return Err(format!("Failed to get session info: {}", response.status()));
-    }
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(AppError::new(error::ErrorCode::from_status(status), format!("Failed to get session info: {}", status)));
+        }
     
-    let session_data: SessionData = response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse session data: {}", e))?;
+        let session_data: SessionData = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse session data: {}", e))?;
     
-    Ok(session_data)
+        Ok(session_data)
+    })
+    .await
 }
 
 #[tauri::command]
-fn handle_protocol_launch(session_id: String, token: Option<String>, user_id: Option<String>, app_handle: AppHandle) -> Result<String, String> {
-    info!("Handling protocol launch for session: {}", session_id);
+#[specta::specta]
+fn handle_protocol_launch(session_id: String, token: Option<String>, user_id: Option<String>, app_handle: AppHandle) -> Response<String> {
+    envelope::respond_sync("handle_protocol_launch", || {
+        // Credit checkout returns are forwarded here as full URLs
+        if session_id.starts_with(credits::DEEP_LINK_PREFIX) {
+            credits::handle_deep_link(&app_handle, &session_id);
+            return Ok("Handled credits link".to_string());
+        }
     
-    // Parse the session_id from the protocol URL if it's a full URL
-    let clean_session_id = if session_id.starts_with("mockmate://session/") {
-        session_id.strip_prefix("mockmate://session/").unwrap_or(&session_id).to_string()
-    } else {
-        session_id.clone()
-    };
+        info!("Handling protocol launch for session: {}", session_id);
     
-    info!("Clean session ID: {}", clean_session_id);
+        // Parse the session_id from the protocol URL if it's a full URL
+        let clean_session_id = if session_id.starts_with("mockmate://session/") {
+            session_id.strip_prefix("mockmate://session/").unwrap_or(&session_id).to_string()
+        } else {
+            session_id.clone()
+        };
     
-    // Bring the main window to front and focus
-    if let Some(main_window) = app_handle.get_webview_window("main") {
-        if let Err(e) = main_window.show() {
-            error!("Failed to show main window: {}", e);
-        }
-        if let Err(e) = main_window.set_focus() {
-            error!("Failed to focus main window: {}", e);
-        }
+        info!("Clean session ID: {}", clean_session_id);
+    
+        // Bring the main window to front and focus
+        if let Some(main_window) = app_handle.get_webview_window("main") {
+            if let Err(e) = main_window.show() {
+                error!("Failed to show main window: {}", e);
+            }
+            if let Err(e) = main_window.set_focus() {
+                error!("Failed to focus main window: {}", e);
+            }
         
-        // Send session info to the frontend
-        let session_launch_data = serde_json::json!({
-            "session_id": clean_session_id,
-            "token": token,
-            "user_id": user_id,
-            "launched_at": chrono::Utc::now().to_rfc3339()
-        });
+            // Send session info to the frontend
+            let session_launch_data = events::SessionLaunch {
+                session_id: clean_session_id.clone(),
+                token,
+                user_id,
+                launched_at: chrono::Utc::now().to_rfc3339(),
+                ..Default::default()
+            };
         
-        if let Err(e) = app_handle.emit("session-launch", session_launch_data) {
-            error!("Failed to emit session-launch event: {}", e);
-        }
+            if let Err(e) = app_handle.emit(events::SESSION_LAUNCH, session_launch_data) {
+                error!("Failed to emit session-launch event: {}", e);
+            }
         
-        info!("Protocol launch handled successfully for session: {}", clean_session_id);
-        Ok(format!("Launched session: {}", clean_session_id))
-    } else {
-        error!("Main window not found for protocol launch");
-        Err("Main window not found".to_string())
-    }
+            info!("Protocol launch handled successfully for session: {}", clean_session_id);
+            Ok(format!("Launched session: {}", clean_session_id))
+        } else {
+            error!("Main window not found for protocol launch");
+            Err("Main window not found".into())
+        }
+    })
 }
 
 // New Shared Database Session Commands
 
 #[tauri::command]
-async fn connect_session(session_id: String) -> Result<crate::database::SessionWithUser, String> {
-    info!("🔗 Connecting to session: {}", session_id);
-    
-    // Initialize database connection if not already done
-    crate::database::initialize_database().await?;
-    
-    // Get session details with user info
-    let session_info = crate::database::get_session_with_user_info(&session_id).await?;
+#[specta::specta]
+async fn connect_session(session_id: String, system_prompt: Option<String>) -> Response<crate::database::SessionWithUser> {
+    envelope::respond("connect_session", async move {
+        info!("🔗 Connecting to session: {}", session_id);
+    
+        // Initialize database connection if not already done
+        crate::database::initialize_database().await?;
+    
+        // Get session details with user info
+        let session_info = crate::database::get_session_with_user_info(&session_id).await?;
+        if let Some(system_prompt) = &system_prompt {
+            session_prompt::set(&session_id, system_prompt)?;
+        }
     
-    info!("✅ Successfully connected to session: {}", session_info.session_name);
-    Ok(session_info)
+        info!("✅ Successfully connected to session: {}", session_info.session_name);
+        Ok(session_info)
+    })
+    .await
 }
 
 #[tauri::command]
-async fn activate_session_cmd(session_id: String) -> Result<String, String> {
-    info!("🚀 Activating session: {}", session_id);
-    
-    // Activate session and deduct credits
-    crate::database::activate_session(&session_id).await?;
-    
-    info!("✅ Session activated successfully");
-    Ok("Session activated successfully".to_string())
+#[specta::specta]
+async fn activate_session_cmd(app_handle: AppHandle, session_id: String, idempotency_key: Option<String>, system_prompt: Option<String>) -> Response<String> {
+    envelope::respond("activate_session_cmd", async move {
+        info!("🚀 Activating session: {}", session_id);
+    
+        // Activate session and deduct credits, once per idempotency key
+        let session = session_id.as_str();
+        let activation = idempotency::run("activate_session_cmd", idempotency_key, session, |attempt| async move {
+            if attempt.resumed && crate::database::activation_charged(session).await? {
+                info!("💳 Earlier activation of {} already went through; not charging again", session);
+                return Ok(());
+            }
+            crate::database::activate_session(session).await
+        })
+        .await;
+        if let Err(e) = activation {
+            error!("Session activation failed: {}", e.message);
+            telemetry::record_error("session_activation_failed");
+            // A session the backend confirmed recently stays usable through a short outage
+            if !e.retryable() || entitlement::try_offline(&session_id, &e.message).is_none() {
+                return Err(e);
+            }
+        }
+        audit::set_current_session(Some(session_id.clone()));
+        session_prompt::activate(&app_handle.state::<AppState>(), &session_id, system_prompt.as_deref());
+        warmup::spawn_warm_up(&app_handle);
+        session_timeline::mark(&session_id, session_timeline::MarkKind::SessionStarted, "Session started");
+        session_recorder::on_session_activated(&app_handle, &session_id);
+        webhooks::dispatch(webhooks::WebhookEvent::SessionStarted, serde_json::json!({ "session_id": session_id }));
+    
+        telemetry::record_feature("session_activated");
+        info!("✅ Session activated successfully");
+        Ok("Session activated successfully".to_string())
+    })
+    .await
 }
 
 #[tauri::command]
-async fn disconnect_session_cmd(session_id: String) -> Result<String, String> {
-    info!("🔌 Disconnecting from session: {}", session_id);
-    
-    crate::database::disconnect_session(&session_id).await?;
-    
-    info!("✅ Session disconnected successfully");
-    Ok("Session disconnected successfully".to_string())
+#[specta::specta]
+async fn disconnect_session_cmd(app_handle: AppHandle, session_id: String) -> Response<String> {
+    envelope::respond("disconnect_session_cmd", async move {
+        info!("🔌 Disconnecting from session: {}", session_id);
+    
+        crate::database::disconnect_session(&session_id).await?;
+        audit::set_current_session(None);
+        session_prompt::deactivate(&app_handle.state::<AppState>());
+        session_auth::forget(&session_id);
+        session_timeline::mark(&session_id, session_timeline::MarkKind::SessionEnded, "Session ended");
+        session_recorder::on_session_ended(&app_handle, &session_id);
+        pacing::on_session_ended(&session_id);
+        context_builder::clear();
+        webhooks::dispatch(webhooks::WebhookEvent::SessionFinalized, serde_json::json!({ "session_id": session_id }));
+    
+        info!("✅ Session disconnected successfully");
+        Ok("Session disconnected successfully".to_string())
+    })
+    .await
 }
 
 // Frontend compatibility command wrappers
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Type)]
 struct SessionValidationResult {
     valid: bool,
     message: String,
 }
 
 #[tauri::command]
-async fn validate_session_id(session_id: String) -> Result<SessionValidationResult, String> {
-    info!("🔍 Validating session ID: {}", session_id);
-    
-    // Validate UUID format
-    let uuid_pattern = regex::Regex::new(r"^[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$")
-        .map_err(|e| format!("Regex error: {}", e))?;
-    
-    if !uuid_pattern.is_match(&session_id.to_lowercase()) {
-        return Ok(SessionValidationResult {
-            valid: false,
-            message: "Invalid session ID format - must be a valid UUID".to_string(),
-        });
-    }
+#[specta::specta]
+async fn validate_session_id(session_id: String) -> Response<SessionValidationResult> {
+    envelope::respond("validate_session_id", async move {
+        info!("🔍 Validating session ID: {}", session_id);
+    
+        // Validate UUID format
+        let uuid_pattern = regex::Regex::new(r"^[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$")
+            .map_err(|e| format!("Regex error: {}", e))?;
+    
+        if !uuid_pattern.is_match(&session_id.to_lowercase()) {
+            return Ok(SessionValidationResult {
+                valid: false,
+                message: "Invalid session ID format - must be a valid UUID".to_string(),
+            });
+        }
     
-    // Try to initialize database and validate session exists
-    match crate::database::initialize_database().await {
-        Ok(()) => {
-            match crate::database::get_session_with_user_info(&session_id).await {
-                Ok(_) => Ok(SessionValidationResult {
-                    valid: true,
-                    message: "Session ID is valid and exists".to_string(),
-                }),
-                Err(e) => {
-                    if e.contains("not found") {
-                        Ok(SessionValidationResult {
-                            valid: false,
-                            message: "Session not found".to_string(),
-                        })
-                    } else {
-                        Ok(SessionValidationResult {
-                            valid: false,
-                            message: format!("Validation error: {}", e),
-                        })
+        // Try to initialize database and validate session exists
+        match crate::database::initialize_database().await {
+            Ok(()) => {
+                match crate::database::get_session_with_user_info(&session_id).await {
+                    Ok(_) => Ok(SessionValidationResult {
+                        valid: true,
+                        message: "Session ID is valid and exists".to_string(),
+                    }),
+                    Err(e) => {
+                        if e.contains("not found") {
+                            Ok(SessionValidationResult {
+                                valid: false,
+                                message: "Session not found".to_string(),
+                            })
+                        } else {
+                            Ok(SessionValidationResult {
+                                valid: false,
+                                message: format!("Validation error: {}", e),
+                            })
+                        }
                     }
                 }
             }
+            Err(e) => {
+                warn!("Database not available for validation: {}", e);
+                // If database is not available, just validate format
+                Ok(SessionValidationResult {
+                    valid: true,
+                    message: "Session ID format is valid (database validation skipped)".to_string(),
+                })
+            }
         }
-        Err(e) => {
-            warn!("Database not available for validation: {}", e);
-            // If database is not available, just validate format
-            Ok(SessionValidationResult {
-                valid: true,
-                message: "Session ID format is valid (database validation skipped)".to_string(),
-            })
-        }
-    }
-}
-
-#[tauri::command]
-async fn activate_session(session_id: String) -> Result<bool, String> {
-    info!("🚀 Activating session (frontend compatibility): {}", session_id);
-    
-    // Call the existing activate_session_cmd and return boolean result
-    match activate_session_cmd(session_id).await {
-        Ok(_) => Ok(true),
-        Err(e) => {
-            error!("Session activation failed: {}", e);
-            Err(e)
-        }
-    }
-}
-
-#[tauri::command]
-async fn disconnect_session(session_id: String) -> Result<String, String> {
-    info!("🔌 Disconnecting from session (frontend compatibility): {}", session_id);
-    
-    // Call the existing disconnect_session_cmd
-    disconnect_session_cmd(session_id).await
+    })
+    .await
 }
 
 // Diagnostic command for database connectivity
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Type)]
 struct DatabaseDiagnostic {
     database_connected: bool,
     connection_error: Option<String>,
@@ -2854,78 +3462,86 @@ struct DatabaseDiagnostic {
 }
 
 #[tauri::command]
-async fn diagnose_database() -> Result<DatabaseDiagnostic, String> {
-    info!("🔍 Running database diagnostics");
-    
-    let mut diagnostic = DatabaseDiagnostic {
-        database_connected: false,
-        connection_error: None,
-        tables_exist: false,
-        sample_data_count: None,
-        test_query_result: None,
-    };
+#[specta::specta]
+async fn diagnose_database() -> Response<DatabaseDiagnostic> {
+    envelope::respond("diagnose_database", async move {
+        info!("🔍 Running database diagnostics");
+    
+        let mut diagnostic = DatabaseDiagnostic {
+            database_connected: false,
+            connection_error: None,
+            tables_exist: false,
+            sample_data_count: None,
+            test_query_result: None,
+        };
     
-    // Test database initialization
-    match crate::database::initialize_database().await {
-        Ok(()) => {
-            diagnostic.database_connected = true;
+        // Test database initialization
+        match crate::database::initialize_database().await {
+            Ok(()) => {
+                diagnostic.database_connected = true;
             
-            // Test if tables exist by trying to query sessions table
-            match crate::database::shared::DATABASE_POOL.get().await {
-                Ok(client) => {
-                    // Check if sessions table exists and get count
-                    match client.query_one("SELECT COUNT(*) as count FROM sessions", &[]).await {
-                        Ok(row) => {
-                            diagnostic.tables_exist = true;
-                            let count: i64 = row.get("count");
-                            diagnostic.sample_data_count = Some(count);
-                            diagnostic.test_query_result = Some(format!("Found {} sessions in database", count));
-                            info!("✅ Database diagnostic passed: {} sessions found", count);
-                        }
-                        Err(e) => {
-                            diagnostic.test_query_result = Some(format!("Table query failed: {}", e));
-                            warn!("⚠️ Sessions table query failed: {}", e);
+                // Test if tables exist by trying to query sessions table
+                match crate::database::shared::pool()?.get().await {
+                    Ok(client) => {
+                        // Check if sessions table exists and get count
+                        match client.query_one("SELECT COUNT(*) as count FROM sessions", &[]).await {
+                            Ok(row) => {
+                                diagnostic.tables_exist = true;
+                                let count: i64 = row.get("count");
+                                diagnostic.sample_data_count = Some(count);
+                                diagnostic.test_query_result = Some(format!("Found {} sessions in database", count));
+                                info!("✅ Database diagnostic passed: {} sessions found", count);
+                            }
+                            Err(e) => {
+                                diagnostic.test_query_result = Some(format!("Table query failed: {}", e));
+                                warn!("⚠️ Sessions table query failed: {}", e);
+                            }
                         }
                     }
-                }
-                Err(e) => {
-                    diagnostic.connection_error = Some(format!("Pool connection failed: {}", e));
-                    warn!("⚠️ Database pool connection failed: {}", e);
+                    Err(e) => {
+                        diagnostic.connection_error = Some(format!("Pool connection failed: {}", e));
+                        warn!("⚠️ Database pool connection failed: {}", e);
+                    }
                 }
             }
+            Err(e) => {
+                diagnostic.connection_error = Some(e.clone());
+                warn!("⚠️ Database initialization failed: {}", e);
+            }
         }
-        Err(e) => {
-            diagnostic.connection_error = Some(e.clone());
-            warn!("⚠️ Database initialization failed: {}", e);
-        }
-    }
     
-    Ok(diagnostic)
+        Ok(diagnostic)
+    })
+    .await
 }
 
 #[tauri::command]
-async fn test_session_query(session_id: String) -> Result<String, String> {
-    info!("🧪 Testing session query for ID: {}", session_id);
-    
-    // First run diagnostic
-    let diagnostic = diagnose_database().await?;
-    if !diagnostic.database_connected {
-        return Err(format!("Database not connected: {}", diagnostic.connection_error.unwrap_or("Unknown error".to_string())));
-    }
-    
-    // Try to get session info
-    match crate::database::get_session_with_user_info(&session_id).await {
-        Ok(session_info) => {
-            Ok(format!("✅ Session found: {} (User: {}, Credits: {})", 
-                session_info.session_name, 
-                session_info.user_details.name,
-                session_info.credits_available
-            ))
+#[specta::specta]
+async fn test_session_query(session_id: String) -> Response<String> {
+    envelope::respond("test_session_query", async move {
+        info!("🧪 Testing session query for ID: {}", session_id);
+    
+        // First run diagnostic
+        let diagnostic = diagnose_database().await.into_result()?;
+        if !diagnostic.database_connected {
+            return Err(format!("Database not connected: {}", diagnostic.connection_error.unwrap_or("Unknown error".to_string())).into());
         }
-        Err(e) => {
-            Err(format!("❌ Session query failed: {}", e))
+    
+        // Try to get session info
+        match crate::database::get_session_with_user_info(&session_id).await {
+            Ok(session_info) => {
+                Ok(format!("✅ Session found: {} (User: {}, Credits: {})", 
+                    session_info.session_name, 
+                    session_info.user_details.name,
+                    session_info.credits_available
+                ))
+            }
+            Err(e) => {
+                Err(format!("❌ Session query failed: {}", e).into())
+            }
         }
-    }
+    })
+    .await
 }
 
 // Timer management command
@@ -2941,74 +3557,87 @@ struct UpdateTimerPayload {
 }
 
 #[tauri::command]
-async fn update_session_timer(session_id: String, elapsed_minutes: i32, is_final: Option<bool>) -> Result<String, String> {
-    let is_final = is_final.unwrap_or(false);
+#[specta::specta]
+async fn update_session_timer(session_id: String, elapsed_minutes: i32, is_final: Option<bool>) -> Response<String> {
+    guard::respond("update_session_timer", &[Require::Session(session_id.clone())], async move {
+        let is_final = is_final.unwrap_or(false);
     
-    if is_final {
-        info!("⏱️ Updating session timer (FINAL): {} - {} minutes", session_id, elapsed_minutes);
-    } else {
-        info!("⏱️ Updating session timer: {} - {} minutes", session_id, elapsed_minutes);
-    }
+        if is_final {
+            info!("⏱️ Updating session timer (FINAL): {} - {} minutes", session_id, elapsed_minutes);
+        } else {
+            info!("⏱️ Updating session timer: {} - {} minutes", session_id, elapsed_minutes);
+        }
     
-    // For now, we'll just log the timer update. Later we can add database persistence.
-    // This could save to the sessions table's total_duration_minutes field
+        session_timeline::mark(
+            &session_id,
+            session_timeline::MarkKind::Timer,
+            format!("{} min{}", elapsed_minutes, if is_final { " (final)" } else { "" }),
+        );
+
+        // For now, we'll just log the timer update. Later we can add database persistence.
+        // This could save to the sessions table's total_duration_minutes field
     
-    // TODO: Implement database update for timer state
-    // UPDATE sessions SET total_duration_minutes = elapsed_minutes WHERE id = session_id;
+        // TODO: Implement database update for timer state
+        // UPDATE sessions SET total_duration_minutes = elapsed_minutes WHERE id = session_id;
     
-    if is_final {
-        info!("✅ Final session timer saved: {} minutes", elapsed_minutes);
-        Ok(format!("Final session timer saved: {} minutes", elapsed_minutes))
-    } else {
-        info!("✅ Session timer updated: {} minutes", elapsed_minutes);
-        Ok(format!("Session timer updated: {} minutes", elapsed_minutes))
-    }
+        if is_final {
+            info!("✅ Final session timer saved: {} minutes", elapsed_minutes);
+            Ok(format!("Final session timer saved: {} minutes", elapsed_minutes))
+        } else {
+            info!("✅ Session timer updated: {} minutes", elapsed_minutes);
+            Ok(format!("Session timer updated: {} minutes", elapsed_minutes))
+        }
+    })
+    .await
 }
 
 // Window management commands
 
 #[tauri::command]
-fn resize_main_window(app_handle: AppHandle, width: u32, height: u32) -> Result<String, String> {
-    info!("📐 Resizing main window to: {}x{}", width, height);
-    
-    if let Some(window) = app_handle.get_webview_window("main") {
-        // Get current size for comparison
-        let current_size = window.outer_size().map_err(|e| {
-            error!("❌ Failed to get current main window size: {}", e);
-            e.to_string()
-        })?;
+#[specta::specta]
+fn resize_main_window(app_handle: AppHandle, width: u32, height: u32) -> Response<String> {
+    envelope::respond_sync("resize_main_window", || {
+        info!("📐 Resizing main window to: {}x{}", width, height);
+    
+        if let Some(window) = app_handle.get_webview_window("main") {
+            // Get current size for comparison
+            let current_size = window.outer_size().map_err(|e| {
+                error!("❌ Failed to get current main window size: {}", e);
+                e.to_string()
+            })?;
         
-        info!("📊 Main window resize: current={}x{}, requested={}x{}", 
-              current_size.width, current_size.height, width, height);
+            info!("📊 Main window resize: current={}x{}, requested={}x{}", 
+                  current_size.width, current_size.height, width, height);
         
-        match window.set_size(tauri::Size::Physical(tauri::PhysicalSize {
-            width,
-            height,
-        })) {
-            Ok(_) => {
-                info!("✅ Main window successfully resized to: {}x{}", width, height);
+            match window.set_size(tauri::Size::Physical(tauri::PhysicalSize {
+                width,
+                height,
+            })) {
+                Ok(_) => {
+                    info!("✅ Main window successfully resized to: {}x{}", width, height);
                 
-                // Verify the resize worked
-                match window.outer_size() {
-                    Ok(new_size) => {
-                        info!("🔍 Post-resize verification: actual new size is {}x{}", new_size.width, new_size.height);
-                        Ok(format!("Main window resized to {}x{}", new_size.width, new_size.height))
-                    }
-                    Err(e) => {
-                        warn!("❌ Failed to verify main window resize: {}", e);
-                        Ok(format!("Main window resize attempted: {}x{}", width, height))
+                    // Verify the resize worked
+                    match window.outer_size() {
+                        Ok(new_size) => {
+                            info!("🔍 Post-resize verification: actual new size is {}x{}", new_size.width, new_size.height);
+                            Ok(format!("Main window resized to {}x{}", new_size.width, new_size.height))
+                        }
+                        Err(e) => {
+                            warn!("❌ Failed to verify main window resize: {}", e);
+                            Ok(format!("Main window resize attempted: {}x{}", width, height))
+                        }
                     }
                 }
+                Err(e) => {
+                    error!("❌ Failed to resize main window: {}", e);
+                    Err(format!("Failed to resize main window: {}", e).into())
+                }
             }
-            Err(e) => {
-                error!("❌ Failed to resize main window: {}", e);
-                Err(format!("Failed to resize main window: {}", e))
-            }
+        } else {
+            error!("❌ Main window not found for resize");
+            Err("Main window not found".into())
         }
-    } else {
-        error!("❌ Main window not found for resize");
-        Err("Main window not found".to_string())
-    }
+    })
 }
 
 // ===== NEW WINDOW MANAGEMENT COMMANDS FOR HOTKEYS =====
@@ -3028,433 +3657,295 @@ struct ResizeWindowPayload {
 }
 
 #[tauri::command]
-fn move_window_relative(app_handle: AppHandle, delta_x: i32, delta_y: i32) -> Result<String, String> {
-    info!("📍 Moving main window by: ({}, {})", delta_x, delta_y);
-    
-    if let Some(window) = app_handle.get_webview_window("main") {
-        // Get current position
-        let current_position = window.outer_position().map_err(|e| {
-            error!("❌ Failed to get current window position: {}", e);
-            e.to_string()
-        })?;
+#[specta::specta]
+fn move_window_relative(app_handle: AppHandle, delta_x: i32, delta_y: i32) -> Response<String> {
+    envelope::respond_sync("move_window_relative", || {
+        info!("📍 Moving main window by: ({}, {})", delta_x, delta_y);
+        if window_manager::positioning_strategy() == window_manager::PositioningStrategy::CompositorRelative {
+            return Err("Windows cannot be moved by the app on Wayland; drag the window instead".into());
+        }
+    
+        if let Some(window) = app_handle.get_webview_window("main") {
+            // Get current position
+            let current_position = window.outer_position().map_err(|e| {
+                error!("❌ Failed to get current window position: {}", e);
+                e.to_string()
+            })?;
         
-        // Calculate new position
-        let new_x = current_position.x + delta_x;
-        let new_y = current_position.y + delta_y;
+            // Calculate new position
+            let new_x = current_position.x + delta_x;
+            let new_y = current_position.y + delta_y;
         
-        info!("📊 Window move: current=({}, {}), delta=({}, {}), new=({}, {})", 
-              current_position.x, current_position.y, delta_x, delta_y, new_x, new_y);
+            info!("📊 Window move: current=({}, {}), delta=({}, {}), new=({}, {})", 
+                  current_position.x, current_position.y, delta_x, delta_y, new_x, new_y);
         
-        match window.set_position(tauri::Position::Physical(tauri::PhysicalPosition {
-            x: new_x,
-            y: new_y,
-        })) {
-            Ok(_) => {
-                info!("✅ Main window successfully moved to: ({}, {})", new_x, new_y);
+            match window.set_position(tauri::Position::Physical(tauri::PhysicalPosition {
+                x: new_x,
+                y: new_y,
+            })) {
+                Ok(_) => {
+                    info!("✅ Main window successfully moved to: ({}, {})", new_x, new_y);
                 
-                // Verify the move worked
-                match window.outer_position() {
-                    Ok(new_position) => {
-                        info!("🔍 Post-move verification: actual new position is ({}, {})", new_position.x, new_position.y);
-                        Ok(format!("Window moved to ({}, {})", new_position.x, new_position.y))
-                    }
-                    Err(e) => {
-                        warn!("❌ Failed to verify window move: {}", e);
-                        Ok(format!("Window move attempted to ({}, {})", new_x, new_y))
+                    // Verify the move worked
+                    match window.outer_position() {
+                        Ok(new_position) => {
+                            info!("🔍 Post-move verification: actual new position is ({}, {})", new_position.x, new_position.y);
+                            Ok(format!("Window moved to ({}, {})", new_position.x, new_position.y))
+                        }
+                        Err(e) => {
+                            warn!("❌ Failed to verify window move: {}", e);
+                            Ok(format!("Window move attempted to ({}, {})", new_x, new_y))
+                        }
                     }
                 }
+                Err(e) => {
+                    error!("❌ Failed to move main window: {}", e);
+                    Err(format!("Failed to move main window: {}", e).into())
+                }
             }
-            Err(e) => {
-                error!("❌ Failed to move main window: {}", e);
-                Err(format!("Failed to move main window: {}", e))
-            }
+        } else {
+            error!("❌ Main window not found for move");
+            Err("Main window not found".into())
         }
-    } else {
-        error!("❌ Main window not found for move");
-        Err("Main window not found".to_string())
-    }
+    })
 }
 #[tauri::command]
-fn resize_window_scale(app_handle: AppHandle, width: u32, height: u32) -> Result<String, String> {
-    info!("📏 Resizing main window to responsive size: {}x{}", width, height);
-    
-    if let Some(window) = app_handle.get_webview_window("main") {
-        // Get current size for comparison
-        let current_size = window.outer_size().map_err(|e| {
-            error!("❌ Failed to get current window size: {}", e);
-            e.to_string()
-        })?;
+#[specta::specta]
+fn resize_window_scale(app_handle: AppHandle, width: u32, height: u32) -> Response<String> {
+    envelope::respond_sync("resize_window_scale", || {
+        info!("📏 Resizing main window to responsive size: {}x{}", width, height);
+    
+        if let Some(window) = app_handle.get_webview_window("main") {
+            // Get current size for comparison
+            let current_size = window.outer_size().map_err(|e| {
+                error!("❌ Failed to get current window size: {}", e);
+                e.to_string()
+            })?;
         
-        // Get monitor info for DPI-aware scaling
-        let monitor = window.current_monitor().map_err(|e| e.to_string())?
-            .ok_or_else(|| "No monitor found".to_string())?;
-        let scale_factor = monitor.scale_factor();
-        let monitor_size = monitor.size();
+            // Get monitor info for DPI-aware scaling
+            let monitor = window.current_monitor().map_err(|e| e.to_string())?
+                .ok_or_else(|| "No monitor found".to_string())?;
+            let scale_factor = monitor.scale_factor();
+            let monitor_size = monitor.size();
         
-        // Apply DPI scaling to the requested dimensions
-        let dpi_adjusted_width = ((width as f64) * scale_factor) as u32;
-        let dpi_adjusted_height = ((height as f64) * scale_factor) as u32;
+            // Apply DPI scaling to the requested dimensions
+            let dpi_adjusted_width = ((width as f64) * scale_factor) as u32;
+            let dpi_adjusted_height = ((height as f64) * scale_factor) as u32;
         
-        // Ensure minimum and maximum constraints based on monitor size
-        let min_width = 400;
-        let min_height = 200;
-        let max_width = ((monitor_size.width as f64) * 0.9) as u32;
-        let max_height = ((monitor_size.height as f64) * 0.9) as u32;
+            // Ensure minimum and maximum constraints based on monitor size
+            let min_width = 400;
+            let min_height = 200;
+            let max_width = ((monitor_size.width as f64) * 0.9) as u32;
+            let max_height = ((monitor_size.height as f64) * 0.9) as u32;
         
-        let final_width = dpi_adjusted_width.max(min_width).min(max_width);
-        let final_height = dpi_adjusted_height.max(min_height).min(max_height);
+            let final_width = dpi_adjusted_width.max(min_width).min(max_width);
+            let final_height = dpi_adjusted_height.max(min_height).min(max_height);
         
-        info!("📊 Responsive resize: current={}x{}, requested={}x{}, DPI_scale={:.2}, DPI_adjusted={}x{}, final={}x{}", 
-              current_size.width, current_size.height, width, height, scale_factor,
-              dpi_adjusted_width, dpi_adjusted_height, final_width, final_height);
+            info!("📊 Responsive resize: current={}x{}, requested={}x{}, DPI_scale={:.2}, DPI_adjusted={}x{}, final={}x{}", 
+                  current_size.width, current_size.height, width, height, scale_factor,
+                  dpi_adjusted_width, dpi_adjusted_height, final_width, final_height);
         
-        match window.set_size(tauri::Size::Physical(tauri::PhysicalSize {
-            width: final_width,
-            height: final_height,
-        })) {
-            Ok(_) => {
-                info!("✅ Main window successfully resized to responsive size: {}x{}", final_width, final_height);
+            match window.set_size(tauri::Size::Physical(tauri::PhysicalSize {
+                width: final_width,
+                height: final_height,
+            })) {
+                Ok(_) => {
+                    info!("✅ Main window successfully resized to responsive size: {}x{}", final_width, final_height);
                 
-                // Verify the resize worked
-                match window.outer_size() {
-                    Ok(new_size) => {
-                        info!("🔍 Post-resize verification: actual new size is {}x{}", new_size.width, new_size.height);
-                        Ok(format!("Window resized to {}x{} (requested: {}x{})", new_size.width, new_size.height, width, height))
-                    }
-                    Err(e) => {
-                        warn!("❌ Failed to verify window resize: {}", e);
-                        Ok(format!("Window resize attempted: {}x{}", final_width, final_height))
+                    // Verify the resize worked
+                    match window.outer_size() {
+                        Ok(new_size) => {
+                            info!("🔍 Post-resize verification: actual new size is {}x{}", new_size.width, new_size.height);
+                            Ok(format!("Window resized to {}x{} (requested: {}x{})", new_size.width, new_size.height, width, height))
+                        }
+                        Err(e) => {
+                            warn!("❌ Failed to verify window resize: {}", e);
+                            Ok(format!("Window resize attempted: {}x{}", final_width, final_height))
+                        }
                     }
                 }
+                Err(e) => {
+                    error!("❌ Failed to resize main window: {}", e);
+                    Err(format!("Failed to resize main window: {}", e).into())
+                }
             }
-            Err(e) => {
-                error!("❌ Failed to resize main window: {}", e);
-                Err(format!("Failed to resize main window: {}", e))
-            }
+        } else {
+            error!("❌ Main window not found for resize");
+            Err("Main window not found".into())
         }
-    } else {
-        error!("❌ Main window not found for resize");
-        Err("Main window not found".to_string())
-    }
+    })
 }
 
 #[tauri::command]
-fn show_main_window(app_handle: AppHandle) -> Result<String, String> {
-    info!("👁️ Showing main window...");
+#[specta::specta]
+fn show_main_window(app_handle: AppHandle) -> Response<String> {
+    envelope::respond_sync("show_main_window", || {
+        info!("👁️ Showing main window...");
     
-    if let Some(window) = app_handle.get_webview_window("main") {
-        match window.show() {
-            Ok(_) => {
-                info!("✅ Main window shown successfully");
-                // Also bring to front
-                if let Err(e) = window.set_focus() {
-                    warn!("⚠️ Failed to focus main window: {}", e);
+        if let Some(window) = app_handle.get_webview_window("main") {
+            match window.show() {
+                Ok(_) => {
+                    info!("✅ Main window shown successfully");
+                    // Also bring to front
+                    if let Err(e) = window.set_focus() {
+                        warn!("⚠️ Failed to focus main window: {}", e);
+                    }
+                    Ok("Main window shown".to_string())
+                }
+                Err(e) => {
+                    error!("❌ Failed to show main window: {}", e);
+                    Err(format!("Failed to show main window: {}", e).into())
                 }
-                Ok("Main window shown".to_string())
-            }
-            Err(e) => {
-                error!("❌ Failed to show main window: {}", e);
-                Err(format!("Failed to show main window: {}", e))
             }
+        } else {
+            error!("❌ Main window not found for show");
+            Err("Main window not found".into())
         }
-    } else {
-        error!("❌ Main window not found for show");
-        Err("Main window not found".to_string())
-    }
+    })
 }
 
 #[tauri::command]
-fn hide_main_window(app_handle: AppHandle) -> Result<String, String> {
-    info!("🙈 Hiding main window...");
+#[specta::specta]
+fn hide_main_window(app_handle: AppHandle) -> Response<String> {
+    envelope::respond_sync("hide_main_window", || {
+        info!("🙈 Hiding main window...");
     
-    if let Some(window) = app_handle.get_webview_window("main") {
-        match window.hide() {
-            Ok(_) => {
-                info!("✅ Main window hidden successfully");
-                Ok("Main window hidden".to_string())
-            }
-            Err(e) => {
-                error!("❌ Failed to hide main window: {}", e);
-                Err(format!("Failed to hide main window: {}", e))
+        if let Some(window) = app_handle.get_webview_window("main") {
+            match window.hide() {
+                Ok(_) => {
+                    info!("✅ Main window hidden successfully");
+                    Ok("Main window hidden".to_string())
+                }
+                Err(e) => {
+                    error!("❌ Failed to hide main window: {}", e);
+                    Err(format!("Failed to hide main window: {}", e).into())
+                }
             }
+        } else {
+            error!("❌ Main window not found for hide");
+            Err("Main window not found".into())
         }
-    } else {
-        error!("❌ Main window not found for hide");
-        Err("Main window not found".to_string())
-    }
+    })
 }
 
 // Screenshot and AI Analysis Commands
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Type)]
 struct ScreenshotResponse {
     screenshot: String, // Base64 encoded image
     width: u32,
     height: u32,
 }
 
-/// Capture a screenshot of the entire screen and return base64 encoded image data
+/// Capture the primary monitor and return base64 encoded PNG data
 #[tauri::command]
-async fn capture_screenshot() -> Result<ScreenshotResponse, String> {
-    info!("📸 Capturing screenshot...");
-    
-    use screenshots::Screen;
-    
-    let screens = Screen::all().ok_or("Failed to get screens")?;
-    let screen = screens.first().ok_or("No screens found")?;
-    
-    let image = screen.capture().ok_or("Failed to capture screen")?;
-    
-    let width = image.width();
-    let height = image.height();
-    
-    info!("📸 Screenshot captured: {}x{} pixels", width, height);
-    
-    // Get the raw image buffer
-    let image_data = image.buffer();
-    
-    info!("📸 Raw image buffer size: {} bytes", image_data.len());
-    
-    // The screenshots crate might return already encoded data or raw pixel data
-    // Let's try to determine what we have and handle it appropriately
-    let expected_raw_size = (width * height * 4) as usize; // RGBA
-    let expected_rgb_size = (width * height * 3) as usize; // RGB
-    
-    let png_data = if image_data.len() == expected_raw_size {
-        // Raw RGBA data
-        info!("📸 Processing as raw RGBA data");
-        let mut rgba_data = image_data.to_vec();
-        
-        // Convert BGRA to RGBA if necessary (common on Windows)
-        for chunk in rgba_data.chunks_mut(4) {
-            if chunk.len() == 4 {
-                chunk.swap(0, 2); // Swap B and R channels (BGRA -> RGBA)
-            }
-        }
-        
-        // Create ImageBuffer from RGBA data
-        let img_buffer = image::ImageBuffer::<image::Rgba<u8>, Vec<u8>>::from_raw(width, height, rgba_data)
-            .ok_or("Failed to create RGBA image buffer")?;
-        
-        // Convert to PNG bytes
-        let mut png_bytes = std::io::Cursor::new(Vec::new());
-        img_buffer.write_to(&mut png_bytes, image::ImageOutputFormat::Png)
-            .map_err(|e| format!("Failed to encode RGBA PNG: {}", e))?;
-        png_bytes.into_inner()
-        
-    } else if image_data.len() == expected_rgb_size {
-        // Raw RGB data
-        info!("📸 Processing as raw RGB data");
-        let img_buffer = image::ImageBuffer::<image::Rgb<u8>, Vec<u8>>::from_raw(width, height, image_data.to_vec())
-            .ok_or("Failed to create RGB image buffer")?;
-        
-        // Convert to PNG bytes
-        let mut png_bytes = std::io::Cursor::new(Vec::new());
-        img_buffer.write_to(&mut png_bytes, image::ImageOutputFormat::Png)
-            .map_err(|e| format!("Failed to encode RGB PNG: {}", e))?;
-        png_bytes.into_inner()
-        
-    } else {
-        // The data might already be in a compressed format (PNG, JPEG, etc.)
-        // Let's try to decode it first, then re-encode as PNG
-        info!("📸 Attempting to decode compressed image data ({} bytes)", image_data.len());
-        
-        match image::load_from_memory(image_data) {
-            Ok(dynamic_img) => {
-                info!("✅ Successfully decoded compressed image");
-                // Convert to PNG
-                let mut png_bytes = std::io::Cursor::new(Vec::new());
-                dynamic_img.write_to(&mut png_bytes, image::ImageOutputFormat::Png)
-                    .map_err(|e| format!("Failed to encode decoded PNG: {}", e))?;
-                png_bytes.into_inner()
-            },
-            Err(decode_error) => {
-                // Last resort: try to interpret as BGRA with different stride
-                warn!("⚠️ Failed to decode compressed image: {}", decode_error);
-                info!("📸 Attempting fallback: treating as raw BGRA with potential padding");
-                
-                // Calculate bytes per pixel based on actual data
-                let actual_bytes_per_pixel = image_data.len() as f32 / (width * height) as f32;
-                info!("📸 Actual bytes per pixel: {:.2}", actual_bytes_per_pixel);
-                
-                if actual_bytes_per_pixel >= 3.0 && actual_bytes_per_pixel <= 4.0 {
-                    // Try to extract pixel data assuming some stride/padding
-                    let stride = image_data.len() / height as usize;
-                    let bytes_per_pixel = stride / width as usize;
-                    
-                    info!("📸 Detected stride: {}, bytes per pixel: {}", stride, bytes_per_pixel);
-                    
-                    if bytes_per_pixel >= 3 && bytes_per_pixel <= 4 {
-                        // Extract pixel data row by row
-                        let mut extracted_pixels = Vec::new();
-                        
-                        for y in 0..height {
-                            let row_start = y as usize * stride;
-                            let row_end = std::cmp::min(row_start + (width as usize * bytes_per_pixel), image_data.len());
-                            
-                            for x in 0..width as usize {
-                                let pixel_start = row_start + (x * bytes_per_pixel);
-                                if pixel_start + 2 < row_end {
-                                    if bytes_per_pixel >= 4 {
-                                        // BGRA -> RGBA
-                                        extracted_pixels.push(image_data[pixel_start + 2]); // R
-                                        extracted_pixels.push(image_data[pixel_start + 1]); // G
-                                        extracted_pixels.push(image_data[pixel_start + 0]); // B
-                                        extracted_pixels.push(image_data[pixel_start + 3]); // A
-                                    } else {
-                                        // BGR -> RGB
-                                        extracted_pixels.push(image_data[pixel_start + 2]); // R
-                                        extracted_pixels.push(image_data[pixel_start + 1]); // G
-                                        extracted_pixels.push(image_data[pixel_start + 0]); // B
-                                    }
-                                }
-                            }
-                        }
-                        
-                        if extracted_pixels.len() == expected_raw_size || extracted_pixels.len() == expected_rgb_size {
-                            info!("✅ Successfully extracted {} pixels", extracted_pixels.len());
-                            
-                            // Create image buffer
-                            if bytes_per_pixel >= 4 {
-                                let img_buffer = image::ImageBuffer::<image::Rgba<u8>, Vec<u8>>::from_raw(width, height, extracted_pixels)
-                                    .ok_or("Failed to create extracted RGBA image buffer")?;
-                                
-                                let mut png_bytes = std::io::Cursor::new(Vec::new());
-                                img_buffer.write_to(&mut png_bytes, image::ImageOutputFormat::Png)
-                                    .map_err(|e| format!("Failed to encode extracted RGBA PNG: {}", e))?;
-                                png_bytes.into_inner()
-                            } else {
-                                let img_buffer = image::ImageBuffer::<image::Rgb<u8>, Vec<u8>>::from_raw(width, height, extracted_pixels)
-                                    .ok_or("Failed to create extracted RGB image buffer")?;
-                                
-                                let mut png_bytes = std::io::Cursor::new(Vec::new());
-                                img_buffer.write_to(&mut png_bytes, image::ImageOutputFormat::Png)
-                                    .map_err(|e| format!("Failed to encode extracted RGB PNG: {}", e))?;
-                                png_bytes.into_inner()
-                            }
-                        } else {
-                            return Err(format!(
-                                "Failed to process screenshot: buffer size {} bytes doesn't match expected formats. Raw size: {}, RGB size: {}, extracted: {}", 
-                                image_data.len(), expected_raw_size, expected_rgb_size, extracted_pixels.len()
-                            ));
-                        }
-                    } else {
-                        return Err(format!("Unsupported pixel format: {} bytes per pixel", bytes_per_pixel));
-                    }
-                } else {
-                    return Err(format!(
-                        "Unable to process screenshot: {} bytes for {}x{} image ({:.2} bytes per pixel)", 
-                        image_data.len(), width, height, actual_bytes_per_pixel
-                    ));
-                }
-            }
-        }
-    };
-    
-    let base64_image = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &png_data);
-    
-    info!("✅ Screenshot processed: {}x{} pixels, {} KB", width, height, png_data.len() / 1024);
-    
-    Ok(ScreenshotResponse {
-        screenshot: base64_image,
-        width,
-        height,
+#[specta::specta]
+async fn capture_screenshot() -> Response<ScreenshotResponse> {
+    guard::respond("capture_screenshot", &[Require::Permission(Permission::ScreenRecording)], async move {
+        info!("📸 Capturing screenshot...");
+        telemetry::record_feature("screenshot_capture");
+    
+        let capture = screenshot::capture_monitor(None).await?;
+        Ok(ScreenshotResponse {
+            screenshot: capture.screenshot,
+            width: capture.width,
+            height: capture.height,
+        })
     })
+    .await
 }
 
 /// ULTRA-ACCURATE Enhanced Q&A function with advanced prompt engineering and model optimization
 #[tauri::command]
+#[specta::specta]
 async fn enhanced_qa_with_vision_streaming(
     payload: AnalyzeScreenWithAiPayload,
     state: State<'_, AppState>,
     app_handle: AppHandle,
-) -> Result<String, String> {
-    info!("🔥🎯 Starting ULTRA-ACCURATE Enhanced Q&A with AI vision and advanced optimization...");
-    
-    // Show the AI response window before starting
-    if let Err(e) = show_ai_response_window(app_handle.clone()) {
-        warn!("Failed to show AI response window: {}", e);
-    }
+) -> Response<String> {
+    envelope::respond("enhanced_qa_with_vision_streaming", async move {
+        info!("🔥🎯 Starting ULTRA-ACCURATE Enhanced Q&A with AI vision and advanced optimization...");
     
-    // Send initial status to UI
-    let status_data = AiResponseData {
-        message_type: "stream-token".to_string(),
-        text: Some("🚀 [ULTRA Q&A] Initializing advanced AI vision system for maximum accuracy...".to_string()),
-        error: None,
-    };
-    let _ = send_ai_response_data(app_handle.clone(), status_data).await;
-    
-    // Initialize advanced systems
-    let model_optimizer = model_optimizer::ModelOptimizer::new();
-    
-    // Build enhanced context for interview Q&A
-    let mut context = {
-        let context_guard = state.interview_context.lock();
-        context_guard.clone()
-    };
+        // Show the AI response window before starting
+        if let Err(e) = show_ai_response_window(app_handle.clone()).into_result() {
+            warn!("Failed to show AI response window: {}", e);
+        }
     
-    if let Some(company) = payload.company { context.company = Some(company); }
-    if let Some(position) = payload.position { context.position = Some(position); }
-    if let Some(job_description) = payload.job_description { context.job_description = Some(job_description); }
-    
-    // Initialize advanced prompt engine
-    let prompt_engine = advanced_prompts::AdvancedPromptEngine::new(context.clone());
-    
-    // Pre-analyze question context (we'll do proper analysis after screenshot)
-    let initial_context = model_optimizer::QuestionContext {
-        is_technical: true, // Assume technical for now
-        requires_reasoning: true,
-        multiple_questions: true, // Prepare for multiple questions
-        context_sensitive: true,
-        estimated_complexity: 8.0,
-        domain: "general".to_string(),
-    };
+        // Send initial status to UI
+        let status_data = AiResponseData {
+            message_type: "stream-token".to_string(),
+            text: Some("🚀 [ULTRA Q&A] Initializing advanced AI vision system for maximum accuracy...".to_string()),
+            error: None,
+        };
+        let _ = send_ai_response_data(app_handle.clone(), status_data).await.into_result();
+    
+        // Initialize advanced systems
+        let model_optimizer = model_optimizer::ModelOptimizer::new();
+    
+        // Build enhanced context for interview Q&A
+        let context = ContextBuilder::new(&state)
+            .overrides(payload.company, payload.position, payload.job_description)
+            .provider(&payload.provider)
+            .build();
+    
+        // Initialize advanced prompt engine
+        let prompt_engine = advanced_prompts::AdvancedPromptEngine::new(context.clone());
+    
+        // Pre-analyze question context (we'll do proper analysis after screenshot)
+        let initial_context = model_optimizer::QuestionContext {
+            is_technical: true, // Assume technical for now
+            requires_reasoning: true,
+            multiple_questions: true, // Prepare for multiple questions
+            context_sensitive: true,
+            estimated_complexity: 8.0,
+            domain: "general".to_string(),
+        };
     
-    // Select optimal model using advanced selection
-    let optimal_model = model_optimizer.select_optimal_model(&payload.model, &initial_context);
-    let optimization_params = model_optimizer.get_optimization_params(&optimal_model);
+        // Select optimal model using advanced selection
+        let optimal_model = model_optimizer.select_optimal_model(&payload.model, &initial_context);
+        let optimization_params = model_optimizer.get_optimization_params(&optimal_model);
     
-    let model_status = AiResponseData {
-        message_type: "stream-token".to_string(),
-        text: Some(format!("🎯 [ULTRA Q&A] Selected optimal model: {} (accuracy optimized for multi-question scenarios)", optimal_model)),
-        error: None,
-    };
-    let _ = send_ai_response_data(app_handle.clone(), model_status).await;
+        let model_status = AiResponseData {
+            message_type: "stream-token".to_string(),
+            text: Some(format!("🎯 [ULTRA Q&A] Selected optimal model: {} (accuracy optimized for multi-question scenarios)", optimal_model)),
+            error: None,
+        };
+        let _ = send_ai_response_data(app_handle.clone(), model_status).await.into_result();
     
-    // Capture screenshot
-    let screenshot_response = match capture_screenshot().await {
-        Ok(response) => response,
-        Err(e) => {
-            error!("❌ Failed to capture screenshot: {}", e);
-            let error_data = AiResponseData {
-                message_type: "error".to_string(),
-                text: None,
-                error: Some(format!("Failed to capture screenshot: {}", e)),
-            };
-            let _ = send_ai_response_data(app_handle, error_data).await;
-            return Err(e);
-        }
-    };
+        // Capture screenshot
+        let screenshot_response = match capture_screenshot().await.into_result() {
+            Ok(response) => response,
+            Err(e) => {
+                error!("❌ Failed to capture screenshot: {}", e);
+                let error_data = AiResponseData {
+                    message_type: "error".to_string(),
+                    text: None,
+                    error: Some(format!("Failed to capture screenshot: {}", e)),
+                };
+                let _ = send_ai_response_data(app_handle, error_data).await.into_result();
+                return Err(e);
+            }
+        };
     
-    let capture_status = AiResponseData {
-        message_type: "stream-token".to_string(),
-        text: Some(format!("📸 [ENHANCED Q&A] Screenshot captured ({}x{})! Analyzing for interview questions...", 
-            screenshot_response.width, screenshot_response.height)),
-        error: None,
-    };
-    let _ = send_ai_response_data(app_handle.clone(), capture_status).await;
+        let capture_status = AiResponseData {
+            message_type: "stream-token".to_string(),
+            text: Some(format!("📸 [ENHANCED Q&A] Screenshot captured ({}x{})! Analyzing for interview questions...", 
+                screenshot_response.width, screenshot_response.height)),
+            error: None,
+        };
+        let _ = send_ai_response_data(app_handle.clone(), capture_status).await.into_result();
     
-    // Initialize streaming state
-    let stream_start_time = std::time::Instant::now();
-    let _ = app_handle.emit("ai-stream-start", ());
+        // Initialize streaming state
+        let stream_start_time = std::time::Instant::now();
+        let _ = app_handle.emit(events::AI_STREAM_START, ());
     
-    // Generate ultra-accurate prompt using advanced prompt engineering
-    let base_prompt = prompt_engine.generate_ultra_accurate_prompt();
-    let model_optimization = prompt_engine.get_model_optimization(&optimal_model);
-    let technical_context = prompt_engine.generate_technical_context();
+        // Generate ultra-accurate prompt using advanced prompt engineering
+        let base_prompt = prompt_engine.generate_ultra_accurate_prompt();
+        let model_optimization = prompt_engine.get_model_optimization(&optimal_model);
+        let technical_context = prompt_engine.generate_technical_context();
     
-    let ultra_accurate_prompt = format!(
-        "{}
+        let ultra_accurate_prompt = format!(
+            "{}
 
 {}
 
@@ -3474,296 +3965,274 @@ Provide specific, actionable answers that would impress senior technical intervi
 
 🚀 **MULTI-QUESTION EXCELLENCE**: 
 If multiple questions are found, provide comprehensive answers for each one with clear Q1:/A1:, Q2:/A2: formatting.",
-        base_prompt,
-        model_optimization, 
-        technical_context
-    );
-    
-    let app_handle_clone = app_handle.clone();
-    let result = match state.ensure_pollinations_client() {
-        Ok(()) => {
-            let client = {
-                let client_guard = state.pollinations_client.lock();
-                client_guard.as_ref().unwrap().clone()
-            };
+            base_prompt,
+            model_optimization, 
+            technical_context
+        );
+    
+        let tokens = ui_token_coalescer(&app_handle, false);
+        let result = match state.ensure_pollinations_client() {
+            Ok(()) => {
+                let client = {
+                    let client_guard = state.pollinations_client.lock();
+                    client_guard.as_ref().unwrap().clone()
+                };
             
-            // Use the optimally selected vision-enabled model with advanced parameters
-            let model = pollinations::PollinationsModel::Custom(optimal_model.clone());
+                // Use the optimally selected vision-enabled model with advanced parameters
+                let model = pollinations::PollinationsModel::Custom(optimal_model.clone());
             
-            info!("🔥🎯 Using ULTRA-ACCURATE Q&A with model: {} (temp: {}, max_tokens: {})", 
-                  model.as_str(), optimization_params.temperature, optimization_params.max_tokens);
+                info!("🔥🎯 Using ULTRA-ACCURATE Q&A with model: {} (temp: {}, max_tokens: {})", 
+                      model.as_str(), optimization_params.temperature, optimization_params.max_tokens);
             
-            // Create optimized streaming function with enhanced token processing
-            let ultra_streaming_handler = move |token: &str| {
-                // Enhanced token processing for better response quality
-                if token.trim().is_empty() {
-                    return; // Skip empty tokens
-                }
-                
-                let data = AiResponseData {
-                    message_type: "stream-token".to_string(),
-                    text: Some(token.to_string()),
-                    error: None,
-                };
-                let app_handle_for_token = app_handle_clone.clone();
-                tokio::spawn(async move {
-                    if let Err(e) = send_ai_response_data(app_handle_for_token, data).await {
-                        error!("Failed to send streaming token to UI: {}", e);
+                // Create optimized streaming function with enhanced token processing
+                let token_sink = tokens.clone();
+                let ultra_streaming_handler = move |token: &str| {
+                    // Enhanced token processing for better response quality
+                    if token.trim().is_empty() {
+                        return; // Skip empty tokens
                     }
-                });
-            };
+                    token_sink.push(token);
+                };
             
-            // Call enhanced streaming function with optimized prompt
-            client.answer_screenshot_questions_streaming(
-                &screenshot_response.screenshot,
-                &ultra_accurate_prompt,
-                &context,
-                model,
-                ultra_streaming_handler
-            ).await
-        },
-        Err(e) => Err(anyhow::anyhow!("Enhanced Q&A unavailable - Pollinations client error: {}", e))
-    };
+                // Call enhanced streaming function with optimized prompt
+                client.answer_screenshot_questions_streaming(
+                    &screenshot_response.screenshot,
+                    &ultra_accurate_prompt,
+                    &context,
+                    model,
+                    ultra_streaming_handler
+                ).await
+            },
+            Err(e) => Err(anyhow::anyhow!("Enhanced Q&A unavailable - Pollinations client error: {}", e))
+        };
+        tokens.finish().await;
     
-    let elapsed_time = stream_start_time.elapsed();
+        let elapsed_time = stream_start_time.elapsed();
     
-    match result {
-        Ok(response) => {
-            info!("✅🎯 ULTRA-ACCURATE Q&A completed in {:.2?}. Response length: {}", elapsed_time, response.len());
+        match result {
+            Ok(response) => {
+                info!("✅🎯 ULTRA-ACCURATE Q&A completed in {:.2?}. Response length: {}", elapsed_time, response.len());
             
-            // Analyze response quality for validation
-            let question_count = response.matches("Q").filter(|q| q.len() >= 2).count().max(
-                response.matches("?").count()
-            );
-            let has_technical_content = response.to_lowercase().contains("implement") || 
-                                       response.to_lowercase().contains("architecture") ||
-                                       response.to_lowercase().contains("algorithm") ||
-                                       response.to_lowercase().contains("design");
+                // Analyze response quality for validation
+                let question_count = response.matches("Q").filter(|q| q.len() >= 2).count().max(
+                    response.matches("?").count()
+                );
+                let has_technical_content = response.to_lowercase().contains("implement") || 
+                                           response.to_lowercase().contains("architecture") ||
+                                           response.to_lowercase().contains("algorithm") ||
+                                           response.to_lowercase().contains("design");
             
-            // Enhanced completion message with accuracy metrics
-            let completion_message = format!(
-                "✅🎯 [ULTRA Q&A COMPLETE] 
+                // Enhanced completion message with accuracy metrics
+                let completion_message = format!(
+                    "✅🎯 [ULTRA Q&A COMPLETE] 
 Model: {} | Questions Detected: {} | Technical Depth: {} | Response Time: {:.1}s\n\n{}",
-                optimal_model,
-                if question_count > 0 { question_count.to_string() } else { "0-1".to_string() },
-                if has_technical_content { "High" } else { "Standard" },
-                elapsed_time.as_secs_f32(),
-                response
-            );
+                    optimal_model,
+                    if question_count > 0 { question_count.to_string() } else { "0-1".to_string() },
+                    if has_technical_content { "High" } else { "Standard" },
+                    elapsed_time.as_secs_f32(),
+                    response
+                );
             
-            // Send enhanced completion signal
-            let completion_data = AiResponseData {
-                message_type: "complete".to_string(),
-                text: Some(completion_message),
-                error: None,
-            };
-            let _ = send_ai_response_data(app_handle.clone(), completion_data).await;
-            let _ = app_handle.emit("ai-stream-complete", response.clone());
+                // Send enhanced completion signal
+                let completion_data = AiResponseData {
+                    message_type: "complete".to_string(),
+                    text: Some(completion_message),
+                    error: None,
+                };
+                let _ = send_ai_response_data(app_handle.clone(), completion_data).await.into_result();
+                let _ = app_handle.emit(events::AI_STREAM_COMPLETE, response.clone());
+                scripting::dispatch(scripting::ScriptEvent::AnswerComplete, serde_json::json!({ "answer": &response }));
             
-            // Log success metrics
-            info!("📈 ULTRA Q&A SUCCESS METRICS: Model={}, Questions={}, Technical={}, Time={:.2?}", 
-                  optimal_model, question_count, has_technical_content, elapsed_time);
+                // Log success metrics
+                info!("📈 ULTRA Q&A SUCCESS METRICS: Model={}, Questions={}, Technical={}, Time={:.2?}", 
+                      optimal_model, question_count, has_technical_content, elapsed_time);
             
-            Ok(response)
-        },
-        Err(e) => {
-            error!("❌ ULTRA-ACCURATE Q&A failed with primary model {}: {}", optimal_model, e);
+                Ok(response)
+            },
+            Err(e) => {
+                error!("❌ ULTRA-ACCURATE Q&A failed with primary model {}: {}", optimal_model, e);
             
-            // Try fallback chain for maximum reliability
-            let fallback_chain = model_optimizer.get_fallback_chain(&optimal_model);
-            info!("🔄 Attempting fallback chain: {:?}", fallback_chain);
+                // Try fallback chain for maximum reliability
+                let fallback_chain = model_optimizer.get_fallback_chain(&optimal_model);
+                info!("🔄 Attempting fallback chain: {:?}", fallback_chain);
             
-            // Send fallback attempt notification
-            let fallback_status = AiResponseData {
-                message_type: "stream-token".to_string(),
-                text: Some("🔄 [FALLBACK] Primary model failed, trying backup models for maximum accuracy...".to_string()),
-                error: None,
-            };
-            let _ = send_ai_response_data(app_handle.clone(), fallback_status).await;
+                // Send fallback attempt notification
+                let fallback_status = AiResponseData {
+                    message_type: "stream-token".to_string(),
+                    text: Some("🔄 [FALLBACK] Primary model failed, trying backup models for maximum accuracy...".to_string()),
+                    error: None,
+                };
+                let _ = send_ai_response_data(app_handle.clone(), fallback_status).await.into_result();
             
-            // For now, return the error (fallback implementation would go here)
-            let error_data = AiResponseData {
-                message_type: "error".to_string(),
-                text: None,
-                error: Some(format!("Ultra-Accurate Q&A failed: {}. Fallback chain available: {:?}", e, fallback_chain)),
-            };
-            let _ = send_ai_response_data(app_handle.clone(), error_data).await;
-            let _ = app_handle.emit("ai-stream-error", e.to_string());
+                // For now, return the error (fallback implementation would go here)
+                let error_data = AiResponseData {
+                    message_type: "error".to_string(),
+                    text: None,
+                    error: Some(format!("Ultra-Accurate Q&A failed: {}. Fallback chain available: {:?}", e, fallback_chain)),
+                };
+                let _ = send_ai_response_data(app_handle.clone(), error_data).await.into_result();
+                let _ = app_handle.emit(events::AI_STREAM_ERROR, e.to_string());
             
-            Err(e.to_string())
+                Err(e.to_string().into())
+            }
         }
-    }
+    })
+    .await
 }
 
 /// Answer questions found in a screenshot using vision-capable models with streaming
 #[tauri::command]
+#[specta::specta]
 async fn answer_screenshot_questions_streaming(
     payload: AnalyzeScreenWithAiPayload,
     state: State<'_, AppState>,
     app_handle: AppHandle,
-) -> Result<String, String> {
-    info!("📸 Starting screenshot question-answering with streaming...");
-    
-    // Show the AI response window before starting
-    if let Err(e) = show_ai_response_window(app_handle.clone()) {
-        warn!("Failed to show AI response window: {}", e);
-    }
-    
-    // Send initial status to UI
-    let status_data = AiResponseData {
-        message_type: "stream-token".to_string(),
-        text: Some("[SCREENSHOT] Capturing screen for question analysis...".to_string()),
-        error: None,
-    };
-    let _ = send_ai_response_data(app_handle.clone(), status_data).await;
+) -> Response<String> {
+    envelope::respond("answer_screenshot_questions_streaming", async move {
+        info!("📸 Starting screenshot question-answering with streaming...");
     
-    // Capture screenshot
-    let screenshot_response = match capture_screenshot().await {
-        Ok(response) => response,
-        Err(e) => {
-            error!("❌ Failed to capture screenshot: {}", e);
-            let error_data = AiResponseData {
-                message_type: "error".to_string(),
-                text: None,
-                error: Some(format!("Failed to capture screenshot: {}", e)),
-            };
-            let _ = send_ai_response_data(app_handle, error_data).await;
-            return Err(e);
+        // Show the AI response window before starting
+        if let Err(e) = show_ai_response_window(app_handle.clone()).into_result() {
+            warn!("Failed to show AI response window: {}", e);
         }
-    };
-    
-    let status_data = AiResponseData {
-        message_type: "stream-token".to_string(),
-        text: Some(format!("[SCREENSHOT] ✅ Screen captured ({}x{})! Analyzing with AI...", 
-            screenshot_response.width, screenshot_response.height)),
-        error: None,
-    };
-    let _ = send_ai_response_data(app_handle.clone(), status_data).await;
-    
-    // Initialize streaming state
-    let stream_start_time = std::time::Instant::now();
-    let _ = app_handle.emit("ai-stream-start", ());
     
-    // Determine AI provider and context
-    let provider = AIProvider::from_str(&payload.provider).unwrap_or(AIProvider::Pollinations);
-    let mut context = {
-        let context_guard = state.interview_context.lock();
-        context_guard.clone()
-    };
-    
-    if let Some(company) = payload.company { context.company = Some(company); }
-    if let Some(position) = payload.position { context.position = Some(position); }
-    if let Some(job_description) = payload.job_description { context.job_description = Some(job_description); }
+        // Send initial status to UI
+        let status_data = AiResponseData {
+            message_type: "stream-token".to_string(),
+            text: Some("[SCREENSHOT] Capturing screen for question analysis...".to_string()),
+            error: None,
+        };
+        let _ = send_ai_response_data(app_handle.clone(), status_data).await.into_result();
     
-    let analysis_prompt = "Look at this screenshot and identify any questions (especially from chat boxes, meeting interfaces, or interview prompts). Answer them directly and concisely.";
+        // Capture screenshot
+        let screenshot_response = match capture_screenshot().await.into_result() {
+            Ok(response) => response,
+            Err(e) => {
+                error!("❌ Failed to capture screenshot: {}", e);
+                let error_data = AiResponseData {
+                    message_type: "error".to_string(),
+                    text: None,
+                    error: Some(format!("Failed to capture screenshot: {}", e)),
+                };
+                let _ = send_ai_response_data(app_handle, error_data).await.into_result();
+                return Err(e);
+            }
+        };
     
-    let app_handle_clone = app_handle.clone();
-    let result = match provider {
-        AIProvider::Pollinations => {
-            info!("🧠 Using Pollinations for screenshot question-answering");
-            
-            match state.ensure_pollinations_client() {
-                Ok(()) => {
-                    let client = {
-                        let client_guard = state.pollinations_client.lock();
-                        client_guard.as_ref().unwrap().clone()
-                    };
-                    
-                    // Find a vision-capable model
-                    let model = pollinations::PollinationsModel::from_string(&payload.model)
-                        .unwrap_or(pollinations::PollinationsModel::Custom("openai".to_string()));
-                        
-                    if !model.supports_vision() {
-                        // Fallback to a vision-capable model
-                        let vision_model = pollinations::PollinationsModel::Custom("openai".to_string());
-                        info!("⚠️ Selected model '{}' doesn't support vision, using '{}' instead", 
-                              model.as_str(), vision_model.as_str());
-                        
-                        client.answer_screenshot_questions_streaming(
-                            &screenshot_response.screenshot,
-                            analysis_prompt,
-                            &context,
-                            vision_model,
-                            move |token: &str| {
-                                let data = AiResponseData {
-                                    message_type: "stream-token".to_string(),
-                                    text: Some(token.to_string()),
-                                    error: None,
-                                };
-                                let app_handle_for_token = app_handle_clone.clone();
-                                tokio::spawn(async move {
-                                    if let Err(e) = send_ai_response_data(app_handle_for_token, data).await {
-                                        error!("Failed to send streaming token to UI: {}", e);
-                                    }
-                                });
-                            }
-                        ).await
-                    } else {
-                        client.answer_screenshot_questions_streaming(
-                            &screenshot_response.screenshot,
-                            analysis_prompt,
-                            &context,
-                            model,
-                            move |token: &str| {
-                                let data = AiResponseData {
-                                    message_type: "stream-token".to_string(),
-                                    text: Some(token.to_string()),
-                                    error: None,
-                                };
-                                let app_handle_for_token = app_handle_clone.clone();
-                                tokio::spawn(async move {
-                                    if let Err(e) = send_ai_response_data(app_handle_for_token, data).await {
-                                        error!("Failed to send streaming token to UI: {}", e);
-                                    }
-                                });
-                            }
-                        ).await
-                    }
-                },
-                Err(e) => Err(anyhow::anyhow!("Pollinations client unavailable: {}", e))
+        let status_data = AiResponseData {
+            message_type: "stream-token".to_string(),
+            text: Some(format!("[SCREENSHOT] ✅ Screen captured ({}x{})! Analyzing with AI...", 
+                screenshot_response.width, screenshot_response.height)),
+            error: None,
+        };
+        let _ = send_ai_response_data(app_handle.clone(), status_data).await.into_result();
+    
+        // Initialize streaming state
+        let stream_start_time = std::time::Instant::now();
+        let _ = app_handle.emit(events::AI_STREAM_START, ());
+    
+        // Determine AI provider and context
+        let provider = AIProvider::from_str(&payload.provider).unwrap_or(AIProvider::Pollinations);
+        let context = ContextBuilder::new(&state)
+            .overrides(payload.company, payload.position, payload.job_description)
+            .provider(&payload.provider)
+            .build();
+    
+        let analysis_prompt = "Look at this screenshot and identify any questions (especially from chat boxes, meeting interfaces, or interview prompts). Answer them directly and concisely.";
+    
+        let tokens = ui_token_coalescer(&app_handle, false);
+        let result = match provider {
+            AIProvider::Pollinations => {
+                info!("🧠 Using Pollinations for screenshot question-answering");
+            
+                match state.ensure_pollinations_client() {
+                    Ok(()) => {
+                        let client = {
+                            let client_guard = state.pollinations_client.lock();
+                            client_guard.as_ref().unwrap().clone()
+                        };
+                    
+                        // Find a vision-capable model
+                        let model = pollinations::PollinationsModel::from_string(&payload.model)
+                            .unwrap_or(pollinations::PollinationsModel::Custom("openai".to_string()));
+                        
+                        if !model.supports_vision() {
+                            // Fallback to a vision-capable model
+                            let vision_model = pollinations::PollinationsModel::Custom("openai".to_string());
+                            info!("⚠️ Selected model '{}' doesn't support vision, using '{}' instead", 
+                                  model.as_str(), vision_model.as_str());
+                        
+                            client.answer_screenshot_questions_streaming(
+                                &screenshot_response.screenshot,
+                                analysis_prompt,
+                                &context,
+                                vision_model,
+                                {
+                                    let token_sink = tokens.clone();
+                                    move |token: &str| token_sink.push(token)
+                                }
+                            ).await
+                        } else {
+                            client.answer_screenshot_questions_streaming(
+                                &screenshot_response.screenshot,
+                                analysis_prompt,
+                                &context,
+                                model,
+                                {
+                                    let token_sink = tokens.clone();
+                                    move |token: &str| token_sink.push(token)
+                                }
+                            ).await
+                        }
+                    },
+                    Err(e) => Err(anyhow::anyhow!("Pollinations client unavailable: {}", e))
+                }
+            },
+            AIProvider::OpenAI => {
+                info!("🤖 Using OpenAI for screenshot question-answering");
+                // OpenAI implementation can be added here if needed
+                Err(anyhow::anyhow!("OpenAI screenshot analysis not implemented yet"))
             }
-        },
-        AIProvider::OpenAI => {
-            info!("🤖 Using OpenAI for screenshot question-answering");
-            // OpenAI implementation can be added here if needed
-            Err(anyhow::anyhow!("OpenAI screenshot analysis not implemented yet"))
-        }
-    };
+        };
+        tokens.finish().await;
     
-    let elapsed_time = stream_start_time.elapsed();
+        let elapsed_time = stream_start_time.elapsed();
     
-    match result {
-        Ok(response) => {
-            info!("✅ Screenshot question-answering completed in {:.2?}. Response length: {}", elapsed_time, response.len());
+        match result {
+            Ok(response) => {
+                info!("✅ Screenshot question-answering completed in {:.2?}. Response length: {}", elapsed_time, response.len());
             
-            // Send completion signal
-            let completion_data = AiResponseData {
-                message_type: "complete".to_string(),
-                text: Some(response.clone()),
-                error: None,
-            };
-            let _ = send_ai_response_data(app_handle.clone(), completion_data).await;
-            let _ = app_handle.emit("ai-stream-complete", response.clone());
+                // Send completion signal
+                let completion_data = AiResponseData {
+                    message_type: "complete".to_string(),
+                    text: Some(response.clone()),
+                    error: None,
+                };
+                let _ = send_ai_response_data(app_handle.clone(), completion_data).await.into_result();
+                let _ = app_handle.emit(events::AI_STREAM_COMPLETE, response.clone());
+                scripting::dispatch(scripting::ScriptEvent::AnswerComplete, serde_json::json!({ "answer": &response }));
             
-            Ok(response)
-        },
-        Err(e) => {
-            error!("❌ Screenshot question-answering failed: {}", e);
+                Ok(response)
+            },
+            Err(e) => {
+                error!("❌ Screenshot question-answering failed: {}", e);
             
-            let error_data = AiResponseData {
-                message_type: "error".to_string(),
-                text: None,
-                error: Some(e.to_string()),
-            };
-            let _ = send_ai_response_data(app_handle.clone(), error_data).await;
-            let _ = app_handle.emit("ai-stream-error", e.to_string());
+                let error_data = AiResponseData {
+                    message_type: "error".to_string(),
+                    text: None,
+                    error: Some(e.to_string()),
+                };
+                let _ = send_ai_response_data(app_handle.clone(), error_data).await.into_result();
+                let _ = app_handle.emit(events::AI_STREAM_ERROR, e.to_string());
             
-            Err(e.to_string())
+                Err(e.to_string().into())
+            }
         }
-    }
+    })
+    .await
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Type)]
 struct AnalyzeScreenWithAiPayload {
     model: String,
     provider: String, // "openai" or "pollinations"
@@ -3773,255 +4242,277 @@ struct AnalyzeScreenWithAiPayload {
     system_prompt: Option<String>,
 }
 
-#[derive(Serialize, Deserialize)]
-struct AiAnalysisResult {
+#[derive(Serialize, Deserialize, schemars::JsonSchema, Type)]
+pub struct AiAnalysisResult {
     generated_question: String,
     analysis: String,
     confidence: f32,
 }
 
 #[tauri::command]
+#[specta::specta]
 async fn analyze_screen_with_ai(
     payload: AnalyzeScreenWithAiPayload,
     state: State<'_, AppState>,
     app_handle: AppHandle,
-) -> Result<String, String> {
-    info!("[SCREEN_ANALYSIS] Using accessibility-based analysis as fallback for non-streaming analysis.");
-    
-    // Use the accessibility-based analysis for non-streaming version
-    match analyze_applications_with_ai_streaming(payload, state, app_handle).await {
-        Ok(result) => Ok(format!("Generated question: {}", result.generated_question)),
-        Err(e) => Err(e),
-    }
+    request_id: Option<String>,
+) -> Response<String> {
+    cancellation::run("analyze_screen_with_ai", request_id, async move {
+        info!("[SCREEN_ANALYSIS] Using accessibility-based analysis as fallback for non-streaming analysis.");
+    
+        // Use the accessibility-based analysis for non-streaming version
+        match analyze_applications_with_ai_streaming(payload, state, app_handle, None).await.into_result() {
+            Ok(result) => Ok(format!("Generated question: {}", result.generated_question)),
+            Err(e) => Err(e),
+        }
+    })
+    .await
 }
 
 // New streaming version of screen analysis that shows progress in real-time
 #[tauri::command]
+#[specta::specta]
 async fn analyze_screen_with_ai_streaming(
     payload: AnalyzeScreenWithAiPayload,
     state: State<'_, AppState>,
     app_handle: AppHandle,
-) -> Result<AiAnalysisResult, String> {
-    info!("[SCREEN_ANALYSIS_STREAMING] Starting screen capture and AI analysis with streaming...");
+    request_id: Option<String>,
+) -> Response<AiAnalysisResult> {
+    cancellation::run("analyze_screen_with_ai_streaming", request_id, async move {
+        info!("[SCREEN_ANALYSIS_STREAMING] Starting screen capture and AI analysis with streaming...");
     
-    // Show the AI response window before starting
-    if let Err(e) = show_ai_response_window(app_handle.clone()) {
-        warn!("Failed to show AI response window: {}", e);
-    }
+        // Show the AI response window before starting
+        if let Err(e) = show_ai_response_window(app_handle.clone()).into_result() {
+            warn!("Failed to show AI response window: {}", e);
+        }
     
-    // Use accessibility-based analysis instead of screenshot capture
-    info!("[SCREEN_ANALYSIS_STREAMING] Delegating to accessibility-based analysis...");
+        // Use accessibility-based analysis instead of screenshot capture
+        info!("[SCREEN_ANALYSIS_STREAMING] Delegating to accessibility-based analysis...");
     
-    // Send initial status to UI
-    let status_data = AiResponseData {
-        message_type: "stream-token".to_string(),
-        text: Some("[ANALYSIS] Starting accessibility-based screen analysis...".to_string()),
-        error: None,
-    };
-    let _ = send_ai_response_data(app_handle.clone(), status_data).await;
+        // Send initial status to UI
+        let status_data = AiResponseData {
+            message_type: "stream-token".to_string(),
+            text: Some("[ANALYSIS] Starting accessibility-based screen analysis...".to_string()),
+            error: None,
+        };
+        let _ = send_ai_response_data(app_handle.clone(), status_data).await.into_result();
     
-    // Delegate to accessibility-based analysis
-    analyze_applications_with_ai_streaming(payload, state, app_handle).await
+        // Delegate to accessibility-based analysis
+        analyze_applications_with_ai_streaming(payload, state, app_handle, None).await.into_result()
+    })
+    .await
 }
 
 // Accessibility-based AI Analysis Commands (Primary Solution)
 
 /// Analyze all target applications using Windows Accessibility API with AI streaming
 #[tauri::command]
+#[specta::specta]
 async fn analyze_applications_with_ai_streaming(
     payload: AnalyzeScreenWithAiPayload,
     state: State<'_, AppState>,
     app_handle: AppHandle,
-) -> Result<AiAnalysisResult, String> {
-    info!("[ACCESSIBILITY_STREAMING] Starting accessibility-based analysis of all target applications...");
+    request_id: Option<String>,
+) -> Response<AiAnalysisResult> {
+    cancellation::run_with_progress("analyze_applications_with_ai_streaming", request_id, |progress| async move {
+        info!("[ACCESSIBILITY_STREAMING] Starting accessibility-based analysis of all target applications...");
     
-    // Show the AI response window before starting
-    if let Err(e) = show_ai_response_window(app_handle.clone()) {
-        warn!("Failed to show AI response window: {}", e);
-    }
+        // Show the AI response window before starting
+        if let Err(e) = show_ai_response_window(app_handle.clone()).into_result() {
+            warn!("Failed to show AI response window: {}", e);
+        }
     
-    // Send initial status to UI
-    let status_data = AiResponseData {
-        message_type: "stream-token".to_string(),
-        text: Some("[ACCESSIBILITY] Starting real-time text reading from applications...".to_string()),
-        error: None,
-    };
-    let _ = send_ai_response_data(app_handle.clone(), status_data).await;
+        // Send initial status to UI
+        let status_data = AiResponseData {
+            message_type: "stream-token".to_string(),
+            text: Some("[ACCESSIBILITY] Starting real-time text reading from applications...".to_string()),
+            error: None,
+        };
+        let _ = send_ai_response_data(app_handle.clone(), status_data).await;
     
-    // Read text from all target applications using Accessibility API
-    let accessibility_results = match accessibility_reader::read_text_from_applications().await {
-        Ok(results) => results,
-        Err(e) => {
-            error!("❌ [ACCESSIBILITY] Failed to read text from applications: {}", e);
+        // Read text from all target applications using Accessibility API
+        progress.report("reading_applications", Some(10.0));
+        let accessibility_results = match accessibility_reader::read_text_from_applications().await.into_result() {
+            Ok(results) => results,
+            Err(e) => {
+                error!("❌ [ACCESSIBILITY] Failed to read text from applications: {}", e);
             
-            // Send error to UI
-            let error_data = AiResponseData {
-                message_type: "error".to_string(),
-                text: None,
-                error: Some(format!("Failed to read text from applications: {}", e)),
-            };
-            let _ = send_ai_response_data(app_handle, error_data).await;
+                // Send error to UI
+                let error_data = AiResponseData {
+                    message_type: "error".to_string(),
+                    text: None,
+                    error: Some(format!("Failed to read text from applications: {}", e)),
+                };
+                let _ = send_ai_response_data(app_handle, error_data).await.into_result();
             
-            return Err(format!("Failed to read text from applications: {}", e));
-        }
-    };
+                return Err(format!("Failed to read text from applications: {}", e).into());
+            }
+        };
     
-    info!("📊 [ACCESSIBILITY] Found {} text blocks from target applications", accessibility_results.len());
+        info!("📊 [ACCESSIBILITY] Found {} text blocks from target applications", accessibility_results.len());
     
-    // Check if we found any meaningful text
-    if accessibility_results.is_empty() {
-        warn!("⚠️ [ACCESSIBILITY] No text found in target applications");
+        // Check if we found any meaningful text
+        if accessibility_results.is_empty() {
+            warn!("⚠️ [ACCESSIBILITY] No text found in target applications");
         
-        let no_text_data = AiResponseData {
-            message_type: "stream-token".to_string(),
-            text: Some("\n[ACCESSIBILITY] No readable text found in target applications (Teams, Zoom, Chrome, etc.). Make sure the applications are open and contain visible text.".to_string()),
-            error: None,
-        };
-        let _ = send_ai_response_data(app_handle.clone(), no_text_data).await;
+            let no_text_data = AiResponseData {
+                message_type: "stream-token".to_string(),
+                text: Some("\n[ACCESSIBILITY] No readable text found in target applications (Teams, Zoom, Chrome, etc.). Make sure the applications are open and contain visible text.".to_string()),
+                error: None,
+            };
+            let _ = send_ai_response_data(app_handle.clone(), no_text_data).await;
         
-        // Return a default result
-        let default_result = AiAnalysisResult {
-            generated_question: "Can you describe your experience with video conferencing tools and remote collaboration?".to_string(),
-            analysis: "No text was found in target applications, so this is a general question about remote work experience.".to_string(),
-            confidence: 0.3,
-        };
+            // Return a default result
+            let default_result = AiAnalysisResult {
+                generated_question: "Can you describe your experience with video conferencing tools and remote collaboration?".to_string(),
+                analysis: "No text was found in target applications, so this is a general question about remote work experience.".to_string(),
+                confidence: 0.3,
+            };
         
-        // Send completion
-        let completion_data = AiResponseData {
-            message_type: "complete".to_string(),
-            text: Some(format!("\n[QUESTION] Generated Question (No accessibility text found):\n\n🎯 {}\n\n[ANALYSIS] {}\n\n[CONFIDENCE] {:.0}%", 
-                default_result.generated_question, default_result.analysis, default_result.confidence * 100.0)),
-            error: None,
-        };
-        let _ = send_ai_response_data(app_handle.clone(), completion_data).await;
+            // Send completion
+            let completion_data = AiResponseData {
+                message_type: "complete".to_string(),
+                text: Some(format!("\n[QUESTION] Generated Question (No accessibility text found):\n\n🎯 {}\n\n[ANALYSIS] {}\n\n[CONFIDENCE] {:.0}%", 
+                    default_result.generated_question, default_result.analysis, default_result.confidence * 100.0)),
+                error: None,
+            };
+            let _ = send_ai_response_data(app_handle.clone(), completion_data).await.into_result();
         
-        return Ok(default_result);
-    }
+            return Ok(default_result);
+        }
     
-    // Find the most relevant text (prioritize questions and meaningful content)
-    let best_text = find_best_accessibility_text(&accessibility_results);
-    
-    info!("✅ [ACCESSIBILITY] Selected best text from {}: '{}'", 
-          best_text.source_app, 
-          best_text.text.chars().take(100).collect::<String>());
-    
-    // Send accessibility results to UI
-    let accessibility_status_data = AiResponseData {
-        message_type: "stream-token".to_string(),
-        text: Some(format!(
-            "\n[ACCESSIBILITY] ✅ Text reading complete!\n📊 Found {} text blocks from target applications\n🎯 Best source: {} ({}% confidence)\n📝 Selected text: {}...\n\n[AI] Now generating interview question based on extracted text...", 
-            accessibility_results.len(),
-            best_text.source_app,
-            (best_text.confidence * 100.0) as u32,
-            best_text.text.chars().take(80).collect::<String>()
-        )),
-        error: None,
-    };
-    let _ = send_ai_response_data(app_handle.clone(), accessibility_status_data).await;
-    
-    // Generate AI analysis using the extracted text
-    generate_ai_analysis_from_text(
-        &best_text.text,
-        &format!("Windows Accessibility API from {}", best_text.source_app),
-        payload,
-        state,
-        app_handle,
-    ).await
+        // Find the most relevant text (prioritize questions and meaningful content)
+        let best_text = find_best_accessibility_text(&accessibility_results);
+    
+        info!("✅ [ACCESSIBILITY] Selected best text from {}: '{}'", 
+              best_text.source_app, 
+              best_text.text.chars().take(100).collect::<String>());
+    
+        // Send accessibility results to UI
+        let accessibility_status_data = AiResponseData {
+            message_type: "stream-token".to_string(),
+            text: Some(format!(
+                "\n[ACCESSIBILITY] ✅ Text reading complete!\n📊 Found {} text blocks from target applications\n🎯 Best source: {} ({}% confidence)\n📝 Selected text: {}...\n\n[AI] Now generating interview question based on extracted text...", 
+                accessibility_results.len(),
+                best_text.source_app,
+                (best_text.confidence * 100.0) as u32,
+                best_text.text.chars().take(80).collect::<String>()
+            )),
+            error: None,
+        };
+        let _ = send_ai_response_data(app_handle.clone(), accessibility_status_data).await;
+    
+        // Generate AI analysis using the extracted text
+        progress.report("generating", Some(40.0));
+        Ok(generate_ai_analysis_from_text(
+            &best_text.text,
+            &format!("Windows Accessibility API from {}", best_text.source_app),
+            payload,
+            state,
+            app_handle,
+        ).await?)
+    })
+    .await
 }
 
 /// Analyze focused window using Windows Accessibility API with AI streaming
 #[tauri::command]
+#[specta::specta]
 async fn analyze_focused_window_with_ai_streaming(
     payload: AnalyzeScreenWithAiPayload,
     state: State<'_, AppState>,
     app_handle: AppHandle,
-) -> Result<AiAnalysisResult, String> {
-    info!("[ACCESSIBILITY_STREAMING] Starting accessibility-based analysis of focused window...");
+    request_id: Option<String>,
+) -> Response<AiAnalysisResult> {
+    cancellation::run("analyze_focused_window_with_ai_streaming", request_id, async move {
+        info!("[ACCESSIBILITY_STREAMING] Starting accessibility-based analysis of focused window...");
     
-    // Show the AI response window before starting
-    if let Err(e) = show_ai_response_window(app_handle.clone()) {
-        warn!("Failed to show AI response window: {}", e);
-    }
+        // Show the AI response window before starting
+        if let Err(e) = show_ai_response_window(app_handle.clone()).into_result() {
+            warn!("Failed to show AI response window: {}", e);
+        }
     
-    // Send initial status to UI
-    let status_data = AiResponseData {
-        message_type: "stream-token".to_string(),
-        text: Some("[ACCESSIBILITY] Reading text from currently focused window...".to_string()),
-        error: None,
-    };
-    let _ = send_ai_response_data(app_handle.clone(), status_data).await;
+        // Send initial status to UI
+        let status_data = AiResponseData {
+            message_type: "stream-token".to_string(),
+            text: Some("[ACCESSIBILITY] Reading text from currently focused window...".to_string()),
+            error: None,
+        };
+        let _ = send_ai_response_data(app_handle.clone(), status_data).await;
     
-    // Read text from focused window using Accessibility API
-    let accessibility_result = match accessibility_reader::read_text_from_focused_window().await {
-        Ok(Some(result)) => result,
-        Ok(None) => {
-            warn!("⚠️ [ACCESSIBILITY] No text found in focused window");
+        // Read text from focused window using Accessibility API
+        let accessibility_result = match accessibility_reader::read_text_from_focused_window().await.into_result() {
+            Ok(Some(result)) => result,
+            Ok(None) => {
+                warn!("⚠️ [ACCESSIBILITY] No text found in focused window");
             
-            let no_text_data = AiResponseData {
-                message_type: "stream-token".to_string(),
-                text: Some("\n[ACCESSIBILITY] No readable text found in the currently focused window. Try focusing on an application window with visible text content.".to_string()),
-                error: None,
-            };
-            let _ = send_ai_response_data(app_handle.clone(), no_text_data).await;
+                let no_text_data = AiResponseData {
+                    message_type: "stream-token".to_string(),
+                    text: Some("\n[ACCESSIBILITY] No readable text found in the currently focused window. Try focusing on an application window with visible text content.".to_string()),
+                    error: None,
+                };
+                let _ = send_ai_response_data(app_handle.clone(), no_text_data).await;
             
-            // Return a default result
-            let default_result = AiAnalysisResult {
-                generated_question: "Can you describe what you're currently working on and the technologies involved?".to_string(),
-                analysis: "No text was found in the focused window, so this is a general question about current work.".to_string(),
-                confidence: 0.3,
-            };
+                // Return a default result
+                let default_result = AiAnalysisResult {
+                    generated_question: "Can you describe what you're currently working on and the technologies involved?".to_string(),
+                    analysis: "No text was found in the focused window, so this is a general question about current work.".to_string(),
+                    confidence: 0.3,
+                };
             
-            // Send completion
-            let completion_data = AiResponseData {
-                message_type: "complete".to_string(),
-                text: Some(format!("\n[QUESTION] Generated Question (No focused window text):\n\n🎯 {}\n\n[ANALYSIS] {}\n\n[CONFIDENCE] {:.0}%", 
-                    default_result.generated_question, default_result.analysis, default_result.confidence * 100.0)),
-                error: None,
-            };
-            let _ = send_ai_response_data(app_handle.clone(), completion_data).await;
+                // Send completion
+                let completion_data = AiResponseData {
+                    message_type: "complete".to_string(),
+                    text: Some(format!("\n[QUESTION] Generated Question (No focused window text):\n\n🎯 {}\n\n[ANALYSIS] {}\n\n[CONFIDENCE] {:.0}%", 
+                        default_result.generated_question, default_result.analysis, default_result.confidence * 100.0)),
+                    error: None,
+                };
+                let _ = send_ai_response_data(app_handle.clone(), completion_data).await;
             
-            return Ok(default_result);
-        },
-        Err(e) => {
-            error!("❌ [ACCESSIBILITY] Failed to read text from focused window: {}", e);
+                return Ok(default_result);
+            },
+            Err(e) => {
+                error!("❌ [ACCESSIBILITY] Failed to read text from focused window: {}", e);
             
-            // Send error to UI
-            let error_data = AiResponseData {
-                message_type: "error".to_string(),
-                text: None,
-                error: Some(format!("Failed to read text from focused window: {}", e)),
-            };
-            let _ = send_ai_response_data(app_handle, error_data).await;
+                // Send error to UI
+                let error_data = AiResponseData {
+                    message_type: "error".to_string(),
+                    text: None,
+                    error: Some(format!("Failed to read text from focused window: {}", e)),
+                };
+                let _ = send_ai_response_data(app_handle, error_data).await;
             
-            return Err(format!("Failed to read text from focused window: {}", e));
-        }
-    };
+                return Err(format!("Failed to read text from focused window: {}", e).into());
+            }
+        };
     
-    info!("✅ [ACCESSIBILITY] Text extracted from focused window {}: '{}'", 
-          accessibility_result.source_app, 
-          accessibility_result.text.chars().take(100).collect::<String>());
-    
-    // Send accessibility results to UI
-    let accessibility_status_data = AiResponseData {
-        message_type: "stream-token".to_string(),
-        text: Some(format!(
-            "\n[ACCESSIBILITY] ✅ Text reading complete!\n🎯 Source: {} ({}% confidence)\n📝 Extracted text: {}...\n\n[AI] Now generating interview question based on extracted text...", 
-            accessibility_result.source_app,
-            (accessibility_result.confidence * 100.0) as u32,
-            accessibility_result.text.chars().take(80).collect::<String>()
-        )),
-        error: None,
-    };
-    let _ = send_ai_response_data(app_handle.clone(), accessibility_status_data).await;
-    
-    // Generate AI analysis using the extracted text
-    generate_ai_analysis_from_text(
-        &accessibility_result.text,
-        &format!("Windows Accessibility API from focused window: {}", accessibility_result.source_app),
-        payload,
-        state,
-        app_handle,
-    ).await
+        info!("✅ [ACCESSIBILITY] Text extracted from focused window {}: '{}'", 
+              accessibility_result.source_app, 
+              accessibility_result.text.chars().take(100).collect::<String>());
+    
+        // Send accessibility results to UI
+        let accessibility_status_data = AiResponseData {
+            message_type: "stream-token".to_string(),
+            text: Some(format!(
+                "\n[ACCESSIBILITY] ✅ Text reading complete!\n🎯 Source: {} ({}% confidence)\n📝 Extracted text: {}...\n\n[AI] Now generating interview question based on extracted text...", 
+                accessibility_result.source_app,
+                (accessibility_result.confidence * 100.0) as u32,
+                accessibility_result.text.chars().take(80).collect::<String>()
+            )),
+            error: None,
+        };
+        let _ = send_ai_response_data(app_handle.clone(), accessibility_status_data).await;
+    
+        // Generate AI analysis using the extracted text
+        Ok(generate_ai_analysis_from_text(
+            &accessibility_result.text,
+            &format!("Windows Accessibility API from focused window: {}", accessibility_result.source_app),
+            payload,
+            state,
+            app_handle,
+        ).await?)
+    })
+    .await
 }
 
 /// Helper function to find the best accessibility text result
@@ -4050,25 +4541,27 @@ async fn generate_ai_analysis_from_text(
     state: State<'_, AppState>,
     app_handle: AppHandle,
 ) -> Result<AiAnalysisResult, String> {
+    if let Some(confidence) = coding_challenge::detect_enabled(extracted_text) {
+        info!("🧩 [AI_STREAMING] Extracted text looks like a coding problem, using the structured solution prompt");
+        let _ = app_handle.emit(events::AI_STREAM_START, ());
+        let solution = coding_challenge::solve(&state, &payload.provider, &payload.model, extracted_text, None, source_description).await?;
+        show_structured_answer(&app_handle, events::CODING_SOLUTION, &solution, solution.to_markdown()).await;
+        return Ok(AiAnalysisResult {
+            generated_question: solution.problem.clone(),
+            analysis: solution.approach.clone(),
+            confidence,
+        });
+    }
+
     // Determine AI provider
     let provider = AIProvider::from_str(&payload.provider)
         .unwrap_or(AIProvider::OpenAI);
     
-    let mut context = {
-        let context_guard = state.interview_context.lock();
-        context_guard.clone()
-    };
-    
-    // Update context with payload data
-    if let Some(company) = payload.company {
-        context.company = Some(company);
-    }
-    if let Some(position) = payload.position {
-        context.position = Some(position);
-    }
-    if let Some(job_description) = payload.job_description {
-        context.job_description = Some(job_description);
-    }
+    let context = ContextBuilder::new(&state)
+        .overrides(payload.company, payload.position, payload.job_description)
+        .provider(&payload.provider)
+        .question(extracted_text)
+        .build();
     
     // Build AI prompt using extracted text
     let system_prompt = format!(
@@ -4085,7 +4578,7 @@ async fn generate_ai_analysis_from_text(
     // Initialize streaming state and timing
     let stream_start_time = std::time::Instant::now();
     info!("[AI_STREAMING] Starting AI analysis with extracted accessibility text...");
-    let _ = app_handle.emit("ai-stream-start", ());
+    let _ = app_handle.emit(events::AI_STREAM_START, ());
     
     // Stream AI analysis
     let app_handle_clone = app_handle.clone();
@@ -4138,27 +4631,16 @@ async fn generate_ai_analysis_from_text(
                         .map_err(|e| format!("Invalid Pollinations model: {}", e))?;
                     
                     // Try Pollinations streaming
-                    let app_handle_for_streaming = app_handle_clone.clone();
-                    match client.generate_answer_streaming(
+                    let tokens = ui_token_coalescer(&app_handle_clone, false);
+                    let token_sink = tokens.clone();
+                    let streamed = client.generate_answer_streaming(
                         &analysis_prompt, 
                         &context, 
                         model,
-                        move |token: &str| {
-                            info!("[STREAM_TOKEN] Received Pollinations token: '{}'", token.chars().take(50).collect::<String>());
-                            
-                            let data = AiResponseData {
-                                message_type: "stream-token".to_string(),
-                                text: Some(token.to_string()),
-                                error: None,
-                            };
-                            let app_handle_for_token = app_handle_for_streaming.clone();
-                            tokio::spawn(async move {
-                                if let Err(e) = send_ai_response_data(app_handle_for_token, data).await {
-                                    error!("Failed to send streaming token to UI: {}", e);
-                                }
-                            });
-                        }
-                    ).await {
+                        move |token: &str| token_sink.push(token)
+                    ).await;
+                    tokens.finish().await;
+                    match streamed {
                         Ok(result) => {
                             info!("[SUCCESS] Pollinations streaming completed successfully");
                             Ok(result)
@@ -4292,32 +4774,33 @@ async fn generate_ai_analysis_from_text(
     let _ = send_ai_response_data(app_handle.clone(), completion_data).await;
     
     // Emit completion event
-    let _ = app_handle.emit("ai-stream-complete", &analysis_result);
+    let _ = app_handle.emit(events::AI_STREAM_COMPLETE, &analysis_result);
+    scripting::dispatch(scripting::ScriptEvent::AnswerComplete, &analysis_result);
     
     info!("[SUCCESS] Accessibility-based AI analysis completed: {}", analysis_result.generated_question);
     
     // Store the generated question
     let question_text = &analysis_result.generated_question;
     if !question_text.is_empty() {
-        let storage_payload = serde_json::json!({
-            "questionText": question_text,
-            "questionNumber": 1,
-            "category": "ai_generated",
-            "difficultyLevel": "medium",
-            "source": "accessibility_api_analysis",
-            "metadata": {
-                "timestamp": chrono::Utc::now().to_rfc3339(),
-                "aiProvider": payload.provider,
-                "aiModel": payload.model,
-                "confidence": analysis_result.confidence,
-                "analysisType": "accessibility_api",
-                "expectedDuration": 5,
-                "sourceDescription": source_description,
-                "extractedTextLength": extracted_text.len()
-            }
-        });
+        let storage_payload = events::StoreAiQuestion {
+            question_text: question_text.clone(),
+            question_number: 1,
+            category: "ai_generated".to_string(),
+            difficulty_level: "medium".to_string(),
+            source: "accessibility_api_analysis".to_string(),
+            metadata: events::StoreAiQuestionMetadata {
+                timestamp: chrono::Utc::now().to_rfc3339(),
+                ai_provider: payload.provider.clone(),
+                ai_model: payload.model.clone(),
+                confidence: analysis_result.confidence,
+                analysis_type: "accessibility_api".to_string(),
+                expected_duration: 5,
+                source_description: source_description.to_string(),
+                extracted_text_length: extracted_text.len(),
+            },
+        };
         
-        if let Err(e) = app_handle.emit("store-ai-question", storage_payload) {
+        if let Err(e) = app_handle.emit(events::STORE_AI_QUESTION, storage_payload) {
             error!("[ERROR] Failed to emit store-ai-question event: {}", e);
         } else {
             info!("[SUCCESS] Accessibility-based AI question storage event emitted");
@@ -4523,87 +5006,102 @@ fn extract_confidence_from_text(text: &str) -> f32 {
 // DPI-aware window management command implementations
 
 #[tauri::command]
-fn setup_dpi_aware_positioning(app_handle: AppHandle) -> Result<String, String> {
-    info!("🖥️ Setting up DPI-aware positioning for main window");
+#[specta::specta]
+fn setup_dpi_aware_positioning(app_handle: AppHandle) -> Response<String> {
+    envelope::respond_sync("setup_dpi_aware_positioning", || {
+        info!("🖥️ Setting up DPI-aware positioning for main window");
     
-    match window_manager::setup_main_window_dpi_aware(&app_handle) {
-        Ok(_) => Ok("DPI-aware positioning setup completed".to_string()),
-        Err(e) => {
-            error!("Failed to setup DPI-aware positioning: {}", e);
-            Err(e)
+        match window_manager::setup_main_window_dpi_aware(&app_handle) {
+            Ok(_) => Ok("DPI-aware positioning setup completed".to_string()),
+            Err(e) => {
+                error!("Failed to setup DPI-aware positioning: {}", e);
+                Err(e.into())
+            }
         }
-    }
+    })
 }
 
 #[tauri::command]
-fn get_window_info(app_handle: AppHandle) -> Result<window_manager::WindowConfiguration, String> {
-    info!("📊 Getting current window information");
-    
-    if let Some(window) = app_handle.get_webview_window("main") {
-        match window_manager::get_window_configuration(&window) {
-            Ok(config) => {
-                info!("✅ Window info: {}x{} at ({}, {}) on {}", 
-                      config.width, config.height, config.x, config.y, config.monitor_name);
-                Ok(config)
-            }
-            Err(e) => {
-                error!("Failed to get window info: {}", e);
-                Err(e)
+#[specta::specta]
+fn get_window_info(app_handle: AppHandle) -> Response<window_manager::WindowConfiguration> {
+    envelope::respond_sync("get_window_info", || {
+        info!("📊 Getting current window information");
+    
+        if let Some(window) = app_handle.get_webview_window("main") {
+            match window_manager::get_window_configuration(&window) {
+                Ok(config) => {
+                    info!("✅ Window info: {}x{} at ({}, {}) on {}", 
+                          config.width, config.height, config.x, config.y, config.monitor_name);
+                    Ok(config)
+                }
+                Err(e) => {
+                    error!("Failed to get window info: {}", e);
+                    Err(e.into())
+                }
             }
+        } else {
+            Err("Main window not found".into())
         }
-    } else {
-        Err("Main window not found".to_string())
-    }
+    })
 }
 
 #[tauri::command]
-fn get_monitors_info(app_handle: AppHandle) -> Result<Vec<serde_json::Value>, String> {
-    info!("🖥️ Getting monitors information");
-    
-    match window_manager::get_monitors_info(&app_handle) {
-        Ok(monitors) => {
-            info!("✅ Found {} monitors", monitors.len());
-            Ok(monitors)
-        }
-        Err(e) => {
-            error!("Failed to get monitors info: {}", e);
-            Err(e)
+#[specta::specta]
+fn get_monitors_info(app_handle: AppHandle) -> Response<Vec<serde_json::Value>> {
+    envelope::respond_sync("get_monitors_info", || {
+        info!("🖥️ Getting monitors information");
+    
+        match window_manager::get_monitors_info(&app_handle) {
+            Ok(monitors) => {
+                info!("✅ Found {} monitors", monitors.len());
+                Ok(monitors)
+            }
+            Err(e) => {
+                error!("Failed to get monitors info: {}", e);
+                Err(e.into())
+            }
         }
-    }
+    })
 }
 
 #[tauri::command]
-fn lock_window_size(app_handle: AppHandle, width: u32, height: u32) -> Result<String, String> {
-    info!("🔒 Locking window size to {}x{}", width, height);
-    
-    if let Some(window) = app_handle.get_webview_window("main") {
-        match window_manager::lock_window_size(&window, width, height) {
-            Ok(_) => Ok(format!("Window size locked to {}x{}", width, height)),
-            Err(e) => {
-                error!("Failed to lock window size: {}", e);
-                Err(e)
+#[specta::specta]
+fn lock_window_size(app_handle: AppHandle, width: u32, height: u32) -> Response<String> {
+    envelope::respond_sync("lock_window_size", || {
+        info!("🔒 Locking window size to {}x{}", width, height);
+    
+        if let Some(window) = app_handle.get_webview_window("main") {
+            match window_manager::lock_window_size(&window, width, height) {
+                Ok(_) => Ok(format!("Window size locked to {}x{}", width, height)),
+                Err(e) => {
+                    error!("Failed to lock window size: {}", e);
+                    Err(e.into())
+                }
             }
+        } else {
+            Err("Main window not found".into())
         }
-    } else {
-        Err("Main window not found".to_string())
-    }
+    })
 }
 
 #[tauri::command]
-fn ensure_window_visible(app_handle: AppHandle) -> Result<String, String> {
-    info!("👁️ Ensuring window is visible on current screen setup");
-    
-    if let Some(window) = app_handle.get_webview_window("main") {
-        match window_manager::ensure_window_visible(&window) {
-            Ok(_) => Ok("Window visibility check completed".to_string()),
-            Err(e) => {
-                error!("Failed to ensure window visibility: {}", e);
-                Err(e)
+#[specta::specta]
+fn ensure_window_visible(app_handle: AppHandle) -> Response<String> {
+    envelope::respond_sync("ensure_window_visible", || {
+        info!("👁️ Ensuring window is visible on current screen setup");
+    
+        if let Some(window) = app_handle.get_webview_window("main") {
+            match window_manager::ensure_window_visible(&window) {
+                Ok(_) => Ok("Window visibility check completed".to_string()),
+                Err(e) => {
+                    error!("Failed to ensure window visibility: {}", e);
+                    Err(e.into())
+                }
             }
+        } else {
+            Err("Main window not found".into())
         }
-    } else {
-        Err("Main window not found".to_string())
-    }
+    })
 }
 
 // Helper function to get environment variables using runtime loading
@@ -4685,38 +5183,40 @@ async fn handle_protocol_launch_with_temp_token(
             temp_token: temp_token.clone(),
         };
         
-        match connect_with_temp_token(auth_payload).await {
+        match connect_with_temp_token(auth_payload, None).await.into_result() {
             Ok(auth_response) => {
                 if auth_response.success {
                     info!("✅ Auto-authentication successful!");
                     
                     // Emit successful authentication event to frontend
-                    let auth_event_data = serde_json::json!({
-                        "type": "temp-token-auth-success",
-                        "session_id": session_id,
-                        "user_id": auth_response.user_id,
-                        "session": auth_response.session,
-                        "remaining_credits": auth_response.remaining_credits,
-                        "auto_authenticated": true,
-                        "launched_at": chrono::Utc::now().to_rfc3339()
-                    });
+                    let auth_event_data = events::TempTokenAuthResult {
+                        kind: "temp-token-auth-success".to_string(),
+                        session_id: session_id.clone(),
+                        user_id: auth_response.user_id,
+                        session: auth_response.session,
+                        remaining_credits: auth_response.remaining_credits,
+                        auto_authenticated: true,
+                        launched_at: chrono::Utc::now().to_rfc3339(),
+                        ..Default::default()
+                    };
                     
-                    if let Err(e) = app_handle.emit("temp-token-auth-result", auth_event_data) {
+                    if let Err(e) = app_handle.emit(events::TEMP_TOKEN_AUTH_RESULT, auth_event_data) {
                         error!("Failed to emit temp-token-auth-result event: {}", e);
                     }
                 } else {
                     warn!("❌ Auto-authentication failed: {}", auth_response.message);
                     
                     // Emit failed authentication event
-                    let auth_event_data = serde_json::json!({
-                        "type": "temp-token-auth-failed",
-                        "session_id": session_id,
-                        "error": auth_response.message,
-                        "auto_authenticated": true,
-                        "launched_at": chrono::Utc::now().to_rfc3339()
-                    });
+                    let auth_event_data = events::TempTokenAuthResult {
+                        kind: "temp-token-auth-failed".to_string(),
+                        session_id: session_id.clone(),
+                        error: Some(auth_response.message),
+                        auto_authenticated: true,
+                        launched_at: chrono::Utc::now().to_rfc3339(),
+                        ..Default::default()
+                    };
                     
-                    if let Err(e) = app_handle.emit("temp-token-auth-result", auth_event_data) {
+                    if let Err(e) = app_handle.emit(events::TEMP_TOKEN_AUTH_RESULT, auth_event_data) {
                         error!("Failed to emit temp-token-auth-result event: {}", e);
                     }
                 }
@@ -4725,15 +5225,16 @@ async fn handle_protocol_launch_with_temp_token(
                 error!("❌ Auto-authentication error: {}", e);
                 
                 // Emit error authentication event
-                let auth_event_data = serde_json::json!({
-                    "type": "temp-token-auth-error",
-                    "session_id": session_id,
-                    "error": e,
-                    "auto_authenticated": true,
-                    "launched_at": chrono::Utc::now().to_rfc3339()
-                });
+                let auth_event_data = events::TempTokenAuthResult {
+                    kind: "temp-token-auth-error".to_string(),
+                    session_id: session_id.clone(),
+                    error: Some(e.to_string()),
+                    auto_authenticated: true,
+                    launched_at: chrono::Utc::now().to_rfc3339(),
+                    ..Default::default()
+                };
                 
-                if let Err(emit_err) = app_handle.emit("temp-token-auth-result", auth_event_data) {
+                if let Err(emit_err) = app_handle.emit(events::TEMP_TOKEN_AUTH_RESULT, auth_event_data) {
                     error!("Failed to emit temp-token-auth-result event: {}", emit_err);
                 }
             }
@@ -4742,15 +5243,16 @@ async fn handle_protocol_launch_with_temp_token(
         info!("📋 No auto-authentication - sending session launch data to frontend");
         
         // Send session launch data with temp token to frontend for manual handling
-        let session_launch_data = serde_json::json!({
-            "type": "session-launch",
-            "session_id": session_id,
-            "temp_token": temp_token,
-            "auto_connect": auto_connect,
-            "launched_at": chrono::Utc::now().to_rfc3339()
-        });
+        let session_launch_data = events::SessionLaunch {
+            kind: Some("session-launch".to_string()),
+            session_id: session_id.clone(),
+            temp_token,
+            auto_connect,
+            launched_at: chrono::Utc::now().to_rfc3339(),
+            ..Default::default()
+        };
         
-        if let Err(e) = app_handle.emit("session-launch", session_launch_data) {
+        if let Err(e) = app_handle.emit(events::SESSION_LAUNCH, session_launch_data) {
             error!("Failed to emit session-launch event: {}", e);
         }
     }
@@ -4817,82 +5319,135 @@ fn log_environment_status() {
 
 // Temporary token authentication command
 #[tauri::command]
-async fn connect_with_temp_token(payload: TempTokenAuthPayload) -> Result<TempTokenAuthResponse, String> {
-    info!("🔐 Authenticating with temporary token for session: {}", payload.session_id);
-    
-    // Prepare the request
-    let backend_url = get_env_var("BACKEND_URL")
-        .unwrap_or_else(|| "http://localhost:3001".to_string());
-    let endpoint = format!("{}/api/sessions/{}/connect-with-temp-token", backend_url, payload.session_id);
-    
-    info!("📡 Sending temp token auth request to: {}", endpoint);
-    
-    // Create the request payload
-    let request_body = serde_json::json!({
-        "temp_token": payload.temp_token
-    });
-    
-    // Make the HTTP request
-    let client = reqwest::Client::new();
-    match client
-        .post(&endpoint)
-        .header("Content-Type", "application/json")
-        .json(&request_body)
-        .send()
-        .await
-    {
-        Ok(response) => {
-            let status = response.status();
-            info!("📡 Received response with status: {}", status);
+#[specta::specta]
+async fn connect_with_temp_token(payload: TempTokenAuthPayload, idempotency_key: Option<String>) -> Response<TempTokenAuthResponse> {
+    envelope::respond("connect_with_temp_token", async move {
+        info!("🔐 Authenticating with temporary token for session: {}", payload.session_id);
+    
+        // Prepare the request
+        let backend_url = get_env_var("BACKEND_URL")
+            .unwrap_or_else(|| "http://localhost:3001".to_string());
+        let endpoint = format!("{}/api/sessions/{}/connect-with-temp-token", backend_url, payload.session_id);
+    
+        info!("📡 Sending temp token auth request to: {}", endpoint);
+    
+        // Create the request payload
+        let request_body = serde_json::json!({
+            "temp_token": payload.temp_token
+        });
+    
+        // Make the HTTP request, once per idempotency key
+        let session_id = payload.session_id.clone();
+        let outcome = idempotency::run("connect_with_temp_token", idempotency_key, &session_id, |attempt| async move {
+            let client = reqwest::Client::new();
+            match client
+                .post(&endpoint)
+                .header("Content-Type", "application/json")
+                .header(idempotency::HEADER, &attempt.key)
+                .json(&request_body)
+                .send_traced()
+                .await
+            {
+                Ok(response) => {
+                    let status = response.status();
+                    info!("📡 Received response with status: {}", status);
             
-            if status.is_success() {
-                match response.json::<TempTokenAuthResponse>().await {
-                    Ok(auth_response) => {
-                        if auth_response.success {
-                            info!("✅ Temporary token authentication successful!");
-                            info!("👤 Authenticated user: {:?}", auth_response.user_id);
-                            info!("💳 Remaining credits: {:?}", auth_response.remaining_credits);
-                        } else {
-                            warn!("❌ Authentication failed: {}", auth_response.message);
+                    if status.is_success() {
+                        match response.json::<TempTokenAuthResponse>().await {
+                            Ok(auth_response) => {
+                                if auth_response.success {
+                                    info!("✅ Temporary token authentication successful!");
+                                    info!("👤 Authenticated user: {:?}", auth_response.user_id);
+                                    info!("💳 Remaining credits: {:?}", auth_response.remaining_credits);
+                                } else {
+                                    warn!("❌ Authentication failed: {}", auth_response.message);
+                                }
+                                Ok(auth_response)
+                            }
+                            Err(e) => {
+                                let error_msg = format!("Failed to parse authentication response: {}", e);
+                                error!("❌ {}", error_msg);
+                                Err(error_msg.into())
+                            }
                         }
-                        Ok(auth_response)
-                    }
-                    Err(e) => {
-                        let error_msg = format!("Failed to parse authentication response: {}", e);
+                    } else if error::ErrorCode::from_status(status).retryable() {
+                        // The backend is down or overloaded; keep the key pending for a retry
+                        Err(AppError::new(error::ErrorCode::from_status(status), format!("Authentication service unavailable: {}", status)))
+                    } else {
+                        // Handle HTTP error status
+                        let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+                        let error_msg = format!("Authentication failed with status {}: {}", status, error_text);
                         error!("❌ {}", error_msg);
-                        Err(error_msg)
+                
+                        // Return a failed response instead of an error for better UX
+                        Ok(TempTokenAuthResponse {
+                            success: false,
+                            message: error_msg,
+                            user_id: None,
+                            session: None,
+                            remaining_credits: None,
+                            expires_at: None,
+                            expires_in: None,
+                            entitlement: None,
+                            offline: false,
+                        })
                     }
                 }
-            } else {
-                // Handle HTTP error status
-                let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-                let error_msg = format!("Authentication failed with status {}: {}", status, error_text);
-                error!("❌ {}", error_msg);
-                
+                // The backend may have seen the request; keep the key pending for a retry
+                Err(e) => Err(e.context("Network error during authentication")),
+            }
+        })
+        .await;
+    
+        match outcome {
+            Err(e) if e.retryable() => {
+                error!("❌ {}", e.message);
+            
+                // A session the backend confirmed recently stays usable through a short outage
+                if let Some(grant) = entitlement::try_offline(&payload.session_id, &e.message) {
+                    return Ok(TempTokenAuthResponse {
+                        success: true,
+                        message: format!("Backend unreachable; continuing offline until {}", grant.usable_until.to_rfc3339()),
+                        user_id: grant.user_id,
+                        session: None,
+                        remaining_credits: grant.credits_available.map(|credits| credits.max(0) as u32),
+                        expires_at: None,
+                        expires_in: None,
+                        entitlement: None,
+                        offline: true,
+                    });
+                }
+            
                 // Return a failed response instead of an error for better UX
                 Ok(TempTokenAuthResponse {
                     success: false,
-                    message: error_msg,
+                    message: e.message,
                     user_id: None,
                     session: None,
                     remaining_credits: None,
+                    expires_at: None,
+                    expires_in: None,
+                    entitlement: None,
+                    offline: false,
                 })
             }
+            Ok(auth_response) => {
+                if auth_response.success {
+                    entitlement::remember(&payload.session_id, auth_response.entitlement.as_ref());
+                    session_auth::track(
+                        &payload.session_id,
+                        &payload.temp_token,
+                        auth_response.user_id.clone(),
+                        auth_response.expires_at.as_deref(),
+                        auth_response.expires_in,
+                    );
+                }
+                Ok(auth_response)
+            }
+            outcome => outcome,
         }
-        Err(e) => {
-            let error_msg = format!("Network error during authentication: {}", e);
-            error!("❌ {}", error_msg);
-            
-            // Return a failed response instead of an error for better UX
-            Ok(TempTokenAuthResponse {
-                success: false,
-                message: error_msg,
-                user_id: None,
-                session: None,
-                remaining_credits: None,
-            })
-        }
-    }
+    })
+    .await
 }
 
 
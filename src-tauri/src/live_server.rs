@@ -0,0 +1,361 @@
+// Local WebSocket server for external tools
+// OBS overlays, note-taking apps or a second machine can follow a session live: when enabled, a
+// WebSocket server on 127.0.0.1 (or every interface with `allow_lan`) broadcasts transcript
+// segments, detected questions and answer streams as JSON messages:
+//   {"topic": "answer", "event": "ai-stream-token", "timestamp": "...", "data": {...}}
+// where `data` is the payload of the app event named by `event`. Clients authenticate with the
+// token from the settings, as `?token=...` or `Authorization: Bearer ...`, and can narrow what they
+// get with `?topics=transcript,question` or by sending {"type": "subscribe", "topics": [...]}.
+// Off until the user opts in; settings are stored in %APPDATA%\MockMate\live_server.json.
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+
+use futures_util::{SinkExt, StreamExt};
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Listener};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, watch};
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response as HandshakeResponse};
+use tokio_tungstenite::tungstenite::http::StatusCode;
+use tokio_tungstenite::tungstenite::protocol::Message;
+
+use crate::envelope::{self, Response};
+use crate::events;
+use crate::local_server;
+
+const DEFAULT_PORT: u16 = 18480;
+/// Messages buffered per client before a slow client starts skipping
+const CHANNEL_CAPACITY: usize = 512;
+
+/// App events forwarded to clients, by topic
+const FORWARDED: &[(Topic, &str)] = &[
+    (Topic::Transcript, events::TRANSCRIPTION_RESULT),
+    (Topic::Transcript, events::ZOOM_CAPTION),
+    (Topic::Question, events::ACCESSIBILITY_QUESTION_DETECTED),
+    (Topic::Question, events::QUESTION_TRANSLATED),
+    (Topic::Question, events::QUESTION_DEDUPLICATED),
+    (Topic::Answer, events::AI_STREAM_START),
+    (Topic::Answer, events::AI_STREAM_TOKEN),
+    (Topic::Answer, events::AI_STREAM_COMPLETE),
+    (Topic::Answer, events::AI_STREAM_ERROR),
+];
+
+static SETTINGS: Lazy<Mutex<LiveServerSettings>> = Lazy::new(|| Mutex::new(local_server::load_settings(SETTINGS_FILE)));
+static STATUS: Lazy<Mutex<LiveServerStatus>> = Lazy::new(|| Mutex::new(LiveServerStatus::default()));
+static SERVER: Lazy<Mutex<Option<RunningServer>>> = Lazy::new(|| Mutex::new(None));
+static BROADCAST: Lazy<broadcast::Sender<(Topic, String)>> = Lazy::new(|| broadcast::channel(CHANNEL_CAPACITY).0);
+static CLIENTS: AtomicUsize = AtomicUsize::new(0);
+static LISTENING: OnceLock<()> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Topic {
+    Transcript,
+    Question,
+    Answer,
+}
+
+impl Topic {
+    const ALL: [Topic; 3] = [Topic::Transcript, Topic::Question, Topic::Answer];
+
+    fn parse(name: &str) -> Option<Topic> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "transcript" => Some(Topic::Transcript),
+            "question" | "questions" => Some(Topic::Question),
+            "answer" | "answers" => Some(Topic::Answer),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct LiveServerSettings {
+    pub enabled: bool,
+    pub port: u16,
+    /// Listen on every interface so other machines on the network can connect
+    pub allow_lan: bool,
+    /// Clients must present this token
+    pub token: String,
+}
+
+impl Default for LiveServerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_PORT,
+            allow_lan: false,
+            token: local_server::new_token(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct LiveServerStatus {
+    pub running: bool,
+    /// e.g. "ws://127.0.0.1:18480"
+    pub address: Option<String>,
+    pub clients: usize,
+    pub messages_sent: u64,
+    pub last_error: Option<String>,
+}
+
+struct RunningServer {
+    stop: watch::Sender<bool>,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+/// Client → server control message
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    Subscribe { topics: Vec<String> },
+}
+
+const SETTINGS_FILE: &str = "live_server.json";
+
+/// Validate settings; listening beyond loopback is subject to local-only mode
+fn check_settings(settings: &LiveServerSettings) -> Result<(), String> {
+    local_server::check_port_and_token(settings.port, &settings.token)?;
+    if settings.allow_lan {
+        crate::privacy::ensure_cloud_allowed("Live server network access")?;
+    }
+    Ok(())
+}
+
+fn query_param<'a>(request: &'a Request, name: &str) -> Option<&'a str> {
+    request.uri().query()?.split('&').find_map(|pair| {
+        let (key, value) = pair.split_once('=')?;
+        (key == name).then_some(value)
+    })
+}
+
+fn parse_topics<'a>(names: impl Iterator<Item = &'a str>) -> Vec<Topic> {
+    let mut topics = Vec::new();
+    for topic in names.filter_map(Topic::parse) {
+        if !topics.contains(&topic) {
+            topics.push(topic);
+        }
+    }
+    topics
+}
+
+/// Forward an app event to connected clients
+fn publish(topic: Topic, event: &str, payload: &str) {
+    if CLIENTS.load(Ordering::Relaxed) == 0 {
+        return;
+    }
+    let data: serde_json::Value = serde_json::from_str(payload).unwrap_or(serde_json::Value::Null);
+    let message = serde_json::json!({
+        "topic": topic,
+        "event": event,
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "data": data,
+    });
+    let _ = BROADCAST.send((topic, message.to_string()));
+}
+
+async fn serve_client(stream: TcpStream, peer: SocketAddr, token: String, mut stop: watch::Receiver<bool>) {
+    let mut topics = Topic::ALL.to_vec();
+    // The error type is tungstenite's handshake response
+    #[allow(clippy::result_large_err)]
+    let callback = |request: &Request, response: HandshakeResponse| -> Result<HandshakeResponse, ErrorResponse> {
+        let bearer = request
+            .headers()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        let given = bearer.or_else(|| query_param(request, "token")).unwrap_or_default();
+        if !local_server::token_matches(given.trim(), &token) {
+            let mut rejection = ErrorResponse::new(Some("Invalid or missing token".to_string()));
+            *rejection.status_mut() = StatusCode::UNAUTHORIZED;
+            return Err(rejection);
+        }
+        if let Some(names) = query_param(request, "topics") {
+            topics = parse_topics(names.split(','));
+        }
+        Ok(response)
+    };
+    let socket = match tokio_tungstenite::accept_hdr_async(stream, callback).await {
+        Ok(socket) => socket,
+        Err(e) => {
+            debug!("Live server rejected {}: {}", peer, e);
+            return;
+        }
+    };
+    let (mut write, mut read) = socket.split();
+    let mut messages = BROADCAST.subscribe();
+    let clients = CLIENTS.fetch_add(1, Ordering::Relaxed) + 1;
+    STATUS.lock().clients = clients;
+    info!("📡 Live server client connected from {} ({} connected)", peer, clients);
+
+    loop {
+        tokio::select! {
+            message = messages.recv() => match message {
+                Ok((topic, body)) => {
+                    if !topics.contains(&topic) {
+                        continue;
+                    }
+                    if write.send(Message::Text(body)).await.is_err() {
+                        break;
+                    }
+                    STATUS.lock().messages_sent += 1;
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    debug!("Live server client {} skipped {} messages", peer, skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            incoming = read.next() => match incoming {
+                Some(Ok(Message::Text(text))) => match serde_json::from_str::<ClientMessage>(&text) {
+                    Ok(ClientMessage::Subscribe { topics: names }) => {
+                        topics = parse_topics(names.iter().map(String::as_str));
+                    }
+                    Err(e) => debug!("Ignoring live server message from {}: {}", peer, e),
+                },
+                Some(Ok(Message::Ping(payload))) => {
+                    let _ = write.send(Message::Pong(payload)).await;
+                }
+                Some(Ok(Message::Close(_))) | None | Some(Err(_)) => break,
+                Some(Ok(_)) => {}
+            },
+            _ = stop.changed() => {
+                let _ = write.send(Message::Close(None)).await;
+                break;
+            }
+        }
+    }
+
+    let clients = CLIENTS.fetch_sub(1, Ordering::Relaxed) - 1;
+    STATUS.lock().clients = clients;
+    info!("📡 Live server client {} disconnected", peer);
+}
+
+/// Bind and accept clients until stopped; replaces a running server
+async fn start(settings: LiveServerSettings) -> Result<LiveServerStatus, String> {
+    let ip = if settings.allow_lan { IpAddr::V4(Ipv4Addr::UNSPECIFIED) } else { IpAddr::V4(Ipv4Addr::LOCALHOST) };
+    local_server::wait_stopped(stop()).await;
+    let listener = TcpListener::bind(SocketAddr::new(ip, settings.port))
+        .await
+        .map_err(|e| format!("Live server could not listen on port {}: {}", settings.port, e))?;
+
+    let (stop_tx, mut stop_rx) = watch::channel(false);
+    let address = format!("ws://{}:{}", ip, settings.port);
+    info!("📡 Live server listening on {}", address);
+    let status = {
+        let mut status = STATUS.lock();
+        status.running = true;
+        status.address = Some(address);
+        status.last_error = None;
+        status.clone()
+    };
+
+    let task = tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, peer)) => {
+                        tauri::async_runtime::spawn(serve_client(stream, peer, settings.token.clone(), stop_rx.clone()));
+                    }
+                    Err(e) => warn!("Live server accept failed: {}", e),
+                },
+                _ = stop_rx.changed() => break,
+            }
+        }
+        info!("📡 Live server stopped");
+    });
+    *SERVER.lock() = Some(RunningServer { stop: stop_tx, task });
+    Ok(status)
+}
+
+/// Signal the running server and its clients to stop; returns the accept task to wait on
+fn stop() -> Option<tauri::async_runtime::JoinHandle<()>> {
+    let server = SERVER.lock().take()?;
+    let _ = server.stop.send(true);
+    let mut status = STATUS.lock();
+    status.running = false;
+    status.address = None;
+    Some(server.task)
+}
+
+/// Forward app events to the broadcast channel and start the server when enabled
+pub fn init(app_handle: AppHandle) {
+    if LISTENING.set(()).is_ok() {
+        for (topic, event) in FORWARDED {
+            let (topic, name) = (*topic, *event);
+            app_handle.listen_any(name, move |event| publish(topic, name, event.payload()));
+        }
+    }
+
+    let settings = SETTINGS.lock().clone();
+    if settings.enabled {
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = start(settings).await {
+                warn!("{}", e);
+                STATUS.lock().last_error = Some(e);
+            }
+        });
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_live_server_settings() -> Response<LiveServerSettings> {
+    envelope::respond("get_live_server_settings", async move {
+        Ok(SETTINGS.lock().clone())
+    })
+    .await
+}
+
+/// Save the live server settings and apply them; a restart or stop disconnects every client
+#[tauri::command]
+#[specta::specta]
+pub async fn set_live_server_settings(settings: LiveServerSettings) -> Response<LiveServerStatus> {
+    envelope::respond("set_live_server_settings", async move {
+        let settings = LiveServerSettings { token: settings.token.trim().to_string(), ..settings };
+        check_settings(&settings)?;
+        let enabled = settings.enabled;
+        let start_server = |settings| async move {
+            start(settings).await.inspect_err(|e| STATUS.lock().last_error = Some(e.clone()))
+        };
+        let stop_server = || {
+            stop();
+            STATUS.lock().clone()
+        };
+        Ok(local_server::apply_settings(SETTINGS_FILE, settings, &SETTINGS, enabled, start_server, stop_server).await?)
+    })
+    .await
+}
+
+/// Replace the token; connected clients are dropped and must reconnect with the new one
+#[tauri::command]
+#[specta::specta]
+pub async fn regenerate_live_server_token() -> Response<LiveServerSettings> {
+    envelope::respond("regenerate_live_server_token", async move {
+        let settings = {
+            let mut settings = SETTINGS.lock();
+            settings.token = local_server::new_token();
+            local_server::save_settings(SETTINGS_FILE, &*settings)?;
+            settings.clone()
+        };
+        if settings.enabled {
+            start(settings.clone()).await?;
+        }
+        Ok(settings)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_live_server_status() -> Response<LiveServerStatus> {
+    envelope::respond("get_live_server_status", async move {
+        Ok(STATUS.lock().clone())
+    })
+    .await
+}
@@ -0,0 +1,102 @@
+// Pieces shared by the token-authenticated local servers (rest_api, live_server)
+// Token generation and checking, settings stored as JSON in the profile's data folder, and the
+// save-then-start-or-stop flow behind their settings commands.
+
+use std::future::Future;
+use std::path::PathBuf;
+
+use parking_lot::Mutex;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+pub fn new_token() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+/// Compare without leaking the position of the first difference through timing
+pub fn token_matches(given: &str, expected: &str) -> bool {
+    given.len() == expected.len() && given.bytes().zip(expected.bytes()).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+pub fn check_port_and_token(port: u16, token: &str) -> Result<(), String> {
+    if port == 0 {
+        return Err("Port must be between 1 and 65535".to_string());
+    }
+    if token.trim().len() < 16 {
+        return Err("Token must be at least 16 characters".to_string());
+    }
+    Ok(())
+}
+
+fn settings_path(file_name: &str) -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join(file_name)
+}
+
+/// Stored settings, or defaults that are saved right away so the generated token survives restarts
+pub fn load_settings<S: Serialize + DeserializeOwned + Default>(file_name: &str) -> S {
+    let settings: Option<S> = std::fs::read(settings_path(file_name))
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok());
+    settings.unwrap_or_else(|| {
+        let settings = S::default();
+        let _ = save_settings(file_name, &settings);
+        settings
+    })
+}
+
+pub fn save_settings<S: Serialize>(file_name: &str, settings: &S) -> Result<(), String> {
+    let path = settings_path(file_name);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(settings).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| e.to_string())
+}
+
+/// Wait for a stopped server's accept task; its listener must be closed before the port can be
+/// bound again
+pub async fn wait_stopped(task: Option<tauri::async_runtime::JoinHandle<()>>) {
+    if let Some(task) = task {
+        let _ = task.await;
+    }
+}
+
+/// Save settings and apply them: enabling starts (or restarts) the server, disabling stops it
+pub async fn apply_settings<S, T, F>(
+    file_name: &str,
+    settings: S,
+    current: &Mutex<S>,
+    enabled: bool,
+    start: impl FnOnce(S) -> F,
+    stop: impl FnOnce() -> T,
+) -> Result<T, String>
+where
+    S: Serialize + Clone,
+    F: Future<Output = Result<T, String>>,
+{
+    save_settings(file_name, &settings)?;
+    *current.lock() = settings.clone();
+    if enabled {
+        start(settings).await
+    } else {
+        Ok(stop())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_matches_only_the_exact_token() {
+        let token = new_token();
+        assert!(token_matches(&token, &token));
+        assert!(!token_matches(&token[1..], &token));
+        assert!(!token_matches(&format!("{}x", token), &token));
+        let first = if token.starts_with('0') { "1" } else { "0" };
+        assert!(!token_matches(&format!("{}{}", first, &token[1..]), &token));
+        assert!(!token_matches("", &token));
+    }
+}
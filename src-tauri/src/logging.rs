@@ -0,0 +1,383 @@
+// Structured logging for MockMate
+// tracing subscriber with a rotating log file in the app data dir
+// Existing `log` macros are bridged into tracing, so modules don't need to change
+// Recent events are also kept in memory for the in-app debug console
+
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+
+use chrono::{Local, NaiveDate};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_log::NormalizeEvent;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+use crate::envelope::{self, Response};
+use crate::events;
+
+/// Name of the active log file; rotated files get a date/sequence suffix
+pub const LOG_FILE_NAME: &str = "mockmate.log";
+/// Rotate the active file once it grows past this size
+const MAX_LOG_FILE_BYTES: u64 = 10 * 1024 * 1024;
+/// Number of rotated files kept next to the active one
+const MAX_ROTATED_FILES: usize = 7;
+/// Default per-module filter; overridable with MOCKMATE_LOG (EnvFilter syntax)
+const DEFAULT_FILTER: &str = "info,tao=warn,wry=warn,hyper=warn,reqwest=warn,rustls=warn,tungstenite=warn,tokio_tungstenite=warn,tokio_postgres=warn";
+
+/// Number of recent log entries kept in memory for tail_logs / log-event
+const LOG_BUFFER_CAPACITY: usize = 2000;
+
+/// Keeps the non-blocking file writer alive for the lifetime of the process
+static FILE_GUARD: OnceLock<WorkerGuard> = OnceLock::new();
+static LOG_DIR: OnceLock<PathBuf> = OnceLock::new();
+/// Recent log entries, oldest first
+static LOG_BUFFER: Lazy<Mutex<VecDeque<LogEntry>>> =
+    Lazy::new(|| Mutex::new(VecDeque::with_capacity(LOG_BUFFER_CAPACITY)));
+/// Set once the Tauri app is up so entries can be streamed as `log-event`
+static LOG_EVENT_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+thread_local! {
+    /// Guards against recursion when emitting `log-event` itself produces log output
+    static IN_LOG_EMIT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// A single captured log record
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct LogEntry {
+    pub timestamp: String,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+}
+
+/// Directory holding MockMate log files (%APPDATA%\MockMate\logs)
+pub fn log_dir() -> PathBuf {
+    LOG_DIR
+        .get_or_init(|| {
+            crate::portable::data_root()
+                .join("MockMate")
+                .join("logs")
+        })
+        .clone()
+}
+
+/// Path of the log file currently being written
+pub fn current_log_file() -> PathBuf {
+    log_dir().join(LOG_FILE_NAME)
+}
+
+/// Build the filter from MOCKMATE_LOG, then RUST_LOG, then the built-in default
+fn build_filter() -> EnvFilter {
+    let directives = std::env::var("MOCKMATE_LOG")
+        .or_else(|_| std::env::var("RUST_LOG"))
+        .unwrap_or_else(|_| DEFAULT_FILTER.to_string());
+
+    EnvFilter::try_new(&directives).unwrap_or_else(|e| {
+        eprintln!("Invalid log filter '{}': {} - using default", directives, e);
+        EnvFilter::new(DEFAULT_FILTER)
+    })
+}
+
+/// Lets a hot path (per token, per audio packet, per window event) log one call out of `every`
+pub struct LogSampler {
+    every: u64,
+    seen: AtomicU64,
+}
+
+impl LogSampler {
+    pub const fn new(every: u64) -> Self {
+        Self {
+            every: if every == 0 { 1 } else { every },
+            seen: AtomicU64::new(0),
+        }
+    }
+
+    /// Count a call; returns the running count for the first call and every `every`th one after
+    pub fn sample(&self) -> Option<u64> {
+        let seen = self.seen.fetch_add(1, Ordering::Relaxed);
+        seen.is_multiple_of(self.every).then_some(seen + 1)
+    }
+}
+
+/// Initialize tracing: rotating file output always, console output in debug builds only
+pub fn init() {
+    let dir = log_dir();
+    let file_layer = match RollingFileWriter::new(&dir) {
+        Ok(writer) => {
+            let (non_blocking, guard) = tracing_appender::non_blocking(writer);
+            let _ = FILE_GUARD.set(guard);
+            Some(
+                fmt::layer()
+                    .with_writer(non_blocking)
+                    .with_ansi(false)
+                    .with_target(true)
+                    .with_thread_ids(true),
+            )
+        }
+        Err(e) => {
+            eprintln!("Failed to open log directory {}: {}", dir.display(), e);
+            None
+        }
+    };
+
+    let console_layer = if cfg!(debug_assertions) {
+        Some(fmt::layer().with_target(true).with_writer(io::stdout))
+    } else {
+        None
+    };
+
+    // Installing the registry also installs the log -> tracing bridge for existing log macros
+    let result = tracing_subscriber::registry()
+        .with(file_layer.with_filter(build_filter()))
+        .with(console_layer.with_filter(build_filter()))
+        .with(RingBufferLayer.with_filter(build_filter()))
+        .try_init();
+
+    if let Err(e) = result {
+        eprintln!("Logging already initialized: {}", e);
+        return;
+    }
+
+    tracing::info!(log_dir = %dir.display(), "Logging initialized");
+}
+
+/// Start streaming captured log entries to the frontend as `log-event`
+pub fn attach_app_handle(app_handle: AppHandle) {
+    let _ = LOG_EVENT_HANDLE.set(app_handle);
+}
+
+/// The most recent `lines` entries, oldest first
+pub fn recent_entries(lines: usize) -> Vec<LogEntry> {
+    let buffer = LOG_BUFFER.lock();
+    let skip = buffer.len().saturating_sub(lines);
+    buffer.iter().skip(skip).cloned().collect()
+}
+
+/// Collects an event's message and structured fields into one line
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: Vec<String>,
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else if !field.name().starts_with("log.") {
+            self.fields.push(format!("{}={}", field.name(), value));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        } else if !field.name().starts_with("log.") {
+            self.fields.push(format!("{}={:?}", field.name(), value));
+        }
+    }
+}
+
+/// tracing layer that feeds the in-memory ring buffer and the `log-event` stream
+struct RingBufferLayer;
+
+impl<S: Subscriber> Layer<S> for RingBufferLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if IN_LOG_EMIT.with(|flag| flag.get()) {
+            return;
+        }
+
+        // Events bridged from the `log` crate carry their real target in normalized metadata
+        let normalized = event.normalized_metadata();
+        let metadata = normalized.as_ref().unwrap_or_else(|| event.metadata());
+
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let mut message = visitor.message;
+        if !visitor.fields.is_empty() {
+            if !message.is_empty() {
+                message.push(' ');
+            }
+            message.push_str(&visitor.fields.join(" "));
+        }
+
+        let entry = LogEntry {
+            timestamp: Local::now().to_rfc3339(),
+            level: metadata.level().to_string(),
+            target: metadata.target().to_string(),
+            message,
+        };
+
+        {
+            let mut buffer = LOG_BUFFER.lock();
+            if buffer.len() >= LOG_BUFFER_CAPACITY {
+                buffer.pop_front();
+            }
+            buffer.push_back(entry.clone());
+        }
+
+        if let Some(app_handle) = LOG_EVENT_HANDLE.get() {
+            IN_LOG_EMIT.with(|flag| flag.set(true));
+            let _ = app_handle.emit(events::LOG_EVENT, &entry);
+            IN_LOG_EMIT.with(|flag| flag.set(false));
+        }
+    }
+}
+
+/// Return the last `lines` log entries, optionally only those at or above `level_filter`
+#[tauri::command]
+#[specta::specta]
+pub async fn tail_logs(lines: Option<usize>, level_filter: Option<String>) -> Response<Vec<LogEntry>> {
+    envelope::respond("tail_logs", async move {
+        let lines = lines.unwrap_or(200);
+
+        let min_level = match level_filter.as_deref() {
+            Some(level) if !level.is_empty() => Some(
+                Level::from_str(level).map_err(|_| format!("Invalid level filter: {}", level))?,
+            ),
+            _ => None,
+        };
+
+        let buffer = LOG_BUFFER.lock();
+        let mut entries: Vec<LogEntry> = buffer
+            .iter()
+            .rev()
+            .filter(|entry| match min_level {
+                // tracing orders levels by verbosity, so "at least as severe" means <=
+                Some(min) => Level::from_str(&entry.level).map(|l| l <= min).unwrap_or(false),
+                None => true,
+            })
+            .take(lines)
+            .cloned()
+            .collect();
+        entries.reverse();
+
+        Ok(entries)
+    })
+    .await
+}
+
+/// Log file writer that rotates by day and by size
+struct RollingFileWriter {
+    dir: PathBuf,
+    file: File,
+    date: NaiveDate,
+    size: u64,
+}
+
+impl RollingFileWriter {
+    fn new(dir: &Path) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(LOG_FILE_NAME);
+
+        // A file left over from a previous day is rotated before we append to it
+        if let Ok(meta) = fs::metadata(&path) {
+            let modified_date = meta
+                .modified()
+                .ok()
+                .map(|t| chrono::DateTime::<Local>::from(t).date_naive());
+            if modified_date.is_some_and(|d| d != Local::now().date_naive()) {
+                Self::archive(dir, modified_date.unwrap())?;
+            }
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            file,
+            date: Local::now().date_naive(),
+            size,
+        })
+    }
+
+    /// Move the active file to mockmate.YYYY-MM-DD.N.log and prune old files
+    fn archive(dir: &Path, date: NaiveDate) -> io::Result<()> {
+        let active = dir.join(LOG_FILE_NAME);
+        let stamp = date.format("%Y-%m-%d");
+        let mut seq = 0;
+        let target = loop {
+            let candidate = dir.join(format!("mockmate.{}.{}.log", stamp, seq));
+            if !candidate.exists() {
+                break candidate;
+            }
+            seq += 1;
+        };
+        fs::rename(&active, &target)?;
+        Self::prune(dir);
+        Ok(())
+    }
+
+    fn prune(dir: &Path) {
+        let mut rotated = rotated_log_files(dir);
+        // Newest first, drop anything past the retention count
+        rotated.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+        for (path, _) in rotated.into_iter().skip(MAX_ROTATED_FILES) {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        Self::archive(&self.dir, self.date)?;
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.dir.join(LOG_FILE_NAME))?;
+        self.date = Local::now().date_naive();
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RollingFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let today = Local::now().date_naive();
+        let over_size = self.size > 0 && self.size + buf.len() as u64 > MAX_LOG_FILE_BYTES;
+        if today != self.date || over_size {
+            if let Err(e) = self.rotate() {
+                eprintln!("Log rotation failed: {}", e);
+            }
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Rotated log files in `dir` with their modification times
+pub fn rotated_log_files(dir: &Path) -> Vec<(PathBuf, std::time::SystemTime)> {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| {
+                    let name = e.file_name().to_string_lossy().to_string();
+                    name.starts_with("mockmate.") && name.ends_with(".log") && name != LOG_FILE_NAME
+                })
+                .filter_map(|e| {
+                    let modified = e.metadata().and_then(|m| m.modified()).ok()?;
+                    Some((e.path(), modified))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
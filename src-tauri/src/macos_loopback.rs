@@ -0,0 +1,349 @@
+// System audio loopback on macOS
+// macOS has no Stereo Mix or loopback endpoint of its own. The supported route is a virtual
+// loopback driver such as BlackHole: the user plays sound through a Multi-Output Device that
+// includes it, and MockMate records the driver's input side through an AudioQueue, which converts
+// whatever the device delivers to mono 44.1kHz f32. Recording any input device needs microphone
+// permission, which is checked through `permissions` before the queue starts.
+
+use std::ffi::{c_void, CStr, CString};
+use std::os::raw::c_char;
+
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+
+/// Names of virtual loopback drivers, matched case-insensitively against device names
+const LOOPBACK_DRIVERS: [&str; 4] = ["blackhole", "loopback audio", "soundflower", "vb-cable"];
+
+pub const SETUP_GUIDANCE: &str = "Install BlackHole (https://existential.audio/blackhole/), then in Audio MIDI Setup \
+     create a Multi-Output Device containing your speakers or headphones and BlackHole, and select it as the \
+     sound output";
+
+const SAMPLE_RATE: f64 = 44100.0;
+/// Three buffers of ~23ms each keep the queue fed without adding noticeable latency
+const BUFFER_COUNT: usize = 3;
+const BUFFER_BYTES: u32 = 1024 * 4;
+
+type OSStatus = i32;
+type AudioObjectID = u32;
+type CFStringRef = *const c_void;
+type AudioQueueRef = *mut c_void;
+
+const fn four_cc(code: &[u8; 4]) -> u32 {
+    u32::from_be_bytes(*code)
+}
+
+const SYSTEM_OBJECT: AudioObjectID = 1;
+const PROPERTY_DEVICES: u32 = four_cc(b"dev#");
+const PROPERTY_DEFAULT_OUTPUT: u32 = four_cc(b"dOut");
+const PROPERTY_NAME: u32 = four_cc(b"lnam");
+const PROPERTY_DEVICE_UID: u32 = four_cc(b"uid ");
+const PROPERTY_STREAMS: u32 = four_cc(b"stm#");
+const SCOPE_GLOBAL: u32 = four_cc(b"glob");
+const SCOPE_INPUT: u32 = four_cc(b"inpt");
+const ELEMENT_MAIN: u32 = 0;
+const FORMAT_LINEAR_PCM: u32 = four_cc(b"lpcm");
+const FORMAT_FLAG_FLOAT: u32 = 1;
+const FORMAT_FLAG_PACKED: u32 = 8;
+const QUEUE_PROPERTY_CURRENT_DEVICE: u32 = four_cc(b"aqcd");
+const CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+
+#[repr(C)]
+struct AudioObjectPropertyAddress {
+    selector: u32,
+    scope: u32,
+    element: u32,
+}
+
+#[repr(C)]
+struct AudioStreamBasicDescription {
+    sample_rate: f64,
+    format_id: u32,
+    format_flags: u32,
+    bytes_per_packet: u32,
+    frames_per_packet: u32,
+    bytes_per_frame: u32,
+    channels_per_frame: u32,
+    bits_per_channel: u32,
+    reserved: u32,
+}
+
+#[repr(C)]
+struct AudioQueueBuffer {
+    audio_data_bytes_capacity: u32,
+    audio_data: *mut c_void,
+    audio_data_byte_size: u32,
+    user_data: *mut c_void,
+    packet_description_capacity: u32,
+    packet_descriptions: *mut c_void,
+    packet_description_count: u32,
+}
+
+type AudioQueueInputCallback =
+    extern "C" fn(*mut c_void, AudioQueueRef, *mut AudioQueueBuffer, *const c_void, u32, *const c_void);
+
+#[link(name = "CoreAudio", kind = "framework")]
+extern "C" {
+    fn AudioObjectGetPropertyDataSize(
+        object: AudioObjectID,
+        address: *const AudioObjectPropertyAddress,
+        qualifier_size: u32,
+        qualifier: *const c_void,
+        size: *mut u32,
+    ) -> OSStatus;
+    fn AudioObjectGetPropertyData(
+        object: AudioObjectID,
+        address: *const AudioObjectPropertyAddress,
+        qualifier_size: u32,
+        qualifier: *const c_void,
+        size: *mut u32,
+        data: *mut c_void,
+    ) -> OSStatus;
+}
+
+#[link(name = "AudioToolbox", kind = "framework")]
+extern "C" {
+    fn AudioQueueNewInput(
+        format: *const AudioStreamBasicDescription,
+        callback: AudioQueueInputCallback,
+        user_data: *mut c_void,
+        run_loop: *const c_void,
+        run_loop_mode: CFStringRef,
+        flags: u32,
+        queue: *mut AudioQueueRef,
+    ) -> OSStatus;
+    fn AudioQueueSetProperty(queue: AudioQueueRef, property: u32, data: *const c_void, size: u32) -> OSStatus;
+    fn AudioQueueAllocateBuffer(queue: AudioQueueRef, bytes: u32, buffer: *mut *mut AudioQueueBuffer) -> OSStatus;
+    fn AudioQueueEnqueueBuffer(
+        queue: AudioQueueRef,
+        buffer: *mut AudioQueueBuffer,
+        packet_descriptions: u32,
+        descriptions: *const c_void,
+    ) -> OSStatus;
+    fn AudioQueueStart(queue: AudioQueueRef, start_time: *const c_void) -> OSStatus;
+    fn AudioQueueStop(queue: AudioQueueRef, immediate: u8) -> OSStatus;
+    fn AudioQueueDispose(queue: AudioQueueRef, immediate: u8) -> OSStatus;
+}
+
+#[link(name = "CoreFoundation", kind = "framework")]
+extern "C" {
+    fn CFStringGetCString(string: CFStringRef, buffer: *mut c_char, size: isize, encoding: u32) -> u8;
+    fn CFStringCreateWithCString(allocator: *const c_void, string: *const c_char, encoding: u32) -> CFStringRef;
+    fn CFRelease(object: *const c_void);
+}
+
+fn check(status: OSStatus, what: &str) -> Result<()> {
+    if status == 0 {
+        Ok(())
+    } else {
+        Err(anyhow!("{} failed (OSStatus {})", what, status))
+    }
+}
+
+fn address(selector: u32, scope: u32) -> AudioObjectPropertyAddress {
+    AudioObjectPropertyAddress {
+        selector,
+        scope,
+        element: ELEMENT_MAIN,
+    }
+}
+
+/// Read a CFString property and release it
+fn string_property(object: AudioObjectID, selector: u32) -> Option<String> {
+    let mut value: CFStringRef = std::ptr::null();
+    let mut size = std::mem::size_of::<CFStringRef>() as u32;
+    let status = unsafe {
+        AudioObjectGetPropertyData(
+            object,
+            &address(selector, SCOPE_GLOBAL),
+            0,
+            std::ptr::null(),
+            &mut size,
+            &mut value as *mut CFStringRef as *mut c_void,
+        )
+    };
+    if status != 0 || value.is_null() {
+        return None;
+    }
+    let mut buffer = [0 as c_char; 512];
+    let copied = unsafe { CFStringGetCString(value, buffer.as_mut_ptr(), buffer.len() as isize, CF_STRING_ENCODING_UTF8) };
+    unsafe { CFRelease(value) };
+    (copied != 0).then(|| unsafe { CStr::from_ptr(buffer.as_ptr()) }.to_string_lossy().into_owned())
+}
+
+fn has_input_streams(device: AudioObjectID) -> bool {
+    let mut size = 0u32;
+    let status =
+        unsafe { AudioObjectGetPropertyDataSize(device, &address(PROPERTY_STREAMS, SCOPE_INPUT), 0, std::ptr::null(), &mut size) };
+    status == 0 && size > 0
+}
+
+fn device_ids() -> Result<Vec<AudioObjectID>> {
+    let devices = address(PROPERTY_DEVICES, SCOPE_GLOBAL);
+    let mut size = 0u32;
+    check(
+        unsafe { AudioObjectGetPropertyDataSize(SYSTEM_OBJECT, &devices, 0, std::ptr::null(), &mut size) },
+        "Listing audio devices",
+    )?;
+    let mut ids = vec![0 as AudioObjectID; size as usize / std::mem::size_of::<AudioObjectID>()];
+    check(
+        unsafe {
+            AudioObjectGetPropertyData(SYSTEM_OBJECT, &devices, 0, std::ptr::null(), &mut size, ids.as_mut_ptr() as *mut c_void)
+        },
+        "Listing audio devices",
+    )?;
+    ids.truncate(size as usize / std::mem::size_of::<AudioObjectID>());
+    Ok(ids)
+}
+
+fn default_output_name() -> Option<String> {
+    let mut device: AudioObjectID = 0;
+    let mut size = std::mem::size_of::<AudioObjectID>() as u32;
+    let status = unsafe {
+        AudioObjectGetPropertyData(
+            SYSTEM_OBJECT,
+            &address(PROPERTY_DEFAULT_OUTPUT, SCOPE_GLOBAL),
+            0,
+            std::ptr::null(),
+            &mut size,
+            &mut device as *mut AudioObjectID as *mut c_void,
+        )
+    };
+    (status == 0).then(|| string_property(device, PROPERTY_NAME)).flatten()
+}
+
+#[derive(Debug, Clone)]
+pub struct LoopbackDevice {
+    pub name: String,
+    pub uid: String,
+}
+
+/// The first installed loopback driver that exposes an input side
+pub fn find_loopback_device() -> Result<Option<LoopbackDevice>> {
+    for id in device_ids()? {
+        if !has_input_streams(id) {
+            continue;
+        }
+        let Some(name) = string_property(id, PROPERTY_NAME) else { continue };
+        let lower = name.to_lowercase();
+        if !LOOPBACK_DRIVERS.iter().any(|driver| lower.contains(driver)) {
+            continue;
+        }
+        if let Some(uid) = string_property(id, PROPERTY_DEVICE_UID) {
+            return Ok(Some(LoopbackDevice { name, uid }));
+        }
+    }
+    Ok(None)
+}
+
+type SampleSink = Box<dyn FnMut(&[f32]) + Send>;
+
+struct CallbackState {
+    on_samples: SampleSink,
+    samples: Vec<f32>,
+}
+
+extern "C" fn input_callback(
+    user_data: *mut c_void,
+    queue: AudioQueueRef,
+    buffer: *mut AudioQueueBuffer,
+    _start_time: *const c_void,
+    _packet_count: u32,
+    _packet_descriptions: *const c_void,
+) {
+    // SAFETY: user_data is the CallbackState owned by LoopbackCapture, which outlives the queue, and
+    // the queue only hands back buffers it allocated
+    let state = unsafe { &mut *(user_data as *mut CallbackState) };
+    let buffer_ref = unsafe { &*buffer };
+    if buffer_ref.audio_data_byte_size > 0 {
+        let bytes = unsafe {
+            std::slice::from_raw_parts(buffer_ref.audio_data as *const u8, buffer_ref.audio_data_byte_size as usize)
+        };
+        state.samples.clear();
+        crate::audio_buffer::extend_f32_from_le_bytes(bytes, &mut state.samples);
+        (state.on_samples)(&state.samples);
+    }
+    // Fails harmlessly once the queue is stopping
+    unsafe { AudioQueueEnqueueBuffer(queue, buffer, 0, std::ptr::null()) };
+}
+
+/// A running AudioQueue on the loopback device; stops and disposes the queue on drop
+pub struct LoopbackCapture {
+    queue: AudioQueueRef,
+    state: *mut CallbackState,
+}
+
+impl LoopbackCapture {
+    /// Start recording `device`; `on_samples` runs on the AudioQueue's own thread
+    pub fn start(device: &LoopbackDevice, on_samples: impl FnMut(&[f32]) + Send + 'static) -> Result<Self> {
+        let format = AudioStreamBasicDescription {
+            sample_rate: SAMPLE_RATE,
+            format_id: FORMAT_LINEAR_PCM,
+            format_flags: FORMAT_FLAG_FLOAT | FORMAT_FLAG_PACKED,
+            bytes_per_packet: 4,
+            frames_per_packet: 1,
+            bytes_per_frame: 4,
+            channels_per_frame: 1,
+            bits_per_channel: 32,
+            reserved: 0,
+        };
+        let state = Box::into_raw(Box::new(CallbackState {
+            on_samples: Box::new(on_samples),
+            samples: Vec::with_capacity(BUFFER_BYTES as usize / 4),
+        }));
+        let mut queue: AudioQueueRef = std::ptr::null_mut();
+        let created = unsafe {
+            AudioQueueNewInput(&format, input_callback, state as *mut c_void, std::ptr::null(), std::ptr::null(), 0, &mut queue)
+        };
+        if let Err(e) = check(created, "Creating the audio queue") {
+            drop(unsafe { Box::from_raw(state) });
+            return Err(e);
+        }
+        // From here Drop disposes the queue and frees the state
+        let capture = Self { queue, state };
+
+        let uid = CString::new(device.uid.as_str())?;
+        let uid = unsafe { CFStringCreateWithCString(std::ptr::null(), uid.as_ptr(), CF_STRING_ENCODING_UTF8) };
+        if uid.is_null() {
+            return Err(anyhow!("Invalid device UID for {}", device.name));
+        }
+        let selected = unsafe {
+            AudioQueueSetProperty(
+                queue,
+                QUEUE_PROPERTY_CURRENT_DEVICE,
+                &uid as *const CFStringRef as *const c_void,
+                std::mem::size_of::<CFStringRef>() as u32,
+            )
+        };
+        unsafe { CFRelease(uid) };
+        check(selected, "Selecting the loopback device")?;
+
+        for _ in 0..BUFFER_COUNT {
+            let mut buffer: *mut AudioQueueBuffer = std::ptr::null_mut();
+            check(unsafe { AudioQueueAllocateBuffer(queue, BUFFER_BYTES, &mut buffer) }, "Allocating audio buffers")?;
+            check(unsafe { AudioQueueEnqueueBuffer(queue, buffer, 0, std::ptr::null()) }, "Queueing audio buffers")?;
+        }
+        check(unsafe { AudioQueueStart(queue, std::ptr::null()) }, "Starting the audio queue")?;
+
+        info!("🍎 Recording system audio from {}", device.name);
+        match default_output_name() {
+            Some(output) if !output.to_lowercase().contains("multi-output") && !output.eq_ignore_ascii_case(&device.name) => {
+                warn!(
+                    "⚠️ Sound output is '{}', so nothing reaches {}. Select a Multi-Output Device that includes it.",
+                    output, device.name
+                );
+            }
+            _ => {}
+        }
+        Ok(capture)
+    }
+}
+
+impl Drop for LoopbackCapture {
+    fn drop(&mut self) {
+        unsafe {
+            AudioQueueStop(self.queue, 1);
+            AudioQueueDispose(self.queue, 1);
+            drop(Box::from_raw(self.state));
+        }
+    }
+}
@@ -2,9 +2,20 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
-    // Initialize logging with info level output
-    std::env::set_var("RUST_LOG", "info");
-    env_logger::init();
+    mockmate_lib::startup::mark_process_start();
+    
+    // Initialize structured logging (rotating file in %APPDATA%\MockMate\logs, console in debug builds)
+    mockmate_lib::logging::init();
+    mockmate_lib::crash_reporter::install_panic_hook();
+    mockmate_lib::portable::prepare();
+    mockmate_lib::managed_policy::apply();
+    
+    // CLI mode: capture and transcribe without creating any windows
+    let args: Vec<String> = std::env::args().collect();
+    if mockmate_lib::headless::requested(&args) {
+        mockmate_lib::headless::attach_console();
+        std::process::exit(mockmate_lib::headless::run(&args));
+    }
     
     println!("=== Starting MockMate Application ===");
     if let Err(e) = mockmate_lib::run() {
@@ -0,0 +1,279 @@
+// Administrator-managed policy
+// Companies rolling MockMate out to their staff need some settings fixed regardless of what a user
+// picks: local-only mode forced on, clipboard monitoring off, the backend pinned to their own
+// deployment. Policy is read once at startup from, in order of precedence:
+//   - Windows: HKLM\Software\Policies\MockMate, then HKCU\Software\Policies\MockMate (Group Policy)
+//   - macOS: /Library/Managed Preferences[/<user>]/com.mockmate.app.plist (MDM configuration profiles)
+//   - a managed JSON file: %ProgramData%\MockMate\policy.json, /Library/Application Support/MockMate/
+//     policy.json or /etc/mockmate/policy.json
+// The first source that sets a value wins. A value set by policy overrides the user's setting and
+// cannot be changed in the app; unset values fall back to the user's setting, then the default.
+// `get_effective_policy` reports each effective value and where it came from.
+
+use std::path::PathBuf;
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::envelope::{self, Response};
+
+const DEFAULT_BACKEND_URL: &str = "https://mockmate-backend.onrender.com";
+
+static POLICY: Lazy<LoadedPolicy> = Lazy::new(load);
+
+/// Values one source sets; registry values and plist keys use the CamelCase names
+#[derive(Debug, Clone, Default, Deserialize)]
+struct PolicyValues {
+    #[serde(default, alias = "ForceLocalOnly")]
+    force_local_only: Option<bool>,
+    #[serde(default, alias = "DisableClipboardMonitoring")]
+    disable_clipboard_monitoring: Option<bool>,
+    #[serde(default, alias = "BackendUrl")]
+    backend_url: Option<String>,
+}
+
+impl PolicyValues {
+    fn is_empty(&self) -> bool {
+        self.force_local_only.is_none() && self.disable_clipboard_monitoring.is_none() && self.backend_url.is_none()
+    }
+
+    /// Take the values `self` does not set yet from a lower-precedence source
+    fn fill_from(&mut self, other: PolicyValues) {
+        self.force_local_only = self.force_local_only.or(other.force_local_only);
+        self.disable_clipboard_monitoring = self.disable_clipboard_monitoring.or(other.disable_clipboard_monitoring);
+        self.backend_url = self.backend_url.take().or(other.backend_url);
+    }
+}
+
+#[derive(Debug, Default)]
+struct LoadedPolicy {
+    values: PolicyValues,
+    sources: Vec<String>,
+    problems: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum SettingSource {
+    Policy,
+    User,
+    Default,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct EffectiveSetting<T> {
+    pub value: T,
+    pub source: SettingSource,
+    /// Set by policy, so the app refuses to change it
+    pub locked: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct EffectivePolicy {
+    pub local_only: EffectiveSetting<bool>,
+    pub clipboard_monitoring: EffectiveSetting<bool>,
+    pub backend_url: EffectiveSetting<String>,
+    /// Policy locations that set at least one value, highest precedence first
+    pub sources: Vec<String>,
+    /// Policy that was found but could not be read; it is ignored
+    pub problems: Vec<String>,
+}
+
+#[cfg(windows)]
+mod os {
+    use std::process::Command;
+
+    use super::PolicyValues;
+
+    pub const KEYS: [&str; 2] = ["HKLM\\Software\\Policies\\MockMate", "HKCU\\Software\\Policies\\MockMate"];
+
+    fn flag(data: &str) -> Option<bool> {
+        match data.strip_prefix("0x") {
+            Some(hex) => u32::from_str_radix(hex, 16).ok().map(|value| value != 0),
+            None => match data.to_ascii_lowercase().as_str() {
+                "1" | "true" => Some(true),
+                "0" | "false" => Some(false),
+                _ => None,
+            },
+        }
+    }
+
+    /// Values under `key`; None when the key does not exist
+    pub fn read(key: &str) -> Option<PolicyValues> {
+        let output = Command::new("reg").args(["query", key]).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let text = String::from_utf8_lossy(&output.stdout);
+        let mut values = PolicyValues::default();
+        // Lines look like "    ForceLocalOnly    REG_DWORD    0x1"
+        for line in text.lines() {
+            let mut fields = line.trim().splitn(3, "    ");
+            let (Some(name), Some(_kind), Some(data)) = (fields.next(), fields.next(), fields.next()) else {
+                continue;
+            };
+            let data = data.trim();
+            match name {
+                "ForceLocalOnly" => values.force_local_only = flag(data),
+                "DisableClipboardMonitoring" => values.disable_clipboard_monitoring = flag(data),
+                "BackendUrl" => values.backend_url = Some(data.to_string()),
+                _ => {}
+            }
+        }
+        Some(values)
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod os {
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    use super::PolicyValues;
+
+    const DOMAIN: &str = "com.mockmate.app.plist";
+
+    /// Machine-wide profile first, then the one pushed for the signed-in user
+    pub fn plists() -> Vec<PathBuf> {
+        let root = PathBuf::from("/Library/Managed Preferences");
+        let mut paths = vec![root.join(DOMAIN)];
+        if let Ok(user) = std::env::var("USER") {
+            paths.push(root.join(user).join(DOMAIN));
+        }
+        paths
+    }
+
+    pub fn read(path: &Path) -> Result<PolicyValues, String> {
+        let output = Command::new("plutil")
+            .args(["-convert", "json", "-o", "-"])
+            .arg(path)
+            .output()
+            .map_err(|e| format!("failed to run plutil: {}", e))?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+        serde_json::from_slice(&output.stdout).map_err(|e| e.to_string())
+    }
+}
+
+fn policy_file() -> PathBuf {
+    if cfg!(windows) {
+        std::env::var("ProgramData")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from("C:\\ProgramData"))
+            .join("MockMate")
+            .join("policy.json")
+    } else if cfg!(target_os = "macos") {
+        PathBuf::from("/Library/Application Support/MockMate/policy.json")
+    } else {
+        PathBuf::from("/etc/mockmate/policy.json")
+    }
+}
+
+/// Drop a backend URL that is not an http(s) URL rather than pointing the app at nothing
+fn validate(values: &mut PolicyValues, source: &str, problems: &mut Vec<String>) {
+    if let Some(url) = values.backend_url.take() {
+        let url = url.trim().trim_end_matches('/').to_string();
+        match reqwest::Url::parse(&url) {
+            Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => values.backend_url = Some(url),
+            _ => problems.push(format!("{}: BackendUrl \"{}\" is not an http(s) URL", source, url)),
+        }
+    }
+}
+
+fn load() -> LoadedPolicy {
+    let mut found: Vec<(String, Result<PolicyValues, String>)> = Vec::new();
+
+    #[cfg(windows)]
+    for key in os::KEYS {
+        if let Some(values) = os::read(key) {
+            found.push((key.to_string(), Ok(values)));
+        }
+    }
+    #[cfg(target_os = "macos")]
+    for path in os::plists() {
+        if path.exists() {
+            found.push((path.display().to_string(), os::read(&path)));
+        }
+    }
+    let file = policy_file();
+    if let Ok(body) = std::fs::read(&file) {
+        found.push((file.display().to_string(), serde_json::from_slice(&body).map_err(|e| e.to_string())));
+    }
+
+    let mut policy = LoadedPolicy::default();
+    for (source, values) in found {
+        match values {
+            Ok(mut values) => {
+                validate(&mut values, &source, &mut policy.problems);
+                if !values.is_empty() {
+                    policy.sources.push(source);
+                    policy.values.fill_from(values);
+                }
+            }
+            Err(e) => policy.problems.push(format!("{}: {}", source, e)),
+        }
+    }
+    for problem in &policy.problems {
+        warn!("🏢 Ignoring managed policy: {}", problem);
+    }
+    if !policy.sources.is_empty() {
+        info!("🏢 Managed policy from {}: {:?}", policy.sources.join(", "), policy.values);
+    }
+    policy
+}
+
+/// Read the policy and pin the backend URL when it sets one; call at startup before anything reads
+/// the backend URL from the environment
+pub fn apply() {
+    if let Some(url) = POLICY.values.backend_url.as_deref() {
+        std::env::set_var("MOCKMATE_BACKEND_URL", url);
+        std::env::set_var("BACKEND_URL", url);
+    }
+}
+
+/// Whether policy forces local-only mode on
+pub fn forces_local_only() -> bool {
+    POLICY.values.force_local_only == Some(true)
+}
+
+/// Whether the accessibility reader may fall back to watching the clipboard
+pub fn clipboard_monitoring_allowed() -> bool {
+    POLICY.values.disable_clipboard_monitoring != Some(true)
+}
+
+fn setting<T>(policy: Option<T>, user: Option<T>, default: T) -> EffectiveSetting<T> {
+    match (policy, user) {
+        (Some(value), _) => EffectiveSetting { value, source: SettingSource::Policy, locked: true },
+        (None, Some(value)) => EffectiveSetting { value, source: SettingSource::User, locked: false },
+        (None, None) => EffectiveSetting { value: default, source: SettingSource::Default, locked: false },
+    }
+}
+
+pub fn effective() -> EffectivePolicy {
+    let values = &POLICY.values;
+    let user_local_only = crate::privacy::user_local_only();
+    // `apply` overwrote the environment when policy pins the URL, but policy is checked first then
+    let user_backend = std::env::var("MOCKMATE_BACKEND_URL").ok().filter(|url| !url.is_empty());
+    EffectivePolicy {
+        // Policy can only force these on; a 0 / false policy value leaves the setting to the user
+        local_only: setting(forces_local_only().then_some(true), user_local_only.then_some(true), false),
+        clipboard_monitoring: setting((!clipboard_monitoring_allowed()).then_some(false), None, true),
+        backend_url: setting(values.backend_url.clone(), user_backend, DEFAULT_BACKEND_URL.to_string()),
+        sources: POLICY.sources.clone(),
+        problems: POLICY.problems.clone(),
+    }
+}
+
+/// Settings the app runs with, which of them an administrator has locked and where the policy came
+/// from
+#[tauri::command]
+#[specta::specta]
+pub async fn get_effective_policy() -> Response<EffectivePolicy> {
+    envelope::respond("get_effective_policy", async move {
+        Ok(effective())
+    })
+    .await
+}
@@ -0,0 +1,233 @@
+// Markdown vault export for Obsidian/Logseq
+// Writes one note per session under <vault>/MockMate/Sessions with YAML frontmatter, and one note per
+// question under <vault>/MockMate/Questions. Question notes are shared across sessions: re-asking a
+// question in a later session appends a section linking back to that session, so the vault graph
+// shows every time a question came up. Exporting the same session again is idempotent.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use log::info;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::State;
+
+use crate::database::models::{InterviewAnswer, InterviewQuestion};
+use crate::rubric::AnswerEvaluation;
+use crate::session_transcript::TranscriptLine;
+use crate::AppState;
+use crate::cancellation;
+use crate::envelope::{IntoResult, Response};
+
+/// Transcript lines quoted per question in question notes / in the session note
+const QUESTION_EXCERPT_LINES: usize = 20;
+const SESSION_EXCERPT_LINES: usize = 5;
+const MAX_NOTE_NAME_CHARS: usize = 80;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MarkdownExport {
+    pub session_note: String,
+    pub question_notes: Vec<String>,
+}
+
+/// Characters Obsidian and common filesystems reject in note names
+fn note_name(text: &str) -> String {
+    let cleaned: String = text
+        .chars()
+        .map(|c| if r#"\/:*?"<>|#^[]"#.contains(c) || c.is_control() { ' ' } else { c })
+        .collect();
+    let name = cleaned.split_whitespace().collect::<Vec<_>>().join(" ");
+    name.chars().take(MAX_NOTE_NAME_CHARS).collect::<String>().trim_end_matches('.').trim().to_string()
+}
+
+/// JSON strings are valid YAML double-quoted scalars
+fn yaml_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "\"\"".to_string())
+}
+
+fn quote(lines: &[&TranscriptLine]) -> String {
+    lines
+        .iter()
+        .map(|line| {
+            let speaker = line.speaker.as_deref().map(|s| format!("**{}:** ", s)).unwrap_or_default();
+            format!("> [{}] {}{}\n", line.timestamp.format("%H:%M:%S"), speaker, line.text)
+        })
+        .collect()
+}
+
+/// Transcript lines between the question being asked and the next one
+fn excerpt(transcript: &[TranscriptLine], from: DateTime<Utc>, until: Option<DateTime<Utc>>) -> Vec<&TranscriptLine> {
+    transcript
+        .iter()
+        .filter(|line| line.timestamp >= from && until.is_none_or(|until| line.timestamp < until))
+        .collect()
+}
+
+fn latest_answer<'a>(answers: &'a [InterviewAnswer], question: &InterviewQuestion) -> Option<&'a InterviewAnswer> {
+    answers.iter().filter(|a| a.question_id == question.id).max_by_key(|a| a.answered_at)
+}
+
+fn question_section(
+    session_note: &str,
+    answer: Option<&InterviewAnswer>,
+    evaluation: Option<&AnswerEvaluation>,
+    excerpt: &[&TranscriptLine],
+) -> String {
+    let mut section = format!("\n## [[{}]]\n\n", session_note);
+    match answer.and_then(|a| a.answer_text.as_deref()).filter(|t| !t.trim().is_empty()) {
+        Some(text) => section.push_str(&format!("**Answer:** {}\n\n", text.trim())),
+        None => section.push_str("_No answer recorded._\n\n"),
+    }
+    if let Some(score) = answer.and_then(|a| a.ai_score) {
+        section.push_str(&format!("**Score:** {}\n\n", score));
+    }
+    if let Some(feedback) = answer.and_then(|a| a.ai_feedback.as_deref()).filter(|f| !f.trim().is_empty()) {
+        section.push_str(&format!("**Feedback:** {}\n\n", feedback.trim()));
+    }
+    if let Some(evaluation) = evaluation {
+        section.push_str(&format!("**Rubric:** {:.1}/{}\n\n", evaluation.overall, evaluation.scale_max));
+        for score in &evaluation.scores {
+            section.push_str(&format!("- {}: {} — {}\n", score.criterion, score.score, score.comment));
+        }
+        if !evaluation.comment.is_empty() {
+            section.push_str(&format!("\n{}\n", evaluation.comment));
+        }
+        section.push('\n');
+    }
+    if !excerpt.is_empty() {
+        section.push_str(&quote(&excerpt[..excerpt.len().min(QUESTION_EXCERPT_LINES)]));
+    }
+    section
+}
+
+/// Create the question note or append this session's section to it
+fn write_question_note(path: &Path, question: &InterviewQuestion, session_note: &str, section: &str) -> Result<(), String> {
+    let existing = std::fs::read_to_string(path).ok();
+    let body = match existing {
+        Some(body) if body.contains(&format!("## [[{}]]", session_note)) => return Ok(()),
+        Some(mut body) => {
+            body.push_str(section);
+            body
+        }
+        None => format!(
+            "---\nquestion: {}\ncategory: {}\ndifficulty: {}\ntags: [mockmate/question]\n---\n\n# {}\n{}",
+            yaml_string(&question.question_text),
+            yaml_string(&question.category),
+            yaml_string(&question.difficulty_level),
+            question.question_text.trim(),
+            section
+        ),
+    };
+    std::fs::write(path, body).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Export a session as Markdown notes into an Obsidian/Logseq vault
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn export_session_markdown(
+    state: State<'_, AppState>,
+    session_id: String,
+    vault_path: String,
+    request_id: Option<String>,
+) -> Response<MarkdownExport> {
+    cancellation::run("export_session_markdown", request_id, async move {
+        let vault = PathBuf::from(vault_path.trim());
+        if !vault.is_dir() {
+            return Err(format!("Vault folder {} does not exist", vault.display()).into());
+        }
+        let report = crate::database::postgres::get_interview_report(session_id.clone()).await.into_result()?;
+        let transcript = crate::session_transcript::load(&session_id);
+        let session = &report.session;
+
+        // The company is only known from the interview context of the session it was entered for
+        let company = {
+            let context = state.interview_context.lock();
+            context
+                .position
+                .as_deref()
+                .filter(|position| position.trim().eq_ignore_ascii_case(session.job_title.trim()))
+                .and(context.company.clone())
+        };
+
+        let sessions_dir = vault.join("MockMate").join("Sessions");
+        let questions_dir = vault.join("MockMate").join("Questions");
+        std::fs::create_dir_all(&sessions_dir).map_err(|e| e.to_string())?;
+        std::fs::create_dir_all(&questions_dir).map_err(|e| e.to_string())?;
+
+        let date = session.session_started_at.unwrap_or(session.created_at);
+        let session_note = note_name(&format!(
+            "{} {}{} interview",
+            date.format("%Y-%m-%d"),
+            company.as_deref().map(|c| format!("{} ", c)).unwrap_or_default(),
+            session.job_title
+        ));
+
+        let mut questions: Vec<&InterviewQuestion> = report.questions.iter().collect();
+        questions.sort_by_key(|q| q.asked_at);
+
+        let mut body = format!(
+            "---\ncompany: {}\nrole: {}\ndate: {}\noutcome: {}\ndifficulty: {}\nsession_type: {}\nsession_id: {}\nquestions: {}\naverage_score: {:.1}\ntags: [mockmate/interview]\n---\n\n# {}\n\n",
+            company.as_deref().map(yaml_string).unwrap_or_default(),
+            yaml_string(&session.job_title),
+            date.format("%Y-%m-%d"),
+            yaml_string(&session.status),
+            yaml_string(&session.difficulty),
+            yaml_string(&session.session_type),
+            session.id,
+            report.total_questions,
+            report.average_score,
+            session_note
+        );
+        body.push_str(&format!(
+            "- **Duration:** {} min\n- **Answers:** {}\n- **Average response time:** {:.1}s\n\n## Questions\n\n",
+            session.interview_duration.unwrap_or(0),
+            report.total_answers,
+            report.average_response_time
+        ));
+
+        let mut question_notes = Vec::new();
+        let mut excerpts = String::new();
+        for (i, question) in questions.iter().enumerate() {
+            let name = note_name(&question.question_text);
+            if name.is_empty() {
+                continue;
+            }
+            let answer = latest_answer(&report.answers, question);
+            let evaluation = answer.and_then(|a| report.evaluations.iter().find(|e| e.answer_id == a.id.to_string()));
+            let lines = excerpt(&transcript, question.asked_at, questions.get(i + 1).map(|next| next.asked_at));
+
+            let score = answer.and_then(|a| a.ai_score).map(|s| format!(" — score {}", s)).unwrap_or_default();
+            let rubric = evaluation.map(|e| format!(" — rubric {:.1}/{}", e.overall, e.scale_max)).unwrap_or_default();
+            body.push_str(&format!("{}. [[{}]]{}{}\n", i + 1, name, score, rubric));
+            if !lines.is_empty() {
+                excerpts.push_str(&format!("\n### [[{}]]\n\n", name));
+                excerpts.push_str(&quote(&lines[..lines.len().min(SESSION_EXCERPT_LINES)]));
+            }
+
+            let path = questions_dir.join(format!("{}.md", name));
+            write_question_note(&path, question, &session_note, &question_section(&session_note, answer, evaluation, &lines))?;
+            question_notes.push(path.display().to_string());
+        }
+        if questions.is_empty() {
+            body.push_str("_No questions were recorded._\n");
+        }
+        if !excerpts.is_empty() {
+            body.push_str("\n## Transcript excerpts\n");
+            body.push_str(&excerpts);
+        }
+
+        let session_path = sessions_dir.join(format!("{}.md", session_note));
+        std::fs::write(&session_path, body).map_err(|e| format!("Failed to write {}: {}", session_path.display(), e))?;
+        info!(
+            "📝 Exported session {} to {} with {} question notes",
+            session_id,
+            session_path.display(),
+            question_notes.len()
+        );
+        Ok(MarkdownExport {
+            session_note: session_path.display().to_string(),
+            question_notes,
+        })
+    })
+    .await
+}
@@ -0,0 +1,457 @@
+// Automatic meeting detection
+// Notices when a Teams, Zoom or Google Meet call starts and ends, so transcription and the session
+// timer do not depend on me remembering to press start. Every few seconds the active audio sessions
+// are read per process (WASAPI session manager on Windows, PulseAudio stream list on Linux) and
+// combined with window heuristics: Teams and Zoom count as in a call while they hold the microphone
+// or play audio with a meeting window open, Meet while a browser plays or records audio with a Meet
+// tab in front. A call has to be seen twice to start and missing for a while to end, so a ringtone
+// or a dropped poll does not flap. `meeting-started` / `meeting-ended` carry whether the frontend
+// should auto-start system-audio transcription and the session. Off by default; settings are in
+// %APPDATA%\MockMate\meeting_detection.json.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter};
+
+use crate::envelope::{self, Response};
+use crate::events;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Consecutive polls a call must be seen in before it counts as started
+const START_POLLS: u32 = 2;
+/// Consecutive polls a call must be missing from before it counts as ended (~30s)
+const END_POLLS: u32 = 6;
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+static SETTINGS: Lazy<Mutex<MeetingDetectionSettings>> = Lazy::new(|| Mutex::new(load()));
+static TRACKED: Lazy<Mutex<HashMap<MeetingApp, Tracked>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum MeetingApp {
+    Teams,
+    Zoom,
+    GoogleMeet,
+}
+
+impl MeetingApp {
+    const ALL: [MeetingApp; 3] = [MeetingApp::Teams, MeetingApp::Zoom, MeetingApp::GoogleMeet];
+
+    /// Lower-case executable names without extension
+    fn processes(self) -> &'static [&'static str] {
+        match self {
+            MeetingApp::Teams => &["ms-teams", "teams", "teams-for-linux"],
+            MeetingApp::Zoom => &["zoom", "cpthost"],
+            MeetingApp::GoogleMeet => &["chrome", "msedge", "firefox", "brave", "opera", "vivaldi", "arc"],
+        }
+    }
+
+    /// Whether a window of one of the app's processes looks like a call
+    #[cfg(windows)]
+    fn is_meeting_window(self, title: &str, class: &str) -> bool {
+        let title = title.to_lowercase();
+        match self {
+            MeetingApp::Teams => {
+                title.contains("microsoft teams") && (title.contains("meeting") || title.contains("call"))
+            }
+            MeetingApp::Zoom => {
+                class == "ZPContentViewWndClass" || title.starts_with("zoom meeting") || title.starts_with("zoom webinar")
+            }
+            MeetingApp::GoogleMeet => title.starts_with("meet - ") || title.contains("google meet"),
+        }
+    }
+
+    /// Holding the microphone alone is enough; browsers also need a Meet window
+    fn capture_is_conclusive(self) -> bool {
+        matches!(self, MeetingApp::Teams | MeetingApp::Zoom)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct MeetingDetectionSettings {
+    enabled: bool,
+    auto_start_transcription: bool,
+    auto_start_session: bool,
+}
+
+/// What one poll saw for an app
+#[derive(Debug, Clone, Default)]
+struct Signals {
+    capturing: bool,
+    playing: bool,
+    meeting_window: Option<String>,
+}
+
+impl Signals {
+    fn in_call(&self, app: MeetingApp) -> bool {
+        (self.capturing && app.capture_is_conclusive())
+            || ((self.capturing || self.playing) && self.meeting_window.is_some())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Tracked {
+    seen: u32,
+    missing: u32,
+    started_at: Option<DateTime<Utc>>,
+    window_title: Option<String>,
+}
+
+/// Payload of `meeting-started` and `meeting-ended`
+#[derive(Debug, Clone, Serialize, JsonSchema, Type)]
+pub struct MeetingEvent {
+    pub app: MeetingApp,
+    pub started_at: DateTime<Utc>,
+    /// Set on `meeting-ended`
+    pub ended_at: Option<DateTime<Utc>>,
+    pub window_title: Option<String>,
+    /// The frontend should turn on system-audio transcription
+    pub auto_start_transcription: bool,
+    /// The frontend should start the connected session and its timer
+    pub auto_start_session: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ActiveMeeting {
+    pub app: MeetingApp,
+    pub started_at: DateTime<Utc>,
+    pub window_title: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct MeetingDetectionStatus {
+    pub enabled: bool,
+    pub auto_start_transcription: bool,
+    pub auto_start_session: bool,
+    /// False where call activity cannot be read (macOS)
+    pub supported: bool,
+    pub active: Vec<ActiveMeeting>,
+}
+
+fn settings_path() -> PathBuf {
+    crate::portable::data_root().join("MockMate").join("meeting_detection.json")
+}
+
+fn load() -> MeetingDetectionSettings {
+    std::fs::read(settings_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn save(settings: &MeetingDetectionSettings) -> Result<(), String> {
+    let path = settings_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(settings).map_err(|e| e.to_string())?;
+    std::fs::write(path, body).map_err(|e| e.to_string())
+}
+
+/// "C:\...\ms-teams.exe" or "ms-teams.exe" -> "ms-teams"
+fn process_stem(name: &str) -> String {
+    let file = name.rsplit(['\\', '/']).next().unwrap_or(name).to_lowercase();
+    file.strip_suffix(".exe").map(str::to_string).unwrap_or(file)
+}
+
+fn app_for_process(name: &str) -> Option<MeetingApp> {
+    let stem = process_stem(name);
+    MeetingApp::ALL.into_iter().find(|app| app.processes().contains(&stem.as_str()))
+}
+
+/// Executable name per process ID
+#[cfg(windows)]
+fn process_names() -> HashMap<u32, String> {
+    use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+    use winapi::um::tlhelp32::{CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS};
+
+    let mut names = HashMap::new();
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0);
+        if snapshot == INVALID_HANDLE_VALUE {
+            return names;
+        }
+        let mut entry: PROCESSENTRY32W = std::mem::zeroed();
+        entry.dwSize = std::mem::size_of::<PROCESSENTRY32W>() as u32;
+        let mut ok = Process32FirstW(snapshot, &mut entry);
+        while ok != 0 {
+            let len = entry.szExeFile.iter().position(|&c| c == 0).unwrap_or(entry.szExeFile.len());
+            names.insert(entry.th32ProcessID, String::from_utf16_lossy(&entry.szExeFile[..len]));
+            ok = Process32NextW(snapshot, &mut entry);
+        }
+        CloseHandle(snapshot);
+    }
+    names
+}
+
+/// Process IDs with an active audio session, per direction (capturing, playing)
+#[cfg(windows)]
+fn active_audio_processes() -> (Vec<u32>, Vec<u32>) {
+    use windows::core::Interface;
+    use windows::Win32::Media::Audio::{
+        eCapture, eRender, AudioSessionStateActive, EDataFlow, IAudioSessionControl2, IAudioSessionManager2,
+        IMMDeviceEnumerator, MMDeviceEnumerator, DEVICE_STATE_ACTIVE,
+    };
+    use windows::Win32::System::Com::{CoCreateInstance, CLSCTX_ALL};
+
+    let _ = wasapi::initialize_mta();
+    let collect = |flow: EDataFlow| -> windows::core::Result<Vec<u32>> {
+        let mut pids = Vec::new();
+        unsafe {
+            let enumerator: IMMDeviceEnumerator = CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)?;
+            let devices = enumerator.EnumAudioEndpoints(flow, DEVICE_STATE_ACTIVE)?;
+            for d in 0..devices.GetCount()? {
+                let manager: IAudioSessionManager2 = devices.Item(d)?.Activate(CLSCTX_ALL, None)?;
+                let sessions = manager.GetSessionEnumerator()?;
+                for s in 0..sessions.GetCount()? {
+                    let control = sessions.GetSession(s)?;
+                    if control.GetState()? != AudioSessionStateActive {
+                        continue;
+                    }
+                    if let Ok(pid) = control.cast::<IAudioSessionControl2>().and_then(|c| c.GetProcessId()) {
+                        pids.push(pid);
+                    }
+                }
+            }
+        }
+        Ok(pids)
+    };
+    let capturing = collect(eCapture).unwrap_or_else(|e| {
+        warn!("Meeting detection: failed to read capture sessions: {}", e);
+        Vec::new()
+    });
+    let playing = collect(eRender).unwrap_or_else(|e| {
+        warn!("Meeting detection: failed to read playback sessions: {}", e);
+        Vec::new()
+    });
+    (capturing, playing)
+}
+
+/// Visible top-level windows as (process ID, title, class)
+#[cfg(windows)]
+fn visible_windows() -> Vec<(u32, String, String)> {
+    use winapi::shared::minwindef::{BOOL, LPARAM, TRUE};
+    use winapi::shared::windef::HWND;
+    use winapi::um::winuser::{EnumWindows, GetClassNameW, GetWindowTextW, GetWindowThreadProcessId, IsWindowVisible};
+
+    unsafe extern "system" fn collect(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let windows = &mut *(lparam as *mut Vec<(u32, String, String)>);
+        if IsWindowVisible(hwnd) == 0 {
+            return TRUE;
+        }
+        let mut title = [0u16; 512];
+        let title_len = GetWindowTextW(hwnd, title.as_mut_ptr(), title.len() as i32);
+        let mut class = [0u16; 256];
+        let class_len = GetClassNameW(hwnd, class.as_mut_ptr(), class.len() as i32);
+        let mut pid = 0;
+        GetWindowThreadProcessId(hwnd, &mut pid);
+        windows.push((
+            pid,
+            String::from_utf16_lossy(&title[..title_len.max(0) as usize]),
+            String::from_utf16_lossy(&class[..class_len.max(0) as usize]),
+        ));
+        TRUE
+    }
+
+    let mut windows: Vec<(u32, String, String)> = Vec::new();
+    unsafe {
+        EnumWindows(Some(collect), &mut windows as *mut _ as LPARAM);
+    }
+    windows
+}
+
+#[cfg(windows)]
+fn poll_signals() -> HashMap<MeetingApp, Signals> {
+    let names = process_names();
+    let app_of = |pid: &u32| names.get(pid).and_then(|name| app_for_process(name));
+    let (capturing, playing) = active_audio_processes();
+
+    let mut signals: HashMap<MeetingApp, Signals> = HashMap::new();
+    for app in capturing.iter().filter_map(app_of) {
+        signals.entry(app).or_default().capturing = true;
+    }
+    for app in playing.iter().filter_map(app_of) {
+        signals.entry(app).or_default().playing = true;
+    }
+    for (pid, title, class) in visible_windows() {
+        let Some(app) = app_of(&pid) else { continue };
+        if app.is_meeting_window(&title, &class) {
+            signals.entry(app).or_default().meeting_window.get_or_insert(title);
+        }
+    }
+    signals
+}
+
+/// PulseAudio/PipeWire streams per application binary; no window heuristics, so Meet is not detected
+#[cfg(target_os = "linux")]
+fn poll_signals() -> HashMap<MeetingApp, Signals> {
+    let binaries = |kind: &str| -> Vec<String> {
+        std::process::Command::new("pactl")
+            .args(["list", kind])
+            .output()
+            .map(|output| {
+                String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .filter_map(|line| line.trim().strip_prefix("application.process.binary = "))
+                    .map(|value| value.trim_matches('"').to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let mut signals: HashMap<MeetingApp, Signals> = HashMap::new();
+    for app in binaries("source-outputs").iter().filter_map(|name| app_for_process(name)) {
+        signals.entry(app).or_default().capturing = true;
+    }
+    for app in binaries("sink-inputs").iter().filter_map(|name| app_for_process(name)) {
+        signals.entry(app).or_default().playing = true;
+    }
+    signals
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+fn poll_signals() -> HashMap<MeetingApp, Signals> {
+    HashMap::new()
+}
+
+/// Advance the per-app debouncers and emit the calls that started or ended
+fn apply(app_handle: &AppHandle, signals: HashMap<MeetingApp, Signals>) {
+    let settings = SETTINGS.lock().clone();
+    let mut tracked = TRACKED.lock();
+    for app in MeetingApp::ALL {
+        let signal = signals.get(&app).cloned().unwrap_or_default();
+        let state = tracked.entry(app).or_default();
+        let event = if signal.in_call(app) {
+            state.seen += 1;
+            state.missing = 0;
+            if signal.meeting_window.is_some() {
+                state.window_title = signal.meeting_window;
+            }
+            if state.started_at.is_some() || state.seen < START_POLLS {
+                continue;
+            }
+            let started_at = Utc::now();
+            state.started_at = Some(started_at);
+            info!("📞 {:?} call started", app);
+            (events::MEETING_STARTED, started_at, None)
+        } else {
+            state.seen = 0;
+            let Some(started_at) = state.started_at else { continue };
+            state.missing += 1;
+            if state.missing < END_POLLS {
+                continue;
+            }
+            state.started_at = None;
+            state.missing = 0;
+            info!("📞 {:?} call ended", app);
+            (events::MEETING_ENDED, started_at, Some(Utc::now()))
+        };
+
+        let (name, started_at, ended_at) = event;
+        let payload = MeetingEvent {
+            app,
+            started_at,
+            ended_at,
+            window_title: state.window_title.clone(),
+            auto_start_transcription: settings.auto_start_transcription,
+            auto_start_session: settings.auto_start_session,
+        };
+        if ended_at.is_some() {
+            state.window_title = None;
+        }
+        if let Err(e) = app_handle.emit(name, &payload) {
+            warn!("Failed to emit {}: {}", name, e);
+        }
+    }
+}
+
+pub fn init(app_handle: AppHandle) {
+    if APP_HANDLE.set(app_handle).is_err() {
+        return;
+    }
+    tauri::async_runtime::spawn(async {
+        let mut ticker = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            ticker.tick().await;
+            if !SETTINGS.lock().enabled {
+                continue;
+            }
+            let Ok(signals) = tauri::async_runtime::spawn_blocking(poll_signals).await else { continue };
+            if let Some(app_handle) = APP_HANDLE.get() {
+                apply(app_handle, signals);
+            }
+        }
+    });
+}
+
+fn status() -> MeetingDetectionStatus {
+    let settings = SETTINGS.lock().clone();
+    let mut active: Vec<ActiveMeeting> = TRACKED
+        .lock()
+        .iter()
+        .filter_map(|(app, state)| {
+            state.started_at.map(|started_at| ActiveMeeting {
+                app: *app,
+                started_at,
+                window_title: state.window_title.clone(),
+            })
+        })
+        .collect();
+    active.sort_by_key(|meeting| meeting.started_at);
+    MeetingDetectionStatus {
+        enabled: settings.enabled,
+        auto_start_transcription: settings.auto_start_transcription,
+        auto_start_session: settings.auto_start_session,
+        supported: cfg!(any(windows, target_os = "linux")),
+        active,
+    }
+}
+
+/// Meeting detection settings and the calls in progress
+#[tauri::command]
+#[specta::specta]
+pub fn get_meeting_detection() -> Response<MeetingDetectionStatus> {
+    envelope::respond_sync("get_meeting_detection", || Ok(status()))
+}
+
+/// Turn meeting detection on or off and choose what starts automatically with a call
+#[tauri::command]
+#[specta::specta]
+pub fn set_meeting_detection(
+    enabled: bool,
+    auto_start_transcription: Option<bool>,
+    auto_start_session: Option<bool>,
+) -> Response<MeetingDetectionStatus> {
+    envelope::respond_sync("set_meeting_detection", || {
+        {
+            let mut settings = SETTINGS.lock();
+            settings.enabled = enabled;
+            if let Some(auto_start_transcription) = auto_start_transcription {
+                settings.auto_start_transcription = auto_start_transcription;
+            }
+            if let Some(auto_start_session) = auto_start_session {
+                settings.auto_start_session = auto_start_session;
+            }
+            save(&settings)?;
+            info!(
+                "📞 Meeting detection {} (auto-start transcription: {}, session: {})",
+                if enabled { "enabled" } else { "disabled" },
+                settings.auto_start_transcription,
+                settings.auto_start_session
+            );
+        }
+        if !enabled {
+            TRACKED.lock().clear();
+        }
+        Ok(status())
+    })
+}
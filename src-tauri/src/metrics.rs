@@ -0,0 +1,109 @@
+// Latency metrics for MockMate
+// Records end-to-end timings from the hot paths and reports percentiles
+
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter};
+use crate::envelope::{self, Response};
+use crate::events;
+
+/// Audio frame sent to Deepgram -> transcript (interim or final) covering it received
+pub const AUDIO_TO_TRANSCRIPT: &str = "audio_to_deepgram_transcript";
+/// Question received by a streaming command -> first AI token delivered
+pub const QUESTION_TO_FIRST_TOKEN: &str = "question_to_first_ai_token";
+/// Resize command invoked -> window size applied
+pub const RESIZE_APPLIED: &str = "resize_command_to_applied";
+
+/// Samples kept per metric; percentiles are computed over this window
+const MAX_SAMPLES_PER_METRIC: usize = 500;
+/// How often the `metrics-snapshot` event is emitted
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(10);
+
+static SAMPLES: Lazy<Mutex<HashMap<&'static str, VecDeque<f64>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Percentile summary for one metric, in milliseconds
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct MetricSummary {
+    pub name: String,
+    pub count: usize,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+/// Record one latency sample
+pub fn record(name: &'static str, elapsed: Duration) {
+    let mut samples = SAMPLES.lock();
+    let series = samples.entry(name).or_default();
+    if series.len() >= MAX_SAMPLES_PER_METRIC {
+        series.pop_front();
+    }
+    series.push_back(elapsed.as_secs_f64() * 1000.0);
+}
+
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((pct / 100.0) * (sorted.len() - 1) as f64).round() as usize;
+    sorted[rank.min(sorted.len() - 1)]
+}
+
+/// Summaries for every metric that has at least one sample
+pub fn snapshot() -> Vec<MetricSummary> {
+    let samples = SAMPLES.lock();
+    let mut summaries: Vec<MetricSummary> = samples
+        .iter()
+        .filter(|(_, series)| !series.is_empty())
+        .map(|(name, series)| {
+            let mut sorted: Vec<f64> = series.iter().copied().collect();
+            sorted.sort_by(|a, b| a.total_cmp(b));
+            MetricSummary {
+                name: name.to_string(),
+                count: sorted.len(),
+                mean_ms: sorted.iter().sum::<f64>() / sorted.len() as f64,
+                p50_ms: percentile(&sorted, 50.0),
+                p90_ms: percentile(&sorted, 90.0),
+                p95_ms: percentile(&sorted, 95.0),
+                p99_ms: percentile(&sorted, 99.0),
+                max_ms: sorted.last().copied().unwrap_or(0.0),
+            }
+        })
+        .collect();
+    summaries.sort_by(|a, b| a.name.cmp(&b.name));
+    summaries
+}
+
+/// Periodically emit `metrics-snapshot` while there is data to report
+pub fn start_snapshot_emitter(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(SNAPSHOT_INTERVAL);
+        loop {
+            interval.tick().await;
+            let summaries = snapshot();
+            if !summaries.is_empty() {
+                let _ = app_handle.emit(events::METRICS_SNAPSHOT, &summaries);
+            }
+        }
+    });
+}
+
+/// Get latency percentiles for all instrumented paths
+#[tauri::command]
+#[specta::specta]
+pub async fn get_performance_metrics() -> Response<Vec<MetricSummary>> {
+    envelope::respond("get_performance_metrics", async move {
+        Ok(snapshot())
+    })
+    .await
+}
@@ -0,0 +1,288 @@
+// Microphone self-test
+// `test_microphone_capture` only shows that capture starts. `run_mic_selftest` records a short clip
+// through the same stream the live pipeline uses, plays it back on the default output so I hear what
+// transcription will get, and scores the clip: speech level, noise floor, signal-to-noise ratio and
+// clipping, each with a recommendation when it is off.
+
+use std::time::{Duration, Instant};
+
+use futures_util::StreamExt;
+use log::{info, warn};
+use serde::Serialize;
+use specta::Type;
+
+use crate::cancellation::{self, Progress};
+use crate::envelope::Response;
+use crate::error::{AppError, ErrorCode};
+use crate::guard::{self, Require};
+use crate::permissions::Permission;
+use crate::pluely_microphone::PluelyMicrophoneInput;
+
+const SAMPLE_RATE: u32 = 44100;
+const DEFAULT_DURATION_SECS: f32 = 4.0;
+const DURATION_RANGE: (f32, f32) = (1.0, 15.0);
+/// Analysis window for the level statistics (50ms)
+const WINDOW_SAMPLES: usize = SAMPLE_RATE as usize / 20;
+
+/// Speech quieter than this is hard to transcribe
+const MIN_LEVEL_DBFS: f32 = -38.0;
+/// Speech louder than this leaves no headroom
+const MAX_LEVEL_DBFS: f32 = -6.0;
+/// Background noise above this competes with speech
+const MAX_NOISE_FLOOR_DBFS: f32 = -45.0;
+const MIN_SNR_DB: f32 = 15.0;
+/// Samples at or above this magnitude count as clipped
+const CLIP_THRESHOLD: f32 = 0.99;
+const MAX_CLIPPING_RATIO: f32 = 0.001;
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct MicSelftestReport {
+    pub passed: bool,
+    /// Seconds of audio actually recorded
+    pub duration_secs: f32,
+    /// Level of the loudest tenth of the clip, i.e. my speech, in dBFS
+    pub level_dbfs: f32,
+    /// Level of the quietest tenth of the clip, in dBFS
+    pub noise_floor_dbfs: f32,
+    pub snr_db: f32,
+    pub peak_dbfs: f32,
+    /// Share of samples at full scale, 0-1
+    pub clipping_ratio: f32,
+    pub played_back: bool,
+    /// What to change, empty when the test passed cleanly
+    pub recommendations: Vec<String>,
+}
+
+fn dbfs(amplitude: f32) -> f32 {
+    20.0 * amplitude.max(1e-6).log10()
+}
+
+/// Value at `share` (0-1) of the sorted values
+fn percentile(sorted: &[f32], share: f32) -> f32 {
+    let index = ((sorted.len() - 1) as f32 * share).round() as usize;
+    sorted[index]
+}
+
+fn analyze(samples: &[f32], played_back: bool) -> MicSelftestReport {
+    let mut window_rms: Vec<f32> = samples
+        .chunks(WINDOW_SAMPLES)
+        .filter(|window| window.len() == WINDOW_SAMPLES)
+        .map(|window| (window.iter().map(|s| s * s).sum::<f32>() / window.len() as f32).sqrt())
+        .collect();
+    window_rms.sort_by(f32::total_cmp);
+    let (level, floor) = if window_rms.is_empty() {
+        (0.0, 0.0)
+    } else {
+        (percentile(&window_rms, 0.9), percentile(&window_rms, 0.1))
+    };
+    let peak = samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+    let clipped = samples.iter().filter(|s| s.abs() >= CLIP_THRESHOLD).count();
+
+    let level_dbfs = dbfs(level);
+    let noise_floor_dbfs = dbfs(floor);
+    let snr_db = level_dbfs - noise_floor_dbfs;
+    let clipping_ratio = if samples.is_empty() { 0.0 } else { clipped as f32 / samples.len() as f32 };
+
+    let mut passed = true;
+    let mut recommendations = Vec::new();
+    if level_dbfs < MIN_LEVEL_DBFS {
+        passed = false;
+        recommendations.push(format!(
+            "Your voice is too quiet ({:.0} dBFS): raise the microphone level in Sound settings or move closer",
+            level_dbfs
+        ));
+    } else if level_dbfs > MAX_LEVEL_DBFS {
+        recommendations.push(format!(
+            "Your voice is very loud ({:.0} dBFS): lower the microphone level a little to leave headroom",
+            level_dbfs
+        ));
+    }
+    if clipping_ratio > MAX_CLIPPING_RATIO {
+        passed = false;
+        recommendations.push(format!(
+            "{:.1}% of the clip is clipped: lower the microphone level or turn off microphone boost",
+            clipping_ratio * 100.0
+        ));
+    }
+    if noise_floor_dbfs > MAX_NOISE_FLOOR_DBFS {
+        recommendations.push(format!(
+            "Background noise is high ({:.0} dBFS): close windows, move away from fans or enable noise suppression",
+            noise_floor_dbfs
+        ));
+    }
+    if snr_db < MIN_SNR_DB {
+        passed = false;
+        recommendations.push(format!(
+            "Speech is only {:.0} dB above the background: speak during the test and reduce noise around you",
+            snr_db
+        ));
+    }
+    if !played_back {
+        recommendations.push("The clip could not be played back; check the default output device".to_string());
+    }
+
+    MicSelftestReport {
+        passed,
+        duration_secs: samples.len() as f32 / SAMPLE_RATE as f32,
+        level_dbfs,
+        noise_floor_dbfs,
+        snr_db,
+        peak_dbfs: dbfs(peak),
+        clipping_ratio,
+        played_back,
+        recommendations,
+    }
+}
+
+async fn record(duration: Duration, progress: &Progress) -> Result<Vec<f32>, AppError> {
+    let mut stream = PluelyMicrophoneInput::new()
+        .and_then(|input| input.stream())
+        .map_err(|e| AppError::new(ErrorCode::Unavailable, format!("Failed to open the microphone: {}", e)))?;
+
+    let wanted = (duration.as_secs_f32() * SAMPLE_RATE as f32) as usize;
+    let mut samples = Vec::with_capacity(wanted);
+    // Leave time for a slow device to start delivering
+    let deadline = Instant::now() + duration + Duration::from_secs(2);
+    let mut reported_secs = 0;
+    while samples.len() < wanted {
+        if progress.is_cancelled() {
+            return Err(AppError::new(ErrorCode::Cancelled, "Microphone test cancelled"));
+        }
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match tokio::time::timeout(remaining, stream.next()).await {
+            Ok(Some(sample)) => samples.push(sample),
+            Ok(None) => break,
+            Err(_) => break,
+        }
+        let secs = samples.len() / SAMPLE_RATE as usize;
+        if secs > reported_secs {
+            reported_secs = secs;
+            progress.report("recording", Some(60.0 * samples.len() as f32 / wanted as f32));
+        }
+    }
+
+    if samples.len() < WINDOW_SAMPLES {
+        return Err(AppError::new(
+            ErrorCode::Unavailable,
+            "The microphone delivered no audio; check that it is connected and not used exclusively by another app",
+        ));
+    }
+    Ok(samples)
+}
+
+/// Play mono 44.1kHz samples on the selected output device, else the default
+#[cfg(windows)]
+fn play_back(samples: &[f32]) -> anyhow::Result<()> {
+    use wasapi::{get_default_device, Direction, SampleType, StreamMode, WaveFormat};
+
+    let _ = wasapi::initialize_mta();
+    let device = match crate::audio_devices::preferred_device(&Direction::Render) {
+        Some(device) => device,
+        None => get_default_device(&Direction::Render)?,
+    };
+    let mut audio_client = device.get_iaudioclient()?;
+    let format = WaveFormat::new(32, 32, &SampleType::Float, SAMPLE_RATE as usize, 1, None);
+    let (_def_time, min_time) = audio_client.get_device_period()?;
+    let mode = StreamMode::EventsShared {
+        autoconvert: true,
+        buffer_duration_hns: min_time,
+    };
+    audio_client.initialize_client(&format, &Direction::Render, &mode)?;
+    let h_event = audio_client.set_get_eventhandle()?;
+    let render_client = audio_client.get_audiorenderclient()?;
+
+    let bytes: Vec<u8> = samples.iter().flat_map(|sample| sample.to_le_bytes()).collect();
+    audio_client.start_stream()?;
+    let mut offset = 0;
+    while offset < bytes.len() {
+        let frames = (audio_client.get_available_space_in_frames()? as usize).min((bytes.len() - offset) / 4);
+        if frames > 0 {
+            render_client.write_to_device(frames, &bytes[offset..offset + frames * 4], None)?;
+            offset += frames * 4;
+        }
+        h_event.wait_for_event(1000)?;
+    }
+    // Let the last buffer drain before stopping
+    std::thread::sleep(Duration::from_millis(250));
+    audio_client.stop_stream()?;
+    Ok(())
+}
+
+/// Play mono 44.1kHz samples on the default PulseAudio/PipeWire sink
+#[cfg(target_os = "linux")]
+fn play_back(samples: &[f32]) -> anyhow::Result<()> {
+    use anyhow::Context;
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new("pacat")
+        .args(["--playback", "--format=float32le", "--channels=1"])
+        .arg(format!("--rate={}", SAMPLE_RATE))
+        .stdin(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("failed to start pacat; install pulseaudio-utils or pipewire-pulse")?;
+    let bytes: Vec<u8> = samples.iter().flat_map(|sample| sample.to_le_bytes()).collect();
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(&bytes)?;
+    }
+    let status = child.wait()?;
+    anyhow::ensure!(status.success(), "pacat exited with {}", status);
+    Ok(())
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+fn play_back(_samples: &[f32]) -> anyhow::Result<()> {
+    anyhow::bail!("Playback is not available on this platform")
+}
+
+/// Record `duration_secs` (default 4s) from the microphone, play it back and score it
+#[tauri::command]
+#[specta::specta]
+pub async fn run_mic_selftest(duration_secs: Option<f32>, request_id: Option<String>) -> Response<MicSelftestReport> {
+    cancellation::run_with_progress("run_mic_selftest", request_id, |progress| async move {
+        guard::check(&[Require::Permission(Permission::Microphone)])?;
+        let duration_secs = duration_secs.unwrap_or(DEFAULT_DURATION_SECS);
+        if !(DURATION_RANGE.0..=DURATION_RANGE.1).contains(&duration_secs) {
+            return Err(AppError::new(
+                ErrorCode::InvalidInput,
+                format!("Duration must be between {} and {} seconds", DURATION_RANGE.0, DURATION_RANGE.1),
+            ));
+        }
+        info!("🎙️ Running microphone self-test ({:.1}s)", duration_secs);
+        crate::telemetry::record_feature("mic_selftest");
+
+        progress.report("recording", Some(0.0));
+        let samples = record(Duration::from_secs_f32(duration_secs), &progress).await?;
+
+        progress.report("playing_back", Some(65.0));
+        let played_back = match tauri::async_runtime::spawn_blocking({
+            let samples = samples.clone();
+            move || play_back(&samples)
+        })
+        .await
+        {
+            Ok(Ok(())) => true,
+            Ok(Err(e)) => {
+                warn!("Microphone self-test playback failed: {}", e);
+                false
+            }
+            Err(e) => {
+                warn!("Microphone self-test playback task failed: {}", e);
+                false
+            }
+        };
+
+        progress.report("analyzing", Some(95.0));
+        let report = analyze(&samples, played_back);
+        info!(
+            "🎙️ Microphone self-test {}: level {:.1} dBFS, noise {:.1} dBFS, clipping {:.2}%",
+            if report.passed { "passed" } else { "failed" },
+            report.level_dbfs,
+            report.noise_floor_dbfs,
+            report.clipping_ratio * 100.0
+        );
+        Ok(report)
+    })
+    .await
+}
@@ -0,0 +1,258 @@
+// Network request tracing for MockMate
+// Wraps reqwest sends to record method, host, status, duration and retries.
+// Headers, bodies and query strings are never recorded; path segments that could
+// carry user content (prompts in GET URLs, ids) are redacted.
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use reqwest::{RequestBuilder, Response};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use crate::envelope;
+use crate::error::{AppError, ErrorCode};
+
+/// Number of requests kept for get_recent_network_activity
+const MAX_ENTRIES: usize = 500;
+/// Failed requests to the same host within this window count as retries of each other
+const RETRY_WINDOW: Duration = Duration::from_secs(60);
+/// Path segments longer than this are treated as content and redacted
+const MAX_PLAIN_SEGMENT_LEN: usize = 32;
+
+static ENABLED: Lazy<AtomicBool> = Lazy::new(|| {
+    AtomicBool::new(std::env::var("MOCKMATE_NET_TRACE").map(|v| v != "0").unwrap_or(true))
+});
+static ENTRIES: Lazy<Mutex<VecDeque<NetworkEntry>>> =
+    Lazy::new(|| Mutex::new(VecDeque::with_capacity(MAX_ENTRIES)));
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct NetworkEntry {
+    pub timestamp: String,
+    pub method: String,
+    pub host: String,
+    /// Path with content-bearing segments replaced by ":redacted"
+    pub path: String,
+    /// HTTP status, or None when the request failed before a response
+    pub status: Option<u16>,
+    /// Time until response headers were received
+    pub duration_ms: u64,
+    /// Consecutive failed requests to the same host immediately before this one
+    pub retry_count: u32,
+    pub error: Option<String>,
+    #[serde(skip)]
+    recorded_at: Option<Instant>,
+}
+
+impl NetworkEntry {
+    fn failed(&self) -> bool {
+        self.status.map(|s| s >= 500).unwrap_or(true)
+    }
+}
+
+fn redact_path(path: &str) -> String {
+    let redacted: Vec<&str> = path
+        .split('/')
+        .map(|segment| {
+            let plain = !segment.is_empty()
+                && segment.len() <= MAX_PLAIN_SEGMENT_LEN
+                && segment.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+                && uuid::Uuid::parse_str(segment).is_err();
+            if segment.is_empty() || plain {
+                segment
+            } else {
+                ":redacted"
+            }
+        })
+        .collect();
+    redacted.join("/")
+}
+
+fn record(mut entry: NetworkEntry) {
+    let mut entries = ENTRIES.lock();
+
+    entry.retry_count = entries
+        .iter()
+        .rev()
+        .take_while(|prev| {
+            prev.host == entry.host
+                && prev.failed()
+                && prev.recorded_at.map(|t| t.elapsed() < RETRY_WINDOW).unwrap_or(false)
+        })
+        .count() as u32;
+
+    tracing::debug!(
+        target: "mockmate::net",
+        method = %entry.method,
+        host = %entry.host,
+        path = %entry.path,
+        status = ?entry.status,
+        duration_ms = entry.duration_ms,
+        retry_count = entry.retry_count,
+        "HTTP request"
+    );
+
+    if entries.len() >= MAX_ENTRIES {
+        entries.pop_front();
+    }
+    entries.push_back(entry);
+}
+
+/// Record what this request carries off the machine in the outbound audit log
+fn audit_request(request: &reqwest::Request, host: &str) {
+    let body = request.body().and_then(|b| b.as_bytes()).unwrap_or_default();
+    // GET-style endpoints carry the prompt in the URL itself
+    let url_bytes = request.url().path().len() + request.url().query().map(str::len).unwrap_or(0);
+
+    let provider = crate::audit::provider_for_host(host);
+    let purpose = crate::audit::purpose_for_request(&provider, request.url().path());
+    let category = if body.windows(11).any(|w| w == b"data:image/") {
+        crate::audit::PayloadCategory::Image
+    } else if purpose == "ai_answer" {
+        crate::audit::PayloadCategory::Text
+    } else {
+        crate::audit::PayloadCategory::Metadata
+    };
+
+    crate::audit::record(&provider, purpose, category, (body.len() + url_bytes) as u64);
+}
+
+/// Error from `send_traced`: either the request was refused locally or reqwest failed
+#[derive(Debug)]
+pub enum SendError {
+    /// Local-only mode forbids requests to this host
+    Blocked(String),
+    Http(reqwest::Error),
+}
+
+impl std::fmt::Display for SendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SendError::Blocked(host) => write!(f, "Request to {} {}", host, crate::privacy::BLOCKED_MESSAGE),
+            SendError::Http(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for SendError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SendError::Blocked(_) => None,
+            SendError::Http(e) => Some(e),
+        }
+    }
+}
+
+impl SendError {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            SendError::Blocked(_) => ErrorCode::PrivacyBlocked,
+            SendError::Http(e) => crate::error::request_code(e),
+        }
+    }
+
+    /// `AppError` with the code of this failure, e.g. "Failed to activate session: <error>"
+    #[track_caller]
+    pub fn context(self, what: &str) -> AppError {
+        AppError::new(self.code(), format!("{}: {}", what, self))
+    }
+}
+
+impl From<reqwest::Error> for SendError {
+    fn from(e: reqwest::Error) -> Self {
+        SendError::Http(e)
+    }
+}
+
+/// Drop-in replacement for `RequestBuilder::send` that records the request
+/// and enforces local-only mode
+pub trait TracedSend {
+    fn send_traced(self) -> impl Future<Output = Result<Response, SendError>> + Send;
+}
+
+impl TracedSend for RequestBuilder {
+    async fn send_traced(self) -> Result<Response, SendError> {
+        let (client, request) = self.build_split();
+        let request = request?;
+
+        let host = request.url().host_str().unwrap_or("").to_string();
+        if crate::privacy::is_local_only() && !crate::privacy::is_loopback_host(&host) {
+            tracing::warn!(target: "mockmate::net", host = %host, "Outbound request {}", crate::privacy::BLOCKED_MESSAGE);
+            return Err(SendError::Blocked(host));
+        }
+
+        audit_request(&request, &host);
+
+        if !ENABLED.load(Ordering::Relaxed) {
+            return Ok(client.execute(request).await?);
+        }
+
+        let method = request.method().to_string();
+        let path = redact_path(request.url().path());
+        let started = Instant::now();
+
+        let result = client.execute(request).await;
+
+        let (status, error) = match &result {
+            Ok(response) => (Some(response.status().as_u16()), None),
+            Err(e) => {
+                let kind = if e.is_timeout() {
+                    "timeout"
+                } else if e.is_connect() {
+                    "connect"
+                } else {
+                    "request"
+                };
+                (None, Some(kind.to_string()))
+            }
+        };
+
+        record(NetworkEntry {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            method,
+            host,
+            path,
+            status,
+            duration_ms: started.elapsed().as_millis() as u64,
+            retry_count: 0,
+            error,
+            recorded_at: Some(Instant::now()),
+        });
+
+        Ok(result?)
+    }
+}
+
+/// Recent outbound HTTP requests (newest last), optionally limited to one host
+#[tauri::command]
+#[specta::specta]
+pub async fn get_recent_network_activity(limit: Option<usize>, host: Option<String>) -> envelope::Response<Vec<NetworkEntry>> {
+    envelope::respond("get_recent_network_activity", async move {
+        let limit = limit.unwrap_or(100);
+        let entries = ENTRIES.lock();
+        let mut matching: Vec<NetworkEntry> = entries
+            .iter()
+            .rev()
+            .filter(|e| host.as_ref().map(|h| &e.host == h).unwrap_or(true))
+            .take(limit)
+            .cloned()
+            .collect();
+        matching.reverse();
+        Ok(matching)
+    })
+    .await
+}
+
+/// Turn request tracing on or off at runtime
+#[tauri::command]
+#[specta::specta]
+pub async fn set_network_tracing_enabled(enabled: bool) -> envelope::Response<bool> {
+    envelope::respond("set_network_tracing_enabled", async move {
+        ENABLED.store(enabled, Ordering::Relaxed);
+        Ok(enabled)
+    })
+    .await
+}
@@ -0,0 +1,242 @@
+// Notion export of interview notes
+// Creates a page in a user-chosen Notion database with the session report, a Q&A table and the
+// transcript. The integration token lives in the Windows Credential Manager; the database must be
+// shared with that integration in Notion.
+
+use chrono::{DateTime, Utc};
+use log::info;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use serde_json::{json, Value};
+
+use crate::database::postgres::SessionReport;
+use crate::net_trace::TracedSend;
+use crate::cancellation;
+use crate::envelope::{self, IntoResult, Response};
+
+const NOTION_URL: &str = "https://api.notion.com/v1";
+const NOTION_VERSION: &str = "2022-06-28";
+const TOKEN_SECRET: &str = "notion_token";
+/// Notion accepts at most 100 blocks per request and 2000 characters per text object
+const MAX_BLOCKS_PER_REQUEST: usize = 100;
+const MAX_TEXT_CHARS: usize = 2000;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct NotionStatus {
+    pub connected: bool,
+    pub workspace_bot: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct NotionExport {
+    pub page_id: String,
+    pub url: Option<String>,
+    pub blocks: usize,
+}
+
+fn token() -> Result<String, String> {
+    crate::secrets::get_secret(TOKEN_SECRET)?.ok_or_else(|| "Notion is not connected".to_string())
+}
+
+fn request(client: &reqwest::Client, method: reqwest::Method, path: &str, token: &str) -> reqwest::RequestBuilder {
+    client
+        .request(method, format!("{}{}", NOTION_URL, path))
+        .bearer_auth(token)
+        .header("Notion-Version", NOTION_VERSION)
+}
+
+async fn send(builder: reqwest::RequestBuilder, what: &str) -> Result<Value, String> {
+    let response = builder
+        .send_traced()
+        .await
+        .map_err(|e| format!("Notion {} failed: {}", what, e))?;
+    let status = response.status();
+    let body: Value = response.json().await.unwrap_or(Value::Null);
+    if !status.is_success() {
+        let message = body["message"].as_str().unwrap_or("no details");
+        return Err(format!("Notion {} failed: {} {}", what, status, message));
+    }
+    Ok(body)
+}
+
+/// Rich text array, split so no text object exceeds Notion's length limit
+fn rich_text(text: &str) -> Value {
+    let chars: Vec<char> = text.chars().collect();
+    let parts: Vec<Value> = chars
+        .chunks(MAX_TEXT_CHARS)
+        .map(|chunk| json!({ "type": "text", "text": { "content": chunk.iter().collect::<String>() } }))
+        .collect();
+    Value::Array(parts)
+}
+
+fn block(kind: &str, text: &str) -> Value {
+    json!({ "object": "block", "type": kind, kind: { "rich_text": rich_text(text) } })
+}
+
+fn table(header: &[&str], rows: &[Vec<String>]) -> Value {
+    let row = |cells: Vec<Value>| json!({ "type": "table_row", "table_row": { "cells": cells } });
+    let mut children = vec![row(header.iter().map(|h| rich_text(h)).collect())];
+    children.extend(rows.iter().map(|cells| row(cells.iter().map(|c| rich_text(c)).collect())));
+    json!({
+        "object": "block",
+        "type": "table",
+        "table": { "table_width": header.len(), "has_column_header": true, "children": children }
+    })
+}
+
+fn page_blocks(report: &SessionReport, transcript: &[crate::session_transcript::TranscriptLine]) -> Vec<Value> {
+    let session = &report.session;
+    let mut blocks = vec![block("heading_2", "Report")];
+    let started = session.session_started_at.unwrap_or(session.created_at);
+    let facts = [
+        format!("Role: {}", session.job_title),
+        format!("Date: {}", started.format("%Y-%m-%d %H:%M UTC")),
+        format!("Difficulty: {}", session.difficulty),
+        format!("Session type: {}", session.session_type),
+        format!("Status: {}", session.status),
+        format!("Duration: {} min", session.interview_duration.unwrap_or(0)),
+        format!("Questions: {} · Answers: {}", report.total_questions, report.total_answers),
+        format!("Average response time: {:.1}s", report.average_response_time),
+        format!("Average score: {:.1}", report.average_score),
+    ];
+    blocks.extend(facts.iter().map(|fact| block("bulleted_list_item", fact)));
+
+    blocks.push(block("heading_2", "Questions & Answers"));
+    let mut questions: Vec<_> = report.questions.iter().collect();
+    questions.sort_by_key(|q| q.question_number);
+    let rows: Vec<Vec<String>> = questions
+        .iter()
+        .map(|q| {
+            let answer = report
+                .answers
+                .iter()
+                .filter(|a| a.question_id == q.id)
+                .max_by_key(|a| a.answered_at);
+            vec![
+                q.question_number.to_string(),
+                q.question_text.clone(),
+                answer.and_then(|a| a.answer_text.clone()).unwrap_or_default(),
+                answer.and_then(|a| a.ai_score).map(|s| s.to_string()).unwrap_or_default(),
+                answer
+                    .and_then(|a| report.evaluations.iter().find(|e| e.answer_id == a.id.to_string()))
+                    .map(|e| format!("{:.1}/{} ({})", e.overall, e.scale_max, e.compact()))
+                    .unwrap_or_default(),
+            ]
+        })
+        .collect();
+    if rows.is_empty() {
+        blocks.push(block("paragraph", "No questions were recorded."));
+    }
+    // A table holds at most MAX_BLOCKS_PER_REQUEST rows including its header
+    for chunk in rows.chunks(MAX_BLOCKS_PER_REQUEST - 1) {
+        blocks.push(table(&["#", "Question", "Answer", "Score", "Rubric"], chunk));
+    }
+
+    blocks.push(block("heading_2", "Transcript"));
+    if transcript.is_empty() {
+        blocks.push(block("paragraph", "No transcript was stored for this session."));
+    }
+    blocks.extend(transcript.iter().map(|line| {
+        let speaker = line.speaker.as_deref().map(|s| format!("{}: ", s)).unwrap_or_default();
+        block("paragraph", &format!("[{}] {}{}", line.timestamp.format("%H:%M:%S"), speaker, line.text))
+    }));
+    blocks
+}
+
+/// Page properties: the database's title property plus its first date property, if any
+fn page_properties(database: &Value, title: &str, date: DateTime<Utc>) -> Result<Value, String> {
+    let schema = database["properties"].as_object().ok_or("Unexpected Notion database response")?;
+    let property_of = |kind: &str| schema.iter().find(|(_, p)| p["type"] == kind).map(|(name, _)| name.clone());
+    let title_property = property_of("title").ok_or("The Notion database has no title property")?;
+    let mut properties = json!({ title_property: { "title": rich_text(title) } });
+    if let Some(date_property) = property_of("date") {
+        properties[date_property] = json!({ "date": { "start": date.to_rfc3339() } });
+    }
+    Ok(properties)
+}
+
+/// Store (or with None, remove) the Notion integration token after checking it works
+#[tauri::command]
+#[specta::specta]
+pub async fn set_notion_token(token: Option<String>) -> Response<NotionStatus> {
+    envelope::respond("set_notion_token", async move {
+        let Some(token) = token.map(|t| t.trim().to_string()).filter(|t| !t.is_empty()) else {
+            crate::secrets::delete_secret(TOKEN_SECRET)?;
+            info!("📓 Notion disconnected");
+            return Ok(NotionStatus { connected: false, workspace_bot: None });
+        };
+        crate::privacy::ensure_cloud_allowed("Notion")?;
+        let me = send(request(&reqwest::Client::new(), reqwest::Method::GET, "/users/me", &token), "token check").await?;
+        crate::secrets::set_secret(TOKEN_SECRET, &token)?;
+        info!("📓 Notion connected");
+        Ok(NotionStatus {
+            connected: true,
+            workspace_bot: me["name"].as_str().map(str::to_string),
+        })
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_notion_status() -> Response<NotionStatus> {
+    envelope::respond("get_notion_status", async move {
+        Ok(NotionStatus {
+            connected: crate::secrets::get_secret(TOKEN_SECRET)?.is_some(),
+            workspace_bot: None,
+        })
+    })
+    .await
+}
+
+/// Create a page for the session in the Notion database `database_id`
+#[tauri::command]
+#[specta::specta]
+pub async fn export_session_to_notion(session_id: String, database_id: String, request_id: Option<String>) -> Response<NotionExport> {
+    cancellation::run("export_session_to_notion", request_id, async move {
+        crate::privacy::ensure_cloud_allowed("Notion")?;
+        let token = token()?;
+        let report = crate::database::postgres::get_interview_report(session_id.clone()).await.into_result()?;
+        let transcript = crate::session_transcript::load(&session_id);
+        let client = reqwest::Client::new();
+
+        let database_id = database_id.trim().replace('-', "");
+        let database = send(
+            request(&client, reqwest::Method::GET, &format!("/databases/{}", database_id), &token),
+            "database lookup",
+        )
+        .await?;
+        let date = report.session.session_started_at.unwrap_or(report.session.created_at);
+        let title = format!("{} interview · {}", report.session.job_title, date.format("%Y-%m-%d"));
+        let properties = page_properties(&database, &title, date)?;
+
+        let blocks = page_blocks(&report, &transcript);
+        let mut chunks = blocks.chunks(MAX_BLOCKS_PER_REQUEST);
+        let page = send(
+            request(&client, reqwest::Method::POST, "/pages", &token).json(&json!({
+                "parent": { "database_id": database_id },
+                "properties": properties,
+                "children": chunks.next().unwrap_or_default(),
+            })),
+            "page creation",
+        )
+        .await?;
+        let page_id = page["id"].as_str().ok_or("Notion did not return a page id")?.to_string();
+        for chunk in chunks {
+            send(
+                request(&client, reqwest::Method::PATCH, &format!("/blocks/{}/children", page_id), &token)
+                    .json(&json!({ "children": chunk })),
+                "block append",
+            )
+            .await?;
+        }
+
+        info!("📓 Exported session {} to Notion page {} ({} blocks)", session_id, page_id, blocks.len());
+        Ok(NotionExport {
+            page_id,
+            url: page["url"].as_str().map(str::to_string),
+            blocks: blocks.len(),
+        })
+    })
+    .await
+}
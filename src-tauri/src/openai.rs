@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use anyhow::Result;
 use log::{info, error};
 
+use crate::net_trace::TracedSend;
+
 #[derive(Debug, Clone)]
 pub enum OpenAIModel {
     GPT4Turbo,
@@ -94,25 +96,69 @@ impl OpenAIClient {
         }
     }
 
+    /// Open a pooled connection (DNS + TLS) ahead of the first answer; with `ping` also request a single token
+    pub async fn warm_up(&self, ping: bool) -> Result<()> {
+        let response = if ping {
+            let request = OpenAIRequest {
+                model: OpenAIModel::GPT35Turbo.as_str().to_string(),
+                messages: vec![OpenAIMessage {
+                    role: "user".to_string(),
+                    content: serde_json::Value::String("ping".to_string()),
+                }],
+                max_tokens: 1,
+                temperature: 0.0,
+                stream: false,
+            };
+            self.client
+                .post(format!("{}/chat/completions", self.base_url))
+                .header("Authorization", format!("Bearer {}", self.api_key))
+                .json(&request)
+                .send_traced()
+                .await?
+        } else {
+            self.client
+                .get(format!("{}/models", self.base_url))
+                .header("Authorization", format!("Bearer {}", self.api_key))
+                .send_traced()
+                .await?
+        };
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("OpenAI warm-up returned {}", response.status()));
+        }
+        // Read the body so the connection goes back to the pool
+        let _ = response.bytes().await;
+        Ok(())
+    }
+
     pub async fn generate_answer(
         &self,
         question: &str,
         context: &InterviewContext,
         model: OpenAIModel,
     ) -> Result<String> {
-        let system_prompt = self.build_system_prompt(context);
+        let system_prompt = Self::build_system_prompt(context);
         let user_prompt = format!("Interview Question: {}\n\nProvide a confident, direct, and authentic answer that demonstrates your qualifications. Keep it focused and conversational - aim for 30-60 seconds when spoken aloud. Be specific and impactful.", question);
+        self.complete(&system_prompt, &user_prompt, model).await
+    }
 
+    /// Run a prompt with a caller-supplied system prompt
+    pub async fn complete(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+        model: OpenAIModel,
+    ) -> Result<String> {
         let request = OpenAIRequest {
             model: model.as_str().to_string(),
             messages: vec![
                 OpenAIMessage {
                     role: "system".to_string(),
-                    content: serde_json::Value::String(system_prompt),
+                    content: serde_json::Value::String(system_prompt.to_string()),
                 },
                 OpenAIMessage {
                     role: "user".to_string(),
-                    content: serde_json::Value::String(user_prompt),
+                    content: serde_json::Value::String(user_prompt.to_string()),
                 },
             ],
             max_tokens: 1000,
@@ -128,7 +174,7 @@ impl OpenAIClient {
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("Content-Type", "application/json")
             .json(&request)
-            .send()
+            .send_traced()
             .await?;
 
         if !response.status().is_success() {
@@ -158,7 +204,7 @@ impl OpenAIClient {
         }
     }
 
-    fn build_system_prompt(&self, context: &InterviewContext) -> String {
+    pub(crate) fn build_system_prompt(context: &InterviewContext) -> String {
         let mut prompt = String::new();
         
         // Core role and personality - ULTRA FOCUSED for interview speed
@@ -230,14 +276,10 @@ impl OpenAIClient {
             }
         }
         
+        // Job description and resume arrive already cut to the prompt budget by `ContextBuilder`
         if let Some(job_description) = &context.job_description {
             if !job_description.is_empty() {
-                let job_desc_summary = if job_description.len() > 300 {
-                    format!("{}...", &job_description[..300])
-                } else {
-                    job_description.clone()
-                };
-                prompt.push_str(&format!("\n\nJob Description Summary: {}", job_desc_summary));
+                prompt.push_str(&format!("\n\nJob Description Summary: {}", job_description));
                 prompt.push_str("\n• Align your responses with the specific requirements and qualifications mentioned");
             }
         }
@@ -246,17 +288,22 @@ impl OpenAIClient {
         if let Some(resume) = &context.resume_content {
             if !resume.is_empty() {
                 prompt.push_str("\n\n=== CANDIDATE BACKGROUND ===\n");
-                let resume_summary = if resume.len() > 500 {
-                    format!("{}...", &resume[..500])
-                } else {
-                    resume.clone()
-                };
-                prompt.push_str(&format!("Resume Summary: {}", resume_summary));
+                prompt.push_str(&format!("Resume Summary: {}", resume));
                 prompt.push_str("\n• Draw from this background to provide authentic, personalized responses");
                 prompt.push_str("\n• Reference specific experiences, skills, and achievements when relevant");
             }
         }
         
+        if let Some(transcript) = &context.recent_transcript {
+            prompt.push_str("\n\n=== RECENT CONVERSATION (interviewer) ===\n");
+            prompt.push_str(transcript);
+        }
+
+        if let Some(prior_qa) = &context.prior_qa {
+            prompt.push_str("\n\n=== EARLIER ANSWERS (stay consistent with these) ===\n");
+            prompt.push_str(prior_qa);
+        }
+        
         // ULTRA-FOCUSED Response Guidelines for Interview Speed
         prompt.push_str("\n\n=== INTERVIEW SPEED GUIDELINES (CRITICAL) ===");
         prompt.push_str("\n\n⚡ SPEED & DIRECTNESS:");
@@ -277,6 +324,20 @@ impl OpenAIClient {
         prompt.push_str("\n• Experience: State what you've done, not what you could do");
         
         prompt.push_str("\n\nCRITICAL: This is for LIVE INTERVIEW assistance. Responses must be fast, direct, and immediately usable. NO verbose explanations or context.");
+
+        if let Some(custom) = &context.custom_system_prompt {
+            prompt.push_str("\n\n=== SESSION INSTRUCTIONS (follow these over the guidelines above) ===\n");
+            prompt.push_str(custom);
+        }
+
+        if let Some(glossary) = crate::glossary::prompt_section(2000) {
+            prompt.push_str("\n\n=== GLOSSARY (use these terms exactly as written) ===");
+            prompt.push_str(&glossary);
+        }
+
+        if let Some(language) = crate::answer_language::prompt_instruction() {
+            prompt.push_str(&format!("\n\nLANGUAGE:{}", language));
+        }
         
         prompt
     }
@@ -289,7 +350,7 @@ impl OpenAIClient {
     ) -> Result<String> {
         let system_prompt = format!(
             "You are analyzing screen content during an interview. Help the candidate understand what's being discussed and provide relevant talking points.\n\n{}",
-            self.build_system_prompt(context)
+            Self::build_system_prompt(context)
         );
 
         let user_prompt = format!(
@@ -320,7 +381,7 @@ impl OpenAIClient {
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("Content-Type", "application/json")
             .json(&request)
-            .send()
+            .send_traced()
             .await?;
 
         if !response.status().is_success() {
@@ -369,7 +430,7 @@ impl OpenAIClient {
         let system_prompt = format!(
             "You are an expert technical interviewer analyzing a screenshot. {}\n\n{}",
             analysis_prompt,
-            self.build_system_prompt(context)
+            Self::build_system_prompt(context)
         );
         
         // Build the vision message with image data
@@ -411,7 +472,7 @@ impl OpenAIClient {
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("Content-Type", "application/json")
             .json(&request)
-            .send()
+            .send_traced()
             .await?;
         
         if !response.status().is_success() {
@@ -455,6 +516,11 @@ pub struct InterviewContext {
     pub resume_content: Option<String>,
     pub user_experience_level: Option<String>,
     pub interview_style: Option<String>,
+    // Filled by `ContextBuilder` for each answer
+    pub recent_transcript: Option<String>,
+    pub prior_qa: Option<String>,
+    /// Instructions set for the current session, applied to every generation (see session_prompt)
+    pub custom_system_prompt: Option<String>,
 }
 
 impl InterviewContext {
@@ -0,0 +1,360 @@
+// Answer pacing and talk-time feedback
+// Each saved interview question opens an answer window that lasts until the next question or the end
+// of the session. During the window, microphone VAD measures how long I actually speak, and final
+// transcripts that overlap my speech are counted for words-per-minute and filler words. Live
+// `pacing-feedback` events nudge me when an answer runs long or gets rushed; per-answer stats are
+// saved to %APPDATA%\MockMate\pacing\<session id>.json and included in the interview report.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use once_cell::sync::{Lazy, OnceCell};
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter};
+use crate::envelope::{self, Response};
+use crate::events;
+
+const TICK_INTERVAL: Duration = Duration::from_secs(5);
+/// Talk-time reminders start at this many minutes and repeat every minute after
+const LONG_ANSWER_MINUTES: u64 = 2;
+/// Words per minute outside this range get a one-off nudge once enough speech is measured
+const WPM_RANGE: (f32, f32) = (100.0, 175.0);
+const MIN_SPEECH_FOR_WPM: Duration = Duration::from_secs(20);
+/// Fillers per 100 words above which a nudge is sent
+const FILLER_RATE_LIMIT: f32 = 6.0;
+/// Transcripts arrive this long after the audio they describe
+const TRANSCRIPT_LAG: Duration = Duration::from_millis(1500);
+/// Share of a transcript's span that must overlap mic speech to count as mine
+const MIC_OVERLAP_SHARE: f32 = 0.3;
+/// Mic speech history kept for transcript attribution
+const SPEECH_HISTORY: Duration = Duration::from_secs(60);
+
+const FILLER_WORDS: &[&str] = &["um", "uh", "uhm", "umm", "er", "erm", "ah", "hmm", "basically", "literally"];
+const FILLER_PHRASES: &[(&str, &str)] = &[("you", "know"), ("sort", "of"), ("kind", "of"), ("i", "mean")];
+
+static APP_HANDLE: OnceCell<AppHandle> = OnceCell::new();
+static STATE: Lazy<Mutex<PacingState>> = Lazy::new(|| Mutex::new(PacingState::default()));
+
+#[derive(Default)]
+struct PacingState {
+    /// Recent mic speech spans, oldest first
+    speech: VecDeque<(Instant, Instant)>,
+    current: Option<ActiveAnswer>,
+}
+
+struct ActiveAnswer {
+    session_id: String,
+    question_id: String,
+    question_text: String,
+    started: Instant,
+    started_at: DateTime<Utc>,
+    speaking: Duration,
+    words: usize,
+    fillers: BTreeMap<String, usize>,
+    /// Talk-time minute last reported, so each reminder fires once
+    reported_minutes: u64,
+    pace_nudged: bool,
+    filler_nudged: bool,
+}
+
+impl ActiveAnswer {
+    fn filler_count(&self) -> usize {
+        self.fillers.values().sum()
+    }
+
+    fn words_per_minute(&self) -> Option<f32> {
+        (self.speaking >= MIN_SPEECH_FOR_WPM).then(|| self.words as f32 / (self.speaking.as_secs_f32() / 60.0))
+    }
+
+    fn stats(&self) -> AnswerPacing {
+        AnswerPacing {
+            question_id: self.question_id.clone(),
+            question_text: self.question_text.clone(),
+            started_at: self.started_at,
+            duration_seconds: self.started.elapsed().as_secs_f32(),
+            speaking_seconds: self.speaking.as_secs_f32(),
+            words: self.words,
+            words_per_minute: self.words_per_minute(),
+            filler_count: self.filler_count(),
+            fillers: self.fillers.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AnswerPacing {
+    pub question_id: String,
+    pub question_text: String,
+    pub started_at: DateTime<Utc>,
+    /// Time from the question until the next one (or session end)
+    pub duration_seconds: f32,
+    /// Time my microphone detected speech during that window
+    pub speaking_seconds: f32,
+    pub words: usize,
+    pub words_per_minute: Option<f32>,
+    pub filler_count: usize,
+    pub fillers: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct PacingReport {
+    pub answers: Vec<AnswerPacing>,
+    pub total_speaking_seconds: f32,
+    pub average_words_per_minute: Option<f32>,
+    pub total_fillers: usize,
+    /// Fillers per 100 words across the session
+    pub filler_rate: Option<f32>,
+    pub longest_answer_seconds: f32,
+}
+
+impl PacingReport {
+    fn from_answers(answers: Vec<AnswerPacing>) -> Self {
+        let total_speaking_seconds = answers.iter().map(|a| a.speaking_seconds).sum();
+        let total_words: usize = answers.iter().map(|a| a.words).sum();
+        let total_fillers = answers.iter().map(|a| a.filler_count).sum();
+        let measured: Vec<f32> = answers.iter().filter_map(|a| a.words_per_minute).collect();
+        Self {
+            average_words_per_minute: (!measured.is_empty()).then(|| measured.iter().sum::<f32>() / measured.len() as f32),
+            filler_rate: (total_words > 0).then(|| total_fillers as f32 * 100.0 / total_words as f32),
+            longest_answer_seconds: answers.iter().map(|a| a.speaking_seconds).fold(0.0, f32::max),
+            total_speaking_seconds,
+            total_fillers,
+            answers,
+        }
+    }
+}
+
+/// Payload of `pacing-feedback`
+#[derive(Debug, Clone, Serialize, JsonSchema, Type)]
+pub struct PacingFeedback {
+    question_id: String,
+    elapsed_seconds: f32,
+    speaking_seconds: f32,
+    words_per_minute: Option<f32>,
+    filler_count: usize,
+    /// Set when there is something worth telling me right now
+    message: Option<String>,
+}
+
+fn pacing_dir() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("pacing")
+}
+
+fn load_answers(session_id: &str) -> Vec<AnswerPacing> {
+    std::fs::read(pacing_dir().join(format!("{}.json", session_id)))
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn save_answer(session_id: &str, answer: AnswerPacing) {
+    let mut answers = load_answers(session_id);
+    answers.retain(|a| a.question_id != answer.question_id);
+    answers.push(answer);
+    let result = std::fs::create_dir_all(pacing_dir()).and_then(|_| {
+        let body = serde_json::to_vec_pretty(&answers).map_err(std::io::Error::other)?;
+        std::fs::write(pacing_dir().join(format!("{}.json", session_id)), body)
+    });
+    if let Err(e) = result {
+        warn!("Failed to save pacing stats for session {}: {}", session_id, e);
+    }
+}
+
+fn finish_current(state: &mut PacingState) {
+    if let Some(answer) = state.current.take() {
+        let stats = answer.stats();
+        info!(
+            "⏱️ Answer to {} took {:.0}s ({:.0}s speaking, {} words, {} fillers)",
+            stats.question_id, stats.duration_seconds, stats.speaking_seconds, stats.words, stats.filler_count
+        );
+        save_answer(&answer.session_id, stats);
+    }
+}
+
+/// Count words and fillers in one transcript segment
+fn count_words(text: &str, fillers: &mut BTreeMap<String, usize>) -> usize {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    for (i, word) in words.iter().enumerate() {
+        if FILLER_WORDS.contains(&word.as_str()) {
+            *fillers.entry(word.clone()).or_default() += 1;
+        } else if let Some((first, second)) = words.get(i + 1).and_then(|next| {
+            FILLER_PHRASES
+                .iter()
+                .find(|(first, second)| word == first && next == second)
+        }) {
+            *fillers.entry(format!("{} {}", first, second)).or_default() += 1;
+        }
+    }
+    words.len()
+}
+
+/// Start the live feedback ticker; call once from setup
+pub fn init(app_handle: AppHandle) {
+    if APP_HANDLE.set(app_handle).is_err() {
+        return;
+    }
+    tauri::async_runtime::spawn(async {
+        let mut ticker = tokio::time::interval(TICK_INTERVAL);
+        loop {
+            ticker.tick().await;
+            tick();
+        }
+    });
+}
+
+fn tick() {
+    let Some(app_handle) = APP_HANDLE.get() else { return };
+    let mut state = STATE.lock();
+    let Some(answer) = state.current.as_mut() else { return };
+
+    let speaking_minutes = answer.speaking.as_secs() / 60;
+    let wpm = answer.words_per_minute();
+    let filler_rate = (answer.words >= 50).then(|| answer.filler_count() as f32 * 100.0 / answer.words as f32);
+    let message = if speaking_minutes >= LONG_ANSWER_MINUTES && speaking_minutes > answer.reported_minutes {
+        answer.reported_minutes = speaking_minutes;
+        Some(format!("You've been talking {} minutes - consider wrapping up", speaking_minutes))
+    } else if let Some(wpm) = wpm.filter(|wpm| !answer.pace_nudged && (*wpm < WPM_RANGE.0 || *wpm > WPM_RANGE.1)) {
+        answer.pace_nudged = true;
+        Some(if wpm > WPM_RANGE.1 {
+            format!("You're speaking quickly ({:.0} wpm) - slow down a little", wpm)
+        } else {
+            format!("You're speaking slowly ({:.0} wpm) - pick up the pace a little", wpm)
+        })
+    } else if filler_rate.is_some_and(|rate| !answer.filler_nudged && rate > FILLER_RATE_LIMIT) {
+        answer.filler_nudged = true;
+        Some(format!("{} filler words so far - try pausing instead", answer.filler_count()))
+    } else {
+        None
+    };
+
+    let feedback = PacingFeedback {
+        question_id: answer.question_id.clone(),
+        elapsed_seconds: answer.started.elapsed().as_secs_f32(),
+        speaking_seconds: answer.speaking.as_secs_f32(),
+        words_per_minute: wpm,
+        filler_count: answer.filler_count(),
+        message,
+    };
+    drop(state);
+    let _ = app_handle.emit(events::PACING_FEEDBACK, feedback);
+}
+
+/// One microphone VAD decision covering `chunk` of audio
+pub fn on_mic_vad(is_speech: bool, chunk: Duration) {
+    if !is_speech {
+        return;
+    }
+    let now = Instant::now();
+    let mut state = STATE.lock();
+    match state.speech.back_mut() {
+        // Merge with the previous span across short gaps between VAD chunks
+        Some((_, end)) if now.duration_since(*end) <= chunk * 4 => *end = now,
+        _ => state.speech.push_back((now.checked_sub(chunk).unwrap_or(now), now)),
+    }
+    while state.speech.front().is_some_and(|(_, end)| now.duration_since(*end) > SPEECH_HISTORY) {
+        state.speech.pop_front();
+    }
+    if let Some(answer) = state.current.as_mut() {
+        answer.speaking += chunk;
+    }
+}
+
+/// A final transcript covering `duration_seconds` of audio; counted when it overlaps my speech
+pub fn on_transcript(text: &str, duration_seconds: f64) {
+    let mut state = STATE.lock();
+    if state.current.is_none() {
+        return;
+    }
+    let now = Instant::now();
+    let span = Duration::from_secs_f64(duration_seconds.max(0.5));
+    let window_end = now.checked_sub(TRANSCRIPT_LAG).unwrap_or(now);
+    let window_start = window_end.checked_sub(span).unwrap_or(window_end);
+    let overlap: Duration = state
+        .speech
+        .iter()
+        .filter(|(start, end)| *end > window_start && *start < window_end)
+        .map(|(start, end)| (*end).min(window_end).saturating_duration_since((*start).max(window_start)))
+        .sum();
+    if overlap.as_secs_f32() < span.as_secs_f32() * MIC_OVERLAP_SHARE {
+        return;
+    }
+    if let Some(answer) = state.current.as_mut() {
+        answer.words += count_words(text, &mut answer.fillers);
+    }
+}
+
+/// A question was saved: close the previous answer window and open one for this question
+pub fn on_question(session_id: &str, question_id: &str, question_text: &str) {
+    let mut state = STATE.lock();
+    finish_current(&mut state);
+    state.current = Some(ActiveAnswer {
+        session_id: session_id.to_string(),
+        question_id: question_id.to_string(),
+        question_text: question_text.to_string(),
+        started: Instant::now(),
+        started_at: Utc::now(),
+        speaking: Duration::ZERO,
+        words: 0,
+        fillers: BTreeMap::new(),
+        reported_minutes: 0,
+        pace_nudged: false,
+        filler_nudged: false,
+    });
+}
+
+/// Close the open answer when its session ends
+pub fn on_session_ended(session_id: &str) {
+    let mut state = STATE.lock();
+    if state.current.as_ref().is_some_and(|a| a.session_id == session_id) {
+        finish_current(&mut state);
+    }
+}
+
+/// Sessions with saved pacing stats
+pub fn measured_sessions() -> Vec<String> {
+    std::fs::read_dir(pacing_dir())
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .filter_map(|path| path.file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Pacing stats recorded for a session, including the answer still in progress
+pub fn report(session_id: &str) -> Option<PacingReport> {
+    let mut answers = load_answers(session_id);
+    if let Some(current) = STATE.lock().current.as_ref().filter(|a| a.session_id == session_id) {
+        answers.retain(|a| a.question_id != current.question_id);
+        answers.push(current.stats());
+    }
+    if answers.is_empty() {
+        return None;
+    }
+    answers.sort_by_key(|a| a.started_at);
+    Some(PacingReport::from_answers(answers))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_pacing_report(session_id: String) -> Response<Option<PacingReport>> {
+    envelope::respond("get_pacing_report", async move {
+        Ok(report(&session_id))
+    })
+    .await
+}
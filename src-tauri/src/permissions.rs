@@ -1,22 +1,63 @@
 use std::process::Command;
 use log::{info, error, warn};
 use anyhow::{Result, anyhow};
-use std::path::PathBuf;
+use schemars::JsonSchema;
+use serde::{Serialize, Deserialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter};
+use crate::envelope::{self, Response};
+use crate::events;
+use crate::error::AppError;
 
 pub struct PermissionManager;
 
+/// Payload of the `permission-revoked` event
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct PermissionRevokedEvent {
+    /// "microphone", "camera", ...
+    pub permission: String,
+    /// Where the denial was detected, e.g. "capture_start" or "capture_loop"
+    pub source: String,
+    pub detail: String,
+    /// Subsystems that were stopped because they depend on the permission
+    pub paused: Vec<String>,
+    pub timestamp: u64,
+}
+
+/// Whether an audio/OS error means access was denied (E_ACCESSDENIED, e.g. privacy settings)
+pub fn is_access_denied(error: &(impl std::fmt::Debug + std::fmt::Display)) -> bool {
+    let text = format!("{} {:?}", error, error).to_lowercase();
+    text.contains("0x80070005") || text.contains("e_accessdenied") || text.contains("access is denied")
+}
+
+/// Tell the frontend a permission was withdrawn and which subsystems were paused because of it
+pub fn notify_permission_revoked(app_handle: &AppHandle, permission: &str, source: &str, detail: &str, paused: Vec<String>) {
+    warn!("🚫 {} permission revoked ({}): {}", permission, source, detail);
+    let event = PermissionRevokedEvent {
+        permission: permission.to_string(),
+        source: source.to_string(),
+        detail: detail.to_string(),
+        paused,
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+    };
+    if let Err(e) = app_handle.emit(events::PERMISSION_REVOKED, &event) {
+        error!("Failed to emit permission-revoked event: {}", e);
+    }
+}
+
 impl PermissionManager {
     /// Check if this is the first run of the application
     pub fn is_first_run() -> Result<bool> {
-        let app_data = std::env::var("APPDATA")?;
-        let config_path = PathBuf::from(app_data).join("MockMate").join(".initialized");
+        let config_path = crate::portable::data_root().join("MockMate").join(".initialized");
         Ok(!config_path.exists())
     }
 
     /// Mark the application as initialized
     pub fn mark_initialized() -> Result<()> {
-        let app_data = std::env::var("APPDATA")?;
-        let config_dir = PathBuf::from(app_data).join("MockMate");
+        let config_dir = crate::portable::data_root().join("MockMate");
         let config_path = config_dir.join(".initialized");
         
         std::fs::create_dir_all(&config_dir)?;
@@ -24,54 +65,25 @@ impl PermissionManager {
         Ok(())
     }
 
-    /// Request microphone permissions through Windows Settings
+    /// Request microphone permissions through Windows Settings (System Settings on macOS)
     pub fn request_microphone_permission() -> Result<()> {
         info!("Requesting microphone permissions...");
-        
-        // Open Windows Privacy Settings for Microphone
-        let output = Command::new("cmd")
-            .args(&["/C", "start", "ms-settings:privacy-microphone"])
-            .output()?;
-
-        if output.status.success() {
-            info!("Opened microphone privacy settings");
-            Ok(())
-        } else {
-            Err(anyhow!("Failed to open microphone settings"))
-        }
+        os::request(Permission::Microphone).map_err(|e| anyhow!("Failed to request microphone access: {}", e))
     }
 
-    /// Check if microphone permission is granted using Windows API
+    /// Whether the microphone can be recorded without the user changing a setting first
     pub fn check_microphone_permission() -> Result<bool> {
-        // Check Windows registry for microphone permission
-        match Command::new("reg")
-            .args(&[
-                "query", 
-                "HKCU\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\CapabilityAccessManager\\ConsentStore\\microphone", 
-                "/v", "Value"
-            ])
-            .output() {
-            Ok(output) => {
-                let output_str = String::from_utf8_lossy(&output.stdout);
-                if output_str.contains("Allow") {
-                    info!("Microphone access granted via registry check");
-                    Ok(true)
-                } else {
-                    warn!("Microphone access not granted - registry check failed");
-                    Ok(false)
-                }
-            }
-            Err(e) => {
-                warn!("Failed to check microphone permission via registry: {}", e);
-                // Fallback: assume permission is granted if we can't check
-                Ok(true)
-            }
+        let state = os::state(Permission::Microphone);
+        if !state.usable() {
+            warn!("Microphone access not granted ({:?})", state);
         }
+        Ok(state.usable())
     }
 
     /// Enable exclusive mode access for better audio capture
     pub fn enable_exclusive_mode() -> Result<()> {
         info!("Enabling exclusive mode for audio devices...");
+        crate::portable::ensure_installed("Changing audio privacy settings in the registry").map_err(|e| anyhow!(e))?;
         
         // This requires registry modification - should be done during installation
         let registry_commands = vec![
@@ -116,6 +128,14 @@ impl PermissionManager {
 
     /// Initialize permissions on first run
     pub fn initialize_permissions_on_first_run() -> Result<()> {
+        // The privacy settings page and registry consent store only exist on Windows
+        if !cfg!(windows) {
+            return Ok(());
+        }
+        // Portable copies leave the machine's settings alone; permissions are asked for on first use
+        if crate::portable::enabled() {
+            return Ok(());
+        }
         if Self::is_first_run()? {
             info!("First run detected, requesting permissions...");
             
@@ -137,23 +157,477 @@ impl PermissionManager {
     }
 }
 
+/// OS permissions MockMate depends on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    Microphone,
+    ScreenRecording,
+    /// Reading other applications' windows (macOS Accessibility, the AT-SPI bus on Linux)
+    Accessibility,
+}
+
+impl Permission {
+    const ALL: [Permission; 3] = [Permission::Microphone, Permission::ScreenRecording, Permission::Accessibility];
+
+    fn needed_for(self) -> &'static str {
+        match self {
+            Permission::Microphone => "Microphone capture, and on macOS recording the system audio loopback device",
+            Permission::ScreenRecording => "Screenshots and screen analysis",
+            Permission::Accessibility => "Reading questions from other applications' windows",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionState {
+    Granted,
+    Denied,
+    /// Not decided yet; the OS asks on first use (or, for the Linux screen cast portal, on every use)
+    NotDetermined,
+    /// This OS does not gate the feature
+    NotRequired,
+    /// The service behind the permission is missing (no AT-SPI bus, no desktop portal)
+    Unavailable,
+}
+
+impl PermissionState {
+    /// Whether the feature works without the user changing a setting first
+    pub fn usable(self) -> bool {
+        matches!(self, PermissionState::Granted | PermissionState::NotDetermined | PermissionState::NotRequired)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Remediation {
+    pub instructions: String,
+    /// Settings URL for the page to change (ms-settings: on Windows, x-apple.systempreferences: on macOS)
+    pub deep_link: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PermissionStatus {
+    pub permission: Permission,
+    pub state: PermissionState,
+    pub usable: bool,
+    pub needed_for: String,
+    /// Set whenever the user can do something about the state
+    pub remediation: Option<Remediation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PermissionReport {
+    /// `std::env::consts::OS`
+    pub os: String,
+    pub permissions: Vec<PermissionStatus>,
+    /// Every permission is usable
+    pub ready: bool,
+}
+
+pub fn permission_status(permission: Permission) -> PermissionStatus {
+    let state = os::state(permission);
+    PermissionStatus {
+        permission,
+        state,
+        usable: state.usable(),
+        needed_for: permission.needed_for().to_string(),
+        remediation: os::instructions(permission, state).map(|instructions| Remediation {
+            instructions: instructions.to_string(),
+            deep_link: os::settings_link(permission).map(str::to_string),
+        }),
+    }
+}
+
+pub fn permission_report() -> PermissionReport {
+    let permissions: Vec<PermissionStatus> = Permission::ALL.into_iter().map(permission_status).collect();
+    PermissionReport {
+        os: std::env::consts::OS.to_string(),
+        ready: permissions.iter().all(|status| status.usable),
+        permissions,
+    }
+}
+
 /// Tauri command to check permissions from frontend
 #[tauri::command]
-pub async fn check_permissions() -> Result<bool, String> {
-    PermissionManager::check_audio_permissions()
-        .map_err(|e| e.to_string())
+#[specta::specta]
+pub async fn check_permissions() -> Response<PermissionReport> {
+    envelope::respond("check_permissions", async move {
+        tokio::task::spawn_blocking(permission_report)
+            .await
+            .map_err(|e| AppError::from(e.to_string()))
+    })
+    .await
+}
+
+/// Tauri command to request a permission (the microphone by default) from frontend. Depending on
+/// the OS this shows the system prompt, turns the service on, or opens the settings page.
+#[tauri::command]
+#[specta::specta]
+pub async fn request_permissions(permission: Option<Permission>) -> Response<PermissionStatus> {
+    envelope::respond("request_permissions", async move {
+        let permission = permission.unwrap_or(Permission::Microphone);
+        info!("Requesting {:?} permission...", permission);
+        tokio::task::spawn_blocking(move || {
+            os::request(permission)?;
+            Ok(permission_status(permission))
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    })
+    .await
 }
 
-/// Tauri command to request permissions from frontend
+/// Open the OS privacy settings page for `permission` (microphone by default)
 #[tauri::command]
-pub async fn request_permissions() -> Result<(), String> {
-    PermissionManager::request_microphone_permission()
-        .map_err(|e| e.to_string())
+#[specta::specta]
+pub async fn reopen_privacy_settings(permission: Option<String>) -> Response<()> {
+    envelope::respond("reopen_privacy_settings", async move {
+        let name = permission.as_deref().unwrap_or("microphone");
+        let page = match name {
+            "microphone" => os::settings_link(Permission::Microphone),
+            "camera" | "webcam" => os::CAMERA_SETTINGS,
+            "screen_capture" | "screen_recording" => os::settings_link(Permission::ScreenRecording),
+            "accessibility" => os::settings_link(Permission::Accessibility),
+            other => return Err(format!("Unknown permission: {}", other).into()),
+        }
+        .ok_or_else(|| format!("There is no {} settings page to open on {}", name, std::env::consts::OS))?;
+
+        info!("Opening privacy settings: {}", page);
+        Ok(open_settings_page(page).map_err(|e| format!("Failed to open privacy settings: {}", e))?)
+    })
+    .await
+}
+
+/// Open a settings URL (ms-settings: on Windows, x-apple.systempreferences: on macOS)
+fn open_settings_page(page: &str) -> Result<(), String> {
+    let output = if cfg!(windows) {
+        Command::new("cmd").args(["/C", "start", page]).output()
+    } else if cfg!(target_os = "macos") {
+        Command::new("open").arg(page).output()
+    } else {
+        Command::new("xdg-open").arg(page).output()
+    }
+    .map_err(|e| e.to_string())?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+#[cfg(windows)]
+use windows_privacy as os;
+#[cfg(target_os = "macos")]
+use macos_tcc as os;
+#[cfg(target_os = "linux")]
+use linux_services as os;
+#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+use ungated as os;
+
+/// Windows privacy settings, read from the CapabilityAccessManager consent store
+#[cfg(windows)]
+mod windows_privacy {
+    use super::{open_settings_page, Permission, PermissionState};
+    use log::warn;
+    use std::process::Command;
+
+    pub const CAMERA_SETTINGS: Option<&str> = Some("ms-settings:privacy-webcam");
+
+    fn consent(store: &str) -> Option<PermissionState> {
+        let key = format!(
+            "HKCU\\SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\CapabilityAccessManager\\ConsentStore\\{}",
+            store
+        );
+        match Command::new("reg").args(["query", &key, "/v", "Value"]).output() {
+            Ok(output) => {
+                let value = String::from_utf8_lossy(&output.stdout);
+                if value.contains("Allow") {
+                    Some(PermissionState::Granted)
+                } else if value.contains("Deny") {
+                    Some(PermissionState::Denied)
+                } else {
+                    None
+                }
+            }
+            Err(e) => {
+                warn!("Failed to read the {} consent store: {}", store, e);
+                None
+            }
+        }
+    }
+
+    pub fn state(permission: Permission) -> PermissionState {
+        match permission {
+            Permission::Microphone => consent("microphone").unwrap_or(PermissionState::NotDetermined),
+            // Only recent Windows 11 builds have the programmatic screen capture toggle
+            Permission::ScreenRecording => consent("graphicsCaptureProgrammatic").unwrap_or(PermissionState::NotRequired),
+            Permission::Accessibility => PermissionState::NotRequired,
+        }
+    }
+
+    pub fn settings_link(permission: Permission) -> Option<&'static str> {
+        match permission {
+            Permission::Microphone => Some("ms-settings:privacy-microphone"),
+            Permission::ScreenRecording => Some("ms-settings:privacy-graphicscaptureprogrammatic"),
+            Permission::Accessibility => None,
+        }
+    }
+
+    pub fn instructions(permission: Permission, state: PermissionState) -> Option<&'static str> {
+        match (permission, state) {
+            (Permission::Microphone, PermissionState::Denied | PermissionState::NotDetermined) => Some(
+                "Turn on Microphone access and Let desktop apps access your microphone in Settings > Privacy & security > Microphone",
+            ),
+            (Permission::ScreenRecording, PermissionState::Denied) => {
+                Some("Allow desktop apps to capture the screen in Settings > Privacy & security > Screenshots and apps")
+            }
+            _ => None,
+        }
+    }
+
+    /// Windows has no runtime prompt for desktop apps; open the settings page instead
+    pub fn request(permission: Permission) -> Result<(), String> {
+        match settings_link(permission) {
+            Some(page) => open_settings_page(page),
+            None => Ok(()),
+        }
+    }
+}
+
+/// TCC on macOS: microphone from AVCaptureDevice (through the Objective-C runtime), screen
+/// recording from CoreGraphics and accessibility from the AX trust check. Recording any input
+/// device, virtual loopback drivers included, needs microphone access.
+#[cfg(target_os = "macos")]
+mod macos_tcc {
+    use super::{open_settings_page, Permission, PermissionState};
+    use std::ffi::c_void;
+    use std::os::raw::c_char;
+
+    pub const CAMERA_SETTINGS: Option<&str> = Some("x-apple.systempreferences:com.apple.preference.security?Privacy_Camera");
+
+    #[link(name = "AVFoundation", kind = "framework")]
+    extern "C" {
+        static AVMediaTypeAudio: *const c_void;
+    }
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    extern "C" {
+        fn CGPreflightScreenCaptureAccess() -> bool;
+        fn CGRequestScreenCaptureAccess() -> bool;
+    }
+
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn AXIsProcessTrusted() -> u8;
+    }
+
+    #[link(name = "objc")]
+    extern "C" {
+        fn objc_getClass(name: *const c_char) -> *const c_void;
+        fn sel_registerName(name: *const c_char) -> *const c_void;
+        fn objc_msgSend();
+    }
+
+    fn microphone_state() -> PermissionState {
+        // +[AVCaptureDevice authorizationStatusForMediaType:] returns an NSInteger
+        type StatusForMediaType = unsafe extern "C" fn(*const c_void, *const c_void, *const c_void) -> isize;
+        unsafe {
+            let class = objc_getClass(c"AVCaptureDevice".as_ptr());
+            if class.is_null() {
+                return PermissionState::NotDetermined;
+            }
+            let selector = sel_registerName(c"authorizationStatusForMediaType:".as_ptr());
+            let send: StatusForMediaType = std::mem::transmute(objc_msgSend as unsafe extern "C" fn());
+            match send(class, selector, AVMediaTypeAudio) {
+                // Restricted (1) is a device policy the user cannot change either
+                1 | 2 => PermissionState::Denied,
+                3 => PermissionState::Granted,
+                _ => PermissionState::NotDetermined,
+            }
+        }
+    }
+
+    pub fn state(permission: Permission) -> PermissionState {
+        // The screen recording and accessibility checks cannot tell "denied" from "never asked"
+        match permission {
+            Permission::Microphone => microphone_state(),
+            Permission::ScreenRecording if unsafe { CGPreflightScreenCaptureAccess() } => PermissionState::Granted,
+            Permission::Accessibility if unsafe { AXIsProcessTrusted() } != 0 => PermissionState::Granted,
+            _ => PermissionState::Denied,
+        }
+    }
+
+    pub fn settings_link(permission: Permission) -> Option<&'static str> {
+        Some(match permission {
+            Permission::Microphone => "x-apple.systempreferences:com.apple.preference.security?Privacy_Microphone",
+            Permission::ScreenRecording => "x-apple.systempreferences:com.apple.preference.security?Privacy_ScreenCapture",
+            Permission::Accessibility => "x-apple.systempreferences:com.apple.preference.security?Privacy_Accessibility",
+        })
+    }
+
+    pub fn instructions(permission: Permission, state: PermissionState) -> Option<&'static str> {
+        match (permission, state) {
+            (Permission::Microphone, PermissionState::NotDetermined) => {
+                Some("macOS asks for microphone access the first time capture starts; choose Allow")
+            }
+            (Permission::Microphone, PermissionState::Denied) => {
+                Some("Allow MockMate in System Settings > Privacy & Security > Microphone")
+            }
+            (Permission::ScreenRecording, PermissionState::Denied) => {
+                Some("Allow MockMate in System Settings > Privacy & Security > Screen Recording, then restart MockMate")
+            }
+            (Permission::Accessibility, PermissionState::Denied) => {
+                Some("Allow MockMate in System Settings > Privacy & Security > Accessibility")
+            }
+            _ => None,
+        }
+    }
+
+    pub fn request(permission: Permission) -> Result<(), String> {
+        match permission {
+            // The system prompt appears when recording starts; once denied only System Settings can change it
+            Permission::Microphone if microphone_state() == PermissionState::NotDetermined => Ok(()),
+            // Prompts the first time and registers MockMate in the Screen Recording list
+            Permission::ScreenRecording if unsafe { CGRequestScreenCaptureAccess() } => Ok(()),
+            _ => open_settings_page(settings_link(permission).unwrap_or_default()),
+        }
+    }
+}
+
+/// Linux has no microphone gate outside sandboxes. Screen capture goes through the desktop portal
+/// (which asks on every capture) on Wayland, and reading other windows needs the AT-SPI bus on.
+#[cfg(target_os = "linux")]
+mod linux_services {
+    use super::{Permission, PermissionState};
+    use log::info;
+    use zbus::blocking::Connection;
+    use zbus::zvariant::{OwnedValue, Value};
+
+    pub const CAMERA_SETTINGS: Option<&str> = None;
+
+    const PROPERTIES: &str = "org.freedesktop.DBus.Properties";
+
+    fn property(bus: &Connection, destination: &str, path: &str, iface: &str, name: &str) -> Option<OwnedValue> {
+        bus.call_method(Some(destination), path, Some(PROPERTIES), "Get", &(iface, name))
+            .and_then(|reply| reply.body().deserialize::<OwnedValue>())
+            .ok()
+    }
+
+    fn accessibility_state() -> PermissionState {
+        let Ok(bus) = Connection::session() else {
+            return PermissionState::Unavailable;
+        };
+        match property(&bus, "org.a11y.Bus", "/org/a11y/bus", "org.a11y.Status", "IsEnabled").map(bool::try_from) {
+            Some(Ok(true)) => PermissionState::Granted,
+            Some(_) => PermissionState::NotDetermined,
+            None => PermissionState::Unavailable,
+        }
+    }
+
+    fn screen_recording_state() -> PermissionState {
+        let portal = Connection::session().ok().and_then(|bus| {
+            property(
+                &bus,
+                "org.freedesktop.portal.Desktop",
+                "/org/freedesktop/portal/desktop",
+                "org.freedesktop.portal.ScreenCast",
+                "version",
+            )
+        });
+        if portal.is_some() {
+            PermissionState::NotDetermined
+        } else if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            PermissionState::Unavailable
+        } else {
+            // Any X11 client can read the screen
+            PermissionState::NotRequired
+        }
+    }
+
+    pub fn state(permission: Permission) -> PermissionState {
+        match permission {
+            Permission::Microphone => PermissionState::NotRequired,
+            Permission::ScreenRecording => screen_recording_state(),
+            Permission::Accessibility => accessibility_state(),
+        }
+    }
+
+    pub fn settings_link(_permission: Permission) -> Option<&'static str> {
+        None
+    }
+
+    pub fn instructions(permission: Permission, state: PermissionState) -> Option<&'static str> {
+        match (permission, state) {
+            (Permission::ScreenRecording, PermissionState::NotDetermined) => {
+                Some("Your desktop asks which screen or window to share each time a capture starts")
+            }
+            (Permission::ScreenRecording, PermissionState::Unavailable) => Some(
+                "Install xdg-desktop-portal and the backend for your desktop (xdg-desktop-portal-gnome, -kde or -wlr)",
+            ),
+            (Permission::Accessibility, PermissionState::NotDetermined) => {
+                Some("Turn on assistive technologies in your desktop's accessibility settings, or request the permission to let MockMate turn them on")
+            }
+            (Permission::Accessibility, PermissionState::Unavailable) => Some("Install at-spi2-core and log in again"),
+            _ => None,
+        }
+    }
+
+    pub fn request(permission: Permission) -> Result<(), String> {
+        match (permission, state(permission)) {
+            (Permission::Accessibility, PermissionState::NotDetermined) => {
+                let bus = Connection::session().map_err(|e| e.to_string())?;
+                bus.call_method(
+                    Some("org.a11y.Bus"),
+                    "/org/a11y/bus",
+                    Some(PROPERTIES),
+                    "Set",
+                    &("org.a11y.Status", "IsEnabled", Value::from(true)),
+                )
+                .map_err(|e| format!("Failed to turn on the AT-SPI bus: {}", e))?;
+                info!("♿ Turned on assistive technology support (AT-SPI)");
+                Ok(())
+            }
+            (_, PermissionState::Unavailable) => Err(instructions(permission, PermissionState::Unavailable)
+                .unwrap_or("This permission is not available on this desktop")
+                .to_string()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Platforms without permission gates MockMate knows about
+#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+mod ungated {
+    use super::{Permission, PermissionState};
+
+    pub const CAMERA_SETTINGS: Option<&str> = None;
+
+    pub fn state(_permission: Permission) -> PermissionState {
+        PermissionState::NotRequired
+    }
+
+    pub fn settings_link(_permission: Permission) -> Option<&'static str> {
+        None
+    }
+
+    pub fn instructions(_permission: Permission, _state: PermissionState) -> Option<&'static str> {
+        None
+    }
+
+    pub fn request(_permission: Permission) -> Result<(), String> {
+        Ok(())
+    }
 }
 
 /// Tauri command to initialize first run permissions
 #[tauri::command]
-pub async fn initialize_first_run() -> Result<(), String> {
-    PermissionManager::initialize_permissions_on_first_run()
-        .map_err(|e| e.to_string())
+#[specta::specta]
+pub async fn initialize_first_run() -> Response<()> {
+    envelope::respond("initialize_first_run", async move {
+        PermissionManager::initialize_permissions_on_first_run()
+            .map_err(|e| AppError::from(e.to_string()))
+    })
+    .await
 }
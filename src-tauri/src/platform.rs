@@ -0,0 +1,147 @@
+// Per-OS feature availability
+// Much of the capture and window work is built on Win32/WASAPI/WinRT. On other platforms those
+// modules are replaced by native backends where one exists (PulseAudio and AT-SPI on Linux, a
+// loopback device on macOS) or by stubs that fail with a clear message instead of not compiling,
+// and the frontend asks `get_platform_capabilities` up front so it can hide or explain what is
+// missing.
+
+use serde::Serialize;
+use specta::Type;
+use crate::envelope::{self, Response};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Capability {
+    /// Loopback capture of what the speakers play
+    SystemAudioCapture,
+    MicrophoneCapture,
+    /// Reading question text out of other applications' windows
+    AccessibilityReader,
+    /// Enabling the Stereo Mix recording device
+    StereoMix,
+    /// Hiding MockMate's windows from screen capture and screen sharing
+    CaptureProtection,
+    Screenshot,
+    SessionRecording,
+    /// Task Manager / taskbar hiding
+    Stealth,
+    /// OS credential store for integration tokens
+    CredentialStore,
+}
+
+impl Capability {
+    pub const ALL: [Capability; 9] = [
+        Capability::SystemAudioCapture,
+        Capability::MicrophoneCapture,
+        Capability::AccessibilityReader,
+        Capability::StereoMix,
+        Capability::CaptureProtection,
+        Capability::Screenshot,
+        Capability::SessionRecording,
+        Capability::Stealth,
+        Capability::CredentialStore,
+    ];
+
+    pub fn available(self) -> bool {
+        if cfg!(windows) {
+            return true;
+        }
+        if cfg!(target_os = "linux") {
+            return matches!(
+                self,
+                Capability::SystemAudioCapture
+                    | Capability::MicrophoneCapture
+                    | Capability::AccessibilityReader
+                    | Capability::CredentialStore
+            );
+        }
+        cfg!(target_os = "macos")
+            && matches!(
+                self,
+                Capability::SystemAudioCapture | Capability::CaptureProtection | Capability::CredentialStore
+            )
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Capability::SystemAudioCapture => "System audio capture",
+            Capability::MicrophoneCapture => "Microphone capture",
+            Capability::AccessibilityReader => "Reading text from other applications",
+            Capability::StereoMix => "Stereo Mix",
+            Capability::CaptureProtection => "Screen capture protection",
+            Capability::Screenshot => "Screenshots",
+            Capability::SessionRecording => "Session recording",
+            Capability::Stealth => "Stealth mode",
+            Capability::CredentialStore => "Secure credential storage",
+        }
+    }
+
+    /// What the user can do instead when the feature is missing
+    fn fallback(self) -> Option<&'static str> {
+        if self.available() {
+            return None;
+        }
+        match self {
+            Capability::SystemAudioCapture
+            | Capability::MicrophoneCapture
+            | Capability::AccessibilityReader
+            | Capability::Screenshot => Some("Type or paste the question into MockMate instead"),
+            Capability::CaptureProtection => Some("Share a single application window rather than the whole screen"),
+            Capability::CredentialStore => Some("Integrations that need a stored token cannot be connected"),
+            Capability::StereoMix | Capability::SessionRecording | Capability::Stealth => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct FeatureSupport {
+    pub capability: Capability,
+    pub available: bool,
+    pub fallback: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct PlatformCapabilities {
+    /// `std::env::consts::OS`, e.g. "windows", "macos", "linux"
+    pub os: String,
+    pub arch: String,
+    pub features: Vec<FeatureSupport>,
+}
+
+/// Fail with a user-facing message when `capability` does not exist on this OS
+pub fn ensure_available(capability: Capability) -> Result<(), String> {
+    if capability.available() {
+        Ok(())
+    } else {
+        Err(unavailable(capability))
+    }
+}
+
+/// The message used for a feature that does not exist on this OS
+pub fn unavailable(capability: Capability) -> String {
+    format!("{} is not available on {}", capability.label(), std::env::consts::OS)
+}
+
+pub fn capabilities() -> PlatformCapabilities {
+    PlatformCapabilities {
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        features: Capability::ALL
+            .iter()
+            .map(|&capability| FeatureSupport {
+                capability,
+                available: capability.available(),
+                fallback: capability.fallback().map(str::to_string),
+            })
+            .collect(),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_platform_capabilities() -> Response<PlatformCapabilities> {
+    envelope::respond("get_platform_capabilities", async move {
+        Ok(capabilities())
+    })
+    .await
+}
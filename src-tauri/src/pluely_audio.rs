@@ -8,13 +8,16 @@ use std::collections::VecDeque;
 use std::sync::{mpsc, Arc, Mutex};
 use std::task::{Poll, Waker};
 use std::thread;
-use wasapi::{get_default_device, Direction, SampleType, StreamMode, WaveFormat};
 use std::time::Duration;
-use log::{info, error, warn, debug};
+use log::{info, error, debug};
+#[cfg(windows)]
+use log::warn;
 use tauri::{AppHandle, Emitter};
 use hound::{WavSpec, WavWriter};
 use base64::{Engine as _, engine::general_purpose::STANDARD as B64};
 use std::io::Cursor;
+use crate::envelope::{self, IntoResult, Response};
+use crate::events;
 
 // Ultra-low latency constants optimized for real-time interview AI transcription
 const HOP_SIZE: usize = 512;               // Analysis chunk size (~11.6ms at 44.1kHz) - HALF for ultra-low latency
@@ -29,12 +32,13 @@ pub struct PluelySpeakerInput {}
 
 impl PluelySpeakerInput {
     pub fn new() -> Result<Self> {
+        crate::platform::ensure_available(crate::platform::Capability::SystemAudioCapture).map_err(anyhow::Error::msg)?;
         Ok(Self {})
     }
 
     /// Start the audio stream - returns a Pluely-style speaker stream
     pub fn stream(self) -> PluelySpeakerStream {
-        let sample_queue = Arc::new(Mutex::new(VecDeque::new()));
+        let sample_queue = Arc::new(Mutex::new(VecDeque::with_capacity(crate::audio_thread::QUEUE_CAPACITY_SAMPLES)));
         let waker_state = Arc::new(Mutex::new(WakerState {
             waker: None,
             has_data: false,
@@ -45,11 +49,13 @@ impl PluelySpeakerInput {
         let queue_clone = sample_queue.clone();
         let waker_clone = waker_state.clone();
 
-        let capture_thread = thread::spawn(move || {
+        let capture_thread = crate::audio_thread::spawn_capture_thread("system-audio-capture", move || {
             if let Err(e) = PluelySpeakerStream::capture_audio_loop(queue_clone, waker_clone, init_tx) {
                 error!("Pluely Audio capture loop failed: {}", e);
             }
-        });
+        })
+        .map_err(|e| error!("Failed to spawn system audio capture thread: {}", e))
+        .ok();
 
         // Wait for initialization with timeout
         if let Ok(Err(e)) = init_rx.recv_timeout(Duration::from_secs(5)) {
@@ -59,7 +65,7 @@ impl PluelySpeakerInput {
         PluelySpeakerStream {
             sample_queue,
             waker_state,
-            capture_thread: Some(capture_thread),
+            capture_thread,
         }
     }
 }
@@ -84,6 +90,7 @@ impl PluelySpeakerStream {
         44100
     }
 
+    #[cfg(windows)]
     /// Main audio capture loop using direct WASAPI - based on Pluely's implementation
     fn capture_audio_loop(
         sample_queue: Arc<Mutex<VecDeque<f32>>>,
@@ -92,38 +99,15 @@ impl PluelySpeakerStream {
     ) -> Result<()> {
         info!("🎵 Starting Pluely-style WASAPI capture loop...");
 
-        let init_result = (|| -> Result<_> {
-            // Get default render device for loopback capture
-            let device = get_default_device(&Direction::Render)?;
-            let mut audio_client = device.get_iaudioclient()?;
-
-            // Use Pluely's exact format configuration
-            let desired_format = WaveFormat::new(32, 32, &SampleType::Float, 44100, 1, None);
-
-            let (_def_time, min_time) = audio_client.get_device_period()?;
-
-            let mode = StreamMode::EventsShared {
-                autoconvert: true,
-                buffer_duration_hns: min_time,
-            };
-
-            // Initialize in capture mode for loopback
-            audio_client.initialize_client(&desired_format, &Direction::Capture, &mode)?;
-
-            let h_event = audio_client.set_get_eventhandle()?;
-            let render_client = audio_client.get_audiocaptureclient()?;
-
-            audio_client.start_stream()?;
-            info!("✅ Pluely-style WASAPI capture initialized successfully");
-
-            Ok((h_event, render_client))
-        })();
-
-        match init_result {
-            Ok((h_event, render_client)) => {
+        match crate::stereo_mix_manager::open_system_audio_stream() {
+            Ok((h_event, render_client, source)) => {
+                info!("✅ Pluely-style WASAPI capture initialized successfully ({:?})", source);
                 let _ = init_tx.send(Ok(()));
 
                 info!("🎵 Pluely audio capture loop running...");
+                // Allocated once; the loop only clears and refills them
+                let mut temp_queue: VecDeque<u8> = VecDeque::with_capacity(crate::audio_thread::PACKET_BUFFER_BYTES);
+                let mut samples: Vec<f32> = Vec::with_capacity(crate::audio_thread::PACKET_BUFFER_BYTES / 4);
                 loop {
                     // Check shutdown signal
                     {
@@ -140,7 +124,7 @@ impl PluelySpeakerStream {
                     }
 
                     // Read audio data from device
-                    let mut temp_queue = VecDeque::new();
+                    temp_queue.clear();
                     if let Err(e) = render_client.read_from_device_to_deque(&mut temp_queue) {
                         warn!("Pluely failed to read audio data: {}", e);
                         continue;
@@ -150,24 +134,17 @@ impl PluelySpeakerStream {
                         continue;
                     }
 
-                    // Convert raw bytes to f32 samples (Pluely's method)
-                    let mut samples = Vec::new();
-                    while temp_queue.len() >= 4 {
-                        let bytes = [
-                            temp_queue.pop_front().unwrap(),
-                            temp_queue.pop_front().unwrap(),
-                            temp_queue.pop_front().unwrap(),
-                            temp_queue.pop_front().unwrap(),
-                        ];
-                        let sample = f32::from_le_bytes(bytes);
-                        samples.push(sample);
-                    }
+                    // Convert raw bytes to f32 samples in one pass over the contiguous buffer
+                    samples.clear();
+                    crate::audio_buffer::extend_f32_from_le_bytes(temp_queue.make_contiguous(), &mut samples);
 
                     if !samples.is_empty() {
+                        crate::audio_debug::tap("system", crate::audio_debug::Stage::Raw, &samples, 44100);
+
                         // Add samples to queue with buffer management
                         {
                             let mut queue = sample_queue.lock().unwrap();
-                            queue.extend(samples);
+                            queue.extend(samples.iter().copied());
 
                             // Keep buffer size reasonable (like Pluely - 8192 samples)
                             let len = queue.len();
@@ -199,6 +176,145 @@ impl PluelySpeakerStream {
         info!("🛑 Pluely audio capture loop ended");
         Ok(())
     }
+
+    /// Capture loop on Linux: loopback from the default PulseAudio/PipeWire sink monitor
+    #[cfg(target_os = "linux")]
+    fn capture_audio_loop(
+        sample_queue: Arc<Mutex<VecDeque<f32>>>,
+        waker_state: Arc<Mutex<WakerState>>,
+        init_tx: mpsc::Sender<Result<()>>,
+    ) -> Result<()> {
+        use crate::pulse_capture::{PulseCapture, PulseSource};
+
+        let mut capture = match PulseCapture::start(PulseSource::Monitor) {
+            Ok(capture) => capture,
+            Err(e) => {
+                let _ = init_tx.send(Err(e));
+                return Ok(());
+            }
+        };
+        let _ = init_tx.send(Ok(()));
+
+        info!("🎵 Pluely audio capture loop running (PulseAudio monitor)...");
+        let mut buffer = vec![0u8; crate::audio_thread::PACKET_BUFFER_BYTES];
+        let mut samples: Vec<f32> = Vec::with_capacity(crate::audio_thread::PACKET_BUFFER_BYTES / 4);
+        loop {
+            if waker_state.lock().unwrap().shutdown {
+                break;
+            }
+
+            samples.clear();
+            let ended = match capture.read(&mut buffer, &mut samples) {
+                Ok(0) => Some("parec stopped".to_string()),
+                Ok(_) => None,
+                Err(e) => Some(e.to_string()),
+            };
+            if let Some(reason) = ended {
+                // End the stream rather than leaving the consumer waiting on a dead capture
+                error!("❌ System audio capture ended: {}", reason);
+                let mut state = waker_state.lock().unwrap();
+                state.shutdown = true;
+                if let Some(waker) = state.waker.take() {
+                    drop(state);
+                    waker.wake();
+                }
+                break;
+            }
+            if samples.is_empty() {
+                continue;
+            }
+
+            crate::audio_debug::tap("system", crate::audio_debug::Stage::Raw, &samples, 44100);
+            {
+                let mut queue = sample_queue.lock().unwrap();
+                queue.extend(samples.iter().copied());
+                let len = queue.len();
+                if len > 8192 {
+                    queue.drain(0..(len - 8192));
+                }
+            }
+            let mut state = waker_state.lock().unwrap();
+            if !state.has_data {
+                state.has_data = true;
+                if let Some(waker) = state.waker.take() {
+                    drop(state);
+                    waker.wake();
+                }
+            }
+        }
+
+        info!("🛑 Pluely audio capture loop ended");
+        Ok(())
+    }
+
+    /// Capture loop on macOS: records a virtual loopback device (BlackHole) through an AudioQueue
+    #[cfg(target_os = "macos")]
+    fn capture_audio_loop(
+        sample_queue: Arc<Mutex<VecDeque<f32>>>,
+        waker_state: Arc<Mutex<WakerState>>,
+        init_tx: mpsc::Sender<Result<()>>,
+    ) -> Result<()> {
+        use crate::macos_loopback::{find_loopback_device, LoopbackCapture, SETUP_GUIDANCE};
+
+        let started = (|| -> Result<LoopbackCapture> {
+            if !crate::permissions::PermissionManager::check_microphone_permission()? {
+                return Err(anyhow::anyhow!(
+                    "Microphone access is off for MockMate, and macOS needs it to record the loopback device. \
+                     Allow MockMate in System Settings > Privacy & Security > Microphone"
+                ));
+            }
+            let device = find_loopback_device()?
+                .ok_or_else(|| anyhow::anyhow!("No loopback audio device found. {}", SETUP_GUIDANCE))?;
+            let (queue, waker) = (sample_queue.clone(), waker_state.clone());
+            LoopbackCapture::start(&device, move |samples| {
+                crate::audio_debug::tap("system", crate::audio_debug::Stage::Raw, samples, 44100);
+                {
+                    let mut queue = queue.lock().unwrap();
+                    queue.extend(samples.iter().copied());
+                    let len = queue.len();
+                    if len > 8192 {
+                        queue.drain(0..(len - 8192));
+                    }
+                }
+                let mut state = waker.lock().unwrap();
+                if !state.has_data {
+                    state.has_data = true;
+                    if let Some(waker) = state.waker.take() {
+                        drop(state);
+                        waker.wake();
+                    }
+                }
+            })
+        })();
+        let _capture = match started {
+            Ok(capture) => capture,
+            Err(e) => {
+                let _ = init_tx.send(Err(e));
+                return Ok(());
+            }
+        };
+        let _ = init_tx.send(Ok(()));
+
+        // Audio arrives on the AudioQueue's thread; this one only keeps the queue alive until shutdown
+        info!("🎵 Pluely audio capture loop running (loopback device)...");
+        while !waker_state.lock().unwrap().shutdown {
+            thread::sleep(Duration::from_millis(50));
+        }
+
+        info!("🛑 Pluely audio capture loop ended");
+        Ok(())
+    }
+
+    /// No capture backend on this platform; `new` refuses first, so this only reports the reason
+    #[cfg(not(any(windows, target_os = "linux", target_os = "macos")))]
+    fn capture_audio_loop(
+        _sample_queue: Arc<Mutex<VecDeque<f32>>>,
+        _waker_state: Arc<Mutex<WakerState>>,
+        init_tx: mpsc::Sender<Result<()>>,
+    ) -> Result<()> {
+        let _ = init_tx.send(Err(anyhow::anyhow!(crate::platform::unavailable(crate::platform::Capability::SystemAudioCapture))));
+        Ok(())
+    }
 }
 
 /// Clean shutdown for the speaker stream
@@ -275,6 +391,7 @@ pub struct PluelyAudioProcessor {
     in_speech: bool,
     silence_chunks: usize,
     speech_chunks: usize,
+    level_counter: usize,
     sample_rate: u32,
 }
 
@@ -291,6 +408,7 @@ impl PluelyAudioProcessor {
             in_speech: false,
             silence_chunks: 0,
             speech_chunks: 0,
+            level_counter: 0,
             sample_rate: 44100,
         }
     }
@@ -305,7 +423,7 @@ pub async fn start_capture_with_transcription(&mut self) -> Result<()> {
         self.sample_rate = sr;
 
         // Emit debug: capture initialized
-        let _ = self.app_handle.emit("pluely-audio-debug", serde_json::json!({
+        let _ = self.app_handle.emit(events::PLUELY_AUDIO_DEBUG, serde_json::json!({
             "event": "capture-initialized",
             "sample_rate": sr,
             "hop_size": HOP_SIZE,
@@ -346,26 +464,20 @@ pub async fn start_capture_with_transcription(&mut self) -> Result<()> {
         if self.streaming_enabled {
             self.streaming_buffer.push(sample);
             
-            // Send streaming chunks every 2048 samples (~46ms at 44.1kHz for low latency)
-            const STREAMING_CHUNK_SIZE: usize = 2048;
-            if self.streaming_buffer.len() >= STREAMING_CHUNK_SIZE {
-                if let Ok(b64_chunk) = self.samples_to_wav_b64(&self.streaming_buffer) {
-                    // Emit real-time audio chunk for Deepgram streaming
-                    let _ = self.app_handle.emit("audio-chunk", b64_chunk);
-                }
+            // Chunk size comes from the transcription preset (2048 samples, ~46ms at 44.1kHz, by default)
+            if self.streaming_buffer.len() >= crate::transcription_preset::chunk_samples() {
+                let chunk: Arc<[f32]> = Arc::from(self.streaming_buffer.as_slice());
                 self.streaming_buffer.clear();
+                crate::audio_debug::tap("system", crate::audio_debug::Stage::Processed, &chunk, self.sample_rate);
+                // Backend consumers (Deepgram) share the samples directly
+                crate::audio_buffer::publish(crate::audio_buffer::AudioSource::System, chunk.clone(), self.sample_rate);
+                if let Ok(b64_chunk) = self.samples_to_wav_b64(&chunk) {
+                    // Emit real-time audio chunk for frontend listeners
+                    let _ = self.app_handle.emit(events::AUDIO_CHUNK, b64_chunk);
+                }
             }
         }
         
-        // Debug logging every 100,000 samples (about every 2.3 seconds at 44.1kHz)
-        static mut SAMPLE_COUNTER: usize = 0;
-        unsafe {
-            SAMPLE_COUNTER += 1;
-            if SAMPLE_COUNTER % 100000 == 0 {
-                info!("🎵 Audio samples processed: {} (sample value: {:.6})", SAMPLE_COUNTER, sample);
-            }
-        }
-
         // Process in chunks of HOP_SIZE (Pluely's method)
         while self.sample_buffer.len() >= HOP_SIZE {
             let mut chunk = Vec::with_capacity(HOP_SIZE);
@@ -378,24 +490,17 @@ pub async fn start_capture_with_transcription(&mut self) -> Result<()> {
             let (rms, peak) = Self::process_chunk(&chunk);
             let is_speech = rms > VAD_SENSITIVITY_RMS || peak > SPEECH_PEAK_THRESHOLD;
             
-            // Debug logging every 100 chunks (about every 2.3 seconds)
-            static mut DEBUG_COUNTER: usize = 0;
-            unsafe {
-                DEBUG_COUNTER += 1;
-                if DEBUG_COUNTER % 100 == 0 {
-                info!("📊 Audio activity: RMS={:.6}, Peak={:.6}, Speech={}, Threshold RMS={:.6}, Threshold Peak={:.6}", 
-                      rms, peak, is_speech, VAD_SENSITIVITY_RMS, SPEECH_PEAK_THRESHOLD);
-                
-                // Emit audio level update for UI visualization
-                if true {
-                    let _ = self.app_handle.emit("audio-level", serde_json::json!({
-                        "rms": rms,
-                        "peak": peak,
-                        "speech": is_speech,
-                        "timestamp": chrono::Utc::now().timestamp_millis()
-                    }));
-                }
-                }
+            // Emit an audio level update for UI visualization every 100 chunks (~1.2s)
+            self.level_counter += 1;
+            if self.level_counter >= 100 {
+                self.level_counter = 0;
+                debug!("Audio activity: RMS={:.6}, Peak={:.6}, Speech={}", rms, peak, is_speech);
+                let _ = self.app_handle.emit(events::AUDIO_LEVEL, events::AudioLevel {
+                    rms,
+                    peak,
+                    speech: is_speech,
+                    timestamp: chrono::Utc::now().timestamp_millis(),
+                });
             }
 
             if is_speech {
@@ -409,7 +514,7 @@ pub async fn start_capture_with_transcription(&mut self) -> Result<()> {
                     self.speech_buffer.extend(self.pre_speech_buffer.drain(..));
                     
                     // Emit speech start event
-                    let _ = self.app_handle.emit("speech-start", ()).map_err(|e| {
+                    let _ = self.app_handle.emit(events::SPEECH_START, ()).map_err(|e| {
                         error!("Failed to emit speech-start: {}", e);
                     });
                     
@@ -423,7 +528,7 @@ pub async fn start_capture_with_transcription(&mut self) -> Result<()> {
                 let max_samples = self.sample_rate as usize * 30;
                 if self.speech_buffer.len() > max_samples {
                     if let Ok(b64) = self.samples_to_wav_b64(&self.speech_buffer) {
-                        let _ = self.app_handle.emit("speech-detected", b64);
+                        let _ = self.app_handle.emit(events::SPEECH_DETECTED, b64);
                         info!("🎵 Emitted speech segment (safety cap): {} samples", self.speech_buffer.len());
                     }
                     self.speech_buffer.clear();
@@ -434,11 +539,6 @@ pub async fn start_capture_with_transcription(&mut self) -> Result<()> {
                     self.silence_chunks += 1;
                     self.speech_buffer.extend_from_slice(&chunk);
                     
-                    // Debug log silence accumulation
-                    if self.silence_chunks % 10 == 0 {
-                        info!("🔇 Silence detected: {} chunks (need {} to complete)", self.silence_chunks, SILENCE_CHUNKS);
-                    }
-                    
                     // Check if we have enough silence to end speech
                     if self.silence_chunks >= SILENCE_CHUNKS {
                         if self.speech_chunks >= MIN_SPEECH_CHUNKS && !self.speech_buffer.is_empty() {
@@ -450,7 +550,7 @@ pub async fn start_capture_with_transcription(&mut self) -> Result<()> {
                             
                             // Convert to WAV and emit
                             if let Ok(b64) = self.samples_to_wav_b64(&self.speech_buffer) {
-                                let _ = self.app_handle.emit("speech-detected", b64);
+                                let _ = self.app_handle.emit(events::SPEECH_DETECTED, b64);
                                 info!("🎵 Emitted speech segment: {} samples ({:.2}s)", 
                                       self.speech_buffer.len(), 
                                       self.speech_buffer.len() as f32 / self.sample_rate as f32);
@@ -527,131 +627,171 @@ fn get_stop_flag() -> Arc<std::sync::atomic::AtomicBool> {
     AUDIO_STOP_FLAG.get_or_init(|| Arc::new(std::sync::atomic::AtomicBool::new(false))).clone()
 }
 
+/// Capture/VAD configuration, used by the audio debug dump
+pub fn pipeline_config() -> serde_json::Value {
+    serde_json::json!({
+        "source": match crate::stereo_mix_manager::current_source() {
+            Some(crate::stereo_mix_manager::SystemAudioSource::StereoMix) => "stereo mix capture device (loopback failed)",
+            _ => "default render device (loopback)",
+        },
+        "capture_format": "f32 mono 44100Hz (WASAPI autoconvert)",
+        "hop_size": HOP_SIZE,
+        "vad_sensitivity_rms": VAD_SENSITIVITY_RMS,
+        "speech_peak_threshold": SPEECH_PEAK_THRESHOLD,
+        "silence_chunks": SILENCE_CHUNKS,
+        "min_speech_chunks": MIN_SPEECH_CHUNKS,
+        "pre_speech_chunks": PRE_SPEECH_CHUNKS,
+        "streaming_chunk_size": 2048,
+        "streaming_event": "audio-chunk",
+    })
+}
+
 /// Tauri command to start Pluely-style system audio capture
 #[tauri::command]
-pub async fn start_pluely_system_audio_capture(app: AppHandle) -> Result<(), String> {
-    info!("🚀 Starting Pluely-style system audio capture...");
-
-    // Emit debug event to UI
-    let _ = app.emit("pluely-audio-debug", serde_json::json!({
-        "event": "start-requested",
-        "timestamp": std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.as_millis())
-            .unwrap_or(0)
-    }));
+#[specta::specta]
+pub async fn start_pluely_system_audio_capture(app: AppHandle) -> Response<()> {
+    envelope::respond("start_pluely_system_audio_capture", async move {
+        info!("🚀 Starting Pluely-style system audio capture...");
+        crate::telemetry::record_feature("system_audio_capture");
+
+        // Emit debug event to UI
+        let _ = app.emit(events::PLUELY_AUDIO_DEBUG, serde_json::json!({
+            "event": "start-requested",
+            "timestamp": std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_millis())
+                .unwrap_or(0)
+        }));
     
-    let processor_arc = get_audio_processor();
+        let processor_arc = get_audio_processor();
     
-    // Stop existing processor if running (scope the MutexGuard)
-    {
-        let mut processor_guard = processor_arc.lock().unwrap();
-        if processor_guard.is_some() {
-            info!("Stopping existing audio processor...");
-            *processor_guard = None;
+        // Stop existing processor if running (scope the MutexGuard)
+        {
+            let mut processor_guard = processor_arc.lock().unwrap();
+            if processor_guard.is_some() {
+                info!("Stopping existing audio processor...");
+                *processor_guard = None;
+            }
         }
-    }
     
-    // Create new processor
-    let mut processor = PluelyAudioProcessor::new(app.clone());
+        // Create new processor
+        let mut processor = PluelyAudioProcessor::new(app.clone());
+    
+        // Start capture with transcription
+        if let Err(e) = processor.start_capture_with_transcription().await.map_err(|e| e.to_string()) {
+            crate::telemetry::record_error("audio_capture_failed");
+            let _ = app.emit(events::PLUELY_AUDIO_DEBUG, serde_json::json!({
+                "event": "start-error",
+                "error": e,
+                "timestamp": std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_millis())
+                    .unwrap_or(0)
+            }));
+            return Err(e.into());
+        }
     
-    // Start capture with transcription
-    if let Err(e) = processor.start_capture_with_transcription().await.map_err(|e| e.to_string()) {
-        let _ = app.emit("pluely-audio-debug", serde_json::json!({
-            "event": "start-error",
-            "error": e,
+        // Store the processor (scope the MutexGuard)
+        {
+            let mut processor_guard = processor_arc.lock().unwrap();
+            *processor_guard = Some(processor);
+        }
+
+        // Emit started event
+        let _ = app.emit(events::PLUELY_AUDIO_DEBUG, serde_json::json!({
+            "event": "started",
             "timestamp": std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .map(|d| d.as_millis())
                 .unwrap_or(0)
         }));
-        return Err(e);
-    }
     
-    // Store the processor (scope the MutexGuard)
-    {
-        let mut processor_guard = processor_arc.lock().unwrap();
-        *processor_guard = Some(processor);
-    }
-
-    // Emit started event
-    let _ = app.emit("pluely-audio-debug", serde_json::json!({
-        "event": "started",
-        "timestamp": std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.as_millis())
-            .unwrap_or(0)
-    }));
-    
-    info!("✅ Pluely-style system audio capture started successfully");
-    Ok(())
+        info!("✅ Pluely-style system audio capture started successfully");
+        Ok(())
+    })
+    .await
 }
 
 /// Tauri command to stop system audio capture
 #[tauri::command]
-pub async fn stop_pluely_system_audio_capture(app: AppHandle) -> Result<(), String> {
-    info!("🛑 Stopping Pluely-style system audio capture...");
-
-    // Emit debug event to UI
-    let _ = app.emit("pluely-audio-debug", serde_json::json!({
-        "event": "stop-requested",
-        "timestamp": std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.as_millis())
-            .unwrap_or(0)
-    }));
+#[specta::specta]
+pub async fn stop_pluely_system_audio_capture(app: AppHandle) -> Response<()> {
+    envelope::respond("stop_pluely_system_audio_capture", async move {
+        info!("🛑 Stopping Pluely-style system audio capture...");
+
+        // Emit debug event to UI
+        let _ = app.emit(events::PLUELY_AUDIO_DEBUG, serde_json::json!({
+            "event": "stop-requested",
+            "timestamp": std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_millis())
+                .unwrap_or(0)
+        }));
     
-    // Set the stop flag to signal the capture task to stop
-    let stop_flag = get_stop_flag();
-    stop_flag.store(true, std::sync::atomic::Ordering::Relaxed);
-    info!("🛑 Stop flag set to true");
+        // Set the stop flag to signal the capture task to stop
+        let stop_flag = get_stop_flag();
+        stop_flag.store(true, std::sync::atomic::Ordering::Relaxed);
+        info!("🛑 Stop flag set to true");
     
-    // Wait a bit for the task to stop
-    tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+        // Wait a bit for the task to stop
+        tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
     
-    let processor_arc = get_audio_processor();
-    let mut processor_guard = processor_arc.lock().unwrap();
+        let processor_arc = get_audio_processor();
+        let mut processor_guard = processor_arc.lock().unwrap();
     
-    *processor_guard = None;
-
-    // Emit stopped event
-    let _ = app.emit("pluely-audio-debug", serde_json::json!({
-        "event": "stopped",
-        "timestamp": std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.as_millis())
-            .unwrap_or(0)
-    }));
+        *processor_guard = None;
+        drop(processor_guard);
+        // Saving ends a recording before this, so what is left was never saved
+        crate::audio_wasapi::discard_capture_recording();
+
+        // Emit stopped event
+        let _ = app.emit(events::PLUELY_AUDIO_DEBUG, serde_json::json!({
+            "event": "stopped",
+            "timestamp": std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_millis())
+                .unwrap_or(0)
+        }));
     
-    info!("✅ Pluely-style system audio capture stopped");
-    Ok(())
+        info!("✅ Pluely-style system audio capture stopped");
+        Ok(())
+    })
+    .await
 }
 
 /// Check if system audio capture is active
 #[tauri::command]
-pub async fn is_pluely_audio_active() -> Result<bool, String> {
-    let processor_arc = get_audio_processor();
-    let processor_guard = processor_arc.lock().unwrap();
-    Ok(processor_guard.is_some())
+#[specta::specta]
+pub async fn is_pluely_audio_active() -> Response<bool> {
+    envelope::respond("is_pluely_audio_active", async move {
+        let processor_arc = get_audio_processor();
+        let processor_guard = processor_arc.lock().unwrap();
+        Ok(processor_guard.is_some())
+    })
+    .await
 }
 
 /// Test command to verify Pluely system audio capture works
 #[tauri::command]
-pub async fn test_pluely_system_audio_capture(app: AppHandle, duration_seconds: u64) -> Result<String, String> {
-    info!("🧑‍🔬 Testing Pluely system audio capture for {} seconds", duration_seconds);
+#[specta::specta]
+pub async fn test_pluely_system_audio_capture(app: AppHandle, duration_seconds: u64) -> Response<String> {
+    envelope::respond("test_pluely_system_audio_capture", async move {
+        info!("🧑‍🔬 Testing Pluely system audio capture for {} seconds", duration_seconds);
     
-    // Start capture
-    if let Err(e) = start_pluely_system_audio_capture(app.clone()).await {
-        return Err(format!("Failed to start capture: {}", e));
-    }
+        // Start capture
+        if let Err(e) = start_pluely_system_audio_capture(app.clone()).await.into_result() {
+            return Err(format!("Failed to start capture: {}", e).into());
+        }
     
-    info!("Capture started, waiting {} seconds...", duration_seconds);
-    tokio::time::sleep(tokio::time::Duration::from_secs(duration_seconds)).await;
+        info!("Capture started, waiting {} seconds...", duration_seconds);
+        tokio::time::sleep(tokio::time::Duration::from_secs(duration_seconds)).await;
     
-    // Stop capture
-    if let Err(e) = stop_pluely_system_audio_capture(app.clone()).await {
-        return Err(format!("Failed to stop capture: {}", e));
-    }
+        // Stop capture
+        if let Err(e) = stop_pluely_system_audio_capture(app.clone()).await.into_result() {
+            return Err(format!("Failed to stop capture: {}", e).into());
+        }
     
-    Ok(format!("Pluely system audio capture test completed successfully for {} seconds", duration_seconds))
+        Ok(format!("Pluely system audio capture test completed successfully for {} seconds", duration_seconds))
+    })
+    .await
 }
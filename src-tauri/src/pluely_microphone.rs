@@ -8,13 +8,20 @@ use std::collections::VecDeque;
 use std::sync::{mpsc, Arc, Mutex};
 use std::task::{Poll, Waker};
 use std::thread;
-use wasapi::{get_default_device, Direction, SampleType, StreamMode, WaveFormat};
+#[cfg(windows)]
+use wasapi::Direction;
 use std::time::Duration;
-use log::{info, error, warn, debug};
+use log::{info, error};
+#[cfg(windows)]
+use log::{debug, warn};
 use tauri::{AppHandle, Emitter};
 use hound::{WavSpec, WavWriter};
 use base64::{Engine as _, engine::general_purpose::STANDARD as B64};
 use std::io::Cursor;
+use crate::envelope::{self, Response};
+use crate::guard::{self, Require};
+use crate::permissions::Permission;
+use crate::events;
 
 // Ultra-low latency constants optimized for real-time interview AI microphone transcription
 const HOP_SIZE: usize = 512;               // Analysis chunk size (~11.6ms at 44.1kHz) - HALF for ultra-low latency
@@ -24,17 +31,21 @@ const SILENCE_CHUNKS: usize = 8;           // ~0.09s silence to end speech - ULT
 const MIN_SPEECH_CHUNKS: usize = 3;        // ~0.035s min speech duration - ULTRA FAST
 const PRE_SPEECH_CHUNKS: usize = 3;        // ~0.035s pre-speech buffer - ULTRA FAST
 
+/// Set by the capture thread when Windows denies microphone access mid-stream
+static ACCESS_REVOKED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
 /// Pluely-style microphone input
 pub struct PluelyMicrophoneInput {}
 
 impl PluelyMicrophoneInput {
     pub fn new() -> Result<Self> {
+        crate::platform::ensure_available(crate::platform::Capability::MicrophoneCapture).map_err(anyhow::Error::msg)?;
         Ok(Self {})
     }
 
-    /// Start the microphone stream
-    pub fn stream(self) -> PluelyMicrophoneStream {
-        let sample_queue = Arc::new(Mutex::new(VecDeque::new()));
+    /// Start the microphone stream; fails if the device can't be opened (e.g. access denied)
+    pub fn stream(self) -> Result<PluelyMicrophoneStream> {
+        let sample_queue = Arc::new(Mutex::new(VecDeque::with_capacity(crate::audio_thread::QUEUE_CAPACITY_SAMPLES)));
         let waker_state = Arc::new(Mutex::new(WakerState {
             waker: None,
             has_data: false,
@@ -45,22 +56,24 @@ impl PluelyMicrophoneInput {
         let queue_clone = sample_queue.clone();
         let waker_clone = waker_state.clone();
 
-        let capture_thread = thread::spawn(move || {
+        let capture_thread = crate::audio_thread::spawn_capture_thread("microphone-capture", move || {
             if let Err(e) = PluelyMicrophoneStream::capture_audio_loop(queue_clone, waker_clone, init_tx) {
                 error!("Pluely Microphone capture loop failed: {}", e);
             }
-        });
+        })
+        .map_err(|e| anyhow::anyhow!("Failed to spawn microphone capture thread: {}", e))?;
 
         // Wait for initialization with timeout
         if let Ok(Err(e)) = init_rx.recv_timeout(Duration::from_secs(5)) {
             error!("Pluely Microphone initialization failed: {}", e);
+            return Err(e);
         }
 
-        PluelyMicrophoneStream {
+        Ok(PluelyMicrophoneStream {
             sample_queue,
             waker_state,
             capture_thread: Some(capture_thread),
-        }
+        })
     }
 }
 
@@ -84,6 +97,7 @@ impl PluelyMicrophoneStream {
         44100
     }
 
+    #[cfg(windows)]
     /// Main microphone capture loop using WASAPI - based on Pluely's implementation
     fn capture_audio_loop(
         sample_queue: Arc<Mutex<VecDeque<f32>>>,
@@ -92,38 +106,15 @@ impl PluelyMicrophoneStream {
     ) -> Result<()> {
         info!("🎤 Starting Pluely-style WASAPI microphone capture loop...");
 
-        let init_result = (|| -> Result<_> {
-            // Get default CAPTURE device for microphone input
-            let device = get_default_device(&Direction::Capture)?;
-            let mut audio_client = device.get_iaudioclient()?;
-
-            // Use Pluely's exact format configuration
-            let desired_format = WaveFormat::new(32, 32, &SampleType::Float, 44100, 1, None);
-
-            let (_def_time, min_time) = audio_client.get_device_period()?;
-
-            let mode = StreamMode::EventsShared {
-                autoconvert: true,
-                buffer_duration_hns: min_time,
-            };
-
-            // Initialize in capture mode for microphone
-            audio_client.initialize_client(&desired_format, &Direction::Capture, &mode)?;
-
-            let h_event = audio_client.set_get_eventhandle()?;
-            let capture_client = audio_client.get_audiocaptureclient()?;
-
-            audio_client.start_stream()?;
-            info!("✅ Pluely-style WASAPI microphone capture initialized successfully");
-
-            Ok((h_event, capture_client))
-        })();
-
-        match init_result {
+        match crate::audio_thread::open_shared_stream(Direction::Capture) {
             Ok((h_event, capture_client)) => {
+                info!("✅ Pluely-style WASAPI microphone capture initialized successfully");
                 let _ = init_tx.send(Ok(()));
 
                 info!("🎤 Pluely microphone capture loop running...");
+                // Allocated once; the loop only clears and refills them
+                let mut temp_queue: VecDeque<u8> = VecDeque::with_capacity(crate::audio_thread::PACKET_BUFFER_BYTES);
+                let mut samples: Vec<f32> = Vec::with_capacity(crate::audio_thread::PACKET_BUFFER_BYTES / 4);
                 loop {
                     // Check shutdown signal
                     {
@@ -140,8 +131,20 @@ impl PluelyMicrophoneStream {
                     }
 
                     // Read audio data from microphone
-                    let mut temp_queue = VecDeque::new();
+                    temp_queue.clear();
                     if let Err(e) = capture_client.read_from_device_to_deque(&mut temp_queue) {
+                        if crate::permissions::is_access_denied(&e) {
+                            // Access was revoked in privacy settings - end the stream instead of spinning on errors
+                            error!("🚫 Microphone access denied while capturing: {}", e);
+                            ACCESS_REVOKED.store(true, std::sync::atomic::Ordering::Relaxed);
+                            let mut state = waker_state.lock().unwrap();
+                            state.shutdown = true;
+                            if let Some(waker) = state.waker.take() {
+                                drop(state);
+                                waker.wake();
+                            }
+                            break;
+                        }
                         warn!("Pluely microphone failed to read audio data: {}", e);
                         continue;
                     }
@@ -150,24 +153,17 @@ impl PluelyMicrophoneStream {
                         continue;
                     }
 
-                    // Convert raw bytes to f32 samples (Pluely's method)
-                    let mut samples = Vec::new();
-                    while temp_queue.len() >= 4 {
-                        let bytes = [
-                            temp_queue.pop_front().unwrap(),
-                            temp_queue.pop_front().unwrap(),
-                            temp_queue.pop_front().unwrap(),
-                            temp_queue.pop_front().unwrap(),
-                        ];
-                        let sample = f32::from_le_bytes(bytes);
-                        samples.push(sample);
-                    }
+                    // Convert raw bytes to f32 samples in one pass over the contiguous buffer
+                    samples.clear();
+                    crate::audio_buffer::extend_f32_from_le_bytes(temp_queue.make_contiguous(), &mut samples);
 
                     if !samples.is_empty() {
+                        crate::audio_debug::tap("microphone", crate::audio_debug::Stage::Raw, &samples, 44100);
+
                         // Add samples to queue with buffer management
                         {
                             let mut queue = sample_queue.lock().unwrap();
-                            queue.extend(samples);
+                            queue.extend(samples.iter().copied());
 
                             // Keep buffer size reasonable (like Pluely - 8192 samples)
                             let len = queue.len();
@@ -199,6 +195,87 @@ impl PluelyMicrophoneStream {
         info!("🛑 Pluely microphone capture loop ended");
         Ok(())
     }
+
+    /// Capture loop on Linux: the default PulseAudio/PipeWire source
+    #[cfg(target_os = "linux")]
+    fn capture_audio_loop(
+        sample_queue: Arc<Mutex<VecDeque<f32>>>,
+        waker_state: Arc<Mutex<WakerState>>,
+        init_tx: mpsc::Sender<Result<()>>,
+    ) -> Result<()> {
+        use crate::pulse_capture::{PulseCapture, PulseSource};
+
+        let mut capture = match PulseCapture::start(PulseSource::Microphone) {
+            Ok(capture) => capture,
+            Err(e) => {
+                let _ = init_tx.send(Err(e));
+                return Ok(());
+            }
+        };
+        let _ = init_tx.send(Ok(()));
+
+        info!("🎵 Pluely microphone capture loop running (PulseAudio source)...");
+        let mut buffer = vec![0u8; crate::audio_thread::PACKET_BUFFER_BYTES];
+        let mut samples: Vec<f32> = Vec::with_capacity(crate::audio_thread::PACKET_BUFFER_BYTES / 4);
+        loop {
+            if waker_state.lock().unwrap().shutdown {
+                break;
+            }
+
+            samples.clear();
+            let ended = match capture.read(&mut buffer, &mut samples) {
+                Ok(0) => Some("parec stopped".to_string()),
+                Ok(_) => None,
+                Err(e) => Some(e.to_string()),
+            };
+            if let Some(reason) = ended {
+                // End the stream rather than leaving the consumer waiting on a dead capture
+                error!("❌ Microphone capture ended: {}", reason);
+                let mut state = waker_state.lock().unwrap();
+                state.shutdown = true;
+                if let Some(waker) = state.waker.take() {
+                    drop(state);
+                    waker.wake();
+                }
+                break;
+            }
+            if samples.is_empty() {
+                continue;
+            }
+
+            crate::audio_debug::tap("microphone", crate::audio_debug::Stage::Raw, &samples, 44100);
+            {
+                let mut queue = sample_queue.lock().unwrap();
+                queue.extend(samples.iter().copied());
+                let len = queue.len();
+                if len > 8192 {
+                    queue.drain(0..(len - 8192));
+                }
+            }
+            let mut state = waker_state.lock().unwrap();
+            if !state.has_data {
+                state.has_data = true;
+                if let Some(waker) = state.waker.take() {
+                    drop(state);
+                    waker.wake();
+                }
+            }
+        }
+
+        info!("🛑 Pluely microphone capture loop ended");
+        Ok(())
+    }
+
+    /// No capture backend on this platform; `new` refuses first, so this only reports the reason
+    #[cfg(not(any(windows, target_os = "linux")))]
+    fn capture_audio_loop(
+        _sample_queue: Arc<Mutex<VecDeque<f32>>>,
+        _waker_state: Arc<Mutex<WakerState>>,
+        init_tx: mpsc::Sender<Result<()>>,
+    ) -> Result<()> {
+        let _ = init_tx.send(Err(anyhow::anyhow!(crate::platform::unavailable(crate::platform::Capability::MicrophoneCapture))));
+        Ok(())
+    }
 }
 
 /// Clean shutdown for the microphone stream
@@ -298,12 +375,12 @@ impl PluelyMicrophoneProcessor {
         info!("🎤 Starting Pluely-style microphone capture with transcription...");
 
         let input = PluelyMicrophoneInput::new()?;
-        let mut stream = input.stream();
+        let mut stream = input.stream()?;
         let sr = stream.sample_rate();
         self.sample_rate = sr;
 
         // Emit debug: capture initialized
-        let _ = self.app_handle.emit("pluely-microphone-debug", serde_json::json!({
+        let _ = self.app_handle.emit(events::PLUELY_MICROPHONE_DEBUG, serde_json::json!({
             "event": "capture-initialized",
             "sample_rate": sr,
             "hop_size": HOP_SIZE,
@@ -316,6 +393,7 @@ impl PluelyMicrophoneProcessor {
         let app_clone = self.app_handle.clone();
         let stop_flag = get_mic_stop_flag();
         stop_flag.store(false, std::sync::atomic::Ordering::Relaxed);
+        ACCESS_REVOKED.store(false, std::sync::atomic::Ordering::Relaxed);
         
         tokio::spawn(async move {
             let mut processor = PluelyMicrophoneProcessor::new(app_clone.clone());
@@ -330,6 +408,18 @@ impl PluelyMicrophoneProcessor {
                 }
                 processor.process_sample(sample).await;
             }
+
+            if ACCESS_REVOKED.swap(false, std::sync::atomic::Ordering::Relaxed) {
+                // Pause microphone capture until the user restores access and restarts it
+                *get_mic_audio_processor().lock().unwrap() = None;
+                crate::permissions::notify_permission_revoked(
+                    &app_clone,
+                    "microphone",
+                    "capture_loop",
+                    "Windows denied microphone access while capturing",
+                    vec!["microphone_capture".to_string()],
+                );
+            }
             info!("🛑 Microphone capture task ended");
         });
 
@@ -338,19 +428,23 @@ impl PluelyMicrophoneProcessor {
 
     /// Process individual audio samples with Pluely's VAD algorithm
     async fn process_sample(&mut self, sample: f32) {
+        // Push-to-talk gate: silence keeps the timing and lets the VAD close the segment
+        let sample = if crate::push_to_talk::is_forwarding() { sample } else { 0.0 };
         self.sample_buffer.push_back(sample);
         
         // Real-time streaming: accumulate samples for streaming transcription
         if self.streaming_enabled {
             self.streaming_buffer.push(sample);
             
-            // Send streaming chunks every 2048 samples (~46ms at 44.1kHz)
-            const STREAMING_CHUNK_SIZE: usize = 2048;
-            if self.streaming_buffer.len() >= STREAMING_CHUNK_SIZE {
-                if let Ok(b64_chunk) = self.samples_to_wav_b64(&self.streaming_buffer) {
-                    let _ = self.app_handle.emit("mic-audio-chunk", b64_chunk);
-                }
+            // Chunk size comes from the transcription preset (2048 samples, ~46ms at 44.1kHz, by default)
+            if self.streaming_buffer.len() >= crate::transcription_preset::chunk_samples() {
+                let chunk: Arc<[f32]> = Arc::from(self.streaming_buffer.as_slice());
                 self.streaming_buffer.clear();
+                crate::audio_debug::tap("microphone", crate::audio_debug::Stage::Processed, &chunk, self.sample_rate);
+                crate::audio_buffer::publish(crate::audio_buffer::AudioSource::Microphone, chunk.clone(), self.sample_rate);
+                if let Ok(b64_chunk) = self.samples_to_wav_b64(&chunk) {
+                    let _ = self.app_handle.emit(events::MIC_AUDIO_CHUNK, b64_chunk);
+                }
             }
         }
         
@@ -365,6 +459,7 @@ impl PluelyMicrophoneProcessor {
 
             let (rms, peak) = Self::process_chunk(&chunk);
             let is_speech = rms > VAD_SENSITIVITY_RMS || peak > SPEECH_PEAK_THRESHOLD;
+            crate::pacing::on_mic_vad(is_speech, std::time::Duration::from_secs_f32(HOP_SIZE as f32 / self.sample_rate as f32));
             
             if is_speech {
                 if !self.in_speech {
@@ -377,7 +472,7 @@ impl PluelyMicrophoneProcessor {
                     self.speech_buffer.extend(self.pre_speech_buffer.drain(..));
                     
                     // Emit speech start event
-                    let _ = self.app_handle.emit("mic-speech-start", ()).map_err(|e| {
+                    let _ = self.app_handle.emit(events::MIC_SPEECH_START, ()).map_err(|e| {
                         error!("Failed to emit mic-speech-start: {}", e);
                     });
                     
@@ -391,7 +486,7 @@ impl PluelyMicrophoneProcessor {
                 let max_samples = self.sample_rate as usize * 30;
                 if self.speech_buffer.len() > max_samples {
                     if let Ok(b64) = self.samples_to_wav_b64(&self.speech_buffer) {
-                        let _ = self.app_handle.emit("mic-speech-detected", b64);
+                        let _ = self.app_handle.emit(events::MIC_SPEECH_DETECTED, b64);
                         info!("🎤 Emitted microphone speech segment (safety cap): {} samples", self.speech_buffer.len());
                     }
                     self.speech_buffer.clear();
@@ -413,7 +508,7 @@ impl PluelyMicrophoneProcessor {
                             
                             // Convert to WAV and emit
                             if let Ok(b64) = self.samples_to_wav_b64(&self.speech_buffer) {
-                                let _ = self.app_handle.emit("mic-speech-detected", b64);
+                                let _ = self.app_handle.emit(events::MIC_SPEECH_DETECTED, b64);
                                 info!("🎤 Emitted microphone speech segment: {} samples ({:.2}s)", 
                                       self.speech_buffer.len(), 
                                       self.speech_buffer.len() as f32 / self.sample_rate as f32);
@@ -489,106 +584,147 @@ fn get_mic_stop_flag() -> Arc<std::sync::atomic::AtomicBool> {
     MIC_STOP_FLAG.get_or_init(|| Arc::new(std::sync::atomic::AtomicBool::new(false))).clone()
 }
 
+/// Capture/VAD configuration, used by the audio debug dump
+pub fn pipeline_config() -> serde_json::Value {
+    serde_json::json!({
+        "source": "default capture device",
+        "capture_format": "f32 mono 44100Hz (WASAPI autoconvert)",
+        "hop_size": HOP_SIZE,
+        "vad_sensitivity_rms": VAD_SENSITIVITY_RMS,
+        "speech_peak_threshold": SPEECH_PEAK_THRESHOLD,
+        "silence_chunks": SILENCE_CHUNKS,
+        "min_speech_chunks": MIN_SPEECH_CHUNKS,
+        "pre_speech_chunks": PRE_SPEECH_CHUNKS,
+        "streaming_chunk_size": 2048,
+        "streaming_event": "mic-audio-chunk",
+    })
+}
+
 /// Tauri command to start Pluely-style microphone capture
 #[tauri::command]
-pub async fn start_pluely_microphone_capture(app: AppHandle) -> Result<(), String> {
-    info!("🚀 Starting Pluely-style microphone capture...");
-
-    let _ = app.emit("pluely-microphone-debug", serde_json::json!({
-        "event": "start-requested",
-        "timestamp": std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.as_millis())
-            .unwrap_or(0)
-    }));
+#[specta::specta]
+pub async fn start_pluely_microphone_capture(app: AppHandle) -> Response<()> {
+    guard::respond("start_pluely_microphone_capture", &[Require::Permission(Permission::Microphone)], async move {
+        info!("🚀 Starting Pluely-style microphone capture...");
+        crate::telemetry::record_feature("microphone_capture");
+
+        let _ = app.emit(events::PLUELY_MICROPHONE_DEBUG, serde_json::json!({
+            "event": "start-requested",
+            "timestamp": std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_millis())
+                .unwrap_or(0)
+        }));
     
-    let processor_arc = get_mic_audio_processor();
+        let processor_arc = get_mic_audio_processor();
     
-    // Stop existing processor if running
-    {
-        let mut processor_guard = processor_arc.lock().unwrap();
-        if processor_guard.is_some() {
-            info!("Stopping existing microphone processor...");
-            *processor_guard = None;
+        // Stop existing processor if running
+        {
+            let mut processor_guard = processor_arc.lock().unwrap();
+            if processor_guard.is_some() {
+                info!("Stopping existing microphone processor...");
+                *processor_guard = None;
+            }
         }
-    }
     
-    // Create new processor
-    let mut processor = PluelyMicrophoneProcessor::new(app.clone());
+        // Create new processor
+        let mut processor = PluelyMicrophoneProcessor::new(app.clone());
+    
+        // Start capture with transcription
+        if let Err(e) = processor.start_capture_with_transcription().await {
+            let e = if crate::permissions::is_access_denied(&e) {
+                crate::permissions::notify_permission_revoked(
+                    &app,
+                    "microphone",
+                    "capture_start",
+                    &e.to_string(),
+                    vec!["microphone_capture".to_string()],
+                );
+                "Microphone access is blocked in Windows privacy settings. Allow MockMate to use the microphone and try again.".to_string()
+            } else {
+                e.to_string()
+            };
+            let _ = app.emit(events::PLUELY_MICROPHONE_DEBUG, serde_json::json!({
+                "event": "start-error",
+                "error": e,
+                "timestamp": std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_millis())
+                    .unwrap_or(0)
+            }));
+            return Err(e.into());
+        }
     
-    // Start capture with transcription
-    if let Err(e) = processor.start_capture_with_transcription().await.map_err(|e| e.to_string()) {
-        let _ = app.emit("pluely-microphone-debug", serde_json::json!({
-            "event": "start-error",
-            "error": e,
+        // Store the processor
+        {
+            let mut processor_guard = processor_arc.lock().unwrap();
+            *processor_guard = Some(processor);
+        }
+
+        let _ = app.emit(events::PLUELY_MICROPHONE_DEBUG, serde_json::json!({
+            "event": "started",
             "timestamp": std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .map(|d| d.as_millis())
                 .unwrap_or(0)
         }));
-        return Err(e);
-    }
-    
-    // Store the processor
-    {
-        let mut processor_guard = processor_arc.lock().unwrap();
-        *processor_guard = Some(processor);
-    }
-
-    let _ = app.emit("pluely-microphone-debug", serde_json::json!({
-        "event": "started",
-        "timestamp": std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.as_millis())
-            .unwrap_or(0)
-    }));
     
-    info!("✅ Pluely-style microphone capture started successfully");
-    Ok(())
+        info!("✅ Pluely-style microphone capture started successfully");
+        Ok(())
+    })
+    .await
 }
 
 /// Tauri command to stop microphone capture
 #[tauri::command]
-pub async fn stop_pluely_microphone_capture(app: AppHandle) -> Result<(), String> {
-    info!("🛑 Stopping Pluely-style microphone capture...");
-
-    let _ = app.emit("pluely-microphone-debug", serde_json::json!({
-        "event": "stop-requested",
-        "timestamp": std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.as_millis())
-            .unwrap_or(0)
-    }));
+#[specta::specta]
+pub async fn stop_pluely_microphone_capture(app: AppHandle) -> Response<()> {
+    envelope::respond("stop_pluely_microphone_capture", async move {
+        info!("🛑 Stopping Pluely-style microphone capture...");
+
+        let _ = app.emit(events::PLUELY_MICROPHONE_DEBUG, serde_json::json!({
+            "event": "stop-requested",
+            "timestamp": std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_millis())
+                .unwrap_or(0)
+        }));
     
-    // Set the stop flag to signal the capture task to stop
-    let stop_flag = get_mic_stop_flag();
-    stop_flag.store(true, std::sync::atomic::Ordering::Relaxed);
-    info!("🛑 Microphone stop flag set to true");
+        // Set the stop flag to signal the capture task to stop
+        let stop_flag = get_mic_stop_flag();
+        stop_flag.store(true, std::sync::atomic::Ordering::Relaxed);
+        info!("🛑 Microphone stop flag set to true");
     
-    // Wait a bit for the task to stop
-    tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+        // Wait a bit for the task to stop
+        tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
     
-    let processor_arc = get_mic_audio_processor();
-    let mut processor_guard = processor_arc.lock().unwrap();
+        let processor_arc = get_mic_audio_processor();
+        let mut processor_guard = processor_arc.lock().unwrap();
     
-    *processor_guard = None;
-
-    let _ = app.emit("pluely-microphone-debug", serde_json::json!({
-        "event": "stopped",
-        "timestamp": std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.as_millis())
-            .unwrap_or(0)
-    }));
+        *processor_guard = None;
+
+        let _ = app.emit(events::PLUELY_MICROPHONE_DEBUG, serde_json::json!({
+            "event": "stopped",
+            "timestamp": std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_millis())
+                .unwrap_or(0)
+        }));
     
-    info!("✅ Pluely-style microphone capture stopped");
-    Ok(())
+        info!("✅ Pluely-style microphone capture stopped");
+        Ok(())
+    })
+    .await
 }
 
 /// Check if microphone capture is active
 #[tauri::command]
-pub async fn is_pluely_microphone_active() -> Result<bool, String> {
-    let processor_arc = get_mic_audio_processor();
-    let processor_guard = processor_arc.lock().unwrap();
-    Ok(processor_guard.is_some())
+#[specta::specta]
+pub async fn is_pluely_microphone_active() -> Response<bool> {
+    envelope::respond("is_pluely_microphone_active", async move {
+        let processor_arc = get_mic_audio_processor();
+        let processor_guard = processor_arc.lock().unwrap();
+        Ok(processor_guard.is_some())
+    })
+    .await
 }
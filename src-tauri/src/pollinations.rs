@@ -1,10 +1,16 @@
 use reqwest::Client;
 use serde::Deserialize;
 use anyhow::Result;
-use log::{info, error, warn, debug};
+use log::{info, error, warn, debug, trace};
 use futures_util::stream::StreamExt;
 use serde_json::Value;
 
+use crate::net_trace::TracedSend;
+use crate::logging::LogSampler;
+
+/// Per-token logging is sampled so streaming doesn't pay for a log line per token
+static TOKEN_LOG: LogSampler = LogSampler::new(50);
+
 // Model enum for Pollinations models
 #[derive(Debug, Clone)]
 pub enum PollinationsModel {
@@ -111,7 +117,7 @@ impl PollinationsClient {
             std::time::Duration::from_secs(5), // Very short timeout for health check
             self.client.get(health_check_url)
                 .header("User-Agent", "MockMate/1.0")
-                .send()
+                .send_traced()
         ).await {
             Ok(Ok(response)) => {
                 let is_healthy = response.status().is_success() || response.status().as_u16() < 500;
@@ -145,7 +151,7 @@ impl PollinationsClient {
             .timeout(std::time::Duration::from_secs(15))     // Shorter timeout for faster failure
             .connect_timeout(std::time::Duration::from_secs(3))  // Faster connection timeout
             .tcp_keepalive(std::time::Duration::from_secs(15))
-            .pool_idle_timeout(std::time::Duration::from_secs(5))
+            .pool_idle_timeout(std::time::Duration::from_secs(90))  // Keep warmed-up connections until the first question
             .pool_max_idle_per_host(10)  // Reduced for faster failure detection
             .http2_keep_alive_interval(std::time::Duration::from_secs(5))
             .user_agent("MockMate/1.0")  // Set default user agent
@@ -160,21 +166,58 @@ impl PollinationsClient {
         }
     }
 
+    /// Open a pooled connection (DNS + TLS) ahead of the first answer; with `ping` also request a single token
+    pub async fn warm_up(&self, ping: bool) -> Result<()> {
+        let response = if ping {
+            let mut request = self.client
+                .post(format!("{}/openai", self.base_url))
+                .json(&serde_json::json!({
+                    "model": "openai",
+                    "messages": [{ "role": "user", "content": "ping" }],
+                    "max_tokens": 1,
+                    "private": true,
+                    "referrer": self.referrer,
+                }));
+            if !self.api_key.is_empty() {
+                request = request.header("Authorization", format!("Bearer {}", self.api_key));
+            }
+            request.send_traced().await?
+        } else {
+            self.client.get(format!("{}/models", self.base_url)).send_traced().await?
+        };
+
+        if response.status().is_server_error() {
+            return Err(anyhow::anyhow!("Pollinations warm-up returned {}", response.status()));
+        }
+        // Read the body so the connection goes back to the pool
+        let _ = response.bytes().await;
+        Ok(())
+    }
+
     pub async fn generate_answer(
         &self,
         question: &str,
         context: &super::openai::InterviewContext,
         model: PollinationsModel,
     ) -> Result<String> {
-        let system_prompt = self.build_system_prompt(context);
-        let prompt = format!("{}
+        let system_prompt = Self::build_system_prompt(context);
+        let user_prompt = format!("Interview Question: {}
 
-Interview Question: {}
-
-Provide a confident, direct, and authentic answer that demonstrates your qualifications. Keep it focused and conversational - aim for 30-60 seconds when spoken aloud. Be specific and impactful.", system_prompt, question);
+Provide a confident, direct, and authentic answer that demonstrates your qualifications. Keep it focused and conversational - aim for 30-60 seconds when spoken aloud. Be specific and impactful.", question);
 
         info!("Generating answer with Pollinations model: {}", model.as_str());
-        
+        self.complete(&system_prompt, &user_prompt, model).await
+    }
+
+    /// Run a prompt with a caller-supplied system prompt, with the same endpoint fallback as answers
+    pub async fn complete(
+        &self,
+        system_prompt: &str,
+        user_prompt: &str,
+        model: PollinationsModel,
+    ) -> Result<String> {
+        let prompt = format!("{}\n\n{}", system_prompt, user_prompt);
+
         // Quick health check first to fail fast if service is down
         if !self.health_check().await {
             let error_msg = "❌ Pollinations service is currently unavailable (health check failed). This may be due to temporary infrastructure issues. Please try using OpenAI or wait a few minutes and retry.";
@@ -276,7 +319,7 @@ Provide a confident, direct, and authentic answer that demonstrates your qualifi
             .header("User-Agent", "MockMate/1.0")
             .header("Referer", referrer.as_str())  // Add referrer header for seed tier
             .json(&final_payload)
-            .send()
+            .send_traced()
             .await?;
 
         if !response.status().is_success() {
@@ -341,7 +384,7 @@ Provide a confident, direct, and authentic answer that demonstrates your qualifi
             .header("User-Agent", "MockMate/1.0")
             .header("Accept", "text/plain")
             .header("Referer", "mockmate")  // Add referrer header for seed tier
-            .send()
+            .send_traced()
             .await?;
 
         if !response.status().is_success() {
@@ -419,7 +462,7 @@ EXAMPLE RESPONSES:
 
 Provide only the JSON response, no other text.",
             analysis_prompt,
-            self.build_system_prompt(context),
+            Self::build_system_prompt(context),
             context.position.as_deref().unwrap_or("software development"),
             context.company.as_ref().map(|c| format!(" at {}", c)).unwrap_or_default()
         );
@@ -485,7 +528,7 @@ Provide only the JSON response, no other text.",
 
         let response = request_builder
             .json(&payload)
-            .send()
+            .send_traced()
             .await?;
 
         if !response.status().is_success() {
@@ -558,7 +601,7 @@ Instructions:
 - If no clear question is present, extract the most relevant technical topic and provide a brief summary.
 - Do not include extra commentary; just the answers.",
             if let Some(company) = &context.company { format!(" Company: {}.", company) } else { String::new() },
-            self.build_system_prompt(context)
+            Self::build_system_prompt(context)
         );
         
         let api_key = std::env::var("POLLINATIONS_API_KEY").unwrap_or_default();
@@ -604,7 +647,7 @@ Instructions:
             request_builder = request_builder.header("Authorization", format!("Bearer {}", api_key));
         }
 
-        let response = request_builder.json(&payload).send().await?;
+        let response = request_builder.json(&payload).send_traced().await?;
         if !response.status().is_success() {
             let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
@@ -711,7 +754,7 @@ EXAMPLE RESPONSES:
 
 Provide only the JSON response, no other text.",
             analysis_prompt,
-            self.build_system_prompt(context),
+            Self::build_system_prompt(context),
             context.position.as_deref().unwrap_or("software development"),
             context.company.as_ref().map(|c| format!(" at {}", c)).unwrap_or_default()
         );
@@ -775,7 +818,7 @@ Provide only the JSON response, no other text.",
 
         let response = request_builder
             .json(&payload)
-            .send()
+            .send_traced()
             .await?;
 
         if !response.status().is_success() {
@@ -810,8 +853,9 @@ Provide only the JSON response, no other text.",
                                 // Send individual token for progressive display
                                 on_token(&content);
                                 full_response.push_str(&content);
-                                info!("📤 POLLINATIONS VISION: Sent token to callback: '{}', token length: {}, total length: {}", 
-                                    content.chars().take(50).collect::<String>(), content.len(), full_response.len());
+                                if let Some(tokens) = TOKEN_LOG.sample() {
+                                    trace!("Streamed {} tokens, {} chars so far", tokens, full_response.len());
+                                }
                             }
                         }
                     }
@@ -841,7 +885,7 @@ Provide only the JSON response, no other text.",
         }
     }
 
-    fn build_system_prompt(&self, context: &super::openai::InterviewContext) -> String {
+    pub(crate) fn build_system_prompt(context: &super::openai::InterviewContext) -> String {
         // Optimized prompt for speed and quality balance
         let mut prompt = String::from("Provide clear, concise answers. Be direct and helpful.");
         
@@ -851,6 +895,32 @@ Provide only the JSON response, no other text.",
                 prompt.push_str(&format!(" Context: {}.", position));
             }
         }
+
+        // Kept on one line: this prompt is sent in a GET URL
+        if let Some(job_description) = &context.job_description {
+            prompt.push_str(&format!(" Job: {}.", job_description.replace('\n', " ")));
+        }
+        if let Some(resume) = &context.resume_content {
+            prompt.push_str(&format!(" Candidate background: {}.", resume.replace('\n', " ")));
+        }
+        if let Some(transcript) = &context.recent_transcript {
+            prompt.push_str(&format!(" Interviewer said recently: {}.", transcript.replace('\n', " ")));
+        }
+        if let Some(prior_qa) = &context.prior_qa {
+            prompt.push_str(&format!(" Stay consistent with earlier answers: {}", prior_qa.replace('\n', " ")));
+        }
+        if let Some(custom) = &context.custom_system_prompt {
+            prompt.push_str(&format!(" Session instructions, which take precedence: {}", custom.replace('\n', " ")));
+        }
+
+        // The prompt goes in a GET URL here, so the glossary gets a smaller budget
+        if let Some(glossary) = crate::glossary::prompt_section(600) {
+            prompt.push_str(&format!(" Use these terms exactly as written:{}", glossary));
+        }
+
+        if let Some(language) = crate::answer_language::prompt_instruction() {
+            prompt.push_str(&language);
+        }
         
         prompt
     }
@@ -932,15 +1002,11 @@ Provide only the JSON response, no other text.",
             info!("⚡ First token received in {:?}", time_to_first_token);
         }
         
-        // Send token with optimized logging
-        if content.len() <= 20 {
-            debug!("📤 Token: '{}' ({})", content, content.len());
-        } else {
-            debug!("📤 Token: {}chars", content.len());
-        }
-        
         on_token(content);
         full_response.push_str(content);
+        if let Some(tokens) = TOKEN_LOG.sample() {
+            trace!("Streamed {} tokens, {} chars so far", tokens, full_response.len());
+        }
     }
 
     async fn try_streaming_with_endpoint<F>(
@@ -954,7 +1020,7 @@ Provide only the JSON response, no other text.",
     where
         F: FnMut(&str) + Send,
     {
-        let system_prompt = self.build_system_prompt(context);
+        let system_prompt = Self::build_system_prompt(context);
         let full_prompt = format!("{} Question: {}", system_prompt, question);
 
         info!("🚀 Using Pollinations GET streaming API with model: {}", model.as_str());
@@ -1019,7 +1085,7 @@ Provide only the JSON response, no other text.",
             .header("Connection", "keep-alive")
             .header("Referer", referrer.as_str());    // For seed tier access
 
-        let response = request_builder.send().await?;
+        let response = request_builder.send_traced().await?;
         let request_time = start_time.elapsed();
         info!("📡 GET streaming request sent in {:?}", request_time);
 
@@ -1088,7 +1154,6 @@ Provide only the JSON response, no other text.",
                             }
                             
                             // For plain text, send the chunk directly - this should provide word-by-word streaming
-                            debug!("📤 Plain text chunk: '{}' ({})", chunk_text.chars().take(20).collect::<String>(), chunk_text.len());
                             on_token(&chunk_text);
                             full_response.push_str(&chunk_text);
                         }
@@ -1212,7 +1277,7 @@ Provide only the JSON response, no other text.",
         model: PollinationsModel,
         stream: bool,
     ) -> Result<reqwest::Response> {
-        let system_prompt = self.build_system_prompt(context);
+        let system_prompt = Self::build_system_prompt(context);
         
         // Get API key and referrer from environment
         let api_key = std::env::var("POLLINATIONS_API_KEY")
@@ -1263,7 +1328,7 @@ Provide only the JSON response, no other text.",
 
         let response = request_builder
             .json(&payload)
-            .send()
+            .send_traced()
             .await?;
 
         if !response.status().is_success() {
@@ -1314,7 +1379,9 @@ Provide only the JSON response, no other text.",
                                 // Send individual token for progressive display
                                 on_token(&content);
                                 full_response.push_str(&content);
-                                info!("POST streamed individual token: '{}', token length: {}, total length: {}", content.chars().take(50).collect::<String>(), content.len(), full_response.len());
+                                if let Some(tokens) = TOKEN_LOG.sample() {
+                                    trace!("Streamed {} tokens, {} chars so far", tokens, full_response.len());
+                                }
                             }
                         }
                     }
@@ -1373,7 +1440,7 @@ Provide only the JSON response, no other text.",
         let response = self.client.get(endpoint)
             .header("User-Agent", "MockMate/1.0")
             .header("Accept", "application/json")
-            .send()
+            .send_traced()
             .await?;
             
         if !response.status().is_success() {
@@ -0,0 +1,102 @@
+// Portable mode for locked-down machines where installers and registry writes are not allowed
+// Turned on by a file named `portable` next to the executable or by the `--portable` argument.
+// Everything MockMate stores goes to a MockMate folder beside the executable instead of
+// %APPDATA%, mockmate:// links are ignored (only the installer registers the scheme), the
+// first-run registry and privacy settings work is skipped, and sessions are joined by entering the
+// session ID by hand.
+
+use std::path::PathBuf;
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use specta::Type;
+use crate::envelope::{self, Response};
+
+const MARKER_FILE: &str = "portable";
+const FLAG: &str = "--portable";
+
+fn exe_dir() -> Option<PathBuf> {
+    std::env::current_exe().ok()?.parent().map(PathBuf::from)
+}
+
+/// The executable's folder when portable mode is on and that folder is writable
+static PORTABLE_ROOT: Lazy<Option<PathBuf>> = Lazy::new(|| {
+    let dir = exe_dir()?;
+    let requested = std::env::args().any(|arg| arg == FLAG) || dir.join(MARKER_FILE).exists();
+    if !requested {
+        return None;
+    }
+    // A read-only location (e.g. a network share) would lose every setting, so fall back loudly
+    let probe = dir.join("MockMate").join(".write-test");
+    let writable = std::fs::create_dir_all(dir.join("MockMate")).is_ok() && std::fs::write(&probe, b"").is_ok();
+    let _ = std::fs::remove_file(&probe);
+    if !writable {
+        warn!("Portable mode requested but {} is not writable; using the per-user data folder", dir.display());
+        return None;
+    }
+    Some(dir)
+});
+
+pub fn enabled() -> bool {
+    PORTABLE_ROOT.is_some()
+}
+
+/// Machine-wide folder shared by all account profiles: next to the executable in portable mode,
+/// otherwise %APPDATA% (the temp directory when that is unset)
+pub fn base_root() -> PathBuf {
+    match PORTABLE_ROOT.as_ref() {
+        Some(dir) => dir.clone(),
+        None => std::env::var("APPDATA")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| std::env::temp_dir()),
+    }
+}
+
+/// Folder that holds the `MockMate` data directory of the active account profile; the default
+/// profile uses `base_root` itself (see accounts)
+pub fn data_root() -> PathBuf {
+    crate::accounts::profile_root(base_root())
+}
+
+/// Call before the webview starts so its profile is kept beside the executable too, and apart for
+/// each account profile
+pub fn prepare() {
+    if PORTABLE_ROOT.is_none() && crate::accounts::is_default_active() {
+        return;
+    }
+    std::env::set_var("WEBVIEW2_USER_DATA_FOLDER", data_root().join("MockMate").join("webview"));
+    if let Some(dir) = PORTABLE_ROOT.as_ref() {
+        info!("🧳 Portable mode: storing data in {}", dir.join("MockMate").display());
+    }
+}
+
+/// Fail with a user-facing message for actions that need an installed copy (registry writes,
+/// running an installer)
+pub fn ensure_installed(action: &str) -> Result<(), String> {
+    if enabled() {
+        Err(format!("{} is not available in portable mode", action))
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct PortableStatus {
+    pub enabled: bool,
+    pub data_dir: String,
+    /// Whether mockmate:// links open sessions; when false the session ID has to be typed in
+    pub protocol_links: bool,
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_portable_status() -> Response<PortableStatus> {
+    envelope::respond_sync("get_portable_status", || {
+        Ok(PortableStatus {
+            enabled: enabled(),
+            data_dir: data_root().join("MockMate").display().to_string(),
+            protocol_links: !enabled(),
+        })
+    })
+}
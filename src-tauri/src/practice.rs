@@ -0,0 +1,606 @@
+// Practice mode: the configured AI provider plays the interviewer
+// A practice session plans questions from the job description, collects the candidate's answer
+// (typed, or the final transcripts heard while a question is open), scores it, optionally asks a
+// follow-up and finally writes a report. Sessions are independent of backend interview sessions
+// and reports are kept under %APPDATA%\MockMate\practice.
+
+use std::path::PathBuf;
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use specta::Type;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::question_topic::QuestionTopic;
+use crate::AppState;
+use crate::envelope::{self, Response};
+use crate::events;
+
+const DEFAULT_QUESTION_COUNT: usize = 5;
+const MAX_QUESTION_COUNT: usize = 20;
+
+static SESSION: Lazy<Mutex<Option<PracticeSession>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PracticeConfig {
+    /// "openai" or "pollinations"
+    pub provider: String,
+    pub model: String,
+    /// Falls back to the current interview context when omitted
+    pub company: Option<String>,
+    pub position: Option<String>,
+    pub job_description: Option<String>,
+    /// e.g. "junior", "mid", "senior"
+    pub difficulty: Option<String>,
+    pub question_count: Option<usize>,
+    /// Follow-ups the interviewer may ask per planned question
+    pub max_follow_ups: Option<u32>,
+    /// Profile whose per-topic performance drives adaptive planning
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// Weight topics and difficulty by past performance (on by default)
+    #[serde(default)]
+    pub adaptive: Option<bool>,
+}
+
+impl PracticeConfig {
+    fn profile(&self) -> &str {
+        self.profile.as_deref().unwrap_or(crate::practice_profile::DEFAULT_PROFILE)
+    }
+
+    fn is_adaptive(&self) -> bool {
+        self.adaptive.unwrap_or(true)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TurnKind {
+    Question,
+    FollowUp,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct PracticeTurn {
+    pub kind: TurnKind,
+    pub question: String,
+    #[serde(default)]
+    pub topic: Option<QuestionTopic>,
+    pub answer: Option<String>,
+    /// 1-10
+    pub score: Option<u8>,
+    pub feedback: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum PracticeState {
+    AwaitingAnswer,
+    Evaluating,
+    Completed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PracticeSession {
+    pub id: String,
+    pub config: PracticeConfig,
+    pub state: PracticeState,
+    pub planned_questions: Vec<String>,
+    /// Topic of each planned question, when the interviewer labelled it
+    #[serde(default)]
+    pub planned_topics: Vec<Option<QuestionTopic>>,
+    /// Index of the planned question currently being discussed
+    pub question_index: usize,
+    pub follow_ups_asked: u32,
+    pub turns: Vec<PracticeTurn>,
+    /// Final transcripts heard since the current question was asked
+    pub heard: String,
+    pub started_at: String,
+}
+
+impl PracticeSession {
+    fn current_turn(&self) -> Option<&PracticeTurn> {
+        self.turns.last()
+    }
+
+    /// Move on to the next planned question; None when the plan is exhausted
+    fn advance(&mut self) -> Option<PracticeTurn> {
+        self.question_index += 1;
+        self.follow_ups_asked = 0;
+        self.planned_questions.get(self.question_index).map(|question| PracticeTurn {
+            kind: TurnKind::Question,
+            question: question.clone(),
+            topic: self.planned_topics.get(self.question_index).copied().flatten(),
+            answer: None,
+            score: None,
+            feedback: None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PracticeEvaluation {
+    pub score: u8,
+    pub feedback: String,
+}
+
+/// Result of answering the current question
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PracticeStep {
+    pub evaluation: PracticeEvaluation,
+    pub next_question: Option<PracticeTurn>,
+    pub completed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PracticeReport {
+    pub session_id: String,
+    pub company: Option<String>,
+    pub position: Option<String>,
+    /// Practice profile the session counted towards; reports from before profiles belong to the default
+    #[serde(default)]
+    pub profile: Option<String>,
+    pub started_at: String,
+    pub completed_at: String,
+    /// Average of the turn scores, 0-10
+    pub overall_score: f32,
+    pub summary: String,
+    pub strengths: Vec<String>,
+    pub improvements: Vec<String>,
+    pub turns: Vec<PracticeTurn>,
+}
+
+/// Payload of `practice-update`
+#[derive(Debug, Clone, Serialize, JsonSchema, Type)]
+pub struct PracticeUpdate<'a> {
+    session_id: &'a str,
+    state: PracticeState,
+    turn: Option<&'a PracticeTurn>,
+}
+
+#[derive(Deserialize)]
+struct EvaluationReply {
+    score: u8,
+    feedback: String,
+    follow_up: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PlannedReply {
+    question: String,
+    #[serde(default)]
+    topic: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ReportReply {
+    summary: String,
+    #[serde(default)]
+    strengths: Vec<String>,
+    #[serde(default)]
+    improvements: Vec<String>,
+}
+
+fn reports_dir() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("practice")
+}
+
+/// A saved report by session id
+pub fn load_report(session_id: &str) -> Result<PracticeReport, String> {
+    let body = std::fs::read(reports_dir().join(format!("{}.json", session_id)))
+        .map_err(|_| format!("Practice report {} not found", session_id))?;
+    serde_json::from_slice(&body).map_err(|e| format!("Practice report {} is unreadable: {}", session_id, e))
+}
+
+fn save_report(report: &PracticeReport) -> Result<(), String> {
+    let dir = reports_dir();
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let body = serde_json::to_vec_pretty(report).map_err(|e| e.to_string())?;
+    std::fs::write(dir.join(format!("{}.json", report.session_id)), body).map_err(|e| e.to_string())
+}
+
+/// Parse the first JSON value delimited by `open`/`close` in a model reply, ignoring any prose or
+/// code fences around it
+pub(crate) fn extract_json<T: DeserializeOwned>(reply: &str, open: char, close: char) -> Result<T, String> {
+    let start = reply.find(open).ok_or("AI reply did not contain JSON")?;
+    let end = reply.rfind(close).filter(|&end| end > start).ok_or("AI reply did not contain JSON")?;
+    serde_json::from_str(&reply[start..=end]).map_err(|e| format!("AI reply was not in the expected format: {}", e))
+}
+
+fn interviewer_prompt(config: &PracticeConfig) -> String {
+    let mut prompt = String::from(
+        "You are an experienced interviewer running a realistic mock interview. Be professional, \
+         concise and fair. Reply only in the JSON format requested.",
+    );
+    if let Some(company) = &config.company {
+        prompt.push_str(&format!("\nCompany: {}", company));
+    }
+    if let Some(position) = &config.position {
+        prompt.push_str(&format!("\nRole: {}", position));
+    }
+    if let Some(difficulty) = &config.difficulty {
+        prompt.push_str(&format!("\nCandidate level: {}", difficulty));
+    }
+    if let Some(job_description) = &config.job_description {
+        prompt.push_str(&format!("\n\nJob description:\n{}", job_description));
+    }
+    prompt
+}
+
+fn emit(app_handle: &AppHandle, session: &PracticeSession) {
+    let _ = app_handle.emit(
+        events::PRACTICE_UPDATE,
+        PracticeUpdate {
+            session_id: &session.id,
+            state: session.state,
+            turn: session.current_turn(),
+        },
+    );
+}
+
+/// Collect a final transcript segment as part of the open question's answer
+pub fn on_transcript(text: &str) {
+    if let Some(session) = SESSION.lock().as_mut().filter(|s| s.state == PracticeState::AwaitingAnswer) {
+        if !session.heard.is_empty() {
+            session.heard.push(' ');
+        }
+        session.heard.push_str(text.trim());
+    }
+}
+
+fn level_name(level: u8) -> &'static str {
+    match level {
+        1 => "entry-level",
+        2 => "easy",
+        3 => "intermediate",
+        4 => "advanced",
+        _ => "expert",
+    }
+}
+
+async fn plan_questions(state: &AppState, config: &PracticeConfig, count: usize) -> Result<Vec<(String, Option<QuestionTopic>)>, String> {
+    let topics = QuestionTopic::ALL.iter().map(|t| t.as_str()).collect::<Vec<_>>().join(", ");
+    let user_prompt = if config.is_adaptive() {
+        let profile = crate::practice_profile::load(config.profile());
+        let plan = profile
+            .plan(count)
+            .iter()
+            .map(|&(topic, n)| format!("- {} {} question(s) at {} difficulty", n, topic.as_str(), level_name(profile.stats(topic).level)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!(
+            "Plan {} interview questions for this role with this topic mix, tailored to the candidate's \
+             practice history:\n{}\nOrder them as you would ask them. Reply with a JSON array only: \
+             [{{\"question\": string, \"topic\": one of {}}}]",
+            count, plan, topics
+        )
+    } else {
+        format!(
+            "Plan {} interview questions for this role, mixing behavioral and role-specific technical \
+             questions in the order you would ask them. Reply with a JSON array only: \
+             [{{\"question\": string, \"topic\": one of {}}}]",
+            count, topics
+        )
+    };
+    let reply = state
+        .complete(&config.provider, &config.model, &interviewer_prompt(config), &user_prompt)
+        .await?;
+    let planned: Vec<PlannedReply> = extract_json(&reply, '[', ']')?;
+    let questions: Vec<(String, Option<QuestionTopic>)> = planned
+        .into_iter()
+        .map(|p| (p.question.trim().to_string(), p.topic.as_deref().and_then(QuestionTopic::parse)))
+        .filter(|(q, _)| !q.is_empty())
+        .take(count)
+        .collect();
+    if questions.is_empty() {
+        return Err("The interviewer did not produce any questions".to_string());
+    }
+    Ok(questions)
+}
+
+async fn evaluate(state: &AppState, config: &PracticeConfig, turn: &PracticeTurn, allow_follow_up: bool) -> Result<EvaluationReply, String> {
+    let user_prompt = format!(
+        "Question: {}\n\nCandidate's answer (transcribed speech, may contain recognition errors):\n{}\n\n\
+         Score the answer from 1 to 10 and give two or three sentences of specific feedback. {}\
+         Reply with JSON only: {{\"score\": number, \"feedback\": string, \"follow_up\": string or null}}",
+        turn.question,
+        turn.answer.as_deref().unwrap_or_default(),
+        if allow_follow_up {
+            "If a natural follow-up question would probe a gap or claim in the answer, include it; otherwise use null. "
+        } else {
+            "Do not ask a follow-up; use null. "
+        }
+    );
+    let reply = state
+        .complete(&config.provider, &config.model, &interviewer_prompt(config), &user_prompt)
+        .await?;
+    let mut reply: EvaluationReply = extract_json(&reply, '{', '}')?;
+    reply.score = reply.score.clamp(1, 10);
+    if !allow_follow_up {
+        reply.follow_up = None;
+    }
+    Ok(reply)
+}
+
+async fn build_report(state: &AppState, session: &PracticeSession) -> PracticeReport {
+    let answered: Vec<&PracticeTurn> = session.turns.iter().filter(|t| t.score.is_some()).collect();
+    let overall_score = if answered.is_empty() {
+        0.0
+    } else {
+        answered.iter().filter_map(|t| t.score).map(f32::from).sum::<f32>() / answered.len() as f32
+    };
+
+    let transcript = answered
+        .iter()
+        .map(|t| {
+            format!(
+                "Q: {}\nA: {}\nScore: {}/10\nFeedback: {}",
+                t.question,
+                t.answer.as_deref().unwrap_or_default(),
+                t.score.unwrap_or_default(),
+                t.feedback.as_deref().unwrap_or_default()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    let user_prompt = format!(
+        "Here is the full mock interview:\n\n{}\n\nWrite a short overall assessment. Reply with JSON only: \
+         {{\"summary\": string, \"strengths\": [string], \"improvements\": [string]}}",
+        transcript
+    );
+
+    let reply = if answered.is_empty() {
+        Err("No answers were given".to_string())
+    } else {
+        match state
+            .complete(&session.config.provider, &session.config.model, &interviewer_prompt(&session.config), &user_prompt)
+            .await
+        {
+            Ok(reply) => extract_json::<ReportReply>(&reply, '{', '}'),
+            Err(e) => Err(e),
+        }
+    };
+    let reply = reply.unwrap_or_else(|e| {
+        warn!("Practice report summary unavailable: {}", e);
+        ReportReply {
+            summary: format!("Answered {} question(s) with an average score of {:.1}/10.", answered.len(), overall_score),
+            strengths: Vec::new(),
+            improvements: Vec::new(),
+        }
+    });
+
+    PracticeReport {
+        session_id: session.id.clone(),
+        company: session.config.company.clone(),
+        position: session.config.position.clone(),
+        profile: Some(session.config.profile().to_string()),
+        started_at: session.started_at.clone(),
+        completed_at: chrono::Utc::now().to_rfc3339(),
+        overall_score,
+        summary: reply.summary,
+        strengths: reply.strengths,
+        improvements: reply.improvements,
+        turns: session.turns.clone(),
+    }
+}
+
+/// Start a practice session and return the first question
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn start_practice_session(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    config: PracticeConfig,
+) -> Response<PracticeTurn> {
+    envelope::respond("start_practice_session", async move {
+        if SESSION.lock().is_some() {
+            return Err("A practice session is already running".into());
+        }
+
+        let mut config = config;
+        {
+            let context = state.interview_context.lock();
+            config.company = config.company.or_else(|| context.company.clone());
+            config.position = config.position.or_else(|| context.position.clone());
+            config.job_description = config.job_description.or_else(|| context.job_description.clone());
+            config.difficulty = config.difficulty.or_else(|| context.difficulty_level.clone());
+        }
+        let count = config.question_count.unwrap_or(DEFAULT_QUESTION_COUNT).clamp(1, MAX_QUESTION_COUNT);
+        let (planned_questions, planned_topics): (Vec<String>, Vec<Option<QuestionTopic>>) =
+            plan_questions(&state, &config, count).await?.into_iter().unzip();
+
+        let first = PracticeTurn {
+            kind: TurnKind::Question,
+            question: planned_questions[0].clone(),
+            topic: planned_topics[0],
+            answer: None,
+            score: None,
+            feedback: None,
+        };
+        let session = PracticeSession {
+            id: uuid::Uuid::new_v4().to_string(),
+            config,
+            state: PracticeState::AwaitingAnswer,
+            planned_questions,
+            planned_topics,
+            question_index: 0,
+            follow_ups_asked: 0,
+            turns: vec![first.clone()],
+            heard: String::new(),
+            started_at: chrono::Utc::now().to_rfc3339(),
+        };
+
+        let mut current = SESSION.lock();
+        if current.is_some() {
+            return Err("A practice session is already running".into());
+        }
+        info!("🎯 Practice session {} started with {} questions", session.id, session.planned_questions.len());
+        crate::telemetry::record_feature("practice_session");
+        emit(&app_handle, &session);
+        *current = Some(session);
+        Ok(first)
+    })
+    .await
+}
+
+/// Answer the open question; without `answer` the transcripts heard since it was asked are used
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn submit_practice_answer(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    answer: Option<String>,
+) -> Response<PracticeStep> {
+    envelope::respond("submit_practice_answer", async move {
+        let (config, turn, allow_follow_up) = {
+            let mut guard = SESSION.lock();
+            let session = guard.as_mut().ok_or("No practice session is running")?;
+            if session.state != PracticeState::AwaitingAnswer {
+                return Err("The current answer is already being evaluated".into());
+            }
+            let answer = answer
+                .filter(|a| !a.trim().is_empty())
+                .unwrap_or_else(|| std::mem::take(&mut session.heard));
+            if answer.trim().is_empty() {
+                return Err("No answer was typed or heard yet".into());
+            }
+            session.heard.clear();
+            session.state = PracticeState::Evaluating;
+            let max_follow_ups = session.config.max_follow_ups.unwrap_or(1);
+            let turn = session.turns.last_mut().ok_or("Practice session has no open question")?;
+            turn.answer = Some(answer);
+            (session.config.clone(), turn.clone(), session.follow_ups_asked < max_follow_ups)
+        };
+
+        let evaluation = evaluate(&state, &config, &turn, allow_follow_up).await;
+
+        let mut guard = SESSION.lock();
+        let session = guard.as_mut().ok_or("Practice session ended during evaluation")?;
+        let reply = match evaluation {
+            Ok(reply) => reply,
+            Err(e) => {
+                // Let the candidate retry the same answer
+                session.state = PracticeState::AwaitingAnswer;
+                return Err(e.into());
+            }
+        };
+        if let Some(turn) = session.turns.last_mut() {
+            turn.score = Some(reply.score);
+            turn.feedback = Some(reply.feedback.clone());
+        }
+        if let Some(topic) = turn.topic.filter(|_| session.config.is_adaptive()) {
+            if let Err(e) = crate::practice_profile::record(session.config.profile(), topic, reply.score) {
+                warn!("Failed to update practice profile: {}", e);
+            }
+        }
+
+        let next = match reply.follow_up.filter(|q| !q.trim().is_empty()) {
+            Some(follow_up) => {
+                session.follow_ups_asked += 1;
+                Some(PracticeTurn {
+                    kind: TurnKind::FollowUp,
+                    question: follow_up,
+                    topic: turn.topic,
+                    answer: None,
+                    score: None,
+                    feedback: None,
+                })
+            }
+            None => session.advance(),
+        };
+
+        session.state = match &next {
+            Some(turn) => {
+                session.turns.push(turn.clone());
+                PracticeState::AwaitingAnswer
+            }
+            None => PracticeState::Completed,
+        };
+        emit(&app_handle, session);
+
+        Ok(PracticeStep {
+            evaluation: PracticeEvaluation {
+                score: reply.score,
+                feedback: reply.feedback,
+            },
+            completed: next.is_none(),
+            next_question: next,
+        })
+    })
+    .await
+}
+
+/// Skip the open question without scoring it
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn skip_practice_question(app_handle: AppHandle) -> Response<Option<PracticeTurn>> {
+    envelope::respond("skip_practice_question", async move {
+        let mut guard = SESSION.lock();
+        let session = guard.as_mut().ok_or("No practice session is running")?;
+        if session.state != PracticeState::AwaitingAnswer {
+            return Err("The current answer is already being evaluated".into());
+        }
+        session.heard.clear();
+        let next = session.advance();
+        match &next {
+            Some(turn) => session.turns.push(turn.clone()),
+            None => session.state = PracticeState::Completed,
+        }
+        emit(&app_handle, session);
+        Ok(next)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn get_practice_session() -> Response<Option<PracticeSession>> {
+    envelope::respond("get_practice_session", async move {
+        Ok(SESSION.lock().clone())
+    })
+    .await
+}
+
+/// End the session (finished or not) and produce its report
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn end_practice_session(app_handle: AppHandle, state: State<'_, AppState>) -> Response<PracticeReport> {
+    envelope::respond("end_practice_session", async move {
+        let mut session = SESSION.lock().take().ok_or("No practice session is running")?;
+        session.state = PracticeState::Completed;
+        emit(&app_handle, &session);
+
+        let report = build_report(&state, &session).await;
+        save_report(&report)?;
+        info!("🎯 Practice session {} finished: {:.1}/10", report.session_id, report.overall_score);
+        Ok(report)
+    })
+    .await
+}
+
+/// Saved reports, newest first
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn list_practice_reports() -> Response<Vec<PracticeReport>> {
+    envelope::respond("list_practice_reports", async move {
+        let entries = match std::fs::read_dir(reports_dir()) {
+            Ok(entries) => entries,
+            Err(_) => return Ok(Vec::new()),
+        };
+        let mut reports: Vec<PracticeReport> = entries
+            .flatten()
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|entry| std::fs::read(entry.path()).ok())
+            .filter_map(|body| serde_json::from_slice(&body).ok())
+            .collect();
+        reports.sort_by(|a, b| b.completed_at.cmp(&a.completed_at));
+        Ok(reports)
+    })
+    .await
+}
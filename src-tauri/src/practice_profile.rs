@@ -0,0 +1,163 @@
+// Adaptive practice: per-topic performance and difficulty, persisted per profile
+// Every scored practice answer updates its topic's rating (a moving average of scores) and difficulty
+// level. When a new practice session is planned, weaker topics get more of the questions and each
+// topic is asked at its current level. Profiles live in %APPDATA%\MockMate\practice\profiles.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::question_topic::QuestionTopic;
+use crate::envelope::{self, Response};
+
+pub const DEFAULT_PROFILE: &str = "default";
+/// Weight of the newest score in a topic's rating
+const RATING_WEIGHT: f32 = 0.3;
+/// Rating assumed for topics that have not been practiced yet
+const UNSEEN_RATING: f32 = 5.5;
+const MIN_LEVEL: u8 = 1;
+const MAX_LEVEL: u8 = 5;
+const START_LEVEL: u8 = 2;
+/// Scores at or above / at or below these move the topic's level up / down
+const LEVEL_UP_SCORE: u8 = 8;
+const LEVEL_DOWN_SCORE: u8 = 4;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TopicStats {
+    /// Moving average of scores, 1-10
+    pub rating: f32,
+    /// Difficulty questions are asked at, 1 (easiest) to 5
+    pub level: u8,
+    pub answered: u32,
+    pub last_score: Option<u8>,
+}
+
+impl Default for TopicStats {
+    fn default() -> Self {
+        Self {
+            rating: UNSEEN_RATING,
+            level: START_LEVEL,
+            answered: 0,
+            last_score: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PracticeProfile {
+    pub name: String,
+    pub topics: BTreeMap<QuestionTopic, TopicStats>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl PracticeProfile {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            topics: BTreeMap::new(),
+            updated_at: None,
+        }
+    }
+
+    pub fn stats(&self, topic: QuestionTopic) -> TopicStats {
+        self.topics.get(&topic).cloned().unwrap_or_default()
+    }
+
+    /// Split `count` questions across topics, weaker topics first. Every topic keeps a small share
+    /// so strong areas are still revisited.
+    pub fn plan(&self, count: usize) -> Vec<(QuestionTopic, usize)> {
+        let weights: Vec<(QuestionTopic, f32)> = QuestionTopic::ALL
+            .iter()
+            .map(|&topic| (topic, (11.0 - self.stats(topic).rating).max(1.0)))
+            .collect();
+        let total: f32 = weights.iter().map(|(_, w)| w).sum();
+        let shares: Vec<(QuestionTopic, f32)> = weights.iter().map(|&(t, w)| (t, w / total * count as f32)).collect();
+
+        // Largest remainder so the counts add up to exactly `count`
+        let mut plan: Vec<(QuestionTopic, usize)> = shares.iter().map(|&(t, s)| (t, s.floor() as usize)).collect();
+        let mut remainders: Vec<(usize, f32)> = shares.iter().enumerate().map(|(i, &(_, s))| (i, s.fract())).collect();
+        remainders.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let assigned: usize = plan.iter().map(|(_, n)| n).sum();
+        for &(i, _) in remainders.iter().take(count.saturating_sub(assigned)) {
+            plan[i].1 += 1;
+        }
+        plan.retain(|(_, n)| *n > 0);
+        plan.sort_by_key(|&(_, n)| std::cmp::Reverse(n));
+        plan
+    }
+
+    fn record(&mut self, topic: QuestionTopic, score: u8) {
+        let stats = self.topics.entry(topic).or_default();
+        stats.rating = if stats.answered == 0 {
+            f32::from(score)
+        } else {
+            stats.rating * (1.0 - RATING_WEIGHT) + f32::from(score) * RATING_WEIGHT
+        };
+        if score >= LEVEL_UP_SCORE {
+            stats.level = (stats.level + 1).min(MAX_LEVEL);
+        } else if score <= LEVEL_DOWN_SCORE {
+            stats.level = stats.level.saturating_sub(1).max(MIN_LEVEL);
+        }
+        stats.answered += 1;
+        stats.last_score = Some(score);
+        self.updated_at = Some(Utc::now());
+    }
+}
+
+fn profile_path(name: &str) -> PathBuf {
+    let file: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("practice")
+        .join("profiles")
+        .join(format!("{}.json", file))
+}
+
+pub fn load(name: &str) -> PracticeProfile {
+    std::fs::read(profile_path(name))
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_else(|| PracticeProfile::new(name))
+}
+
+fn save(profile: &PracticeProfile) -> Result<(), String> {
+    let path = profile_path(&profile.name);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(profile).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| e.to_string())
+}
+
+/// Fold one scored answer into the profile
+pub fn record(name: &str, topic: QuestionTopic, score: u8) -> Result<(), String> {
+    let mut profile = load(name);
+    profile.record(topic, score);
+    save(&profile)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_practice_profile(profile: Option<String>) -> Response<PracticeProfile> {
+    envelope::respond("get_practice_profile", async move {
+        Ok(load(profile.as_deref().unwrap_or(DEFAULT_PROFILE)))
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn reset_practice_profile(profile: Option<String>) -> Response<PracticeProfile> {
+    envelope::respond("reset_practice_profile", async move {
+        let profile = PracticeProfile::new(profile.as_deref().unwrap_or(DEFAULT_PROFILE));
+        save(&profile)?;
+        Ok(profile)
+    })
+    .await
+}
@@ -0,0 +1,109 @@
+// Local-only privacy mode for MockMate
+// When enabled, nothing leaves the machine: outbound HTTP to non-loopback hosts, the Deepgram
+// socket and the Postgres pool all refuse to connect with a "blocked by local-only mode" error.
+// An administrator can force the mode on through managed policy; the user's own choice is kept but
+// cannot turn it off then.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use crate::envelope::{self, IntoResult, Response};
+use crate::error::{AppError, ErrorCode};
+
+pub const BLOCKED_MESSAGE: &str = "blocked by local-only mode";
+
+static LOCAL_ONLY: Lazy<AtomicBool> = Lazy::new(|| AtomicBool::new(load_settings().local_only));
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct PrivacySettings {
+    local_only: bool,
+}
+
+fn settings_path() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("privacy.json")
+}
+
+fn load_settings() -> PrivacySettings {
+    std::fs::read(settings_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn save_settings(settings: &PrivacySettings) -> Result<(), String> {
+    let path = settings_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(settings).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| e.to_string())
+}
+
+/// Whether cloud calls are currently disabled
+pub fn is_local_only() -> bool {
+    user_local_only() || crate::managed_policy::forces_local_only()
+}
+
+/// The user's own choice, before managed policy
+pub fn user_local_only() -> bool {
+    LOCAL_ONLY.load(Ordering::Relaxed)
+}
+
+/// Loopback hosts stay reachable so local services keep working in local-only mode
+pub fn is_loopback_host(host: &str) -> bool {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    host.eq_ignore_ascii_case("localhost")
+        || host
+            .parse::<std::net::IpAddr>()
+            .map(|ip| ip.is_loopback())
+            .unwrap_or(false)
+}
+
+/// Fail with a "blocked by local-only mode" error if `service` would leave the machine
+pub fn ensure_cloud_allowed(service: &str) -> Result<(), String> {
+    if is_local_only() {
+        warn!("🔒 {} request {}", service, BLOCKED_MESSAGE);
+        Err(format!("{} is {}", service, BLOCKED_MESSAGE))
+    } else {
+        Ok(())
+    }
+}
+
+/// Turn local-only mode on or off; the choice persists across restarts
+#[tauri::command]
+#[specta::specta]
+pub async fn set_local_only_mode(app_handle: AppHandle, enabled: bool) -> Response<bool> {
+    envelope::respond("set_local_only_mode", async move {
+        if !enabled && crate::managed_policy::forces_local_only() {
+            return Err(AppError::new(ErrorCode::Unsupported, "Local-only mode is enforced by your administrator"));
+        }
+        save_settings(&PrivacySettings { local_only: enabled })?;
+        LOCAL_ONLY.store(enabled, Ordering::Relaxed);
+
+        if enabled {
+            // Drop open cloud connections so nothing keeps streaming after the switch
+            if let Err(e) = crate::deepgram_streaming::stop_deepgram_streaming(app_handle).await.into_result() {
+                warn!("Failed to stop Deepgram streaming for local-only mode: {}", e);
+            }
+        }
+
+        info!("🔒 Local-only mode {}", if enabled { "enabled - cloud calls blocked" } else { "disabled" });
+        Ok(enabled)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_local_only_mode() -> Response<bool> {
+    envelope::respond("get_local_only_mode", async move {
+        Ok(is_local_only())
+    })
+    .await
+}
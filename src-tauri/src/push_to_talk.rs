@@ -0,0 +1,335 @@
+// Push-to-talk and push-to-mute
+// By default every microphone sample goes to transcription. In push-to-talk mode the microphone is
+// only forwarded while the configured hotkey is held, in push-to-mute mode it is forwarded except
+// while the hotkey is held. The gate is an atomic read by the microphone processor for every
+// sample, so opening or closing it takes effect on the next sample; gated samples are replaced by
+// silence so the VAD ends the current segment and buffers keep their timing. The hotkey is watched
+// with rdev, which sees key releases (RegisterHotKey only reports presses), and the UI can hold the
+// gate with `set_ptt_held`. The mode and hotkey are stored in %APPDATA%\MockMate\push_to_talk.json,
+// and `ptt-state` is emitted whenever the gate or mode changes.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::{Once, OnceLock};
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use rdev::{Event, EventType, Key};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter};
+
+use crate::envelope::{self, Response};
+use crate::error::{AppError, ErrorCode};
+use crate::events;
+
+const DEFAULT_HOTKEY: &str = "F8";
+
+const MOD_CTRL: u8 = 1;
+const MOD_SHIFT: u8 = 2;
+const MOD_ALT: u8 = 4;
+const MOD_META: u8 = 8;
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+static CONFIG: Lazy<Mutex<PttConfig>> = Lazy::new(|| Mutex::new(load()));
+static HOTKEY: Lazy<Mutex<Option<Hotkey>>> = Lazy::new(|| Mutex::new(parse_hotkey(&CONFIG.lock().hotkey).ok()));
+static LISTENER: Once = Once::new();
+/// Modifier keys currently down, as MOD_* bits
+static MODIFIERS: AtomicU8 = AtomicU8::new(0);
+/// The hotkey is held on the keyboard
+static KEY_HELD: AtomicBool = AtomicBool::new(false);
+/// The push-to-talk button in the UI is held
+static UI_HELD: AtomicBool = AtomicBool::new(false);
+/// Whether microphone samples reach transcription right now
+static FORWARDING: AtomicBool = AtomicBool::new(true);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureMode {
+    /// The microphone is always forwarded
+    #[default]
+    Always,
+    /// Forwarded only while the hotkey is held
+    PushToTalk,
+    /// Forwarded except while the hotkey is held
+    PushToMute,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct PttConfig {
+    pub mode: CaptureMode,
+    /// e.g. "F8", "Ctrl+Space" or "RightCtrl"
+    pub hotkey: String,
+}
+
+impl Default for PttConfig {
+    fn default() -> Self {
+        Self { mode: CaptureMode::Always, hotkey: DEFAULT_HOTKEY.to_string() }
+    }
+}
+
+/// Payload of `ptt-state`
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema, Type)]
+pub struct PttState {
+    pub mode: CaptureMode,
+    pub hotkey: String,
+    /// The hotkey or the UI button is held
+    pub held: bool,
+    /// Microphone audio is reaching transcription
+    pub forwarding: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Hotkey {
+    modifiers: u8,
+    key: Key,
+}
+
+fn config_path() -> PathBuf {
+    crate::portable::data_root().join("MockMate").join("push_to_talk.json")
+}
+
+fn load() -> PttConfig {
+    std::fs::read(config_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn save(config: &PttConfig) -> Result<(), String> {
+    let path = config_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(config).map_err(|e| e.to_string())?;
+    std::fs::write(path, body).map_err(|e| e.to_string())
+}
+
+fn parse_key(name: &str) -> Option<Key> {
+    let key = match name.to_ascii_lowercase().as_str() {
+        "f1" => Key::F1,
+        "f2" => Key::F2,
+        "f3" => Key::F3,
+        "f4" => Key::F4,
+        "f5" => Key::F5,
+        "f6" => Key::F6,
+        "f7" => Key::F7,
+        "f8" => Key::F8,
+        "f9" => Key::F9,
+        "f10" => Key::F10,
+        "f11" => Key::F11,
+        "f12" => Key::F12,
+        "space" => Key::Space,
+        "tab" => Key::Tab,
+        "capslock" => Key::CapsLock,
+        "`" | "backquote" => Key::BackQuote,
+        "insert" => Key::Insert,
+        "home" => Key::Home,
+        "end" => Key::End,
+        "pageup" => Key::PageUp,
+        "pagedown" => Key::PageDown,
+        "pause" => Key::Pause,
+        "scrolllock" => Key::ScrollLock,
+        "rightctrl" => Key::ControlRight,
+        "rightshift" => Key::ShiftRight,
+        "altgr" | "rightalt" => Key::AltGr,
+        "0" => Key::Num0,
+        "1" => Key::Num1,
+        "2" => Key::Num2,
+        "3" => Key::Num3,
+        "4" => Key::Num4,
+        "5" => Key::Num5,
+        "6" => Key::Num6,
+        "7" => Key::Num7,
+        "8" => Key::Num8,
+        "9" => Key::Num9,
+        "a" => Key::KeyA,
+        "b" => Key::KeyB,
+        "c" => Key::KeyC,
+        "d" => Key::KeyD,
+        "e" => Key::KeyE,
+        "f" => Key::KeyF,
+        "g" => Key::KeyG,
+        "h" => Key::KeyH,
+        "i" => Key::KeyI,
+        "j" => Key::KeyJ,
+        "k" => Key::KeyK,
+        "l" => Key::KeyL,
+        "m" => Key::KeyM,
+        "n" => Key::KeyN,
+        "o" => Key::KeyO,
+        "p" => Key::KeyP,
+        "q" => Key::KeyQ,
+        "r" => Key::KeyR,
+        "s" => Key::KeyS,
+        "t" => Key::KeyT,
+        "u" => Key::KeyU,
+        "v" => Key::KeyV,
+        "w" => Key::KeyW,
+        "x" => Key::KeyX,
+        "y" => Key::KeyY,
+        "z" => Key::KeyZ,
+        _ => return None,
+    };
+    Some(key)
+}
+
+/// Parse "Ctrl+Shift+Space"-style hotkeys: any modifiers followed by one key
+fn parse_hotkey(hotkey: &str) -> Result<Hotkey, String> {
+    let parts: Vec<&str> = hotkey.split('+').map(str::trim).collect();
+    let (key_name, modifier_names) = parts.split_last().filter(|(key, _)| !key.is_empty()).ok_or("Hotkey is empty")?;
+    let mut modifiers = 0;
+    for name in modifier_names {
+        modifiers |= match name.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => MOD_CTRL,
+            "shift" => MOD_SHIFT,
+            "alt" => MOD_ALT,
+            "win" | "meta" | "super" | "cmd" => MOD_META,
+            _ => return Err(format!("Unknown modifier '{}' in hotkey {}", name, hotkey)),
+        };
+    }
+    let key = parse_key(key_name).ok_or_else(|| format!("Unknown key '{}' in hotkey {}", key_name, hotkey))?;
+    Ok(Hotkey { modifiers, key })
+}
+
+fn modifier_bit(key: Key) -> u8 {
+    match key {
+        Key::ControlLeft | Key::ControlRight => MOD_CTRL,
+        Key::ShiftLeft | Key::ShiftRight => MOD_SHIFT,
+        Key::Alt => MOD_ALT,
+        Key::MetaLeft | Key::MetaRight => MOD_META,
+        _ => 0,
+    }
+}
+
+/// Whether microphone samples should reach transcription; read for every sample
+#[inline]
+pub fn is_forwarding() -> bool {
+    FORWARDING.load(Ordering::Relaxed)
+}
+
+fn state() -> PttState {
+    let config = CONFIG.lock();
+    PttState {
+        mode: config.mode,
+        hotkey: config.hotkey.clone(),
+        held: KEY_HELD.load(Ordering::Relaxed) || UI_HELD.load(Ordering::Relaxed),
+        forwarding: is_forwarding(),
+    }
+}
+
+/// Recompute the gate and emit `ptt-state` when it or the mode changed
+fn update(force_emit: bool) {
+    let mode = CONFIG.lock().mode;
+    let held = KEY_HELD.load(Ordering::Relaxed) || UI_HELD.load(Ordering::Relaxed);
+    let forwarding = match mode {
+        CaptureMode::Always => true,
+        CaptureMode::PushToTalk => held,
+        CaptureMode::PushToMute => !held,
+    };
+    let changed = FORWARDING.swap(forwarding, Ordering::Relaxed) != forwarding;
+    if changed || force_emit {
+        if let Some(app_handle) = APP_HANDLE.get() {
+            if let Err(e) = app_handle.emit(events::PTT_STATE, state()) {
+                warn!("Failed to emit {}: {}", events::PTT_STATE, e);
+            }
+        }
+    }
+}
+
+fn on_key_event(event: Event) {
+    match event.event_type {
+        EventType::KeyPress(key) => {
+            MODIFIERS.fetch_or(modifier_bit(key), Ordering::Relaxed);
+            let Some(hotkey) = *HOTKEY.lock() else { return };
+            let modifiers = MODIFIERS.load(Ordering::Relaxed) & !modifier_bit(key);
+            // Key repeat sends presses while held; only the first one changes anything
+            if key == hotkey.key && modifiers == hotkey.modifiers && !KEY_HELD.swap(true, Ordering::Relaxed) {
+                update(false);
+            }
+        }
+        EventType::KeyRelease(key) => {
+            MODIFIERS.fetch_and(!modifier_bit(key), Ordering::Relaxed);
+            // Releasing a modifier first keeps the gate as it is until the key itself goes up
+            let is_hotkey = HOTKEY.lock().is_some_and(|hotkey| hotkey.key == key);
+            if is_hotkey && KEY_HELD.swap(false, Ordering::Relaxed) {
+                update(false);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Start watching the keyboard; rdev cannot stop listening, so this happens once per process
+fn ensure_listener() {
+    LISTENER.call_once(|| {
+        let spawned = std::thread::Builder::new().name("ptt-hotkey".into()).spawn(|| {
+            info!("⌨️ Listening for the push-to-talk hotkey");
+            if let Err(e) = rdev::listen(on_key_event) {
+                warn!("Push-to-talk hotkey listener failed: {:?}", e);
+            }
+        });
+        if let Err(e) = spawned {
+            warn!("Failed to start the push-to-talk hotkey listener: {}", e);
+        }
+    });
+}
+
+pub fn init(app_handle: AppHandle) {
+    if APP_HANDLE.set(app_handle).is_err() {
+        return;
+    }
+    if CONFIG.lock().mode != CaptureMode::Always {
+        ensure_listener();
+    }
+    update(false);
+}
+
+/// Current capture mode, hotkey and gate
+#[tauri::command]
+#[specta::specta]
+pub fn get_push_to_talk() -> Response<PttState> {
+    envelope::respond_sync("get_push_to_talk", || Ok(state()))
+}
+
+/// Change the capture mode and, when given, the hotkey
+#[tauri::command]
+#[specta::specta]
+pub fn set_push_to_talk(mode: CaptureMode, hotkey: Option<String>) -> Response<PttState> {
+    envelope::respond_sync("set_push_to_talk", || {
+        let parsed = match &hotkey {
+            Some(hotkey) => Some(parse_hotkey(hotkey).map_err(|e| AppError::new(ErrorCode::InvalidInput, e))?),
+            None => None,
+        };
+        {
+            let mut config = CONFIG.lock();
+            config.mode = mode;
+            if let (Some(hotkey), Some(parsed)) = (hotkey, parsed) {
+                config.hotkey = hotkey;
+                *HOTKEY.lock() = Some(parsed);
+                KEY_HELD.store(false, Ordering::Relaxed);
+            }
+            save(&config)?;
+            info!("🎙️ Microphone capture mode set to {:?} (hotkey {})", config.mode, config.hotkey);
+        }
+        if mode != CaptureMode::Always {
+            ensure_listener();
+        }
+        update(true);
+        Ok(state())
+    })
+}
+
+/// Hold or release the gate from the UI, for a push-to-talk button
+#[tauri::command]
+#[specta::specta]
+pub fn set_ptt_held(held: bool) -> Response<PttState> {
+    envelope::respond_sync("set_ptt_held", || {
+        if UI_HELD.swap(held, Ordering::Relaxed) != held {
+            update(false);
+        }
+        Ok(state())
+    })
+}
@@ -0,0 +1,568 @@
+// Local question bank with tags and spaced repetition
+// Holds curated questions plus every question saved during an interview session, with tags,
+// difficulty and the best answer so far. A Leitner-box scheduler decides when each question is due
+// for rehearsal; `get_rehearsal_queue` surfaces everything due before an upcoming interview. The bank
+// is a single JSON file in %APPDATA%\MockMate and never leaves the machine.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Duration, Utc};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::question_topic::QuestionTopic;
+use crate::cancellation;
+use crate::envelope::{self, Response};
+
+/// Days until the next rehearsal for each Leitner box; a question climbs one box per recall
+const BOX_INTERVAL_DAYS: [i64; 6] = [1, 2, 4, 8, 16, 32];
+const EXPORT_VERSION: u32 = 1;
+
+static BANK: Lazy<Mutex<QuestionBank>> = Lazy::new(|| Mutex::new(load_bank()));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    /// Lenient parse of the difficulty strings used elsewhere ("junior", "senior", "advanced", ...)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "easy" | "entry" | "junior" | "beginner" => Some(Difficulty::Easy),
+            "medium" | "mid" | "intermediate" => Some(Difficulty::Medium),
+            "hard" | "senior" | "advanced" | "expert" => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum QuestionSource {
+    /// Added or imported by the user
+    Curated,
+    /// Saved automatically when it came up in an interview session
+    Asked,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RehearsalState {
+    /// Leitner box, 0 = rehearse tomorrow
+    pub box_index: usize,
+    pub due_at: DateTime<Utc>,
+    pub last_reviewed_at: Option<DateTime<Utc>>,
+    pub reviews: u32,
+    pub lapses: u32,
+}
+
+impl RehearsalState {
+    fn new(now: DateTime<Utc>) -> Self {
+        // New questions are due straight away
+        Self {
+            box_index: 0,
+            due_at: now,
+            last_reviewed_at: None,
+            reviews: 0,
+            lapses: 0,
+        }
+    }
+
+    fn record(&mut self, recalled: bool, now: DateTime<Utc>) {
+        if recalled {
+            self.box_index = (self.box_index + 1).min(BOX_INTERVAL_DAYS.len() - 1);
+        } else {
+            self.box_index = 0;
+            self.lapses += 1;
+        }
+        self.reviews += 1;
+        self.last_reviewed_at = Some(now);
+        self.due_at = now + Duration::days(BOX_INTERVAL_DAYS[self.box_index]);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct BankQuestion {
+    pub id: String,
+    pub question: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub difficulty: Option<Difficulty>,
+    pub best_answer: Option<String>,
+    pub source: QuestionSource,
+    #[serde(default)]
+    pub times_asked: u32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub rehearsal: RehearsalState,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct QuestionBank {
+    questions: Vec<BankQuestion>,
+}
+
+impl QuestionBank {
+    fn find_by_text(&mut self, text: &str) -> Option<&mut BankQuestion> {
+        let key = normalize(text);
+        self.questions.iter_mut().find(|q| normalize(&q.question) == key)
+    }
+
+    fn get_mut(&mut self, id: &str) -> Result<&mut BankQuestion, String> {
+        self.questions
+            .iter_mut()
+            .find(|q| q.id == id)
+            .ok_or_else(|| format!("Question {} not found", id))
+    }
+}
+
+/// New or edited question from the UI or an import file
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct QuestionInput {
+    pub question: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub difficulty: Option<Difficulty>,
+    pub best_answer: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct QuestionQuery {
+    /// Questions must carry every listed tag
+    pub tags: Option<Vec<String>>,
+    pub difficulty: Option<Difficulty>,
+    pub source: Option<QuestionSource>,
+    /// Questions tagged with this topic (saved questions are tagged when categorized)
+    pub topic: Option<QuestionTopic>,
+    /// Case-insensitive substring match on the question and best answer
+    pub search: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TagCount {
+    pub tag: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct ImportSummary {
+    pub added: usize,
+    pub updated: usize,
+    pub skipped: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ExportFile {
+    version: u32,
+    exported_at: DateTime<Utc>,
+    questions: Vec<BankQuestion>,
+}
+
+/// Import files may be a full export or a plain list of questions
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ImportFile {
+    Export { questions: Vec<QuestionInput> },
+    List(Vec<QuestionInput>),
+}
+
+fn bank_path() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("question_bank.json")
+}
+
+fn load_bank() -> QuestionBank {
+    std::fs::read(bank_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn save_bank(bank: &QuestionBank) -> Result<(), String> {
+    let path = bank_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(bank).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| e.to_string())
+}
+
+/// Key used to spot the same question worded with different case, spacing or punctuation
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn clean_tags(tags: &[String]) -> Vec<String> {
+    let mut tags: Vec<String> = tags
+        .iter()
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+fn new_question(text: &str, input: &QuestionInput, source: QuestionSource, now: DateTime<Utc>) -> BankQuestion {
+    BankQuestion {
+        id: uuid::Uuid::new_v4().to_string(),
+        question: text.trim().to_string(),
+        tags: clean_tags(input.tags.as_deref().unwrap_or_default()),
+        difficulty: input.difficulty,
+        best_answer: input.best_answer.clone().filter(|a| !a.trim().is_empty()),
+        source,
+        times_asked: u32::from(source == QuestionSource::Asked),
+        created_at: now,
+        updated_at: now,
+        rehearsal: RehearsalState::new(now),
+    }
+}
+
+/// Merge `input` into an existing question: tags are unioned, other fields only fill or replace
+/// when provided
+fn merge(question: &mut BankQuestion, input: &QuestionInput, now: DateTime<Utc>) {
+    if let Some(tags) = &input.tags {
+        let mut merged = question.tags.clone();
+        merged.extend(tags.iter().cloned());
+        question.tags = clean_tags(&merged);
+    }
+    if input.difficulty.is_some() {
+        question.difficulty = input.difficulty;
+    }
+    if let Some(answer) = input.best_answer.as_ref().filter(|a| !a.trim().is_empty()) {
+        question.best_answer = Some(answer.clone());
+    }
+    question.updated_at = now;
+}
+
+/// Add a question that came up in a live session, or bump its count if it is already banked
+pub fn record_asked(question: &str, category: &str, topic: QuestionTopic, difficulty: &str) {
+    if question.trim().is_empty() {
+        return;
+    }
+    let now = Utc::now();
+    let input = QuestionInput {
+        question: None,
+        tags: Some(vec![category.to_string(), topic.as_str().to_string()]),
+        difficulty: Difficulty::parse(difficulty),
+        best_answer: None,
+    };
+
+    let mut bank = BANK.lock();
+    match bank.find_by_text(question) {
+        Some(existing) => {
+            existing.times_asked += 1;
+            merge(existing, &input, now);
+        }
+        None => {
+            let question = new_question(question, &input, QuestionSource::Asked, now);
+            bank.questions.push(question);
+        }
+    }
+    if let Err(e) = save_bank(&bank) {
+        warn!("Failed to save question bank: {}", e);
+    }
+}
+
+fn matches(question: &BankQuestion, query: &QuestionQuery) -> bool {
+    if let Some(tags) = &query.tags {
+        if !clean_tags(tags).iter().all(|tag| question.tags.contains(tag)) {
+            return false;
+        }
+    }
+    if query.difficulty.is_some() && question.difficulty != query.difficulty {
+        return false;
+    }
+    if query.source.is_some_and(|source| question.source != source) {
+        return false;
+    }
+    if query.topic.is_some_and(|topic| !question.tags.iter().any(|tag| tag == topic.as_str())) {
+        return false;
+    }
+    if let Some(search) = query.search.as_ref().map(|s| s.to_lowercase()).filter(|s| !s.is_empty()) {
+        let in_answer = question
+            .best_answer
+            .as_ref()
+            .is_some_and(|a| a.to_lowercase().contains(&search));
+        if !question.question.to_lowercase().contains(&search) && !in_answer {
+            return false;
+        }
+    }
+    true
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn add_bank_question(input: QuestionInput) -> Response<BankQuestion> {
+    envelope::respond("add_bank_question", async move {
+        let text = input
+            .question
+            .clone()
+            .filter(|q| !q.trim().is_empty())
+            .ok_or("Question text is required")?;
+        let now = Utc::now();
+        let mut bank = BANK.lock();
+        if bank.find_by_text(&text).is_some() {
+            return Err("This question is already in the bank".into());
+        }
+        let question = new_question(&text, &input, QuestionSource::Curated, now);
+        bank.questions.push(question.clone());
+        save_bank(&bank)?;
+        Ok(question)
+    })
+    .await
+}
+
+/// Edit a question; unlike imports, provided tags replace the existing ones
+#[tauri::command]
+#[specta::specta]
+pub async fn update_bank_question(id: String, input: QuestionInput) -> Response<BankQuestion> {
+    envelope::respond("update_bank_question", async move {
+        let mut bank = BANK.lock();
+        let question = bank.get_mut(&id)?;
+        if let Some(text) = input.question.as_ref().filter(|q| !q.trim().is_empty()) {
+            question.question = text.trim().to_string();
+        }
+        if let Some(tags) = &input.tags {
+            question.tags = clean_tags(tags);
+        }
+        question.difficulty = input.difficulty.or(question.difficulty);
+        if let Some(answer) = &input.best_answer {
+            question.best_answer = Some(answer.clone()).filter(|a| !a.trim().is_empty());
+        }
+        question.updated_at = Utc::now();
+        let question = question.clone();
+        save_bank(&bank)?;
+        Ok(question)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_bank_question(id: String) -> Response<()> {
+    envelope::respond("delete_bank_question", async move {
+        let mut bank = BANK.lock();
+        let before = bank.questions.len();
+        bank.questions.retain(|q| q.id != id);
+        if bank.questions.len() == before {
+            return Err(format!("Question {} not found", id).into());
+        }
+        Ok(save_bank(&bank)?)
+    })
+    .await
+}
+
+/// Questions matching the query, most often asked first
+#[tauri::command]
+#[specta::specta]
+pub async fn query_bank_questions(query: Option<QuestionQuery>) -> Response<Vec<BankQuestion>> {
+    envelope::respond("query_bank_questions", async move {
+        let query = query.unwrap_or_default();
+        let bank = BANK.lock();
+        let mut questions: Vec<BankQuestion> = bank.questions.iter().filter(|q| matches(q, &query)).cloned().collect();
+        questions.sort_by(|a, b| b.times_asked.cmp(&a.times_asked).then_with(|| a.question.cmp(&b.question)));
+        if let Some(limit) = query.limit {
+            questions.truncate(limit);
+        }
+        Ok(questions)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_bank_tags() -> Response<Vec<TagCount>> {
+    envelope::respond("list_bank_tags", async move {
+        let bank = BANK.lock();
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for tag in bank.questions.iter().flat_map(|q| q.tags.iter()) {
+            *counts.entry(tag).or_default() += 1;
+        }
+        Ok(counts
+            .into_iter()
+            .map(|(tag, count)| TagCount { tag: tag.to_string(), count })
+            .collect())
+    })
+    .await
+}
+
+/// Merge questions from a JSON file; existing questions (matched by text) gain the imported tags,
+/// difficulty and answer
+#[tauri::command]
+#[specta::specta]
+pub async fn import_question_bank(path: String) -> Response<ImportSummary> {
+    envelope::respond("import_question_bank", async move {
+        let body = std::fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        let inputs = match serde_json::from_slice::<ImportFile>(&body).map_err(|e| format!("Invalid question bank file: {}", e))? {
+            ImportFile::Export { questions } | ImportFile::List(questions) => questions,
+        };
+
+        let now = Utc::now();
+        let mut summary = ImportSummary::default();
+        let mut bank = BANK.lock();
+        for input in &inputs {
+            let Some(text) = input.question.as_ref().filter(|q| !q.trim().is_empty()) else {
+                summary.skipped += 1;
+                continue;
+            };
+            match bank.find_by_text(text) {
+                Some(existing) => {
+                    merge(existing, input, now);
+                    summary.updated += 1;
+                }
+                None => {
+                    let question = new_question(text, input, QuestionSource::Curated, now);
+                    bank.questions.push(question);
+                    summary.added += 1;
+                }
+            }
+        }
+        save_bank(&bank)?;
+        info!(
+            "📚 Imported question bank from {}: {} added, {} updated, {} skipped",
+            path, summary.added, summary.updated, summary.skipped
+        );
+        Ok(summary)
+    })
+    .await
+}
+
+/// Write the bank (or the questions matching `query`) to a JSON file; returns the number exported
+#[tauri::command]
+#[specta::specta]
+pub async fn export_question_bank(path: String, query: Option<QuestionQuery>, request_id: Option<String>) -> Response<usize> {
+    cancellation::run("export_question_bank", request_id, async move {
+        let query = query.unwrap_or_default();
+        let export = ExportFile {
+            version: EXPORT_VERSION,
+            exported_at: Utc::now(),
+            questions: BANK.lock().questions.iter().filter(|q| matches(q, &query)).cloned().collect(),
+        };
+        let body = serde_json::to_vec_pretty(&export).map_err(|e| e.to_string())?;
+        if let Some(dir) = Path::new(&path).parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        std::fs::write(&path, body).map_err(|e| format!("Failed to write {}: {}", path, e))?;
+        info!("📚 Exported {} questions to {}", export.questions.len(), path);
+        Ok(export.questions.len())
+    })
+    .await
+}
+
+/// Questions due for rehearsal by `before` (an RFC 3339 interview start; now when omitted),
+/// most overdue and most frequently forgotten first
+#[tauri::command]
+#[specta::specta]
+pub async fn get_rehearsal_queue(
+    before: Option<String>,
+    query: Option<QuestionQuery>,
+) -> Response<Vec<BankQuestion>> {
+    envelope::respond("get_rehearsal_queue", async move {
+        let cutoff = match before {
+            Some(before) => DateTime::parse_from_rfc3339(&before)
+                .map_err(|e| format!("Invalid date '{}': {}", before, e))?
+                .with_timezone(&Utc),
+            None => Utc::now(),
+        };
+        let query = query.unwrap_or_default();
+        let bank = BANK.lock();
+        let mut due: Vec<BankQuestion> = bank
+            .questions
+            .iter()
+            .filter(|q| q.rehearsal.due_at <= cutoff && matches(q, &query))
+            .cloned()
+            .collect();
+        due.sort_by(|a, b| {
+            a.rehearsal
+                .due_at
+                .cmp(&b.rehearsal.due_at)
+                .then_with(|| b.rehearsal.lapses.cmp(&a.rehearsal.lapses))
+        });
+        if let Some(limit) = query.limit {
+            due.truncate(limit);
+        }
+        Ok(due)
+    })
+    .await
+}
+
+/// Record a rehearsal: recalled questions move up a box, forgotten ones start over. A provided
+/// answer becomes the new best answer.
+#[tauri::command]
+#[specta::specta]
+pub async fn record_rehearsal(id: String, recalled: bool, answer: Option<String>) -> Response<BankQuestion> {
+    envelope::respond("record_rehearsal", async move {
+        let now = Utc::now();
+        let mut bank = BANK.lock();
+        let question = bank.get_mut(&id)?;
+        question.rehearsal.record(recalled, now);
+        if let Some(answer) = answer.filter(|a| !a.trim().is_empty()) {
+            question.best_answer = Some(answer);
+        }
+        question.updated_at = now;
+        let question = question.clone();
+        save_bank(&bank)?;
+        Ok(question)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 1, 9, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn new_questions_are_due_immediately() {
+        let state = RehearsalState::new(now());
+        assert_eq!(state.box_index, 0);
+        assert_eq!(state.due_at, now());
+    }
+
+    #[test]
+    fn recall_promotes_one_box_up_to_the_last() {
+        let mut state = RehearsalState::new(now());
+        let mut schedule = Vec::new();
+        for _ in 0..7 {
+            state.record(true, now());
+            schedule.push((state.box_index, (state.due_at - now()).num_days()));
+        }
+        assert_eq!(schedule, [(1, 2), (2, 4), (3, 8), (4, 16), (5, 32), (5, 32), (5, 32)]);
+        assert_eq!(state.reviews, 7);
+        assert_eq!(state.lapses, 0);
+    }
+
+    #[test]
+    fn forgetting_demotes_to_the_first_box() {
+        let mut state = RehearsalState::new(now());
+        for _ in 0..3 {
+            state.record(true, now());
+        }
+        let later = now() + Duration::days(10);
+        state.record(false, later);
+        assert_eq!(state.box_index, 0);
+        assert_eq!(state.due_at, later + Duration::days(1));
+        assert_eq!(state.last_reviewed_at, Some(later));
+        assert_eq!(state.lapses, 1);
+        assert_eq!(state.reviews, 4);
+    }
+}
@@ -0,0 +1,405 @@
+// Question deduplication
+// The same question often arrives two or three times: read from the meeting chat by accessibility,
+// heard in the transcript, and typed in by hand, sometimes reworded. Each question is compared with
+// the ones seen in the last few minutes, first by normalized text and then by cosine similarity of a
+// local embedding (hashed word and character n-grams; nothing leaves the machine). Similar wording is
+// not enough on its own: both questions must also use the same content words, give or take
+// stopwords, filler verbs and inflections, so "worst case" never matches "average case". A match is linked
+// to the first, canonical question: it gets that question's answer, or waits for it while it is
+// still being generated, instead of a second AI call, and saving it to the session returns the
+// canonical question's ID.
+
+use std::collections::BTreeSet;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::Serialize;
+use specta::Type;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::watch;
+
+use crate::envelope::{self, Response};
+use crate::error::{AppError, ErrorCode};
+use crate::events;
+
+/// Questions older than this are not matched any more
+const DEDUP_WINDOW: Duration = Duration::from_secs(180);
+/// Cosine similarity at or above which two questions with the same content words are the same one
+/// reworded
+const SIMILARITY_THRESHOLD: f32 = 0.8;
+const MAX_TRACKED: usize = 50;
+/// How long a duplicate waits for the canonical question's answer
+const ANSWER_WAIT: Duration = Duration::from_secs(90);
+const EMBEDDING_DIMS: usize = 512;
+
+/// Openers that do not change what is being asked
+const LEAD_INS: [&str; 14] = [
+    "so", "okay", "ok", "um", "uh", "well", "alright", "right", "and", "now", "next", "question", "please", "also",
+];
+const STOPWORDS: [&str; 24] = [
+    "a", "an", "the", "to", "of", "in", "on", "for", "with", "is", "are", "be", "do", "does", "can", "could", "would",
+    "you", "me", "i", "it", "that", "this", "about",
+];
+/// Ways of asking that do not change the question, ignored when comparing content words
+const FILLERS: [&str; 11] = ["explain", "describe", "tell", "walk", "through", "talk", "give", "us", "your", "my", "some"];
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+static RECENT: Mutex<Vec<Tracked>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Clone, PartialEq)]
+enum AnswerState {
+    Pending,
+    Done(String),
+    /// Not answered yet, or the attempt failed; the next arrival answers it
+    Unanswered,
+}
+
+struct Tracked {
+    record: CanonicalQuestion,
+    normalized: String,
+    content: BTreeSet<String>,
+    embedding: Vec<f32>,
+    answer: watch::Sender<AnswerState>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema, Type)]
+pub struct QuestionSighting {
+    pub text: String,
+    /// "transcript", "accessibility", "manual", ...
+    pub source: String,
+    pub seen_at: DateTime<Utc>,
+    /// 1.0 for the canonical question itself
+    pub similarity: f32,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema, Type)]
+pub struct CanonicalQuestion {
+    pub id: String,
+    pub text: String,
+    pub first_seen: DateTime<Utc>,
+    /// Every arrival of the question, the canonical one first
+    pub sightings: Vec<QuestionSighting>,
+    /// Session question ID once it was saved
+    pub saved_question_id: Option<String>,
+    pub answered: bool,
+}
+
+/// Payload of `question-deduplicated`
+#[derive(Debug, Clone, Serialize, JsonSchema, Type)]
+pub struct QuestionDeduplicated {
+    pub canonical_id: String,
+    pub canonical_text: String,
+    pub duplicate_text: String,
+    pub source: String,
+    pub similarity: f32,
+}
+
+fn normalize(text: &str) -> String {
+    let lowered = text.to_lowercase().replace('’', "'");
+    let words: Vec<&str> = lowered
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|word| !word.is_empty())
+        .collect();
+    let start = words.iter().position(|word| !LEAD_INS.contains(word)).unwrap_or(words.len());
+    words[start..].join(" ")
+}
+
+/// Crude suffix stripping so "works", "working" and "worked" compare equal
+fn stem(word: &str) -> String {
+    let word = word.strip_suffix("'s").unwrap_or(word);
+    if let Some(base) = word.strip_suffix("ies").filter(|base| base.len() > 1) {
+        return format!("{}y", base);
+    }
+    for suffix in ["ing", "ed", "es", "s"] {
+        if let Some(base) = word.strip_suffix(suffix).filter(|base| base.len() > 2) {
+            if suffix == "s" && base.ends_with('s') {
+                break;
+            }
+            return base.to_string();
+        }
+    }
+    word.to_string()
+}
+
+/// Stems of the words that carry the meaning of a normalized question
+fn content_words(normalized: &str) -> BTreeSet<String> {
+    normalized
+        .split(' ')
+        .filter(|word| !word.is_empty() && !STOPWORDS.contains(word) && !FILLERS.contains(word))
+        .map(stem)
+        .collect()
+}
+
+/// FNV-1a, stable across runs so embeddings can be compared at any time
+fn bucket(feature: &str) -> usize {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in feature.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    (hash % EMBEDDING_DIMS as u64) as usize
+}
+
+/// Unit vector of hashed word stems, stem pairs and character trigrams; filler verbs are left out
+fn embed(normalized: &str) -> Vec<f32> {
+    let mut vector = vec![0.0f32; EMBEDDING_DIMS];
+    let words: Vec<(String, bool)> = normalized
+        .split(' ')
+        .filter(|w| !w.is_empty() && !FILLERS.contains(w))
+        .map(|w| (stem(w), STOPWORDS.contains(&w)))
+        .collect();
+    for (word, stopword) in &words {
+        let weight = if *stopword { 0.3 } else { 1.0 };
+        vector[bucket(&format!("w:{}", word))] += weight;
+        let padded: Vec<char> = format!(" {} ", word).chars().collect();
+        for trigram in padded.windows(3) {
+            vector[bucket(&format!("c:{}", trigram.iter().collect::<String>()))] += 0.25 * weight;
+        }
+    }
+    let content: Vec<&String> = words.iter().filter(|(_, stopword)| !stopword).map(|(word, _)| word).collect();
+    for pair in content.windows(2) {
+        vector[bucket(&format!("b:{} {}", pair[0], pair[1]))] += 0.5;
+    }
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|v| *v /= norm);
+    }
+    vector
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn prune(recent: &mut Vec<Tracked>) {
+    let cutoff = Utc::now() - chrono::Duration::from_std(DEDUP_WINDOW).unwrap_or_default();
+    recent.retain(|tracked| tracked.record.sightings.last().is_some_and(|s| s.seen_at >= cutoff));
+    while recent.len() > MAX_TRACKED {
+        recent.remove(0);
+    }
+}
+
+/// Whether two questions with these content words and similarity ask the same thing
+fn same_question(a: &BTreeSet<String>, b: &BTreeSet<String>, similarity: f32) -> bool {
+    a == b && similarity >= SIMILARITY_THRESHOLD
+}
+
+/// Index and similarity of the best recent match for `normalized`
+fn find_match(recent: &[Tracked], normalized: &str, content: &BTreeSet<String>, embedding: &[f32]) -> Option<(usize, f32)> {
+    if let Some(i) = recent.iter().position(|tracked| tracked.normalized == normalized) {
+        return Some((i, 1.0));
+    }
+    recent
+        .iter()
+        .enumerate()
+        .map(|(i, tracked)| (i, cosine(&tracked.embedding, embedding)))
+        .filter(|(i, similarity)| same_question(&recent[*i].content, content, *similarity))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+fn new_record(question: &str, source: &str) -> CanonicalQuestion {
+    let now = Utc::now();
+    CanonicalQuestion {
+        id: uuid::Uuid::new_v4().to_string(),
+        text: question.trim().to_string(),
+        first_seen: now,
+        sightings: vec![QuestionSighting { text: question.trim().to_string(), source: source.to_string(), seen_at: now, similarity: 1.0 }],
+        saved_question_id: None,
+        answered: false,
+    }
+}
+
+pub fn init(app_handle: AppHandle) {
+    let _ = APP_HANDLE.set(app_handle);
+}
+
+/// The right to answer a new question; `finish` shares the answer with its duplicates. Dropping it
+/// without an answer lets the next arrival of the question try again.
+pub struct Ticket {
+    canonical_id: String,
+    answer: watch::Sender<AnswerState>,
+    finished: bool,
+}
+
+impl Ticket {
+    pub fn finish(&mut self, answer: &str) {
+        self.finished = true;
+        self.answer.send_replace(AnswerState::Done(answer.to_string()));
+        if let Some(tracked) = RECENT.lock().iter_mut().find(|t| t.record.id == self.canonical_id) {
+            tracked.record.answered = true;
+        }
+    }
+}
+
+impl Drop for Ticket {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.answer.send_replace(AnswerState::Unanswered);
+        }
+    }
+}
+
+/// A question already seen recently
+pub struct Duplicate {
+    pub canonical_id: String,
+    answer: watch::Receiver<AnswerState>,
+}
+
+impl Duplicate {
+    /// The canonical question's answer, waiting for it while it is generated
+    pub async fn answer(mut self) -> Result<String, AppError> {
+        let wait = self.answer.wait_for(|state| *state != AnswerState::Pending);
+        match tokio::time::timeout(ANSWER_WAIT, wait).await {
+            Ok(Ok(state)) => match &*state {
+                AnswerState::Done(answer) => Ok(answer.clone()),
+                _ => Err(AppError::new(ErrorCode::Unavailable, "Answering the same question failed; ask it again")),
+            },
+            Ok(Err(_)) => Err(AppError::new(ErrorCode::Unavailable, "Answering the same question was abandoned; ask it again")),
+            Err(_) => Err(AppError::new(ErrorCode::Timeout, "Timed out waiting for the answer to the same question")),
+        }
+    }
+}
+
+pub enum Claim {
+    New(Ticket),
+    Duplicate(Duplicate),
+}
+
+/// Register an arriving question; the first arrival gets to answer it, later ones are duplicates
+/// unless the earlier attempt failed
+pub fn claim(question: &str, source: &str) -> Claim {
+    let normalized = normalize(question);
+    let content = content_words(&normalized);
+    let embedding = embed(&normalized);
+    let now = Utc::now();
+    let mut recent = RECENT.lock();
+    prune(&mut recent);
+
+    if let Some((i, similarity)) = find_match(&recent, &normalized, &content, &embedding) {
+        let tracked = &mut recent[i];
+        tracked.record.sightings.push(QuestionSighting {
+            text: question.trim().to_string(),
+            source: source.to_string(),
+            seen_at: now,
+            similarity,
+        });
+        if *tracked.answer.borrow() != AnswerState::Unanswered {
+            info!("🔁 Question from {} is a duplicate ({:.2}) of '{}'", source, similarity, tracked.record.text);
+            let event = QuestionDeduplicated {
+                canonical_id: tracked.record.id.clone(),
+                canonical_text: tracked.record.text.clone(),
+                duplicate_text: question.trim().to_string(),
+                source: source.to_string(),
+                similarity,
+            };
+            if let Some(app_handle) = APP_HANDLE.get() {
+                if let Err(e) = app_handle.emit(events::QUESTION_DEDUPLICATED, &event) {
+                    warn!("Failed to emit {}: {}", events::QUESTION_DEDUPLICATED, e);
+                }
+            }
+            return Claim::Duplicate(Duplicate { canonical_id: tracked.record.id.clone(), answer: tracked.answer.subscribe() });
+        }
+        // The earlier attempt failed: this arrival answers the same canonical question
+        tracked.answer.send_replace(AnswerState::Pending);
+        return Claim::New(Ticket { canonical_id: tracked.record.id.clone(), answer: tracked.answer.clone(), finished: false });
+    }
+
+    let (answer, _) = watch::channel(AnswerState::Pending);
+    let record = new_record(question, source);
+    let ticket = Ticket { canonical_id: record.id.clone(), answer: answer.clone(), finished: false };
+    recent.push(Tracked { record, normalized, content, embedding, answer });
+    Claim::New(ticket)
+}
+
+/// Saved session question ID of a recent question matching `question`, if it was saved
+pub fn saved_question_id(question: &str) -> Option<String> {
+    let normalized = normalize(question);
+    let content = content_words(&normalized);
+    let embedding = embed(&normalized);
+    let mut recent = RECENT.lock();
+    prune(&mut recent);
+    find_match(&recent, &normalized, &content, &embedding).and_then(|(i, _)| recent[i].record.saved_question_id.clone())
+}
+
+/// Link a saved session question to its canonical record, registering it when it is new
+pub fn link_saved(question: &str, question_id: &str) {
+    let normalized = normalize(question);
+    let content = content_words(&normalized);
+    let embedding = embed(&normalized);
+    let mut recent = RECENT.lock();
+    prune(&mut recent);
+    match find_match(&recent, &normalized, &content, &embedding) {
+        Some((i, _)) => {
+            recent[i].record.saved_question_id.get_or_insert_with(|| question_id.to_string());
+        }
+        None => {
+            let mut record = new_record(question, "saved");
+            record.saved_question_id = Some(question_id.to_string());
+            let (answer, _) = watch::channel(AnswerState::Unanswered);
+            recent.push(Tracked { record, normalized, content, embedding, answer });
+        }
+    }
+}
+
+/// Recent canonical questions with every arrival linked to them
+#[tauri::command]
+#[specta::specta]
+pub fn list_recent_questions() -> Response<Vec<CanonicalQuestion>> {
+    envelope::respond_sync("list_recent_questions", || {
+        let mut recent = RECENT.lock();
+        prune(&mut recent);
+        Ok(recent.iter().map(|tracked| tracked.record.clone()).collect())
+    })
+}
+
+/// Forget recent questions, so the next arrival of any of them is answered again
+#[tauri::command]
+#[specta::specta]
+pub fn clear_recent_questions() -> Response<()> {
+    envelope::respond_sync("clear_recent_questions", || {
+        let cleared = std::mem::take(&mut *RECENT.lock()).len();
+        info!("🔁 Cleared {} recent questions", cleared);
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn same(a: &str, b: &str) -> bool {
+        let (a, b) = (normalize(a), normalize(b));
+        same_question(&content_words(&a), &content_words(&b), cosine(&embed(&a), &embed(&b)))
+    }
+
+    #[test]
+    fn near_misses_are_different_questions() {
+        assert!(!same(
+            "What is the worst case time complexity of quicksort?",
+            "What is the average case time complexity of quicksort?"
+        ));
+        assert!(!same("How does a hash map work in Java?", "How does a hash map work in Python?"));
+        assert!(!same("How do you reverse a linked list?", "How do you reverse a doubly linked list?"));
+    }
+
+    #[test]
+    fn paraphrases_are_the_same_question() {
+        assert!(same("So, how do you reverse a linked list?", "How would you reverse a linked list"));
+        assert!(same("Can you tell me about your experience with React?", "Tell me about your experience with React."));
+        assert!(same("How does a hash map work?", "Can you explain how hash maps work?"));
+        assert!(same("What's the time complexity of binary search?", "What is the time complexity of a binary search?"));
+    }
+
+    #[test]
+    fn stem_folds_inflections() {
+        assert_eq!(stem("works"), "work");
+        assert_eq!(stem("working"), "work");
+        assert_eq!(stem("queries"), "query");
+        assert_eq!(stem("class"), "class");
+        assert_eq!(stem("what's"), "what");
+    }
+}
@@ -0,0 +1,116 @@
+// Interview question topics shared by practice planning, categorization and search
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum QuestionTopic {
+    Behavioral,
+    Coding,
+    SystemDesign,
+    /// Role- or industry-specific knowledge
+    Domain,
+}
+
+impl QuestionTopic {
+    pub const ALL: [QuestionTopic; 4] = [
+        QuestionTopic::Behavioral,
+        QuestionTopic::Coding,
+        QuestionTopic::SystemDesign,
+        QuestionTopic::Domain,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            QuestionTopic::Behavioral => "behavioral",
+            QuestionTopic::Coding => "coding",
+            QuestionTopic::SystemDesign => "system_design",
+            QuestionTopic::Domain => "domain",
+        }
+    }
+
+    /// Lenient parse of names like "System Design", "system-design" or "technical"
+    pub fn parse(value: &str) -> Option<Self> {
+        let normalized: String = value
+            .trim()
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '_' })
+            .collect();
+        match normalized.as_str() {
+            "behavioral" | "behavioural" | "behavior" | "hr" | "culture" | "situational" => Some(QuestionTopic::Behavioral),
+            "coding" | "code" | "algorithm" | "algorithms" | "programming" | "technical" => Some(QuestionTopic::Coding),
+            "system_design" | "systemdesign" | "design" | "architecture" => Some(QuestionTopic::SystemDesign),
+            "domain" | "domain_knowledge" | "role_specific" | "knowledge" => Some(QuestionTopic::Domain),
+            _ => None,
+        }
+    }
+}
+
+/// Phrases that point at each topic; multi-word phrases count double since they are less ambiguous
+const TOPIC_KEYWORDS: [(QuestionTopic, &[&str]); 4] = [
+    (
+        QuestionTopic::Behavioral,
+        &[
+            "tell me about a time", "describe a time", "give me an example", "how did you handle",
+            "conflict", "disagree", "teammate", "manager", "mistake", "failure", "proud", "challenge you faced",
+            "weakness", "strength", "motivat", "leadership", "feedback", "deadline", "why do you want",
+            "why should we", "where do you see yourself", "tell me about yourself", "stakeholder",
+        ],
+    ),
+    (
+        QuestionTopic::Coding,
+        &[
+            "write a function", "implement", "algorithm", "time complexity", "space complexity", "big o",
+            "array", "linked list", "binary tree", "hash map", "hashmap", "string", "recursion", "recursive",
+            "sort", "reverse", "dynamic programming", "graph traversal", "stack", "queue", "pointer",
+            "leetcode", "code", "debug", "palindrome", "substring", "integer",
+        ],
+    ),
+    (
+        QuestionTopic::SystemDesign,
+        &[
+            "design a", "design an", "system design", "architecture", "scale", "scalab", "distributed",
+            "load balanc", "cache", "caching", "shard", "partition", "replication", "microservice",
+            "throughput", "latency", "availability", "consistency", "message queue", "rate limit",
+            "high level design", "database schema", "url shortener", "millions of users",
+        ],
+    ),
+    (
+        QuestionTopic::Domain,
+        &[
+            "what is", "explain", "difference between", "how does", "what are", "define", "protocol",
+            "regulation", "compliance", "framework", "best practice", "industry", "product", "market",
+        ],
+    ),
+];
+
+impl QuestionTopic {
+    /// Keyword classifier for a question's wording. Returns `None` when nothing matches, so callers
+    /// can fall back to a caller-provided category or a default.
+    pub fn classify(question: &str) -> Option<Self> {
+        let text = question.to_lowercase();
+        let mut best: Option<(QuestionTopic, usize)> = None;
+        for (topic, keywords) in TOPIC_KEYWORDS.iter() {
+            let score: usize = keywords
+                .iter()
+                .filter(|keyword| text.contains(*keyword))
+                .map(|keyword| if keyword.contains(' ') { 2 } else { 1 })
+                .sum();
+            // Ties keep the earlier, more specific topic; Domain is listed last as the catch-all
+            if score > 0 && best.is_none_or(|(_, top)| score > top) {
+                best = Some((*topic, score));
+            }
+        }
+        best.map(|(topic, _)| topic)
+    }
+
+    /// Topic for a saved question: an explicit category wins, then the wording, then Domain
+    pub fn categorize(category: &str, question: &str) -> Self {
+        Self::parse(category)
+            .or_else(|| Self::classify(question))
+            .unwrap_or(QuestionTopic::Domain)
+    }
+}
@@ -6,6 +6,7 @@ use std::ffi::{CString, OsString};
 use std::os::windows::ffi::OsStringExt;
 use std::ptr;
 use serde::{Serialize, Deserialize};
+use specta::Type;
 use log::{info, warn, error};
 use anyhow::Result;
 
@@ -26,8 +27,10 @@ use winapi::{
         windef::HWND,
     },
 };
+use crate::envelope::{self, Response};
+use crate::error::AppError;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct RealStealthStatus {
     pub process_id: u32,
     pub original_name: String,
@@ -356,25 +359,34 @@ fn get_real_stealth() -> Option<&'static mut RealTaskManagerStealth> {
 
 // Tauri commands for REAL stealth
 #[tauri::command]
-pub fn activate_real_stealth() -> Result<String, String> {
-    match get_real_stealth() {
-        Some(stealth) => stealth.activate_real_stealth().map_err(|e| e.to_string()),
-        None => Err("Real stealth system not initialized".to_string()),
-    }
+#[specta::specta]
+pub fn activate_real_stealth() -> Response<String> {
+    envelope::respond_sync("activate_real_stealth", || {
+        match get_real_stealth() {
+            Some(stealth) => stealth.activate_real_stealth().map_err(|e| AppError::from(e.to_string())),
+            None => Err("Real stealth system not initialized".into()),
+        }
+    })
 }
 
 #[tauri::command]
-pub fn deactivate_real_stealth() -> Result<String, String> {
-    match get_real_stealth() {
-        Some(stealth) => stealth.deactivate_stealth().map_err(|e| e.to_string()),
-        None => Err("Real stealth system not initialized".to_string()),
-    }
+#[specta::specta]
+pub fn deactivate_real_stealth() -> Response<String> {
+    envelope::respond_sync("deactivate_real_stealth", || {
+        match get_real_stealth() {
+            Some(stealth) => stealth.deactivate_stealth().map_err(|e| AppError::from(e.to_string())),
+            None => Err("Real stealth system not initialized".into()),
+        }
+    })
 }
 
 #[tauri::command]
-pub fn get_real_stealth_status() -> Result<RealStealthStatus, String> {
-    match get_real_stealth() {
-        Some(stealth) => Ok(stealth.get_status()),
-        None => Err("Real stealth system not initialized".to_string()),
-    }
+#[specta::specta]
+pub fn get_real_stealth_status() -> Response<RealStealthStatus> {
+    envelope::respond_sync("get_real_stealth_status", || {
+        match get_real_stealth() {
+            Some(stealth) => Ok(stealth.get_status()),
+            None => Err("Real stealth system not initialized".into()),
+        }
+    })
 }
\ No newline at end of file
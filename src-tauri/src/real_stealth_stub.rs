@@ -0,0 +1,30 @@
+// Process hiding is built on Win32 process and window APIs; other platforms report it as unavailable
+
+use crate::platform::{unavailable, Capability};
+use crate::envelope::{self, Response};
+
+pub fn initialize_real_stealth() {}
+
+#[tauri::command]
+#[specta::specta]
+pub fn activate_real_stealth() -> Response<String> {
+    envelope::respond_sync("activate_real_stealth", || {
+        Err(unavailable(Capability::Stealth).into())
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn deactivate_real_stealth() -> Response<String> {
+    envelope::respond_sync("deactivate_real_stealth", || {
+        Err(unavailable(Capability::Stealth).into())
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_real_stealth_status() -> Response<serde_json::Value> {
+    envelope::respond_sync("get_real_stealth_status", || {
+        Err(unavailable(Capability::Stealth).into())
+    })
+}
@@ -6,6 +6,8 @@ use anyhow::Result;
 use log::{info, warn};
 use serde_json::json;
 use tauri::AppHandle;
+use crate::envelope::{self, Response};
+use crate::error::AppError;
 
 /// Audio capture configuration - DEPRECATED (stub)
 #[derive(Debug, Clone)]
@@ -27,51 +29,69 @@ impl Default for AudioConfig {
 
 /// DEPRECATED - Use JavaScript SDK instead
 #[tauri::command]
-pub async fn start_microphone_transcription(app: AppHandle) -> Result<(), String> {
-    warn!("start_microphone_transcription is deprecated - use JavaScript SDK instead");
-    Ok(())
+pub async fn start_microphone_transcription(app: AppHandle) -> Response<()> {
+    envelope::respond("start_microphone_transcription", async move {
+        warn!("start_microphone_transcription is deprecated - use JavaScript SDK instead");
+        Ok(())
+    })
+    .await
 }
 
 /// DEPRECATED - Use JavaScript SDK instead  
 #[tauri::command]
-pub async fn start_system_audio_transcription(app: AppHandle) -> Result<(), String> {
-    warn!("start_system_audio_transcription is deprecated - use JavaScript SDK instead");
-    Ok(())
+pub async fn start_system_audio_transcription(app: AppHandle) -> Response<()> {
+    envelope::respond("start_system_audio_transcription", async move {
+        warn!("start_system_audio_transcription is deprecated - use JavaScript SDK instead");
+        Ok(())
+    })
+    .await
 }
 
 /// DEPRECATED - Use JavaScript SDK instead
 #[tauri::command]
-pub async fn stop_transcription() -> Result<String, String> {
-    warn!("stop_transcription is deprecated - use JavaScript SDK instead");
-    Ok("Transcription stopped (deprecated)".to_string())
+pub async fn stop_transcription() -> Response<String> {
+    envelope::respond("stop_transcription", async move {
+        warn!("stop_transcription is deprecated - use JavaScript SDK instead");
+        Ok("Transcription stopped (deprecated)".to_string())
+    })
+    .await
 }
 
 /// DEPRECATED - Use JavaScript SDK instead
 #[tauri::command]
-pub async fn get_transcription_status() -> Result<serde_json::Value, String> {
-    warn!("get_transcription_status is deprecated - use JavaScript SDK instead");
-    Ok(json!({
-        "status": "deprecated",
-        "message": "Use JavaScript SDK instead"
-    }))
+pub async fn get_transcription_status() -> Response<serde_json::Value> {
+    envelope::respond("get_transcription_status", async move {
+        warn!("get_transcription_status is deprecated - use JavaScript SDK instead");
+        Ok(json!({
+            "status": "deprecated",
+            "message": "Use JavaScript SDK instead"
+        }))
+    })
+    .await
 }
 
 /// DEPRECATED - Use JavaScript SDK instead
 #[tauri::command]
-pub async fn get_deepgram_config() -> Result<serde_json::Value, String> {
-    warn!("get_deepgram_config is deprecated - use JavaScript SDK instead");
-    Ok(json!({
-        "model": "nova-3",
-        "language": "en-US",
-        "deprecated": true
-    }))
+pub async fn get_deepgram_config() -> Response<serde_json::Value> {
+    envelope::respond("get_deepgram_config", async move {
+        warn!("get_deepgram_config is deprecated - use JavaScript SDK instead");
+        Ok(json!({
+            "model": "nova-3",
+            "language": "en-US",
+            "deprecated": true
+        }))
+    })
+    .await
 }
 
 /// DEPRECATED - Use JavaScript SDK instead
 #[tauri::command]
-pub async fn test_deepgram_connection() -> Result<String, String> {
-    warn!("test_deepgram_connection is deprecated - use JavaScript SDK instead");
-    Ok("Connection test deprecated - use JavaScript SDK instead".to_string())
+pub async fn test_deepgram_connection() -> Response<String> {
+    envelope::respond("test_deepgram_connection", async move {
+        warn!("test_deepgram_connection is deprecated - use JavaScript SDK instead");
+        Ok("Connection test deprecated - use JavaScript SDK instead".to_string())
+    })
+    .await
 }
 
 // Internal implementation struct - kept for compatibility
@@ -0,0 +1,332 @@
+// Report card: scores, pacing and question topics across all of a profile's sessions
+// Practice sessions count towards the profile they were run under. Live interview sessions are
+// not tied to a profile and count towards the default one, using their rubric evaluations and
+// pacing stats. Everything is bucketed by week so trends (e.g. "are my system design answers
+// improving?") come from a least-squares slope over the weekly averages.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use log::info;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::practice_profile::DEFAULT_PROFILE;
+use crate::question_topic::QuestionTopic;
+use crate::cancellation;
+use crate::envelope::{IntoResult, Response};
+
+/// Score change per week (on the 0-10 scale) below which a trend counts as steady
+const SCORE_TREND_THRESHOLD: f32 = 0.25;
+/// Filler-rate change per week (fillers per 100 words) below which a trend counts as steady
+const FILLER_TREND_THRESHOLD: f32 = 0.2;
+/// Change per week in words-per-minute distance from the comfortable range
+const PACE_TREND_THRESHOLD: f32 = 2.0;
+/// Comfortable speaking pace, matching the live pacing feedback
+const PACE_RANGE: (f32, f32) = (100.0, 175.0);
+const RECENT_SESSIONS: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionKind {
+    Practice,
+    Interview,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SessionPoint {
+    pub session_id: String,
+    pub kind: SessionKind,
+    pub date: DateTime<Utc>,
+    pub title: Option<String>,
+    /// Average answer score on a 0-10 scale, when any answer was scored
+    pub score: Option<f32>,
+    pub answers: usize,
+    pub topics: BTreeMap<QuestionTopic, usize>,
+    pub words_per_minute: Option<f32>,
+    pub filler_rate: Option<f32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TrendDirection {
+    Improving,
+    Declining,
+    Steady,
+    /// Fewer than two weeks with data
+    NotEnoughData,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct WeekPoint {
+    /// Monday of the week
+    pub week_start: NaiveDate,
+    pub average: f32,
+    pub samples: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Trend {
+    pub weekly: Vec<WeekPoint>,
+    /// Change of the weekly average per week
+    pub slope_per_week: Option<f32>,
+    pub direction: TrendDirection,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TopicCard {
+    pub topic: QuestionTopic,
+    /// Questions asked on this topic, scored or not
+    pub questions: usize,
+    pub average_score: Option<f32>,
+    pub trend: Trend,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ReportCard {
+    pub profile: String,
+    pub generated_at: DateTime<Utc>,
+    pub sessions: usize,
+    pub scored_answers: usize,
+    pub average_score: Option<f32>,
+    pub score_trend: Trend,
+    pub topics: Vec<TopicCard>,
+    pub words_per_minute: Trend,
+    pub filler_rate: Trend,
+    /// Newest first
+    pub recent_sessions: Vec<SessionPoint>,
+}
+
+/// A dated measurement feeding one trend
+struct Sample {
+    date: DateTime<Utc>,
+    value: f32,
+}
+
+#[derive(Default)]
+struct Samples {
+    scores: Vec<Sample>,
+    topic_scores: BTreeMap<QuestionTopic, Vec<Sample>>,
+    topic_questions: BTreeMap<QuestionTopic, usize>,
+    words_per_minute: Vec<Sample>,
+    filler_rate: Vec<Sample>,
+}
+
+fn week_start(date: DateTime<Utc>) -> NaiveDate {
+    let day = date.date_naive();
+    day - Duration::days(i64::from(day.weekday().num_days_from_monday()))
+}
+
+fn mean(values: impl Iterator<Item = f32>) -> Option<f32> {
+    let (sum, count) = values.fold((0.0, 0usize), |(sum, count), v| (sum + v, count + 1));
+    (count > 0).then(|| sum / count as f32)
+}
+
+/// Weekly averages of `samples` and their direction. `goodness` maps a weekly average to a value
+/// where higher is better, so fewer fillers or a pace closer to the comfortable range count as
+/// improving.
+fn trend(samples: &[Sample], goodness: impl Fn(f32) -> f32, threshold: f32) -> Trend {
+    let mut weeks: BTreeMap<NaiveDate, Vec<f32>> = BTreeMap::new();
+    for sample in samples {
+        weeks.entry(week_start(sample.date)).or_default().push(sample.value);
+    }
+    let weekly: Vec<WeekPoint> = weeks
+        .into_iter()
+        .map(|(week_start, values)| WeekPoint {
+            week_start,
+            average: mean(values.iter().copied()).unwrap_or_default(),
+            samples: values.len(),
+        })
+        .collect();
+
+    let Some(first) = weekly.first().map(|w| w.week_start).filter(|_| weekly.len() >= 2) else {
+        return Trend {
+            weekly,
+            slope_per_week: None,
+            direction: TrendDirection::NotEnoughData,
+        };
+    };
+    // Least squares over (weeks since the first week, weekly average)
+    let points: Vec<(f32, f32)> = weekly
+        .iter()
+        .map(|w| ((w.week_start - first).num_days() as f32 / 7.0, w.average))
+        .collect();
+    let slope = |values: &dyn Fn(f32) -> f32| {
+        let n = points.len() as f32;
+        let mean_x = points.iter().map(|(x, _)| x).sum::<f32>() / n;
+        let mean_y = points.iter().map(|&(_, y)| values(y)).sum::<f32>() / n;
+        let covariance: f32 = points.iter().map(|&(x, y)| (x - mean_x) * (values(y) - mean_y)).sum();
+        let variance: f32 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+        covariance / variance
+    };
+    let improvement = slope(&goodness);
+    let direction = if improvement > threshold {
+        TrendDirection::Improving
+    } else if improvement < -threshold {
+        TrendDirection::Declining
+    } else {
+        TrendDirection::Steady
+    };
+    Trend {
+        slope_per_week: Some(slope(&|y| y)),
+        weekly,
+        direction,
+    }
+}
+
+fn pace_goodness(words_per_minute: f32) -> f32 {
+    let (low, high) = PACE_RANGE;
+    -((low - words_per_minute).max(0.0) + (words_per_minute - high).max(0.0))
+}
+
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value).ok().map(|d| d.with_timezone(&Utc))
+}
+
+async fn practice_sessions(profile: &str, samples: &mut Samples) -> Vec<SessionPoint> {
+    let reports = crate::practice::list_practice_reports().await.into_result().unwrap_or_default();
+    let mut points = Vec::new();
+    for report in reports {
+        if report.profile.as_deref().unwrap_or(DEFAULT_PROFILE) != profile {
+            continue;
+        }
+        let Some(date) = parse_date(&report.completed_at).or_else(|| parse_date(&report.started_at)) else {
+            continue;
+        };
+        let mut topics = BTreeMap::new();
+        let mut scores = Vec::new();
+        for turn in &report.turns {
+            if let Some(topic) = turn.topic {
+                *topics.entry(topic).or_default() += 1;
+            }
+            let Some(score) = turn.score.map(f32::from) else {
+                continue;
+            };
+            scores.push(score);
+            samples.scores.push(Sample { date, value: score });
+            if let Some(topic) = turn.topic {
+                samples.topic_scores.entry(topic).or_default().push(Sample { date, value: score });
+            }
+        }
+        for (topic, count) in &topics {
+            *samples.topic_questions.entry(*topic).or_default() += count;
+        }
+        points.push(SessionPoint {
+            session_id: report.session_id,
+            kind: SessionKind::Practice,
+            date,
+            title: report.position.or(report.company),
+            score: mean(scores.iter().copied()),
+            answers: scores.len(),
+            topics,
+            words_per_minute: None,
+            filler_rate: None,
+        });
+    }
+    points
+}
+
+fn interview_sessions(samples: &mut Samples) -> Vec<SessionPoint> {
+    let session_ids: BTreeSet<String> = crate::rubric::evaluated_sessions()
+        .into_iter()
+        .chain(crate::pacing::measured_sessions())
+        .collect();
+    let mut points = Vec::new();
+    for session_id in session_ids {
+        let evaluations = crate::rubric::evaluations(&session_id);
+        let pacing = crate::pacing::report(&session_id);
+        let date = evaluations
+            .iter()
+            .map(|e| e.evaluated_at)
+            .chain(pacing.iter().flat_map(|p| p.answers.iter().map(|a| a.started_at)))
+            .min();
+        let Some(date) = date else {
+            continue;
+        };
+
+        let mut topics = BTreeMap::new();
+        let mut scores = Vec::new();
+        for evaluation in &evaluations {
+            // Rubric scales are configurable; bring every score onto 0-10
+            let score = evaluation.overall / f32::from(evaluation.scale_max.max(1)) * 10.0;
+            scores.push(score);
+            samples.scores.push(Sample { date, value: score });
+            if let Some(topic) = evaluation.topic {
+                *topics.entry(topic).or_default() += 1;
+                *samples.topic_questions.entry(topic).or_default() += 1;
+                samples.topic_scores.entry(topic).or_default().push(Sample { date, value: score });
+            }
+        }
+        let words_per_minute = pacing.as_ref().and_then(|p| p.average_words_per_minute);
+        let filler_rate = pacing.as_ref().and_then(|p| p.filler_rate);
+        if let Some(value) = words_per_minute {
+            samples.words_per_minute.push(Sample { date, value });
+        }
+        if let Some(value) = filler_rate {
+            samples.filler_rate.push(Sample { date, value });
+        }
+        points.push(SessionPoint {
+            session_id,
+            kind: SessionKind::Interview,
+            date,
+            title: None,
+            score: mean(scores.iter().copied()),
+            answers: scores.len(),
+            topics,
+            words_per_minute,
+            filler_rate,
+        });
+    }
+    points
+}
+
+/// Aggregate a profile's sessions into a report card with weekly trends
+#[tauri::command]
+#[specta::specta]
+pub async fn generate_report_card(profile: Option<String>, request_id: Option<String>) -> Response<ReportCard> {
+    cancellation::run("generate_report_card", request_id, async move {
+        let profile = profile
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+        let mut samples = Samples::default();
+        let mut sessions = practice_sessions(&profile, &mut samples).await;
+        if profile == DEFAULT_PROFILE {
+            sessions.extend(interview_sessions(&mut samples));
+        }
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.date));
+
+        let topics = QuestionTopic::ALL
+            .iter()
+            .filter_map(|&topic| {
+                let questions = samples.topic_questions.get(&topic).copied().unwrap_or(0);
+                let scores = samples.topic_scores.get(&topic).map(Vec::as_slice).unwrap_or_default();
+                (questions > 0 || !scores.is_empty()).then(|| TopicCard {
+                    topic,
+                    questions,
+                    average_score: mean(scores.iter().map(|s| s.value)),
+                    trend: trend(scores, |y| y, SCORE_TREND_THRESHOLD),
+                })
+            })
+            .collect();
+
+        let card = ReportCard {
+            generated_at: Utc::now(),
+            sessions: sessions.len(),
+            scored_answers: samples.scores.len(),
+            average_score: mean(samples.scores.iter().map(|s| s.value)),
+            score_trend: trend(&samples.scores, |y| y, SCORE_TREND_THRESHOLD),
+            topics,
+            words_per_minute: trend(&samples.words_per_minute, pace_goodness, PACE_TREND_THRESHOLD),
+            filler_rate: trend(&samples.filler_rate, |y| -y, FILLER_TREND_THRESHOLD),
+            recent_sessions: sessions.into_iter().take(RECENT_SESSIONS).collect(),
+            profile,
+        };
+        info!(
+            "📈 Report card for '{}': {} sessions, {} scored answers, score trend {:?}",
+            card.profile, card.sessions, card.scored_answers, card.score_trend.direction
+        );
+        Ok(card)
+    })
+    .await
+}
@@ -0,0 +1,146 @@
+// Resize coordinator for the AI response window
+// Resize requests are merged and debounced; only the last one within the window is applied, and
+// set_size/set_position always run one at a time. Once applied, the settled geometry is emitted
+// as `ai-response-geometry`.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use log::{debug, error, info};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::ai_stream::RESPONSE_WINDOW_LABEL;
+use crate::events;
+
+/// Requests arriving within this window collapse into one resize
+const DEBOUNCE: Duration = Duration::from_millis(40);
+
+static PENDING: Lazy<Mutex<Option<GeometryRequest>>> = Lazy::new(|| Mutex::new(None));
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+/// Requests received since the last applied resize
+static MERGED: AtomicU64 = AtomicU64::new(0);
+/// Held while a resize is being applied so set_size/set_position never interleave
+static APPLY_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+/// Physical-pixel geometry; fields left as None keep their current value
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GeometryRequest {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub x: Option<i32>,
+    pub y: Option<i32>,
+}
+
+impl GeometryRequest {
+    /// Later requests win field by field
+    fn merge(self, newer: GeometryRequest) -> GeometryRequest {
+        GeometryRequest {
+            width: newer.width.or(self.width),
+            height: newer.height.or(self.height),
+            x: newer.x.or(self.x),
+            y: newer.y.or(self.y),
+        }
+    }
+}
+
+/// Payload of `ai-response-geometry`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct SettledGeometry {
+    pub width: u32,
+    pub height: u32,
+    pub x: i32,
+    pub y: i32,
+    /// Requests merged into this resize
+    pub merged_requests: u64,
+}
+
+/// Queue a resize of the AI response window; it is applied once requests stop arriving
+pub fn request(app_handle: &AppHandle, geometry: GeometryRequest) {
+    {
+        let mut pending = PENDING.lock();
+        *pending = Some(pending.unwrap_or_default().merge(geometry));
+    }
+    MERGED.fetch_add(1, Ordering::SeqCst);
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(DEBOUNCE).await;
+        // A newer request arrived; its task applies the merged geometry
+        if GENERATION.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        apply_pending(&app_handle).await;
+    });
+}
+
+async fn apply_pending(app_handle: &AppHandle) {
+    let _apply = APPLY_LOCK.lock().await;
+    let Some(geometry) = PENDING.lock().take() else {
+        return;
+    };
+    let merged_requests = MERGED.swap(0, Ordering::SeqCst);
+
+    let Some(window) = app_handle.get_webview_window(RESPONSE_WINDOW_LABEL) else {
+        error!("❌ AI response window not found for resize");
+        return;
+    };
+
+    let started = std::time::Instant::now();
+    if geometry.width.is_some() || geometry.height.is_some() {
+        match window.outer_size() {
+            Ok(current) => {
+                let target = tauri::PhysicalSize {
+                    width: geometry.width.unwrap_or(current.width),
+                    height: geometry.height.unwrap_or(current.height),
+                };
+                if target != current {
+                    if let Err(e) = window.set_size(tauri::Size::Physical(target)) {
+                        error!("❌ Failed to resize AI response window: {}", e);
+                    }
+                }
+            }
+            Err(e) => error!("❌ Failed to get current window size: {}", e),
+        }
+    }
+    if geometry.x.is_some() || geometry.y.is_some() {
+        match window.outer_position() {
+            Ok(current) => {
+                let target = tauri::PhysicalPosition {
+                    x: geometry.x.unwrap_or(current.x),
+                    y: geometry.y.unwrap_or(current.y),
+                };
+                if target != current {
+                    if let Err(e) = crate::window_manager::place_window(&window, target) {
+                        error!("❌ Failed to move AI response window: {}", e);
+                    }
+                }
+            }
+            Err(e) => error!("❌ Failed to get current window position: {}", e),
+        }
+    }
+    crate::metrics::record(crate::metrics::RESIZE_APPLIED, started.elapsed());
+
+    let (Ok(size), Ok(position)) = (window.outer_size(), window.outer_position()) else {
+        return;
+    };
+    let settled = SettledGeometry {
+        width: size.width,
+        height: size.height,
+        x: position.x,
+        y: position.y,
+        merged_requests,
+    };
+    info!(
+        "✅ AI response window settled at {}x{} ({}, {}) after {} request(s)",
+        settled.width, settled.height, settled.x, settled.y, settled.merged_requests
+    );
+    if let Err(e) = app_handle.emit(events::AI_RESPONSE_GEOMETRY, &settled) {
+        debug!("Failed to emit ai-response-geometry: {}", e);
+    }
+}
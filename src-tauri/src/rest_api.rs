@@ -0,0 +1,487 @@
+// Localhost REST API for automation
+// Stream Deck buttons, AutoHotkey or shell scripts can drive the app without the webview. When
+// enabled, a small HTTP/1.1 server on 127.0.0.1 answers:
+//   GET  /v1/status                      transcription state and the last detected question
+//   POST /v1/transcription/start|stop    start or stop Deepgram transcription
+//   GET  /v1/questions/last              the last question detected from transcript or screen
+//   POST /v1/answer                      answer {"question", "model", "provider"} (all optional;
+//                                        the last detected question by default)
+//   GET  /v1/sessions/<id>/report        the interview report of a session
+// Every request needs `Authorization: Bearer <token>`. Bodies are the same `{ ok, data, error }`
+// envelope the commands return, with an HTTP status matching the error code. The server only ever
+// binds to loopback. Off until the user opts in; settings are stored in
+// %APPDATA%\MockMate\rest_api.json.
+
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Listener, Manager};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+
+use crate::envelope::{self, Envelope, Response};
+use crate::error::{AppError, ErrorCode};
+use crate::events;
+use crate::local_server;
+use crate::AppState;
+
+const DEFAULT_PORT: u16 = 18481;
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+const MAX_BODY_BYTES: usize = 64 * 1024;
+/// A client has this long to send its request
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+/// Pollinations model used when a request doesn't name one
+const DEFAULT_ANSWER_MODEL: &str = "openai";
+
+static SETTINGS: Lazy<Mutex<RestApiSettings>> = Lazy::new(|| Mutex::new(local_server::load_settings(SETTINGS_FILE)));
+static STATUS: Lazy<Mutex<RestApiStatus>> = Lazy::new(|| Mutex::new(RestApiStatus::default()));
+static SERVER: Lazy<Mutex<Option<RunningServer>>> = Lazy::new(|| Mutex::new(None));
+static LAST_QUESTION: Mutex<Option<DetectedQuestion>> = Mutex::new(None);
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RestApiSettings {
+    pub enabled: bool,
+    pub port: u16,
+    /// Sent by clients as `Authorization: Bearer <token>`
+    pub token: String,
+}
+
+impl Default for RestApiSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_PORT,
+            token: local_server::new_token(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct RestApiStatus {
+    pub running: bool,
+    /// e.g. "http://127.0.0.1:18481"
+    pub address: Option<String>,
+    pub requests_served: u64,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DetectedQuestion {
+    pub text: String,
+    /// "transcript" or the app the question was read from
+    pub source: String,
+    pub detected_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ApiStatus {
+    pub transcribing: bool,
+    pub session_id: Option<String>,
+    pub last_question: Option<DetectedQuestion>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct AnswerRequest {
+    question: Option<String>,
+    model: Option<String>,
+    provider: Option<String>,
+}
+
+struct RunningServer {
+    stop: watch::Sender<bool>,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+struct HttpRequest {
+    method: String,
+    path: String,
+    authorization: Option<String>,
+    body: Vec<u8>,
+}
+
+const SETTINGS_FILE: &str = "rest_api.json";
+
+fn check_settings(settings: &RestApiSettings) -> Result<(), String> {
+    local_server::check_port_and_token(settings.port, &settings.token)
+}
+
+fn remember_question(text: &str, source: &str) {
+    let text = text.trim();
+    if text.is_empty() {
+        return;
+    }
+    *LAST_QUESTION.lock() = Some(DetectedQuestion {
+        text: text.to_string(),
+        source: source.to_string(),
+        detected_at: Utc::now(),
+    });
+}
+
+fn on_question_detected(payload: &str) {
+    if let Ok(result) = serde_json::from_str::<crate::accessibility_reader::AccessibilityTextResult>(payload) {
+        remember_question(&result.text, &result.source_app);
+    }
+}
+
+fn on_transcription(payload: &str) {
+    if let Ok(result) = serde_json::from_str::<crate::deepgram_streaming::DeepgramTranscriptionResult>(payload) {
+        if result.is_final && crate::headless::looks_like_question(&result.text) {
+            remember_question(&result.text, "transcript");
+        }
+    }
+}
+
+fn status_code(code: ErrorCode) -> u16 {
+    match code {
+        ErrorCode::NotFound => 404,
+        ErrorCode::InvalidInput => 400,
+        ErrorCode::Unsupported => 501,
+        ErrorCode::PermissionDenied | ErrorCode::PrivacyBlocked => 403,
+        ErrorCode::Unauthorized => 401,
+        ErrorCode::Network | ErrorCode::Unavailable => 503,
+        ErrorCode::Timeout => 504,
+        ErrorCode::Cancelled | ErrorCode::NoActiveSession => 409,
+        ErrorCode::Internal => 500,
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        501 => "Not Implemented",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Internal Server Error",
+    }
+}
+
+/// Status and JSON body for a command's envelope
+fn reply<T: Serialize>(response: Response<T>) -> (u16, String) {
+    let (status, body) = match response {
+        Ok(envelope) => (200, serde_json::to_string(&envelope)),
+        Err(envelope) => {
+            let status = envelope.error.as_ref().map(|e| status_code(e.code)).unwrap_or(500);
+            (status, serde_json::to_string(&envelope))
+        }
+    };
+    (status, body.unwrap_or_else(|e| format!(r#"{{"ok":false,"error":{{"message":"{}"}}}}"#, e)))
+}
+
+fn error_reply(status: u16, code: ErrorCode, message: &str) -> (u16, String) {
+    let envelope: Envelope<()> = Envelope {
+        ok: false,
+        data: None,
+        error: Some(AppError::new(code, message)),
+        request_id: uuid::Uuid::new_v4().to_string(),
+        duration_ms: 0.0,
+    };
+    (status, serde_json::to_string(&envelope).unwrap_or_default())
+}
+
+async fn read_request(stream: &mut TcpStream) -> Result<HttpRequest, (u16, String)> {
+    let bad_request = |message: &str| error_reply(400, ErrorCode::InvalidInput, message);
+    let mut buffer = Vec::new();
+    let header_end = loop {
+        if let Some(end) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end;
+        }
+        if buffer.len() > MAX_HEADER_BYTES {
+            return Err(error_reply(413, ErrorCode::InvalidInput, "Request headers too large"));
+        }
+        let mut chunk = [0u8; 4096];
+        let read = stream.read(&mut chunk).await.map_err(|e| bad_request(&e.to_string()))?;
+        if read == 0 {
+            return Err(bad_request("Connection closed before the request was complete"));
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_ascii_uppercase();
+    let target = request_line.next().ok_or_else(|| bad_request("Malformed request line"))?;
+    let path = target.split('?').next().unwrap_or_default().trim_end_matches('/').to_string();
+
+    let mut authorization = None;
+    let mut content_length = 0;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else { continue };
+        match name.trim().to_ascii_lowercase().as_str() {
+            "authorization" => authorization = Some(value.trim().to_string()),
+            "content-length" => content_length = value.trim().parse().map_err(|_| bad_request("Invalid Content-Length"))?,
+            _ => {}
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        return Err(error_reply(413, ErrorCode::InvalidInput, "Request body too large"));
+    }
+
+    let mut body = buffer[header_end + 4..].to_vec();
+    while body.len() < content_length {
+        let mut chunk = vec![0u8; content_length - body.len()];
+        let read = stream.read(&mut chunk).await.map_err(|e| bad_request(&e.to_string()))?;
+        if read == 0 {
+            return Err(bad_request("Connection closed before the body was complete"));
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+    body.truncate(content_length);
+
+    Ok(HttpRequest { method, path, authorization, body })
+}
+
+async fn answer(app: &AppHandle, body: &[u8]) -> (u16, String) {
+    let request: AnswerRequest = if body.iter().all(u8::is_ascii_whitespace) {
+        AnswerRequest::default()
+    } else {
+        match serde_json::from_slice(body) {
+            Ok(request) => request,
+            Err(e) => return error_reply(400, ErrorCode::InvalidInput, &format!("Invalid answer request: {}", e)),
+        }
+    };
+    let question = request
+        .question
+        .filter(|q| !q.trim().is_empty())
+        .or_else(|| LAST_QUESTION.lock().as_ref().map(|q| q.text.clone()));
+    let Some(question) = question else {
+        return error_reply(404, ErrorCode::NotFound, "No question given and none detected yet");
+    };
+    let provider = request.provider.unwrap_or_else(|| "pollinations".to_string());
+    let payload = crate::GenerateAnswerPayload {
+        question,
+        model: request.model.unwrap_or_else(|| DEFAULT_ANSWER_MODEL.to_string()),
+        provider: provider.clone(),
+        company: None,
+        position: None,
+        job_description: None,
+        source: Some("api".to_string()),
+    };
+    let state = app.state::<AppState>();
+    // Pollinations answers stream into the response window like answers requested from the UI
+    if provider.eq_ignore_ascii_case("pollinations") {
+        reply(crate::pollinations_generate_answer_streaming(payload, state, app.clone(), None).await)
+    } else {
+        reply(crate::generate_ai_answer(payload, state, None).await)
+    }
+}
+
+async fn route(app: &AppHandle, request: HttpRequest) -> (u16, String) {
+    let segments: Vec<&str> = request.path.trim_start_matches('/').split('/').collect();
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["v1", "status"]) => {
+            reply(
+                envelope::respond("api_status", async move {
+                    let transcribing = crate::deepgram_streaming::is_deepgram_streaming_active()
+                        .await
+                        .ok()
+                        .and_then(|envelope| envelope.data)
+                        .unwrap_or(false);
+                    Ok(ApiStatus {
+                        transcribing,
+                        session_id: crate::audit::current_session(),
+                        last_question: LAST_QUESTION.lock().clone(),
+                    })
+                })
+                .await,
+            )
+        }
+        ("POST", ["v1", "transcription", "start"]) => {
+            reply(crate::deepgram_streaming::start_deepgram_streaming(app.clone()).await)
+        }
+        ("POST", ["v1", "transcription", "stop"]) => {
+            reply(crate::deepgram_streaming::stop_deepgram_streaming(app.clone()).await)
+        }
+        ("GET", ["v1", "questions", "last"]) => {
+            reply(
+                envelope::respond("api_last_question", async move {
+                    LAST_QUESTION
+                        .lock()
+                        .clone()
+                        .ok_or_else(|| AppError::new(ErrorCode::NotFound, "No question detected yet"))
+                })
+                .await,
+            )
+        }
+        ("POST", ["v1", "answer"]) => answer(app, &request.body).await,
+        ("GET", ["v1", "sessions", session_id, "report"]) => {
+            reply(crate::database::postgres::get_interview_report(session_id.to_string()).await)
+        }
+        (_, ["v1", "status"] | ["v1", "transcription", _] | ["v1", "questions", "last"] | ["v1", "answer"]) => {
+            error_reply(405, ErrorCode::InvalidInput, "Method not allowed")
+        }
+        _ => error_reply(404, ErrorCode::NotFound, &format!("No endpoint {} {}", request.method, request.path)),
+    }
+}
+
+async fn serve_client(app: AppHandle, mut stream: TcpStream, peer: SocketAddr, token: String) {
+    let (status, body) = match tokio::time::timeout(READ_TIMEOUT, read_request(&mut stream)).await {
+        Err(_) => error_reply(400, ErrorCode::Timeout, "Timed out reading the request"),
+        Ok(Err(rejection)) => rejection,
+        Ok(Ok(request)) => {
+            let given = request.authorization.as_deref().and_then(|value| value.strip_prefix("Bearer ")).unwrap_or_default();
+            if !local_server::token_matches(given.trim(), &token) {
+                warn!("REST API request from {} rejected: invalid or missing token", peer);
+                error_reply(401, ErrorCode::Unauthorized, "Invalid or missing token")
+            } else {
+                info!("🔧 REST API {} {}", request.method, request.path);
+                route(&app, request).await
+            }
+        }
+    };
+    STATUS.lock().requests_served += 1;
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason(status),
+        body.len(),
+        body
+    );
+    if let Err(e) = stream.write_all(response.as_bytes()).await {
+        debug!("REST API reply to {} failed: {}", peer, e);
+    }
+    let _ = stream.shutdown().await;
+}
+
+/// Bind and serve until stopped; replaces a running server
+async fn start(app: AppHandle, settings: RestApiSettings) -> Result<RestApiStatus, String> {
+    local_server::wait_stopped(stop()).await;
+    let listener = TcpListener::bind(SocketAddr::new(Ipv4Addr::LOCALHOST.into(), settings.port))
+        .await
+        .map_err(|e| format!("REST API could not listen on port {}: {}", settings.port, e))?;
+
+    let (stop_tx, mut stop_rx) = watch::channel(false);
+    let address = format!("http://127.0.0.1:{}", settings.port);
+    info!("🔧 REST API listening on {}", address);
+    let status = {
+        let mut status = STATUS.lock();
+        status.running = true;
+        status.address = Some(address);
+        status.last_error = None;
+        status.clone()
+    };
+
+    let task = tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, peer)) => {
+                        tauri::async_runtime::spawn(serve_client(app.clone(), stream, peer, settings.token.clone()));
+                    }
+                    Err(e) => warn!("REST API accept failed: {}", e),
+                },
+                _ = stop_rx.changed() => break,
+            }
+        }
+        info!("🔧 REST API stopped");
+    });
+    *SERVER.lock() = Some(RunningServer { stop: stop_tx, task });
+    Ok(status)
+}
+
+/// Stop accepting requests; returns the accept task to wait on
+fn stop() -> Option<tauri::async_runtime::JoinHandle<()>> {
+    let server = SERVER.lock().take()?;
+    let _ = server.stop.send(true);
+    let mut status = STATUS.lock();
+    status.running = false;
+    status.address = None;
+    Some(server.task)
+}
+
+/// Track detected questions and start the server when enabled
+pub fn init(app_handle: AppHandle) {
+    if APP_HANDLE.set(app_handle.clone()).is_err() {
+        return;
+    }
+    app_handle.listen_any(events::ACCESSIBILITY_QUESTION_DETECTED, |event| on_question_detected(event.payload()));
+    app_handle.listen_any(events::TRANSCRIPTION_RESULT, |event| on_transcription(event.payload()));
+
+    let settings = SETTINGS.lock().clone();
+    if settings.enabled {
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = start(app_handle, settings).await {
+                warn!("{}", e);
+                STATUS.lock().last_error = Some(e);
+            }
+        });
+    }
+}
+
+fn app_handle() -> Result<AppHandle, String> {
+    APP_HANDLE.get().cloned().ok_or_else(|| "REST API is not initialized".to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_rest_api_settings() -> Response<RestApiSettings> {
+    envelope::respond("get_rest_api_settings", async move {
+        Ok(SETTINGS.lock().clone())
+    })
+    .await
+}
+
+/// Save the REST API settings, then start, restart or stop the server to match
+#[tauri::command]
+#[specta::specta]
+pub async fn set_rest_api_settings(settings: RestApiSettings) -> Response<RestApiStatus> {
+    envelope::respond("set_rest_api_settings", async move {
+        let settings = RestApiSettings { token: settings.token.trim().to_string(), ..settings };
+        check_settings(&settings)?;
+        let enabled = settings.enabled;
+        let start_server = |settings| async move {
+            start(app_handle()?, settings).await.inspect_err(|e| STATUS.lock().last_error = Some(e.clone()))
+        };
+        let stop_server = || {
+            stop();
+            STATUS.lock().clone()
+        };
+        Ok(local_server::apply_settings(SETTINGS_FILE, settings, &SETTINGS, enabled, start_server, stop_server).await?)
+    })
+    .await
+}
+
+/// Replace the token; scripts must be updated with the new one
+#[tauri::command]
+#[specta::specta]
+pub async fn regenerate_rest_api_token() -> Response<RestApiSettings> {
+    envelope::respond("regenerate_rest_api_token", async move {
+        let settings = {
+            let mut settings = SETTINGS.lock();
+            settings.token = local_server::new_token();
+            local_server::save_settings(SETTINGS_FILE, &*settings)?;
+            settings.clone()
+        };
+        if settings.enabled {
+            start(app_handle()?, settings.clone()).await?;
+        }
+        Ok(settings)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_rest_api_status() -> Response<RestApiStatus> {
+    envelope::respond("get_rest_api_status", async move {
+        Ok(STATUS.lock().clone())
+    })
+    .await
+}
@@ -0,0 +1,402 @@
+// Data retention for MockMate
+// Recordings, transcripts and debug artifacts are deleted once they outlive the configured
+// retention; a background janitor enforces the policy and purge_all_data wipes everything.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use crate::envelope::{self, Response};
+use crate::error::AppError;
+
+/// How often the janitor re-applies the policy
+const JANITOR_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+/// Recordings older versions wrote under the working directory: subfolder, file name prefix and
+/// extension
+const LEGACY_RECORDINGS: [(&str, &str, &str); 3] = [
+    ("mic", "mic_capture_", "wav"),
+    ("Sound", "audio_capture_", "wav"),
+    // session_recorder MP4s, named after the session
+    ("sessions", "", "mp4"),
+];
+
+static POLICY: Lazy<Mutex<RetentionPolicy>> = Lazy::new(|| Mutex::new(load_policy()));
+
+/// Days to keep each kind of data; `None` keeps it until purged
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RetentionPolicy {
+    pub recordings_days: Option<u32>,
+    pub transcripts_days: Option<u32>,
+    pub audio_debug_days: Option<u32>,
+    pub crash_reports_days: Option<u32>,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            recordings_days: Some(7),
+            transcripts_days: Some(30),
+            audio_debug_days: Some(7),
+            crash_reports_days: Some(30),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum DataCategory {
+    Recordings,
+    Transcripts,
+    AudioDebug,
+    CrashReports,
+    /// Rotated by the logging module itself; only counted and purged here
+    Logs,
+    /// Outbound-data audit log; kept until purged
+    AuditLog,
+    /// Raw system audio spilled to disk by long captures; only files whose capture is gone are removed
+    CaptureSpill,
+}
+
+impl DataCategory {
+    pub const ALL: [DataCategory; 7] = [
+        DataCategory::Recordings,
+        DataCategory::Transcripts,
+        DataCategory::AudioDebug,
+        DataCategory::CrashReports,
+        DataCategory::Logs,
+        DataCategory::AuditLog,
+        DataCategory::CaptureSpill,
+    ];
+
+    pub fn dir(self) -> PathBuf {
+        match self {
+            DataCategory::Recordings => app_data_dir().join("recordings"),
+            DataCategory::Transcripts => app_data_dir().join("transcripts"),
+            DataCategory::AudioDebug => crate::audio_debug::dump_root(),
+            DataCategory::CrashReports => crate::crash_reporter::crash_dir(),
+            DataCategory::Logs => crate::logging::log_dir(),
+            DataCategory::AuditLog => crate::audit::audit_dir(),
+            DataCategory::CaptureSpill => crate::capture_buffer::spill_dir(),
+        }
+    }
+
+    fn retention_days(self, policy: &RetentionPolicy) -> Option<u32> {
+        match self {
+            DataCategory::Recordings => policy.recordings_days,
+            DataCategory::Transcripts => policy.transcripts_days,
+            DataCategory::AudioDebug => policy.audio_debug_days,
+            DataCategory::CrashReports => policy.crash_reports_days,
+            DataCategory::Logs | DataCategory::AuditLog | DataCategory::CaptureSpill => None,
+        }
+    }
+
+    /// Whether `path` may be deleted; a running instance's capture spill is still in use
+    fn deletable(self, path: &Path) -> bool {
+        match self {
+            DataCategory::CaptureSpill => crate::capture_buffer::is_stale_spill(path),
+            _ => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CategoryUsage {
+    pub category: DataCategory,
+    pub path: String,
+    pub bytes: u64,
+    pub files: u64,
+    pub oldest_modified: Option<String>,
+    pub retention_days: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct StorageUsage {
+    pub total_bytes: u64,
+    pub categories: Vec<CategoryUsage>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct CleanupResult {
+    pub files_deleted: u64,
+    pub bytes_freed: u64,
+    /// Files that could not be removed (e.g. the log file currently being written)
+    pub errors: Vec<String>,
+}
+
+fn app_data_dir() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+}
+
+fn legacy_migrated_marker() -> PathBuf {
+    app_data_dir().join(".recordings-migrated")
+}
+
+fn policy_path() -> PathBuf {
+    app_data_dir().join("retention.json")
+}
+
+fn load_policy() -> RetentionPolicy {
+    std::fs::read(policy_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn save_policy(policy: &RetentionPolicy) -> Result<(), String> {
+    let path = policy_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(policy).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| e.to_string())
+}
+
+/// All regular files under `dir`, recursively, with size and modification time
+fn walk_files(dir: &Path, out: &mut Vec<(PathBuf, u64, SystemTime)>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            walk_files(&entry.path(), out);
+        } else if metadata.is_file() {
+            let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            out.push((entry.path(), metadata.len(), modified));
+        }
+    }
+}
+
+/// Remove empty subdirectories left behind (e.g. per-dump folders), keeping `dir` itself
+fn remove_empty_dirs(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            remove_empty_dirs(&path);
+            // Fails harmlessly when the directory still has content
+            let _ = std::fs::remove_dir(&path);
+        }
+    }
+}
+
+/// Delete files in `category` last modified before `cutoff` (all files when `None`)
+fn delete_files(category: DataCategory, cutoff: Option<SystemTime>, result: &mut CleanupResult) {
+    let dir = category.dir();
+    let mut files = Vec::new();
+    walk_files(&dir, &mut files);
+
+    for (path, size, modified) in files {
+        if cutoff.map(|c| modified >= c).unwrap_or(false) || !category.deletable(&path) {
+            continue;
+        }
+        match std::fs::remove_file(&path) {
+            Ok(()) => {
+                result.files_deleted += 1;
+                result.bytes_freed += size;
+            }
+            Err(e) => result.errors.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+    remove_empty_dirs(&dir);
+}
+
+/// Apply the current retention policy once
+pub fn enforce_policy() -> CleanupResult {
+    let policy = POLICY.lock().clone();
+    let mut result = CleanupResult::default();
+
+    for category in DataCategory::ALL {
+        let Some(days) = category.retention_days(&policy) else {
+            continue;
+        };
+        let cutoff = SystemTime::now()
+            .checked_sub(Duration::from_secs(days as u64 * SECONDS_PER_DAY))
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        delete_files(category, Some(cutoff), &mut result);
+    }
+    // Spill files are only useful to the capture that wrote them, so orphans go regardless of age
+    delete_files(DataCategory::CaptureSpill, None, &mut result);
+
+    if result.files_deleted > 0 {
+        info!("🧹 Retention cleanup removed {} files ({} bytes)", result.files_deleted, result.bytes_freed);
+    }
+    for e in &result.errors {
+        warn!("Retention cleanup could not delete {}", e);
+    }
+    result
+}
+
+/// Rename `from` to `to`, copying instead when they are on different volumes
+fn move_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    if let Some(dir) = to.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::rename(from, to).or_else(|_| {
+        std::fs::copy(from, to)?;
+        std::fs::remove_file(from)
+    })
+}
+
+/// Move recordings that older versions left in `recordings` under the working directory into the
+/// default profile's data folder, once. Only files MockMate named itself are touched, since the
+/// working directory may hold an unrelated `recordings` folder.
+pub fn migrate_legacy_recordings() {
+    if !crate::accounts::is_default_active() || legacy_migrated_marker().exists() {
+        return;
+    }
+    let Ok(legacy) = std::env::current_dir().map(|dir| dir.join("recordings")) else {
+        return;
+    };
+    let target = DataCategory::Recordings.dir();
+    let (mut moved, mut failed) = (0, 0);
+    for (subdir, prefix, extension) in LEGACY_RECORDINGS {
+        let Ok(entries) = std::fs::read_dir(legacy.join(subdir)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let from = entry.path();
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !from.is_file() || !name.starts_with(prefix) || from.extension().is_none_or(|e| e != extension) {
+                continue;
+            }
+            match move_file(&from, &target.join(subdir).join(&name)) {
+                Ok(()) => moved += 1,
+                Err(e) => {
+                    failed += 1;
+                    warn!("Failed to move recording {}: {}", from.display(), e);
+                }
+            }
+        }
+        // Both fail harmlessly while anything else is left in them
+        let _ = std::fs::remove_dir(legacy.join(subdir));
+    }
+    let _ = std::fs::remove_dir(&legacy);
+
+    if moved > 0 {
+        info!("🧹 Moved {} recordings from {} to {}", moved, legacy.display(), target.display());
+    }
+    // Retry next launch when something could not be moved
+    if failed == 0 {
+        let marker = legacy_migrated_marker();
+        let written = std::fs::create_dir_all(app_data_dir()).and_then(|()| std::fs::write(&marker, b""));
+        if let Err(e) = written {
+            warn!("Failed to write {}: {}", marker.display(), e);
+        }
+    }
+}
+
+/// Start the background janitor (first pass runs immediately, after moving legacy recordings)
+pub fn start_janitor() {
+    tauri::async_runtime::spawn(async {
+        if let Err(e) = tokio::task::spawn_blocking(migrate_legacy_recordings).await {
+            warn!("Moving legacy recordings failed: {}", e);
+        }
+        let mut interval = tokio::time::interval(JANITOR_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = tokio::task::spawn_blocking(enforce_policy).await {
+                warn!("Retention janitor pass failed: {}", e);
+            }
+        }
+    });
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_retention_policy() -> Response<RetentionPolicy> {
+    envelope::respond("get_retention_policy", async move {
+        Ok(POLICY.lock().clone())
+    })
+    .await
+}
+
+/// Update the retention policy and apply it right away
+#[tauri::command]
+#[specta::specta]
+pub async fn set_retention_policy(policy: RetentionPolicy) -> Response<CleanupResult> {
+    envelope::respond("set_retention_policy", async move {
+        save_policy(&policy)?;
+        info!("🧹 Retention policy updated: {:?}", policy);
+        *POLICY.lock() = policy;
+        tokio::task::spawn_blocking(enforce_policy)
+            .await
+            .map_err(|e| AppError::from(e.to_string()))
+    })
+    .await
+}
+
+/// Disk space used by each kind of locally stored data
+#[tauri::command]
+#[specta::specta]
+pub async fn get_storage_usage() -> Response<StorageUsage> {
+    envelope::respond("get_storage_usage", async move {
+        let policy = POLICY.lock().clone();
+        tokio::task::spawn_blocking(move || {
+            let categories: Vec<CategoryUsage> = DataCategory::ALL
+                .iter()
+                .map(|&category| {
+                    let dir = category.dir();
+                    let mut files = Vec::new();
+                    walk_files(&dir, &mut files);
+                    CategoryUsage {
+                        category,
+                        path: dir.display().to_string(),
+                        bytes: files.iter().map(|(_, size, _)| size).sum(),
+                        files: files.len() as u64,
+                        oldest_modified: files
+                            .iter()
+                            .map(|(_, _, modified)| *modified)
+                            .min()
+                            .map(|t| chrono::DateTime::<chrono::Utc>::from(t).to_rfc3339()),
+                        retention_days: category.retention_days(&policy),
+                    }
+                })
+                .collect();
+
+            StorageUsage {
+                total_bytes: categories.iter().map(|c| c.bytes).sum(),
+                categories,
+            }
+        })
+        .await
+        .map_err(|e| AppError::from(e.to_string()))
+    })
+    .await
+}
+
+/// Delete every recording, transcript, debug dump, crash report, log and audit file, along with
+/// any unsaved capture
+#[tauri::command]
+#[specta::specta]
+pub async fn purge_all_data() -> Response<CleanupResult> {
+    envelope::respond("purge_all_data", async move {
+        warn!("🧹 Purging all locally stored MockMate data");
+        let result = tokio::task::spawn_blocking(|| {
+            // Drops this instance's spill files; other running instances keep theirs
+            crate::audio_wasapi::discard_capture_recording();
+            let mut result = CleanupResult::default();
+            for category in DataCategory::ALL {
+                delete_files(category, None, &mut result);
+            }
+            result
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+
+        info!("🧹 Purge removed {} files ({} bytes), {} could not be deleted",
+              result.files_deleted, result.bytes_freed, result.errors.len());
+        Ok(result)
+    })
+    .await
+}
@@ -0,0 +1,422 @@
+// Rubric-based answer evaluation
+// Every answer saved during a session can be scored against a configurable rubric (clarity,
+// correctness, depth and structure by default). The configured AI provider rates each criterion and
+// leaves a short comment; evaluations are kept per session in %APPDATA%\MockMate\evaluations and
+// attached to the interview report and its exports.
+
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Manager, State};
+
+use crate::question_topic::QuestionTopic;
+use crate::AppState;
+use crate::envelope::{self, IntoResult, Response};
+
+/// Answer characters sent to the model
+const MAX_ANSWER_CHARS: usize = 6000;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RubricCriterion {
+    /// Stable identifier used in stored scores, e.g. "clarity"
+    pub key: String,
+    pub name: String,
+    /// What a strong answer looks like for this criterion; shown to the model
+    pub description: String,
+    #[serde(default = "default_weight")]
+    pub weight: f32,
+}
+
+fn default_weight() -> f32 {
+    1.0
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Rubric {
+    pub criteria: Vec<RubricCriterion>,
+    /// Scores run from 1 to this value
+    pub scale_max: u8,
+    /// Score answers automatically as they are saved
+    pub auto_evaluate: bool,
+    /// Provider and model used for automatic evaluation
+    pub provider: String,
+    pub model: String,
+}
+
+impl Default for Rubric {
+    fn default() -> Self {
+        let criterion = |key: &str, name: &str, description: &str| RubricCriterion {
+            key: key.to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+            weight: 1.0,
+        };
+        Self {
+            criteria: vec![
+                criterion("clarity", "Clarity", "Easy to follow, concise, free of rambling and jargon"),
+                criterion("correctness", "Correctness", "Technically and factually accurate; directly answers the question"),
+                criterion("depth", "Depth", "Shows understanding beyond the surface: trade-offs, examples, reasoning"),
+                criterion("structure", "Structure", "Logically ordered, e.g. STAR for behavioral or approach-then-detail for technical"),
+            ],
+            scale_max: 10,
+            auto_evaluate: false,
+            provider: "pollinations".to_string(),
+            model: "openai".to_string(),
+        }
+    }
+}
+
+impl Rubric {
+    fn validate(&self) -> Result<(), String> {
+        if self.criteria.is_empty() {
+            return Err("A rubric needs at least one criterion".to_string());
+        }
+        if !(3..=100).contains(&self.scale_max) {
+            return Err("The rubric scale must be between 3 and 100".to_string());
+        }
+        for (i, criterion) in self.criteria.iter().enumerate() {
+            if criterion.key.trim().is_empty() || criterion.name.trim().is_empty() {
+                return Err("Every rubric criterion needs a key and a name".to_string());
+            }
+            if criterion.weight <= 0.0 || !criterion.weight.is_finite() {
+                return Err(format!("Criterion {} needs a positive weight", criterion.key));
+            }
+            if self.criteria[..i].iter().any(|c| c.key == criterion.key) {
+                return Err(format!("Criterion {} is listed twice", criterion.key));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CriterionScore {
+    pub criterion: String,
+    pub score: u8,
+    pub comment: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AnswerEvaluation {
+    pub answer_id: String,
+    pub question_id: String,
+    /// Topic of the question, for trends across sessions
+    #[serde(default)]
+    pub topic: Option<QuestionTopic>,
+    pub scores: Vec<CriterionScore>,
+    /// Weighted average of the criterion scores, on the rubric's scale
+    pub overall: f32,
+    pub scale_max: u8,
+    pub comment: String,
+    pub evaluated_at: DateTime<Utc>,
+}
+
+impl AnswerEvaluation {
+    /// One-line summary like "Clarity 8 · Depth 6", for tables and exports
+    pub fn compact(&self) -> String {
+        self.scores
+            .iter()
+            .map(|s| format!("{} {}", capitalize(&s.criterion), s.score))
+            .collect::<Vec<_>>()
+            .join(" · ")
+    }
+}
+
+fn capitalize(key: &str) -> String {
+    let mut chars = key.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[derive(Deserialize)]
+struct EvaluationReply {
+    scores: Vec<ReplyScore>,
+    #[serde(default)]
+    comment: String,
+}
+
+#[derive(Deserialize)]
+struct ReplyScore {
+    criterion: String,
+    score: f32,
+    #[serde(default)]
+    comment: String,
+}
+
+/// Serializes read-modify-write of the per-session evaluation files
+static STORE_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+fn mockmate_dir() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+}
+
+fn rubric_path() -> PathBuf {
+    mockmate_dir().join("rubric.json")
+}
+
+fn evaluations_path(session_id: &str) -> PathBuf {
+    mockmate_dir().join("evaluations").join(format!("{}.json", session_id))
+}
+
+pub fn load_rubric() -> Rubric {
+    std::fs::read(rubric_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+/// Stored evaluations for a session, in the order they were made
+pub fn evaluations(session_id: &str) -> Vec<AnswerEvaluation> {
+    std::fs::read(evaluations_path(session_id))
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+/// Sessions with stored evaluations
+pub fn evaluated_sessions() -> Vec<String> {
+    std::fs::read_dir(mockmate_dir().join("evaluations"))
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .filter_map(|path| path.file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn store(session_id: &str, evaluation: AnswerEvaluation) -> Result<(), String> {
+    let _guard = STORE_LOCK.lock();
+    let mut all = evaluations(session_id);
+    all.retain(|e| e.answer_id != evaluation.answer_id);
+    all.push(evaluation);
+    let path = evaluations_path(session_id);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(&all).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| e.to_string())
+}
+
+async fn evaluate(
+    state: &AppState,
+    rubric: &Rubric,
+    provider: &str,
+    model: &str,
+    question: &str,
+    answer: &str,
+) -> Result<(Vec<CriterionScore>, f32, String), String> {
+    let criteria = rubric
+        .criteria
+        .iter()
+        .map(|c| format!("- {} ({}): {}", c.key, c.name, c.description))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut prompt = String::from("Evaluate this interview answer against the rubric.\n");
+    {
+        let context = state.interview_context.lock();
+        if let Some(position) = &context.position {
+            prompt.push_str(&format!("\nRole: {}", position));
+        }
+        if let Some(level) = &context.user_experience_level {
+            prompt.push_str(&format!("\nCandidate level: {}", level));
+        }
+    }
+    prompt.push_str(&format!(
+        "\n\nRubric (score each criterion from 1 to {}):\n{}\n\nQuestion: {}\n\nAnswer: {}\n\n\
+         Reply only with JSON: {{\"scores\": [{{\"criterion\": \"<key>\", \"score\": <number>, \"comment\": \"<one sentence>\"}}], \
+         \"comment\": \"<one or two sentences of overall feedback>\"}}",
+        rubric.scale_max,
+        criteria,
+        question.trim(),
+        answer.trim().chars().take(MAX_ANSWER_CHARS).collect::<String>()
+    ));
+
+    let reply = state
+        .complete(provider, model, "You are a fair, exacting interview assessor. Reply only in the JSON format requested.", &prompt)
+        .await?;
+    let parsed: EvaluationReply = crate::practice::extract_json(&reply, '{', '}')?;
+
+    let mut scores = Vec::new();
+    let (mut weighted, mut total_weight) = (0.0, 0.0);
+    for criterion in &rubric.criteria {
+        let Some(found) = parsed.scores.iter().find(|s| s.criterion.trim().eq_ignore_ascii_case(&criterion.key)) else {
+            continue;
+        };
+        let score = found.score.round().clamp(1.0, f32::from(rubric.scale_max)) as u8;
+        weighted += f32::from(score) * criterion.weight;
+        total_weight += criterion.weight;
+        scores.push(CriterionScore {
+            criterion: criterion.key.clone(),
+            score,
+            comment: found.comment.trim().to_string(),
+        });
+    }
+    if scores.is_empty() {
+        return Err("AI reply did not score any rubric criterion".to_string());
+    }
+    Ok((scores, weighted / total_weight, parsed.comment.trim().to_string()))
+}
+
+/// An answer waiting to be scored
+struct PendingAnswer {
+    session_id: String,
+    question_id: String,
+    question: String,
+    topic: Option<QuestionTopic>,
+    answer_id: String,
+    answer: String,
+}
+
+async fn evaluate_and_store(
+    state: &AppState,
+    rubric: &Rubric,
+    provider: &str,
+    model: &str,
+    pending: &PendingAnswer,
+) -> Result<AnswerEvaluation, String> {
+    let (scores, overall, comment) = evaluate(state, rubric, provider, model, &pending.question, &pending.answer).await?;
+    let evaluation = AnswerEvaluation {
+        answer_id: pending.answer_id.clone(),
+        question_id: pending.question_id.clone(),
+        topic: pending.topic,
+        scores,
+        overall,
+        scale_max: rubric.scale_max,
+        comment,
+        evaluated_at: Utc::now(),
+    };
+    store(&pending.session_id, evaluation.clone())?;
+    Ok(evaluation)
+}
+
+/// Score a freshly saved answer in the background when automatic evaluation is on
+pub fn on_answer_saved(app: &AppHandle, session_id: &str, question_id: &str, answer_id: &str, answer: &str) {
+    let rubric = load_rubric();
+    if !rubric.auto_evaluate || answer.trim().is_empty() {
+        return;
+    }
+    let app = app.clone();
+    let (session_id, question_id, answer_id, answer) =
+        (session_id.to_string(), question_id.to_string(), answer_id.to_string(), answer.to_string());
+    tauri::async_runtime::spawn(async move {
+        let question = match crate::database::postgres::get_session_questions(session_id.clone(), None, None).await.into_result() {
+            Ok(questions) => questions.items.into_iter().find(|q| q.id.to_string() == question_id),
+            Err(e) => {
+                warn!("Rubric evaluation skipped, questions unavailable: {}", e);
+                return;
+            }
+        };
+        let Some(question) = question else {
+            warn!("Rubric evaluation skipped, question {} not found", question_id);
+            return;
+        };
+        let pending = PendingAnswer {
+            session_id,
+            question_id,
+            question: question.question_text,
+            topic: question.topic,
+            answer_id,
+            answer,
+        };
+        let state = app.state::<AppState>();
+        match evaluate_and_store(&state, &rubric, &rubric.provider, &rubric.model, &pending).await {
+            Ok(evaluation) => info!("📐 Evaluated answer {}: {:.1}/{}", pending.answer_id, evaluation.overall, evaluation.scale_max),
+            Err(e) => warn!("Rubric evaluation failed for answer {}: {}", pending.answer_id, e),
+        }
+    });
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_rubric() -> Response<Rubric> {
+    envelope::respond("get_rubric", async move {
+        Ok(load_rubric())
+    })
+    .await
+}
+
+/// Save the rubric; `None` restores the default
+#[tauri::command]
+#[specta::specta]
+pub async fn set_rubric(rubric: Option<Rubric>) -> Response<Rubric> {
+    envelope::respond("set_rubric", async move {
+        let rubric = rubric.unwrap_or_default();
+        rubric.validate()?;
+        let path = rubric_path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let body = serde_json::to_vec_pretty(&rubric).map_err(|e| e.to_string())?;
+        std::fs::write(&path, body).map_err(|e| e.to_string())?;
+        info!("📐 Rubric saved with {} criteria", rubric.criteria.len());
+        Ok(rubric)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_answer_evaluations(session_id: String) -> Response<Vec<AnswerEvaluation>> {
+    envelope::respond("get_answer_evaluations", async move {
+        Ok(evaluations(&session_id))
+    })
+    .await
+}
+
+/// Score every answer of a session that has no evaluation yet (or all of them with `force`)
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn evaluate_session_answers(
+    state: State<'_, AppState>,
+    session_id: String,
+    provider: Option<String>,
+    model: Option<String>,
+    force: Option<bool>,
+) -> Response<Vec<AnswerEvaluation>> {
+    envelope::respond("evaluate_session_answers", async move {
+        let rubric = load_rubric();
+        let provider = provider.unwrap_or_else(|| rubric.provider.clone());
+        let model = model.unwrap_or_else(|| rubric.model.clone());
+        let questions = crate::database::postgres::get_session_questions(session_id.clone(), None, None).await.into_result()?.items;
+        let answers = crate::database::postgres::get_session_answers(session_id.clone(), None).await.into_result()?.items;
+        let existing = evaluations(&session_id);
+
+        let mut evaluated = 0;
+        for answer in &answers {
+            let answer_id = answer.id.to_string();
+            if !force.unwrap_or(false) && existing.iter().any(|e| e.answer_id == answer_id) {
+                continue;
+            }
+            let (Some(text), Some(question)) = (
+                answer.answer_text.as_deref().filter(|t| !t.trim().is_empty()),
+                questions.iter().find(|q| q.id == answer.question_id),
+            ) else {
+                continue;
+            };
+            let pending = PendingAnswer {
+                session_id: session_id.clone(),
+                question_id: question.id.to_string(),
+                question: question.question_text.clone(),
+                topic: question.topic,
+                answer_id,
+                answer: text.to_string(),
+            };
+            evaluate_and_store(&state, &rubric, &provider, &model, &pending).await?;
+            evaluated += 1;
+        }
+        info!("📐 Evaluated {} answers for session {}", evaluated, session_id);
+        Ok(evaluations(&session_id))
+    })
+    .await
+}
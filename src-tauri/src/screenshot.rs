@@ -0,0 +1,385 @@
+// Screenshot capture via the Windows Graphics Capture API
+// Used for content with no accessible text (images, PDFs, shared screens). Each capture creates a
+// free-threaded frame pool, takes the first frame, copies it through a CPU-readable staging
+// texture and encodes it as PNG for the vision-analysis path. `CaptureStream` keeps the pool open
+// for continuous capture (session recording).
+
+use std::io::Cursor;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use base64::Engine;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use windows::core::{IInspectable, Interface};
+use windows::Foundation::TypedEventHandler;
+use windows::Graphics::Capture::{Direct3D11CaptureFrame, Direct3D11CaptureFramePool, GraphicsCaptureItem, GraphicsCaptureSession};
+use windows::Graphics::DirectX::Direct3D11::IDirect3DDevice;
+use windows::Graphics::DirectX::DirectXPixelFormat;
+use windows::Win32::Foundation::{BOOL, HMODULE, HWND, LPARAM, POINT, RECT};
+use windows::Win32::Graphics::Direct3D::D3D_DRIVER_TYPE_HARDWARE;
+use windows::Win32::Graphics::Direct3D11::{
+    D3D11CreateDevice, ID3D11Device, ID3D11Texture2D, D3D11_CPU_ACCESS_READ, D3D11_CREATE_DEVICE_BGRA_SUPPORT,
+    D3D11_MAPPED_SUBRESOURCE, D3D11_MAP_READ, D3D11_SDK_VERSION, D3D11_TEXTURE2D_DESC, D3D11_USAGE_STAGING,
+};
+use windows::Win32::Graphics::Dxgi::IDXGIDevice;
+use windows::Win32::Graphics::Gdi::{
+    EnumDisplayMonitors, GetMonitorInfoW, MonitorFromPoint, HDC, HMONITOR, MONITORINFO, MONITOR_DEFAULTTOPRIMARY,
+};
+use windows::Win32::System::WinRT::Direct3D11::{CreateDirect3D11DeviceFromDXGIDevice, IDirect3DDxgiInterfaceAccess};
+use windows::Win32::System::WinRT::Graphics::Capture::IGraphicsCaptureItemInterop;
+use windows::Win32::System::WinRT::{RoInitialize, RO_INIT_MULTITHREADED};
+use windows_sys::Win32::UI::WindowsAndMessaging::MONITORINFOF_PRIMARY;
+use crate::envelope::{self, Response};
+use crate::guard::{self, Require};
+use crate::permissions::Permission;
+
+/// How long to wait for the first frame before giving up
+const FRAME_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A captured image as base64 PNG
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Screenshot {
+    /// Base64 encoded PNG
+    pub screenshot: String,
+    pub width: u32,
+    pub height: u32,
+    /// "monitor", "window" or "region"
+    pub source: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MonitorInfo {
+    pub index: usize,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub primary: bool,
+}
+
+/// Tightly packed BGRA pixels, top row first
+pub(crate) struct Frame {
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) bgra: Vec<u8>,
+}
+
+struct Monitor {
+    handle: HMONITOR,
+    rect: RECT,
+    primary: bool,
+}
+
+fn monitors() -> Vec<Monitor> {
+    unsafe extern "system" fn collect(monitor: HMONITOR, _hdc: HDC, _rect: *mut RECT, data: LPARAM) -> BOOL {
+        let monitors = &mut *(data.0 as *mut Vec<Monitor>);
+        let mut info = MONITORINFO {
+            cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+            ..Default::default()
+        };
+        if GetMonitorInfoW(monitor, &mut info).as_bool() {
+            monitors.push(Monitor {
+                handle: monitor,
+                rect: info.rcMonitor,
+                primary: info.dwFlags & MONITORINFOF_PRIMARY != 0,
+            });
+        }
+        BOOL(1)
+    }
+
+    let mut monitors: Vec<Monitor> = Vec::new();
+    unsafe {
+        let _ = EnumDisplayMonitors(HDC::default(), None, Some(collect), LPARAM(&mut monitors as *mut _ as isize));
+    }
+    monitors
+}
+
+fn create_device() -> windows::core::Result<(ID3D11Device, IDirect3DDevice)> {
+    let mut device = None;
+    unsafe {
+        D3D11CreateDevice(
+            None,
+            D3D_DRIVER_TYPE_HARDWARE,
+            HMODULE::default(),
+            D3D11_CREATE_DEVICE_BGRA_SUPPORT,
+            None,
+            D3D11_SDK_VERSION,
+            Some(&mut device),
+            None,
+            None,
+        )?;
+    }
+    let device = device.ok_or_else(|| windows::core::Error::from(windows::Win32::Foundation::E_FAIL))?;
+    let dxgi_device: IDXGIDevice = device.cast()?;
+    let winrt_device: IDirect3DDevice = unsafe { CreateDirect3D11DeviceFromDXGIDevice(&dxgi_device)? }.cast()?;
+    Ok((device, winrt_device))
+}
+
+/// An open capture session that delivers frames as they are presented
+pub(crate) struct CaptureStream {
+    device: ID3D11Device,
+    pool: Direct3D11CaptureFramePool,
+    session: GraphicsCaptureSession,
+    frames: mpsc::Receiver<Direct3D11CaptureFrame>,
+}
+
+impl CaptureStream {
+    /// Start capturing `item`; `buffers` is the number of frames the pool can hold at once
+    fn start(item: &GraphicsCaptureItem, buffers: i32) -> Result<Self, String> {
+        let (device, winrt_device) = create_device().map_err(|e| format!("Failed to create D3D11 device: {}", e))?;
+        let size = item.Size().map_err(|e| e.to_string())?;
+        let pool =
+            Direct3D11CaptureFramePool::CreateFreeThreaded(&winrt_device, DirectXPixelFormat::B8G8R8A8UIntNormalized, buffers, size)
+                .map_err(|e| format!("Failed to create frame pool: {}", e))?;
+        let session = pool.CreateCaptureSession(item).map_err(|e| e.to_string())?;
+        // Older Windows 10 builds always include the cursor
+        let _ = session.SetIsCursorCaptureEnabled(false);
+
+        let (frame_tx, frames) = mpsc::channel::<Direct3D11CaptureFrame>();
+        pool.FrameArrived(&TypedEventHandler::<Direct3D11CaptureFramePool, IInspectable>::new(move |pool, _| {
+            if let Some(pool) = pool.as_ref() {
+                let _ = frame_tx.send(pool.TryGetNextFrame()?);
+            }
+            Ok(())
+        }))
+        .map_err(|e| e.to_string())?;
+
+        session.StartCapture().map_err(|e| format!("Failed to start capture: {}", e))?;
+        Ok(Self { device, pool, session, frames })
+    }
+
+    /// Continuous capture of a monitor; None means the primary monitor
+    pub(crate) fn monitor(index: Option<usize>, buffers: i32) -> Result<Self, String> {
+        Self::start(&monitor_item(monitor_handle(index)?)?, buffers)
+    }
+
+    /// Continuous capture of a single window
+    pub(crate) fn window(hwnd: isize, buffers: i32) -> Result<Self, String> {
+        Self::start(&window_item(hwnd)?, buffers)
+    }
+
+    /// Wait up to `timeout` for the next frame
+    pub(crate) fn next_frame(&self, timeout: Duration) -> Result<Frame, String> {
+        let frame = self
+            .frames
+            .recv_timeout(timeout)
+            .map_err(|_| "Timed out waiting for a captured frame".to_string())?;
+        self.read_frame(&frame)
+    }
+
+    /// The newest frame presented since the last call, skipping older ones; None when nothing changed
+    pub(crate) fn latest_frame(&self) -> Result<Option<Frame>, String> {
+        match self.frames.try_iter().last() {
+            Some(frame) => self.read_frame(&frame).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn read_frame(&self, frame: &Direct3D11CaptureFrame) -> Result<Frame, String> {
+        let texture: ID3D11Texture2D = frame
+            .Surface()
+            .and_then(|surface| surface.cast::<IDirect3DDxgiInterfaceAccess>())
+            .and_then(|access| unsafe { access.GetInterface() })
+            .map_err(|e| format!("Failed to access captured surface: {}", e))?;
+        read_texture(&self.device, &texture).map_err(|e| format!("Failed to read captured frame: {}", e))
+    }
+}
+
+impl Drop for CaptureStream {
+    fn drop(&mut self) {
+        let _ = self.session.Close();
+        let _ = self.pool.Close();
+    }
+}
+
+/// Grab one frame of `item`
+fn capture_item(item: &GraphicsCaptureItem) -> Result<Frame, String> {
+    CaptureStream::start(item, 1)?.next_frame(FRAME_TIMEOUT)
+}
+
+/// Copy a GPU texture into a staging texture and read its BGRA rows out
+fn read_texture(device: &ID3D11Device, texture: &ID3D11Texture2D) -> windows::core::Result<Frame> {
+    unsafe {
+        let mut desc = D3D11_TEXTURE2D_DESC::default();
+        texture.GetDesc(&mut desc);
+        desc.Usage = D3D11_USAGE_STAGING;
+        desc.BindFlags = 0;
+        desc.CPUAccessFlags = D3D11_CPU_ACCESS_READ.0 as u32;
+        desc.MiscFlags = 0;
+
+        let mut staging = None;
+        device.CreateTexture2D(&desc, None, Some(&mut staging))?;
+        let staging = staging.ok_or_else(|| windows::core::Error::from(windows::Win32::Foundation::E_FAIL))?;
+
+        let context = device.GetImmediateContext()?;
+        context.CopyResource(&staging, texture);
+
+        let mut mapped = D3D11_MAPPED_SUBRESOURCE::default();
+        context.Map(&staging, 0, D3D11_MAP_READ, 0, Some(&mut mapped))?;
+
+        let (width, height) = (desc.Width, desc.Height);
+        let row_bytes = width as usize * 4;
+        let mut bgra = Vec::with_capacity(row_bytes * height as usize);
+        for row in 0..height as usize {
+            let src = std::slice::from_raw_parts((mapped.pData as *const u8).add(row * mapped.RowPitch as usize), row_bytes);
+            bgra.extend_from_slice(src);
+        }
+        context.Unmap(&staging, 0);
+
+        Ok(Frame { width, height, bgra })
+    }
+}
+
+fn crop(frame: &Frame, x: u32, y: u32, width: u32, height: u32) -> Frame {
+    let row_bytes = frame.width as usize * 4;
+    let mut bgra = Vec::with_capacity(width as usize * height as usize * 4);
+    for row in y..y + height {
+        let start = row as usize * row_bytes + x as usize * 4;
+        bgra.extend_from_slice(&frame.bgra[start..start + width as usize * 4]);
+    }
+    Frame { width, height, bgra }
+}
+
+fn encode(frame: Frame, source: &str) -> Result<Screenshot, String> {
+    let (width, height) = (frame.width, frame.height);
+    let rgba = frame
+        .bgra
+        .chunks_exact(4)
+        .flat_map(|pixel| [pixel[2], pixel[1], pixel[0], 255])
+        .collect();
+    let image = image::RgbaImage::from_raw(width, height, rgba).ok_or("Captured frame has an unexpected size")?;
+    let mut png = Cursor::new(Vec::new());
+    image
+        .write_to(&mut png, image::ImageOutputFormat::Png)
+        .map_err(|e| format!("Failed to encode PNG: {}", e))?;
+    let png = png.into_inner();
+
+    info!("📸 Captured {} screenshot: {}x{} ({} KB)", source, width, height, png.len() / 1024);
+    Ok(Screenshot {
+        screenshot: base64::engine::general_purpose::STANDARD.encode(&png),
+        width,
+        height,
+        source: source.to_string(),
+    })
+}
+
+/// Run a capture on a blocking thread with WinRT initialized
+async fn run_capture<F>(capture: F) -> Result<Screenshot, String>
+where
+    F: FnOnce() -> Result<Screenshot, String> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || {
+        // Already-initialized threads report an error here that is safe to ignore
+        let _ = unsafe { RoInitialize(RO_INIT_MULTITHREADED) };
+        capture()
+    })
+    .await
+    .map_err(|e| format!("Screenshot task failed: {}", e))?
+}
+
+fn monitor_item(monitor: HMONITOR) -> Result<GraphicsCaptureItem, String> {
+    let interop = windows::core::factory::<GraphicsCaptureItem, IGraphicsCaptureItemInterop>().map_err(|e| e.to_string())?;
+    unsafe { interop.CreateForMonitor(monitor) }.map_err(|e| format!("Monitor cannot be captured: {}", e))
+}
+
+fn window_item(hwnd: isize) -> Result<GraphicsCaptureItem, String> {
+    let interop = windows::core::factory::<GraphicsCaptureItem, IGraphicsCaptureItemInterop>().map_err(|e| e.to_string())?;
+    unsafe { interop.CreateForWindow(HWND(hwnd as *mut _)) }.map_err(|e| format!("Window cannot be captured: {}", e))
+}
+
+fn monitor_handle(index: Option<usize>) -> Result<HMONITOR, String> {
+    match index {
+        Some(index) => monitors().get(index).map(|m| m.handle).ok_or(format!("No monitor at index {}", index)),
+        None => Ok(unsafe { MonitorFromPoint(POINT { x: 0, y: 0 }, MONITOR_DEFAULTTOPRIMARY) }),
+    }
+}
+
+/// Capture a whole monitor; None means the primary monitor
+pub async fn capture_monitor(index: Option<usize>) -> Result<Screenshot, String> {
+    run_capture(move || encode(capture_item(&monitor_item(monitor_handle(index)?)?)?, "monitor")).await
+}
+
+/// Capture a single window, even when it is partly covered by other windows
+pub async fn capture_window(hwnd: isize) -> Result<Screenshot, String> {
+    run_capture(move || encode(capture_item(&window_item(hwnd)?)?, "window")).await
+}
+
+/// Capture a rectangle in virtual-screen coordinates; it must lie on a single monitor
+pub async fn capture_region(x: i32, y: i32, width: u32, height: u32) -> Result<Screenshot, String> {
+    if width == 0 || height == 0 {
+        return Err("Region must have a non-zero size".to_string());
+    }
+    run_capture(move || {
+        let monitor = monitors()
+            .into_iter()
+            .find(|m| x >= m.rect.left && y >= m.rect.top && x < m.rect.right && y < m.rect.bottom)
+            .ok_or("Region is not on any monitor")?;
+
+        let frame = capture_item(&monitor_item(monitor.handle)?)?;
+        let left = (x - monitor.rect.left) as u32;
+        let top = (y - monitor.rect.top) as u32;
+        if left + width > frame.width || top + height > frame.height {
+            warn!("Screenshot region {}x{} at ({}, {}) extends past its monitor; clipping", width, height, x, y);
+        }
+        let width = width.min(frame.width - left);
+        let height = height.min(frame.height - top);
+        encode(crop(&frame, left, top, width, height), "region")
+    })
+    .await
+}
+
+/// Monitors available for capture, in enumeration order (the index used by capture commands)
+#[tauri::command]
+#[specta::specta]
+pub async fn list_capture_monitors() -> Response<Vec<MonitorInfo>> {
+    envelope::respond("list_capture_monitors", async move {
+        Ok(monitors()
+            .iter()
+            .enumerate()
+            .map(|(index, m)| MonitorInfo {
+                index,
+                x: m.rect.left,
+                y: m.rect.top,
+                width: (m.rect.right - m.rect.left) as u32,
+                height: (m.rect.bottom - m.rect.top) as u32,
+                primary: m.primary,
+            })
+            .collect())
+    })
+    .await
+}
+
+/// Capture a monitor as PNG (primary when no index is given)
+#[tauri::command]
+#[specta::specta]
+pub async fn capture_monitor_screenshot(monitor_index: Option<usize>) -> Response<Screenshot> {
+    guard::respond("capture_monitor_screenshot", &[Require::Permission(Permission::ScreenRecording)], async move {
+        crate::telemetry::record_feature("screenshot_monitor");
+        Ok(capture_monitor(monitor_index).await?)
+    })
+    .await
+}
+
+/// Capture one window as PNG
+#[tauri::command]
+#[specta::specta]
+pub async fn capture_window_screenshot(hwnd: isize) -> Response<Screenshot> {
+    guard::respond("capture_window_screenshot", &[Require::Permission(Permission::ScreenRecording)], async move {
+        crate::telemetry::record_feature("screenshot_window");
+        Ok(capture_window(hwnd).await?)
+    })
+    .await
+}
+
+/// Capture a screen region as PNG
+#[tauri::command]
+#[specta::specta]
+pub async fn capture_region_screenshot(x: i32, y: i32, width: u32, height: u32) -> Response<Screenshot> {
+    guard::respond("capture_region_screenshot", &[Require::Permission(Permission::ScreenRecording)], async move {
+        crate::telemetry::record_feature("screenshot_region");
+        Ok(capture_region(x, y, width, height).await?)
+    })
+    .await
+}
@@ -0,0 +1,70 @@
+// Screenshots on platforms without the Windows Graphics Capture API
+// Same commands and types as screenshot.rs; every capture fails with the "not available" message
+// and no monitors are listed.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::platform::{unavailable, Capability};
+use crate::envelope::{self, Response};
+
+/// A captured image as base64 PNG
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Screenshot {
+    /// Base64 encoded PNG
+    pub screenshot: String,
+    pub width: u32,
+    pub height: u32,
+    /// "monitor", "window" or "region"
+    pub source: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct MonitorInfo {
+    pub index: usize,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub primary: bool,
+}
+
+pub async fn capture_monitor(_index: Option<usize>) -> Result<Screenshot, String> {
+    Err(unavailable(Capability::Screenshot))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_capture_monitors() -> Response<Vec<MonitorInfo>> {
+    envelope::respond("list_capture_monitors", async move {
+        Ok(Vec::new())
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn capture_monitor_screenshot(monitor_index: Option<usize>) -> Response<Screenshot> {
+    envelope::respond("capture_monitor_screenshot", async move {
+        Ok(capture_monitor(monitor_index).await?)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn capture_window_screenshot(_hwnd: isize) -> Response<Screenshot> {
+    envelope::respond("capture_window_screenshot", async move {
+        Err(unavailable(Capability::Screenshot).into())
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn capture_region_screenshot(_x: i32, _y: i32, _width: u32, _height: u32) -> Response<Screenshot> {
+    envelope::respond("capture_region_screenshot", async move {
+        Err(unavailable(Capability::Screenshot).into())
+    })
+    .await
+}
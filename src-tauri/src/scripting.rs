@@ -0,0 +1,492 @@
+// Rhai scripting hooks for power users
+// Scripts in %APPDATA%\MockMate\scripts\*.rhai define hook functions that run on backend events:
+//   fn on_question_detected(event) { ... }   - returning false drops the question
+//   fn on_transcript_segment(event) { ... }
+//   fn on_answer_complete(event) { ... }
+// Scripts can only reach the app through `log(msg)` and `call(command, #{ ... })`, which accepts
+// the commands in WHITELISTED_COMMANDS. Hooks run on their own thread with an operation budget,
+// so a slow or looping script can't stall capture or streaming. Disabled until the user opts in.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, OnceLock};
+
+use log::{error, info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use rhai::{Dynamic, Engine, EvalAltResult, Map, Scope, AST};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter};
+use crate::envelope::{self, IntoResult, Response};
+use crate::events;
+
+/// Commands scripts may invoke through `call`
+pub const WHITELISTED_COMMANDS: &[&str] = &[
+    "copy_to_clipboard",
+    "notify",
+    "emit_ui",
+    "show_main_window",
+    "hide_main_window",
+];
+
+/// Upper bound on work per hook invocation; a script exceeding it is aborted
+const MAX_OPERATIONS: u64 = 200_000;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static SETTINGS: Lazy<Mutex<ScriptingSettings>> = Lazy::new(|| Mutex::new(load_settings()));
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+static WORKER: OnceLock<mpsc::Sender<Job>> = OnceLock::new();
+/// Result of the last (re)load, for get_scripting_status
+static LOADED: Lazy<Mutex<Vec<ScriptInfo>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+type Job = Box<dyn FnOnce(&mut ScriptHost) + Send>;
+
+/// Backend events scripts can hook
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ScriptEvent {
+    QuestionDetected,
+    TranscriptSegment,
+    AnswerComplete,
+}
+
+impl ScriptEvent {
+    fn hook_name(&self) -> &'static str {
+        match self {
+            ScriptEvent::QuestionDetected => "on_question_detected",
+            ScriptEvent::TranscriptSegment => "on_transcript_segment",
+            ScriptEvent::AnswerComplete => "on_answer_complete",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ScriptingSettings {
+    enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ScriptInfo {
+    pub name: String,
+    /// Hook functions the script defines
+    pub hooks: Vec<String>,
+    /// Compile error, if the script failed to load
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ScriptingStatus {
+    pub enabled: bool,
+    pub scripts_dir: String,
+    pub scripts: Vec<ScriptInfo>,
+    pub commands: Vec<String>,
+}
+
+/// Payload of `script-notification`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct ScriptNotification {
+    pub title: String,
+    pub body: String,
+}
+
+struct LoadedScript {
+    name: String,
+    ast: AST,
+}
+
+/// Engine plus compiled scripts; lives on the script thread only
+struct ScriptHost {
+    engine: Engine,
+    scripts: Vec<LoadedScript>,
+}
+
+pub fn scripts_dir() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("scripts")
+}
+
+fn settings_path() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("scripting.json")
+}
+
+fn load_settings() -> ScriptingSettings {
+    let settings = std::fs::read(settings_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice::<ScriptingSettings>(&body).ok())
+        // Opt-in: scripts never run until the user enables them
+        .unwrap_or(ScriptingSettings { enabled: false });
+    ENABLED.store(settings.enabled, Ordering::Relaxed);
+    settings
+}
+
+fn save_settings(settings: &ScriptingSettings) -> Result<(), String> {
+    let path = settings_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(settings).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| e.to_string())
+}
+
+/// Remember the app handle for whitelisted commands and load scripts if scripting is enabled
+pub fn init(app_handle: AppHandle) {
+    let _ = APP_HANDLE.set(app_handle);
+    let enabled = SETTINGS.lock().enabled;
+    info!("📜 Script hooks {}", if enabled { "enabled" } else { "disabled" });
+    if enabled {
+        reload_in_background();
+    }
+}
+
+fn build_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(32);
+    engine.disable_symbol("eval");
+
+    engine.register_fn("log", |message: &str| {
+        info!("📜 [script] {}", message);
+    });
+    engine.register_fn("call", |command: &str, args: Map| -> Result<Dynamic, Box<EvalAltResult>> {
+        call_command(command, &args).map_err(|e| format!("call(\"{}\"): {}", command, e).into())
+    });
+    engine
+}
+
+fn string_arg(args: &Map, key: &str) -> Result<String, String> {
+    args.get(key)
+        .map(|value| value.to_string())
+        .ok_or_else(|| format!("missing argument '{}'", key))
+}
+
+/// Run a whitelisted command on behalf of a script
+fn call_command(command: &str, args: &Map) -> Result<Dynamic, String> {
+    if !WHITELISTED_COMMANDS.contains(&command) {
+        return Err("command is not available to scripts".to_string());
+    }
+    let app_handle = APP_HANDLE.get().ok_or("app is not ready")?.clone();
+
+    match command {
+        "copy_to_clipboard" => set_clipboard_text(&string_arg(args, "text")?).map(|_| Dynamic::UNIT),
+        "notify" => {
+            let notification = ScriptNotification {
+                title: string_arg(args, "title")?,
+                body: string_arg(args, "body")?,
+            };
+            app_handle.emit(events::SCRIPT_NOTIFICATION, &notification).map_err(|e| e.to_string())?;
+            Ok(Dynamic::UNIT)
+        }
+        "emit_ui" => {
+            let payload = match args.get("payload") {
+                Some(value) => rhai::serde::from_dynamic::<serde_json::Value>(value).map_err(|e| e.to_string())?,
+                None => serde_json::Value::Null,
+            };
+            let event = events::ScriptUiEvent {
+                name: string_arg(args, "name")?,
+                payload,
+            };
+            app_handle.emit(events::SCRIPT_EVENT, &event).map_err(|e| e.to_string())?;
+            Ok(Dynamic::UNIT)
+        }
+        "show_main_window" => crate::show_main_window(app_handle).into_result().map(Dynamic::from).map_err(|e| e.to_string()),
+        "hide_main_window" => crate::hide_main_window(app_handle).into_result().map(Dynamic::from).map_err(|e| e.to_string()),
+        _ => Err("command is not available to scripts".to_string()),
+    }
+}
+
+#[cfg(windows)]
+fn set_clipboard_text(text: &str) -> Result<(), String> {
+    use windows_sys::Win32::System::DataExchange::{CloseClipboard, EmptyClipboard, OpenClipboard, SetClipboardData};
+    use windows_sys::Win32::Foundation::GlobalFree;
+    use windows_sys::Win32::System::Memory::{GlobalAlloc, GlobalLock, GlobalUnlock, GMEM_MOVEABLE};
+    use windows_sys::Win32::System::Ole::CF_UNICODETEXT;
+
+    let wide: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+    unsafe {
+        if OpenClipboard(0) == 0 {
+            return Err(format!("failed to open clipboard: {}", std::io::Error::last_os_error()));
+        }
+        let result = (|| {
+            EmptyClipboard();
+            let memory = GlobalAlloc(GMEM_MOVEABLE, wide.len() * std::mem::size_of::<u16>());
+            if memory.is_null() {
+                return Err("failed to allocate clipboard memory".to_string());
+            }
+            let target = GlobalLock(memory) as *mut u16;
+            if target.is_null() {
+                GlobalFree(memory);
+                return Err("failed to lock clipboard memory".to_string());
+            }
+            std::ptr::copy_nonoverlapping(wide.as_ptr(), target, wide.len());
+            GlobalUnlock(memory);
+            // On success the clipboard owns the memory
+            if SetClipboardData(CF_UNICODETEXT as u32, memory as isize) == 0 {
+                GlobalFree(memory);
+                return Err(format!("failed to set clipboard data: {}", std::io::Error::last_os_error()));
+            }
+            Ok(())
+        })();
+        CloseClipboard();
+        result
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn set_clipboard_text(text: &str) -> Result<(), String> {
+    use std::io::Write;
+
+    let mut pbcopy = std::process::Command::new("pbcopy")
+        .stdin(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to run pbcopy: {}", e))?;
+    if let Some(mut stdin) = pbcopy.stdin.take() {
+        stdin.write_all(text.as_bytes()).map_err(|e| format!("failed to write to pbcopy: {}", e))?;
+    }
+    let status = pbcopy.wait().map_err(|e| format!("failed to run pbcopy: {}", e))?;
+    if !status.success() {
+        return Err(format!("pbcopy failed: {}", status));
+    }
+    Ok(())
+}
+
+/// wl-copy on Wayland sessions, xclip on X11
+#[cfg(target_os = "linux")]
+fn set_clipboard_text(text: &str) -> Result<(), String> {
+    use std::io::Write;
+
+    let (program, args): (&str, &[&str]) = if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        ("wl-copy", &[])
+    } else {
+        ("xclip", &["-selection", "clipboard"])
+    };
+    let mut copy = std::process::Command::new(program)
+        .args(args)
+        .stdin(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to run {}: {}", program, e))?;
+    if let Some(mut stdin) = copy.stdin.take() {
+        stdin.write_all(text.as_bytes()).map_err(|e| format!("failed to write to {}: {}", program, e))?;
+    }
+    let status = copy.wait().map_err(|e| format!("failed to run {}: {}", program, e))?;
+    if !status.success() {
+        return Err(format!("{} failed: {}", program, status));
+    }
+    Ok(())
+}
+
+#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+fn set_clipboard_text(_text: &str) -> Result<(), String> {
+    Err("clipboard access is not available on this platform".to_string())
+}
+
+impl ScriptHost {
+    fn new() -> Self {
+        Self {
+            engine: build_engine(),
+            scripts: Vec::new(),
+        }
+    }
+
+    /// Compile every *.rhai file in the scripts directory, replacing the loaded set
+    fn reload(&mut self) -> Vec<ScriptInfo> {
+        let dir = scripts_dir();
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(&dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| path.extension().is_some_and(|ext| ext == "rhai"))
+                    .collect()
+            })
+            .unwrap_or_default();
+        paths.sort();
+
+        self.scripts.clear();
+        let mut infos = Vec::new();
+        for path in paths {
+            let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            match self.engine.compile_file(path.clone()) {
+                Ok(ast) => {
+                    let hooks: Vec<String> = [
+                        ScriptEvent::QuestionDetected,
+                        ScriptEvent::TranscriptSegment,
+                        ScriptEvent::AnswerComplete,
+                    ]
+                    .iter()
+                    .map(|event| event.hook_name())
+                    .filter(|hook| ast.iter_functions().any(|f| f.name == *hook && f.params.len() == 1))
+                    .map(str::to_string)
+                    .collect();
+                    info!("📜 Loaded script {} (hooks: {:?})", name, hooks);
+                    infos.push(ScriptInfo { name: name.clone(), hooks, error: None });
+                    self.scripts.push(LoadedScript { name, ast });
+                }
+                Err(e) => {
+                    warn!("Failed to compile script {}: {}", name, e);
+                    infos.push(ScriptInfo {
+                        name,
+                        hooks: Vec::new(),
+                        error: Some(e.to_string()),
+                    });
+                }
+            }
+        }
+        infos
+    }
+
+    /// Run `event`'s hook in every script; false if any hook returned `false`
+    fn run_hooks(&self, event: ScriptEvent, payload: &serde_json::Value) -> bool {
+        let hook = event.hook_name();
+        let argument = match rhai::serde::to_dynamic(payload) {
+            Ok(argument) => argument,
+            Err(e) => {
+                warn!("Failed to convert {} payload for scripts: {}", hook, e);
+                return true;
+            }
+        };
+
+        let mut keep = true;
+        for script in &self.scripts {
+            if !script.ast.iter_functions().any(|f| f.name == hook) {
+                continue;
+            }
+            match self
+                .engine
+                .call_fn::<Dynamic>(&mut Scope::new(), &script.ast, hook, (argument.clone(),))
+            {
+                Ok(result) => {
+                    if result.as_bool() == Ok(false) {
+                        keep = false;
+                    }
+                }
+                Err(e) => warn!("📜 Script {} failed in {}: {}", script.name, hook, e),
+            }
+        }
+        keep
+    }
+}
+
+fn worker() -> &'static mpsc::Sender<Job> {
+    WORKER.get_or_init(|| {
+        let (tx, rx) = mpsc::channel::<Job>();
+        let spawned = std::thread::Builder::new()
+            .name("script-hooks".to_string())
+            .spawn(move || {
+                let mut host = ScriptHost::new();
+                for job in rx {
+                    job(&mut host);
+                }
+            });
+        if let Err(e) = spawned {
+            error!("Failed to spawn script thread: {}", e);
+        }
+        tx
+    })
+}
+
+async fn run<T, F>(job: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut ScriptHost) -> T + Send + 'static,
+{
+    let (reply_tx, reply_rx) = tokio::sync::oneshot::channel();
+    worker()
+        .send(Box::new(move |host| {
+            let _ = reply_tx.send(job(host));
+        }))
+        .map_err(|_| "Script thread is not running".to_string())?;
+    reply_rx
+        .await
+        .map_err(|_| "Script thread stopped before finishing the request".to_string())
+}
+
+fn reload_in_background() {
+    let _ = worker().send(Box::new(|host| {
+        *LOADED.lock() = host.reload();
+    }));
+}
+
+/// Run hooks for `event` without waiting for them
+pub fn dispatch(event: ScriptEvent, payload: impl Serialize) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    match serde_json::to_value(payload) {
+        Ok(payload) => {
+            let _ = worker().send(Box::new(move |host| {
+                host.run_hooks(event, &payload);
+            }));
+        }
+        Err(e) => warn!("Failed to serialize {:?} payload for scripts: {}", event, e),
+    }
+}
+
+/// Run hooks for `event` and wait for them; false means a script asked to drop the event
+pub async fn filter(event: ScriptEvent, payload: impl Serialize) -> bool {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return true;
+    }
+    let Ok(payload) = serde_json::to_value(payload) else {
+        return true;
+    };
+    run(move |host| host.run_hooks(event, &payload)).await.unwrap_or(true)
+}
+
+fn status() -> ScriptingStatus {
+    ScriptingStatus {
+        enabled: ENABLED.load(Ordering::Relaxed),
+        scripts_dir: scripts_dir().display().to_string(),
+        scripts: LOADED.lock().clone(),
+        commands: WHITELISTED_COMMANDS.iter().map(|c| c.to_string()).collect(),
+    }
+}
+
+/// Loaded scripts, their hooks and compile errors
+#[tauri::command]
+#[specta::specta]
+pub async fn get_scripting_status() -> Response<ScriptingStatus> {
+    envelope::respond("get_scripting_status", async move {
+        Ok(status())
+    })
+    .await
+}
+
+/// Turn script hooks on or off; enabling loads the scripts directory
+#[tauri::command]
+#[specta::specta]
+pub async fn set_scripting_enabled(enabled: bool) -> Response<ScriptingStatus> {
+    envelope::respond("set_scripting_enabled", async move {
+        {
+            let mut settings = SETTINGS.lock();
+            settings.enabled = enabled;
+            save_settings(&settings)?;
+        }
+        ENABLED.store(enabled, Ordering::Relaxed);
+        info!("📜 Script hooks {}", if enabled { "enabled" } else { "disabled" });
+
+        if enabled {
+            std::fs::create_dir_all(scripts_dir()).map_err(|e| e.to_string())?;
+            reload_scripts().await.into_result()
+        } else {
+            Ok(status())
+        }
+    })
+    .await
+}
+
+/// Recompile the scripts directory after the user edits scripts
+#[tauri::command]
+#[specta::specta]
+pub async fn reload_scripts() -> Response<ScriptingStatus> {
+    envelope::respond("reload_scripts", async move {
+        let scripts = run(|host| host.reload()).await?;
+        *LOADED.lock() = scripts;
+        Ok(status())
+    })
+    .await
+}
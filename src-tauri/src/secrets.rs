@@ -0,0 +1,232 @@
+// Secrets in the OS credential store
+// Integration tokens are stored as generic credentials named "MockMate/<name>", encrypted by the OS
+// for the signed-in user, instead of in the plain JSON settings files next to them. Windows uses the
+// Credential Manager, macOS the login keychain (through `security`) and Linux the Secret Service
+// keyring (through `secret-tool`); elsewhere there is no store, so nothing can be saved and nothing
+// is ever found.
+
+#[cfg(windows)]
+use windows_sys::Win32::Foundation::{GetLastError, ERROR_NOT_FOUND, FILETIME};
+#[cfg(windows)]
+use windows_sys::Win32::Security::Credentials::{
+    CredDeleteW, CredFree, CredReadW, CredWriteW, CREDENTIALW, CRED_PERSIST_LOCAL_MACHINE, CRED_TYPE_GENERIC,
+};
+
+/// `name` within the active account profile; the default profile keeps unprefixed names
+#[cfg_attr(not(any(windows, target_os = "macos", target_os = "linux")), allow(dead_code))]
+fn scoped(name: &str) -> String {
+    match crate::accounts::secret_scope() {
+        Some(profile) => format!("{}/{}", profile, name),
+        None => name.to_string(),
+    }
+}
+
+#[cfg(windows)]
+fn target_name(name: &str) -> Vec<u16> {
+    format!("MockMate/{}", scoped(name)).encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+#[cfg(windows)]
+pub fn set_secret(name: &str, value: &str) -> Result<(), String> {
+    let mut target = target_name(name);
+    let mut blob = value.as_bytes().to_vec();
+    let credential = CREDENTIALW {
+        Flags: 0,
+        Type: CRED_TYPE_GENERIC,
+        TargetName: target.as_mut_ptr(),
+        Comment: std::ptr::null_mut(),
+        LastWritten: FILETIME { dwLowDateTime: 0, dwHighDateTime: 0 },
+        CredentialBlobSize: blob.len() as u32,
+        CredentialBlob: blob.as_mut_ptr(),
+        Persist: CRED_PERSIST_LOCAL_MACHINE,
+        AttributeCount: 0,
+        Attributes: std::ptr::null_mut(),
+        TargetAlias: std::ptr::null_mut(),
+        UserName: std::ptr::null_mut(),
+    };
+    if unsafe { CredWriteW(&credential, 0) } == 0 {
+        return Err(format!("Failed to store {} in Credential Manager: {}", name, std::io::Error::last_os_error()));
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+pub fn get_secret(name: &str) -> Result<Option<String>, String> {
+    let target = target_name(name);
+    let mut credential: *mut CREDENTIALW = std::ptr::null_mut();
+    unsafe {
+        if CredReadW(target.as_ptr(), CRED_TYPE_GENERIC, 0, &mut credential) == 0 {
+            return match GetLastError() {
+                ERROR_NOT_FOUND => Ok(None),
+                _ => Err(format!("Failed to read {} from Credential Manager: {}", name, std::io::Error::last_os_error())),
+            };
+        }
+        let blob = std::slice::from_raw_parts((*credential).CredentialBlob, (*credential).CredentialBlobSize as usize);
+        let value = String::from_utf8_lossy(blob).into_owned();
+        CredFree(credential as *const _);
+        Ok(Some(value))
+    }
+}
+
+/// Remove a secret; missing secrets are not an error
+#[cfg(windows)]
+pub fn delete_secret(name: &str) -> Result<(), String> {
+    let target = target_name(name);
+    unsafe {
+        if CredDeleteW(target.as_ptr(), CRED_TYPE_GENERIC, 0) == 0 && GetLastError() != ERROR_NOT_FOUND {
+            return Err(format!("Failed to delete {} from Credential Manager: {}", name, std::io::Error::last_os_error()));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+pub use keychain::{delete_secret, get_secret, set_secret};
+
+#[cfg(target_os = "macos")]
+mod keychain {
+    use std::process::{Command, Output};
+
+    const ACCOUNT: &str = "MockMate";
+    /// `security` exit status for an item that is not in the keychain
+    const ITEM_NOT_FOUND: i32 = 44;
+
+    fn security(args: &[&str], name: &str) -> Result<Output, String> {
+        let service = format!("MockMate/{}", super::scoped(name));
+        Command::new("/usr/bin/security")
+            .args(args)
+            .args(["-a", ACCOUNT, "-s", &service])
+            .output()
+            .map_err(|e| format!("Failed to run security for {}: {}", name, e))
+    }
+
+    fn failure(action: &str, name: &str, output: &Output) -> String {
+        format!(
+            "Failed to {} {} in the keychain: {}",
+            action,
+            name,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+    }
+
+    pub fn set_secret(name: &str, value: &str) -> Result<(), String> {
+        // -U updates an existing item instead of failing on the duplicate
+        let output = security(&["add-generic-password", "-U", "-w", value], name)?;
+        if !output.status.success() {
+            return Err(failure("store", name, &output));
+        }
+        Ok(())
+    }
+
+    pub fn get_secret(name: &str) -> Result<Option<String>, String> {
+        let output = security(&["find-generic-password", "-w"], name)?;
+        match output.status.code() {
+            Some(0) => Ok(Some(String::from_utf8_lossy(&output.stdout).trim_end_matches('\n').to_string())),
+            Some(ITEM_NOT_FOUND) => Ok(None),
+            _ => Err(failure("read", name, &output)),
+        }
+    }
+
+    /// Remove a secret; missing secrets are not an error
+    pub fn delete_secret(name: &str) -> Result<(), String> {
+        let output = security(&["delete-generic-password"], name)?;
+        match output.status.code() {
+            Some(0) | Some(ITEM_NOT_FOUND) => Ok(()),
+            _ => Err(failure("delete", name, &output)),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+pub use secret_service::{delete_secret, get_secret, set_secret};
+
+#[cfg(target_os = "linux")]
+mod secret_service {
+    use std::io::Write;
+    use std::process::{Command, Output, Stdio};
+
+    /// Lookup attributes; the pair identifies the item in the keyring
+    fn attributes(name: &str) -> [&str; 4] {
+        ["application", "MockMate", "name", name]
+    }
+
+    fn failure(action: &str, name: &str, output: &Output) -> String {
+        format!(
+            "Failed to {} {} in the keyring: {}",
+            action,
+            name,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+    }
+
+    fn secret_tool(args: &[&str], name: &str, input: Option<&str>) -> Result<Output, String> {
+        let mut child = Command::new("secret-tool")
+            .args(args)
+            .args(attributes(&super::scoped(name)))
+            .stdin(if input.is_some() { Stdio::piped() } else { Stdio::null() })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Failed to run secret-tool for {} (install libsecret-tools): {}", name, e))?;
+        if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+            stdin
+                .write_all(input.as_bytes())
+                .map_err(|e| format!("Failed to pass {} to secret-tool: {}", name, e))?;
+        }
+        child
+            .wait_with_output()
+            .map_err(|e| format!("Failed to run secret-tool for {}: {}", name, e))
+    }
+
+    pub fn set_secret(name: &str, value: &str) -> Result<(), String> {
+        // The value goes over stdin so it never shows up in the process list
+        let label = format!("--label=MockMate/{}", super::scoped(name));
+        let output = secret_tool(&["store", &label], name, Some(value))?;
+        if !output.status.success() {
+            return Err(failure("store", name, &output));
+        }
+        Ok(())
+    }
+
+    pub fn get_secret(name: &str) -> Result<Option<String>, String> {
+        let output = secret_tool(&["lookup"], name, None)?;
+        if output.status.success() {
+            return Ok(Some(String::from_utf8_lossy(&output.stdout).trim_end_matches('\n').to_string()));
+        }
+        // A missing item fails without saying anything; real errors are reported on stderr
+        if output.stderr.is_empty() {
+            Ok(None)
+        } else {
+            Err(failure("read", name, &output))
+        }
+    }
+
+    /// Remove a secret; missing secrets are not an error
+    pub fn delete_secret(name: &str) -> Result<(), String> {
+        let output = secret_tool(&["clear"], name, None)?;
+        if output.status.success() || output.stderr.is_empty() {
+            Ok(())
+        } else {
+            Err(failure("delete", name, &output))
+        }
+    }
+}
+
+#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+pub fn set_secret(name: &str, _value: &str) -> Result<(), String> {
+    Err(format!(
+        "Cannot store {}: {}",
+        name,
+        crate::platform::unavailable(crate::platform::Capability::CredentialStore)
+    ))
+}
+
+#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+pub fn get_secret(_name: &str) -> Result<Option<String>, String> {
+    Ok(None)
+}
+
+#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+pub fn delete_secret(_name: &str) -> Result<(), String> {
+    Ok(())
+}
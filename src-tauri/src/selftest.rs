@@ -0,0 +1,223 @@
+// Runtime self-test of the platform FFI paths
+// The Win32/WASAPI code casts window handles and callback parameters between pointers and
+// integers, and that is where a build for a new architecture (aarch64-pc-windows-msvc on Surface
+// Pro X and other Windows on ARM devices) breaks first. `run_platform_selftest` exercises those
+// paths on the user's machine so a support request can include a pass/fail per path instead of
+// "audio doesn't work".
+
+use log::{info, warn};
+use serde::Serialize;
+use specta::Type;
+use tauri::AppHandle;
+#[cfg(windows)]
+use tauri::Manager;
+use crate::envelope::{self, Response};
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct SelftestCheck {
+    pub name: String,
+    pub passed: bool,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct SelftestReport {
+    pub os: String,
+    /// Architecture this build was compiled for
+    pub arch: String,
+    /// Architecture of the machine, when the OS reports it
+    pub native_arch: Option<String>,
+    /// True when an x64 build runs under emulation on an ARM64 machine
+    pub emulated: bool,
+    pub passed: bool,
+    pub checks: Vec<SelftestCheck>,
+}
+
+fn check(name: &str, result: Result<String, String>) -> SelftestCheck {
+    let (passed, detail) = match result {
+        Ok(detail) => (true, detail),
+        Err(detail) => (false, detail),
+    };
+    SelftestCheck {
+        name: name.to_string(),
+        passed,
+        detail,
+    }
+}
+
+#[cfg(windows)]
+mod win {
+    use windows_sys::Win32::Foundation::{BOOL, HWND, LPARAM};
+    use windows_sys::Win32::System::SystemInformation::{
+        IMAGE_FILE_MACHINE, IMAGE_FILE_MACHINE_AMD64, IMAGE_FILE_MACHINE_ARM64, IMAGE_FILE_MACHINE_I386,
+        IMAGE_FILE_MACHINE_UNKNOWN,
+    };
+    use windows_sys::Win32::System::Threading::{GetCurrentProcess, IsWow64Process2};
+    use windows_sys::Win32::UI::WindowsAndMessaging::{EnumWindows, GetWindowThreadProcessId, IsWindow};
+
+    fn machine_name(machine: IMAGE_FILE_MACHINE) -> String {
+        match machine {
+            IMAGE_FILE_MACHINE_AMD64 => "x86_64".to_string(),
+            IMAGE_FILE_MACHINE_ARM64 => "aarch64".to_string(),
+            IMAGE_FILE_MACHINE_I386 => "x86".to_string(),
+            other => format!("machine 0x{:04x}", other),
+        }
+    }
+
+    /// Native machine architecture; x64 processes emulated on ARM64 report ARM64 here
+    pub fn native_arch() -> Option<String> {
+        let mut process_machine: IMAGE_FILE_MACHINE = IMAGE_FILE_MACHINE_UNKNOWN;
+        let mut native_machine: IMAGE_FILE_MACHINE = IMAGE_FILE_MACHINE_UNKNOWN;
+        // Only on Windows 10 1709 and later
+        if unsafe { IsWow64Process2(GetCurrentProcess(), &mut process_machine, &mut native_machine) } == 0 {
+            return None;
+        }
+        Some(machine_name(native_machine))
+    }
+
+    /// A handle from Tauri must survive the cast to HWND and still name a window of this process
+    pub fn window_handle(hwnd: HWND) -> Result<String, String> {
+        if unsafe { IsWindow(hwnd) } == 0 {
+            return Err(format!("Handle 0x{:x} is not a window after the cast", hwnd));
+        }
+        let mut pid = 0u32;
+        unsafe { GetWindowThreadProcessId(hwnd, &mut pid) };
+        if pid != std::process::id() {
+            return Err(format!("Handle 0x{:x} belongs to process {}, not this one", hwnd, pid));
+        }
+        Ok(format!("Handle 0x{:x} round-trips ({}-bit pointers)", hwnd, usize::BITS))
+    }
+
+    struct EnumState {
+        marker: u64,
+        pid: u32,
+        own_windows: usize,
+        corrupted: bool,
+    }
+
+    const MARKER: u64 = 0x4d6f_636b_4d61_7465;
+
+    unsafe extern "system" fn enum_proc(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let state = &mut *(lparam as *mut EnumState);
+        if state.marker != MARKER {
+            state.corrupted = true;
+            return 0;
+        }
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(hwnd, &mut pid);
+        if pid == state.pid {
+            state.own_windows += 1;
+        }
+        1
+    }
+
+    /// A pointer passed through LPARAM to a callback must come back intact
+    pub fn callback_lparam() -> Result<String, String> {
+        let mut state = EnumState {
+            marker: MARKER,
+            pid: std::process::id(),
+            own_windows: 0,
+            corrupted: false,
+        };
+        unsafe { EnumWindows(Some(enum_proc), &mut state as *mut EnumState as LPARAM) };
+        if state.corrupted {
+            return Err("EnumWindows handed back a different LPARAM than was passed in".to_string());
+        }
+        if state.own_windows == 0 {
+            return Err("EnumWindows did not report any window of this process".to_string());
+        }
+        Ok(format!("LPARAM round-trips; {} windows of this process enumerated", state.own_windows))
+    }
+
+    /// Open the default device through the same path the capture loops use and read one packet
+    pub fn wasapi_stream(direction: wasapi::Direction) -> Result<String, String> {
+        let (h_event, client) = crate::audio_thread::open_shared_stream(direction).map_err(|e| e.to_string())?;
+        if h_event.wait_for_event(1000).is_err() {
+            return Ok("Stream opened; no audio event within 1s (nothing playing?)".to_string());
+        }
+        let mut bytes = std::collections::VecDeque::new();
+        client.read_from_device_to_deque(&mut bytes).map_err(|e| e.to_string())?;
+        let mut samples = Vec::new();
+        crate::audio_buffer::extend_f32_from_le_bytes(bytes.make_contiguous(), &mut samples);
+        if samples.iter().any(|sample| !sample.is_finite()) {
+            return Err("Decoded samples are not finite; the buffer layout is wrong".to_string());
+        }
+        Ok(format!("Stream opened; read {} samples of 44.1kHz mono f32", samples.len()))
+    }
+}
+
+#[cfg(windows)]
+async fn platform_checks(app_handle: &AppHandle) -> Result<Vec<SelftestCheck>, String> {
+    let handle = app_handle
+        .get_webview_window("main")
+        .ok_or_else(|| "Main window not found".to_string())
+        .and_then(|window| window.hwnd().map_err(|e| e.to_string()))
+        .and_then(|hwnd| win::window_handle(hwnd.0 as windows_sys::Win32::Foundation::HWND));
+    let (system_audio, microphone) = tauri::async_runtime::spawn_blocking(|| {
+        let _ = wasapi::initialize_mta();
+        (
+            win::wasapi_stream(wasapi::Direction::Render),
+            win::wasapi_stream(wasapi::Direction::Capture),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    Ok(vec![
+        check("window_handle", handle),
+        check("callback_lparam", win::callback_lparam()),
+        check("wasapi_loopback", system_audio),
+        check("wasapi_microphone", microphone),
+    ])
+}
+
+/// Elsewhere the Win32 paths don't exist; report which audio backends this OS has
+#[cfg(not(windows))]
+async fn platform_checks(_app_handle: &AppHandle) -> Result<Vec<SelftestCheck>, String> {
+    use crate::platform::{ensure_available, Capability};
+
+    Ok(vec![
+        check(
+            "system_audio_capture",
+            ensure_available(Capability::SystemAudioCapture).map(|()| "Backend available".to_string()),
+        ),
+        check(
+            "microphone_capture",
+            ensure_available(Capability::MicrophoneCapture).map(|()| "Backend available".to_string()),
+        ),
+    ])
+}
+
+/// Exercise window handle, callback and audio FFI paths and report each one
+#[tauri::command]
+#[specta::specta]
+pub async fn run_platform_selftest(app_handle: AppHandle) -> Response<SelftestReport> {
+    envelope::respond("run_platform_selftest", async move {
+        info!("🧪 Running platform self-test");
+        let arch = std::env::consts::ARCH.to_string();
+        #[cfg(windows)]
+        let native_arch = win::native_arch();
+        #[cfg(not(windows))]
+        let native_arch: Option<String> = None;
+        let checks = platform_checks(&app_handle).await?;
+
+        let emulated = native_arch.as_deref().is_some_and(|native| native != arch);
+        if emulated {
+            warn!(
+                "Running a {} build emulated on {}; install the native build for working audio and accessibility",
+                arch,
+                native_arch.as_deref().unwrap_or_default()
+            );
+        }
+        let passed = checks.iter().all(|check| check.passed);
+        info!("🧪 Platform self-test {}", if passed { "passed" } else { "failed" });
+        Ok(SelftestReport {
+            os: std::env::consts::OS.to_string(),
+            arch,
+            native_arch,
+            emulated,
+            passed,
+            checks,
+        })
+    })
+    .await
+}
@@ -0,0 +1,271 @@
+// Temporary token lifetime for protocol-launched sessions
+// A mockmate://session launch authenticates with a short-lived temp token. Once it expired the session
+// used to stay half-authenticated: connected in the UI, rejected by the backend. Every successful
+// `connect_with_temp_token` is now tracked here with its expiry (from the backend response, or the
+// token's own `exp` claim when it is a JWT), and the token is exchanged for a fresh one at
+// /api/sessions/<id>/refresh-temp-token shortly before it expires. Failed refreshes are retried until
+// the token has actually expired; then `session-auth` reports "expired" and the UI asks for
+// `reauthenticate_session` with a new temp token from the web app. Tokens are kept in memory only.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter};
+
+use crate::envelope::{self, IntoResult, Response};
+use crate::error::{AppError, ErrorCode};
+use crate::events;
+use crate::net_trace::TracedSend;
+
+/// Refresh this long before expiry, or after three quarters of the lifetime for shorter tokens
+const REFRESH_MARGIN: Duration = Duration::from_secs(120);
+/// Wait between attempts after a failed refresh
+const RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+static TOKENS: Lazy<Mutex<HashMap<String, TrackedToken>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+/// Numbers each tracked token; a refresh loop stops once its session is tracked again or forgotten
+static GENERATION: Mutex<u64> = Mutex::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthStatus {
+    /// The temp token is valid
+    Active,
+    /// Refreshing failed; retrying until the token expires
+    RefreshFailed,
+    /// The token expired; `reauthenticate_session` needs a new one
+    Expired,
+}
+
+/// Payload of `session-auth` and result of the session auth commands
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct SessionAuthStatus {
+    pub session_id: String,
+    pub status: AuthStatus,
+    pub user_id: Option<String>,
+    /// None when the token's lifetime is unknown; it is then never refreshed
+    pub expires_at: Option<DateTime<Utc>>,
+    pub authenticated_at: DateTime<Utc>,
+    /// "authenticated", "refreshed", "reauthenticated", or why refreshing failed
+    pub reason: String,
+}
+
+struct TrackedToken {
+    token: String,
+    issued_at: DateTime<Utc>,
+    generation: u64,
+    status: SessionAuthStatus,
+}
+
+#[derive(Deserialize)]
+struct RefreshResponse {
+    #[serde(default)]
+    success: Option<bool>,
+    temp_token: String,
+    #[serde(default)]
+    expires_at: Option<String>,
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+pub fn init(app_handle: AppHandle) {
+    let _ = APP_HANDLE.set(app_handle);
+}
+
+fn backend_url() -> String {
+    crate::get_env_var("BACKEND_URL").unwrap_or_else(|| "http://localhost:3001".to_string())
+}
+
+/// `exp` claim of a JWT, read without verifying it; only used to schedule the refresh
+fn jwt_expiry(token: &str) -> Option<DateTime<Utc>> {
+    let claims = token.split('.').nth(1)?;
+    let claims = URL_SAFE_NO_PAD.decode(claims.trim_end_matches('=')).ok()?;
+    let claims: serde_json::Value = serde_json::from_slice(&claims).ok()?;
+    DateTime::from_timestamp(claims["exp"].as_i64()?, 0)
+}
+
+fn expiry(token: &str, expires_at: Option<&str>, expires_in: Option<u64>) -> Option<DateTime<Utc>> {
+    expires_at
+        .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+        .map(|at| at.with_timezone(&Utc))
+        .or_else(|| expires_in.map(|secs| Utc::now() + chrono::Duration::seconds(secs as i64)))
+        .or_else(|| jwt_expiry(token))
+}
+
+fn emit(status: &SessionAuthStatus) {
+    if let Some(app_handle) = APP_HANDLE.get() {
+        if let Err(e) = app_handle.emit(events::SESSION_AUTH, status) {
+            warn!("Failed to emit {}: {}", events::SESSION_AUTH, e);
+        }
+    }
+}
+
+/// Start tracking the temp token a session was authenticated with; called after every successful
+/// `connect_with_temp_token`
+pub(crate) fn track(session_id: &str, token: &str, user_id: Option<String>, expires_at: Option<&str>, expires_in: Option<u64>) {
+    let generation = {
+        let mut generation = GENERATION.lock();
+        *generation += 1;
+        *generation
+    };
+    let now = Utc::now();
+    let mut tokens = TOKENS.lock();
+    let reason = if tokens.contains_key(session_id) { "reauthenticated" } else { "authenticated" };
+    let status = SessionAuthStatus {
+        session_id: session_id.to_string(),
+        status: AuthStatus::Active,
+        user_id,
+        expires_at: expiry(token, expires_at, expires_in),
+        authenticated_at: now,
+        reason: reason.to_string(),
+    };
+    info!("🔐 Session {} {}; temp token expires {:?}", session_id, reason, status.expires_at);
+    emit(&status);
+    tokens.insert(
+        session_id.to_string(),
+        TrackedToken { token: token.to_string(), issued_at: now, generation, status },
+    );
+    drop(tokens);
+    spawn_refresh_loop(session_id.to_string(), generation);
+}
+
+/// Stop refreshing a session's token, e.g. when it is disconnected
+pub fn forget(session_id: &str) {
+    TOKENS.lock().remove(session_id);
+}
+
+fn set_status(session_id: &str, status: AuthStatus, reason: &str) {
+    let mut tokens = TOKENS.lock();
+    if let Some(tracked) = tokens.get_mut(session_id) {
+        tracked.status.status = status;
+        tracked.status.reason = reason.to_string();
+        emit(&tracked.status);
+    }
+}
+
+/// Exchange the session's temp token for a fresh one
+async fn refresh(session_id: &str) -> Result<SessionAuthStatus, String> {
+    let token = TOKENS
+        .lock()
+        .get(session_id)
+        .map(|tracked| tracked.token.clone())
+        .ok_or("Session is not authenticated with a temp token")?;
+
+    let response = reqwest::Client::new()
+        .post(format!("{}/api/sessions/{}/refresh-temp-token", backend_url(), session_id))
+        .json(&serde_json::json!({ "temp_token": token }))
+        .send_traced()
+        .await
+        .map_err(|e| format!("Network error refreshing temp token: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Temp token refresh failed: {}", response.status()));
+    }
+    let refreshed: RefreshResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse temp token refresh: {}", e))?;
+    if refreshed.success == Some(false) {
+        return Err("Backend refused to refresh the temp token".to_string());
+    }
+
+    let now = Utc::now();
+    let mut tokens = TOKENS.lock();
+    let tracked = tokens.get_mut(session_id).ok_or("Session was disconnected during the refresh")?;
+    tracked.status.expires_at = expiry(&refreshed.temp_token, refreshed.expires_at.as_deref(), refreshed.expires_in);
+    tracked.token = refreshed.temp_token;
+    tracked.issued_at = now;
+    tracked.status.status = AuthStatus::Active;
+    tracked.status.reason = "refreshed".to_string();
+    info!("🔐 Refreshed temp token for session {}; expires {:?}", session_id, tracked.status.expires_at);
+    emit(&tracked.status);
+    Ok(tracked.status.clone())
+}
+
+/// Expiry and issue time of the session's token while `generation` is current
+fn schedule(session_id: &str, generation: u64) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let tokens = TOKENS.lock();
+    let tracked = tokens.get(session_id).filter(|t| t.generation == generation)?;
+    Some((tracked.status.expires_at?, tracked.issued_at))
+}
+
+fn spawn_refresh_loop(session_id: String, generation: u64) {
+    tauri::async_runtime::spawn(async move {
+        let mut failed = false;
+        loop {
+            let Some((expires_at, issued_at)) = schedule(&session_id, generation) else {
+                return;
+            };
+            let now = Utc::now();
+            let wait = if failed {
+                chrono::Duration::from_std(RETRY_INTERVAL).unwrap_or_default().min(expires_at - now)
+            } else {
+                let margin = chrono::Duration::from_std(REFRESH_MARGIN).unwrap_or_default().min((expires_at - issued_at) / 4);
+                expires_at - margin - now
+            };
+            tokio::time::sleep(wait.to_std().unwrap_or_default()).await;
+            if schedule(&session_id, generation).is_none() {
+                return;
+            }
+
+            match refresh(&session_id).await {
+                Ok(_) => failed = false,
+                Err(e) if Utc::now() >= expires_at => {
+                    warn!("🔐 Temp token of session {} expired: {}", session_id, e);
+                    set_status(&session_id, AuthStatus::Expired, &e);
+                    return;
+                }
+                Err(e) => {
+                    warn!("🔐 Temp token refresh for session {} failed, retrying: {}", session_id, e);
+                    set_status(&session_id, AuthStatus::RefreshFailed, &e);
+                    failed = true;
+                }
+            }
+        }
+    });
+}
+
+/// Auth state of a session authenticated with a temp token; None for other sessions
+#[tauri::command]
+#[specta::specta]
+pub async fn get_session_auth_status(session_id: String) -> Response<Option<SessionAuthStatus>> {
+    envelope::respond("get_session_auth_status", async move {
+        Ok(TOKENS.lock().get(&session_id).map(|tracked| tracked.status.clone()))
+    })
+    .await
+}
+
+/// Authenticate a session again: with a new temp token from the web app when given, otherwise by
+/// refreshing the current one (which only works before it has expired)
+#[tauri::command]
+#[specta::specta]
+pub async fn reauthenticate_session(session_id: String, temp_token: Option<String>) -> Response<SessionAuthStatus> {
+    envelope::respond("reauthenticate_session", async move {
+        let Some(temp_token) = temp_token.filter(|t| !t.is_empty()) else {
+            return refresh(&session_id)
+                .await
+                .map_err(|e| AppError::new(ErrorCode::Unauthorized, format!("{}; open the session from the web app again", e)));
+        };
+
+        let payload = crate::TempTokenAuthPayload { session_id: session_id.clone(), temp_token };
+        let response = crate::connect_with_temp_token(payload, None).await.into_result()?;
+        if !response.success {
+            return Err(AppError::new(ErrorCode::Unauthorized, response.message));
+        }
+        TOKENS
+            .lock()
+            .get(&session_id)
+            .map(|tracked| tracked.status.clone())
+            .ok_or_else(|| AppError::new(ErrorCode::Internal, "Authenticated session was not tracked"))
+    })
+    .await
+}
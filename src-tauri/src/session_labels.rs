@@ -0,0 +1,376 @@
+// Session and answer tags, favorites and saved filters
+// The interview history lives in the shared database, whose schema belongs to the web app, so the
+// desktop keeps its own labels: tags and a favorite flag per session and per saved answer, plus
+// named filters such as "FAANG onsite loops" that combine them. Sessions keep a copy of their job
+// title so filtered lists are readable without a database round trip. Everything is one JSON file
+// in %APPDATA%\MockMate and never leaves the machine.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use log::{debug, info};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::envelope::{self, Response};
+use crate::question_bank::TagCount;
+
+static STORE: Lazy<Mutex<LabelStore>> = Lazy::new(|| Mutex::new(load_store()));
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct ItemLabels {
+    pub tags: Vec<String>,
+    pub favorite: bool,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl ItemLabels {
+    fn is_empty(&self) -> bool {
+        self.tags.is_empty() && !self.favorite
+    }
+
+    fn apply(&mut self, tags: Option<Vec<String>>, favorite: Option<bool>) {
+        if let Some(tags) = tags {
+            self.tags = clean_tags(&tags);
+        }
+        if let Some(favorite) = favorite {
+            self.favorite = favorite;
+        }
+        self.updated_at = Some(Utc::now());
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SessionEntry {
+    title: Option<String>,
+    labels: ItemLabels,
+    /// By answer ID
+    answers: BTreeMap<String, ItemLabels>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct LabelStore {
+    /// By session ID
+    sessions: BTreeMap<String, SessionEntry>,
+    filters: Vec<SavedFilter>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AnswerLabels {
+    pub answer_id: String,
+    #[serde(flatten)]
+    pub labels: ItemLabels,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SessionLabels {
+    pub session_id: String,
+    pub title: Option<String>,
+    #[serde(flatten)]
+    pub labels: ItemLabels,
+    pub answers: Vec<AnswerLabels>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum LabeledKind {
+    Session,
+    Answer,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct LabelFilter {
+    /// Sessions, answers, or both when unset
+    pub kind: Option<LabeledKind>,
+    /// Items must carry every listed tag
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Items must carry at least one of these tags
+    #[serde(default)]
+    pub any_tags: Vec<String>,
+    pub favorite: Option<bool>,
+    /// Case-insensitive substring match on the session title
+    pub search: Option<String>,
+    /// Only items labeled at or after this time
+    pub since: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SavedFilter {
+    pub id: String,
+    pub name: String,
+    pub filter: LabelFilter,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct LabeledItem {
+    pub kind: LabeledKind,
+    pub session_id: String,
+    pub answer_id: Option<String>,
+    pub title: Option<String>,
+    #[serde(flatten)]
+    pub labels: ItemLabels,
+}
+
+fn store_path() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("session_labels.json")
+}
+
+fn load_store() -> LabelStore {
+    std::fs::read(store_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn save_store(store: &LabelStore) -> Result<(), String> {
+    let path = store_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(store).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| e.to_string())
+}
+
+fn clean_tags(tags: &[String]) -> Vec<String> {
+    let mut tags: Vec<String> = tags
+        .iter()
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+fn session_labels(session_id: &str, entry: &SessionEntry) -> SessionLabels {
+    SessionLabels {
+        session_id: session_id.to_string(),
+        title: entry.title.clone(),
+        labels: entry.labels.clone(),
+        answers: entry
+            .answers
+            .iter()
+            .map(|(answer_id, labels)| AnswerLabels { answer_id: answer_id.clone(), labels: labels.clone() })
+            .collect(),
+    }
+}
+
+/// Drop a session entry once neither it nor any of its answers carries a label
+fn prune(store: &mut LabelStore, session_id: &str) {
+    if let Some(entry) = store.sessions.get_mut(session_id) {
+        entry.answers.retain(|_, labels| !labels.is_empty());
+        if entry.labels.is_empty() && entry.answers.is_empty() {
+            store.sessions.remove(session_id);
+        }
+    }
+}
+
+fn matches(labels: &ItemLabels, title: Option<&str>, filter: &LabelFilter) -> bool {
+    let tags = clean_tags(&filter.tags);
+    let any_tags = clean_tags(&filter.any_tags);
+    tags.iter().all(|tag| labels.tags.contains(tag))
+        && (any_tags.is_empty() || any_tags.iter().any(|tag| labels.tags.contains(tag)))
+        && filter.favorite.is_none_or(|favorite| labels.favorite == favorite)
+        && filter.since.is_none_or(|since| labels.updated_at.is_some_and(|at| at >= since))
+        && filter.search.as_deref().is_none_or(|search| {
+            title.is_some_and(|title| title.to_lowercase().contains(&search.trim().to_lowercase()))
+        })
+}
+
+/// Job title of a session from the database, when it is reachable
+async fn fetch_title(session_id: &str) -> Option<String> {
+    let db = crate::database::DatabaseManager::new().await.ok()?;
+    match db.get_session_by_id(session_id).await {
+        Ok(session) => Some(session.job_title),
+        Err(e) => {
+            debug!("No title for labeled session {}: {}", session_id, e);
+            None
+        }
+    }
+}
+
+/// Labels of a session and its answers (empty when nothing is labeled)
+#[tauri::command]
+#[specta::specta]
+pub async fn get_session_labels(session_id: String) -> Response<SessionLabels> {
+    envelope::respond("get_session_labels", async move {
+        let store = STORE.lock();
+        Ok(match store.sessions.get(&session_id) {
+            Some(entry) => session_labels(&session_id, entry),
+            None => session_labels(&session_id, &SessionEntry::default()),
+        })
+    })
+    .await
+}
+
+/// Set a session's tags and/or favorite flag; omitted values are kept
+#[tauri::command]
+#[specta::specta]
+pub async fn set_session_labels(
+    session_id: String,
+    tags: Option<Vec<String>>,
+    favorite: Option<bool>,
+) -> Response<SessionLabels> {
+    envelope::respond("set_session_labels", async move {
+        let needs_title = STORE.lock().sessions.get(&session_id).is_none_or(|entry| entry.title.is_none());
+        let title = if needs_title { fetch_title(&session_id).await } else { None };
+
+        let mut store = STORE.lock();
+        let entry = store.sessions.entry(session_id.clone()).or_default();
+        entry.labels.apply(tags, favorite);
+        if entry.title.is_none() {
+            entry.title = title;
+        }
+        let labels = session_labels(&session_id, entry);
+        prune(&mut store, &session_id);
+        save_store(&store)?;
+        info!("🏷️ Session {} labeled: {:?}, favorite {}", session_id, labels.labels.tags, labels.labels.favorite);
+        Ok(labels)
+    })
+    .await
+}
+
+/// Set a saved answer's tags and/or favorite flag; omitted values are kept
+#[tauri::command]
+#[specta::specta]
+pub async fn set_answer_labels(
+    session_id: String,
+    answer_id: String,
+    tags: Option<Vec<String>>,
+    favorite: Option<bool>,
+) -> Response<AnswerLabels> {
+    envelope::respond("set_answer_labels", async move {
+        let needs_title = !STORE.lock().sessions.contains_key(&session_id);
+        let title = if needs_title { fetch_title(&session_id).await } else { None };
+
+        let mut store = STORE.lock();
+        let entry = store.sessions.entry(session_id.clone()).or_default();
+        if entry.title.is_none() {
+            entry.title = title;
+        }
+        let labels = entry.answers.entry(answer_id.clone()).or_default();
+        labels.apply(tags, favorite);
+        let labels = AnswerLabels { answer_id, labels: labels.clone() };
+        prune(&mut store, &session_id);
+        save_store(&store)?;
+        Ok(labels)
+    })
+    .await
+}
+
+/// Labeled sessions and answers matching `filter`, most recently labeled first
+#[tauri::command]
+#[specta::specta]
+pub async fn find_labeled_items(filter: LabelFilter) -> Response<Vec<LabeledItem>> {
+    envelope::respond("find_labeled_items", async move {
+        let store = STORE.lock();
+        let mut items = Vec::new();
+        for (session_id, entry) in &store.sessions {
+            let title = entry.title.as_deref();
+            if filter.kind != Some(LabeledKind::Answer) && !entry.labels.is_empty() && matches(&entry.labels, title, &filter) {
+                items.push(LabeledItem {
+                    kind: LabeledKind::Session,
+                    session_id: session_id.clone(),
+                    answer_id: None,
+                    title: entry.title.clone(),
+                    labels: entry.labels.clone(),
+                });
+            }
+            if filter.kind != Some(LabeledKind::Session) {
+                for (answer_id, labels) in &entry.answers {
+                    if matches(labels, title, &filter) {
+                        items.push(LabeledItem {
+                            kind: LabeledKind::Answer,
+                            session_id: session_id.clone(),
+                            answer_id: Some(answer_id.clone()),
+                            title: entry.title.clone(),
+                            labels: labels.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        items.sort_by_key(|item| std::cmp::Reverse(item.labels.updated_at));
+        Ok(items)
+    })
+    .await
+}
+
+/// Every tag in use on sessions and answers, most used first
+#[tauri::command]
+#[specta::specta]
+pub async fn list_label_tags() -> Response<Vec<TagCount>> {
+    envelope::respond("list_label_tags", async move {
+        let store = STORE.lock();
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for entry in store.sessions.values() {
+            for labels in std::iter::once(&entry.labels).chain(entry.answers.values()) {
+                for tag in &labels.tags {
+                    *counts.entry(tag).or_default() += 1;
+                }
+            }
+        }
+        let mut tags: Vec<TagCount> = counts.into_iter().map(|(tag, count)| TagCount { tag: tag.to_string(), count }).collect();
+        tags.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+        Ok(tags)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_saved_filters() -> Response<Vec<SavedFilter>> {
+    envelope::respond("list_saved_filters", async move {
+        Ok(STORE.lock().filters.clone())
+    })
+    .await
+}
+
+/// Save `filter` under `name`, replacing a saved filter with the same name
+#[tauri::command]
+#[specta::specta]
+pub async fn save_label_filter(name: String, filter: LabelFilter) -> Response<SavedFilter> {
+    envelope::respond("save_label_filter", async move {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err("A filter name is required".into());
+        }
+        let mut store = STORE.lock();
+        let saved = match store.filters.iter_mut().find(|f| f.name.eq_ignore_ascii_case(&name)) {
+            Some(existing) => {
+                existing.filter = filter;
+                existing.clone()
+            }
+            None => {
+                let saved = SavedFilter { id: uuid::Uuid::new_v4().to_string(), name, filter, created_at: Utc::now() };
+                store.filters.push(saved.clone());
+                saved
+            }
+        };
+        save_store(&store)?;
+        Ok(saved)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_saved_filter(id: String) -> Response<()> {
+    envelope::respond("delete_saved_filter", async move {
+        let mut store = STORE.lock();
+        let before = store.filters.len();
+        store.filters.retain(|f| f.id != id);
+        if store.filters.len() == before {
+            return Err(format!("Saved filter {} not found", id).into());
+        }
+        Ok(save_store(&store)?)
+    })
+    .await
+}
@@ -0,0 +1,126 @@
+// Per-session custom system prompt
+// A session can carry its own instructions for the answer model, e.g. "answer as a staff-level SRE,
+// max 90 seconds of speech". They are given when the session is connected or activated, or later via
+// `update_interview_context` / `set_session_system_prompt`, and kept in
+// %APPDATA%\MockMate\session_prompts.json by session id, since the shared database schema belongs to
+// the web app. Activating a session loads its prompt into the interview context, so every generation
+// in the session is built with it; disconnecting takes it out of the context again.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use tauri::State;
+
+use crate::envelope::{self, Response};
+use crate::AppState;
+
+/// Longer prompts are cut; the instructions compete with the job description and resume for tokens
+const MAX_PROMPT_CHARS: usize = 2000;
+
+static PROMPTS: Lazy<Mutex<BTreeMap<String, String>>> = Lazy::new(|| Mutex::new(load_prompts()));
+
+fn prompts_path() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("session_prompts.json")
+}
+
+fn load_prompts() -> BTreeMap<String, String> {
+    std::fs::read(prompts_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn save_prompts(prompts: &BTreeMap<String, String>) -> Result<(), String> {
+    let path = prompts_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(prompts).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| e.to_string())
+}
+
+/// Trimmed and cut to the limit; `None` for an empty prompt
+fn clean(prompt: &str) -> Option<String> {
+    let prompt = prompt.trim();
+    if prompt.is_empty() {
+        return None;
+    }
+    Some(prompt.chars().take(MAX_PROMPT_CHARS).collect())
+}
+
+/// The custom system prompt stored for `session_id`
+pub fn get(session_id: &str) -> Option<String> {
+    PROMPTS.lock().get(session_id).cloned()
+}
+
+/// Store the prompt of `session_id`; an empty prompt removes it. Returns what was stored
+pub fn set(session_id: &str, prompt: &str) -> Result<Option<String>, String> {
+    let prompt = clean(prompt);
+    let mut prompts = PROMPTS.lock();
+    match &prompt {
+        Some(prompt) => prompts.insert(session_id.to_string(), prompt.clone()),
+        None => prompts.remove(session_id),
+    };
+    save_prompts(&prompts)?;
+    info!("📝 Custom system prompt for session {} {}", session_id, if prompt.is_some() { "set" } else { "cleared" });
+    Ok(prompt)
+}
+
+/// Store `prompt` for `session_id` when one was given, then load the session's prompt into the
+/// interview context; called when a session is activated
+pub(crate) fn activate(state: &AppState, session_id: &str, prompt: Option<&str>) {
+    if let Some(prompt) = prompt {
+        if let Err(e) = set(session_id, prompt) {
+            warn!("Failed to store custom system prompt for session {}: {}", session_id, e);
+        }
+    }
+    state.interview_context.lock().custom_system_prompt = get(session_id);
+}
+
+/// Take the session prompt out of the interview context when the session ends
+pub(crate) fn deactivate(state: &AppState) {
+    state.interview_context.lock().custom_system_prompt = None;
+}
+
+/// Change the prompt of the active session from `update_interview_context`
+pub(crate) fn update_current(state: &AppState, prompt: &str) -> Result<(), String> {
+    let prompt = match crate::audit::current_session() {
+        Some(session_id) => set(&session_id, prompt)?,
+        None => clean(prompt),
+    };
+    state.interview_context.lock().custom_system_prompt = prompt;
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_session_system_prompt(session_id: String) -> Response<Option<String>> {
+    envelope::respond("get_session_system_prompt", async move {
+        Ok(get(&session_id))
+    })
+    .await
+}
+
+/// Set or clear (empty prompt) the custom system prompt of a session; applies right away when it is
+/// the active session
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn set_session_system_prompt(
+    state: State<'_, AppState>,
+    session_id: String,
+    prompt: String,
+) -> Response<Option<String>> {
+    envelope::respond("set_session_system_prompt", async move {
+        let stored = set(&session_id, &prompt)?;
+        if crate::audit::current_session().as_deref() == Some(session_id.as_str()) {
+            state.interview_context.lock().custom_system_prompt = stored.clone();
+        }
+        Ok(stored)
+    })
+    .await
+}
@@ -0,0 +1,568 @@
+// Opt-in session recording to MP4
+// Captures the configured monitor or window through `screenshot::CaptureStream` plus the system
+// and microphone audio published on the audio bus, mixes the audio to mono and encodes both with a
+// Media Foundation sink writer (H.264 + AAC). Files go under recordings/sessions in the active
+// profile's data folder, so they stay per user and per profile and the retention policy applies.
+// When enabled, recording starts on session activation and stops on disconnect; the commands below
+// can also drive it manually.
+
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use log::{error, info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::broadcast::error::TryRecvError;
+use windows::core::HSTRING;
+use windows::Win32::Media::MediaFoundation::{
+    IMFAttributes, IMFByteStream, IMFSample, IMFSinkWriter, MFAudioFormat_AAC, MFAudioFormat_PCM, MFCreateAttributes,
+    MFCreateMediaType, MFCreateMemoryBuffer, MFCreateSample, MFCreateSinkWriterFromURL, MFMediaType_Audio,
+    MFMediaType_Video, MFShutdown, MFStartup, MFVideoFormat_H264, MFVideoFormat_RGB32, MFVideoInterlace_Progressive,
+    MFSTARTUP_FULL, MF_MT_AUDIO_AVG_BYTES_PER_SECOND, MF_MT_AUDIO_BITS_PER_SAMPLE, MF_MT_AUDIO_BLOCK_ALIGNMENT,
+    MF_MT_AUDIO_NUM_CHANNELS, MF_MT_AUDIO_SAMPLES_PER_SECOND, MF_MT_AVG_BITRATE, MF_MT_DEFAULT_STRIDE,
+    MF_MT_FRAME_RATE, MF_MT_FRAME_SIZE, MF_MT_INTERLACE_MODE, MF_MT_MAJOR_TYPE, MF_MT_PIXEL_ASPECT_RATIO,
+    MF_MT_SUBTYPE, MF_READWRITE_ENABLE_HARDWARE_TRANSFORMS, MF_VERSION,
+};
+use windows::Win32::System::WinRT::{RoInitialize, RO_INIT_MULTITHREADED};
+
+use crate::audio_buffer::{AudioChunk, AudioSource};
+use crate::screenshot::{CaptureStream, Frame};
+use crate::envelope::{self, Response};
+use crate::guard::{self, Require};
+use crate::events;
+
+/// Sample rate of the audio bus and of the recorded AAC track
+const SAMPLE_RATE: u32 = 44_100;
+/// AAC output rate; the Media Foundation encoder only accepts a few fixed values
+const AAC_BYTES_PER_SECOND: u32 = 16_000;
+/// Media Foundation timestamps are in 100ns units
+const TICKS_PER_SECOND: i64 = 10_000_000;
+/// Audio queued per source before the oldest samples are dropped
+const MAX_QUEUED_SAMPLES: usize = SAMPLE_RATE as usize;
+/// Frames the capture pool can hold while the encoder is busy
+const CAPTURE_BUFFERS: i32 = 2;
+/// How long to wait for the first captured frame
+const FIRST_FRAME_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RecordingTarget {
+    /// None means the primary monitor
+    Monitor { index: Option<usize> },
+    Window { hwnd: isize },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RecordingSettings {
+    /// Record automatically whenever a session is activated
+    pub enabled: bool,
+    pub target: RecordingTarget,
+    pub fps: u32,
+    pub include_microphone: bool,
+}
+
+impl Default for RecordingSettings {
+    fn default() -> Self {
+        Self {
+            // Opt-in: nothing is recorded until the user turns it on
+            enabled: false,
+            target: RecordingTarget::Monitor { index: None },
+            fps: 15,
+            include_microphone: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct RecordingStatus {
+    pub recording: bool,
+    pub session_id: Option<String>,
+    pub path: Option<String>,
+    pub started_at: Option<String>,
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RecordingResult {
+    pub session_id: Option<String>,
+    pub path: String,
+    pub duration_ms: u64,
+    pub video_frames: u64,
+    pub audio_samples: u64,
+}
+
+struct ActiveRecording {
+    session_id: Option<String>,
+    path: PathBuf,
+    started_at: chrono::DateTime<chrono::Utc>,
+    stop: Arc<AtomicBool>,
+    thread: JoinHandle<Result<RecordingResult, String>>,
+}
+
+static SETTINGS: Lazy<Mutex<RecordingSettings>> = Lazy::new(|| Mutex::new(load_settings()));
+static ACTIVE: Lazy<Mutex<Option<ActiveRecording>>> = Lazy::new(|| Mutex::new(None));
+
+fn settings_path() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("recording.json")
+}
+
+fn load_settings() -> RecordingSettings {
+    std::fs::read(settings_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn save_settings(settings: &RecordingSettings) -> Result<(), String> {
+    let path = settings_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(settings).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| e.to_string())
+}
+
+/// New MP4 under the active profile's recordings folder, never the working directory (often
+/// Program Files or System32)
+fn recording_path(session_id: Option<&str>) -> Result<PathBuf, String> {
+    let dir = crate::retention::DataCategory::Recordings.dir().join("sessions");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    Ok(dir.join(format!("{}_{}.mp4", session_id.unwrap_or("manual"), timestamp)))
+}
+
+/// H.264 + AAC MP4 writer
+struct Mp4Writer {
+    writer: IMFSinkWriter,
+    video_stream: u32,
+    audio_stream: u32,
+    frame_duration: i64,
+}
+
+impl Mp4Writer {
+    fn create(path: &std::path::Path, width: u32, height: u32, fps: u32) -> windows::core::Result<Self> {
+        unsafe {
+            let mut attributes: Option<IMFAttributes> = None;
+            MFCreateAttributes(&mut attributes, 1)?;
+            let attributes = attributes.ok_or_else(|| windows::core::Error::from(windows::Win32::Foundation::E_FAIL))?;
+            attributes.SetUINT32(&MF_READWRITE_ENABLE_HARDWARE_TRANSFORMS, 1)?;
+            let writer = MFCreateSinkWriterFromURL(&HSTRING::from(path.as_os_str()), None::<&IMFByteStream>, &attributes)?;
+
+            let frame_size = ((width as u64) << 32) | height as u64;
+            let frame_rate = ((fps as u64) << 32) | 1;
+            let bitrate = (width * height * fps / 10).clamp(1_000_000, 12_000_000);
+
+            let video_out = MFCreateMediaType()?;
+            video_out.SetGUID(&MF_MT_MAJOR_TYPE, &MFMediaType_Video)?;
+            video_out.SetGUID(&MF_MT_SUBTYPE, &MFVideoFormat_H264)?;
+            video_out.SetUINT32(&MF_MT_AVG_BITRATE, bitrate)?;
+            video_out.SetUINT32(&MF_MT_INTERLACE_MODE, MFVideoInterlace_Progressive.0 as u32)?;
+            video_out.SetUINT64(&MF_MT_FRAME_SIZE, frame_size)?;
+            video_out.SetUINT64(&MF_MT_FRAME_RATE, frame_rate)?;
+            video_out.SetUINT64(&MF_MT_PIXEL_ASPECT_RATIO, (1 << 32) | 1)?;
+            let video_stream = writer.AddStream(&video_out)?;
+
+            let video_in = MFCreateMediaType()?;
+            video_in.SetGUID(&MF_MT_MAJOR_TYPE, &MFMediaType_Video)?;
+            video_in.SetGUID(&MF_MT_SUBTYPE, &MFVideoFormat_RGB32)?;
+            video_in.SetUINT32(&MF_MT_INTERLACE_MODE, MFVideoInterlace_Progressive.0 as u32)?;
+            video_in.SetUINT64(&MF_MT_FRAME_SIZE, frame_size)?;
+            video_in.SetUINT64(&MF_MT_FRAME_RATE, frame_rate)?;
+            video_in.SetUINT64(&MF_MT_PIXEL_ASPECT_RATIO, (1 << 32) | 1)?;
+            // Positive stride: rows are stored top-down as captured
+            video_in.SetUINT32(&MF_MT_DEFAULT_STRIDE, width * 4)?;
+            writer.SetInputMediaType(video_stream, &video_in, None)?;
+
+            let audio_out = MFCreateMediaType()?;
+            audio_out.SetGUID(&MF_MT_MAJOR_TYPE, &MFMediaType_Audio)?;
+            audio_out.SetGUID(&MF_MT_SUBTYPE, &MFAudioFormat_AAC)?;
+            audio_out.SetUINT32(&MF_MT_AUDIO_BITS_PER_SAMPLE, 16)?;
+            audio_out.SetUINT32(&MF_MT_AUDIO_SAMPLES_PER_SECOND, SAMPLE_RATE)?;
+            audio_out.SetUINT32(&MF_MT_AUDIO_NUM_CHANNELS, 1)?;
+            audio_out.SetUINT32(&MF_MT_AUDIO_AVG_BYTES_PER_SECOND, AAC_BYTES_PER_SECOND)?;
+            let audio_stream = writer.AddStream(&audio_out)?;
+
+            let audio_in = MFCreateMediaType()?;
+            audio_in.SetGUID(&MF_MT_MAJOR_TYPE, &MFMediaType_Audio)?;
+            audio_in.SetGUID(&MF_MT_SUBTYPE, &MFAudioFormat_PCM)?;
+            audio_in.SetUINT32(&MF_MT_AUDIO_BITS_PER_SAMPLE, 16)?;
+            audio_in.SetUINT32(&MF_MT_AUDIO_SAMPLES_PER_SECOND, SAMPLE_RATE)?;
+            audio_in.SetUINT32(&MF_MT_AUDIO_NUM_CHANNELS, 1)?;
+            audio_in.SetUINT32(&MF_MT_AUDIO_BLOCK_ALIGNMENT, 2)?;
+            audio_in.SetUINT32(&MF_MT_AUDIO_AVG_BYTES_PER_SECOND, SAMPLE_RATE * 2)?;
+            writer.SetInputMediaType(audio_stream, &audio_in, None)?;
+
+            writer.BeginWriting()?;
+            Ok(Self {
+                writer,
+                video_stream,
+                audio_stream,
+                frame_duration: TICKS_PER_SECOND / fps as i64,
+            })
+        }
+    }
+
+    fn sample(bytes: &[u8], time: i64, duration: i64) -> windows::core::Result<IMFSample> {
+        unsafe {
+            let buffer = MFCreateMemoryBuffer(bytes.len() as u32)?;
+            let mut data = std::ptr::null_mut();
+            buffer.Lock(&mut data, None, None)?;
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), data, bytes.len());
+            buffer.Unlock()?;
+            buffer.SetCurrentLength(bytes.len() as u32)?;
+
+            let sample = MFCreateSample()?;
+            sample.AddBuffer(&buffer)?;
+            sample.SetSampleTime(time)?;
+            sample.SetSampleDuration(duration)?;
+            Ok(sample)
+        }
+    }
+
+    fn write_video(&self, bgra: &[u8], time: i64) -> windows::core::Result<()> {
+        let sample = Self::sample(bgra, time, self.frame_duration)?;
+        unsafe { self.writer.WriteSample(self.video_stream, &sample) }
+    }
+
+    fn write_audio(&self, pcm: &[u8], time: i64, duration: i64) -> windows::core::Result<()> {
+        let sample = Self::sample(pcm, time, duration)?;
+        unsafe { self.writer.WriteSample(self.audio_stream, &sample) }
+    }
+
+    fn finish(self) -> windows::core::Result<()> {
+        unsafe { self.writer.Finalize() }
+    }
+}
+
+/// Copy `frame` into a `width` x `height` buffer, cropping or padding with black when a window has
+/// been resized since recording started
+fn fit(frame: &Frame, width: u32, height: u32) -> Vec<u8> {
+    if frame.width == width && frame.height == height {
+        return frame.bgra.clone();
+    }
+    let mut out = vec![0u8; width as usize * height as usize * 4];
+    let copy_bytes = width.min(frame.width) as usize * 4;
+    for row in 0..height.min(frame.height) as usize {
+        let src = row * frame.width as usize * 4;
+        let dst = row * width as usize * 4;
+        out[dst..dst + copy_bytes].copy_from_slice(&frame.bgra[src..src + copy_bytes]);
+    }
+    out
+}
+
+/// Pop `count` samples from each queue and sum them, padding with silence where a source is behind
+fn mix(system: &mut VecDeque<f32>, microphone: &mut VecDeque<f32>, count: usize) -> Vec<f32> {
+    (0..count)
+        .map(|_| {
+            let sample = system.pop_front().unwrap_or(0.0) + microphone.pop_front().unwrap_or(0.0);
+            sample.clamp(-1.0, 1.0)
+        })
+        .collect()
+}
+
+fn ticks(samples: u64) -> i64 {
+    samples as i64 * TICKS_PER_SECOND / SAMPLE_RATE as i64
+}
+
+/// Recording thread body; `ready` reports whether capture and the encoder started
+fn record(
+    settings: RecordingSettings,
+    session_id: Option<String>,
+    path: PathBuf,
+    stop: Arc<AtomicBool>,
+    ready: mpsc::Sender<Result<(), String>>,
+) -> Result<RecordingResult, String> {
+    // Already-initialized threads report an error here that is safe to ignore
+    let _ = unsafe { RoInitialize(RO_INIT_MULTITHREADED) };
+    if let Err(e) = unsafe { MFStartup(MF_VERSION, MFSTARTUP_FULL) } {
+        let e = format!("Failed to start Media Foundation: {}", e);
+        let _ = ready.send(Err(e.clone()));
+        return Err(e);
+    }
+
+    let result = (|| {
+        let fps = settings.fps.clamp(1, 60);
+        let setup = || -> Result<_, String> {
+            let stream = match settings.target {
+                RecordingTarget::Monitor { index } => CaptureStream::monitor(index, CAPTURE_BUFFERS)?,
+                RecordingTarget::Window { hwnd } => CaptureStream::window(hwnd, CAPTURE_BUFFERS)?,
+            };
+            let first = stream.next_frame(FIRST_FRAME_TIMEOUT)?;
+            // H.264 needs even dimensions
+            let (width, height) = (first.width & !1, first.height & !1);
+            let writer = Mp4Writer::create(&path, width, height, fps)
+                .map_err(|e| format!("Failed to create MP4 encoder for {}x{}: {}", width, height, e))?;
+            Ok((stream, writer, fit(&first, width, height), width, height))
+        };
+        let (stream, writer, mut current, width, height) = match setup() {
+            Ok(setup) => {
+                let _ = ready.send(Ok(()));
+                setup
+            }
+            Err(e) => {
+                let _ = ready.send(Err(e.clone()));
+                return Err(e);
+            }
+        };
+
+        let mut audio = crate::audio_buffer::subscribe();
+        let mut system = VecDeque::new();
+        let mut microphone = VecDeque::new();
+        let tick = Duration::from_secs_f64(1.0 / fps as f64);
+        let started = Instant::now();
+        let mut next_tick = started;
+        let mut video_frames = 0u64;
+        let mut audio_samples = 0u64;
+
+        while !stop.load(Ordering::Relaxed) {
+            loop {
+                match audio.try_recv() {
+                    Ok(AudioChunk { source, samples, sample_rate }) => {
+                        if sample_rate != SAMPLE_RATE {
+                            continue;
+                        }
+                        let queue = match source {
+                            AudioSource::System => &mut system,
+                            AudioSource::Microphone if settings.include_microphone => &mut microphone,
+                            AudioSource::Microphone => continue,
+                        };
+                        queue.extend(samples.iter().copied());
+                        let excess = queue.len().saturating_sub(MAX_QUEUED_SAMPLES);
+                        queue.drain(..excess);
+                    }
+                    Err(TryRecvError::Lagged(skipped)) => warn!("Session recorder skipped {} audio chunks", skipped),
+                    Err(TryRecvError::Empty) | Err(TryRecvError::Closed) => break,
+                }
+            }
+
+            let elapsed = started.elapsed();
+            if let Some(frame) = stream.latest_frame()? {
+                current = fit(&frame, width, height);
+            }
+            let time = (elapsed.as_nanos() / 100) as i64;
+            writer.write_video(&current, time).map_err(|e| format!("Failed to encode video frame: {}", e))?;
+            video_frames += 1;
+
+            // Audio follows the wall clock so it stays in sync with video even when a source is silent
+            let due = (elapsed.as_secs_f64() * SAMPLE_RATE as f64) as u64;
+            if due > audio_samples {
+                let count = (due - audio_samples) as usize;
+                let mixed = mix(&mut system, &mut microphone, count);
+                writer
+                    .write_audio(&crate::audio_buffer::pcm16_bytes(&mixed), ticks(audio_samples), ticks(count as u64))
+                    .map_err(|e| format!("Failed to encode audio: {}", e))?;
+                audio_samples = due;
+            }
+
+            next_tick += tick;
+            let now = Instant::now();
+            if next_tick > now {
+                std::thread::sleep(next_tick - now);
+            } else {
+                // Encoder fell behind; drop the missed frames instead of bursting
+                next_tick = now;
+            }
+        }
+
+        writer.finish().map_err(|e| format!("Failed to finalize MP4: {}", e))?;
+        Ok(RecordingResult {
+            session_id,
+            path: path.to_string_lossy().to_string(),
+            duration_ms: started.elapsed().as_millis() as u64,
+            video_frames,
+            audio_samples,
+        })
+    })();
+
+    let _ = unsafe { MFShutdown() };
+    result
+}
+
+/// Start recording; fails if a recording is already running
+pub fn start(app_handle: &AppHandle, session_id: Option<String>) -> Result<RecordingStatus, String> {
+    let mut active = ACTIVE.lock();
+    if active.is_some() {
+        return Err("A session recording is already running".to_string());
+    }
+
+    let settings = SETTINGS.lock().clone();
+    let path = recording_path(session_id.as_deref())?;
+    let stop = Arc::new(AtomicBool::new(false));
+    let (ready_tx, ready_rx) = mpsc::channel();
+    let thread = {
+        let session_id = session_id.clone();
+        let path = path.clone();
+        let stop = stop.clone();
+        std::thread::Builder::new()
+            .name("session-recorder".to_string())
+            .spawn(move || record(settings, session_id, path, stop, ready_tx))
+            .map_err(|e| format!("Failed to start recorder thread: {}", e))?
+    };
+
+    match ready_rx.recv() {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => return Err(e),
+        Err(_) => return Err("Recorder thread exited during startup".to_string()),
+    }
+
+    let recording = ActiveRecording {
+        session_id,
+        path,
+        started_at: chrono::Utc::now(),
+        stop,
+        thread,
+    };
+    info!("🎬 Session recording started: {}", recording.path.display());
+    if let Some(session_id) = &recording.session_id {
+        crate::session_timeline::mark(session_id, crate::session_timeline::MarkKind::RecordingStarted, recording.path.to_string_lossy());
+    }
+    *active = Some(recording);
+    let status = status_of(active.as_ref());
+    drop(active);
+    let _ = app_handle.emit(events::SESSION_RECORDING_STATE, &status);
+    Ok(status)
+}
+
+/// Stop the running recording and wait for the MP4 to be finalized
+pub fn stop(app_handle: &AppHandle) -> Result<RecordingResult, String> {
+    let recording = ACTIVE.lock().take().ok_or("No session recording is running")?;
+    recording.stop.store(true, Ordering::Relaxed);
+    let result = recording
+        .thread
+        .join()
+        .map_err(|_| "Recorder thread panicked".to_string())?;
+    if let Some(session_id) = &recording.session_id {
+        crate::session_timeline::mark(session_id, crate::session_timeline::MarkKind::RecordingStopped, "Recording stopped");
+    }
+    let _ = app_handle.emit(events::SESSION_RECORDING_STATE, &status_of(None));
+    match &result {
+        Ok(result) => info!(
+            "🎬 Session recording saved: {} ({}s, {} frames)",
+            result.path,
+            result.duration_ms / 1000,
+            result.video_frames
+        ),
+        Err(e) => error!("Session recording failed: {}", e),
+    }
+    result
+}
+
+fn status_of(active: Option<&ActiveRecording>) -> RecordingStatus {
+    match active {
+        Some(recording) => RecordingStatus {
+            recording: !recording.thread.is_finished(),
+            session_id: recording.session_id.clone(),
+            path: Some(recording.path.to_string_lossy().to_string()),
+            started_at: Some(recording.started_at.to_rfc3339()),
+            elapsed_ms: (chrono::Utc::now() - recording.started_at).num_milliseconds().max(0) as u64,
+        },
+        None => RecordingStatus {
+            recording: false,
+            session_id: None,
+            path: None,
+            started_at: None,
+            elapsed_ms: 0,
+        },
+    }
+}
+
+/// Session activation hook: records only when the user has enabled it
+pub fn on_session_activated(app_handle: &AppHandle, session_id: &str) {
+    if !SETTINGS.lock().enabled {
+        return;
+    }
+    let app_handle = app_handle.clone();
+    let session_id = session_id.to_string();
+    // Waiting for the first frame blocks, so keep it off the async runtime
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Err(e) = start(&app_handle, Some(session_id)) {
+            warn!("Session recording did not start: {}", e);
+        }
+    });
+}
+
+/// Session disconnect hook: finalizes a recording tied to `session_id`
+pub fn on_session_ended(app_handle: &AppHandle, session_id: &str) {
+    let owned = ACTIVE
+        .lock()
+        .as_ref()
+        .is_some_and(|recording| recording.session_id.as_deref() == Some(session_id));
+    if !owned {
+        return;
+    }
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let _ = stop(&app_handle);
+    });
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_recording_settings() -> Response<RecordingSettings> {
+    envelope::respond("get_recording_settings", async move {
+        Ok(SETTINGS.lock().clone())
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_recording_settings(settings: RecordingSettings) -> Response<RecordingSettings> {
+    envelope::respond("set_recording_settings", async move {
+        if settings.fps == 0 || settings.fps > 60 {
+            return Err("Frame rate must be between 1 and 60".into());
+        }
+        save_settings(&settings)?;
+        info!(
+            "🎬 Session recording {} ({:?}, {} fps)",
+            if settings.enabled { "enabled" } else { "disabled" },
+            settings.target,
+            settings.fps
+        );
+        *SETTINGS.lock() = settings.clone();
+        Ok(settings)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn start_session_recording(app_handle: AppHandle, session_id: Option<String>) -> Response<RecordingStatus> {
+    guard::respond("start_session_recording", &[Require::ActiveSession], async move {
+        crate::telemetry::record_feature("session_recording");
+        Ok(tokio::task::spawn_blocking(move || start(&app_handle, session_id))
+            .await
+            .map_err(|e| format!("Recording task failed: {}", e))??)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn stop_session_recording(app_handle: AppHandle) -> Response<RecordingResult> {
+    envelope::respond("stop_session_recording", async move {
+        Ok(tokio::task::spawn_blocking(move || stop(&app_handle))
+            .await
+            .map_err(|e| format!("Recording task failed: {}", e))??)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_recording_status() -> Response<RecordingStatus> {
+    envelope::respond("get_recording_status", async move {
+        Ok(status_of(ACTIVE.lock().as_ref()))
+    })
+    .await
+}
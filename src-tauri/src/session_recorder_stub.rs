@@ -0,0 +1,115 @@
+// Session recording on platforms without Windows Graphics Capture and Media Foundation
+// Same commands and types as session_recorder.rs. Nothing is ever recording, the session hooks do
+// nothing, and turning recording on or starting it fails with the "not available" message.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::AppHandle;
+
+use crate::platform::{unavailable, Capability};
+use crate::envelope::{self, Response};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RecordingTarget {
+    /// None means the primary monitor
+    Monitor { index: Option<usize> },
+    Window { hwnd: isize },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RecordingSettings {
+    /// Record automatically whenever a session is activated
+    pub enabled: bool,
+    pub target: RecordingTarget,
+    pub fps: u32,
+    pub include_microphone: bool,
+}
+
+impl Default for RecordingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target: RecordingTarget::Monitor { index: None },
+            fps: 15,
+            include_microphone: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct RecordingStatus {
+    pub recording: bool,
+    pub session_id: Option<String>,
+    pub path: Option<String>,
+    pub started_at: Option<String>,
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct RecordingResult {
+    pub session_id: Option<String>,
+    pub path: String,
+    pub duration_ms: u64,
+    pub video_frames: u64,
+    pub audio_samples: u64,
+}
+
+pub fn on_session_activated(_app_handle: &AppHandle, _session_id: &str) {}
+
+pub fn on_session_ended(_app_handle: &AppHandle, _session_id: &str) {}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_recording_settings() -> Response<RecordingSettings> {
+    envelope::respond("get_recording_settings", async move {
+        Ok(RecordingSettings::default())
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_recording_settings(settings: RecordingSettings) -> Response<RecordingSettings> {
+    envelope::respond("set_recording_settings", async move {
+        if settings.enabled {
+            return Err(unavailable(Capability::SessionRecording).into());
+        }
+        Ok(settings)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn start_session_recording(_app_handle: AppHandle, _session_id: Option<String>) -> Response<RecordingStatus> {
+    envelope::respond("start_session_recording", async move {
+        Err(unavailable(Capability::SessionRecording).into())
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn stop_session_recording(_app_handle: AppHandle) -> Response<RecordingResult> {
+    envelope::respond("stop_session_recording", async move {
+        Err("No session recording is running".into())
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_recording_status() -> Response<RecordingStatus> {
+    envelope::respond("get_recording_status", async move {
+        Ok(RecordingStatus {
+            recording: false,
+            session_id: None,
+            path: None,
+            started_at: None,
+            elapsed_ms: 0,
+        })
+    })
+    .await
+}
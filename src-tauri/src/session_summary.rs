@@ -0,0 +1,176 @@
+// End-of-session summaries
+// A short written summary of how an interview went - overall assessment, strengths and things to
+// improve. Practice sessions already carry one in their report; for backend sessions it is written by
+// the configured AI provider from the Q&A and transcript. Summaries are stored in
+// %APPDATA%\MockMate\summaries\<session id>.json so they can be shared or re-read later.
+
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use log::info;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::State;
+
+use crate::AppState;
+use crate::cancellation;
+use crate::envelope::{self, IntoResult, Response};
+
+/// Transcript characters included in the summary prompt
+const MAX_TRANSCRIPT_CHARS: usize = 6000;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SessionSummary {
+    pub session_id: String,
+    pub title: String,
+    /// 0-10 when the session was scored
+    pub overall_score: Option<f32>,
+    pub questions: usize,
+    pub summary: String,
+    pub strengths: Vec<String>,
+    pub improvements: Vec<String>,
+    pub generated_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+struct AiSummary {
+    summary: String,
+    #[serde(default)]
+    strengths: Vec<String>,
+    #[serde(default)]
+    improvements: Vec<String>,
+}
+
+fn summaries_dir() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("summaries")
+}
+
+fn save(summary: &SessionSummary) -> Result<(), String> {
+    let dir = summaries_dir();
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let body = serde_json::to_vec_pretty(summary).map_err(|e| e.to_string())?;
+    std::fs::write(dir.join(format!("{}.json", summary.session_id)), body).map_err(|e| e.to_string())
+}
+
+pub fn load(session_id: &str) -> Option<SessionSummary> {
+    std::fs::read(summaries_dir().join(format!("{}.json", session_id)))
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+}
+
+/// The most recently generated summary, if any
+pub fn latest() -> Option<SessionSummary> {
+    std::fs::read_dir(summaries_dir())
+        .ok()?
+        .flatten()
+        .filter_map(|entry| std::fs::read(entry.path()).ok())
+        .filter_map(|body| serde_json::from_slice::<SessionSummary>(&body).ok())
+        .max_by_key(|summary| summary.generated_at)
+}
+
+fn from_practice(report: crate::practice::PracticeReport) -> SessionSummary {
+    let title = match (&report.position, &report.company) {
+        (Some(position), Some(company)) => format!("{} practice interview · {}", position, company),
+        (Some(position), None) => format!("{} practice interview", position),
+        _ => "Practice interview".to_string(),
+    };
+    SessionSummary {
+        session_id: report.session_id,
+        title,
+        overall_score: Some(report.overall_score),
+        questions: report.turns.len(),
+        summary: report.summary,
+        strengths: report.strengths,
+        improvements: report.improvements,
+        generated_at: Utc::now(),
+    }
+}
+
+async fn from_interview(state: &AppState, session_id: &str, provider: &str, model: &str) -> Result<SessionSummary, String> {
+    let report = crate::database::postgres::get_interview_report(session_id.to_string()).await.into_result().map_err(|e| e.to_string())?;
+    let mut questions: Vec<_> = report.questions.iter().collect();
+    questions.sort_by_key(|q| q.question_number);
+    let qa = questions
+        .iter()
+        .map(|q| {
+            let answer = report
+                .answers
+                .iter()
+                .filter(|a| a.question_id == q.id)
+                .max_by_key(|a| a.answered_at);
+            format!(
+                "Q{}: {}\nA: {}\nScore: {}",
+                q.question_number,
+                q.question_text,
+                answer.and_then(|a| a.answer_text.as_deref()).unwrap_or("(no answer)"),
+                answer.and_then(|a| a.ai_score).map(|s| s.to_string()).unwrap_or_else(|| "-".to_string())
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    let transcript: String = crate::session_transcript::load(session_id)
+        .iter()
+        .map(|line| format!("{}\n", line.text))
+        .collect();
+    let transcript: String = transcript.chars().take(MAX_TRANSCRIPT_CHARS).collect();
+
+    let user_prompt = format!(
+        "Summarize this {} interview for a {} role ({} difficulty).\n\nQuestions and answers:\n{}\n\nTranscript excerpt:\n{}\n\n\
+         Reply with JSON: {{\"summary\": \"3-5 sentence overall assessment\", \"strengths\": [\"...\"], \"improvements\": [\"...\"]}} \
+         with at most 4 items per list.",
+        report.session.session_type,
+        report.session.job_title,
+        report.session.difficulty,
+        if qa.is_empty() { "(none recorded)".to_string() } else { qa },
+        if transcript.is_empty() { "(not available)" } else { &transcript }
+    );
+    let reply = state
+        .complete(provider, model, "You are an interview coach writing a concise post-interview debrief.", &user_prompt)
+        .await?;
+    let ai: AiSummary = crate::practice::extract_json(&reply, '{', '}')?;
+
+    let date = report.session.session_started_at.unwrap_or(report.session.created_at);
+    Ok(SessionSummary {
+        session_id: session_id.to_string(),
+        title: format!("{} interview · {}", report.session.job_title, date.format("%Y-%m-%d")),
+        overall_score: (report.average_score > 0.0).then_some(report.average_score as f32),
+        questions: report.questions.len(),
+        summary: ai.summary,
+        strengths: ai.strengths,
+        improvements: ai.improvements,
+        generated_at: Utc::now(),
+    })
+}
+
+/// Write (or rewrite) the summary for a finished practice or interview session
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn generate_session_summary(
+    state: State<'_, AppState>,
+    session_id: String,
+    provider: String,
+    model: String,
+    request_id: Option<String>,
+) -> Response<SessionSummary> {
+    cancellation::run("generate_session_summary", request_id, async move {
+        let summary = match crate::practice::load_report(&session_id) {
+            Ok(report) => from_practice(report),
+            Err(_) => from_interview(&state, &session_id, &provider, &model).await?,
+        };
+        save(&summary)?;
+        info!("🧾 Summary ready for session {}", session_id);
+        Ok(summary)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_session_summary(session_id: String) -> Response<Option<SessionSummary>> {
+    envelope::respond("get_session_summary", async move {
+        Ok(load(&session_id))
+    })
+    .await
+}
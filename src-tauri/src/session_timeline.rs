@@ -0,0 +1,202 @@
+// Session playback timeline
+// `get_session_timeline` merges everything timestamped about a session into one ordered stream so
+// the frontend can render a scrubber over the recording: questions and answers from the database,
+// the local transcript, rubric evaluations, and marks the desktop writes as the session runs
+// (activation, disconnect, recording start/stop, timer updates). Marks are appended to
+// <app data>\transcripts\<session id>.marks.jsonl next to the transcript, so the retention policy
+// prunes them together. Offsets are relative to the recording start when the session was recorded,
+// otherwise to the first event.
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use log::warn;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::envelope::{self, Response};
+use crate::retention::DataCategory;
+
+/// Serializes appends so concurrent hooks don't interleave lines
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum MarkKind {
+    SessionStarted,
+    SessionEnded,
+    RecordingStarted,
+    RecordingStopped,
+    Timer,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Mark {
+    timestamp: DateTime<Utc>,
+    kind: MarkKind,
+    label: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineEventKind {
+    Question,
+    Answer,
+    Transcript,
+    Analysis,
+    Mark,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TimelineEvent {
+    pub at: DateTime<Utc>,
+    /// Milliseconds from the timeline origin; negative for events before it
+    pub offset_ms: i64,
+    pub kind: TimelineEventKind,
+    /// Question or answer text, transcript segment, evaluation summary or mark label
+    pub text: String,
+    /// Set for marks
+    pub mark: Option<MarkKind>,
+    /// Transcript source ("system_audio", "zoom_captions") or speaker
+    pub source: Option<String>,
+    pub question_id: Option<String>,
+    pub answer_id: Option<String>,
+    /// Answer score or overall rubric score
+    pub score: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SessionTimeline {
+    pub session_id: String,
+    /// Time `offset_ms` is measured from
+    pub origin: Option<DateTime<Utc>>,
+    /// Whether `origin` is the start of a recording of this session
+    pub recording_aligned: bool,
+    /// Milliseconds from the origin to the last event
+    pub duration_ms: i64,
+    /// False when questions and answers could not be loaded from the database
+    pub database_available: bool,
+    pub events: Vec<TimelineEvent>,
+}
+
+fn marks_path(session_id: &str) -> PathBuf {
+    DataCategory::Transcripts.dir().join(format!("{}.marks.jsonl", session_id))
+}
+
+/// Append a timeline mark for `session_id`
+pub fn mark(session_id: &str, kind: MarkKind, label: impl Into<String>) {
+    let mark = Mark { timestamp: Utc::now(), kind, label: label.into() };
+    let Ok(mut body) = serde_json::to_string(&mark) else {
+        return;
+    };
+    body.push('\n');
+
+    let _guard = WRITE_LOCK.lock();
+    let result = std::fs::create_dir_all(DataCategory::Transcripts.dir()).and_then(|_| {
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(marks_path(session_id))?
+            .write_all(body.as_bytes())
+    });
+    if let Err(e) = result {
+        warn!("Failed to write timeline mark for session {}: {}", session_id, e);
+    }
+}
+
+fn load_marks(session_id: &str) -> Vec<Mark> {
+    std::fs::read_to_string(marks_path(session_id))
+        .map(|body| body.lines().filter_map(|line| serde_json::from_str(line).ok()).collect())
+        .unwrap_or_default()
+}
+
+fn event(at: DateTime<Utc>, kind: TimelineEventKind, text: impl Into<String>) -> TimelineEvent {
+    TimelineEvent {
+        at,
+        offset_ms: 0,
+        kind,
+        text: text.into(),
+        mark: None,
+        source: None,
+        question_id: None,
+        answer_id: None,
+        score: None,
+    }
+}
+
+/// Ordered, timestamped events of a session for replay alongside its recording
+#[tauri::command]
+#[specta::specta]
+pub async fn get_session_timeline(session_id: String) -> Response<SessionTimeline> {
+    envelope::respond("get_session_timeline", async move {
+        let mut events = Vec::new();
+
+        let report = match crate::database::DatabaseManager::new().await {
+            Ok(db) => db.get_session_report(&session_id).await.map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        let database_available = match report {
+            Ok(report) => {
+                for question in report.questions {
+                    let mut e = event(question.asked_at, TimelineEventKind::Question, question.question_text);
+                    e.question_id = Some(question.id.to_string());
+                    events.push(e);
+                }
+                for answer in report.answers {
+                    let mut e = event(answer.answered_at, TimelineEventKind::Answer, answer.answer_text.unwrap_or_default());
+                    e.question_id = Some(answer.question_id.to_string());
+                    e.answer_id = Some(answer.id.to_string());
+                    e.score = answer.ai_score.map(|s| s as f32);
+                    events.push(e);
+                }
+                true
+            }
+            Err(e) => {
+                warn!("Timeline for session {} without questions and answers: {}", session_id, e);
+                false
+            }
+        };
+
+        for line in crate::session_transcript::load(&session_id) {
+            let mut e = event(line.timestamp, TimelineEventKind::Transcript, line.text);
+            e.source = Some(line.speaker.unwrap_or(line.source));
+            events.push(e);
+        }
+
+        for evaluation in crate::rubric::evaluations(&session_id) {
+            let mut e = event(evaluation.evaluated_at, TimelineEventKind::Analysis, evaluation.compact());
+            e.question_id = Some(evaluation.question_id);
+            e.answer_id = Some(evaluation.answer_id);
+            e.score = Some(evaluation.overall);
+            events.push(e);
+        }
+
+        let marks = load_marks(&session_id);
+        let recording_start = marks.iter().find(|m| m.kind == MarkKind::RecordingStarted).map(|m| m.timestamp);
+        for mark in marks {
+            let mut e = event(mark.timestamp, TimelineEventKind::Mark, mark.label);
+            e.mark = Some(mark.kind);
+            events.push(e);
+        }
+
+        events.sort_by_key(|e| e.at);
+        let origin = recording_start.or_else(|| events.first().map(|e| e.at));
+        if let Some(origin) = origin {
+            for e in &mut events {
+                e.offset_ms = (e.at - origin).num_milliseconds();
+            }
+        }
+
+        Ok(SessionTimeline {
+            session_id,
+            origin,
+            recording_aligned: recording_start.is_some(),
+            duration_ms: events.last().map(|e| e.offset_ms.max(0)).unwrap_or(0),
+            database_available,
+            events,
+        })
+    })
+    .await
+}
@@ -0,0 +1,81 @@
+// Per-session transcript store
+// Final transcript segments heard while a session is active are appended to
+// <app data>\transcripts\<session id>.jsonl, which the retention policy prunes like other transcripts.
+// Exports and post-session documents read them back from here.
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use log::warn;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::retention::DataCategory;
+use crate::envelope::{self, Response};
+
+/// Serializes appends so concurrent sources don't interleave lines
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TranscriptLine {
+    pub timestamp: DateTime<Utc>,
+    /// Where the text came from: "system_audio" or "zoom_captions"
+    pub source: String,
+    pub speaker: Option<String>,
+    pub text: String,
+}
+
+fn transcript_path(session_id: &str) -> PathBuf {
+    DataCategory::Transcripts.dir().join(format!("{}.jsonl", session_id))
+}
+
+/// Append a final segment to the active session's transcript; ignored when no session is active
+pub fn append(source: &str, speaker: Option<&str>, text: &str) {
+    let Some(session_id) = crate::audit::current_session() else {
+        return;
+    };
+    if text.trim().is_empty() {
+        return;
+    }
+    let line = TranscriptLine {
+        timestamp: Utc::now(),
+        source: source.to_string(),
+        speaker: speaker.map(str::to_string),
+        text: text.trim().to_string(),
+    };
+    let Ok(mut body) = serde_json::to_string(&line) else {
+        return;
+    };
+    body.push('\n');
+
+    let _guard = WRITE_LOCK.lock();
+    let result = std::fs::create_dir_all(DataCategory::Transcripts.dir()).and_then(|_| {
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(transcript_path(&session_id))?
+            .write_all(body.as_bytes())
+    });
+    if let Err(e) = result {
+        warn!("Failed to append to transcript for session {}: {}", session_id, e);
+    }
+    crate::database::postgres::queue_transcript_segment(&session_id, source, speaker, &line.text);
+}
+
+/// Every stored line for a session, oldest first (empty when nothing was recorded)
+pub fn load(session_id: &str) -> Vec<TranscriptLine> {
+    std::fs::read_to_string(transcript_path(session_id))
+        .map(|body| body.lines().filter_map(|line| serde_json::from_str(line).ok()).collect())
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_session_transcript(session_id: String) -> Response<Vec<TranscriptLine>> {
+    envelope::respond("get_session_transcript", async move {
+        Ok(load(&session_id))
+    })
+    .await
+}
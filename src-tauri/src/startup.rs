@@ -0,0 +1,142 @@
+// Startup profiling for MockMate
+// Times each phase of the setup hook and the services deferred until after the window shows
+
+use std::future::Future;
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use crate::envelope::{self, Response};
+
+static PROCESS_START: OnceLock<Instant> = OnceLock::new();
+static SETUP_START: OnceLock<Instant> = OnceLock::new();
+static SETUP_COMPLETE: OnceLock<Instant> = OnceLock::new();
+static PHASES: Lazy<Mutex<Vec<StartupPhase>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct StartupPhase {
+    pub name: String,
+    /// Ran in the background after setup returned
+    pub deferred: bool,
+    /// Offset from process start when the phase began
+    pub started_at_ms: u64,
+    pub duration_ms: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct StartupReport {
+    /// Process start -> Tauri setup hook entered (runtime + webview bootstrap)
+    pub process_to_setup_ms: Option<u64>,
+    /// Time spent inside the setup hook (blocks the first frame)
+    pub setup_duration_ms: Option<u64>,
+    /// Process start -> setup hook returned
+    pub cold_start_ms: Option<u64>,
+    /// Process start -> last deferred service finished (None while some are still running)
+    pub deferred_complete_ms: Option<u64>,
+    pub pending_deferred: usize,
+    pub phases: Vec<StartupPhase>,
+}
+
+static PENDING_DEFERRED: Lazy<Mutex<usize>> = Lazy::new(|| Mutex::new(0));
+
+fn ms_since_process_start(at: Instant) -> u64 {
+    PROCESS_START
+        .get()
+        .map(|start| at.saturating_duration_since(*start).as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Record the process start time; call first thing in main
+pub fn mark_process_start() {
+    let _ = PROCESS_START.set(Instant::now());
+}
+
+pub fn mark_setup_start() {
+    let _ = SETUP_START.set(Instant::now());
+}
+
+pub fn mark_setup_complete() {
+    let now = Instant::now();
+    let _ = SETUP_COMPLETE.set(now);
+    info!("🚀 Setup complete {}ms after process start", ms_since_process_start(now));
+}
+
+fn push_phase(name: &str, deferred: bool, started: Instant, error: Option<String>) {
+    PHASES.lock().push(StartupPhase {
+        name: name.to_string(),
+        deferred,
+        started_at_ms: ms_since_process_start(started),
+        duration_ms: started.elapsed().as_millis() as u64,
+        error,
+    });
+}
+
+/// Run a synchronous setup phase and record how long it took
+pub fn time_phase<T>(name: &str, f: impl FnOnce() -> T) -> T {
+    let started = Instant::now();
+    let result = f();
+    push_phase(name, false, started, None);
+    result
+}
+
+/// Record a setup phase that started at `started` and ends now
+pub fn record_phase(name: &str, started: Instant) {
+    push_phase(name, false, started, None);
+}
+
+/// Run a service in the background so it doesn't delay the first window, recording its timing
+pub fn spawn_deferred<F>(name: &'static str, service: F)
+where
+    F: Future<Output = Result<(), String>> + Send + 'static,
+{
+    *PENDING_DEFERRED.lock() += 1;
+    tauri::async_runtime::spawn(async move {
+        let started = Instant::now();
+        let result = service.await;
+        if let Err(e) = &result {
+            warn!("Deferred startup service '{}' failed: {}", name, e);
+        }
+        push_phase(name, true, started, result.err());
+        *PENDING_DEFERRED.lock() -= 1;
+    });
+}
+
+/// Timing breakdown of the last application start
+#[tauri::command]
+#[specta::specta]
+pub async fn get_startup_report() -> Response<StartupReport> {
+    envelope::respond("get_startup_report", async move {
+        let pending_deferred = *PENDING_DEFERRED.lock();
+        let phases = PHASES.lock().clone();
+
+        let setup_duration_ms = match (SETUP_START.get(), SETUP_COMPLETE.get()) {
+            (Some(start), Some(end)) => Some(end.saturating_duration_since(*start).as_millis() as u64),
+            _ => None,
+        };
+
+        let deferred_complete_ms = if pending_deferred == 0 {
+            phases
+                .iter()
+                .filter(|p| p.deferred)
+                .map(|p| p.started_at_ms + p.duration_ms)
+                .max()
+        } else {
+            None
+        };
+
+        Ok(StartupReport {
+            process_to_setup_ms: SETUP_START.get().map(|t| ms_since_process_start(*t)),
+            setup_duration_ms,
+            cold_start_ms: SETUP_COMPLETE.get().map(|t| ms_since_process_start(*t)),
+            deferred_complete_ms,
+            pending_deferred,
+            phases,
+        })
+    })
+    .await
+}
@@ -16,6 +16,9 @@ use windows_sys::{
 };
 use log::{info, error, warn};
 use anyhow::Result;
+use crate::envelope::{self, Response};
+use crate::events;
+use crate::error::AppError;
 
 /// Stealth hotkey identifiers
 #[repr(u32)]
@@ -206,7 +209,7 @@ impl StealthHotkeyManager {
             info!("🎯 Stealth hotkey triggered: {} ({})", hotkey.to_string(), hotkey_id);
             
             // Emit to frontend without any visual indication
-            let _ = app_handle.emit("stealth-hotkey", serde_json::json!({
+            let _ = app_handle.emit(events::STEALTH_HOTKEY, serde_json::json!({
                 "action": hotkey.to_string(),
                 "hotkey_id": hotkey_id,
                 "timestamp": chrono::Utc::now().to_rfc3339()
@@ -250,79 +253,91 @@ pub fn init_stealth_hotkeys(app_handle: AppHandle) {
 
 /// Tauri command to activate stealth mode
 #[tauri::command]
-pub async fn activate_stealth_mode() -> Result<String, String> {
-    let manager = get_stealth_manager();
-    let guard = manager.lock().unwrap();
+pub async fn activate_stealth_mode() -> Response<String> {
+    envelope::respond("activate_stealth_mode", async move {
+        let manager = get_stealth_manager();
+        let guard = manager.lock().unwrap();
     
-    if let Some(ref stealth_manager) = *guard {
-        stealth_manager.activate_stealth_mode()
-            .map_err(|e| e.to_string())?;
-        Ok("Stealth mode activated - all hotkeys registered".to_string())
-    } else {
-        Err("Stealth manager not initialized".to_string())
-    }
+        if let Some(ref stealth_manager) = *guard {
+            stealth_manager.activate_stealth_mode()
+                .map_err(|e| e.to_string())?;
+            Ok("Stealth mode activated - all hotkeys registered".to_string())
+        } else {
+            Err("Stealth manager not initialized".into())
+        }
+    })
+    .await
 }
 
 /// Tauri command to deactivate stealth mode
 #[tauri::command]
-pub async fn deactivate_stealth_mode() -> Result<String, String> {
-    let manager = get_stealth_manager();
-    let guard = manager.lock().unwrap();
+pub async fn deactivate_stealth_mode() -> Response<String> {
+    envelope::respond("deactivate_stealth_mode", async move {
+        let manager = get_stealth_manager();
+        let guard = manager.lock().unwrap();
     
-    if let Some(ref stealth_manager) = *guard {
-        stealth_manager.deactivate_stealth_mode()
-            .map_err(|e| e.to_string())?;
-        Ok("Stealth mode deactivated - all hotkeys unregistered".to_string())
-    } else {
-        Err("Stealth manager not initialized".to_string())
-    }
+        if let Some(ref stealth_manager) = *guard {
+            stealth_manager.deactivate_stealth_mode()
+                .map_err(|e| e.to_string())?;
+            Ok("Stealth mode deactivated - all hotkeys unregistered".to_string())
+        } else {
+            Err("Stealth manager not initialized".into())
+        }
+    })
+    .await
 }
 
 /// Tauri command to check stealth status
 #[tauri::command]
-pub async fn get_stealth_status() -> Result<serde_json::Value, String> {
-    let manager = get_stealth_manager();
-    let guard = manager.lock().unwrap();
+pub async fn get_stealth_status() -> Response<serde_json::Value> {
+    envelope::respond("get_stealth_status", async move {
+        let manager = get_stealth_manager();
+        let guard = manager.lock().unwrap();
     
-    if let Some(ref stealth_manager) = *guard {
-        let status = serde_json::json!({
-            "active": stealth_manager.is_stealth_active(),
-            "registered_hotkeys": stealth_manager.get_registered_hotkeys(),
-            "hotkey_mappings": {
-                "Shift+Ctrl+S": "System Sound Toggle",
-                "Shift+Ctrl+Z": "AI Answer Trigger", 
-                "Shift+Ctrl+X": "Window Hide/Show",
-                "Shift+Ctrl+M": "Microphone Toggle",
-                "Shift+Ctrl+A": "Analyze Screen",
-                "Shift+Ctrl+I": "Manual Question Entry",
-                "Shift+Ctrl+Enter": "Submit Question",
-                "Shift+Ctrl+C": "Clear Listening Area"
-            }
-        });
-        Ok(status)
-    } else {
-        Err("Stealth manager not initialized".to_string())
-    }
+        if let Some(ref stealth_manager) = *guard {
+            let status = serde_json::json!({
+                "active": stealth_manager.is_stealth_active(),
+                "registered_hotkeys": stealth_manager.get_registered_hotkeys(),
+                "hotkey_mappings": {
+                    "Shift+Ctrl+S": "System Sound Toggle",
+                    "Shift+Ctrl+Z": "AI Answer Trigger", 
+                    "Shift+Ctrl+X": "Window Hide/Show",
+                    "Shift+Ctrl+M": "Microphone Toggle",
+                    "Shift+Ctrl+A": "Analyze Screen",
+                    "Shift+Ctrl+I": "Manual Question Entry",
+                    "Shift+Ctrl+Enter": "Submit Question",
+                    "Shift+Ctrl+C": "Clear Listening Area"
+                }
+            });
+            Ok(status)
+        } else {
+            Err("Stealth manager not initialized".into())
+        }
+    })
+    .await
 }
 
 /// Tauri command to test hotkey functionality
 #[tauri::command]
-pub async fn test_stealth_hotkey(hotkey_name: String) -> Result<String, String> {
-    info!("🧪 Testing stealth hotkey: {}", hotkey_name);
+pub async fn test_stealth_hotkey(hotkey_name: String) -> Response<String> {
+    envelope::respond("test_stealth_hotkey", async move {
+        info!("🧪 Testing stealth hotkey: {}", hotkey_name);
     
-    // This would normally be triggered by actual hotkey press
-    // For testing, we simulate the event
-    let test_result = match hotkey_name.as_str() {
-        "system_sound_toggle" => "System sound toggled (test)",
-        "ai_answer_trigger" => "AI answer triggered (test)",
-        "window_toggle" => "Window visibility toggled (test)",
-        "mic_toggle" => "Microphone toggled (test)",
-        "analyze_screen" => "Screen analysis triggered (test)",
-        "manual_input" => "Manual input mode activated (test)",
-        "submit_question" => "Question submitted (test)",
-        "clear_area" => "Listening area cleared (test)",
-        _ => "Unknown hotkey (test)"
-    };
+        // This would normally be triggered by actual hotkey press
+        // For testing, we simulate the event
+        let test_result = match hotkey_name.as_str() {
+            "system_sound_toggle" => "System sound toggled (test)",
+            "ai_answer_trigger" => "AI answer triggered (test)",
+            "window_toggle" => "Window visibility toggled (test)",
+            "mic_toggle" => "Microphone toggled (test)",
+            "analyze_screen" => "Screen analysis triggered (test)",
+            "manual_input" => "Manual input mode activated (test)",
+            "submit_question" => "Question submitted (test)",
+            "clear_area" => "Listening area cleared (test)",
+            _ => "Unknown hotkey (test)"
+        };
     
-    Ok(test_result.to_string())
+        Ok(test_result.to_string())
+    })
+    .await
 }
@@ -5,7 +5,9 @@ use std::collections::HashMap;
 use std::sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}};
 use std::thread;
 use tauri::{AppHandle, Emitter};
+use schemars::JsonSchema;
 use serde::{Serialize, Deserialize};
+use specta::Type;
 use log::{info, warn, error};
 use anyhow::Result;
 use rdev::{listen, Event, EventType, Key};
@@ -17,12 +19,15 @@ use winapi::shared::windef::HWND;
 #[cfg(windows)]
 use winapi::um::winuser::{
     MOD_CONTROL, MOD_SHIFT, MOD_NOREPEAT,
-    VK_RETURN,
+    VK_RETURN, VK_UP, VK_DOWN,
     RegisterHotKey, UnregisterHotKey, TranslateMessage, DispatchMessageW, GetMessageW, MSG, WM_HOTKEY,
 };
+use crate::envelope::{self, Response};
+use crate::events;
+use crate::error::AppError;
 
 /// Stealth hotkey event structure
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
 pub struct StealthHotkeyEvent {
     pub action: String,
     pub hotkey_id: u32,
@@ -30,7 +35,7 @@ pub struct StealthHotkeyEvent {
 }
 
 /// Stealth status information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct StealthStatus {
     pub active: bool,
     pub registered_hotkeys: Vec<String>,
@@ -46,6 +51,10 @@ const HOTKEY_ANALYZE_SCREEN: i32 = 5;
 const HOTKEY_MANUAL_INPUT: i32 = 6;
 const HOTKEY_SUBMIT_QUESTION: i32 = 7;
 const HOTKEY_CLEAR_AREA: i32 = 8;
+#[cfg(windows)]
+const HOTKEY_TELEPROMPTER_FASTER: i32 = 9;
+#[cfg(windows)]
+const HOTKEY_TELEPROMPTER_SLOWER: i32 = 10;
 
 // Windows virtual-key codes for letter keys (not provided by winapi)
 #[cfg(windows)]
@@ -82,6 +91,8 @@ impl StealthHotkeyManager {
         mappings.insert("Shift+Ctrl+I".to_string(), "manual_input".to_string());
         mappings.insert("Shift+Ctrl+Enter".to_string(), "submit_question".to_string());
         mappings.insert("Shift+Ctrl+C".to_string(), "clear_area".to_string());
+        mappings.insert("Shift+Ctrl+Up".to_string(), "teleprompter_faster".to_string());
+        mappings.insert("Shift+Ctrl+Down".to_string(), "teleprompter_slower".to_string());
 
         Self {
             app_handle,
@@ -178,6 +189,8 @@ impl StealthHotkeyManager {
             (HOTKEY_MANUAL_INPUT, MOD_CONTROL | MOD_SHIFT | MOD_NOREPEAT, VK_I, "Shift+Ctrl+I", "manual_input"),
             (HOTKEY_SUBMIT_QUESTION, MOD_CONTROL | MOD_SHIFT | MOD_NOREPEAT, VK_RETURN, "Shift+Ctrl+Enter", "submit_question"),
             (HOTKEY_CLEAR_AREA, MOD_CONTROL | MOD_SHIFT | MOD_NOREPEAT, VK_C, "Shift+Ctrl+C", "clear_area"),
+            (HOTKEY_TELEPROMPTER_FASTER, MOD_CONTROL | MOD_SHIFT, VK_UP, "Shift+Ctrl+Up", "teleprompter_faster"),
+            (HOTKEY_TELEPROMPTER_SLOWER, MOD_CONTROL | MOD_SHIFT, VK_DOWN, "Shift+Ctrl+Down", "teleprompter_slower"),
         ];
         
         unsafe {
@@ -218,6 +231,7 @@ impl StealthHotkeyManager {
                         // Find the corresponding action for this hotkey ID
                         if let Some(&(_id, _modifiers, _vk_code, hotkey_name, action)) = hotkeys.iter().find(|&&(id, _, _, _, _)| id == hotkey_id) {
                             info!("🎯 HOTKEY TRIGGERED: {} -> {} (ID: {})", hotkey_name, action, hotkey_id);
+                            crate::teleprompter::on_hotkey(action);
                             
                             let event = StealthHotkeyEvent {
                                 action: action.to_string(),
@@ -225,7 +239,7 @@ impl StealthHotkeyManager {
                                 timestamp: chrono::Utc::now().to_rfc3339(),
                             };
                             
-                            if let Err(e) = app_handle.emit("stealth-hotkey", &event) {
+                            if let Err(e) = app_handle.emit(events::STEALTH_HOTKEY, &event) {
                                 error!("Failed to emit hotkey event: {}", e);
                             } else {
                                 info!("✅ Hotkey event emitted: {} ({})", action, hotkey_name);
@@ -302,7 +316,7 @@ impl StealthHotkeyManager {
                     timestamp: chrono::Utc::now().to_rfc3339(),
                 };
                 
-                if let Err(e) = app_handle.emit("stealth-hotkey", &event) {
+                if let Err(e) = app_handle.emit(events::STEALTH_HOTKEY, &event) {
                     error!("Failed to emit hotkey event: {}", e);
                 } else {
                     info!("✅ Hotkey event emitted: {} ({})", action, hotkey_name);
@@ -327,7 +341,7 @@ impl StealthHotkeyManager {
             timestamp: chrono::Utc::now().to_rfc3339(),
         };
         
-        self.app_handle.emit("stealth-hotkey", &event)
+        self.app_handle.emit(events::STEALTH_HOTKEY, &event)
             .map_err(|e| anyhow::anyhow!("Failed to emit manual hotkey: {}", e))?;
         
         Ok(())
@@ -365,7 +379,7 @@ impl StealthHotkeyManager {
                 timestamp: chrono::Utc::now().to_rfc3339(),
             };
             
-            if let Err(e) = self.app_handle.emit("stealth-hotkey", &event) {
+            if let Err(e) = self.app_handle.emit(events::STEALTH_HOTKEY, &event) {
                 warn!("Failed to emit hotkey event: {}", e);
             }
             
@@ -392,7 +406,7 @@ impl StealthHotkeyManager {
             timestamp: chrono::Utc::now().to_rfc3339(),
         };
         
-        if let Err(e) = self.app_handle.emit("stealth-hotkey", &event) {
+        if let Err(e) = self.app_handle.emit(events::STEALTH_HOTKEY, &event) {
             warn!("Failed to emit hotkey event: {}", e);
         }
         
@@ -422,33 +436,45 @@ pub fn get_stealth_manager() -> Option<&'static StealthHotkeyManager> {
 // Tauri commands for stealth hotkey functionality
 
 #[tauri::command]
-pub fn activate_stealth_mode() -> Result<String, String> {
-    match get_stealth_manager() {
-        Some(manager) => manager.activate_stealth_mode().map_err(|e| e.to_string()),
-        None => Err("Stealth manager not initialized".to_string()),
-    }
+#[specta::specta]
+pub fn activate_stealth_mode() -> Response<String> {
+    envelope::respond_sync("activate_stealth_mode", || {
+        match get_stealth_manager() {
+            Some(manager) => manager.activate_stealth_mode().map_err(|e| AppError::from(e.to_string())),
+            None => Err("Stealth manager not initialized".into()),
+        }
+    })
 }
 
 #[tauri::command]
-pub fn deactivate_stealth_mode() -> Result<String, String> {
-    match get_stealth_manager() {
-        Some(manager) => manager.deactivate_stealth_mode().map_err(|e| e.to_string()),
-        None => Err("Stealth manager not initialized".to_string()),
-    }
+#[specta::specta]
+pub fn deactivate_stealth_mode() -> Response<String> {
+    envelope::respond_sync("deactivate_stealth_mode", || {
+        match get_stealth_manager() {
+            Some(manager) => manager.deactivate_stealth_mode().map_err(|e| AppError::from(e.to_string())),
+            None => Err("Stealth manager not initialized".into()),
+        }
+    })
 }
 
 #[tauri::command]
-pub fn get_stealth_status() -> Result<StealthStatus, String> {
-    match get_stealth_manager() {
-        Some(manager) => Ok(manager.get_stealth_status()),
-        None => Err("Stealth manager not initialized".to_string()),
-    }
+#[specta::specta]
+pub fn get_stealth_status() -> Response<StealthStatus> {
+    envelope::respond_sync("get_stealth_status", || {
+        match get_stealth_manager() {
+            Some(manager) => Ok(manager.get_stealth_status()),
+            None => Err("Stealth manager not initialized".into()),
+        }
+    })
 }
 
 #[tauri::command]
-pub fn test_stealth_hotkey(hotkey_name: String) -> Result<String, String> {
-    match get_stealth_manager() {
-        Some(manager) => manager.test_hotkey(&hotkey_name).map_err(|e| e.to_string()),
-        None => Err("Stealth manager not initialized".to_string()),
-    }
+#[specta::specta]
+pub fn test_stealth_hotkey(hotkey_name: String) -> Response<String> {
+    envelope::respond_sync("test_stealth_hotkey", || {
+        match get_stealth_manager() {
+            Some(manager) => manager.test_hotkey(&hotkey_name).map_err(|e| AppError::from(e.to_string())),
+            None => Err("Stealth manager not initialized".into()),
+        }
+    })
 }
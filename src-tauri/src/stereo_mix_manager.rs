@@ -1,7 +1,95 @@
 use anyhow::{Result, anyhow};
 use log::{info, warn, error};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
 use std::process::Command;
 use serde_json::json;
+use crate::cancellation::{self, Progress};
+use crate::envelope::{self, Response};
+use crate::error::{AppError, ErrorCode};
+
+/// Friendly names of the legacy "what you hear" capture devices
+#[cfg(windows)]
+const STEREO_MIX_NAMES: &[&str] = &["stereo mix", "what u hear", "wave out mix"];
+
+/// Where system audio is captured from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SystemAudioSource {
+    /// Loopback of the default render device; works without any setup
+    WasapiLoopback,
+    /// The Stereo Mix capture device, only used when loopback cannot start
+    StereoMix,
+}
+
+/// A chosen source, with the loopback error that made it Stereo Mix
+type SourceChoice = (SystemAudioSource, Option<String>);
+
+/// Source chosen by the last capture start
+static LAST_SOURCE: Lazy<Mutex<Option<SourceChoice>>> = Lazy::new(|| Mutex::new(None));
+
+/// Source chosen by the last system audio capture, if one has started
+pub fn current_source() -> Option<SystemAudioSource> {
+    LAST_SOURCE.lock().as_ref().map(|(source, _)| *source)
+}
+
+/// Open system audio for capture: WASAPI loopback of the default render device first, and the
+/// Stereo Mix capture device only when loopback fails to initialize
+#[cfg(windows)]
+pub fn open_system_audio_stream() -> Result<(wasapi::Handle, wasapi::AudioCaptureClient, SystemAudioSource)> {
+    match crate::audio_thread::open_shared_stream(wasapi::Direction::Render) {
+        Ok((h_event, capture_client)) => {
+            *LAST_SOURCE.lock() = Some((SystemAudioSource::WasapiLoopback, None));
+            Ok((h_event, capture_client, SystemAudioSource::WasapiLoopback))
+        }
+        Err(loopback_error) => {
+            warn!("⚠️ WASAPI loopback failed to start ({}), falling back to Stereo Mix", loopback_error);
+            let device = find_stereo_mix_device().ok_or_else(|| {
+                anyhow!(
+                    "WASAPI loopback failed to start ({}) and no enabled Stereo Mix device was found",
+                    loopback_error
+                )
+            })?;
+            let (h_event, capture_client) = crate::audio_thread::open_device_stream(&device)?;
+            info!("🎚️ Capturing system audio from Stereo Mix");
+            *LAST_SOURCE.lock() = Some((SystemAudioSource::StereoMix, Some(loopback_error.to_string())));
+            Ok((h_event, capture_client, SystemAudioSource::StereoMix))
+        }
+    }
+}
+
+/// First active capture device whose name marks it as Stereo Mix
+#[cfg(windows)]
+fn find_stereo_mix_device() -> Option<wasapi::Device> {
+    let devices = wasapi::DeviceCollection::new(&wasapi::Direction::Capture).ok()?;
+    (0..devices.get_nbr_devices().ok()?)
+        .filter_map(|index| devices.get_device_at_index(index).ok())
+        .find(|device| {
+            device.get_friendlyname().is_ok_and(|name| {
+                let name = name.to_lowercase();
+                STEREO_MIX_NAMES.iter().any(|mix| name.contains(mix))
+            })
+        })
+}
+
+/// Whether loopback capture works, with the error if not. Uses the last capture start when
+/// there was one, otherwise opens (and drops) a loopback stream.
+#[cfg(windows)]
+fn loopback_status() -> (bool, Option<String>) {
+    if let Some((source, error)) = LAST_SOURCE.lock().clone() {
+        return (source == SystemAudioSource::WasapiLoopback, error);
+    }
+    match crate::audio_thread::open_shared_stream(wasapi::Direction::Render) {
+        Ok(_) => (true, None),
+        Err(e) => (false, Some(e.to_string())),
+    }
+}
+
+#[cfg(not(windows))]
+fn loopback_status() -> (bool, Option<String>) {
+    (false, Some("WASAPI loopback is only available on Windows".to_string()))
+}
 
 /// Windows Stereo Mix Manager for automatic enablement
 pub struct StereoMixManager;
@@ -51,163 +139,6 @@ impl StereoMixManager {
         }
     }
 
-    /// Attempt to enable Stereo Mix using PowerShell commands
-    /// This requires elevated privileges
-    pub fn enable_stereo_mix_powershell() -> Result<()> {
-        info!("Attempting to enable Stereo Mix using PowerShell...");
-        
-        // PowerShell script to enable recording devices
-        let powershell_script = r#"
-            Add-Type -TypeDefinition @"
-                using System;
-                using System.Runtime.InteropServices;
-                public class AudioDevices {
-                    [DllImport("ole32.dll")]
-                    public static extern int CoInitialize(IntPtr pvReserved);
-                    
-                    [DllImport("ole32.dll")]
-                    public static extern void CoUninitialize();
-                }
-"@
-
-            # Initialize COM
-            [AudioDevices]::CoInitialize([IntPtr]::Zero)
-
-            try {
-                # Get audio devices
-                $deviceEnumerator = New-Object -ComObject MMDeviceEnumerator
-                $devices = $deviceEnumerator.EnumAudioEndpoints(1, 10) # eCapture, DEVICE_STATEMASK_ALL
-                
-                for ($i = 0; $i -lt $devices.GetCount(); $i++) {
-                    $device = $devices.Item($i)
-                    $properties = $device.OpenPropertyStore(0)
-                    $deviceName = $properties.GetValue([GUID]"{a45c254e-df1c-4efd-8020-67d146a850e0}", 2).GetValue()
-                    
-                    # Check if this is Stereo Mix
-                    if ($deviceName -match "Stereo Mix|What U Hear|Wave Out Mix") {
-                        Write-Host "Found disabled recording device: $deviceName"
-                        
-                        # Enable the device (this part requires admin privileges)
-                        $device.Activate([GUID]"{2EEF81BE-33FA-4800-9670-1CD474972C3F}", 23, [IntPtr]::Zero, [ref]$null)
-                        Write-Host "Enabled: $deviceName"
-                    }
-                    
-                    [System.Runtime.InteropServices.Marshal]::ReleaseComObject($properties) | Out-Null
-                    [System.Runtime.InteropServices.Marshal]::ReleaseComObject($device) | Out-Null
-                }
-                
-                [System.Runtime.InteropServices.Marshal]::ReleaseComObject($devices) | Out-Null
-                [System.Runtime.InteropServices.Marshal]::ReleaseComObject($deviceEnumerator) | Out-Null
-                
-                Write-Host "Stereo Mix enablement completed"
-            }
-            catch {
-                Write-Error "Failed to enable Stereo Mix: $_"
-                exit 1
-            }
-            finally {
-                # Cleanup COM
-                [AudioDevices]::CoUninitialize()
-            }
-        "#;
-        
-        // Execute PowerShell script as administrator
-        let output = Command::new("powershell")
-            .args(&[
-                "-Command",
-                "Start-Process",
-                "powershell",
-                "-ArgumentList",
-                &format!("'{}'", powershell_script.replace('\n', "; ")),
-                "-Verb",
-                "RunAs",
-                "-WindowStyle",
-                "Hidden"
-            ])
-            .output()?;
-            
-        if output.status.success() {
-            info!("PowerShell Stereo Mix enablement completed");
-            Ok(())
-        } else {
-            let error_msg = String::from_utf8_lossy(&output.stderr);
-            error!("PowerShell command failed: {}", error_msg);
-            Err(anyhow!("Failed to enable Stereo Mix via PowerShell: {}", error_msg))
-        }
-    }
-
-    /// Enable Stereo Mix using Windows Registry modifications
-    /// This is more reliable but requires elevated privileges
-    pub fn enable_stereo_mix_registry() -> Result<()> {
-        info!("Attempting to enable Stereo Mix using Registry...");
-        
-        // First, we need to identify the Stereo Mix device in the registry
-        // This is complex because device IDs vary by system
-        let registry_commands = vec![
-            // Enable disabled audio devices globally
-            r#"reg add "HKLM\SOFTWARE\Microsoft\Windows\CurrentVersion\MMDevices\Audio\Capture" /f"#,
-            
-            // Common registry paths for Stereo Mix (these may vary)
-            r#"reg add "HKLM\SYSTEM\CurrentControlSet\Control\Class\{4d36e96c-e325-11ce-bfc1-08002be10318}" /v "EnableDefaultDevices" /t REG_DWORD /d 1 /f"#,
-        ];
-        
-        for cmd in registry_commands {
-            let output = Command::new("cmd")
-                .args(&["/C", cmd])
-                .output();
-                
-            match output {
-                Ok(result) => {
-                    if result.status.success() {
-                        info!("Registry command successful: {}", cmd);
-                    } else {
-                        warn!("Registry command failed: {}", String::from_utf8_lossy(&result.stderr));
-                    }
-                }
-                Err(e) => {
-                    error!("Failed to execute registry command: {}", e);
-                }
-            }
-        }
-        
-        Ok(())
-    }
-
-    /// Use Device Manager command-line tools to enable Stereo Mix
-    pub fn enable_stereo_mix_devcon() -> Result<()> {
-        info!("Attempting to enable Stereo Mix using devcon...");
-        
-        // This requires devcon.exe to be available
-        // devcon is part of Windows Driver Kit
-        let commands = vec![
-            "devcon enable *STEREOMIX*",
-            "devcon enable *WAVEMIX*", 
-            "devcon enable *WHATHEAR*",
-        ];
-        
-        for cmd in commands {
-            let output = Command::new("cmd")
-                .args(&["/C", cmd])
-                .output();
-                
-            match output {
-                Ok(result) => {
-                    if result.status.success() {
-                        info!("devcon command successful: {}", cmd);
-                        return Ok(());
-                    } else {
-                        warn!("devcon command failed: {}", String::from_utf8_lossy(&result.stderr));
-                    }
-                }
-                Err(e) => {
-                    warn!("devcon not available or failed: {}", e);
-                }
-            }
-        }
-        
-        Err(anyhow!("All devcon commands failed"))
-    }
-
     /// Open Windows Sound Control Panel for user to manually enable
     pub fn open_sound_control_panel() -> Result<()> {
         info!("Opening Windows Sound Control Panel...");
@@ -255,50 +186,6 @@ impl StereoMixManager {
         ]
     }
 
-    /// Comprehensive method to attempt enabling Stereo Mix
-    /// Tries multiple approaches in order of reliability
-    pub fn auto_enable_stereo_mix() -> Result<String> {
-        info!("Starting comprehensive Stereo Mix enablement...");
-        
-        // First check if it's already enabled
-        if Self::is_stereo_mix_enabled()? {
-            return Ok("Stereo Mix is already enabled".to_string());
-        }
-        
-        // Method 1: Try devcon (most reliable if available)
-        info!("Attempt 1: Using devcon...");
-        if let Ok(_) = Self::enable_stereo_mix_devcon() {
-            std::thread::sleep(std::time::Duration::from_secs(2));
-            if Self::is_stereo_mix_enabled()? {
-                return Ok("Stereo Mix enabled successfully using devcon".to_string());
-            }
-        }
-        
-        // Method 2: Try PowerShell approach
-        info!("Attempt 2: Using PowerShell...");
-        if let Ok(_) = Self::enable_stereo_mix_powershell() {
-            std::thread::sleep(std::time::Duration::from_secs(3));
-            if Self::is_stereo_mix_enabled()? {
-                return Ok("Stereo Mix enabled successfully using PowerShell".to_string());
-            }
-        }
-        
-        // Method 3: Try registry approach
-        info!("Attempt 3: Using Registry...");
-        if let Ok(_) = Self::enable_stereo_mix_registry() {
-            std::thread::sleep(std::time::Duration::from_secs(2));
-            if Self::is_stereo_mix_enabled()? {
-                return Ok("Stereo Mix enabled successfully using Registry".to_string());
-            }
-        }
-        
-        // If all automated methods fail, guide user to manual enablement
-        warn!("All automatic methods failed, opening manual controls...");
-        Self::open_recording_devices()?;
-        
-        Ok("Could not automatically enable Stereo Mix. Please enable it manually in the Recording devices window that just opened.".to_string())
-    }
-
     /// Check system capabilities for Stereo Mix using Windows API
     pub fn check_stereo_mix_capability() -> Result<serde_json::Value> {
         info!("Checking system Stereo Mix capabilities using Windows API...");
@@ -369,6 +256,20 @@ impl StereoMixManager {
             }
         }
         
+        // Loopback needs no driver setup, so Stereo Mix only matters when it is unavailable
+        let (loopback_available, loopback_error) = loopback_status();
+        capabilities["loopback_available"] = json!(loopback_available);
+        capabilities["loopback_error"] = json!(loopback_error);
+        capabilities["stereo_mix_required"] = json!(!loopback_available);
+        capabilities["preferred_source"] = json!(if loopback_available {
+            SystemAudioSource::WasapiLoopback
+        } else {
+            SystemAudioSource::StereoMix
+        });
+        if loopback_available {
+            capabilities["requires_manual_enable"] = json!(false);
+        }
+        
         // System info
         capabilities["system_info"] = json!({
             "os": std::env::consts::OS,
@@ -381,34 +282,193 @@ impl StereoMixManager {
 
 /// Tauri command to check if Stereo Mix is enabled
 #[tauri::command]
-pub async fn check_stereo_mix_enabled() -> Result<bool, String> {
-    StereoMixManager::is_stereo_mix_enabled()
-        .map_err(|e| e.to_string())
+#[specta::specta]
+pub async fn check_stereo_mix_enabled() -> Response<bool> {
+    envelope::respond("check_stereo_mix_enabled", async move {
+        crate::platform::ensure_available(crate::platform::Capability::StereoMix)?;
+        StereoMixManager::is_stereo_mix_enabled()
+            .map_err(|e| AppError::from(e.to_string()))
+    })
+    .await
+}
+
+/// Enables every disabled Stereo Mix endpoint; run elevated. Exits 2 when the audio driver has none.
+#[cfg(windows)]
+const ENABLE_SCRIPT: &str = r#"
+$ErrorActionPreference = 'Stop'
+$endpoints = @(Get-PnpDevice -Class AudioEndpoint | Where-Object { $_.FriendlyName -match 'Stereo Mix|What U Hear|Wave Out Mix' })
+if ($endpoints.Count -eq 0) { exit 2 }
+$endpoints | Where-Object { $_.Status -ne 'OK' } | Enable-PnpDevice -Confirm:$false
+exit 0
+"#;
+
+/// How long a freshly enabled endpoint gets to show up as active
+#[cfg(windows)]
+const ENABLE_SETTLE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(6);
+
+/// Run `ENABLE_SCRIPT` in an elevated PowerShell, which shows the UAC prompt
+#[cfg(windows)]
+fn enable_stereo_mix_elevated() -> std::result::Result<(), AppError> {
+    use base64::{engine::general_purpose::STANDARD as B64, Engine as _};
+
+    // -EncodedCommand takes base64 UTF-16LE, which avoids quoting the script through two shells
+    let script: Vec<u8> = ENABLE_SCRIPT.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    let launcher = format!(
+        "$p = Start-Process powershell -Verb RunAs -WindowStyle Hidden -Wait -PassThru \
+         -ArgumentList '-NoProfile','-NonInteractive','-EncodedCommand','{}'; exit $p.ExitCode",
+        B64.encode(script)
+    );
+    info!("🛡️ Requesting administrator approval to enable Stereo Mix...");
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &launcher])
+        .output()
+        .map_err(|e| AppError::new(ErrorCode::Unavailable, format!("Failed to start PowerShell: {}", e)))?;
+
+    match output.status.code() {
+        Some(0) => Ok(()),
+        Some(2) => Err(AppError::new(
+            ErrorCode::NotFound,
+            "This audio driver has no Stereo Mix device; system audio needs WASAPI loopback",
+        )),
+        _ => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if stderr.contains("canceled by the user") || stderr.contains("cancelled by the user") {
+                Err(AppError::new(
+                    ErrorCode::PermissionDenied,
+                    "Administrator approval was declined; Stereo Mix was not enabled",
+                ))
+            } else {
+                error!("Elevated Stereo Mix enablement failed: {}", stderr.trim());
+                Err(AppError::new(
+                    ErrorCode::Internal,
+                    format!("Failed to enable Stereo Mix: {}", stderr.trim()),
+                ))
+            }
+        }
+    }
+}
+
+/// Unmute the Stereo Mix endpoint and raise it from zero volume; true when anything changed
+#[cfg(windows)]
+fn unmute_stereo_mix(device: &wasapi::Device) -> std::result::Result<bool, AppError> {
+    use windows::core::HSTRING;
+    use windows::Win32::Media::Audio::Endpoints::IAudioEndpointVolume;
+    use windows::Win32::Media::Audio::{IMMDeviceEnumerator, MMDeviceEnumerator};
+    use windows::Win32::System::Com::{CoCreateInstance, CLSCTX_ALL};
+
+    let id = device.get_id().map_err(|e| AppError::from(e.to_string()))?;
+    let unmute = || -> windows::core::Result<bool> {
+        unsafe {
+            let enumerator: IMMDeviceEnumerator = CoCreateInstance(&MMDeviceEnumerator, None, CLSCTX_ALL)?;
+            let endpoint = enumerator.GetDevice(&HSTRING::from(id.as_str()))?;
+            let volume: IAudioEndpointVolume = endpoint.Activate(CLSCTX_ALL, None)?;
+            let mut changed = false;
+            if volume.GetMute()?.as_bool() {
+                volume.SetMute(false, std::ptr::null())?;
+                changed = true;
+            }
+            if volume.GetMasterVolumeLevelScalar()? < 0.01 {
+                volume.SetMasterVolumeLevelScalar(1.0, std::ptr::null())?;
+                changed = true;
+            }
+            Ok(changed)
+        }
+    };
+    unmute().map_err(|e| AppError::new(ErrorCode::Internal, format!("Failed to unmute Stereo Mix: {}", e)))
+}
+
+/// Enable (elevating if needed), unmute and verify Stereo Mix, reporting each stage
+#[cfg(windows)]
+fn guided_enable(progress: &Progress) -> std::result::Result<String, AppError> {
+    let _ = wasapi::initialize_mta();
+
+    progress.report("checking", Some(5.0));
+    let mut device = find_stereo_mix_device();
+    let mut enabled_now = false;
+    if device.is_none() {
+        progress.report("requesting_elevation", Some(15.0));
+        enable_stereo_mix_elevated()?;
+
+        progress.report("enabling", Some(40.0));
+        let deadline = std::time::Instant::now() + ENABLE_SETTLE_TIMEOUT;
+        while device.is_none() && std::time::Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(500));
+            device = find_stereo_mix_device();
+        }
+        enabled_now = true;
+    }
+    let device = device.ok_or_else(|| {
+        AppError::new(
+            ErrorCode::Unavailable,
+            "Stereo Mix was enabled but did not become active; it may need a restart of the audio service",
+        )
+    })?;
+
+    progress.report("unmuting", Some(70.0));
+    let unmuted = unmute_stereo_mix(&device)?;
+
+    progress.report("verifying", Some(85.0));
+    crate::audio_thread::open_device_stream(&device)
+        .map_err(|e| AppError::new(ErrorCode::Unavailable, format!("Stereo Mix is enabled but could not be opened: {}", e)))?;
+
+    let message = match (enabled_now, unmuted) {
+        (false, false) => "Stereo Mix is already enabled",
+        (false, true) => "Stereo Mix was muted and has been unmuted",
+        (true, false) => "Stereo Mix enabled successfully",
+        (true, true) => "Stereo Mix enabled and unmuted successfully",
+    };
+    info!("✅ {}", message);
+    Ok(message.to_string())
+}
+
+#[cfg(not(windows))]
+fn guided_enable(_progress: &Progress) -> std::result::Result<String, AppError> {
+    Err(AppError::new(ErrorCode::Unsupported, "Stereo Mix is only available on Windows"))
 }
 
 /// Tauri command to automatically enable Stereo Mix
 #[tauri::command]
-pub async fn enable_stereo_mix() -> Result<String, String> {
-    StereoMixManager::auto_enable_stereo_mix()
-        .map_err(|e| e.to_string())
+#[specta::specta]
+pub async fn enable_stereo_mix(request_id: Option<String>) -> Response<String> {
+    cancellation::run_with_progress("enable_stereo_mix", request_id, |progress| async move {
+        crate::platform::ensure_available(crate::platform::Capability::StereoMix)?;
+        tauri::async_runtime::spawn_blocking(move || guided_enable(&progress))
+            .await
+            .map_err(|e| e.to_string())?
+    })
+    .await
 }
 
 /// Tauri command to open recording devices manually
 #[tauri::command]
-pub async fn open_recording_devices() -> Result<(), String> {
-    StereoMixManager::open_recording_devices()
-        .map_err(|e| e.to_string())
+#[specta::specta]
+pub async fn open_recording_devices() -> Response<()> {
+    envelope::respond("open_recording_devices", async move {
+        crate::platform::ensure_available(crate::platform::Capability::StereoMix)?;
+        StereoMixManager::open_recording_devices()
+            .map_err(|e| AppError::from(e.to_string()))
+    })
+    .await
 }
 
 /// Tauri command to get system Stereo Mix capabilities
 #[tauri::command]
-pub async fn get_stereo_mix_capabilities() -> Result<serde_json::Value, String> {
-    StereoMixManager::check_stereo_mix_capability()
-        .map_err(|e| e.to_string())
+#[specta::specta]
+pub async fn get_stereo_mix_capabilities() -> Response<serde_json::Value> {
+    envelope::respond("get_stereo_mix_capabilities", async move {
+        crate::platform::ensure_available(crate::platform::Capability::StereoMix)?;
+        StereoMixManager::check_stereo_mix_capability()
+            .map_err(|e| AppError::from(e.to_string()))
+    })
+    .await
 }
 
 /// Tauri command to get manual enablement instructions
 #[tauri::command]
-pub async fn get_stereo_mix_instructions() -> Result<Vec<String>, String> {
-    Ok(StereoMixManager::get_manual_enable_instructions())
+#[specta::specta]
+pub async fn get_stereo_mix_instructions() -> Response<Vec<String>> {
+    envelope::respond("get_stereo_mix_instructions", async move {
+        Ok(StereoMixManager::get_manual_enable_instructions())
+    })
+    .await
 }
@@ -0,0 +1,339 @@
+// System design answer mode
+// System design questions get a dedicated prompt that answers in fixed sections: a short overview,
+// a text diagram (Mermaid or ASCII), component-by-component narration and talking points. The
+// stream is split on the section markers as it arrives and each piece is emitted to the AI window
+// as a `system-design-section` event, so the diagram can render separately from what to say.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use log::info;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter};
+
+use crate::openai::InterviewContext;
+use crate::pollinations::{PollinationsClient, PollinationsModel};
+use crate::question_topic::QuestionTopic;
+use crate::envelope::{self, Response};
+use crate::events;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagramFormat {
+    Mermaid,
+    Ascii,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SystemDesignSettings {
+    /// Answer detected system design questions in this mode
+    pub enabled: bool,
+    pub diagram_format: DiagramFormat,
+}
+
+impl Default for SystemDesignSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            diagram_format: DiagramFormat::Mermaid,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum Section {
+    Overview,
+    Diagram,
+    Components,
+    TalkingPoints,
+}
+
+impl Section {
+    const ALL: [Section; 4] = [Section::Overview, Section::Diagram, Section::Components, Section::TalkingPoints];
+
+    fn marker(self) -> &'static str {
+        match self {
+            Section::Overview => "=== OVERVIEW ===",
+            Section::Diagram => "=== DIAGRAM ===",
+            Section::Components => "=== COMPONENTS ===",
+            Section::TalkingPoints => "=== TALKING POINTS ===",
+        }
+    }
+
+    fn heading(self) -> &'static str {
+        match self {
+            Section::Overview => "Overview",
+            Section::Diagram => "Diagram",
+            Section::Components => "Components",
+            Section::TalkingPoints => "Talking points",
+        }
+    }
+}
+
+/// Payload of `system-design-section`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct SectionChunk {
+    pub section: Section,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, Type)]
+pub struct SystemDesignAnswer {
+    pub question: String,
+    pub diagram_format: Option<DiagramFormat>,
+    pub overview: String,
+    /// Diagram source without the code fence
+    pub diagram: String,
+    pub components: String,
+    pub talking_points: String,
+}
+
+impl SystemDesignAnswer {
+    fn section_mut(&mut self, section: Section) -> &mut String {
+        match section {
+            Section::Overview => &mut self.overview,
+            Section::Diagram => &mut self.diagram,
+            Section::Components => &mut self.components,
+            Section::TalkingPoints => &mut self.talking_points,
+        }
+    }
+
+    /// The whole answer as Markdown, for the regular response view
+    pub fn to_markdown(&self) -> String {
+        let fence = match self.diagram_format {
+            Some(DiagramFormat::Mermaid) => "mermaid",
+            _ => "text",
+        };
+        let mut markdown = String::new();
+        for section in Section::ALL {
+            let text = match section {
+                Section::Overview => self.overview.trim(),
+                Section::Diagram => self.diagram.trim(),
+                Section::Components => self.components.trim(),
+                Section::TalkingPoints => self.talking_points.trim(),
+            };
+            if text.is_empty() {
+                continue;
+            }
+            markdown.push_str(&format!("## {}\n\n", section.heading()));
+            if section == Section::Diagram {
+                markdown.push_str(&format!("```{}\n{}\n```\n\n", fence, text));
+            } else {
+                markdown.push_str(&format!("{}\n\n", text));
+            }
+        }
+        markdown.trim_end().to_string()
+    }
+}
+
+/// Splits streamed text on the section markers. Marker lines may arrive split across chunks, so a
+/// partial line that could still become a marker is held back until its newline arrives.
+struct SectionSplitter {
+    section: Section,
+    pending: String,
+    /// The current line already started with non-marker text
+    mid_line: bool,
+}
+
+impl SectionSplitter {
+    fn new() -> Self {
+        Self {
+            section: Section::Overview,
+            pending: String::new(),
+            mid_line: false,
+        }
+    }
+
+    fn marker_section(line: &str) -> Option<Section> {
+        let line = line.trim();
+        Section::ALL.into_iter().find(|s| s.marker().eq_ignore_ascii_case(line))
+    }
+
+    fn emit(&self, out: &mut Vec<SectionChunk>, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        match out.last_mut() {
+            Some(last) if last.section == self.section => last.text.push_str(text),
+            _ => out.push(SectionChunk {
+                section: self.section,
+                text: text.to_string(),
+            }),
+        }
+    }
+
+    fn push(&mut self, text: &str) -> Vec<SectionChunk> {
+        let mut out = Vec::new();
+        self.pending.push_str(text);
+        while let Some(newline) = self.pending.find('\n') {
+            let line: String = self.pending.drain(..=newline).collect();
+            match (!self.mid_line).then(|| Self::marker_section(&line)).flatten() {
+                Some(section) => self.section = section,
+                None => self.emit(&mut out, &line),
+            }
+            self.mid_line = false;
+        }
+        // Stream the rest of the line right away unless it may still turn out to be a marker
+        if !self.pending.is_empty() && (self.mid_line || !self.pending.trim_start().starts_with('=')) {
+            let rest = std::mem::take(&mut self.pending);
+            self.emit(&mut out, &rest);
+            self.mid_line = true;
+        }
+        out
+    }
+
+    fn finish(&mut self) -> Vec<SectionChunk> {
+        let mut out = Vec::new();
+        let rest = std::mem::take(&mut self.pending);
+        if self.mid_line || Self::marker_section(&rest).is_none() {
+            self.emit(&mut out, &rest);
+        }
+        out
+    }
+}
+
+/// Models sometimes fence the diagram despite being asked not to
+fn strip_fence(diagram: &str) -> String {
+    let trimmed = diagram.trim();
+    match trimmed.strip_prefix("```") {
+        Some(body) => {
+            let body = body.split_once('\n').map(|(_, rest)| rest).unwrap_or_default();
+            body.trim_end().strip_suffix("```").unwrap_or(body).trim_end().to_string()
+        }
+        None => trimmed.to_string(),
+    }
+}
+
+static SETTINGS: Lazy<Mutex<SystemDesignSettings>> = Lazy::new(|| Mutex::new(load_settings()));
+
+fn settings_path() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("system_design.json")
+}
+
+fn load_settings() -> SystemDesignSettings {
+    std::fs::read(settings_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+/// Whether `question` should be answered in system design mode
+pub fn detect_enabled(question: &str) -> bool {
+    SETTINGS.lock().enabled && QuestionTopic::classify(question) == Some(QuestionTopic::SystemDesign)
+}
+
+fn prompt(question: &str, format: DiagramFormat) -> String {
+    let diagram = match format {
+        DiagramFormat::Mermaid => "a Mermaid `flowchart LR` diagram (only the Mermaid source, no code fence)",
+        DiagramFormat::Ascii => "an ASCII box-and-arrow diagram no wider than 70 characters (no code fence)",
+    };
+    format!(
+        "System design interview question: {question}\n\n\
+         Answer as the candidate would walk an interviewer through the design. Use exactly these section \
+         markers, each on its own line, in this order:\n\
+         {overview}\nTwo or three sentences: requirements and scale assumptions you are designing for.\n\
+         {diagram_marker}\n{diagram} showing clients, services, data stores, caches and queues.\n\
+         {components}\nOne bullet per component in the diagram: what it does, why it is there, and the key \
+         technology choice. Write it as narration the candidate can say aloud.\n\
+         {talking_points}\n4-6 bullets on scaling, bottlenecks, consistency and trade-offs worth raising.\n\n\
+         Do not write anything outside these sections.",
+        overview = Section::Overview.marker(),
+        diagram_marker = Section::Diagram.marker(),
+        components = Section::Components.marker(),
+        talking_points = Section::TalkingPoints.marker(),
+    )
+}
+
+fn emit_chunks(app_handle: &AppHandle, chunks: Vec<SectionChunk>) {
+    for chunk in chunks {
+        if let Err(e) = app_handle.emit_to(crate::ai_stream::RESPONSE_WINDOW_LABEL, events::SYSTEM_DESIGN_SECTION, &chunk) {
+            log::error!("Failed to emit {}: {}", events::SYSTEM_DESIGN_SECTION, e);
+        }
+    }
+}
+
+/// Stream a system design answer, emitting each section as it arrives
+pub(crate) async fn stream_answer(
+    app_handle: &AppHandle,
+    client: &PollinationsClient,
+    context: &InterviewContext,
+    model: PollinationsModel,
+    question: &str,
+) -> Result<SystemDesignAnswer, String> {
+    let format = SETTINGS.lock().diagram_format;
+    let splitter = Arc::new(Mutex::new(SectionSplitter::new()));
+    let answer = Arc::new(Mutex::new(SystemDesignAnswer {
+        question: question.trim().to_string(),
+        diagram_format: Some(format),
+        ..Default::default()
+    }));
+
+    let sink_app = app_handle.clone();
+    let (sink_splitter, sink_answer) = (splitter.clone(), answer.clone());
+    let tokens = crate::ai_stream::TokenCoalescer::spawn(move |chunk: String| {
+        let chunks = sink_splitter.lock().push(&chunk);
+        {
+            let mut answer = sink_answer.lock();
+            for piece in &chunks {
+                answer.section_mut(piece.section).push_str(&piece.text);
+            }
+        }
+        emit_chunks(&sink_app, chunks);
+        Box::pin(async {})
+    });
+    let token_sink = tokens.clone();
+    let result = client
+        .generate_answer_streaming(&prompt(question, format), context, model, move |token: &str| token_sink.push(token))
+        .await;
+    tokens.finish().await;
+    result.map_err(|e| e.to_string())?;
+
+    let rest = splitter.lock().finish();
+    let answer = {
+        let mut answer = answer.lock();
+        for piece in &rest {
+            answer.section_mut(piece.section).push_str(&piece.text);
+        }
+        answer.diagram = strip_fence(&answer.diagram);
+        answer.clone()
+    };
+    emit_chunks(app_handle, rest);
+    info!(
+        "🏗️ System design answer streamed ({} diagram chars, {} component chars)",
+        answer.diagram.len(),
+        answer.components.len()
+    );
+    Ok(answer)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_system_design_settings() -> Response<SystemDesignSettings> {
+    envelope::respond("get_system_design_settings", async move {
+        Ok(SETTINGS.lock().clone())
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_system_design_settings(settings: SystemDesignSettings) -> Response<SystemDesignSettings> {
+    envelope::respond("set_system_design_settings", async move {
+        let path = settings_path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+        }
+        let body = serde_json::to_vec_pretty(&settings).map_err(|e| e.to_string())?;
+        std::fs::write(&path, body).map_err(|e| e.to_string())?;
+        *SETTINGS.lock() = settings.clone();
+        Ok(settings)
+    })
+    .await
+}
@@ -20,6 +20,8 @@ use windows_sys::{
 };
 use log::{info, error, warn};
 use anyhow::Result;
+use crate::envelope::{self, Response};
+use crate::error::AppError;
 
 /// Task Manager stealth manager
 pub struct TaskManagerStealth {
@@ -272,58 +274,70 @@ pub fn init_task_manager_stealth(app_handle: AppHandle) {
 
 /// Tauri command to enable Task Manager stealth
 #[tauri::command]
-pub async fn enable_task_manager_stealth() -> Result<String, String> {
-    let stealth = get_task_manager_stealth();
-    let guard = stealth.lock().unwrap();
+pub async fn enable_task_manager_stealth() -> Response<String> {
+    envelope::respond("enable_task_manager_stealth", async move {
+        let stealth = get_task_manager_stealth();
+        let guard = stealth.lock().unwrap();
     
-    if let Some(ref task_stealth) = *guard {
-        task_stealth.enable_stealth()
-            .map_err(|e| e.to_string())?;
-        Ok("Task Manager stealth enabled - process hidden from detection".to_string())
-    } else {
-        Err("Task Manager stealth not initialized".to_string())
-    }
+        if let Some(ref task_stealth) = *guard {
+            task_stealth.enable_stealth()
+                .map_err(|e| e.to_string())?;
+            Ok("Task Manager stealth enabled - process hidden from detection".to_string())
+        } else {
+            Err("Task Manager stealth not initialized".into())
+        }
+    })
+    .await
 }
 
 /// Tauri command to disable Task Manager stealth
 #[tauri::command]
-pub async fn disable_task_manager_stealth() -> Result<String, String> {
-    let stealth = get_task_manager_stealth();
-    let guard = stealth.lock().unwrap();
+pub async fn disable_task_manager_stealth() -> Response<String> {
+    envelope::respond("disable_task_manager_stealth", async move {
+        let stealth = get_task_manager_stealth();
+        let guard = stealth.lock().unwrap();
     
-    if let Some(ref task_stealth) = *guard {
-        task_stealth.disable_stealth()
-            .map_err(|e| e.to_string())?;
-        Ok("Task Manager stealth disabled - process restored to normal visibility".to_string())
-    } else {
-        Err("Task Manager stealth not initialized".to_string())
-    }
+        if let Some(ref task_stealth) = *guard {
+            task_stealth.disable_stealth()
+                .map_err(|e| e.to_string())?;
+            Ok("Task Manager stealth disabled - process restored to normal visibility".to_string())
+        } else {
+            Err("Task Manager stealth not initialized".into())
+        }
+    })
+    .await
 }
 
 /// Tauri command to apply advanced stealth
 #[tauri::command]
-pub async fn apply_advanced_stealth() -> Result<String, String> {
-    let stealth = get_task_manager_stealth();
-    let guard = stealth.lock().unwrap();
+pub async fn apply_advanced_stealth() -> Response<String> {
+    envelope::respond("apply_advanced_stealth", async move {
+        let stealth = get_task_manager_stealth();
+        let guard = stealth.lock().unwrap();
     
-    if let Some(ref task_stealth) = *guard {
-        task_stealth.apply_advanced_stealth()
-            .map_err(|e| e.to_string())?;
-        Ok("Advanced stealth techniques applied - maximum stealth mode active".to_string())
-    } else {
-        Err("Task Manager stealth not initialized".to_string())
-    }
+        if let Some(ref task_stealth) = *guard {
+            task_stealth.apply_advanced_stealth()
+                .map_err(|e| e.to_string())?;
+            Ok("Advanced stealth techniques applied - maximum stealth mode active".to_string())
+        } else {
+            Err("Task Manager stealth not initialized".into())
+        }
+    })
+    .await
 }
 
 /// Tauri command to get stealth status
 #[tauri::command]
-pub async fn get_task_manager_stealth_status() -> Result<serde_json::Value, String> {
-    let stealth = get_task_manager_stealth();
-    let guard = stealth.lock().unwrap();
+pub async fn get_task_manager_stealth_status() -> Response<serde_json::Value> {
+    envelope::respond("get_task_manager_stealth_status", async move {
+        let stealth = get_task_manager_stealth();
+        let guard = stealth.lock().unwrap();
     
-    if let Some(ref task_stealth) = *guard {
-        Ok(task_stealth.get_process_info())
-    } else {
-        Err("Task Manager stealth not initialized".to_string())
-    }
+        if let Some(ref task_stealth) = *guard {
+            Ok(task_stealth.get_process_info())
+        } else {
+            Err("Task Manager stealth not initialized".into())
+        }
+    })
+    .await
 }
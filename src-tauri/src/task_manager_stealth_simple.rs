@@ -4,6 +4,7 @@
 use std::collections::HashMap;
 use std::ffi::CStr;
 use serde::{Serialize, Deserialize};
+use specta::Type;
 use log::{info, warn};
 use anyhow::Result;
 use windows_sys::Win32::{
@@ -24,9 +25,11 @@ use winapi::{
 };
 #[cfg(windows)]
 use std::mem;
+use crate::envelope::{self, Response};
+use crate::error::AppError;
 
 /// Task manager stealth status with all 4 methods
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct TaskManagerStatus {
     pub process_id: u32,
     pub stealth_enabled: bool,
@@ -1061,33 +1064,45 @@ fn get_task_manager_stealth() -> Option<&'static mut TaskManagerStealth> {
 // Tauri commands for comprehensive stealth
 
 #[tauri::command]
-pub fn enable_task_manager_stealth() -> Result<String, String> {
-    match get_task_manager_stealth() {
-        Some(manager) => manager.enable_stealth().map_err(|e| e.to_string()),
-        None => Err("🔥 Maximum stealth system not initialized".to_string()),
-    }
+#[specta::specta]
+pub fn enable_task_manager_stealth() -> Response<String> {
+    envelope::respond_sync("enable_task_manager_stealth", || {
+        match get_task_manager_stealth() {
+            Some(manager) => manager.enable_stealth().map_err(|e| AppError::from(e.to_string())),
+            None => Err("🔥 Maximum stealth system not initialized".into()),
+        }
+    })
 }
 
 #[tauri::command]
-pub fn disable_task_manager_stealth() -> Result<String, String> {
-    match get_task_manager_stealth() {
-        Some(manager) => manager.disable_stealth().map_err(|e| e.to_string()),
-        None => Err("🔥 Maximum stealth system not initialized".to_string()),
-    }
+#[specta::specta]
+pub fn disable_task_manager_stealth() -> Response<String> {
+    envelope::respond_sync("disable_task_manager_stealth", || {
+        match get_task_manager_stealth() {
+            Some(manager) => manager.disable_stealth().map_err(|e| AppError::from(e.to_string())),
+            None => Err("🔥 Maximum stealth system not initialized".into()),
+        }
+    })
 }
 
 #[tauri::command]
-pub fn apply_advanced_stealth() -> Result<String, String> {
-    match get_task_manager_stealth() {
-        Some(manager) => manager.apply_advanced_stealth().map_err(|e| e.to_string()),
-        None => Err("🔥 Maximum stealth system not initialized".to_string()),
-    }
+#[specta::specta]
+pub fn apply_advanced_stealth() -> Response<String> {
+    envelope::respond_sync("apply_advanced_stealth", || {
+        match get_task_manager_stealth() {
+            Some(manager) => manager.apply_advanced_stealth().map_err(|e| AppError::from(e.to_string())),
+            None => Err("🔥 Maximum stealth system not initialized".into()),
+        }
+    })
 }
 
 #[tauri::command]
-pub fn get_task_manager_stealth_status() -> Result<TaskManagerStatus, String> {
-    match get_task_manager_stealth() {
-        Some(manager) => Ok(manager.get_status()),
-        None => Err("🔥 Maximum stealth system not initialized".to_string()),
-    }
+#[specta::specta]
+pub fn get_task_manager_stealth_status() -> Response<TaskManagerStatus> {
+    envelope::respond_sync("get_task_manager_stealth_status", || {
+        match get_task_manager_stealth() {
+            Some(manager) => Ok(manager.get_status()),
+            None => Err("🔥 Maximum stealth system not initialized".into()),
+        }
+    })
 }
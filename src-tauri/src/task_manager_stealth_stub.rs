@@ -0,0 +1,38 @@
+// Task Manager hiding only exists on Windows; other platforms report it as unavailable
+
+use crate::platform::{unavailable, Capability};
+use crate::envelope::{self, Response};
+
+pub fn initialize_task_manager_stealth() {}
+
+#[tauri::command]
+#[specta::specta]
+pub fn enable_task_manager_stealth() -> Response<String> {
+    envelope::respond_sync("enable_task_manager_stealth", || {
+        Err(unavailable(Capability::Stealth).into())
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn disable_task_manager_stealth() -> Response<String> {
+    envelope::respond_sync("disable_task_manager_stealth", || {
+        Err(unavailable(Capability::Stealth).into())
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn apply_advanced_stealth() -> Response<String> {
+    envelope::respond_sync("apply_advanced_stealth", || {
+        Err(unavailable(Capability::Stealth).into())
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_task_manager_stealth_status() -> Response<serde_json::Value> {
+    envelope::respond_sync("get_task_manager_stealth_status", || {
+        Err(unavailable(Capability::Stealth).into())
+    })
+}
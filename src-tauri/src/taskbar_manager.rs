@@ -14,6 +14,8 @@ use winapi::um::winuser::{
 };
 #[cfg(windows)]
 use winapi::shared::windef::HWND;
+use crate::envelope::{self, Response};
+use crate::error::AppError;
 
 /// Taskbar visibility manager
 pub struct TaskbarManager {
@@ -197,41 +199,56 @@ pub fn get_taskbar_manager() -> Option<&'static mut TaskbarManager> {
 // Tauri commands for taskbar management
 
 #[tauri::command]
-pub fn hide_from_taskbar() -> Result<String, String> {
-    match get_taskbar_manager() {
-        Some(manager) => manager.hide_from_taskbar().map(|_| "Application hidden from taskbar".to_string()).map_err(|e| e.to_string()),
-        None => Err("Taskbar manager not initialized".to_string()),
-    }
+#[specta::specta]
+pub fn hide_from_taskbar() -> Response<String> {
+    envelope::respond_sync("hide_from_taskbar", || {
+        match get_taskbar_manager() {
+            Some(manager) => manager.hide_from_taskbar().map(|_| "Application hidden from taskbar".to_string()).map_err(|e| AppError::from(e.to_string())),
+            None => Err("Taskbar manager not initialized".into()),
+        }
+    })
 }
 
 #[tauri::command]
-pub fn show_in_taskbar() -> Result<String, String> {
-    match get_taskbar_manager() {
-        Some(manager) => manager.show_in_taskbar().map(|_| "Application shown in taskbar".to_string()).map_err(|e| e.to_string()),
-        None => Err("Taskbar manager not initialized".to_string()),
-    }
+#[specta::specta]
+pub fn show_in_taskbar() -> Response<String> {
+    envelope::respond_sync("show_in_taskbar", || {
+        match get_taskbar_manager() {
+            Some(manager) => manager.show_in_taskbar().map(|_| "Application shown in taskbar".to_string()).map_err(|e| AppError::from(e.to_string())),
+            None => Err("Taskbar manager not initialized".into()),
+        }
+    })
 }
 
 #[tauri::command]
-pub fn toggle_taskbar_visibility() -> Result<String, String> {
-    match get_taskbar_manager() {
-        Some(manager) => manager.toggle_taskbar_visibility().map_err(|e| e.to_string()),
-        None => Err("Taskbar manager not initialized".to_string()),
-    }
+#[specta::specta]
+pub fn toggle_taskbar_visibility() -> Response<String> {
+    envelope::respond_sync("toggle_taskbar_visibility", || {
+        match get_taskbar_manager() {
+            Some(manager) => manager.toggle_taskbar_visibility().map_err(|e| AppError::from(e.to_string())),
+            None => Err("Taskbar manager not initialized".into()),
+        }
+    })
 }
 
 #[tauri::command]
-pub fn get_taskbar_status() -> Result<serde_json::Value, String> {
-    match get_taskbar_manager() {
-        Some(manager) => Ok(manager.get_taskbar_status()),
-        None => Err("Taskbar manager not initialized".to_string()),
-    }
+#[specta::specta]
+pub fn get_taskbar_status() -> Response<serde_json::Value> {
+    envelope::respond_sync("get_taskbar_status", || {
+        match get_taskbar_manager() {
+            Some(manager) => Ok(manager.get_taskbar_status()),
+            None => Err("Taskbar manager not initialized".into()),
+        }
+    })
 }
 
 #[tauri::command]
-pub fn is_hidden_from_taskbar() -> Result<bool, String> {
-    match get_taskbar_manager() {
-        Some(manager) => Ok(manager.is_hidden_from_taskbar()),
-        None => Err("Taskbar manager not initialized".to_string()),
-    }
+#[specta::specta]
+pub fn is_hidden_from_taskbar() -> Response<bool> {
+    envelope::respond_sync("is_hidden_from_taskbar", || {
+        match get_taskbar_manager() {
+            Some(manager) => Ok(manager.is_hidden_from_taskbar()),
+            None => Err("Taskbar manager not initialized".into()),
+        }
+    })
 }
\ No newline at end of file
@@ -0,0 +1,456 @@
+// Microsoft Graph integration for Teams meetings
+// After a meeting, pulls the official Teams transcript and recording metadata through Graph and
+// merges them with the local interview report: every saved question is matched to the transcript
+// turn where it was spoken, so the report carries who actually asked it, who answered and what was
+// said. Graph access tokens are issued by the backend, which owns the Microsoft account link.
+// Optional: nothing is fetched unless the command is invoked.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+
+use chrono::{DateTime, Duration, Utc};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::database::postgres::SessionReport;
+use crate::net_trace::TracedSend;
+use crate::envelope::{self, IntoResult, Response};
+use crate::error::AppError;
+
+const GRAPH_URL: &str = "https://graph.microsoft.com/v1.0";
+/// Share of a question's words that must appear in a transcript turn to count as a match
+const MATCH_THRESHOLD: f32 = 0.5;
+/// How far from the locally recorded time a question may appear in the transcript
+const MATCH_WINDOW_MINUTES: i64 = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TeamsMeetingInfo {
+    pub id: String,
+    pub subject: Option<String>,
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    pub join_url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TeamsRecording {
+    pub id: String,
+    pub created_at: Option<DateTime<Utc>>,
+    pub content_url: Option<String>,
+}
+
+/// Consecutive transcript cues from the same speaker
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TranscriptTurn {
+    pub speaker: Option<String>,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SpeakerStats {
+    pub speaker: String,
+    pub talk_time_ms: u64,
+    pub turns: usize,
+    pub is_candidate: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ReconciledQuestion {
+    pub question_id: String,
+    pub question_text: String,
+    pub asked_at: DateTime<Utc>,
+    /// Transcript speaker who asked the question; None when no turn matched
+    pub asked_by: Option<String>,
+    pub transcript_text: Option<String>,
+    pub transcript_offset_ms: Option<u64>,
+    pub match_score: f32,
+    /// Speaker who replied (normally the candidate) and what they said
+    pub answered_by: Option<String>,
+    pub spoken_answer: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TeamsMeetingReport {
+    pub session_id: String,
+    pub meeting: TeamsMeetingInfo,
+    /// Display name of the signed-in Microsoft account
+    pub candidate_name: Option<String>,
+    pub transcript_available: bool,
+    pub recordings: Vec<TeamsRecording>,
+    pub speakers: Vec<SpeakerStats>,
+    pub questions: Vec<ReconciledQuestion>,
+    pub transcript: Vec<TranscriptTurn>,
+    pub report: SessionReport,
+    pub imported_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+struct GraphList<T> {
+    value: Vec<T>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphMeeting {
+    id: String,
+    subject: Option<String>,
+    start_date_time: Option<DateTime<Utc>>,
+    end_date_time: Option<DateTime<Utc>>,
+    join_web_url: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphArtifact {
+    id: String,
+    created_date_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    recording_content_url: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphUser {
+    display_name: Option<String>,
+}
+
+struct Cue {
+    speaker: Option<String>,
+    start_ms: u64,
+    end_ms: u64,
+    text: String,
+}
+
+fn reports_dir() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("teams")
+}
+
+fn backend_url() -> String {
+    std::env::var("MOCKMATE_BACKEND_URL").unwrap_or_else(|_| "https://mockmate-backend.onrender.com".to_string())
+}
+
+/// Graph access token for the user's linked Microsoft account
+async fn graph_token(token: &str) -> Result<String, String> {
+    let response = reqwest::Client::new()
+        .get(format!("{}/api/integrations/microsoft/graph-token", backend_url()))
+        .bearer_auth(token)
+        .send_traced()
+        .await
+        .map_err(|e| format!("Failed to get Microsoft Graph token: {}", e))?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err("No Microsoft account is linked".to_string());
+    }
+    if !response.status().is_success() {
+        return Err(format!("Microsoft Graph token request failed: {}", response.status()));
+    }
+    let body: serde_json::Value = response.json().await.map_err(|e| e.to_string())?;
+    body["access_token"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| "Graph token response did not include an access_token".to_string())
+}
+
+async fn graph_get(client: &reqwest::Client, access_token: &str, path: &str, query: &[(&str, &str)]) -> Result<reqwest::Response, String> {
+    let response = client
+        .get(format!("{}{}", GRAPH_URL, path))
+        .bearer_auth(access_token)
+        .query(query)
+        .send_traced()
+        .await
+        .map_err(|e| format!("Graph request {} failed: {}", path, e))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("Graph request {} failed: {} {}", path, status, body));
+    }
+    Ok(response)
+}
+
+async fn graph_json<T: for<'de> Deserialize<'de>>(client: &reqwest::Client, access_token: &str, path: &str, query: &[(&str, &str)]) -> Result<T, String> {
+    graph_get(client, access_token, path, query)
+        .await?
+        .json()
+        .await
+        .map_err(|e| format!("Unexpected Graph response for {}: {}", path, e))
+}
+
+/// "01:02:03.456" or "02:03.456" in milliseconds
+fn parse_timestamp(value: &str) -> Option<u64> {
+    let (clock, millis) = value.trim().split_once('.').unwrap_or((value.trim(), "0"));
+    let mut seconds = 0u64;
+    for part in clock.split(':') {
+        seconds = seconds * 60 + part.parse::<u64>().ok()?;
+    }
+    Some(seconds * 1000 + millis.parse::<u64>().ok()?)
+}
+
+/// Parse a Teams WebVTT transcript; speakers come from `<v Name>` voice tags
+fn parse_vtt(vtt: &str) -> Vec<Cue> {
+    let mut cues = Vec::new();
+    let mut lines = vtt.lines().map(str::trim).peekable();
+    while let Some(line) = lines.next() {
+        let Some((start, end)) = line.split_once("-->") else { continue };
+        let (Some(start_ms), Some(end_ms)) = (parse_timestamp(start), parse_timestamp(end.split_whitespace().next().unwrap_or_default())) else {
+            continue;
+        };
+        let mut speaker = None;
+        let mut text = Vec::new();
+        while let Some(line) = lines.next_if(|line| !line.is_empty()) {
+            let line = match line.strip_prefix("<v ").and_then(|rest| rest.split_once('>')) {
+                Some((name, rest)) => {
+                    speaker = Some(name.trim().to_string());
+                    rest.trim_end_matches("</v>")
+                }
+                None => line,
+            };
+            text.push(line.trim());
+        }
+        let text = text.join(" ");
+        if !text.is_empty() {
+            cues.push(Cue { speaker, start_ms, end_ms, text });
+        }
+    }
+    cues
+}
+
+fn group_turns(cues: Vec<Cue>) -> Vec<TranscriptTurn> {
+    let mut turns: Vec<TranscriptTurn> = Vec::new();
+    for cue in cues {
+        match turns.last_mut() {
+            Some(turn) if turn.speaker == cue.speaker => {
+                turn.text.push(' ');
+                turn.text.push_str(&cue.text);
+                turn.end_ms = cue.end_ms;
+            }
+            _ => turns.push(TranscriptTurn {
+                speaker: cue.speaker,
+                start_ms: cue.start_ms,
+                end_ms: cue.end_ms,
+                text: cue.text,
+            }),
+        }
+    }
+    turns
+}
+
+fn words(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.len() > 2)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Share of the question's words found in the turn
+fn overlap(question: &HashSet<String>, turn: &str) -> f32 {
+    if question.is_empty() {
+        return 0.0;
+    }
+    let turn = words(turn);
+    question.iter().filter(|w| turn.contains(*w)).count() as f32 / question.len() as f32
+}
+
+fn reconcile(report: &SessionReport, turns: &[TranscriptTurn], meeting_start: Option<DateTime<Utc>>) -> Vec<ReconciledQuestion> {
+    let mut questions: Vec<_> = report.questions.iter().collect();
+    questions.sort_by_key(|q| q.asked_at);
+    let window = Duration::minutes(MATCH_WINDOW_MINUTES);
+
+    // Turn index of each question, so answers stop where the next question starts
+    let matches: Vec<Option<(usize, f32)>> = questions
+        .iter()
+        .map(|question| {
+            let question_words = words(&question.question_text);
+            turns
+                .iter()
+                .enumerate()
+                .filter(|(_, turn)| match meeting_start {
+                    Some(start) => {
+                        let spoken_at = start + Duration::milliseconds(turn.start_ms as i64);
+                        (spoken_at - question.asked_at).abs() <= window
+                    }
+                    None => true,
+                })
+                .map(|(index, turn)| (index, overlap(&question_words, &turn.text)))
+                .filter(|(_, score)| *score >= MATCH_THRESHOLD)
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+        })
+        .collect();
+
+    questions
+        .iter()
+        .enumerate()
+        .map(|(i, question)| {
+            let matched = matches[i];
+            let (answered_by, spoken_answer) = match matched {
+                Some((index, _)) => {
+                    let next_question = matches[i + 1..].iter().flatten().map(|(next, _)| *next).find(|next| *next > index);
+                    let asker = &turns[index].speaker;
+                    let replies: Vec<&TranscriptTurn> = turns[index + 1..next_question.unwrap_or(turns.len())]
+                        .iter()
+                        .filter(|turn| turn.speaker != *asker)
+                        .collect();
+                    let mut talk: BTreeMap<&str, u64> = BTreeMap::new();
+                    for turn in &replies {
+                        if let Some(speaker) = &turn.speaker {
+                            *talk.entry(speaker).or_default() += turn.end_ms.saturating_sub(turn.start_ms);
+                        }
+                    }
+                    let answered_by = talk.into_iter().max_by_key(|(_, ms)| *ms).map(|(speaker, _)| speaker.to_string());
+                    let spoken = replies
+                        .iter()
+                        .filter(|turn| turn.speaker == answered_by)
+                        .map(|turn| turn.text.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    (answered_by, Some(spoken).filter(|s| !s.is_empty()))
+                }
+                None => (None, None),
+            };
+            ReconciledQuestion {
+                question_id: question.id.to_string(),
+                question_text: question.question_text.clone(),
+                asked_at: question.asked_at,
+                asked_by: matched.and_then(|(index, _)| turns[index].speaker.clone()),
+                transcript_text: matched.map(|(index, _)| turns[index].text.clone()),
+                transcript_offset_ms: matched.map(|(index, _)| turns[index].start_ms),
+                match_score: matched.map(|(_, score)| score).unwrap_or(0.0),
+                answered_by,
+                spoken_answer,
+            }
+        })
+        .collect()
+}
+
+fn speaker_stats(turns: &[TranscriptTurn], candidate: Option<&str>) -> Vec<SpeakerStats> {
+    let mut stats: BTreeMap<&str, (u64, usize)> = BTreeMap::new();
+    for turn in turns {
+        let entry = stats.entry(turn.speaker.as_deref().unwrap_or("Unknown")).or_default();
+        entry.0 += turn.end_ms.saturating_sub(turn.start_ms);
+        entry.1 += 1;
+    }
+    let mut stats: Vec<SpeakerStats> = stats
+        .into_iter()
+        .map(|(speaker, (talk_time_ms, turns))| SpeakerStats {
+            is_candidate: candidate.is_some_and(|name| name.eq_ignore_ascii_case(speaker)),
+            speaker: speaker.to_string(),
+            talk_time_ms,
+            turns,
+        })
+        .collect();
+    stats.sort_by_key(|s| std::cmp::Reverse(s.talk_time_ms));
+    stats
+}
+
+/// Pull the Teams transcript and recordings for the meeting at `join_url` and merge them into the
+/// session's interview report. `token` is the MockMate backend token.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_teams_meeting_data(session_id: String, join_url: String, token: String) -> Response<TeamsMeetingReport> {
+    envelope::respond("import_teams_meeting_data", async move {
+        crate::privacy::ensure_cloud_allowed("Microsoft Graph")?;
+        let report = crate::database::postgres::get_interview_report(session_id.clone()).await.into_result()?;
+        let access_token = graph_token(&token).await?;
+        let client = reqwest::Client::new();
+
+        let filter = format!("JoinWebUrl eq '{}'", join_url.replace('\'', "''"));
+        let meetings: GraphList<GraphMeeting> = graph_json(&client, &access_token, "/me/onlineMeetings", &[("$filter", &filter)]).await?;
+        let meeting = meetings
+            .value
+            .into_iter()
+            .next()
+            .ok_or("No Teams meeting found for this join link (only meetings you organized or attended are visible)")?;
+        let meeting_path = format!("/me/onlineMeetings/{}", meeting.id);
+
+        let me: GraphUser = graph_json(&client, &access_token, "/me", &[("$select", "displayName")]).await?;
+
+        let transcripts: GraphList<GraphArtifact> = graph_json(&client, &access_token, &format!("{}/transcripts", meeting_path), &[]).await?;
+        let turns = match transcripts.value.iter().max_by_key(|t| t.created_date_time) {
+            Some(transcript) => {
+                let vtt = graph_get(
+                    &client,
+                    &access_token,
+                    &format!("{}/transcripts/{}/content", meeting_path, transcript.id),
+                    &[("$format", "text/vtt")],
+                )
+                .await?
+                .text()
+                .await
+                .map_err(|e| e.to_string())?;
+                group_turns(parse_vtt(&vtt))
+            }
+            None => {
+                warn!("Teams meeting {} has no transcript (transcription may not have been enabled)", meeting.id);
+                Vec::new()
+            }
+        };
+
+        let recordings = match graph_json::<GraphList<GraphArtifact>>(&client, &access_token, &format!("{}/recordings", meeting_path), &[]).await {
+            Ok(list) => list
+                .value
+                .into_iter()
+                .map(|r| TeamsRecording {
+                    id: r.id,
+                    created_at: r.created_date_time,
+                    content_url: r.recording_content_url,
+                })
+                .collect(),
+            Err(e) => {
+                warn!("Teams recordings unavailable: {}", e);
+                Vec::new()
+            }
+        };
+
+        let questions = reconcile(&report, &turns, meeting.start_date_time);
+        let merged = TeamsMeetingReport {
+            session_id: session_id.clone(),
+            meeting: TeamsMeetingInfo {
+                id: meeting.id,
+                subject: meeting.subject,
+                start: meeting.start_date_time,
+                end: meeting.end_date_time,
+                join_url: meeting.join_web_url.unwrap_or(join_url),
+            },
+            speakers: speaker_stats(&turns, me.display_name.as_deref()),
+            candidate_name: me.display_name,
+            transcript_available: !turns.is_empty(),
+            recordings,
+            questions,
+            transcript: turns,
+            report,
+            imported_at: Utc::now(),
+        };
+
+        let dir = reports_dir();
+        std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        let body = serde_json::to_vec_pretty(&merged).map_err(|e| e.to_string())?;
+        std::fs::write(dir.join(format!("{}.json", session_id)), body).map_err(|e| e.to_string())?;
+
+        info!(
+            "🟦 Imported Teams data for session {}: {} transcript turns, {}/{} questions matched, {} recordings",
+            session_id,
+            merged.transcript.len(),
+            merged.questions.iter().filter(|q| q.asked_by.is_some()).count(),
+            merged.questions.len(),
+            merged.recordings.len()
+        );
+        Ok(merged)
+    })
+    .await
+}
+
+/// The merged report saved by the last import for this session
+#[tauri::command]
+#[specta::specta]
+pub async fn get_teams_meeting_report(session_id: String) -> Response<Option<TeamsMeetingReport>> {
+    envelope::respond("get_teams_meeting_report", async move {
+        match std::fs::read(reports_dir().join(format!("{}.json", session_id))) {
+            Ok(body) => serde_json::from_slice(&body).map(Some).map_err(|e| AppError::from(e.to_string())),
+            Err(_) => Ok(None),
+        }
+    })
+    .await
+}
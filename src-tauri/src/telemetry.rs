@@ -0,0 +1,224 @@
+// Opt-in anonymized telemetry for MockMate
+// Only feature usage counts and error categories are collected - never transcripts or AI content.
+// Names are `&'static str` so call sites can't accidentally pass user data.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::net_trace::TracedSend;
+use crate::envelope::{self, Response};
+
+/// Batches are uploaded at most this often
+const UPLOAD_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static SETTINGS: Lazy<Mutex<TelemetrySettings>> = Lazy::new(|| Mutex::new(load_settings()));
+static PENDING: Lazy<Mutex<PendingCounts>> = Lazy::new(|| Mutex::new(PendingCounts::new()));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TelemetrySettings {
+    enabled: bool,
+    /// Random per-install id, not derived from any user or machine information
+    install_id: String,
+}
+
+struct PendingCounts {
+    period_start: chrono::DateTime<chrono::Utc>,
+    features: BTreeMap<&'static str, u64>,
+    errors: BTreeMap<&'static str, u64>,
+}
+
+impl PendingCounts {
+    fn new() -> Self {
+        Self {
+            period_start: chrono::Utc::now(),
+            features: BTreeMap::new(),
+            errors: BTreeMap::new(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.features.is_empty() && self.errors.is_empty()
+    }
+}
+
+/// Exactly what is sent to the backend
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TelemetryBatch {
+    pub install_id: String,
+    pub app_version: String,
+    pub os: String,
+    pub period_start: String,
+    pub period_end: String,
+    pub feature_counts: BTreeMap<String, u64>,
+    pub error_counts: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TelemetryPreview {
+    pub enabled: bool,
+    pub endpoint: String,
+    pub pending_batch: TelemetryBatch,
+}
+
+fn settings_path() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("telemetry.json")
+}
+
+fn load_settings() -> TelemetrySettings {
+    let settings = std::fs::read(settings_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice::<TelemetrySettings>(&body).ok())
+        .unwrap_or_else(|| TelemetrySettings {
+            // Opt-in: disabled until the user turns it on
+            enabled: false,
+            install_id: uuid::Uuid::new_v4().to_string(),
+        });
+    ENABLED.store(settings.enabled, Ordering::Relaxed);
+    settings
+}
+
+fn save_settings(settings: &TelemetrySettings) -> Result<(), String> {
+    let path = settings_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(settings).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| e.to_string())
+}
+
+fn endpoint() -> String {
+    let backend_url = std::env::var("MOCKMATE_BACKEND_URL")
+        .unwrap_or_else(|_| "https://mockmate-backend.onrender.com".to_string());
+    format!("{}/api/telemetry", backend_url)
+}
+
+/// Load the persisted preference and start the periodic uploader
+pub fn init() {
+    let enabled = SETTINGS.lock().enabled;
+    info!("📊 Telemetry {}", if enabled { "enabled (opt-in)" } else { "disabled" });
+
+    tauri::async_runtime::spawn(async {
+        let mut interval = tokio::time::interval(UPLOAD_INTERVAL);
+        interval.tick().await; // first tick fires immediately
+        loop {
+            interval.tick().await;
+            if let Err(e) = upload_pending().await {
+                warn!("Telemetry upload failed, will retry next interval: {}", e);
+            }
+        }
+    });
+}
+
+/// Count one use of a feature (no-op unless telemetry is enabled)
+pub fn record_feature(name: &'static str) {
+    if ENABLED.load(Ordering::Relaxed) {
+        *PENDING.lock().features.entry(name).or_insert(0) += 1;
+    }
+}
+
+/// Count one error of the given category (no-op unless telemetry is enabled)
+pub fn record_error(category: &'static str) {
+    if ENABLED.load(Ordering::Relaxed) {
+        *PENDING.lock().errors.entry(category).or_insert(0) += 1;
+    }
+}
+
+fn build_batch(pending: &PendingCounts) -> TelemetryBatch {
+    TelemetryBatch {
+        install_id: SETTINGS.lock().install_id.clone(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        period_start: pending.period_start.to_rfc3339(),
+        period_end: chrono::Utc::now().to_rfc3339(),
+        feature_counts: pending.features.iter().map(|(k, v)| (k.to_string(), *v)).collect(),
+        error_counts: pending.errors.iter().map(|(k, v)| (k.to_string(), *v)).collect(),
+    }
+}
+
+async fn upload_pending() -> Result<(), String> {
+    // In local-only mode counts keep accumulating and go out once it is turned off
+    if !ENABLED.load(Ordering::Relaxed) || crate::privacy::is_local_only() {
+        return Ok(());
+    }
+
+    // Take the counts so new events accumulate into a fresh period while uploading
+    let pending = {
+        let mut guard = PENDING.lock();
+        if guard.is_empty() {
+            return Ok(());
+        }
+        std::mem::replace(&mut *guard, PendingCounts::new())
+    };
+    let batch = build_batch(&pending);
+
+    let result = reqwest::Client::new()
+        .post(endpoint())
+        .json(&batch)
+        .timeout(Duration::from_secs(10))
+        .send_traced()
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|r| r.error_for_status().map(|_| ()).map_err(|e| e.to_string()));
+
+    if result.is_err() {
+        // Merge the unsent counts back so nothing is lost
+        let mut guard = PENDING.lock();
+        guard.period_start = guard.period_start.min(pending.period_start);
+        for (k, v) in pending.features {
+            *guard.features.entry(k).or_insert(0) += v;
+        }
+        for (k, v) in pending.errors {
+            *guard.errors.entry(k).or_insert(0) += v;
+        }
+    }
+
+    result
+}
+
+/// Opt in to or out of anonymized telemetry; opting out discards anything pending
+#[tauri::command]
+#[specta::specta]
+pub async fn set_telemetry_enabled(enabled: bool) -> Response<bool> {
+    envelope::respond("set_telemetry_enabled", async move {
+        {
+            let mut settings = SETTINGS.lock();
+            settings.enabled = enabled;
+            save_settings(&settings)?;
+        }
+        ENABLED.store(enabled, Ordering::Relaxed);
+
+        if !enabled {
+            *PENDING.lock() = PendingCounts::new();
+        }
+
+        info!("📊 Telemetry {}", if enabled { "enabled" } else { "disabled" });
+        Ok(enabled)
+    })
+    .await
+}
+
+/// Show exactly what the next telemetry upload would contain
+#[tauri::command]
+#[specta::specta]
+pub async fn get_telemetry_preview() -> Response<TelemetryPreview> {
+    envelope::respond("get_telemetry_preview", async move {
+        let pending_batch = build_batch(&PENDING.lock());
+        Ok(TelemetryPreview {
+            enabled: ENABLED.load(Ordering::Relaxed),
+            endpoint: endpoint(),
+            pending_batch,
+        })
+    })
+    .await
+}
@@ -0,0 +1,297 @@
+// Teleprompter mode
+// Moves the AI response window to a chosen display, usually the one next to the camera, as a
+// full-height column of large text that scrolls on its own, so an answer can be read while looking
+// near the lens instead of down at the main window. The window keeps its `ai-response` label, so
+// streaming and every other route to it work unchanged; it is rebuilt at the display's work area
+// because the normal window is sized and anchored to the main window. Scrolling runs in the page;
+// the backend owns speed, pause and font size and pushes them as `teleprompter-state`. Speed is
+// changed with commands or Ctrl+Shift+Up / Ctrl+Shift+Down. Speed, font size and display are kept in
+// %APPDATA%\MockMate\teleprompter.json.
+
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter, Manager, PhysicalPosition, PhysicalSize};
+
+use crate::ai_stream::RESPONSE_WINDOW_LABEL;
+use crate::envelope::{self, IntoResult, Response};
+use crate::error::{AppError, ErrorCode};
+use crate::events;
+
+/// Pixels per second
+const DEFAULT_SPEED: f32 = 40.0;
+const MAX_SPEED: f32 = 400.0;
+/// Speed change per hotkey press
+const SPEED_STEP: f32 = 10.0;
+const DEFAULT_FONT_SIZE: u32 = 40;
+const FONT_SIZE_RANGE: (u32, u32) = (16, 120);
+/// Share of the display's width the column takes, centered
+const WIDTH_SHARE: f64 = 0.5;
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+static STATE: Lazy<Mutex<TeleprompterState>> = Lazy::new(|| Mutex::new(load()));
+
+/// Payload of `teleprompter-state`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct TeleprompterState {
+    #[serde(default, skip_deserializing)]
+    pub active: bool,
+    #[serde(default, skip_deserializing)]
+    pub paused: bool,
+    /// Scroll speed in pixels per second
+    pub speed: f32,
+    pub font_size: u32,
+    /// Index into `list_displays`; `None` picks the first secondary display
+    pub display: Option<u32>,
+}
+
+impl Default for TeleprompterState {
+    fn default() -> Self {
+        Self { active: false, paused: false, speed: DEFAULT_SPEED, font_size: DEFAULT_FONT_SIZE, display: None }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct DisplayInfo {
+    pub index: u32,
+    pub name: String,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub scale_factor: f64,
+    pub is_primary: bool,
+}
+
+fn state_path() -> PathBuf {
+    crate::portable::data_root().join("MockMate").join("teleprompter.json")
+}
+
+fn load() -> TeleprompterState {
+    std::fs::read(state_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn save(state: &TeleprompterState) {
+    let path = state_path();
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| serde_json::to_vec_pretty(state).map_err(std::io::Error::other))
+        .and_then(|body| std::fs::write(&path, body));
+    if let Err(e) = result {
+        warn!("Failed to save teleprompter settings to {}: {}", path.display(), e);
+    }
+}
+
+/// Apply `change`, persist and push the new state to the page
+fn update(change: impl FnOnce(&mut TeleprompterState)) -> TeleprompterState {
+    let state = {
+        let mut state = STATE.lock();
+        change(&mut state);
+        save(&state);
+        state.clone()
+    };
+    if let Some(app_handle) = APP_HANDLE.get() {
+        if let Err(e) = app_handle.emit(events::TELEPROMPTER_STATE, &state) {
+            warn!("Failed to emit {}: {}", events::TELEPROMPTER_STATE, e);
+        }
+    }
+    state
+}
+
+fn displays(app_handle: &AppHandle) -> Result<Vec<(DisplayInfo, tauri::Monitor)>, AppError> {
+    let primary = app_handle.primary_monitor().map_err(|e| e.to_string())?;
+    let monitors = app_handle.available_monitors().map_err(|e| e.to_string())?;
+    Ok(monitors
+        .into_iter()
+        .enumerate()
+        .map(|(i, monitor)| {
+            let is_primary = primary
+                .as_ref()
+                .is_some_and(|p| p.position() == monitor.position() && p.size() == monitor.size());
+            let info = DisplayInfo {
+                index: i as u32,
+                name: monitor.name().cloned().unwrap_or_else(|| format!("Display {}", i + 1)),
+                x: monitor.position().x,
+                y: monitor.position().y,
+                width: monitor.size().width,
+                height: monitor.size().height,
+                scale_factor: monitor.scale_factor(),
+                is_primary,
+            };
+            (info, monitor)
+        })
+        .collect())
+}
+
+pub fn init(app_handle: AppHandle) {
+    let _ = APP_HANDLE.set(app_handle);
+}
+
+/// Called by the stealth hotkey loop for `teleprompter_faster` / `teleprompter_slower`
+pub fn on_hotkey(action: &str) {
+    if !STATE.lock().active {
+        return;
+    }
+    let step = match action {
+        "teleprompter_faster" => SPEED_STEP,
+        "teleprompter_slower" => -SPEED_STEP,
+        _ => return,
+    };
+    let state = update(|state| state.speed = (state.speed + step).clamp(0.0, MAX_SPEED));
+    info!("📜 Teleprompter speed {:.0} px/s", state.speed);
+}
+
+/// Displays the teleprompter can use, in `available_monitors` order
+#[tauri::command]
+#[specta::specta]
+pub fn list_displays(app_handle: AppHandle) -> Response<Vec<DisplayInfo>> {
+    envelope::respond_sync("list_displays", || Ok(displays(&app_handle)?.into_iter().map(|(info, _)| info).collect()))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_teleprompter_state() -> Response<TeleprompterState> {
+    envelope::respond_sync("get_teleprompter_state", || Ok(STATE.lock().clone()))
+}
+
+/// Show the AI response window as a teleprompter on `display` (default: first secondary display)
+#[tauri::command]
+#[specta::specta]
+pub async fn start_teleprompter(
+    app_handle: AppHandle,
+    display: Option<u32>,
+    font_size: Option<u32>,
+) -> Response<TeleprompterState> {
+    envelope::respond("start_teleprompter", async move {
+        if let Some(font_size) = font_size {
+            if !(FONT_SIZE_RANGE.0..=FONT_SIZE_RANGE.1).contains(&font_size) {
+                return Err(AppError::new(
+                    ErrorCode::InvalidInput,
+                    format!("Font size must be between {} and {}", FONT_SIZE_RANGE.0, FONT_SIZE_RANGE.1),
+                ));
+            }
+        }
+        let display = display.or(STATE.lock().display);
+        let all = displays(&app_handle)?;
+        let (info, monitor) = match display {
+            Some(index) => all
+                .into_iter()
+                .find(|(info, _)| info.index == index)
+                .ok_or_else(|| AppError::new(ErrorCode::NotFound, format!("Display {} is not connected", index)))?,
+            None => {
+                let mut all = all.into_iter();
+                let first = all.next().ok_or_else(|| AppError::new(ErrorCode::Unavailable, "No display found"))?;
+                if first.0.is_primary {
+                    all.find(|(info, _)| !info.is_primary).unwrap_or(first)
+                } else {
+                    first
+                }
+            }
+        };
+        if display.is_none() && info.is_primary {
+            warn!("📜 No secondary display, showing the teleprompter on the primary one");
+        }
+
+        // The normal window is size-capped and anchored to the main window; rebuild it for the display
+        if let Some(window) = app_handle.get_webview_window(RESPONSE_WINDOW_LABEL) {
+            window.destroy().map_err(|e| e.to_string())?;
+        }
+        let area = monitor.work_area();
+        let width = ((area.size.width as f64 * WIDTH_SHARE) as u32).max(400.min(area.size.width));
+        let x = area.position.x + (area.size.width - width) as i32 / 2;
+
+        let window_url = if cfg!(debug_assertions) {
+            tauri::WebviewUrl::External("http://localhost:1420/ai-response.html".parse().unwrap())
+        } else {
+            tauri::WebviewUrl::App("ai-response.html".into())
+        };
+        let style = crate::appearance::window_style();
+        let window = tauri::WebviewWindowBuilder::new(&app_handle, RESPONSE_WINDOW_LABEL, window_url)
+            .title("AI Response")
+            .resizable(true)
+            .always_on_top(true)
+            .skip_taskbar(true)
+            .visible(false)
+            .decorations(false)
+            .transparent(style.transparent)
+            .shadow(style.shadow)
+            .theme(Some(style.theme))
+            .build()
+            .map_err(|e| format!("Failed to create teleprompter window: {}", e))?;
+        window.set_position(PhysicalPosition::new(x, area.position.y)).map_err(|e| e.to_string())?;
+        window.set_size(PhysicalSize::new(width, area.size.height)).map_err(|e| e.to_string())?;
+        if let Err(e) = crate::set_window_capture_protection(&window, true).into_result() {
+            warn!("Failed to protect the teleprompter window from capture: {}", e);
+        }
+        window.show().map_err(|e| e.to_string())?;
+
+        info!("📜 Teleprompter on {} ({}x{})", info.name, width, area.size.height);
+        crate::telemetry::record_feature("teleprompter");
+        Ok(update(|state| {
+            state.active = true;
+            state.paused = false;
+            state.display = display;
+            if let Some(font_size) = font_size {
+                state.font_size = font_size;
+            }
+        }))
+    })
+    .await
+}
+
+/// Close the teleprompter; the next answer opens the normal AI response window again
+#[tauri::command]
+#[specta::specta]
+pub fn stop_teleprompter(app_handle: AppHandle) -> Response<TeleprompterState> {
+    envelope::respond_sync("stop_teleprompter", || {
+        let state = STATE.lock().clone();
+        if !state.active {
+            return Ok(state);
+        }
+        if let Some(window) = app_handle.get_webview_window(RESPONSE_WINDOW_LABEL) {
+            window.destroy().map_err(|e| e.to_string())?;
+        }
+        info!("📜 Teleprompter closed");
+        Ok(update(|state| {
+            state.active = false;
+            state.paused = false;
+        }))
+    })
+}
+
+/// Set the scroll speed in pixels per second, or change it by `delta`
+#[tauri::command]
+#[specta::specta]
+pub fn set_teleprompter_speed(speed: Option<f32>, delta: Option<f32>) -> Response<TeleprompterState> {
+    envelope::respond_sync("set_teleprompter_speed", || {
+        let current = STATE.lock().speed;
+        let speed = match (speed, delta) {
+            (Some(speed), _) => speed,
+            (None, Some(delta)) => current + delta,
+            (None, None) => return Err(AppError::new(ErrorCode::InvalidInput, "Pass a speed or a delta")),
+        };
+        if !speed.is_finite() {
+            return Err(AppError::new(ErrorCode::InvalidInput, "Speed must be a number"));
+        }
+        Ok(update(|state| state.speed = speed.clamp(0.0, MAX_SPEED)))
+    })
+}
+
+/// Pause or resume scrolling; toggles when `paused` is omitted
+#[tauri::command]
+#[specta::specta]
+pub fn set_teleprompter_paused(paused: Option<bool>) -> Response<TeleprompterState> {
+    envelope::respond_sync("set_teleprompter_paused", || {
+        Ok(update(|state| state.paused = paused.unwrap_or(!state.paused)))
+    })
+}
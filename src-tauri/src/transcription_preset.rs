@@ -0,0 +1,147 @@
+// Transcription latency/quality presets
+// One setting instead of tuning each knob: "lowest latency" sends small audio chunks and takes
+// Deepgram's results as early as possible, "highest accuracy" sends larger chunks, waits longer for
+// the end of an utterance, formats numbers and punctuation and prefers the largest local model,
+// and "balanced" sits in between. The preset drives the capture chunk size, the Deepgram endpointing,
+// interim result and formatting parameters, and which Vosk model the offline fallback loads. It is
+// stored in %APPDATA%\MockMate\transcription_preset.json; a running stream reconnects to apply it.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use log::info;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::AppHandle;
+
+use crate::envelope::{self, Response};
+
+/// Samples per captured audio chunk, read for every sample on the capture threads
+static CHUNK_SAMPLES: AtomicUsize = AtomicUsize::new(2048);
+static PRESET: Lazy<Mutex<TranscriptionPreset>> = Lazy::new(|| Mutex::new(load_preset()));
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscriptionPreset {
+    LowestLatency,
+    #[default]
+    Balanced,
+    HighestAccuracy,
+}
+
+/// What a preset sets
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct TranscriptionTuning {
+    pub preset: TranscriptionPreset,
+    /// Samples per audio chunk sent for transcription (44.1kHz)
+    pub chunk_samples: usize,
+    /// Silence in milliseconds after which Deepgram finalizes a segment
+    pub endpointing_ms: u32,
+    /// Stream partial transcripts while the interviewer is speaking
+    pub interim_results: bool,
+    /// Gap in milliseconds after which Deepgram reports the end of an utterance
+    pub utterance_end_ms: Option<u32>,
+    /// Deepgram formatting of numbers, dates and punctuation
+    pub smart_format: bool,
+    /// Folder names under the Vosk installation, in order of preference
+    pub local_models: Vec<String>,
+}
+
+impl TranscriptionPreset {
+    pub fn tuning(self) -> TranscriptionTuning {
+        let models = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+        match self {
+            TranscriptionPreset::LowestLatency => TranscriptionTuning {
+                preset: self,
+                chunk_samples: 1024,
+                endpointing_ms: 10,
+                interim_results: true,
+                utterance_end_ms: None,
+                smart_format: false,
+                local_models: models(&["model-small", "model", "model-large"]),
+            },
+            TranscriptionPreset::Balanced => TranscriptionTuning {
+                preset: self,
+                chunk_samples: 2048,
+                endpointing_ms: 300,
+                interim_results: true,
+                utterance_end_ms: Some(1000),
+                smart_format: true,
+                local_models: models(&["model", "model-small", "model-large"]),
+            },
+            TranscriptionPreset::HighestAccuracy => TranscriptionTuning {
+                preset: self,
+                chunk_samples: 4096,
+                endpointing_ms: 800,
+                interim_results: false,
+                utterance_end_ms: None,
+                smart_format: true,
+                local_models: models(&["model-large", "model", "model-small"]),
+            },
+        }
+    }
+}
+
+fn preset_path() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("transcription_preset.json")
+}
+
+fn load_preset() -> TranscriptionPreset {
+    std::fs::read(preset_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn save_preset(preset: TranscriptionPreset) -> Result<(), String> {
+    let path = preset_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(&preset).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| e.to_string())
+}
+
+/// The tuning of the current preset
+pub fn current() -> TranscriptionTuning {
+    PRESET.lock().tuning()
+}
+
+/// Samples per captured chunk for the current preset
+pub fn chunk_samples() -> usize {
+    CHUNK_SAMPLES.load(Ordering::Relaxed)
+}
+
+/// Apply the saved preset to capture
+pub fn init() {
+    CHUNK_SAMPLES.store(current().chunk_samples, Ordering::Relaxed);
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_transcription_preset() -> Response<TranscriptionTuning> {
+    envelope::respond("get_transcription_preset", async move {
+        Ok(current())
+    })
+    .await
+}
+
+/// Switch presets; a running Deepgram stream reconnects with the new parameters
+#[tauri::command]
+#[specta::specta]
+pub async fn set_transcription_preset(app_handle: AppHandle, preset: TranscriptionPreset) -> Response<TranscriptionTuning> {
+    envelope::respond("set_transcription_preset", async move {
+        save_preset(preset)?;
+        *PRESET.lock() = preset;
+        let tuning = preset.tuning();
+        CHUNK_SAMPLES.store(tuning.chunk_samples, Ordering::Relaxed);
+        info!("🎚️ Transcription preset: {:?}", preset);
+        crate::deepgram_streaming::restart_if_streaming(&app_handle).await;
+        Ok(tuning)
+    })
+    .await
+}
@@ -0,0 +1,360 @@
+// Auto-update with backend-driven release channels
+// The backend publishes the latest release per channel (stable/beta) with a rollout percentage.
+// Each install has a fixed rollout bucket, so a staged release reaches the same installs as the
+// percentage grows. Installers are downloaded in the background, verified against the Ed25519 key
+// embedded at build time, and announced with `update-ready`; install_update runs the verified
+// installer and exits.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use base64::Engine;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter};
+
+use crate::net_trace::TracedSend;
+use crate::envelope::{self, Response};
+use crate::events;
+
+/// Delay before the startup check so it doesn't compete with session setup
+const STARTUP_CHECK_DELAY: Duration = Duration::from_secs(30);
+/// Background checks while the app stays open
+const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+static SETTINGS: Lazy<Mutex<UpdaterSettings>> = Lazy::new(|| Mutex::new(load_settings()));
+/// Latest release offered by the backend for this install
+static AVAILABLE: Lazy<Mutex<Option<ReleaseManifest>>> = Lazy::new(|| Mutex::new(None));
+/// Installer that has been downloaded and verified, ready for install_update
+static STAGED: Lazy<Mutex<Option<StagedUpdate>>> = Lazy::new(|| Mutex::new(None));
+/// Held while an installer is downloading so background and manual checks don't race
+static DOWNLOAD_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    Stable,
+    Beta,
+}
+
+impl UpdateChannel {
+    fn as_str(&self) -> &'static str {
+        match self {
+            UpdateChannel::Stable => "stable",
+            UpdateChannel::Beta => "beta",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct UpdaterSettings {
+    channel: UpdateChannel,
+    /// Fixed per install, 0..100; the install gets a staged release once rollout_percentage exceeds it
+    rollout_bucket: u8,
+}
+
+/// Release description served by the backend for a channel
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ReleaseManifest {
+    pub version: String,
+    pub channel: UpdateChannel,
+    pub notes: Option<String>,
+    pub pub_date: Option<String>,
+    /// Installer download URL (.msi or NSIS .exe)
+    pub url: String,
+    /// Base64 Ed25519 signature of the installer bytes
+    pub signature: String,
+    /// Share of installs (0-100) the release is currently offered to
+    #[serde(default = "full_rollout")]
+    pub rollout_percentage: u8,
+    /// Installs older than this get the release regardless of the rollout
+    pub min_supported_version: Option<String>,
+}
+
+fn full_rollout() -> u8 {
+    100
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct UpdateCheck {
+    pub current_version: String,
+    pub channel: UpdateChannel,
+    /// Newer release offered to this install, if any
+    pub update: Option<ReleaseManifest>,
+    /// The current version is below the release's minimum supported version
+    pub mandatory: bool,
+    /// The verified installer is already downloaded
+    pub ready: bool,
+}
+
+#[derive(Debug, Clone)]
+struct StagedUpdate {
+    version: String,
+    path: PathBuf,
+}
+
+/// Payload of `update-ready`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct UpdateReady {
+    pub version: String,
+    pub notes: Option<String>,
+    pub mandatory: bool,
+}
+
+fn settings_path() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("updater.json")
+}
+
+fn download_dir() -> PathBuf {
+    std::env::temp_dir().join("MockMate").join("updates")
+}
+
+fn load_settings() -> UpdaterSettings {
+    std::fs::read(settings_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice::<UpdaterSettings>(&body).ok())
+        .unwrap_or_else(|| {
+            let settings = UpdaterSettings {
+                channel: UpdateChannel::Stable,
+                rollout_bucket: rand::random::<u8>() % 100,
+            };
+            // Persist right away so the bucket stays the same across restarts
+            if let Err(e) = save_settings(&settings) {
+                warn!("Failed to save updater settings: {}", e);
+            }
+            settings
+        })
+}
+
+fn save_settings(settings: &UpdaterSettings) -> Result<(), String> {
+    let path = settings_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(settings).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| e.to_string())
+}
+
+fn backend_url() -> String {
+    std::env::var("MOCKMATE_BACKEND_URL").unwrap_or_else(|_| "https://mockmate-backend.onrender.com".to_string())
+}
+
+fn platform() -> String {
+    format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH)
+}
+
+fn current_version() -> semver::Version {
+    semver::Version::parse(env!("CARGO_PKG_VERSION")).expect("CARGO_PKG_VERSION is valid semver")
+}
+
+/// Ed25519 key embedded at build time; never read at runtime so it can't be swapped
+fn public_key() -> Result<Vec<u8>, String> {
+    let encoded = option_env!("MOCKMATE_UPDATER_PUBKEY")
+        .filter(|key| !key.is_empty())
+        .ok_or("This build has no update signing key; updates must be installed manually")?;
+    base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .map_err(|e| format!("Invalid update signing key: {}", e))
+}
+
+fn verify_signature(installer: &[u8], signature: &str) -> Result<(), String> {
+    let key = public_key()?;
+    let signature = base64::engine::general_purpose::STANDARD
+        .decode(signature.trim())
+        .map_err(|e| format!("Invalid update signature encoding: {}", e))?;
+    ring::signature::UnparsedPublicKey::new(&ring::signature::ED25519, key)
+        .verify(installer, &signature)
+        .map_err(|_| "Update signature verification failed".to_string())
+}
+
+fn is_mandatory(release: &ReleaseManifest) -> bool {
+    release
+        .min_supported_version
+        .as_deref()
+        .and_then(|v| semver::Version::parse(v).ok())
+        .is_some_and(|min| current_version() < min)
+}
+
+/// Whether the release is newer than this build and offered to this install
+fn is_offered(release: &ReleaseManifest, rollout_bucket: u8) -> bool {
+    let Ok(version) = semver::Version::parse(&release.version) else {
+        warn!("Ignoring release with invalid version '{}'", release.version);
+        return false;
+    };
+    version > current_version() && (rollout_bucket < release.rollout_percentage || is_mandatory(release))
+}
+
+async fn fetch_latest(channel: UpdateChannel) -> Result<Option<ReleaseManifest>, String> {
+    crate::privacy::ensure_cloud_allowed("Update check")?;
+
+    let response = reqwest::Client::new()
+        .get(format!("{}/api/desktop/releases/{}/latest", backend_url(), channel.as_str()))
+        .query(&[("platform", platform()), ("current_version", env!("CARGO_PKG_VERSION").to_string())])
+        .timeout(Duration::from_secs(15))
+        .send_traced()
+        .await
+        .map_err(|e| format!("Update check failed: {}", e))?;
+
+    if response.status() == reqwest::StatusCode::NO_CONTENT || response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        return Err(format!("Update check failed: {}", response.status()));
+    }
+    response
+        .json::<ReleaseManifest>()
+        .await
+        .map(Some)
+        .map_err(|e| format!("Invalid release manifest: {}", e))
+}
+
+async fn check(channel: UpdateChannel) -> Result<UpdateCheck, String> {
+    let rollout_bucket = SETTINGS.lock().rollout_bucket;
+    let update = fetch_latest(channel)
+        .await?
+        .filter(|release| is_offered(release, rollout_bucket));
+
+    *AVAILABLE.lock() = update.clone();
+    let ready = match (&update, STAGED.lock().as_ref()) {
+        (Some(release), Some(staged)) => staged.version == release.version,
+        _ => false,
+    };
+    Ok(UpdateCheck {
+        current_version: env!("CARGO_PKG_VERSION").to_string(),
+        channel,
+        mandatory: update.as_ref().is_some_and(is_mandatory),
+        update,
+        ready,
+    })
+}
+
+/// Download the installer and keep it only if its signature verifies
+async fn download(release: &ReleaseManifest) -> Result<PathBuf, String> {
+    let _download = DOWNLOAD_LOCK.lock().await;
+    if let Some(staged) = STAGED.lock().as_ref() {
+        if staged.version == release.version {
+            return Ok(staged.path.clone());
+        }
+    }
+    crate::privacy::ensure_cloud_allowed("Update download")?;
+
+    info!("⬇️ Downloading MockMate {} ({})", release.version, release.channel.as_str());
+    let response = reqwest::Client::new()
+        .get(&release.url)
+        .timeout(Duration::from_secs(10 * 60))
+        .send_traced()
+        .await
+        .map_err(|e| format!("Update download failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Update download failed: {}", response.status()));
+    }
+    let installer = response.bytes().await.map_err(|e| format!("Update download failed: {}", e))?;
+
+    verify_signature(&installer, &release.signature)?;
+
+    let extension = if release.url.to_lowercase().ends_with(".msi") { "msi" } else { "exe" };
+    let dir = download_dir();
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let path = dir.join(format!("MockMate_{}_setup.{}", release.version, extension));
+    std::fs::write(&path, &installer).map_err(|e| e.to_string())?;
+
+    info!("✅ Update {} verified and staged at {}", release.version, path.display());
+    *STAGED.lock() = Some(StagedUpdate {
+        version: release.version.clone(),
+        path: path.clone(),
+    });
+    Ok(path)
+}
+
+/// Check, download and verify in the background, then emit `update-ready`
+async fn check_and_stage(app_handle: &AppHandle) -> Result<(), String> {
+    let channel = SETTINGS.lock().channel;
+    let result = check(channel).await?;
+    let Some(release) = result.update else {
+        return Ok(());
+    };
+    download(&release).await?;
+
+    let ready = UpdateReady {
+        version: release.version.clone(),
+        notes: release.notes.clone(),
+        mandatory: result.mandatory,
+    };
+    app_handle.emit(events::UPDATE_READY, &ready).map_err(|e| e.to_string())
+}
+
+/// Periodically look for updates while the app runs
+pub fn start_background_checks(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(STARTUP_CHECK_DELAY).await;
+        loop {
+            // Local-only mode just skips the check; it runs again next interval
+            if !crate::privacy::is_local_only() {
+                if let Err(e) = check_and_stage(&app_handle).await {
+                    warn!("Background update check failed: {}", e);
+                }
+            }
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+/// Ask the backend for a newer release; passing a channel switches to it for future checks
+#[tauri::command]
+#[specta::specta]
+pub async fn check_for_updates(channel: Option<UpdateChannel>) -> Response<UpdateCheck> {
+    envelope::respond("check_for_updates", async move {
+        let channel = match channel {
+            Some(channel) => {
+                let mut settings = SETTINGS.lock();
+                if settings.channel != channel {
+                    settings.channel = channel;
+                    save_settings(&settings)?;
+                    info!("🔀 Update channel set to {}", channel.as_str());
+                }
+                channel
+            }
+            None => SETTINGS.lock().channel,
+        };
+        Ok(check(channel).await?)
+    })
+    .await
+}
+
+/// Download (if not already staged) and verify the offered update, run its installer and exit
+#[tauri::command]
+#[specta::specta]
+pub async fn install_update(app_handle: AppHandle) -> Response<String> {
+    envelope::respond("install_update", async move {
+        // The installer writes to the registry; portable users replace the executable themselves
+        crate::portable::ensure_installed("Installing updates")?;
+        let available = AVAILABLE.lock().clone();
+        let release = match available {
+            Some(release) => release,
+            None => {
+                let channel = SETTINGS.lock().channel;
+                check(channel).await?.update.ok_or("MockMate is up to date")?
+            }
+        };
+        let path = download(&release).await?;
+
+        info!("🚀 Installing MockMate {} from {}", release.version, path.display());
+        let spawned = if path.extension().is_some_and(|ext| ext == "msi") {
+            std::process::Command::new("msiexec").arg("/i").arg(&path).arg("/passive").spawn()
+        } else {
+            // Tauri NSIS installer: /P shows passive progress only, /R relaunches MockMate when done
+            std::process::Command::new(&path).arg("/P").arg("/R").spawn()
+        };
+        spawned.map_err(|e| format!("Failed to start installer: {}", e))?;
+
+        app_handle.exit(0);
+        Ok(format!("Installing MockMate {}", release.version))
+    })
+    .await
+}
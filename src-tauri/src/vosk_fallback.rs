@@ -0,0 +1,399 @@
+// Offline Vosk transcription fallback
+// If Deepgram drops mid-interview (network loss, outage) or cannot be reached when transcription
+// starts, system audio is transcribed locally with Vosk instead of stopping, and
+// `transcription-fallback` tells the UI accuracy is reduced. Results go out as ordinary
+// `transcription-result` events and feed the same transcript consumers as Deepgram. Every
+// RECOVERY_INTERVAL the backend tries Deepgram again and switches back when it connects.
+// Vosk is loaded at runtime, so nothing is linked at build time: the fallback is available when
+// the Vosk library (libvosk.dll / libvosk.so / libvosk.dylib) and a model folder are installed in
+// a `vosk` folder beside the executable or in %APPDATA%\MockMate\vosk, e.g.
+//   vosk\libvosk.dll
+//   vosk\model\            (a small model such as vosk-model-small-en-us)
+// The transcription preset may prefer `model-small` or `model-large` folders when they exist.
+
+use std::ffi::{c_char, c_int, c_void, CStr, CString};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter};
+
+use crate::audio_buffer::AudioSource;
+use crate::deepgram_streaming::DeepgramTranscriptionResult;
+use crate::envelope::{self, Response};
+use crate::events;
+
+/// How often Deepgram is retried while the fallback runs
+const RECOVERY_INTERVAL: Duration = Duration::from_secs(30);
+
+#[cfg(windows)]
+const LIBRARY_NAME: &str = "libvosk.dll";
+#[cfg(target_os = "macos")]
+const LIBRARY_NAME: &str = "libvosk.dylib";
+#[cfg(all(unix, not(target_os = "macos")))]
+const LIBRARY_NAME: &str = "libvosk.so";
+
+/// Kept between fallbacks since model loading takes a second or more; replaced when the preset prefers another model
+static ENGINE: Lazy<Mutex<Option<Arc<Engine>>>> = Lazy::new(|| Mutex::new(None));
+static ACTIVE: Lazy<Mutex<Option<ActiveFallback>>> = Lazy::new(|| Mutex::new(None));
+
+type ModelNew = unsafe extern "C" fn(*const c_char) -> *mut c_void;
+type ModelFree = unsafe extern "C" fn(*mut c_void);
+type RecognizerNew = unsafe extern "C" fn(*mut c_void, f32) -> *mut c_void;
+type RecognizerFree = unsafe extern "C" fn(*mut c_void);
+type AcceptWaveform = unsafe extern "C" fn(*mut c_void, *const i16, c_int) -> c_int;
+type RecognizerResult = unsafe extern "C" fn(*mut c_void) -> *const c_char;
+type SetLogLevel = unsafe extern "C" fn(c_int);
+
+/// The Vosk library and a loaded model
+struct Engine {
+    model: *mut c_void,
+    model_path: PathBuf,
+    model_free: ModelFree,
+    recognizer_new: RecognizerNew,
+    recognizer_free: RecognizerFree,
+    accept_waveform: AcceptWaveform,
+    result: RecognizerResult,
+    partial_result: RecognizerResult,
+    final_result: RecognizerResult,
+    /// Keeps the function pointers above valid
+    _library: libloading::Library,
+}
+
+// SAFETY: a Vosk model is immutable once loaded and may be shared by recognizers on any thread
+unsafe impl Send for Engine {}
+unsafe impl Sync for Engine {}
+
+impl Drop for Engine {
+    fn drop(&mut self) {
+        unsafe { (self.model_free)(self.model) };
+    }
+}
+
+/// One recognizer, used from the fallback thread only
+struct Recognizer {
+    engine: Arc<Engine>,
+    handle: *mut c_void,
+    sample_rate: u32,
+}
+
+impl Recognizer {
+    fn new(engine: Arc<Engine>, sample_rate: u32) -> Result<Self, String> {
+        let handle = unsafe { (engine.recognizer_new)(engine.model, sample_rate as f32) };
+        if handle.is_null() {
+            return Err(format!("Vosk could not create a recognizer at {} Hz", sample_rate));
+        }
+        Ok(Self { engine, handle, sample_rate })
+    }
+
+    /// Feed audio; returns the finished utterance when Vosk detects its end
+    fn accept(&mut self, samples: &[i16]) -> Option<String> {
+        let done = unsafe { (self.engine.accept_waveform)(self.handle, samples.as_ptr(), samples.len() as c_int) };
+        if done == 1 {
+            self.text(self.engine.result, "text")
+        } else {
+            None
+        }
+    }
+
+    fn partial(&self) -> Option<String> {
+        self.text(self.engine.partial_result, "partial")
+    }
+
+    fn finish(&self) -> Option<String> {
+        self.text(self.engine.final_result, "text")
+    }
+
+    /// Vosk returns JSON like {"text": "..."}; the string belongs to the recognizer
+    fn text(&self, result: RecognizerResult, field: &str) -> Option<String> {
+        let json = unsafe { CStr::from_ptr(result(self.handle)) }.to_string_lossy().to_string();
+        let value: serde_json::Value = serde_json::from_str(&json).ok()?;
+        let text = value.get(field)?.as_str()?.trim();
+        (!text.is_empty()).then(|| text.to_string())
+    }
+}
+
+impl Drop for Recognizer {
+    fn drop(&mut self) {
+        unsafe { (self.engine.recognizer_free)(self.handle) };
+    }
+}
+
+struct ActiveFallback {
+    stop: Arc<AtomicBool>,
+    reason: String,
+    since: chrono::DateTime<Utc>,
+}
+
+/// Payload of `transcription-fallback`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct TranscriptionFallback {
+    /// True while Vosk is transcribing instead of Deepgram
+    pub active: bool,
+    pub engine: String,
+    /// Why the fallback started, or why it ended
+    pub reason: String,
+    /// Local transcripts are noticeably less accurate than Deepgram's
+    pub reduced_accuracy: bool,
+    /// Unix milliseconds
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct OfflineTranscriptionStatus {
+    /// The Vosk library and a model are installed
+    pub available: bool,
+    pub active: bool,
+    pub library_path: Option<String>,
+    pub model_path: Option<String>,
+    pub reason: Option<String>,
+    pub since: Option<String>,
+}
+
+fn search_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(exe_dir) = std::env::current_exe().ok().and_then(|exe| exe.parent().map(PathBuf::from)) {
+        dirs.push(exe_dir.join("vosk"));
+    }
+    dirs.push(crate::portable::data_root().join("MockMate").join("vosk"));
+    dirs
+}
+
+/// Library and model folder of the first complete installation; the transcription preset decides
+/// which model folder is preferred when several are installed
+fn installation() -> Option<(PathBuf, PathBuf)> {
+    let models = crate::transcription_preset::current().local_models;
+    search_dirs().into_iter().find_map(|dir| {
+        let library = dir.join(LIBRARY_NAME);
+        let model = models.iter().map(|name| dir.join(name)).find(|model| model.is_dir())?;
+        library.is_file().then_some((library, model))
+    })
+}
+
+/// Whether the fallback can run on this machine
+pub fn available() -> bool {
+    installation().is_some()
+}
+
+pub fn is_active() -> bool {
+    ACTIVE.lock().is_some()
+}
+
+/// A function exported by the Vosk library; `T` must be its exact signature
+unsafe fn symbol<T: Copy>(library: &libloading::Library, name: &str) -> Result<T, String> {
+    library
+        .get::<T>(name.as_bytes())
+        .map(|symbol| *symbol)
+        .map_err(|e| format!("Vosk library is missing {}: {}", name, e))
+}
+
+fn load_engine() -> Result<Arc<Engine>, String> {
+    let (library_path, model_path) = installation().ok_or("Vosk is not installed")?;
+    if let Some(engine) = ENGINE.lock().as_ref().filter(|engine| engine.model_path == model_path) {
+        return Ok(engine.clone());
+    }
+    let engine = unsafe {
+        let library = libloading::Library::new(&library_path)
+            .map_err(|e| format!("Failed to load {}: {}", library_path.display(), e))?;
+        let set_log_level: SetLogLevel = symbol(&library, "vosk_set_log_level")?;
+        let model_new: ModelNew = symbol(&library, "vosk_model_new")?;
+        let model_free: ModelFree = symbol(&library, "vosk_model_free")?;
+        let recognizer_new: RecognizerNew = symbol(&library, "vosk_recognizer_new")?;
+        let recognizer_free: RecognizerFree = symbol(&library, "vosk_recognizer_free")?;
+        let accept_waveform: AcceptWaveform = symbol(&library, "vosk_recognizer_accept_waveform_s")?;
+        let result: RecognizerResult = symbol(&library, "vosk_recognizer_result")?;
+        let partial_result: RecognizerResult = symbol(&library, "vosk_recognizer_partial_result")?;
+        let final_result: RecognizerResult = symbol(&library, "vosk_recognizer_final_result")?;
+
+        // Kaldi logs every decoding step to stderr by default
+        set_log_level(-1);
+        let path = CString::new(model_path.to_string_lossy().as_bytes()).map_err(|e| e.to_string())?;
+        let model = model_new(path.as_ptr());
+        if model.is_null() {
+            return Err(format!("Vosk could not load the model in {}", model_path.display()));
+        }
+        Engine {
+            model,
+            model_path: model_path.clone(),
+            model_free,
+            recognizer_new,
+            recognizer_free,
+            accept_waveform,
+            result,
+            partial_result,
+            final_result,
+            _library: library,
+        }
+    };
+    info!("🧩 Vosk model loaded from {}", model_path.display());
+    let engine = Arc::new(engine);
+    *ENGINE.lock() = Some(engine.clone());
+    Ok(engine)
+}
+
+fn emit_result(app_handle: &AppHandle, text: String, is_final: bool, duration_seconds: f64) {
+    let result = DeepgramTranscriptionResult {
+        text,
+        is_final,
+        // Vosk only reports confidence per word; treat local results as low confidence
+        confidence: 0.5,
+        timestamp: Utc::now().to_rfc3339(),
+    };
+    let _ = app_handle.emit(events::TRANSCRIPTION_RESULT, &result);
+    if is_final {
+        crate::deepgram_streaming::on_final_transcript(&result, duration_seconds);
+    }
+}
+
+/// Transcribe system audio until `stop` is set
+fn run(app_handle: AppHandle, engine: Arc<Engine>, stop: Arc<AtomicBool>) {
+    let mut audio_rx = crate::audio_buffer::subscribe();
+    let mut recognizer: Option<Recognizer> = None;
+    let mut last_partial = String::new();
+    let mut utterance_samples = 0usize;
+
+    while !stop.load(Ordering::Relaxed) {
+        let chunk = match audio_rx.try_recv() {
+            Ok(chunk) => chunk,
+            Err(tokio::sync::broadcast::error::TryRecvError::Empty) => {
+                std::thread::sleep(Duration::from_millis(20));
+                continue;
+            }
+            Err(tokio::sync::broadcast::error::TryRecvError::Lagged(skipped)) => {
+                warn!("Vosk fallback fell behind, dropped {} chunks", skipped);
+                continue;
+            }
+            Err(tokio::sync::broadcast::error::TryRecvError::Closed) => break,
+        };
+        // The interviewer is on system audio; mixing in the microphone would garble both
+        if chunk.source != AudioSource::System {
+            continue;
+        }
+        if recognizer.as_ref().is_none_or(|r| r.sample_rate != chunk.sample_rate) {
+            match Recognizer::new(engine.clone(), chunk.sample_rate) {
+                Ok(new) => recognizer = Some(new),
+                Err(e) => {
+                    warn!("{}", e);
+                    break;
+                }
+            }
+        }
+        let Some(active) = recognizer.as_mut() else { continue };
+
+        let samples: Vec<i16> = chunk.samples.iter().map(|s| (s.clamp(-1.0, 1.0) * i16::MAX as f32) as i16).collect();
+        utterance_samples += samples.len();
+        let duration_seconds = utterance_samples as f64 / chunk.sample_rate as f64;
+        if let Some(text) = active.accept(&samples) {
+            emit_result(&app_handle, text, true, duration_seconds);
+            last_partial.clear();
+            utterance_samples = 0;
+        } else if let Some(partial) = active.partial().filter(|partial| *partial != last_partial) {
+            last_partial = partial.clone();
+            emit_result(&app_handle, partial, false, duration_seconds);
+        }
+    }
+
+    if let Some(text) = recognizer.as_ref().and_then(Recognizer::finish) {
+        let duration_seconds = recognizer.as_ref().map_or(0.0, |r| utterance_samples as f64 / r.sample_rate as f64);
+        emit_result(&app_handle, text, true, duration_seconds);
+    }
+    info!("🧩 Vosk fallback stopped");
+}
+
+fn emit_state(app_handle: &AppHandle, active: bool, reason: &str) {
+    let _ = app_handle.emit(
+        events::TRANSCRIPTION_FALLBACK,
+        TranscriptionFallback {
+            active,
+            engine: "vosk".to_string(),
+            reason: reason.to_string(),
+            reduced_accuracy: active,
+            timestamp: Utc::now().timestamp_millis(),
+        },
+    );
+}
+
+/// Switch to local transcription because Deepgram is unavailable; false when Vosk isn't installed
+pub fn engage(app_handle: &AppHandle, reason: &str) -> bool {
+    if is_active() {
+        return true;
+    }
+    if !available() {
+        warn!("Deepgram unavailable ({}) and no offline fallback is installed", reason);
+        return false;
+    }
+
+    let stop = Arc::new(AtomicBool::new(false));
+    *ACTIVE.lock() = Some(ActiveFallback { stop: stop.clone(), reason: reason.to_string(), since: Utc::now() });
+    warn!("🧩 Deepgram unavailable ({}); transcribing locally with Vosk", reason);
+
+    let thread_handle = app_handle.clone();
+    let thread_stop = stop.clone();
+    let spawned = std::thread::Builder::new().name("vosk-fallback".to_string()).spawn(move || {
+        match load_engine() {
+            Ok(engine) => run(thread_handle.clone(), engine, thread_stop),
+            Err(e) => {
+                warn!("Offline transcription fallback failed: {}", e);
+                disengage(&thread_handle, &e);
+            }
+        }
+    });
+    if let Err(e) = spawned {
+        *ACTIVE.lock() = None;
+        warn!("Failed to start the Vosk fallback thread: {}", e);
+        return false;
+    }
+    emit_state(app_handle, true, reason);
+
+    // Go back to Deepgram as soon as it can be reached again
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(RECOVERY_INTERVAL).await;
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+            if crate::deepgram_streaming::reconnect(&app_handle).await {
+                disengage(&app_handle, "Deepgram reconnected");
+                break;
+            }
+        }
+    });
+    true
+}
+
+/// Stop local transcription, e.g. because Deepgram is back or transcription was stopped
+pub fn disengage(app_handle: &AppHandle, reason: &str) {
+    let Some(active) = ACTIVE.lock().take() else {
+        return;
+    };
+    active.stop.store(true, Ordering::Relaxed);
+    info!("🧩 Leaving offline transcription fallback: {}", reason);
+    emit_state(app_handle, false, reason);
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_offline_transcription_status() -> Response<OfflineTranscriptionStatus> {
+    envelope::respond("get_offline_transcription_status", async move {
+        let installed = installation();
+        let active = ACTIVE.lock();
+        Ok(OfflineTranscriptionStatus {
+            available: installed.is_some(),
+            active: active.is_some(),
+            library_path: installed.as_ref().map(|(library, _)| library.to_string_lossy().to_string()),
+            model_path: installed.as_ref().map(|(_, model)| model.to_string_lossy().to_string()),
+            reason: active.as_ref().map(|a| a.reason.clone()),
+            since: active.as_ref().map(|a| a.since.to_rfc3339()),
+        })
+    })
+    .await
+}
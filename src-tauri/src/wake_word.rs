@@ -0,0 +1,628 @@
+// Wake word ("Hey MockMate")
+// A lightweight keyword spotter that runs on its own microphone stream, so saying the wake phrase
+// can show the AI window or answer the last question while both hands are on the keyboard of a
+// shared screen. There is no bundled model: the user records a few samples of the phrase, each
+// stored as a sequence of MFCC frames, and the live microphone is compared against them with
+// dynamic time warping (the template approach rustpotter uses). It only runs while enabled, costs a
+// few milliseconds of CPU per 50ms of audio, and nothing leaves the machine. Settings and samples
+// live in %APPDATA%\MockMate\wake_word.json; each detection emits `wake-word-detected`.
+
+use std::f32::consts::PI;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use futures_util::StreamExt;
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::cancellation::{self, Progress};
+use crate::envelope::{self, Response};
+use crate::error::{AppError, ErrorCode};
+use crate::events;
+use crate::guard::{self, Require};
+use crate::permissions::Permission;
+use crate::pluely_microphone::PluelyMicrophoneInput;
+
+/// Audio is averaged down to roughly this rate before feature extraction
+const TARGET_RATE: u32 = 16000;
+const FRAME_SECS: f32 = 0.025;
+const HOP_SECS: f32 = 0.010;
+const MEL_BANDS: usize = 20;
+/// Cepstral coefficients kept per frame (c1..c12; c0 is loudness)
+const COEFFS: usize = 12;
+/// Frames between two comparisons against the templates (50ms)
+const CHECK_EVERY: usize = 5;
+/// Frames quieter than this RMS are treated as silence
+const SPEECH_RMS: f32 = 0.01;
+const COOLDOWN: Duration = Duration::from_secs(2);
+
+const SAMPLE_SECS: f32 = 2.5;
+/// Shortest usable phrase in a recorded sample
+const MIN_SAMPLE_FRAMES: usize = 30;
+const MAX_SAMPLES: usize = 5;
+const DEFAULT_PHRASE: &str = "Hey MockMate";
+const DEFAULT_SENSITIVITY: f32 = 0.5;
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+static SETTINGS: Lazy<Mutex<WakeWordSettings>> = Lazy::new(|| Mutex::new(load()));
+/// Bumped to stop the running listener; a listener exits once its generation is stale
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+static LISTENING: Lazy<Mutex<bool>> = Lazy::new(|| Mutex::new(false));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum WakeAction {
+    ShowAiWindow,
+    /// Same as the AI answer hotkey (Ctrl+Shift+Z)
+    AnswerLastQuestion,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WakeWordSettings {
+    enabled: bool,
+    phrase: String,
+    sensitivity: f32,
+    actions: Vec<WakeAction>,
+    /// One MFCC frame sequence per recorded sample
+    templates: Vec<Vec<[f32; COEFFS]>>,
+}
+
+impl Default for WakeWordSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            phrase: DEFAULT_PHRASE.to_string(),
+            sensitivity: DEFAULT_SENSITIVITY,
+            actions: vec![WakeAction::ShowAiWindow, WakeAction::AnswerLastQuestion],
+            templates: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct WakeWordStatus {
+    pub enabled: bool,
+    /// The spotter is running on the microphone right now
+    pub listening: bool,
+    /// Only shown to the user; detection matches the recorded samples
+    pub phrase: String,
+    /// 0-1; higher triggers more easily
+    pub sensitivity: f32,
+    pub actions: Vec<WakeAction>,
+    pub samples: usize,
+}
+
+/// Payload of `wake-word-detected`
+#[derive(Debug, Clone, Serialize, JsonSchema, Type)]
+pub struct WakeWordDetected {
+    pub phrase: String,
+    /// 0-1 similarity to the closest sample
+    pub score: f32,
+    pub actions: Vec<WakeAction>,
+}
+
+fn settings_path() -> PathBuf {
+    crate::portable::data_root().join("MockMate").join("wake_word.json")
+}
+
+fn load() -> WakeWordSettings {
+    std::fs::read(settings_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn save(settings: &WakeWordSettings) -> Result<(), String> {
+    let path = settings_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(settings).map_err(|e| e.to_string())?;
+    std::fs::write(path, body).map_err(|e| e.to_string())
+}
+
+fn status() -> WakeWordStatus {
+    let settings = SETTINGS.lock();
+    WakeWordStatus {
+        enabled: settings.enabled,
+        listening: *LISTENING.lock(),
+        phrase: settings.phrase.clone(),
+        sensitivity: settings.sensitivity,
+        actions: settings.actions.clone(),
+        samples: settings.templates.len(),
+    }
+}
+
+/// Lowest similarity that counts as the wake phrase
+fn threshold(sensitivity: f32) -> f32 {
+    0.85 - 0.2 * sensitivity.clamp(0.0, 1.0)
+}
+
+#[derive(Clone)]
+struct Frame {
+    mfcc: [f32; COEFFS],
+    rms: f32,
+}
+
+/// Turns raw samples at the capture rate into MFCC frames
+struct FeatureExtractor {
+    decimation: usize,
+    decimation_acc: f32,
+    decimation_count: usize,
+    /// Decimated samples not yet consumed by a full hop
+    pending: Vec<f32>,
+    frame_len: usize,
+    hop: usize,
+    fft_size: usize,
+    window: Vec<f32>,
+    /// (first bin, weights) per mel band
+    filters: Vec<(usize, Vec<f32>)>,
+    dct: Vec<[f32; MEL_BANDS]>,
+}
+
+fn hz_to_mel(hz: f32) -> f32 {
+    2595.0 * (1.0 + hz / 700.0).log10()
+}
+
+fn mel_to_hz(mel: f32) -> f32 {
+    700.0 * (10f32.powf(mel / 2595.0) - 1.0)
+}
+
+impl FeatureExtractor {
+    fn new(sample_rate: u32) -> Self {
+        let decimation = ((sample_rate as f32 / TARGET_RATE as f32).round() as usize).max(1);
+        let rate = sample_rate as f32 / decimation as f32;
+        let frame_len = (rate * FRAME_SECS) as usize;
+        let hop = (rate * HOP_SECS) as usize;
+        let fft_size = frame_len.next_power_of_two();
+
+        let window = (0..frame_len)
+            .map(|i| 0.54 - 0.46 * (2.0 * PI * i as f32 / (frame_len - 1) as f32).cos())
+            .collect();
+
+        let bins = fft_size / 2 + 1;
+        let (low, high) = (hz_to_mel(100.0), hz_to_mel((rate / 2.0).min(7000.0)));
+        let edges: Vec<f32> = (0..MEL_BANDS + 2)
+            .map(|i| mel_to_hz(low + (high - low) * i as f32 / (MEL_BANDS + 1) as f32) * fft_size as f32 / rate)
+            .collect();
+        let filters = (0..MEL_BANDS)
+            .map(|band| {
+                let (left, center, right) = (edges[band], edges[band + 1], edges[band + 2]);
+                let first = left.ceil() as usize;
+                let last = (right.floor() as usize).min(bins - 1);
+                let weights = (first..=last)
+                    .map(|bin| {
+                        let bin = bin as f32;
+                        if bin <= center {
+                            (bin - left) / (center - left)
+                        } else {
+                            (right - bin) / (right - center)
+                        }
+                    })
+                    .collect();
+                (first, weights)
+            })
+            .collect();
+
+        let dct = (1..=COEFFS)
+            .map(|k| {
+                let mut row = [0.0; MEL_BANDS];
+                for (n, value) in row.iter_mut().enumerate() {
+                    *value = (PI * k as f32 * (n as f32 + 0.5) / MEL_BANDS as f32).cos();
+                }
+                row
+            })
+            .collect();
+
+        Self {
+            decimation,
+            decimation_acc: 0.0,
+            decimation_count: 0,
+            pending: Vec::with_capacity(frame_len * 2),
+            frame_len,
+            hop,
+            fft_size,
+            window,
+            filters,
+            dct,
+        }
+    }
+
+    /// Feed one capture-rate sample; returns a frame every hop once a full frame is buffered
+    fn push(&mut self, sample: f32) -> Option<Frame> {
+        self.decimation_acc += sample;
+        self.decimation_count += 1;
+        if self.decimation_count < self.decimation {
+            return None;
+        }
+        self.pending.push(self.decimation_acc / self.decimation as f32);
+        self.decimation_acc = 0.0;
+        self.decimation_count = 0;
+        if self.pending.len() < self.frame_len {
+            return None;
+        }
+        let frame = self.frame(&self.pending[..self.frame_len]);
+        self.pending.drain(..self.hop);
+        Some(frame)
+    }
+
+    fn frame(&self, samples: &[f32]) -> Frame {
+        let rms = (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt();
+        let mut re = vec![0.0; self.fft_size];
+        let mut im = vec![0.0; self.fft_size];
+        for (i, (sample, weight)) in samples.iter().zip(&self.window).enumerate() {
+            re[i] = sample * weight;
+        }
+        fft(&mut re, &mut im);
+
+        let mut energies = [0.0f32; MEL_BANDS];
+        for (energy, (first, weights)) in energies.iter_mut().zip(&self.filters) {
+            let power: f32 = weights
+                .iter()
+                .enumerate()
+                .map(|(i, weight)| weight * (re[first + i] * re[first + i] + im[first + i] * im[first + i]))
+                .sum();
+            *energy = (power + 1e-10).ln();
+        }
+        let mut mfcc = [0.0; COEFFS];
+        for (coeff, row) in mfcc.iter_mut().zip(&self.dct) {
+            *coeff = row.iter().zip(&energies).map(|(a, b)| a * b).sum();
+        }
+        Frame { mfcc, rms }
+    }
+}
+
+/// In-place iterative radix-2 FFT; the length must be a power of two
+fn fft(re: &mut [f32], im: &mut [f32]) {
+    let n = re.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+    let mut len = 2;
+    while len <= n {
+        let angle = -2.0 * PI / len as f32;
+        for start in (0..n).step_by(len) {
+            for k in 0..len / 2 {
+                let (sin, cos) = (angle * k as f32).sin_cos();
+                let (a, b) = (start + k, start + k + len / 2);
+                let (tr, ti) = (re[b] * cos - im[b] * sin, re[b] * sin + im[b] * cos);
+                re[b] = re[a] - tr;
+                im[b] = im[a] - ti;
+                re[a] += tr;
+                im[a] += ti;
+            }
+        }
+        len <<= 1;
+    }
+}
+
+/// Subtract the mean of each coefficient so the microphone's coloration cancels out
+fn normalize(frames: &[[f32; COEFFS]]) -> Vec<[f32; COEFFS]> {
+    let mut mean = [0.0; COEFFS];
+    for frame in frames {
+        for (m, c) in mean.iter_mut().zip(frame) {
+            *m += c / frames.len() as f32;
+        }
+    }
+    frames
+        .iter()
+        .map(|frame| {
+            let mut out = *frame;
+            for (c, m) in out.iter_mut().zip(&mean) {
+                *c -= m;
+            }
+            out
+        })
+        .collect()
+}
+
+fn frame_cost(a: &[f32; COEFFS], b: &[f32; COEFFS]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norms = a.iter().map(|x| x * x).sum::<f32>().sqrt() * b.iter().map(|y| y * y).sum::<f32>().sqrt();
+    if norms <= f32::EPSILON {
+        return 1.0;
+    }
+    (1.0 - dot / norms) / 2.0
+}
+
+/// Similarity (0-1) of two normalized frame sequences under dynamic time warping
+fn similarity(template: &[[f32; COEFFS]], window: &[[f32; COEFFS]]) -> f32 {
+    let (n, m) = (template.len(), window.len());
+    let mut previous = vec![f32::INFINITY; m + 1];
+    let mut current = vec![f32::INFINITY; m + 1];
+    previous[0] = 0.0;
+    for t in template {
+        current[0] = f32::INFINITY;
+        for (j, w) in window.iter().enumerate() {
+            let best = previous[j].min(previous[j + 1]).min(current[j]);
+            current[j + 1] = frame_cost(t, w) + best;
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    1.0 - previous[m] / (n + m) as f32
+}
+
+/// The spoken part of a recorded sample, without leading and trailing silence
+fn trim_silence(frames: Vec<Frame>) -> Vec<[f32; COEFFS]> {
+    let first = frames.iter().position(|frame| frame.rms >= SPEECH_RMS);
+    let last = frames.iter().rposition(|frame| frame.rms >= SPEECH_RMS);
+    match (first, last) {
+        (Some(first), Some(last)) => frames[first..=last].iter().map(|frame| frame.mfcc).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn run_actions(app_handle: &AppHandle, actions: &[WakeAction]) {
+    for action in actions {
+        match action {
+            WakeAction::ShowAiWindow => match app_handle.get_webview_window("ai-response") {
+                Some(window) => {
+                    let _ = window.show();
+                    let _ = window.set_focus();
+                }
+                None => warn!("Wake word: AI response window not found"),
+            },
+            // The main window already handles this hotkey action
+            WakeAction::AnswerLastQuestion => {
+                let event = crate::stealth_hotkeys_simple::StealthHotkeyEvent {
+                    action: "ai_answer_trigger".to_string(),
+                    hotkey_id: 0,
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                };
+                if let Err(e) = app_handle.emit(events::STEALTH_HOTKEY, &event) {
+                    warn!("Failed to emit {}: {}", events::STEALTH_HOTKEY, e);
+                }
+            }
+        }
+    }
+}
+
+async fn listen(app_handle: AppHandle, generation: u64) {
+    let mut stream = match PluelyMicrophoneInput::new().and_then(|input| input.stream()) {
+        Ok(stream) => stream,
+        Err(e) => {
+            warn!("Wake word: failed to open the microphone: {}", e);
+            return;
+        }
+    };
+    let mut extractor = FeatureExtractor::new(stream.sample_rate());
+    let (templates, longest) = {
+        let settings = SETTINGS.lock();
+        let templates: Vec<Vec<[f32; COEFFS]>> = settings.templates.iter().map(|template| normalize(template)).collect();
+        let longest = templates.iter().map(Vec::len).max().unwrap_or(0);
+        (templates, longest)
+    };
+    *LISTENING.lock() = true;
+    info!("👂 Listening for the wake word ({} samples)", templates.len());
+
+    let mut recent: std::collections::VecDeque<Frame> = std::collections::VecDeque::with_capacity(longest + 1);
+    let mut since_check = 0;
+    let mut cooldown_until = Instant::now();
+    while let Some(sample) = stream.next().await {
+        if GENERATION.load(Ordering::Relaxed) != generation {
+            break;
+        }
+        let Some(frame) = extractor.push(sample) else { continue };
+        if recent.len() == longest {
+            recent.pop_front();
+        }
+        recent.push_back(frame);
+        since_check += 1;
+        if since_check < CHECK_EVERY || Instant::now() < cooldown_until {
+            continue;
+        }
+        since_check = 0;
+        // The phrase has just ended: the newest frames are quiet and something was said before
+        let tail_quiet = recent.iter().rev().take(CHECK_EVERY).all(|frame| frame.rms < SPEECH_RMS);
+        if !tail_quiet || !recent.iter().any(|frame| frame.rms >= SPEECH_RMS) {
+            continue;
+        }
+
+        let (sensitivity, phrase, actions) = {
+            let settings = SETTINGS.lock();
+            (settings.sensitivity, settings.phrase.clone(), settings.actions.clone())
+        };
+        let score = templates
+            .iter()
+            .filter(|template| template.len() <= recent.len())
+            .map(|template| {
+                let spoken = recent.range(recent.len() - template.len()..).cloned().collect();
+                let window = normalize(&trim_silence(spoken));
+                if window.is_empty() {
+                    0.0
+                } else {
+                    similarity(template, &window)
+                }
+            })
+            .fold(0.0f32, f32::max);
+        if score < threshold(sensitivity) {
+            continue;
+        }
+
+        info!("👂 Wake word detected (score {:.2})", score);
+        cooldown_until = Instant::now() + COOLDOWN;
+        recent.clear();
+        let detected = WakeWordDetected { phrase, score, actions: actions.clone() };
+        if let Err(e) = app_handle.emit(events::WAKE_WORD_DETECTED, &detected) {
+            warn!("Failed to emit {}: {}", events::WAKE_WORD_DETECTED, e);
+        }
+        run_actions(&app_handle, &actions);
+    }
+
+    if GENERATION.load(Ordering::Relaxed) == generation {
+        *LISTENING.lock() = false;
+    }
+    info!("👂 Wake word listener stopped");
+}
+
+/// Stop any running listener and start a new one when enabled
+fn restart() {
+    let generation = GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+    *LISTENING.lock() = false;
+    let enabled = {
+        let settings = SETTINGS.lock();
+        settings.enabled && !settings.templates.is_empty()
+    };
+    if !enabled {
+        return;
+    }
+    if let Some(app_handle) = APP_HANDLE.get() {
+        tauri::async_runtime::spawn(listen(app_handle.clone(), generation));
+    }
+}
+
+pub fn init(app_handle: AppHandle) {
+    if APP_HANDLE.set(app_handle).is_err() {
+        return;
+    }
+    if SETTINGS.lock().enabled {
+        if let Err(e) = guard::check(&[Require::Permission(Permission::Microphone)]) {
+            warn!("Wake word stays off: {}", e.message);
+            return;
+        }
+        restart();
+    }
+}
+
+async fn record_sample(progress: &Progress) -> Result<Vec<[f32; COEFFS]>, AppError> {
+    let mut stream = PluelyMicrophoneInput::new()
+        .and_then(|input| input.stream())
+        .map_err(|e| AppError::new(ErrorCode::Unavailable, format!("Failed to open the microphone: {}", e)))?;
+    let sample_rate = stream.sample_rate();
+    let mut extractor = FeatureExtractor::new(sample_rate);
+    let wanted = (SAMPLE_SECS * sample_rate as f32) as usize;
+    let deadline = Instant::now() + Duration::from_secs_f32(SAMPLE_SECS) + Duration::from_secs(2);
+    let mut frames = Vec::new();
+    let mut received = 0;
+    while received < wanted {
+        if progress.is_cancelled() {
+            return Err(AppError::new(ErrorCode::Cancelled, "Wake word recording cancelled"));
+        }
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let Ok(Some(sample)) = tokio::time::timeout(remaining, stream.next()).await else { break };
+        received += 1;
+        if let Some(frame) = extractor.push(sample) {
+            frames.push(frame);
+            if frames.len() % 50 == 0 {
+                progress.report("recording", Some(90.0 * received as f32 / wanted as f32));
+            }
+        }
+    }
+
+    let spoken = trim_silence(frames);
+    if spoken.len() < MIN_SAMPLE_FRAMES {
+        return Err(AppError::new(
+            ErrorCode::InvalidInput,
+            "No clear phrase was heard; say the wake phrase once, close to the microphone",
+        ));
+    }
+    Ok(spoken)
+}
+
+/// Wake word settings and how many samples are recorded
+#[tauri::command]
+#[specta::specta]
+pub fn get_wake_word() -> Response<WakeWordStatus> {
+    envelope::respond_sync("get_wake_word", || Ok(status()))
+}
+
+/// Turn the wake word on or off and change how it behaves; enabling needs at least one sample
+#[tauri::command]
+#[specta::specta]
+pub fn set_wake_word(
+    enabled: bool,
+    phrase: Option<String>,
+    sensitivity: Option<f32>,
+    actions: Option<Vec<WakeAction>>,
+) -> Response<WakeWordStatus> {
+    envelope::respond_sync("set_wake_word", || {
+        if enabled {
+            guard::check(&[Require::Permission(Permission::Microphone)])?;
+        }
+        {
+            let mut settings = SETTINGS.lock();
+            if enabled && settings.templates.is_empty() {
+                return Err(AppError::new(
+                    ErrorCode::InvalidInput,
+                    "Record at least one sample of the wake phrase before enabling it",
+                ));
+            }
+            if let Some(sensitivity) = sensitivity {
+                if !(0.0..=1.0).contains(&sensitivity) {
+                    return Err(AppError::new(ErrorCode::InvalidInput, "Sensitivity must be between 0 and 1"));
+                }
+                settings.sensitivity = sensitivity;
+            }
+            if let Some(phrase) = phrase.filter(|phrase| !phrase.trim().is_empty()) {
+                settings.phrase = phrase.trim().to_string();
+            }
+            if let Some(actions) = actions {
+                settings.actions = actions;
+            }
+            settings.enabled = enabled;
+            save(&settings)?;
+            info!("👂 Wake word {}", if enabled { "enabled" } else { "disabled" });
+        }
+        restart();
+        Ok(status())
+    })
+}
+
+/// Record one ~2.5s sample of me saying the wake phrase; keeps the last five
+#[tauri::command]
+#[specta::specta]
+pub async fn record_wake_word_sample(request_id: Option<String>) -> Response<WakeWordStatus> {
+    cancellation::run_with_progress("record_wake_word_sample", request_id, |progress| async move {
+        guard::check(&[Require::Permission(Permission::Microphone)])?;
+        progress.report("recording", Some(0.0));
+        let template = record_sample(&progress).await?;
+        {
+            let mut settings = SETTINGS.lock();
+            if settings.templates.len() >= MAX_SAMPLES {
+                settings.templates.remove(0);
+            }
+            settings.templates.push(template);
+            save(&settings)?;
+            info!("👂 Recorded wake word sample {}", settings.templates.len());
+        }
+        // A running listener keeps the samples it started with
+        if *LISTENING.lock() {
+            restart();
+        }
+        Ok(status())
+    })
+    .await
+}
+
+/// Delete the recorded samples and turn the wake word off
+#[tauri::command]
+#[specta::specta]
+pub fn clear_wake_word_samples() -> Response<WakeWordStatus> {
+    envelope::respond_sync("clear_wake_word_samples", || {
+        {
+            let mut settings = SETTINGS.lock();
+            settings.templates.clear();
+            settings.enabled = false;
+            save(&settings)?;
+        }
+        restart();
+        Ok(status())
+    })
+}
@@ -0,0 +1,104 @@
+// Provider warm-up at session start
+// Creates the OpenAI/Pollinations clients and opens their pooled connections (DNS + TLS), and
+// checks Deepgram, so the first real answer doesn't pay connection setup latency.
+
+use std::time::Instant;
+
+use log::{info, warn};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::AppState;
+use crate::envelope::{self, Response};
+use crate::events;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct ProviderWarmUp {
+    pub provider: String,
+    pub ok: bool,
+    pub elapsed_ms: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Type)]
+pub struct WarmUpReport {
+    pub providers: Vec<ProviderWarmUp>,
+    pub total_ms: u64,
+}
+
+async fn timed<F>(provider: &str, warm: F) -> ProviderWarmUp
+where
+    F: std::future::Future<Output = Result<(), String>>,
+{
+    let started = Instant::now();
+    let result = warm.await;
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    match &result {
+        Ok(()) => info!("🔥 {} warmed up in {}ms", provider, elapsed_ms),
+        Err(e) => warn!("🔥 {} warm-up failed after {}ms: {}", provider, elapsed_ms, e),
+    }
+    ProviderWarmUp {
+        provider: provider.to_string(),
+        ok: result.is_ok(),
+        elapsed_ms,
+        error: result.err(),
+    }
+}
+
+/// Warm all providers concurrently; `ping` additionally requests one token from each AI provider
+pub(crate) async fn warm_up(state: &AppState, ping: bool) -> WarmUpReport {
+    let started = Instant::now();
+
+    let openai = state.ensure_openai_client().map(|_| state.openai_client.lock().clone());
+    let pollinations = state.ensure_pollinations_client().map(|_| state.pollinations_client.lock().clone());
+
+    let (openai, pollinations, deepgram) = tokio::join!(
+        timed("openai", async {
+            crate::privacy::ensure_cloud_allowed("OpenAI")?;
+            match openai? {
+                Some(client) => client.warm_up(ping).await.map_err(|e| e.to_string()),
+                None => Err("OpenAI client unavailable".to_string()),
+            }
+        }),
+        timed("pollinations", async {
+            crate::privacy::ensure_cloud_allowed("Pollinations")?;
+            match pollinations? {
+                Some(client) => client.warm_up(ping).await.map_err(|e| e.to_string()),
+                None => Err("Pollinations client unavailable".to_string()),
+            }
+        }),
+        timed("deepgram", async {
+            crate::privacy::ensure_cloud_allowed("Deepgram")?;
+            crate::deepgram_streaming::warm_up().await.map_err(|e| e.to_string())
+        }),
+    );
+
+    WarmUpReport {
+        providers: vec![openai, pollinations, deepgram],
+        total_ms: started.elapsed().as_millis() as u64,
+    }
+}
+
+/// Warm up in the background when a session starts; the result is emitted as `providers-warmed-up`
+pub(crate) fn spawn_warm_up(app_handle: &AppHandle) {
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<AppState>();
+        let report = warm_up(&state, false).await;
+        if let Err(e) = app_handle.emit(events::PROVIDERS_WARMED_UP, &report) {
+            warn!("Failed to emit providers-warmed-up: {}", e);
+        }
+    });
+}
+
+/// Pre-create provider clients and open their connections ahead of the first question
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn warm_up_providers(state: State<'_, AppState>, ping: Option<bool>) -> Response<WarmUpReport> {
+    envelope::respond("warm_up_providers", async move {
+        Ok(warm_up(&state, ping.unwrap_or(false)).await)
+    })
+    .await
+}
@@ -3,7 +3,7 @@ use std::sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}};
 use std::collections::VecDeque;
 use std::thread;
 use std::time::Duration;
-use log::{info, warn, error};
+use log::{info, warn, error, trace};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{Device, Host, Stream, SampleFormat, Sample};
 
@@ -612,11 +612,10 @@ impl WasapiLoopback {
                         }
                     }
                     
-                    // Log more frequently to show capture is working
-                    if buffer.len() > 0 && (buffer.len() - initial_len) > 0 {
-                        if count % 100 == 0 {
-                            info!("Audio capture active: {} total samples captured", buffer.len());
-                        }
+                    // Runs on the audio thread for every packet; only a sample of them is logged
+                    static CAPTURE_LOG: crate::logging::LogSampler = crate::logging::LogSampler::new(100);
+                    if buffer.len() > initial_len && CAPTURE_LOG.sample().is_some() {
+                        trace!("Audio capture active: {} total samples captured", buffer.len());
                     }
                 } else {
                     warn!("Failed to acquire audio buffer lock");
@@ -0,0 +1,385 @@
+// Webhook notifications for session events
+// POSTs JSON payloads to user-supplied URLs (Zapier, n8n, custom servers) when a session starts, a
+// question is detected, an answer is generated or the session is finalized. Each body is signed with
+// HMAC-SHA256 over "<timestamp>.<body>" using the endpoint's secret and sent in
+// `X-MockMate-Signature: sha256=<hex>`. Failed deliveries are retried with backoff and every
+// attempt is kept in a delivery log. Endpoints are stored in %APPDATA%\MockMate\webhooks.json.
+
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::net_trace::TracedSend;
+use crate::envelope::{self, Response};
+
+/// Delay before each retry; the number of entries is the number of retries
+const RETRY_DELAYS: [Duration; 3] = [Duration::from_secs(2), Duration::from_secs(10), Duration::from_secs(60)];
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+/// Delivery log entries kept (oldest dropped first)
+const MAX_LOG_ENTRIES: usize = 200;
+
+static SETTINGS: Lazy<Mutex<WebhookSettings>> = Lazy::new(|| Mutex::new(load_settings()));
+static DELIVERIES: Lazy<Mutex<VecDeque<WebhookDelivery>>> = Lazy::new(|| Mutex::new(load_log()));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEvent {
+    SessionStarted,
+    QuestionDetected,
+    AnswerGenerated,
+    SessionFinalized,
+    /// Sent only by `test_webhook`
+    Test,
+}
+
+impl WebhookEvent {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::SessionStarted => "session_started",
+            Self::QuestionDetected => "question_detected",
+            Self::AnswerGenerated => "answer_generated",
+            Self::SessionFinalized => "session_finalized",
+            Self::Test => "test",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct WebhookEndpoint {
+    #[serde(default)]
+    pub id: String,
+    pub url: String,
+    /// HMAC key; generated when saved empty
+    #[serde(default)]
+    pub secret: String,
+    /// Events to deliver; empty means all
+    #[serde(default)]
+    pub events: Vec<WebhookEvent>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct WebhookSettings {
+    endpoints: Vec<WebhookEndpoint>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct WebhookDelivery {
+    pub delivery_id: String,
+    pub endpoint_id: String,
+    pub url: String,
+    pub event: WebhookEvent,
+    pub attempts: u32,
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+    pub success: bool,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+struct WebhookPayload<'a> {
+    id: &'a str,
+    event: WebhookEvent,
+    created_at: DateTime<Utc>,
+    app_version: &'static str,
+    data: &'a serde_json::Value,
+}
+
+fn mockmate_dir() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+}
+
+fn settings_path() -> PathBuf {
+    mockmate_dir().join("webhooks.json")
+}
+
+fn log_path() -> PathBuf {
+    mockmate_dir().join("webhook_deliveries.json")
+}
+
+fn load_settings() -> WebhookSettings {
+    std::fs::read(settings_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn save_settings(settings: &WebhookSettings) -> Result<(), String> {
+    std::fs::create_dir_all(mockmate_dir()).map_err(|e| e.to_string())?;
+    let body = serde_json::to_vec_pretty(settings).map_err(|e| e.to_string())?;
+    std::fs::write(settings_path(), body).map_err(|e| e.to_string())
+}
+
+fn load_log() -> VecDeque<WebhookDelivery> {
+    std::fs::read(log_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn record_delivery(delivery: WebhookDelivery) {
+    let mut log = DELIVERIES.lock();
+    log.push_back(delivery);
+    while log.len() > MAX_LOG_ENTRIES {
+        log.pop_front();
+    }
+    if let Err(e) = serde_json::to_vec(&*log)
+        .map_err(|e| e.to_string())
+        .and_then(|body| {
+            std::fs::create_dir_all(mockmate_dir()).map_err(|e| e.to_string())?;
+            std::fs::write(log_path(), body).map_err(|e| e.to_string())
+        })
+    {
+        warn!("Failed to persist webhook delivery log: {}", e);
+    }
+}
+
+fn generate_secret() -> String {
+    use rand::Rng;
+    let bytes: [u8; 32] = rand::thread_rng().gen();
+    format!("whsec_{}", hex(&bytes))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let key = ring::hmac::Key::new(ring::hmac::HMAC_SHA256, secret.as_bytes());
+    let mut context = ring::hmac::Context::with_key(&key);
+    context.update(timestamp.to_string().as_bytes());
+    context.update(b".");
+    context.update(body);
+    format!("sha256={}", hex(context.sign().as_ref()))
+}
+
+/// Deliver one payload, retrying network errors, 429 and 5xx responses
+async fn deliver(endpoint: WebhookEndpoint, event: WebhookEvent, data: serde_json::Value) -> WebhookDelivery {
+    let delivery_id = uuid::Uuid::new_v4().to_string();
+    let created_at = Utc::now();
+    let body = serde_json::to_vec(&WebhookPayload {
+        id: &delivery_id,
+        event,
+        created_at,
+        app_version: env!("CARGO_PKG_VERSION"),
+        data: &data,
+    })
+    .unwrap_or_default();
+    let client = reqwest::Client::new();
+
+    let mut attempts = 0;
+    let mut status_code;
+    let mut error;
+    let mut success;
+    loop {
+        attempts += 1;
+        let timestamp = Utc::now().timestamp();
+        let result = client
+            .post(&endpoint.url)
+            .timeout(REQUEST_TIMEOUT)
+            .header("Content-Type", "application/json")
+            .header("User-Agent", concat!("MockMate-Webhooks/", env!("CARGO_PKG_VERSION")))
+            .header("X-MockMate-Event", event.as_str())
+            .header("X-MockMate-Delivery", &delivery_id)
+            .header("X-MockMate-Timestamp", timestamp.to_string())
+            .header("X-MockMate-Signature", sign(&endpoint.secret, timestamp, &body))
+            .body(body.clone())
+            .send_traced()
+            .await;
+        let retryable = match result {
+            Ok(response) => {
+                let status = response.status();
+                status_code = Some(status.as_u16());
+                success = status.is_success();
+                error = (!success).then(|| format!("HTTP {}", status));
+                status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            }
+            Err(e) => {
+                status_code = None;
+                success = false;
+                error = Some(e.to_string());
+                true
+            }
+        };
+        if success || !retryable {
+            break;
+        }
+        match RETRY_DELAYS.get(attempts as usize - 1) {
+            Some(delay) => tokio::time::sleep(*delay).await,
+            None => break,
+        }
+    }
+
+    if success {
+        info!("🪝 Webhook {:?} delivered to {} ({} attempt(s))", event, endpoint.url, attempts);
+    } else {
+        warn!("🪝 Webhook {:?} to {} failed after {} attempt(s): {:?}", event, endpoint.url, attempts, error);
+    }
+    WebhookDelivery {
+        delivery_id,
+        endpoint_id: endpoint.id,
+        url: endpoint.url,
+        event,
+        attempts,
+        status_code,
+        error,
+        success,
+        created_at,
+        completed_at: Utc::now(),
+    }
+}
+
+/// Local-only mode blocks webhooks except to loopback receivers (a local n8n, for example)
+fn ensure_allowed(endpoint: &WebhookEndpoint) -> Result<(), String> {
+    let local = url::Url::parse(&endpoint.url)
+        .ok()
+        .and_then(|u| u.host_str().map(crate::privacy::is_loopback_host))
+        .unwrap_or(false);
+    if local {
+        Ok(())
+    } else {
+        crate::privacy::ensure_cloud_allowed("Webhook")
+    }
+}
+
+/// Send `event` to every enabled endpoint subscribed to it. Returns immediately; deliveries run in
+/// the background and land in the delivery log.
+pub fn dispatch(event: WebhookEvent, data: serde_json::Value) {
+    let endpoints: Vec<WebhookEndpoint> = SETTINGS
+        .lock()
+        .endpoints
+        .iter()
+        .filter(|e| e.enabled && (e.events.is_empty() || e.events.contains(&event)))
+        .cloned()
+        .collect();
+    if endpoints.is_empty() {
+        return;
+    }
+    let endpoints: Vec<WebhookEndpoint> = endpoints.into_iter().filter(|e| ensure_allowed(e).is_ok()).collect();
+    for endpoint in endpoints {
+        let data = data.clone();
+        tauri::async_runtime::spawn(async move {
+            record_delivery(deliver(endpoint, event, data).await);
+        });
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_webhooks() -> Response<Vec<WebhookEndpoint>> {
+    envelope::respond("list_webhooks", async move {
+        Ok(SETTINGS.lock().endpoints.clone())
+    })
+    .await
+}
+
+/// Add an endpoint (empty id) or replace the one with the same id
+#[tauri::command]
+#[specta::specta]
+pub async fn save_webhook(mut endpoint: WebhookEndpoint) -> Response<WebhookEndpoint> {
+    envelope::respond("save_webhook", async move {
+        let url = url::Url::parse(endpoint.url.trim()).map_err(|e| format!("Invalid webhook URL: {}", e))?;
+        let local = url.host_str().map(crate::privacy::is_loopback_host).unwrap_or(false);
+        match url.scheme() {
+            "https" => {}
+            "http" if local => {}
+            _ => return Err("Webhook URLs must use https (plain http is only allowed for localhost)".into()),
+        }
+        endpoint.url = url.to_string();
+        if endpoint.secret.trim().is_empty() {
+            endpoint.secret = generate_secret();
+        }
+
+        let mut settings = SETTINGS.lock();
+        if endpoint.id.is_empty() {
+            endpoint.id = uuid::Uuid::new_v4().to_string();
+            settings.endpoints.push(endpoint.clone());
+        } else {
+            let existing = settings
+                .endpoints
+                .iter_mut()
+                .find(|e| e.id == endpoint.id)
+                .ok_or_else(|| format!("Webhook {} not found", endpoint.id))?;
+            *existing = endpoint.clone();
+        }
+        save_settings(&settings)?;
+        info!("🪝 Saved webhook {} -> {}", endpoint.id, endpoint.url);
+        Ok(endpoint)
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_webhook(id: String) -> Response<()> {
+    envelope::respond("delete_webhook", async move {
+        let mut settings = SETTINGS.lock();
+        let before = settings.endpoints.len();
+        settings.endpoints.retain(|e| e.id != id);
+        if settings.endpoints.len() == before {
+            return Err(format!("Webhook {} not found", id).into());
+        }
+        Ok(save_settings(&settings)?)
+    })
+    .await
+}
+
+/// Send a `test` event to one endpoint and wait for the outcome (retries included)
+#[tauri::command]
+#[specta::specta]
+pub async fn test_webhook(id: String) -> Response<WebhookDelivery> {
+    envelope::respond("test_webhook", async move {
+        let endpoint = SETTINGS
+            .lock()
+            .endpoints
+            .iter()
+            .find(|e| e.id == id)
+            .cloned()
+            .ok_or_else(|| format!("Webhook {} not found", id))?;
+        ensure_allowed(&endpoint)?;
+        let data = serde_json::json!({ "message": "MockMate webhook test" });
+        let delivery = deliver(endpoint, WebhookEvent::Test, data).await;
+        record_delivery(delivery.clone());
+        Ok(delivery)
+    })
+    .await
+}
+
+/// Most recent deliveries first
+#[tauri::command]
+#[specta::specta]
+pub async fn get_webhook_deliveries(limit: Option<usize>) -> Response<Vec<WebhookDelivery>> {
+    envelope::respond("get_webhook_deliveries", async move {
+        let log = DELIVERIES.lock();
+        Ok(log.iter().rev().take(limit.unwrap_or(MAX_LOG_ENTRIES)).cloned().collect())
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_matches_known_hmac() {
+        // HMAC-SHA256 of "1700000000.{body}" keyed with the secret, computed independently
+        assert_eq!(
+            sign("whsec_test", 1_700_000_000, br#"{"event":"session_started"}"#),
+            "sha256=b5164331523f7f25b1391f0cbb169d5ab0c7dbe6675ebb1fef38c831afa8760f"
+        );
+    }
+}
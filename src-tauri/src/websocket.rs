@@ -6,6 +6,7 @@ use serde_json::json;
 use tauri::{AppHandle, Emitter};
 use log::{info, error};
 use crate::QuestionPayload;
+use crate::events;
 
 static SERVER_URL: &str = "ws://localhost:3000";
 
@@ -26,7 +27,7 @@ pub async fn setup_socket(handle: &AppHandle) -> Result<()> {
                         info!("Received text message: {}", text);
                         // Parse and emit to frontend
                         if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(&text) {
-                            let _ = handle_clone.emit("websocket-message", parsed.clone());
+                            let _ = handle_clone.emit(events::WEBSOCKET_MESSAGE, parsed.clone());
 
                             // Example of checking for `join-session` type
                             if parsed["type"] == "join-session" {
@@ -39,13 +40,13 @@ pub async fn setup_socket(handle: &AppHandle) -> Result<()> {
                         if let Some(reason) = close {
                             info!("Socket closed with reason: {}", reason);
                         }
-                        let _ = handle_clone.emit("websocket-closed", json!({}));
+                        let _ = handle_clone.emit(events::WEBSOCKET_CLOSED, json!({}));
                     }
                     _ => {}
                 },
                 Err(e) => {
                     error!("WebSocket error: {}", e);
-                    let _ = handle_clone.emit("websocket-error", json!({"error": e.to_string()}));
+                    let _ = handle_clone.emit(events::WEBSOCKET_ERROR, events::ErrorPayload { error: e.to_string() });
                 }
             }
         }
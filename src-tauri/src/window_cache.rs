@@ -0,0 +1,111 @@
+// Cached top-level window enumeration
+// Accessibility extraction asks for the window list on every monitoring tick. The list is reused
+// for a short TTL and dropped early when a WinEvent hook reports a window being created,
+// destroyed, shown/hidden or renamed.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Once;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use log::{info, trace, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use windows_sys::Win32::Foundation::HWND;
+use windows_sys::Win32::UI::Accessibility::{SetWinEventHook, HWINEVENTHOOK};
+use windows_sys::Win32::UI::WindowsAndMessaging::{
+    DispatchMessageW, GetMessageW, TranslateMessage, CHILDID_SELF, EVENT_OBJECT_CREATE, EVENT_OBJECT_HIDE,
+    EVENT_OBJECT_NAMECHANGE, MSG, OBJID_WINDOW, WINEVENT_OUTOFCONTEXT, WINEVENT_SKIPOWNPROCESS,
+};
+
+use crate::accessibility_reader::WindowInfo;
+use crate::logging::LogSampler;
+
+/// Upper bound on how stale a cached list can get, even without window events
+const CACHE_TTL: Duration = Duration::from_secs(1);
+
+static CACHE: Lazy<Mutex<Option<CachedWindows>>> = Lazy::new(|| Mutex::new(None));
+/// Set by the WinEvent hook when the window list may have changed
+static DIRTY: AtomicBool = AtomicBool::new(false);
+static HOOKS: Once = Once::new();
+/// Window events arrive in bursts; only a sample of them is logged
+static EVENT_LOG: LogSampler = LogSampler::new(50);
+
+struct CachedWindows {
+    windows: Vec<WindowInfo>,
+    include_hidden: bool,
+    taken_at: Instant,
+}
+
+/// Drop the cached list; the next lookup enumerates again
+pub fn invalidate() {
+    DIRTY.store(true, Ordering::Relaxed);
+}
+
+/// The cached window list, or a fresh one from `enumerate` if it is stale or was invalidated
+pub(crate) fn windows(include_hidden: bool, enumerate: impl FnOnce() -> Result<Vec<WindowInfo>>) -> Result<Vec<WindowInfo>> {
+    HOOKS.call_once(start_event_hooks);
+
+    let dirty = DIRTY.swap(false, Ordering::Relaxed);
+    {
+        let cache = CACHE.lock();
+        if let Some(cached) = cache.as_ref() {
+            if !dirty && cached.include_hidden == include_hidden && cached.taken_at.elapsed() < CACHE_TTL {
+                return Ok(cached.windows.clone());
+            }
+        }
+    }
+
+    let windows = enumerate()?;
+    *CACHE.lock() = Some(CachedWindows {
+        windows: windows.clone(),
+        include_hidden,
+        taken_at: Instant::now(),
+    });
+    Ok(windows)
+}
+
+unsafe extern "system" fn on_window_event(
+    _hook: HWINEVENTHOOK,
+    event: u32,
+    _hwnd: HWND,
+    id_object: i32,
+    id_child: i32,
+    _event_thread: u32,
+    _event_time: u32,
+) {
+    // Only events about windows themselves, not the accessible objects inside them
+    if id_object == OBJID_WINDOW && id_child == CHILDID_SELF as i32 {
+        if let Some(seen) = EVENT_LOG.sample() {
+            trace!("Window event 0x{:04X} invalidated the window cache ({} events so far)", event, seen);
+        }
+        invalidate();
+    }
+}
+
+/// Install the WinEvent hooks on a thread that pumps messages for their callbacks
+fn start_event_hooks() {
+    let spawned = std::thread::Builder::new()
+        .name("window-event-hooks".to_string())
+        .spawn(|| unsafe {
+            let flags = WINEVENT_OUTOFCONTEXT | WINEVENT_SKIPOWNPROCESS;
+            // Create/destroy/show/hide are contiguous; name changes are hooked separately so the
+            // very frequent location/focus events in between don't reach the callback
+            let lifecycle = SetWinEventHook(EVENT_OBJECT_CREATE, EVENT_OBJECT_HIDE, 0, Some(on_window_event), 0, 0, flags);
+            let renames = SetWinEventHook(EVENT_OBJECT_NAMECHANGE, EVENT_OBJECT_NAMECHANGE, 0, Some(on_window_event), 0, 0, flags);
+            if lifecycle == 0 || renames == 0 {
+                warn!("Window event hooks unavailable; window cache relies on its TTL only");
+            } else {
+                info!("🪝 Window event hooks installed for window cache invalidation");
+            }
+
+            let mut msg: MSG = std::mem::zeroed();
+            while GetMessageW(&mut msg, 0, 0, 0) > 0 {
+                TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+        });
+    if let Err(e) = spawned {
+        warn!("Failed to start window event hook thread: {}", e);
+    }
+}
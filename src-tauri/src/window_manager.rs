@@ -1,12 +1,14 @@
-use tauri::{AppHandle, WebviewWindow, LogicalSize, PhysicalSize, PhysicalPosition, Manager};
-use log::{info, warn};
+use tauri::{AppHandle, WebviewWindow, WebviewWindowBuilder, LogicalSize, PhysicalSize, PhysicalPosition, Manager, Runtime};
+use log::{debug, info, warn};
 use serde::{Serialize, Deserialize};
+use specta::Type;
 use anyhow::Result;
 use std::sync::{Arc, Mutex};
 use std::collections::HashMap;
 use once_cell::sync::Lazy;
+use crate::envelope::{self, Response};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct WindowConfiguration {
     pub width: f64,
     pub height: f64,
@@ -14,9 +16,18 @@ pub struct WindowConfiguration {
     pub y: f64,
     pub scale_factor: f64,
     pub monitor_name: String,
+    #[specta(type = MonitorSize)]
     pub monitor_size: PhysicalSize<u32>,
 }
 
+/// Serialized form of `PhysicalSize<u32>`, for the TypeScript bindings
+#[derive(Type)]
+#[allow(dead_code)]
+struct MonitorSize {
+    width: u32,
+    height: u32,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 struct MonitorInfo {
     width: u32,
@@ -33,6 +44,86 @@ pub struct DpiAwarePosition {
     pub scale_factor: f64,
 }
 
+/// How windows can be placed on the current display server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum PositioningStrategy {
+    /// Global screen coordinates: Windows, macOS and X11 (XWayland included)
+    Absolute,
+    /// Wayland has no global coordinates for clients. The compositor places top-level windows,
+    /// moves are ignored, and secondary windows are anchored as transient children of the main one
+    CompositorRelative,
+}
+
+static POSITIONING: Lazy<PositioningStrategy> = Lazy::new(|| {
+    // GTK uses Wayland whenever WAYLAND_DISPLAY is set, unless GDK_BACKEND forces X11
+    let wayland = cfg!(target_os = "linux")
+        && std::env::var_os("WAYLAND_DISPLAY").is_some()
+        && !std::env::var("GDK_BACKEND").is_ok_and(|backend| backend.starts_with("x11"));
+    let strategy = if wayland {
+        PositioningStrategy::CompositorRelative
+    } else {
+        PositioningStrategy::Absolute
+    };
+    info!("🪟 Window positioning strategy: {:?}", strategy);
+    strategy
+});
+
+pub fn positioning_strategy() -> PositioningStrategy {
+    *POSITIONING
+}
+
+/// Move a window where the display server allows it; a no-op under compositor placement
+pub fn place_window<R: Runtime>(window: &WebviewWindow<R>, position: PhysicalPosition<i32>) -> Result<(), String> {
+    match positioning_strategy() {
+        PositioningStrategy::Absolute => window
+            .set_position(tauri::Position::Physical(position))
+            .map_err(|e| e.to_string()),
+        PositioningStrategy::CompositorRelative => {
+            debug!("Skipping move of '{}' to ({}, {}): the compositor places windows", window.label(), position.x, position.y);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn anchor_transient<'a, R: Runtime, M: Manager<R>>(
+    builder: WebviewWindowBuilder<'a, R, M>,
+    main_window: &WebviewWindow<R>,
+) -> Result<WebviewWindowBuilder<'a, R, M>, String> {
+    builder.transient_for(main_window).map_err(|e| format!("Failed to anchor window to the main window: {}", e))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn anchor_transient<'a, R: Runtime, M: Manager<R>>(
+    builder: WebviewWindowBuilder<'a, R, M>,
+    _main_window: &WebviewWindow<R>,
+) -> Result<WebviewWindowBuilder<'a, R, M>, String> {
+    Ok(builder)
+}
+
+/// Initial placement of a secondary window: at (x, y) with global coordinates, otherwise transient for the main window so the compositor keeps the two together
+pub fn anchor_to_main<'a, R: Runtime, M: Manager<R>>(
+    builder: WebviewWindowBuilder<'a, R, M>,
+    main_window: &WebviewWindow<R>,
+    x: f64,
+    y: f64,
+) -> Result<WebviewWindowBuilder<'a, R, M>, String> {
+    match positioning_strategy() {
+        PositioningStrategy::Absolute => Ok(builder.position(x, y)),
+        PositioningStrategy::CompositorRelative => anchor_transient(builder, main_window),
+    }
+}
+
+/// Which positioning strategy the display server allows, so the UI can hide move controls
+#[tauri::command]
+#[specta::specta]
+pub fn get_positioning_strategy() -> Response<PositioningStrategy> {
+    envelope::respond_sync("get_positioning_strategy", || {
+        Ok(positioning_strategy())
+    })
+}
+
 // Cache for monitor information to prevent duplicate logging
 static MONITOR_CACHE: Lazy<Arc<Mutex<HashMap<String, MonitorInfo>>>> = Lazy::new(|| {
     Arc::new(Mutex::new(HashMap::new()))
@@ -135,10 +226,10 @@ pub fn setup_main_window_dpi_aware(app_handle: &AppHandle) -> Result<(), String>
         }
         
         // Set the window position with DPI awareness  
-        if let Err(e) = window.set_position(tauri::Position::Physical(PhysicalPosition { 
-            x: physical_x, 
-            y: physical_y 
-        })) {
+        if let Err(e) = place_window(&window, PhysicalPosition {
+            x: physical_x,
+            y: physical_y,
+        }) {
             warn!("Failed to set window position: {}", e);
         }
         
@@ -206,10 +297,10 @@ pub fn apply_window_configuration(
         height: adjusted_height,
     })).map_err(|e| e.to_string())?;
     
-    window.set_position(tauri::Position::Physical(PhysicalPosition {
+    place_window(window, PhysicalPosition {
         x: adjusted_x,
         y: adjusted_y,
-    })).map_err(|e| e.to_string())?;
+    })?;
     
     Ok(())
 }
@@ -353,6 +444,10 @@ pub fn get_monitors_info(app_handle: &AppHandle) -> Result<Vec<serde_json::Value
 /// Ensure window is visible on current screen setup
 pub fn ensure_window_visible(window: &WebviewWindow) -> Result<(), String> {
     info!("👁️ Ensuring window is visible on current screen setup...");
+    // Positions are not known to the client under Wayland and the compositor keeps windows on screen
+    if positioning_strategy() == PositioningStrategy::CompositorRelative {
+        return Ok(());
+    }
     
     let position = window.outer_position().map_err(|e| e.to_string())?;
     let size = window.outer_size().map_err(|e| e.to_string())?;
@@ -391,10 +486,10 @@ pub fn ensure_window_visible(window: &WebviewWindow) -> Result<(), String> {
         info!("📍 Adjusting window position: ({}, {}) -> ({}, {})", 
               position.x, position.y, new_x, new_y);
         
-        window.set_position(tauri::Position::Physical(PhysicalPosition {
+        place_window(window, PhysicalPosition {
             x: new_x,
             y: new_y,
-        })).map_err(|e| e.to_string())?;
+        })?;
     }
     
     info!("✅ Window visibility check completed");
@@ -160,6 +160,7 @@ impl WindowsAudioCapture {
         Ok(())
     }
     
+    #[cfg(windows)]
     fn run_capture_thread_internal(
         is_recording: Arc<AtomicBool>,
         audio_samples: Arc<Mutex<VecDeque<f32>>>,
@@ -248,14 +249,14 @@ impl WindowsAudioCapture {
                     ) {
                         Ok(_) => {
                             if !buffer_data.is_null() && buffer_frame_count > 0 {
-                                let sample_count = (buffer_frame_count * channels as u32) as usize;
-                                let samples: &[f32] = std::slice::from_raw_parts(
-                                    buffer_data as *const f32,
-                                    sample_count
-                                );
+                                // WASAPI makes no f32 alignment promise for the packet, so decode bytes instead of reinterpreting
+                                let byte_count = buffer_frame_count as usize * channels as usize * 4;
+                                let bytes = std::slice::from_raw_parts(buffer_data as *const u8, byte_count);
+                                let mut samples = Vec::with_capacity(byte_count / 4);
+                                crate::audio_buffer::extend_f32_from_le_bytes(bytes, &mut samples);
                                 
                                 if let Ok(mut sample_buffer) = audio_samples.lock() {
-                                    for &sample in samples {
+                                    for sample in samples {
                                         sample_buffer.push_back(sample);
                                         if sample_buffer.len() > 44100 * 2 * 30 {
                                             sample_buffer.pop_front();
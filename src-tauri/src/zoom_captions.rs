@@ -0,0 +1,383 @@
+// Zoom caption integration
+// Connects to the caption endpoint of the Zoom Meeting SDK bridge, a local helper that joins the
+// meeting with the user's own Zoom identity and relays closed-caption / live-transcription
+// messages over a WebSocket. Zoom only delivers captions to the bridge when the user is the host
+// or has been granted caption access, so no text is captured without the meeting's permission.
+// Captions carry the speaker and are authoritative, so questions from other participants go straight
+// into the question-detection pipeline instead of relying on window scraping.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::watch;
+use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
+
+use crate::accessibility_reader::AccessibilityTextResult;
+use crate::envelope::{self, Response};
+use crate::events;
+
+const DEFAULT_ENDPOINT: &str = "ws://127.0.0.1:18475/captions";
+const MIN_RETRY: Duration = Duration::from_secs(2);
+const MAX_RETRY: Duration = Duration::from_secs(30);
+
+static SETTINGS: Lazy<Mutex<ZoomCaptionSettings>> = Lazy::new(|| Mutex::new(load_settings()));
+static STATUS: Lazy<Mutex<ZoomCaptionStatus>> = Lazy::new(|| Mutex::new(ZoomCaptionStatus::default()));
+/// Stop signal for the running connection task
+static STOP: Lazy<Mutex<Option<watch::Sender<bool>>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ZoomCaptionSettings {
+    pub enabled: bool,
+    /// WebSocket URL of the caption bridge
+    pub endpoint: String,
+    /// Keep reconnecting while enabled (e.g. between meetings)
+    pub auto_reconnect: bool,
+    /// Treat the user's own captions as questions too (useful for testing)
+    pub include_own_speech: bool,
+}
+
+impl Default for ZoomCaptionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: DEFAULT_ENDPOINT.to_string(),
+            auto_reconnect: true,
+            include_own_speech: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, Type)]
+pub struct ZoomCaptionStatus {
+    pub connected: bool,
+    pub endpoint: Option<String>,
+    pub meeting_id: Option<String>,
+    pub meeting_topic: Option<String>,
+    /// False when Zoom refused caption access (not host and no permission granted)
+    pub caption_access: Option<bool>,
+    pub captions_received: u64,
+    pub questions_detected: u64,
+    pub last_caption_at: Option<String>,
+    pub last_error: Option<String>,
+}
+
+/// Messages sent by the caption bridge
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum BridgeMessage {
+    Meeting {
+        meeting_id: String,
+        #[serde(default)]
+        topic: Option<String>,
+    },
+    CaptionAccess {
+        granted: bool,
+        #[serde(default)]
+        reason: Option<String>,
+    },
+    Caption {
+        text: String,
+        #[serde(default)]
+        speaker: Option<String>,
+        /// Caption spoken by the local user
+        #[serde(default)]
+        is_self: bool,
+        #[serde(default = "default_final")]
+        is_final: bool,
+    },
+    MeetingEnded,
+}
+
+fn default_final() -> bool {
+    true
+}
+
+/// Payload of `zoom-caption`
+#[derive(Debug, Clone, Serialize, JsonSchema, Type)]
+pub struct ZoomCaption {
+    pub text: String,
+    pub speaker: Option<String>,
+    pub is_self: bool,
+    pub is_final: bool,
+    pub timestamp: String,
+}
+
+fn settings_path() -> PathBuf {
+    crate::portable::data_root()
+        .join("MockMate")
+        .join("zoom_captions.json")
+}
+
+fn load_settings() -> ZoomCaptionSettings {
+    std::fs::read(settings_path())
+        .ok()
+        .and_then(|body| serde_json::from_slice(&body).ok())
+        .unwrap_or_default()
+}
+
+fn save_settings(settings: &ZoomCaptionSettings) -> Result<(), String> {
+    let path = settings_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(settings).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| e.to_string())
+}
+
+fn update_status(app_handle: &AppHandle, update: impl FnOnce(&mut ZoomCaptionStatus)) {
+    let status = {
+        let mut status = STATUS.lock();
+        update(&mut status);
+        status.clone()
+    };
+    let _ = app_handle.emit(events::ZOOM_CAPTION_STATUS, &status);
+}
+
+/// Remote bridges are subject to local-only mode; the usual loopback bridge is not
+fn check_endpoint(endpoint: &str) -> Result<(), String> {
+    let url = url::Url::parse(endpoint).map_err(|e| format!("Invalid caption endpoint '{}': {}", endpoint, e))?;
+    if !matches!(url.scheme(), "ws" | "wss") {
+        return Err("Caption endpoint must be a ws:// or wss:// URL".to_string());
+    }
+    let host = url.host_str().unwrap_or_default();
+    if !crate::privacy::is_loopback_host(host) {
+        crate::privacy::ensure_cloud_allowed("Zoom caption bridge")?;
+    }
+    Ok(())
+}
+
+async fn handle_caption(app_handle: &AppHandle, settings: &ZoomCaptionSettings, caption: ZoomCaption) {
+    let _ = app_handle.emit(events::ZOOM_CAPTION, &caption);
+    if !caption.is_final {
+        return;
+    }
+    crate::scripting::dispatch(crate::scripting::ScriptEvent::TranscriptSegment, &caption);
+    crate::session_transcript::append("zoom_captions", caption.speaker.as_deref(), &caption.text);
+    crate::context_builder::on_transcript(&caption.text);
+    update_status(app_handle, |status| {
+        status.captions_received += 1;
+        status.last_caption_at = Some(caption.timestamp.clone());
+    });
+
+    if (caption.is_self && !settings.include_own_speech) || !crate::headless::looks_like_question(&caption.text) {
+        return;
+    }
+    let window_title = STATUS.lock().meeting_topic.clone().unwrap_or_else(|| "Zoom Meeting".to_string());
+    let result = AccessibilityTextResult {
+        text_length: caption.text.chars().count(),
+        text: caption.text,
+        source_app: "Zoom captions".to_string(),
+        window_title,
+        confidence: 1.0,
+        is_potential_question: true,
+        timestamp: chrono::Utc::now().timestamp_millis() as u64,
+        extraction_method: "zoom_captions".to_string(),
+        window_class: String::new(),
+        process_id: 0,
+    };
+    if crate::accessibility_reader::emit_detected_question(app_handle, &result).await {
+        update_status(app_handle, |status| status.questions_detected += 1);
+    }
+}
+
+/// One connection to the bridge; returns when it closes or `stop` fires
+async fn run_connection(app_handle: &AppHandle, settings: &ZoomCaptionSettings, stop: &mut watch::Receiver<bool>) -> Result<(), String> {
+    check_endpoint(&settings.endpoint)?;
+    let (socket, _) = connect_async(settings.endpoint.as_str())
+        .await
+        .map_err(|e| format!("Caption bridge unavailable at {}: {}", settings.endpoint, e))?;
+    let (mut write, mut read) = socket.split();
+    info!("🎞️ Connected to Zoom caption bridge at {}", settings.endpoint);
+    update_status(app_handle, |status| {
+        status.connected = true;
+        status.endpoint = Some(settings.endpoint.clone());
+        status.last_error = None;
+    });
+    let _ = write
+        .send(Message::Text(r#"{"type":"subscribe","events":["captions"]}"#.to_string()))
+        .await;
+
+    loop {
+        let message = tokio::select! {
+            message = read.next() => message,
+            _ = stop.changed() => {
+                let _ = write.send(Message::Close(None)).await;
+                return Ok(());
+            }
+        };
+        let text = match message {
+            Some(Ok(Message::Text(text))) => text,
+            Some(Ok(Message::Ping(payload))) => {
+                let _ = write.send(Message::Pong(payload)).await;
+                continue;
+            }
+            Some(Ok(Message::Close(_))) | None => return Err("Caption bridge closed the connection".to_string()),
+            Some(Ok(_)) => continue,
+            Some(Err(e)) => return Err(format!("Caption bridge connection failed: {}", e)),
+        };
+
+        let message = match serde_json::from_str::<BridgeMessage>(&text) {
+            Ok(message) => message,
+            Err(e) => {
+                debug!("Ignoring caption bridge message: {}", e);
+                continue;
+            }
+        };
+        match message {
+            BridgeMessage::Meeting { meeting_id, topic } => {
+                info!("🎞️ Zoom meeting joined: {}", topic.as_deref().unwrap_or(&meeting_id));
+                update_status(app_handle, |status| {
+                    status.meeting_id = Some(meeting_id);
+                    status.meeting_topic = topic;
+                    status.caption_access = None;
+                });
+            }
+            BridgeMessage::CaptionAccess { granted, reason } => {
+                if !granted {
+                    warn!("Zoom denied caption access: {}", reason.as_deref().unwrap_or("host permission required"));
+                }
+                update_status(app_handle, |status| {
+                    status.caption_access = Some(granted);
+                    status.last_error = if granted { None } else { reason.or(Some("Host permission required".to_string())) };
+                });
+            }
+            BridgeMessage::Caption { text, speaker, is_self, is_final } => {
+                if text.trim().is_empty() {
+                    continue;
+                }
+                let caption = ZoomCaption {
+                    text: text.trim().to_string(),
+                    speaker,
+                    is_self,
+                    is_final,
+                    timestamp: chrono::Utc::now().to_rfc3339(),
+                };
+                handle_caption(app_handle, settings, caption).await;
+            }
+            BridgeMessage::MeetingEnded => {
+                info!("🎞️ Zoom meeting ended");
+                update_status(app_handle, |status| {
+                    status.meeting_id = None;
+                    status.meeting_topic = None;
+                    status.caption_access = None;
+                });
+            }
+        }
+    }
+}
+
+fn spawn_connection(app_handle: AppHandle, settings: ZoomCaptionSettings) {
+    let (stop_tx, mut stop_rx) = watch::channel(false);
+    if let Some(previous) = STOP.lock().replace(stop_tx) {
+        let _ = previous.send(true);
+    }
+
+    tauri::async_runtime::spawn(async move {
+        let mut retry = MIN_RETRY;
+        loop {
+            let result = run_connection(&app_handle, &settings, &mut stop_rx).await;
+            let stopped = *stop_rx.borrow();
+            update_status(&app_handle, |status| {
+                status.connected = false;
+                status.meeting_id = None;
+                status.meeting_topic = None;
+                if let Err(e) = &result {
+                    status.last_error = Some(e.clone());
+                }
+            });
+            if stopped || !settings.auto_reconnect {
+                if let Err(e) = result {
+                    warn!("Zoom caption bridge: {}", e);
+                }
+                break;
+            }
+            match result {
+                Ok(()) => retry = MIN_RETRY,
+                Err(e) => debug!("Zoom caption bridge: {} (retrying in {:?})", e, retry),
+            }
+            tokio::select! {
+                _ = tokio::time::sleep(retry) => {}
+                _ = stop_rx.changed() => break,
+            }
+            retry = (retry * 2).min(MAX_RETRY);
+        }
+        info!("🎞️ Zoom caption integration stopped");
+    });
+}
+
+/// Connect at startup when the integration is enabled
+pub fn init(app_handle: AppHandle) {
+    let settings = SETTINGS.lock().clone();
+    if settings.enabled {
+        spawn_connection(app_handle, settings);
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_zoom_caption_settings() -> Response<ZoomCaptionSettings> {
+    envelope::respond("get_zoom_caption_settings", async move {
+        Ok(SETTINGS.lock().clone())
+    })
+    .await
+}
+
+/// Save settings; enabling connects (or reconnects with the new endpoint), disabling disconnects
+#[tauri::command]
+#[specta::specta]
+pub async fn set_zoom_caption_settings(app_handle: AppHandle, settings: ZoomCaptionSettings) -> Response<ZoomCaptionSettings> {
+    envelope::respond("set_zoom_caption_settings", async move {
+        check_endpoint(&settings.endpoint)?;
+        save_settings(&settings)?;
+        *SETTINGS.lock() = settings.clone();
+        if settings.enabled {
+            spawn_connection(app_handle, settings.clone());
+        } else if let Some(stop) = STOP.lock().take() {
+            let _ = stop.send(true);
+        }
+        Ok(settings)
+    })
+    .await
+}
+
+/// Connect now with the saved settings, whether or not the integration is enabled
+#[tauri::command]
+#[specta::specta]
+pub async fn connect_zoom_captions(app_handle: AppHandle) -> Response<ZoomCaptionStatus> {
+    envelope::respond("connect_zoom_captions", async move {
+        let settings = SETTINGS.lock().clone();
+        check_endpoint(&settings.endpoint)?;
+        spawn_connection(app_handle, settings);
+        Ok(STATUS.lock().clone())
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn disconnect_zoom_captions() -> Response<()> {
+    envelope::respond("disconnect_zoom_captions", async move {
+        if let Some(stop) = STOP.lock().take() {
+            let _ = stop.send(true);
+        }
+        Ok(())
+    })
+    .await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_zoom_caption_status() -> Response<ZoomCaptionStatus> {
+    envelope::respond("get_zoom_caption_status", async move {
+        Ok(STATUS.lock().clone())
+    })
+    .await
+}
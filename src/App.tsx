@@ -1,5 +1,6 @@
 import { useState, useEffect, useRef, useCallback } from 'react';
-import { invoke } from '@tauri-apps/api/core';
+import { commands } from '@/bindings';
+import { CommandError, call, newIdempotencyKey } from '@/lib/ipc';
 import { listen } from '@tauri-apps/api/event';
 import { Button } from '@/components/ui/button';
 import { Input } from '@/components/ui/input';
@@ -132,6 +133,8 @@ function App() {
   
   // Refs for accessing current state in event handlers
   const stateRef = useRef(state);
+  // Activation key kept across retries until the backend gives a definite answer
+  const activationKeyRef = useRef<{ sessionId: string; key: string } | null>(null);
   
   // Timer ref for cleanup
   const timerIntervalRef = useRef<NodeJS.Timeout | null>(null);
@@ -177,12 +180,12 @@ function App() {
         try {
           // ONLY resize height to match content - let the DPI system handle width
           // Get current window size to preserve width
-          const currentSize = await invoke('get_window_info') as any;
+          const currentSize = await call(commands.getWindowInfo()) as any;
           if (currentSize && currentSize.width) {
-            await invoke('resize_main_window', {
-              width: currentSize.width,        // Keep current width (managed by DPI system)
-              height: physicalContentHeight   // Use physical pixels for height
-            });
+            await call(commands.resizeMainWindow(
+              currentSize.width, // Keep current width (managed by DPI system)
+              physicalContentHeight // Use physical pixels for height
+            ));
             console.log(`✅ Window height resized: ${physicalContentHeight}px physical (${contentHeight}px logical, width preserved: ${currentSize.width}px)`);
           }
         } catch (error) {
@@ -233,16 +236,16 @@ function App() {
         
         try {
           // ONLY resize height - preserve DPI-managed width
-          const currentSize = await invoke('get_window_info') as any;
+          const currentSize = await call(commands.getWindowInfo()) as any;
           if (currentSize && currentSize.width) {
             // Add max height constraint to prevent runaway growth
             const maxHeight = 800 * devicePixelRatio; // Max 800px logical height
             const constrainedHeight = Math.min(physicalContentHeight, maxHeight);
             
-            await invoke('resize_main_window', {
-              width: currentSize.width,        // Preserve DPI-managed width
-              height: constrainedHeight       // Use constrained physical pixels for height
-            });
+            await call(commands.resizeMainWindow(
+              currentSize.width, // Preserve DPI-managed width
+              constrainedHeight // Use constrained physical pixels for height
+            ));
             
             console.log(`✅ Resized to height: ${constrainedHeight}px (constrained from ${physicalContentHeight}px)`);
           }
@@ -307,11 +310,11 @@ function App() {
         const elapsedMinutes = Math.floor(elapsedSeconds / 60);
         if (elapsedSeconds > 0 && elapsedSeconds % 60 === 0) {
           console.log(`⏱️ Sending timer update: ${elapsedMinutes} minutes`);
-          invoke('update_session_timer', {
-            sessionId: state.session.sessionId,
+          call(commands.updateSessionTimer(
+            state.session.sessionId,
             elapsedMinutes,
-            isFinal: false
-          }).catch(err => {
+            false
+          )).catch(err => {
             console.warn('Failed to update timer on backend:', err);
           });
         }
@@ -332,7 +335,7 @@ function App() {
     const fetchModels = async () => {
       try {
         console.log('🔍 Fetching available models from backend...');
-        const models = await invoke<Array<{id: string, name: string, provider: string}>>('get_available_models');
+        const models = await call(commands.getAvailableModels());
         console.log('✅ Models fetched from backend:', models);
         
         if (models && models.length > 0) {
@@ -385,7 +388,7 @@ function App() {
       try {
         // Auto-activate stealth mode on app startup
         console.log('🕵️ Auto-activating stealth mode...');
-        await invoke('activate_stealth_mode');
+        await call(commands.activateStealthMode());
         console.log('✅ Stealth mode activated automatically');
 
         // Listen for stealth hotkey events
@@ -410,7 +413,7 @@ function App() {
         unlistenStealth();
       }
       // Deactivate stealth mode when app unmounts
-      invoke('deactivate_stealth_mode').catch(() => {});
+      call(commands.deactivateStealthMode()).catch(() => {});
     };
   }, []);
 
@@ -450,9 +453,9 @@ function App() {
           console.log('🪟 Window toggle triggered');
           // Toggle window visibility
           try {
-            await invoke('hide_main_window');
+            await call(commands.hideMainWindow());
             setTimeout(async () => {
-              await invoke('show_main_window');
+              await call(commands.showMainWindow());
             }, 2000); // Show again after 2 seconds
           } catch (err) {
             console.error('Window toggle error:', err);
@@ -470,7 +473,14 @@ function App() {
             console.warn('⚠️ Ultra Q&A button not found');
             // Fallback: try to call screenshot analysis directly
             try {
-              const screenText = await invoke('analyze_screen_content');
+              const screenText = await call(commands.analyzeScreenWithAi({
+                model: currentState.selectedModel,
+                provider: 'pollinations',
+                company: null,
+                position: null,
+                job_description: null,
+                system_prompt: null
+              }, null));
               setState(prev => ({ 
                 ...prev, 
                 transcriptionText: prev.transcriptionText + '\n[Screen]: ' + screenText
@@ -742,14 +752,14 @@ function App() {
             setState(prev => ({ ...prev, answerSaveAttempted: true }));
             
             // Attempt to save the accumulated answer
-            invoke('save_interview_answer', {
-              sessionId: currentState.session.sessionId,
-              questionId: currentState.currentQuestionId,
-              answerText: batch.content,
-              responseTime: 30,
-              aiFeedback: null,
-              aiScore: null
-            }).then(answerId => {
+            call(commands.saveInterviewAnswer(
+              currentState.session.sessionId,
+              currentState.currentQuestionId,
+              batch.content,
+              30,
+              null,
+              null
+            )).then(answerId => {
               console.log('✅ BACKUP SAVE: Answer saved with ID:', answerId);
             }).catch(error => {
               console.warn('⚠️ BACKUP SAVE: Failed to save answer:', error);
@@ -946,14 +956,14 @@ function App() {
             if (currentState.currentQuestionId && currentState.session.isActive && currentState.session.sessionId && bestAnswer.trim()) {
               try {
                 console.log('💾🚀 SAVING ANSWER TO DATABASE NOW!');
-                const answerId = await invoke('save_interview_answer', {
-                  sessionId: currentState.session.sessionId,
-                  questionId: currentState.currentQuestionId,
-                  answerText: bestAnswer,
-                  responseTime: 30,
-                  aiFeedback: null,
-                  aiScore: null
-                });
+                const answerId = await call(commands.saveInterviewAnswer(
+                  currentState.session.sessionId,
+                  currentState.currentQuestionId,
+                  bestAnswer,
+                  30,
+                  null,
+                  null
+                ));
                 console.log('✅✅✅ ANSWER SAVED SUCCESSFULLY! ID:', answerId);
               } catch (error) {
                 console.error('❌❌❌ FAILED TO SAVE ANSWER:', error);
@@ -962,14 +972,14 @@ function App() {
                 if (sources.payloadDirect && sources.payloadDirect !== bestAnswer) {
                   try {
                     console.log('🔄 Trying backup save with payload text...');
-                    const backupAnswerId = await invoke('save_interview_answer', {
-                      sessionId: currentState.session.sessionId,
-                      questionId: currentState.currentQuestionId,
-                      answerText: sources.payloadDirect,
-                      responseTime: 30,
-                      aiFeedback: null,
-                      aiScore: null
-                    });
+                    const backupAnswerId = await call(commands.saveInterviewAnswer(
+                      currentState.session.sessionId,
+                      currentState.currentQuestionId,
+                      sources.payloadDirect,
+                      30,
+                      null,
+                      null
+                    ));
                     console.log('✅ BACKUP SAVE SUCCESSFUL! ID:', backupAnswerId);
                   } catch (backupError) {
                     console.error('❌ BACKUP SAVE ALSO FAILED:', backupError);
@@ -1070,14 +1080,14 @@ function App() {
                   responseTime: 30
                 });
                 
-                const answerId = await invoke('save_interview_answer', {
-                  sessionId: currentState.session.sessionId,
-                  questionId: currentState.currentQuestionId,
-                  answerText: answerText,
-                  responseTime: 30, // Default response time in seconds
-                  aiFeedback: null,
-                  aiScore: null
-                });
+                const answerId = await call(commands.saveInterviewAnswer(
+                  currentState.session.sessionId,
+                  currentState.currentQuestionId,
+                  answerText,
+                  30, // Default response time in seconds
+                  null,
+                  null
+                ));
                 console.log('✅✅✅ SUCCESS! Answer successfully saved to database with ID:', answerId);
                 console.log('✅ Saved answer length:', answerText.length, 'characters');
                 console.log('✅ First 200 chars of saved answer:', answerText.substring(0, 200));
@@ -1126,14 +1136,14 @@ function App() {
                 state.accumulatedAIResponse && !state.answerSaveAttempted) {
               try {
                 console.log('🔄 FINAL FALLBACK: Saving answer on streaming state change...');
-                const answerId = await invoke('save_interview_answer', {
-                  sessionId: state.session.sessionId,
-                  questionId: state.currentQuestionId,
-                  answerText: state.accumulatedAIResponse,
-                  responseTime: 30,
-                  aiFeedback: null,
-                  aiScore: null
-                });
+                const answerId = await call(commands.saveInterviewAnswer(
+                  state.session.sessionId,
+                  state.currentQuestionId,
+                  state.accumulatedAIResponse,
+                  30,
+                  null,
+                  null
+                ));
                 console.log('✅ FINAL FALLBACK: Answer saved with ID:', answerId);
                 setState(prev => ({ ...prev, answerSaveAttempted: true }));
               } catch (error) {
@@ -1213,14 +1223,14 @@ function App() {
     if (currentState.currentQuestionId && currentState.session.sessionId) {
       try {
         const testAnswer = 'This is a test answer to verify the saving functionality works correctly.';
-        const answerId = await invoke('save_interview_answer', {
-          sessionId: currentState.session.sessionId,
-          questionId: currentState.currentQuestionId,
-          answerText: testAnswer,
-          responseTime: 30,
-          aiFeedback: null,
-          aiScore: null
-        });
+        const answerId = await call(commands.saveInterviewAnswer(
+          currentState.session.sessionId,
+          currentState.currentQuestionId,
+          testAnswer,
+          30,
+          null,
+          null
+        ));
         console.log('✅ TEST SAVE SUCCESSFUL! Answer ID:', answerId);
         return answerId;
       } catch (error) {
@@ -1236,7 +1246,7 @@ function App() {
   // Test database connection
   (window as any).testDbConnection = async () => {
     try {
-      const result = await invoke('test_database_connection');
+      const result = await call(commands.testDatabaseConnection());
       console.log('🔗 Database connection test result:', result);
       return result;
     } catch (error) {
@@ -1271,6 +1281,12 @@ function App() {
         isModelDropdownOpen: false 
       }));
       console.log('Model selected:', selectedModelObj.name);
+      // Automatic screen analysis uses the selected model too
+      call(commands.getAutoAnalysis())
+        .then(settings => settings.enabled
+          ? call(commands.setAutoAnalysis(true, null, null, selectedModelObj.provider, modelId))
+          : undefined)
+        .catch(err => console.warn('Failed to update automatic analysis model:', err));
     }
   };
 
@@ -1339,21 +1355,21 @@ function App() {
       if (currentState.isMicOn) {
         // Stop Deepgram first
         console.log('📡 Stopping Deepgram streaming...');
-        await invoke('stop_deepgram_streaming');
+        await call(commands.stopDeepgramStreaming());
         
         // Stop Pluely microphone capture
         console.log('🎤 Stopping Pluely microphone capture...');
-        await invoke('stop_pluely_microphone_capture');
+        await call(commands.stopPluelyMicrophoneCapture());
         setState(prev => ({ ...prev, isMicOn: false, isTranscriptionActive: false }));
         console.log('🎤 Microphone turned OFF');
       } else {
         // Start Pluely microphone capture
         console.log('🎤 Starting Pluely microphone capture...');
-        await invoke('start_pluely_microphone_capture');
+        await call(commands.startPluelyMicrophoneCapture());
         
         // Start Deepgram streaming
         console.log('📡 Starting Deepgram Nova-3 streaming...');
-        await invoke('start_deepgram_streaming');
+        await call(commands.startDeepgramStreaming());
         
         setState(prev => ({ ...prev, isMicOn: true, isTranscriptionActive: true }));
         console.log('🎤 Microphone turned ON (Pluely + Deepgram active)');
@@ -1381,21 +1397,21 @@ function App() {
       if (currentState.isSystemSoundOn) {
         // Stop Deepgram first
         console.log('📡 Stopping Deepgram streaming...');
-        await invoke('stop_deepgram_streaming');
+        await call(commands.stopDeepgramStreaming());
         
         // Stop Pluely system audio capture
         console.log('🔊 Stopping Pluely system audio capture...');
-        await invoke('stop_pluely_system_audio_capture');
+        await call(commands.stopPluelySystemAudioCapture());
         setState(prev => ({ ...prev, isSystemSoundOn: false, isTranscriptionActive: false }));
         console.log('🔊 System audio turned OFF (Pluely capture stopped)');
       } else {
         // Start Pluely system audio capture
         console.log('🔊 Starting Pluely system audio capture...');
-        await invoke('start_pluely_system_audio_capture');
+        await call(commands.startPluelySystemAudioCapture());
         
         // Start Deepgram streaming
         console.log('📡 Starting Deepgram Nova-3 streaming...');
-        await invoke('start_deepgram_streaming');
+        await call(commands.startDeepgramStreaming());
         
         setState(prev => ({ ...prev, isSystemSoundOn: true, isTranscriptionActive: true }));
         console.log('🔊 System audio turned ON (Pluely WASAPI loopback + Deepgram active)');
@@ -1427,10 +1443,10 @@ function App() {
     try {
       // Reset response window size first (using DPI-FIXED below-main enhanced window)
       console.log('📰 SEND BUTTON: Resetting AI response window (DPI-FIXED below-main enhanced)...');
-      await invoke('reset_ai_response_window_enhanced_below_size').catch(err => {
+      await call(commands.resetAiResponseWindowEnhancedBelowSize()).catch(err => {
         console.warn('⚠️ Failed to reset DPI-fixed AI response window size:', err);
         // Try to create the window if it doesn't exist
-        invoke('create_ai_response_window_enhanced_below').catch(e => 
+        call(commands.createAiResponseWindowEnhancedBelow()).catch(e => 
           console.warn('⚠️ Failed to create DPI-fixed below-main enhanced window:', e)
         );
       });
@@ -1446,7 +1462,8 @@ function App() {
         provider: 'pollinations',
         company: null,
         position: null,
-        job_description: null
+        job_description: null,
+        source: 'manual'
       };
       
       console.log('📦 SEND BUTTON: Payload created:', payload);
@@ -1455,14 +1472,14 @@ function App() {
       // Save question to database if session is active
       if (state.session.isActive && state.session.sessionId) {
         try {
-          const questionId = await invoke('save_interview_question', {
-            sessionId: state.session.sessionId,
-            questionNumber: state.questionCounter + 1,
-            questionText: state.inputText,
-            category: 'user_question',
-            difficultyLevel: 'medium',
-            expectedDuration: 300 // 5 minutes default
-          });
+          const questionId = await call(commands.saveInterviewQuestion(
+            state.session.sessionId,
+            state.questionCounter + 1,
+            state.inputText,
+            'user_question',
+            'medium',
+            300 // 5 minutes default
+          ));
           console.log('💾 Question saved to database with ID:', questionId);
           
           // Store question ID for answer linking
@@ -1477,7 +1494,7 @@ function App() {
       }
       
       // Call AI generation function with proper payload structure (wrapped in payload key)
-      const result = await invoke('pollinations_generate_answer_streaming', { payload });
+      const result = await call(commands.pollinationsGenerateAnswerStreaming(payload, null));
       console.log('✅ SEND BUTTON: Backend call successful, result:', result);
       
       setState(prev => ({ ...prev, inputText: '' }));
@@ -1507,9 +1524,10 @@ function App() {
     
     try {
       // Use the correct backend function name for database connection
-      const sessionData = await invoke('connect_session', {
-        sessionId: state.session.sessionId
-      }) as any;
+      const sessionData = await call(commands.connectSession(
+        state.session.sessionId,
+        null
+      )) as any;
       
       console.log('Session data received:', sessionData);
       
@@ -1551,11 +1569,18 @@ function App() {
     
     setState(prev => ({ ...prev, isStartingSession: true }));
     
+    if (activationKeyRef.current?.sessionId !== state.session.sessionId) {
+      activationKeyRef.current = { sessionId: state.session.sessionId, key: newIdempotencyKey() };
+    }
+    
     try {
       // Use the correct backend function and pass session ID
-      await invoke('activate_session', {
-        sessionId: state.session.sessionId
-      });
+      await call(commands.activateSessionCmd(
+        state.session.sessionId,
+        activationKeyRef.current.key,
+        null
+      ));
+      activationKeyRef.current = null;
       
       setState(prev => ({ 
         ...prev, 
@@ -1573,11 +1598,40 @@ function App() {
       await autoResize(false, 'main');
     } catch (error) {
       console.error('Failed to start session:', error);
+      // A retryable failure may have gone through; only then retry with the same key
+      if (!(error instanceof CommandError && error.retryable)) {
+        activationKeyRef.current = null;
+      }
       showNotification('Session Error', `Failed to start session: ${error}`);
       setState(prev => ({ ...prev, isStartingSession: false }));
     }
   };
 
+  // Latest startSession for event listeners registered once
+  const startSessionRef = useRef(startSession);
+  startSessionRef.current = startSession;
+
+  // Auto-start transcription and the session when a Teams/Zoom/Meet call is detected
+  useEffect(() => {
+    const unlisten = listen<{ app: string; auto_start_transcription: boolean; auto_start_session: boolean }>(
+      'meeting-started',
+      async (event) => {
+        const { app, auto_start_transcription, auto_start_session } = event.payload;
+        console.log('📞 Meeting started:', app);
+        const current = stateRef.current;
+        if (auto_start_transcription && !current.isSystemSoundOn) {
+          await toggleSystemAudio();
+        }
+        if (auto_start_session && current.session.sessionId && !current.session.isActive) {
+          await startSessionRef.current();
+        }
+      }
+    );
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, [toggleSystemAudio]);
+
   const disconnectSession = async () => {
     try {
       // Send final timer update before disconnecting
@@ -1588,11 +1642,11 @@ function App() {
         
         console.log(`⏱️ Sending final timer update: ${elapsedMinutes} minutes`);
         try {
-          await invoke('update_session_timer', {
-            sessionId: state.session.sessionId,
+          await call(commands.updateSessionTimer(
+            state.session.sessionId,
             elapsedMinutes,
-            isFinal: true
-          });
+            true
+          ));
         } catch (timerError) {
           console.warn('Failed to send final timer update:', timerError);
         }
@@ -1604,7 +1658,9 @@ function App() {
         timerIntervalRef.current = null;
       }
       
-      await invoke('disconnect_session');
+      await call(commands.disconnectSessionCmd(
+        state.session.sessionId
+      ));
       setState(prev => ({ 
         ...prev, 
         currentScreen: 'session_connection',
@@ -1641,10 +1697,10 @@ function App() {
     try {
       // Reset response window size first (using DPI-FIXED below-main enhanced window)
       console.log('📰 AI BUTTON: Resetting AI response window (DPI-FIXED below-main enhanced)...');
-      await invoke('reset_ai_response_window_enhanced_below_size').catch(err => {
+      await call(commands.resetAiResponseWindowEnhancedBelowSize()).catch(err => {
         console.warn('⚠️ Failed to reset DPI-fixed AI response window size:', err);
         // Try to create the window if it doesn't exist
-        invoke('create_ai_response_window_enhanced_below').catch(e => 
+        call(commands.createAiResponseWindowEnhancedBelow()).catch(e => 
           console.warn('⚠️ Failed to create DPI-fixed below-main enhanced window:', e)
         );
       });
@@ -1658,7 +1714,8 @@ function App() {
         provider: 'pollinations',
         company: null,
         position: null,
-        job_description: null
+        job_description: null,
+        source: 'transcript'
       };
       
       console.log('📦 AI BUTTON: Payload created:', payload);
@@ -1667,14 +1724,14 @@ function App() {
       // Save question to database if session is active
       if (state.session.isActive && state.session.sessionId) {
         try {
-          const questionId = await invoke('save_interview_question', {
-            sessionId: state.session.sessionId,
-            questionNumber: state.questionCounter + 1,
-            questionText: questionText,
-            category: 'transcription',
-            difficultyLevel: 'medium',
-            expectedDuration: 300 // 5 minutes default
-          });
+          const questionId = await call(commands.saveInterviewQuestion(
+            state.session.sessionId,
+            state.questionCounter + 1,
+            questionText,
+            'transcription',
+            'medium',
+            300 // 5 minutes default
+          ));
           console.log('💾 Question saved to database with ID:', questionId);
           
           // Store question ID for answer linking
@@ -1689,7 +1746,7 @@ function App() {
       }
       
       // Call the streaming AI function with wrapped payload parameter
-      const result = await invoke('pollinations_generate_answer_streaming', { payload });
+      const result = await call(commands.pollinationsGenerateAnswerStreaming(payload, null));
       console.log('✅ AI BUTTON: Backend call successful, result:', result);
       
       // Clear the transcription after sending to AI (cut functionality)
@@ -1720,8 +1777,8 @@ function App() {
 
 
   // Handle window controls
-  const minimizeWindow = () => invoke('minimize_window');
-  const closeWindow = () => invoke('close_application');
+  const minimizeWindow = () => call(commands.minimizeWindow());
+  const closeWindow = () => call(commands.closeApplication());
 
   // Render Session Connection Screen
   const renderSessionConnectionScreen = () => {
@@ -0,0 +1,3365 @@
+// @ts-nocheck
+// This file was generated by [tauri-specta](https://github.com/oscartbeaumont/tauri-specta). Do not edit this file manually.
+
+/** user-defined commands **/
+
+
+export const commands = {
+async startAudioStream() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("start_audio_stream");
+},
+async stopAudioStream() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("stop_audio_stream");
+},
+async startSystemAudioCapture() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("start_system_audio_capture");
+},
+async startMicrophoneCapture() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("start_microphone_capture");
+},
+async testMicrophoneCapture() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("test_microphone_capture");
+},
+async getAudioDevices() : Promise<Envelope<string[]>> {
+    return await TAURI_INVOKE("get_audio_devices");
+},
+async checkAudioStatus() : Promise<Envelope<JsonValue>> {
+    return await TAURI_INVOKE("check_audio_status");
+},
+async startAudioWithConfig(config: AudioConfigPayload) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("start_audio_with_config", { config });
+},
+async testAudioCapture(duration: number) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("test_audio_capture", { duration });
+},
+async testNativeWindowsSystemAudioCapture(duration: number) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("test_native_windows_system_audio_capture", { duration });
+},
+async sendManualQuestion(payload: QuestionPayload) : Promise<Envelope<null>> {
+    return await TAURI_INVOKE("send_manual_question", { payload });
+},
+async connectToSession(sessionId: string) : Promise<Envelope<null>> {
+    return await TAURI_INVOKE("connect_to_session", { sessionId });
+},
+async closeApplication() : Promise<Envelope<null>> {
+    return await TAURI_INVOKE("close_application");
+},
+async minimizeWindow() : Promise<Envelope<null>> {
+    return await TAURI_INVOKE("minimize_window");
+},
+async toggleAlwaysOnTop() : Promise<Envelope<boolean>> {
+    return await TAURI_INVOKE("toggle_always_on_top");
+},
+async createAiResponseWindow() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("create_ai_response_window");
+},
+async closeAiResponseWindow() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("close_ai_response_window");
+},
+async debugMainWindowDimensions() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("debug_main_window_dimensions");
+},
+async fixMainWindowInvisibleBoundary() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("fix_main_window_invisible_boundary");
+},
+async forceWindowExactContentSize() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("force_window_exact_content_size");
+},
+async nuclearFixWebviewPadding() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("nuclear_fix_webview_padding");
+},
+async resizeAiResponseWindow(height: number) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("resize_ai_response_window", { height });
+},
+async showAiResponseWindow() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("show_ai_response_window");
+},
+async hideAiResponseWindow() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("hide_ai_response_window");
+},
+async sendAiResponseData(data: AiResponseData) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("send_ai_response_data", { data });
+},
+async resetAiResponseWindowSize() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("reset_ai_response_window_size");
+},
+/**
+ * NEW: Create AI response window positioned below main window with proper DPI-aware centering
+ */
+async createAiResponseWindowEnhancedBelow() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("create_ai_response_window_enhanced_below");
+},
+/**
+ * NEW: Reset AI response window size and position it below main window with DPI-aware centering
+ */
+async resetAiResponseWindowEnhancedBelowSize() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("reset_ai_response_window_enhanced_below_size");
+},
+/**
+ * Tauri command to start Pluely-style system audio capture
+ */
+async startPluelySystemAudioCapture() : Promise<Envelope<null>> {
+    return await TAURI_INVOKE("start_pluely_system_audio_capture");
+},
+/**
+ * Tauri command to stop system audio capture
+ */
+async stopPluelySystemAudioCapture() : Promise<Envelope<null>> {
+    return await TAURI_INVOKE("stop_pluely_system_audio_capture");
+},
+/**
+ * Check if system audio capture is active
+ */
+async isPluelyAudioActive() : Promise<Envelope<boolean>> {
+    return await TAURI_INVOKE("is_pluely_audio_active");
+},
+/**
+ * Test command to verify Pluely system audio capture works
+ */
+async testPluelySystemAudioCapture(durationSeconds: number) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("test_pluely_system_audio_capture", { durationSeconds });
+},
+/**
+ * Tauri command to start Pluely-style microphone capture
+ */
+async startPluelyMicrophoneCapture() : Promise<Envelope<null>> {
+    return await TAURI_INVOKE("start_pluely_microphone_capture");
+},
+/**
+ * Tauri command to stop microphone capture
+ */
+async stopPluelyMicrophoneCapture() : Promise<Envelope<null>> {
+    return await TAURI_INVOKE("stop_pluely_microphone_capture");
+},
+/**
+ * Check if microphone capture is active
+ */
+async isPluelyMicrophoneActive() : Promise<Envelope<boolean>> {
+    return await TAURI_INVOKE("is_pluely_microphone_active");
+},
+/**
+ * Tauri command to start Deepgram streaming transcription
+ */
+async startDeepgramStreaming() : Promise<Envelope<null>> {
+    return await TAURI_INVOKE("start_deepgram_streaming");
+},
+/**
+ * Tauri command to stop Deepgram streaming
+ */
+async stopDeepgramStreaming() : Promise<Envelope<null>> {
+    return await TAURI_INVOKE("stop_deepgram_streaming");
+},
+/**
+ * Check if Deepgram streaming is active
+ */
+async isDeepgramStreamingActive() : Promise<Envelope<boolean>> {
+    return await TAURI_INVOKE("is_deepgram_streaming_active");
+},
+async getTranscriptionKeywords() : Promise<Envelope<string[]>> {
+    return await TAURI_INVOKE("get_transcription_keywords");
+},
+async setTranscriptionKeywords(keywords: string[]) : Promise<Envelope<string[]>> {
+    return await TAURI_INVOKE("set_transcription_keywords", { keywords });
+},
+async generateAiAnswer(payload: GenerateAnswerPayload, requestId: string | null) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("generate_ai_answer", { payload, requestId });
+},
+async analyzeScreenContent(payload: AnalyzeScreenPayload, requestId: string | null) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("analyze_screen_content", { payload, requestId });
+},
+async updateInterviewContext(payload: InterviewContextPayload) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("update_interview_context", { payload });
+},
+async getAvailableModels() : Promise<Envelope<ModelInfo[]>> {
+    return await TAURI_INVOKE("get_available_models");
+},
+async getAiProviders() : Promise<Envelope<ProviderInfo[]>> {
+    return await TAURI_INVOKE("get_ai_providers");
+},
+async saveMicrophoneFile() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("save_microphone_file");
+},
+async saveSystemAudioFile() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("save_system_audio_file");
+},
+/**
+ * Cap the RAM used by the system audio capture; older audio beyond it spills to temp files
+ */
+async setCaptureMemoryLimit(megabytes: number) : Promise<Envelope<CaptureMemoryStatus>> {
+    return await TAURI_INVOKE("set_capture_memory_limit", { megabytes });
+},
+async getCaptureMemoryStatus() : Promise<Envelope<CaptureMemoryStatus>> {
+    return await TAURI_INVOKE("get_capture_memory_status");
+},
+/**
+ * Pre-create provider clients and open their connections ahead of the first question
+ */
+async warmUpProviders(ping: boolean | null) : Promise<Envelope<WarmUpReport>> {
+    return await TAURI_INVOKE("warm_up_providers", { ping });
+},
+async pollinationsGenerateAnswer(payload: GenerateAnswerPayload, requestId: string | null) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("pollinations_generate_answer", { payload, requestId });
+},
+async pollinationsGenerateAnswerStreaming(payload: GenerateAnswerPayload, requestId: string | null) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("pollinations_generate_answer_streaming", { payload, requestId });
+},
+async pollinationsGenerateAnswerPostStreaming(payload: GenerateAnswerPayload, requestId: string | null) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("pollinations_generate_answer_post_streaming", { payload, requestId });
+},
+async analyzeScreenWithAi(payload: AnalyzeScreenWithAiPayload, requestId: string | null) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("analyze_screen_with_ai", { payload, requestId });
+},
+async analyzeScreenWithAiStreaming(payload: AnalyzeScreenWithAiPayload, requestId: string | null) : Promise<Envelope<AiAnalysisResult>> {
+    return await TAURI_INVOKE("analyze_screen_with_ai_streaming", { payload, requestId });
+},
+/**
+ * Tauri command to read text from applications (now scans all target applications)
+ */
+async readTextFromApplications() : Promise<Envelope<AccessibilityTextResult[]>> {
+    return await TAURI_INVOKE("read_text_from_applications");
+},
+/**
+ * Tauri command to read text from focused window
+ */
+async readTextFromFocusedWindow() : Promise<Envelope<AccessibilityTextResult | null>> {
+    return await TAURI_INVOKE("read_text_from_focused_window");
+},
+/**
+ * Tauri command to read from background windows
+ */
+async readTextFromBackgroundWindows() : Promise<Envelope<AccessibilityTextResult[]>> {
+    return await TAURI_INVOKE("read_text_from_background_windows");
+},
+/**
+ * Tauri command to read text from the current active window
+ */
+async readTextFromCurrentWindow() : Promise<Envelope<AccessibilityTextResult | null>> {
+    return await TAURI_INVOKE("read_text_from_current_window");
+},
+/**
+ * NEW: Tauri command to read text specifically from the window behind MockMate
+ * This is the key function for your use case - extracting text from the interviewer's window
+ */
+async readTextFromWindowBehindMockmate() : Promise<Envelope<AccessibilityTextResult | null>> {
+    return await TAURI_INVOKE("read_text_from_window_behind_mockmate");
+},
+/**
+ * NEW: Tauri command to capture the current focused window before MockMate takes focus
+ * Call this before showing MockMate to remember which window to extract from
+ */
+async capturePreviousFocusedWindow() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("capture_previous_focused_window");
+},
+/**
+ * Tauri command to start real-time monitoring
+ */
+async startRealtimeMonitoring() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("start_realtime_monitoring");
+},
+/**
+ * Tauri command to stop real-time monitoring
+ */
+async stopRealtimeMonitoring() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("stop_realtime_monitoring");
+},
+/**
+ * Tauri command to get monitoring status
+ */
+async getMonitoringStatus() : Promise<Envelope<JsonValue>> {
+    return await TAURI_INVOKE("get_monitoring_status");
+},
+/**
+ * Tauri command for hybrid text extraction (Accessibility + OCR fallback)
+ */
+async extractTextHybridApproach() : Promise<Envelope<AccessibilityTextResult[]>> {
+    return await TAURI_INVOKE("extract_text_hybrid_approach");
+},
+/**
+ * Configuration update command
+ */
+async updateAccessibilityConfig(targetApps: string[] | null, focusedOnly: boolean | null, minQuestionLength: number | null, monitoringIntervalMs: number | null, trackPreviousFocus: boolean | null, monitorHiddenWindows: boolean | null, allowWindowActivation: boolean | null) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("update_accessibility_config", { targetApps, focusedOnly, minQuestionLength, monitoringIntervalMs, trackPreviousFocus, monitorHiddenWindows, allowWindowActivation });
+},
+/**
+ * Analyze all target applications using Windows Accessibility API with AI streaming
+ */
+async analyzeApplicationsWithAiStreaming(payload: AnalyzeScreenWithAiPayload, requestId: string | null) : Promise<Envelope<AiAnalysisResult>> {
+    return await TAURI_INVOKE("analyze_applications_with_ai_streaming", { payload, requestId });
+},
+/**
+ * Analyze focused window using Windows Accessibility API with AI streaming
+ */
+async analyzeFocusedWindowWithAiStreaming(payload: AnalyzeScreenWithAiPayload, requestId: string | null) : Promise<Envelope<AiAnalysisResult>> {
+    return await TAURI_INVOKE("analyze_focused_window_with_ai_streaming", { payload, requestId });
+},
+/**
+ * Capture the primary monitor and return base64 encoded PNG data
+ */
+async captureScreenshot() : Promise<Envelope<ScreenshotResponse>> {
+    return await TAURI_INVOKE("capture_screenshot");
+},
+async listCaptureMonitors() : Promise<Envelope<MonitorInfo[]>> {
+    return await TAURI_INVOKE("list_capture_monitors");
+},
+async captureMonitorScreenshot(monitorIndex: number | null) : Promise<Envelope<Screenshot>> {
+    return await TAURI_INVOKE("capture_monitor_screenshot", { monitorIndex });
+},
+async captureWindowScreenshot(hwnd: number) : Promise<Envelope<Screenshot>> {
+    return await TAURI_INVOKE("capture_window_screenshot", { hwnd });
+},
+async captureRegionScreenshot(x: number, y: number, width: number, height: number) : Promise<Envelope<Screenshot>> {
+    return await TAURI_INVOKE("capture_region_screenshot", { x, y, width, height });
+},
+async getRecordingSettings() : Promise<Envelope<RecordingSettings>> {
+    return await TAURI_INVOKE("get_recording_settings");
+},
+async setRecordingSettings(settings: RecordingSettings) : Promise<Envelope<RecordingSettings>> {
+    return await TAURI_INVOKE("set_recording_settings", { settings });
+},
+async startSessionRecording(sessionId: string | null) : Promise<Envelope<RecordingStatus>> {
+    return await TAURI_INVOKE("start_session_recording", { sessionId });
+},
+async stopSessionRecording() : Promise<Envelope<RecordingResult>> {
+    return await TAURI_INVOKE("stop_session_recording");
+},
+async getRecordingStatus() : Promise<Envelope<RecordingStatus>> {
+    return await TAURI_INVOKE("get_recording_status");
+},
+/**
+ * Start a practice session and return the first question
+ */
+async startPracticeSession(config: PracticeConfig) : Promise<Envelope<PracticeTurn>> {
+    return await TAURI_INVOKE("start_practice_session", { config });
+},
+/**
+ * Answer the open question; without `answer` the transcripts heard since it was asked are used
+ */
+async submitPracticeAnswer(answer: string | null) : Promise<Envelope<PracticeStep>> {
+    return await TAURI_INVOKE("submit_practice_answer", { answer });
+},
+/**
+ * Skip the open question without scoring it
+ */
+async skipPracticeQuestion() : Promise<Envelope<PracticeTurn | null>> {
+    return await TAURI_INVOKE("skip_practice_question");
+},
+async getPracticeSession() : Promise<Envelope<PracticeSession | null>> {
+    return await TAURI_INVOKE("get_practice_session");
+},
+/**
+ * End the session (finished or not) and produce its report
+ */
+async endPracticeSession() : Promise<Envelope<PracticeReport>> {
+    return await TAURI_INVOKE("end_practice_session");
+},
+/**
+ * Saved reports, newest first
+ */
+async listPracticeReports() : Promise<Envelope<PracticeReport[]>> {
+    return await TAURI_INVOKE("list_practice_reports");
+},
+async addBankQuestion(input: QuestionInput) : Promise<Envelope<BankQuestion>> {
+    return await TAURI_INVOKE("add_bank_question", { input });
+},
+/**
+ * Edit a question; unlike imports, provided tags replace the existing ones
+ */
+async updateBankQuestion(id: string, input: QuestionInput) : Promise<Envelope<BankQuestion>> {
+    return await TAURI_INVOKE("update_bank_question", { id, input });
+},
+async deleteBankQuestion(id: string) : Promise<Envelope<null>> {
+    return await TAURI_INVOKE("delete_bank_question", { id });
+},
+/**
+ * Questions matching the query, most often asked first
+ */
+async queryBankQuestions(query: QuestionQuery | null) : Promise<Envelope<BankQuestion[]>> {
+    return await TAURI_INVOKE("query_bank_questions", { query });
+},
+async listBankTags() : Promise<Envelope<TagCount[]>> {
+    return await TAURI_INVOKE("list_bank_tags");
+},
+/**
+ * Merge questions from a JSON file; existing questions (matched by text) gain the imported tags,
+ * difficulty and answer
+ */
+async importQuestionBank(path: string) : Promise<Envelope<ImportSummary>> {
+    return await TAURI_INVOKE("import_question_bank", { path });
+},
+/**
+ * Write the bank (or the questions matching `query`) to a JSON file; returns the number exported
+ */
+async exportQuestionBank(path: string, query: QuestionQuery | null, requestId: string | null) : Promise<Envelope<number>> {
+    return await TAURI_INVOKE("export_question_bank", { path, query, requestId });
+},
+/**
+ * Questions due for rehearsal by `before` (an RFC 3339 interview start; now when omitted),
+ * most overdue and most frequently forgotten first
+ */
+async getRehearsalQueue(before: string | null, query: QuestionQuery | null) : Promise<Envelope<BankQuestion[]>> {
+    return await TAURI_INVOKE("get_rehearsal_queue", { before, query });
+},
+/**
+ * Record a rehearsal: recalled questions move up a box, forgotten ones start over. A provided
+ * answer becomes the new best answer.
+ */
+async recordRehearsal(id: string, recalled: boolean, answer: string | null) : Promise<Envelope<BankQuestion>> {
+    return await TAURI_INVOKE("record_rehearsal", { id, recalled, answer });
+},
+/**
+ * Create cards for the questions of a completed session that do not have one yet
+ */
+async generateFlashcards(source: FlashcardSource, provider: string, model: string, requestId: string | null) : Promise<Envelope<Flashcard[]>> {
+    return await TAURI_INVOKE("generate_flashcards", { source, provider, model, requestId });
+},
+/**
+ * Cards due now, most overdue first
+ */
+async getDueFlashcards(limit: number | null) : Promise<Envelope<Flashcard[]>> {
+    return await TAURI_INVOKE("get_due_flashcards", { limit });
+},
+/**
+ * Grade a review from 0 (blackout) to 5 (perfect recall) and reschedule the card
+ */
+async gradeFlashcard(id: string, grade: number) : Promise<Envelope<Flashcard>> {
+    return await TAURI_INVOKE("grade_flashcard", { id, grade });
+},
+async deleteFlashcard(id: string) : Promise<Envelope<null>> {
+    return await TAURI_INVOKE("delete_flashcard", { id });
+},
+/**
+ * Provide (or clear, on sign-out) the backend token used for calendar requests
+ */
+async setCalendarToken(token: string | null) : Promise<Envelope<null>> {
+    return await TAURI_INVOKE("set_calendar_token", { token });
+},
+/**
+ * Start linking a calendar: the backend returns its OAuth consent URL, which opens in the browser
+ */
+async connectCalendar(provider: CalendarProvider, token: string) : Promise<Envelope<CalendarConnection>> {
+    return await TAURI_INVOKE("connect_calendar", { provider, token });
+},
+async disconnectCalendar(provider: CalendarProvider) : Promise<Envelope<CalendarSettings>> {
+    return await TAURI_INVOKE("disconnect_calendar", { provider });
+},
+async getCalendarSettings() : Promise<Envelope<CalendarSettings>> {
+    return await TAURI_INVOKE("get_calendar_settings");
+},
+async setCalendarSettings(settings: CalendarSettings) : Promise<Envelope<CalendarSettings>> {
+    return await TAURI_INVOKE("set_calendar_settings", { settings });
+},
+/**
+ * Interviews detected in the linked calendars, soonest first; `refresh` syncs right away
+ */
+async getUpcomingInterviews(refresh: boolean | null) : Promise<Envelope<UpcomingInterview[]>> {
+    return await TAURI_INVOKE("get_upcoming_interviews", { refresh });
+},
+async getZoomCaptionSettings() : Promise<Envelope<ZoomCaptionSettings>> {
+    return await TAURI_INVOKE("get_zoom_caption_settings");
+},
+/**
+ * Save settings; enabling connects (or reconnects with the new endpoint), disabling disconnects
+ */
+async setZoomCaptionSettings(settings: ZoomCaptionSettings) : Promise<Envelope<ZoomCaptionSettings>> {
+    return await TAURI_INVOKE("set_zoom_caption_settings", { settings });
+},
+/**
+ * Connect now with the saved settings, whether or not the integration is enabled
+ */
+async connectZoomCaptions() : Promise<Envelope<ZoomCaptionStatus>> {
+    return await TAURI_INVOKE("connect_zoom_captions");
+},
+async disconnectZoomCaptions() : Promise<Envelope<null>> {
+    return await TAURI_INVOKE("disconnect_zoom_captions");
+},
+async getZoomCaptionStatus() : Promise<Envelope<ZoomCaptionStatus>> {
+    return await TAURI_INVOKE("get_zoom_caption_status");
+},
+/**
+ * Pull the Teams transcript and recordings for the meeting at `join_url` and merge them into the
+ * session's interview report. `token` is the MockMate backend token.
+ */
+async importTeamsMeetingData(sessionId: string, joinUrl: string, token: string) : Promise<Envelope<TeamsMeetingReport>> {
+    return await TAURI_INVOKE("import_teams_meeting_data", { sessionId, joinUrl, token });
+},
+/**
+ * The merged report saved by the last import for this session
+ */
+async getTeamsMeetingReport(sessionId: string) : Promise<Envelope<TeamsMeetingReport | null>> {
+    return await TAURI_INVOKE("get_teams_meeting_report", { sessionId });
+},
+async listWebhooks() : Promise<Envelope<WebhookEndpoint[]>> {
+    return await TAURI_INVOKE("list_webhooks");
+},
+/**
+ * Add an endpoint (empty id) or replace the one with the same id
+ */
+async saveWebhook(endpoint: WebhookEndpoint) : Promise<Envelope<WebhookEndpoint>> {
+    return await TAURI_INVOKE("save_webhook", { endpoint });
+},
+async deleteWebhook(id: string) : Promise<Envelope<null>> {
+    return await TAURI_INVOKE("delete_webhook", { id });
+},
+/**
+ * Send a `test` event to one endpoint and wait for the outcome (retries included)
+ */
+async testWebhook(id: string) : Promise<Envelope<WebhookDelivery>> {
+    return await TAURI_INVOKE("test_webhook", { id });
+},
+/**
+ * Most recent deliveries first
+ */
+async getWebhookDeliveries(limit: number | null) : Promise<Envelope<WebhookDelivery[]>> {
+    return await TAURI_INVOKE("get_webhook_deliveries", { limit });
+},
+async getSessionTranscript(sessionId: string) : Promise<Envelope<TranscriptLine[]>> {
+    return await TAURI_INVOKE("get_session_transcript", { sessionId });
+},
+/**
+ * Store (or with None, remove) the Notion integration token after checking it works
+ */
+async setNotionToken(token: string | null) : Promise<Envelope<NotionStatus>> {
+    return await TAURI_INVOKE("set_notion_token", { token });
+},
+async getNotionStatus() : Promise<Envelope<NotionStatus>> {
+    return await TAURI_INVOKE("get_notion_status");
+},
+/**
+ * Create a page for the session in the Notion database `database_id`
+ */
+async exportSessionToNotion(sessionId: string, databaseId: string, requestId: string | null) : Promise<Envelope<NotionExport>> {
+    return await TAURI_INVOKE("export_session_to_notion", { sessionId, databaseId, requestId });
+},
+/**
+ * Export a session as Markdown notes into an Obsidian/Logseq vault
+ */
+async exportSessionMarkdown(sessionId: string, vaultPath: string, requestId: string | null) : Promise<Envelope<MarkdownExport>> {
+    return await TAURI_INVOKE("export_session_markdown", { sessionId, vaultPath, requestId });
+},
+/**
+ * Write (or rewrite) the summary for a finished practice or interview session
+ */
+async generateSessionSummary(sessionId: string, provider: string, model: string, requestId: string | null) : Promise<Envelope<SessionSummary>> {
+    return await TAURI_INVOKE("generate_session_summary", { sessionId, provider, model, requestId });
+},
+async getSessionSummary(sessionId: string) : Promise<Envelope<SessionSummary | null>> {
+    return await TAURI_INVOKE("get_session_summary", { sessionId });
+},
+async getChatChannels() : Promise<Envelope<ChatChannels>> {
+    return await TAURI_INVOKE("get_chat_channels");
+},
+/**
+ * Add or replace a named channel's webhook URL, or remove the channel when `webhook_url` is None
+ */
+async setChatChannel(target: ChatTarget, channel: string, webhookUrl: string | null) : Promise<Envelope<ChatChannels>> {
+    return await TAURI_INVOKE("set_chat_channel", { target, channel, webhookUrl });
+},
+/**
+ * Post a session summary to a configured channel. Without `channel` the target's first channel is
+ * used; without `session_id` the most recently generated summary is sent.
+ */
+async postSessionSummary(target: ChatTarget, channel: string | null, sessionId: string | null) : Promise<Envelope<null>> {
+    return await TAURI_INVOKE("post_session_summary", { target, channel, sessionId });
+},
+/**
+ * Fetch a job posting and load it into the interview context and transcription keywords
+ */
+async importJobDescription(url: string) : Promise<Envelope<ImportedJobDescription>> {
+    return await TAURI_INVOKE("import_job_description", { url });
+},
+/**
+ * Draft a thank-you email for the interviewer(s) of a finished session
+ */
+async generateThankYouEmail(sessionId: string, provider: string, model: string, save: boolean | null, requestId: string | null) : Promise<Envelope<GeneratedDocument>> {
+    return await TAURI_INVOKE("generate_thank_you_email", { sessionId, provider, model, save, requestId });
+},
+/**
+ * Draft a cover letter for a role from the candidate profile and resume
+ */
+async generateCoverLetter(profile: CandidateProfile, job: JobTarget, provider: string, model: string, sessionId: string | null, requestId: string | null) : Promise<Envelope<GeneratedDocument>> {
+    return await TAURI_INVOKE("generate_cover_letter", { profile, job, provider, model, sessionId, requestId });
+},
+async getPacingReport(sessionId: string) : Promise<Envelope<PacingReport | null>> {
+    return await TAURI_INVOKE("get_pacing_report", { sessionId });
+},
+async getPracticeProfile(profile: string | null) : Promise<Envelope<PracticeProfile>> {
+    return await TAURI_INVOKE("get_practice_profile", { profile });
+},
+async resetPracticeProfile(profile: string | null) : Promise<Envelope<PracticeProfile>> {
+    return await TAURI_INVOKE("reset_practice_profile", { profile });
+},
+async getRubric() : Promise<Envelope<Rubric>> {
+    return await TAURI_INVOKE("get_rubric");
+},
+/**
+ * Save the rubric; `None` restores the default
+ */
+async setRubric(rubric: Rubric | null) : Promise<Envelope<Rubric>> {
+    return await TAURI_INVOKE("set_rubric", { rubric });
+},
+async getAnswerEvaluations(sessionId: string) : Promise<Envelope<AnswerEvaluation[]>> {
+    return await TAURI_INVOKE("get_answer_evaluations", { sessionId });
+},
+/**
+ * Score every answer of a session that has no evaluation yet (or all of them with `force`)
+ */
+async evaluateSessionAnswers(sessionId: string, provider: string | null, model: string | null, force: boolean | null) : Promise<Envelope<AnswerEvaluation[]>> {
+    return await TAURI_INVOKE("evaluate_session_answers", { sessionId, provider, model, force });
+},
+async getCodingSettings() : Promise<Envelope<CodingSettings>> {
+    return await TAURI_INVOKE("get_coding_settings");
+},
+async setCodingSettings(settings: CodingSettings) : Promise<Envelope<CodingSettings>> {
+    return await TAURI_INVOKE("set_coding_settings", { settings });
+},
+/**
+ * Solve a problem on request, e.g. one pasted by the user, without showing it in the AI window
+ */
+async solveCodingChallenge(problem: string, language: string | null, provider: string, model: string) : Promise<Envelope<CodingSolution>> {
+    return await TAURI_INVOKE("solve_coding_challenge", { problem, language, provider, model });
+},
+/**
+ * Solutions stored for a session; without a session id, those captured outside any session
+ */
+async listCodingSolutions(sessionId: string | null) : Promise<Envelope<CodingSolution[]>> {
+    return await TAURI_INVOKE("list_coding_solutions", { sessionId });
+},
+async listStories() : Promise<Envelope<Story[]>> {
+    return await TAURI_INVOKE("list_stories");
+},
+async addStory(input: StoryInput) : Promise<Envelope<Story>> {
+    return await TAURI_INVOKE("add_story", { input });
+},
+async updateStory(id: string, input: StoryInput) : Promise<Envelope<Story>> {
+    return await TAURI_INVOKE("update_story", { id, input });
+},
+async deleteStory(id: string) : Promise<Envelope<null>> {
+    return await TAURI_INVOKE("delete_story", { id });
+},
+async findMatchingStories(question: string, limit: number | null) : Promise<Envelope<StoryMatch[]>> {
+    return await TAURI_INVOKE("find_matching_stories", { question, limit });
+},
+/**
+ * Build a STAR answer for a behavioral question from a chosen story (or the best match)
+ */
+async generateStarAnswer(question: string, storyId: string | null, provider: string, model: string, requestId: string | null) : Promise<Envelope<StarAnswer>> {
+    return await TAURI_INVOKE("generate_star_answer", { question, storyId, provider, model, requestId });
+},
+/**
+ * Aggregate a profile's sessions into a report card with weekly trends
+ */
+async generateReportCard(profile: string | null, requestId: string | null) : Promise<Envelope<ReportCard>> {
+    return await TAURI_INVOKE("generate_report_card", { profile, requestId });
+},
+async getSystemDesignSettings() : Promise<Envelope<SystemDesignSettings>> {
+    return await TAURI_INVOKE("get_system_design_settings");
+},
+async setSystemDesignSettings(settings: SystemDesignSettings) : Promise<Envelope<SystemDesignSettings>> {
+    return await TAURI_INVOKE("set_system_design_settings", { settings });
+},
+async getPlatformCapabilities() : Promise<Envelope<PlatformCapabilities>> {
+    return await TAURI_INVOKE("get_platform_capabilities");
+},
+/**
+ * Effective capture protection of every open window
+ */
+async getCaptureProtectionStatus() : Promise<Envelope<WindowProtection[]>> {
+    return await TAURI_INVOKE("get_capture_protection_status");
+},
+/**
+ * Exercise window handle, callback and audio FFI paths and report each one
+ */
+async runPlatformSelftest() : Promise<Envelope<SelftestReport>> {
+    return await TAURI_INVOKE("run_platform_selftest");
+},
+/**
+ * Current OS theme, high-contrast, reduced-motion and reduced-transparency settings
+ */
+async getOsAppearance() : Promise<Envelope<AppearancePrefs>> {
+    return await TAURI_INVOKE("get_os_appearance");
+},
+async getPortableStatus() : Promise<Envelope<PortableStatus>> {
+    return await TAURI_INVOKE("get_portable_status");
+},
+/**
+ * Which positioning strategy the display server allows, so the UI can hide move controls
+ */
+async getPositioningStrategy() : Promise<Envelope<PositioningStrategy>> {
+    return await TAURI_INVOKE("get_positioning_strategy");
+},
+/**
+ * Which loopback, per-app capture, echo cancellation and exclusive-mode options work here
+ */
+async probeAudioCapabilities() : Promise<Envelope<AudioCapabilities>> {
+    return await TAURI_INVOKE("probe_audio_capabilities");
+},
+/**
+ * Every event the backend emits, with the JSON schema of its payload
+ */
+async listEvents() : Promise<Envelope<EventDescriptor[]>> {
+    return await TAURI_INVOKE("list_events");
+},
+/**
+ * Save several questions, answers and transcript segments in one transaction; returns the new
+ * IDs in batch order. Answers may refer to a question earlier in the batch by its `client_ref`
+ */
+async saveInterviewItems(sessionId: string, items: InterviewItem[]) : Promise<Envelope<string[]>> {
+    return await TAURI_INVOKE("save_interview_items", { sessionId, items });
+},
+/**
+ * Cancel a running long-running command; false when no request with that ID is running
+ */
+async cancelRequest(requestId: string) : Promise<Envelope<boolean>> {
+    return await TAURI_INVOKE("cancel_request", { requestId });
+},
+async getApiVersion() : Promise<Envelope<ApiVersion>> {
+    return await TAURI_INVOKE("get_api_version");
+},
+/**
+ * Operations sent but never acknowledged, oldest first
+ */
+async listPendingOperations() : Promise<Envelope<Operation[]>> {
+    return await TAURI_INVOKE("list_pending_operations");
+},
+/**
+ * Forget a pending operation, e.g. once the user confirmed it on the web dashboard
+ */
+async discardPendingOperation(key: string) : Promise<Envelope<boolean>> {
+    return await TAURI_INVOKE("discard_pending_operation", { key });
+},
+/**
+ * Record `duration_secs` (default 4s) from the microphone, play it back and score it
+ */
+async runMicSelftest(durationSecs: number | null, requestId: string | null) : Promise<Envelope<MicSelftestReport>> {
+    return await TAURI_INVOKE("run_mic_selftest", { durationSecs, requestId });
+},
+/**
+ * Connected microphones and outputs that can be captured in loopback
+ */
+async listAudioEndpoints() : Promise<Envelope<AudioEndpoint[]>> {
+    return await TAURI_INVOKE("list_audio_endpoints");
+},
+async getAudioDeviceSelection() : Promise<Envelope<AudioDeviceSelection>> {
+    return await TAURI_INVOKE("get_audio_device_selection");
+},
+/**
+ * Choose the device for `role` by endpoint ID, or go back to the OS default with `None`
+ */
+async setPreferredAudioDevice(role: DeviceRole, deviceId: string | null) : Promise<Envelope<AudioDeviceSelection>> {
+    return await TAURI_INVOKE("set_preferred_audio_device", { role, deviceId });
+},
+/**
+ * Current capture mode, hotkey and gate
+ */
+async getPushToTalk() : Promise<Envelope<PttState>> {
+    return await TAURI_INVOKE("get_push_to_talk");
+},
+/**
+ * Change the capture mode and, when given, the hotkey
+ */
+async setPushToTalk(mode: CaptureMode, hotkey: string | null) : Promise<Envelope<PttState>> {
+    return await TAURI_INVOKE("set_push_to_talk", { mode, hotkey });
+},
+/**
+ * Hold or release the gate from the UI, for a push-to-talk button
+ */
+async setPttHeld(held: boolean) : Promise<Envelope<PttState>> {
+    return await TAURI_INVOKE("set_ptt_held", { held });
+},
+/**
+ * Wake word settings and how many samples are recorded
+ */
+async getWakeWord() : Promise<Envelope<WakeWordStatus>> {
+    return await TAURI_INVOKE("get_wake_word");
+},
+/**
+ * Turn the wake word on or off and change how it behaves; enabling needs at least one sample
+ */
+async setWakeWord(enabled: boolean, phrase: string | null, sensitivity: number | null, actions: WakeAction[] | null) : Promise<Envelope<WakeWordStatus>> {
+    return await TAURI_INVOKE("set_wake_word", { enabled, phrase, sensitivity, actions });
+},
+/**
+ * Record one ~2.5s sample of me saying the wake phrase; keeps the last five
+ */
+async recordWakeWordSample(requestId: string | null) : Promise<Envelope<WakeWordStatus>> {
+    return await TAURI_INVOKE("record_wake_word_sample", { requestId });
+},
+/**
+ * Delete the recorded samples and turn the wake word off
+ */
+async clearWakeWordSamples() : Promise<Envelope<WakeWordStatus>> {
+    return await TAURI_INVOKE("clear_wake_word_samples");
+},
+/**
+ * Meeting detection settings and the calls in progress
+ */
+async getMeetingDetection() : Promise<Envelope<MeetingDetectionStatus>> {
+    return await TAURI_INVOKE("get_meeting_detection");
+},
+/**
+ * Turn meeting detection on or off and choose what starts automatically with a call
+ */
+async setMeetingDetection(enabled: boolean, autoStartTranscription: boolean | null, autoStartSession: boolean | null) : Promise<Envelope<MeetingDetectionStatus>> {
+    return await TAURI_INVOKE("set_meeting_detection", { enabled, autoStartTranscription, autoStartSession });
+},
+/**
+ * Automatic analysis settings
+ */
+async getAutoAnalysis() : Promise<Envelope<AutoAnalysisSettings>> {
+    return await TAURI_INVOKE("get_auto_analysis");
+},
+/**
+ * Turn automatic analysis on or off; pass the provider and model the analysis should use
+ */
+async setAutoAnalysis(enabled: boolean, cooldownSecs: number | null, minNewChars: number | null, provider: string | null, model: string | null) : Promise<Envelope<AutoAnalysisSettings>> {
+    return await TAURI_INVOKE("set_auto_analysis", { enabled, cooldownSecs, minNewChars, provider, model });
+},
+/**
+ * Displays the teleprompter can use, in `available_monitors` order
+ */
+async listDisplays() : Promise<Envelope<DisplayInfo[]>> {
+    return await TAURI_INVOKE("list_displays");
+},
+async getTeleprompterState() : Promise<Envelope<TeleprompterState>> {
+    return await TAURI_INVOKE("get_teleprompter_state");
+},
+/**
+ * Show the AI response window as a teleprompter on `display` (default: first secondary display)
+ */
+async startTeleprompter(display: number | null, fontSize: number | null) : Promise<Envelope<TeleprompterState>> {
+    return await TAURI_INVOKE("start_teleprompter", { display, fontSize });
+},
+/**
+ * Close the teleprompter; the next answer opens the normal AI response window again
+ */
+async stopTeleprompter() : Promise<Envelope<TeleprompterState>> {
+    return await TAURI_INVOKE("stop_teleprompter");
+},
+/**
+ * Set the scroll speed in pixels per second, or change it by `delta`
+ */
+async setTeleprompterSpeed(speed: number | null, delta: number | null) : Promise<Envelope<TeleprompterState>> {
+    return await TAURI_INVOKE("set_teleprompter_speed", { speed, delta });
+},
+/**
+ * Pause or resume scrolling; toggles when `paused` is omitted
+ */
+async setTeleprompterPaused(paused: boolean | null) : Promise<Envelope<TeleprompterState>> {
+    return await TAURI_INVOKE("set_teleprompter_paused", { paused });
+},
+/**
+ * Answer language settings and the currently detected interviewer language
+ */
+async getAnswerLanguage() : Promise<Envelope<AnswerLanguageStatus>> {
+    return await TAURI_INVOKE("get_answer_language");
+},
+/**
+ * Choose the answer language; `interviewer_language` of "auto" goes back to detection
+ */
+async setAnswerLanguage(lang: string, translateQuestions: boolean | null, interviewerLanguage: string | null) : Promise<Envelope<AnswerLanguageSettings>> {
+    return await TAURI_INVOKE("set_answer_language", { lang, translateQuestions, interviewerLanguage });
+},
+async listGlossary(profile: string | null) : Promise<Envelope<Glossary>> {
+    return await TAURI_INVOKE("list_glossary", { profile });
+},
+async addGlossaryTerm(profile: string | null, input: GlossaryEntryInput) : Promise<Envelope<GlossaryEntry>> {
+    return await TAURI_INVOKE("add_glossary_term", { profile, input });
+},
+async updateGlossaryTerm(profile: string | null, id: string, input: GlossaryEntryInput) : Promise<Envelope<GlossaryEntry>> {
+    return await TAURI_INVOKE("update_glossary_term", { profile, id, input });
+},
+async deleteGlossaryTerm(profile: string | null, id: string) : Promise<Envelope<null>> {
+    return await TAURI_INVOKE("delete_glossary_term", { profile, id });
+},
+/**
+ * Use `profile`'s glossary for transcription and prompts; recognition picks it up on the next connect
+ */
+async setActiveGlossaryProfile(profile: string) : Promise<Envelope<Glossary>> {
+    return await TAURI_INVOKE("set_active_glossary_profile", { profile });
+},
+/**
+ * The context and system prompt an answer to `question` would get right now
+ */
+async previewPromptContext(question: string | null, provider: string | null) : Promise<Envelope<ContextPreview>> {
+    return await TAURI_INVOKE("preview_prompt_context", { question, provider });
+},
+/**
+ * Recent canonical questions with every arrival linked to them
+ */
+async listRecentQuestions() : Promise<Envelope<CanonicalQuestion[]>> {
+    return await TAURI_INVOKE("list_recent_questions");
+},
+/**
+ * Forget recent questions, so the next arrival of any of them is answered again
+ */
+async clearRecentQuestions() : Promise<Envelope<null>> {
+    return await TAURI_INVOKE("clear_recent_questions");
+},
+/**
+ * Labels of a session and its answers (empty when nothing is labeled)
+ */
+async getSessionLabels(sessionId: string) : Promise<Envelope<SessionLabels>> {
+    return await TAURI_INVOKE("get_session_labels", { sessionId });
+},
+/**
+ * Set a session's tags and/or favorite flag; omitted values are kept
+ */
+async setSessionLabels(sessionId: string, tags: string[] | null, favorite: boolean | null) : Promise<Envelope<SessionLabels>> {
+    return await TAURI_INVOKE("set_session_labels", { sessionId, tags, favorite });
+},
+/**
+ * Set a saved answer's tags and/or favorite flag; omitted values are kept
+ */
+async setAnswerLabels(sessionId: string, answerId: string, tags: string[] | null, favorite: boolean | null) : Promise<Envelope<AnswerLabels>> {
+    return await TAURI_INVOKE("set_answer_labels", { sessionId, answerId, tags, favorite });
+},
+/**
+ * Labeled sessions and answers matching `filter`, most recently labeled first
+ */
+async findLabeledItems(filter: LabelFilter) : Promise<Envelope<LabeledItem[]>> {
+    return await TAURI_INVOKE("find_labeled_items", { filter });
+},
+/**
+ * Every tag in use on sessions and answers, most used first
+ */
+async listLabelTags() : Promise<Envelope<TagCount[]>> {
+    return await TAURI_INVOKE("list_label_tags");
+},
+async listSavedFilters() : Promise<Envelope<SavedFilter[]>> {
+    return await TAURI_INVOKE("list_saved_filters");
+},
+/**
+ * Save `filter` under `name`, replacing a saved filter with the same name
+ */
+async saveLabelFilter(name: string, filter: LabelFilter) : Promise<Envelope<SavedFilter>> {
+    return await TAURI_INVOKE("save_label_filter", { name, filter });
+},
+async deleteSavedFilter(id: string) : Promise<Envelope<null>> {
+    return await TAURI_INVOKE("delete_saved_filter", { id });
+},
+/**
+ * Ordered, timestamped events of a session for replay alongside its recording
+ */
+async getSessionTimeline(sessionId: string) : Promise<Envelope<SessionTimeline>> {
+    return await TAURI_INVOKE("get_session_timeline", { sessionId });
+},
+async getLiveServerSettings() : Promise<Envelope<LiveServerSettings>> {
+    return await TAURI_INVOKE("get_live_server_settings");
+},
+/**
+ * Save the live server settings and apply them; a restart or stop disconnects every client
+ */
+async setLiveServerSettings(settings: LiveServerSettings) : Promise<Envelope<LiveServerStatus>> {
+    return await TAURI_INVOKE("set_live_server_settings", { settings });
+},
+/**
+ * Replace the token; connected clients are dropped and must reconnect with the new one
+ */
+async regenerateLiveServerToken() : Promise<Envelope<LiveServerSettings>> {
+    return await TAURI_INVOKE("regenerate_live_server_token");
+},
+async getLiveServerStatus() : Promise<Envelope<LiveServerStatus>> {
+    return await TAURI_INVOKE("get_live_server_status");
+},
+async getRestApiSettings() : Promise<Envelope<RestApiSettings>> {
+    return await TAURI_INVOKE("get_rest_api_settings");
+},
+/**
+ * Save the REST API settings, then start, restart or stop the server to match
+ */
+async setRestApiSettings(settings: RestApiSettings) : Promise<Envelope<RestApiStatus>> {
+    return await TAURI_INVOKE("set_rest_api_settings", { settings });
+},
+/**
+ * Replace the token; scripts must be updated with the new one
+ */
+async regenerateRestApiToken() : Promise<Envelope<RestApiSettings>> {
+    return await TAURI_INVOKE("regenerate_rest_api_token");
+},
+async getRestApiStatus() : Promise<Envelope<RestApiStatus>> {
+    return await TAURI_INVOKE("get_rest_api_status");
+},
+async getOfflineTranscriptionStatus() : Promise<Envelope<OfflineTranscriptionStatus>> {
+    return await TAURI_INVOKE("get_offline_transcription_status");
+},
+async getTranscriptionPreset() : Promise<Envelope<TranscriptionTuning>> {
+    return await TAURI_INVOKE("get_transcription_preset");
+},
+/**
+ * Switch presets; a running Deepgram stream reconnects with the new parameters
+ */
+async setTranscriptionPreset(preset: TranscriptionPreset) : Promise<Envelope<TranscriptionTuning>> {
+    return await TAURI_INVOKE("set_transcription_preset", { preset });
+},
+async getSessionSystemPrompt(sessionId: string) : Promise<Envelope<string | null>> {
+    return await TAURI_INVOKE("get_session_system_prompt", { sessionId });
+},
+/**
+ * Set or clear (empty prompt) the custom system prompt of a session; applies right away when it is
+ * the active session
+ */
+async setSessionSystemPrompt(sessionId: string, prompt: string) : Promise<Envelope<string | null>> {
+    return await TAURI_INVOKE("set_session_system_prompt", { sessionId, prompt });
+},
+/**
+ * Open the credit checkout for the session's user in the browser and watch for the new balance.
+ * `token` is the session token from the web app; `pack` selects a credit pack on the checkout page
+ */
+async openPurchaseFlow(token: string, sessionId: string | null, pack: string | null) : Promise<Envelope<CreditPurchase>> {
+    return await TAURI_INVOKE("open_purchase_flow", { token, sessionId, pack });
+},
+/**
+ * Re-read the credit balance of a session's user (the active session when none is given)
+ */
+async refreshCreditBalance(sessionId: string | null) : Promise<Envelope<CreditBalance>> {
+    return await TAURI_INVOKE("refresh_credit_balance", { sessionId });
+},
+/**
+ * Auth state of a session authenticated with a temp token; None for other sessions
+ */
+async getSessionAuthStatus(sessionId: string) : Promise<Envelope<SessionAuthStatus | null>> {
+    return await TAURI_INVOKE("get_session_auth_status", { sessionId });
+},
+/**
+ * Authenticate a session again: with a new temp token from the web app when given, otherwise by
+ * refreshing the current one (which only works before it has expired)
+ */
+async reauthenticateSession(sessionId: string, tempToken: string | null) : Promise<Envelope<SessionAuthStatus>> {
+    return await TAURI_INVOKE("reauthenticate_session", { sessionId, tempToken });
+},
+/**
+ * Sign in through the system browser. Resolves once the browser comes back, or fails after five
+ * minutes
+ */
+async login() : Promise<Envelope<AuthStatus>> {
+    return await TAURI_INVOKE("login");
+},
+/**
+ * Sign out: revoke the refresh token (best effort) and forget every token and the account
+ */
+async logout() : Promise<Envelope<AuthStatus>> {
+    return await TAURI_INVOKE("logout");
+},
+async getAuthStatus() : Promise<Envelope<AuthStatus>> {
+    return await TAURI_INVOKE("get_auth_status");
+},
+async listAccounts() : Promise<Envelope<AccountSummary[]>> {
+    return await TAURI_INVOKE("list_accounts");
+},
+/**
+ * Create an empty profile, e.g. for a new candidate; switch to it to sign in
+ */
+async addAccount(label: string) : Promise<Envelope<AccountSummary>> {
+    return await TAURI_INVOKE("add_account", { label });
+},
+/**
+ * Make `account_id` the active profile and restart the app into it
+ */
+async switchAccount(accountId: string) : Promise<Envelope<AccountSummary>> {
+    return await TAURI_INVOKE("switch_account", { accountId });
+},
+/**
+ * Settings the app runs with, which of them an administrator has locked and where the policy came
+ * from
+ */
+async getEffectivePolicy() : Promise<Envelope<EffectivePolicy>> {
+    return await TAURI_INVOKE("get_effective_policy");
+},
+/**
+ * Grace window setting, and whether `session_id` could be let in offline right now
+ */
+async getOfflineGrace(sessionId: string | null) : Promise<Envelope<OfflineGraceStatus>> {
+    return await TAURI_INVOKE("get_offline_grace", { sessionId });
+},
+/**
+ * How many hours after the backend last confirmed a session it may still be used offline; 0 turns
+ * offline grace off
+ */
+async setOfflineGraceHours(hours: number) : Promise<Envelope<number>> {
+    return await TAURI_INVOKE("set_offline_grace_hours", { hours });
+},
+/**
+ * Answer questions found in a screenshot using vision-capable models with streaming
+ */
+async answerScreenshotQuestionsStreaming(payload: AnalyzeScreenWithAiPayload) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("answer_screenshot_questions_streaming", { payload });
+},
+/**
+ * ULTRA-ACCURATE Enhanced Q&A function with advanced prompt engineering and model optimization
+ */
+async enhancedQaWithVisionStreaming(payload: AnalyzeScreenWithAiPayload) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("enhanced_qa_with_vision_streaming", { payload });
+},
+async connectToWebSession(payload: SessionConnectionPayload, idempotencyKey: string | null) : Promise<Envelope<SessionData>> {
+    return await TAURI_INVOKE("connect_to_web_session", { payload, idempotencyKey });
+},
+async activateWebSession(payload: SessionConnectionPayload, idempotencyKey: string | null) : Promise<Envelope<SessionActivationResponse>> {
+    return await TAURI_INVOKE("activate_web_session", { payload, idempotencyKey });
+},
+async getSessionInfo(sessionId: string, token: string) : Promise<Envelope<SessionData>> {
+    return await TAURI_INVOKE("get_session_info", { sessionId, token });
+},
+async handleProtocolLaunch(sessionId: string, token: string | null, userId: string | null) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("handle_protocol_launch", { sessionId, token, userId });
+},
+async connectWithTempToken(payload: TempTokenAuthPayload, idempotencyKey: string | null) : Promise<Envelope<TempTokenAuthResponse>> {
+    return await TAURI_INVOKE("connect_with_temp_token", { payload, idempotencyKey });
+},
+async connectSession(sessionId: string, systemPrompt: string | null) : Promise<Envelope<SessionWithUser>> {
+    return await TAURI_INVOKE("connect_session", { sessionId, systemPrompt });
+},
+async activateSessionCmd(sessionId: string, idempotencyKey: string | null, systemPrompt: string | null) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("activate_session_cmd", { sessionId, idempotencyKey, systemPrompt });
+},
+async disconnectSessionCmd(sessionId: string) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("disconnect_session_cmd", { sessionId });
+},
+async validateSessionId(sessionId: string) : Promise<Envelope<SessionValidationResult>> {
+    return await TAURI_INVOKE("validate_session_id", { sessionId });
+},
+/**
+ * Deprecated: use `activate_session_cmd`
+ */
+async activateSession(sessionId: string, idempotencyKey: string | null) : Promise<boolean> {
+    return await TAURI_INVOKE("activate_session", { sessionId, idempotencyKey });
+},
+/**
+ * Deprecated: use `disconnect_session_cmd`
+ */
+async disconnectSession(sessionId: string) : Promise<string> {
+    return await TAURI_INVOKE("disconnect_session", { sessionId });
+},
+async updateSessionTimer(sessionId: string, elapsedMinutes: number, isFinal: boolean | null) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("update_session_timer", { sessionId, elapsedMinutes, isFinal });
+},
+async testDatabaseConnection() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("test_database_connection");
+},
+async getDbSessionInfo(sessionId: string) : Promise<Envelope<SessionInfo>> {
+    return await TAURI_INVOKE("get_db_session_info", { sessionId });
+},
+async saveInterviewQuestion(sessionId: string, questionNumber: number, questionText: string, category: string, difficultyLevel: string, expectedDuration: number) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("save_interview_question", { sessionId, questionNumber, questionText, category, difficultyLevel, expectedDuration });
+},
+async saveInterviewAnswer(sessionId: string, questionId: string, answerText: string, responseTime: number, aiFeedback: string | null, aiScore: number | null) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("save_interview_answer", { sessionId, questionId, answerText, responseTime, aiFeedback, aiScore });
+},
+/**
+ * One page of a session's questions; `topic` is kept for callers that predate `query`
+ */
+async getSessionQuestions(sessionId: string, topic: string | null, query: SessionItemQuery | null) : Promise<Envelope<Page<InterviewQuestion>>> {
+    return await TAURI_INVOKE("get_session_questions", { sessionId, topic, query });
+},
+/**
+ * One page of a session's answers
+ */
+async getSessionAnswers(sessionId: string, query: SessionItemQuery | null) : Promise<Envelope<Page<InterviewAnswer>>> {
+    return await TAURI_INVOKE("get_session_answers", { sessionId, query });
+},
+async getInterviewReport(sessionId: string) : Promise<Envelope<SessionReport>> {
+    return await TAURI_INVOKE("get_interview_report", { sessionId });
+},
+async finalizeSessionDuration(sessionId: string, totalMinutes: number) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("finalize_session_duration", { sessionId, totalMinutes });
+},
+async markSessionStarted(sessionId: string) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("mark_session_started", { sessionId });
+},
+async resizeMainWindow(width: number, height: number) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("resize_main_window", { width, height });
+},
+async moveWindowRelative(deltaX: number, deltaY: number) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("move_window_relative", { deltaX, deltaY });
+},
+async resizeWindowScale(width: number, height: number) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("resize_window_scale", { width, height });
+},
+async showMainWindow() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("show_main_window");
+},
+async hideMainWindow() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("hide_main_window");
+},
+async setupDpiAwarePositioning() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("setup_dpi_aware_positioning");
+},
+async getWindowInfo() : Promise<Envelope<WindowConfiguration>> {
+    return await TAURI_INVOKE("get_window_info");
+},
+async getMonitorsInfo() : Promise<Envelope<JsonValue[]>> {
+    return await TAURI_INVOKE("get_monitors_info");
+},
+async lockWindowSize(width: number, height: number) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("lock_window_size", { width, height });
+},
+async ensureWindowVisible() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("ensure_window_visible");
+},
+async diagnoseDatabase() : Promise<Envelope<DatabaseDiagnostic>> {
+    return await TAURI_INVOKE("diagnose_database");
+},
+async testSessionQuery(sessionId: string) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("test_session_query", { sessionId });
+},
+/**
+ * Export a zip with recent logs and system state for support tickets
+ */
+async exportDiagnostics(path: string, requestId: string | null) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("export_diagnostics", { path, requestId });
+},
+/**
+ * Return the last `lines` log entries, optionally only those at or above `level_filter`
+ */
+async tailLogs(lines: number | null, levelFilter: string | null) : Promise<Envelope<LogEntry[]>> {
+    return await TAURI_INVOKE("tail_logs", { lines, levelFilter });
+},
+/**
+ * Return the most recent crash report, if any, so the UI can offer to send it
+ */
+async getLastCrashReport() : Promise<Envelope<CrashReport | null>> {
+    return await TAURI_INVOKE("get_last_crash_report");
+},
+/**
+ * Get latency percentiles for all instrumented paths
+ */
+async getPerformanceMetrics() : Promise<Envelope<MetricSummary[]>> {
+    return await TAURI_INVOKE("get_performance_metrics");
+},
+/**
+ * Check every subsystem and return a per-component readiness report
+ */
+async getHealthStatus() : Promise<Envelope<HealthReport>> {
+    return await TAURI_INVOKE("get_health_status");
+},
+/**
+ * Opt in to or out of anonymized telemetry; opting out discards anything pending
+ */
+async setTelemetryEnabled(enabled: boolean) : Promise<Envelope<boolean>> {
+    return await TAURI_INVOKE("set_telemetry_enabled", { enabled });
+},
+/**
+ * Show exactly what the next telemetry upload would contain
+ */
+async getTelemetryPreview() : Promise<Envelope<TelemetryPreview>> {
+    return await TAURI_INVOKE("get_telemetry_preview");
+},
+/**
+ * Record raw and processed PCM of each active audio stream for `duration` seconds
+ */
+async startAudioDebugDump(duration: number) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("start_audio_debug_dump", { duration });
+},
+/**
+ * Recent outbound HTTP requests (newest last), optionally limited to one host
+ */
+async getRecentNetworkActivity(limit: number | null, host: string | null) : Promise<Envelope<NetworkEntry[]>> {
+    return await TAURI_INVOKE("get_recent_network_activity", { limit, host });
+},
+/**
+ * Turn request tracing on or off at runtime
+ */
+async setNetworkTracingEnabled(enabled: boolean) : Promise<Envelope<boolean>> {
+    return await TAURI_INVOKE("set_network_tracing_enabled", { enabled });
+},
+/**
+ * Timing breakdown of the last application start
+ */
+async getStartupReport() : Promise<Envelope<StartupReport>> {
+    return await TAURI_INVOKE("get_startup_report");
+},
+/**
+ * Turn local-only mode on or off; the choice persists across restarts
+ */
+async setLocalOnlyMode(enabled: boolean) : Promise<Envelope<boolean>> {
+    return await TAURI_INVOKE("set_local_only_mode", { enabled });
+},
+async getLocalOnlyMode() : Promise<Envelope<boolean>> {
+    return await TAURI_INVOKE("get_local_only_mode");
+},
+async getRetentionPolicy() : Promise<Envelope<RetentionPolicy>> {
+    return await TAURI_INVOKE("get_retention_policy");
+},
+/**
+ * Update the retention policy and apply it right away
+ */
+async setRetentionPolicy(policy: RetentionPolicy) : Promise<Envelope<CleanupResult>> {
+    return await TAURI_INVOKE("set_retention_policy", { policy });
+},
+/**
+ * Disk space used by each kind of locally stored data
+ */
+async getStorageUsage() : Promise<Envelope<StorageUsage>> {
+    return await TAURI_INVOKE("get_storage_usage");
+},
+/**
+ * Delete every recording, transcript, debug dump, crash report, log and audit file, along with
+ * any unsaved capture
+ */
+async purgeAllData() : Promise<Envelope<CleanupResult>> {
+    return await TAURI_INVOKE("purge_all_data");
+},
+/**
+ * Entries matching `filter`, newest last
+ */
+async getAuditLog(filter: AuditFilter | null) : Promise<Envelope<AuditEntry[]>> {
+    return await TAURI_INVOKE("get_audit_log", { filter });
+},
+/**
+ * Allow accessibility reading of `app`; with `remember` the approval survives restarts
+ */
+async grantAppConsent(app: string, remember: boolean) : Promise<Envelope<AppConsents>> {
+    return await TAURI_INVOKE("grant_app_consent", { app, remember });
+},
+/**
+ * Withdraw a previous approval; the app will ask again next time it is read
+ */
+async revokeAppConsent(app: string) : Promise<Envelope<AppConsents>> {
+    return await TAURI_INVOKE("revoke_app_consent", { app });
+},
+async getAppConsents() : Promise<Envelope<AppConsents>> {
+    return await TAURI_INVOKE("get_app_consents");
+},
+/**
+ * Ask the backend for a newer release; passing a channel switches to it for future checks
+ */
+async checkForUpdates(channel: UpdateChannel | null) : Promise<Envelope<UpdateCheck>> {
+    return await TAURI_INVOKE("check_for_updates", { channel });
+},
+/**
+ * Download (if not already staged) and verify the offered update, run its installer and exit
+ */
+async installUpdate() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("install_update");
+},
+/**
+ * Loaded scripts, their hooks and compile errors
+ */
+async getScriptingStatus() : Promise<Envelope<ScriptingStatus>> {
+    return await TAURI_INVOKE("get_scripting_status");
+},
+/**
+ * Turn script hooks on or off; enabling loads the scripts directory
+ */
+async setScriptingEnabled(enabled: boolean) : Promise<Envelope<ScriptingStatus>> {
+    return await TAURI_INVOKE("set_scripting_enabled", { enabled });
+},
+/**
+ * Recompile the scripts directory after the user edits scripts
+ */
+async reloadScripts() : Promise<Envelope<ScriptingStatus>> {
+    return await TAURI_INVOKE("reload_scripts");
+},
+/**
+ * Tauri command to check permissions from frontend
+ */
+async checkPermissions() : Promise<Envelope<PermissionReport>> {
+    return await TAURI_INVOKE("check_permissions");
+},
+/**
+ * Tauri command to request a permission (the microphone by default) from frontend. Depending on
+ * the OS this shows the system prompt, turns the service on, or opens the settings page.
+ */
+async requestPermissions(permission: Permission | null) : Promise<Envelope<PermissionStatus>> {
+    return await TAURI_INVOKE("request_permissions", { permission });
+},
+/**
+ * Tauri command to initialize first run permissions
+ */
+async initializeFirstRun() : Promise<Envelope<null>> {
+    return await TAURI_INVOKE("initialize_first_run");
+},
+/**
+ * Open the OS privacy settings page for `permission` (microphone by default)
+ */
+async reopenPrivacySettings(permission: string | null) : Promise<Envelope<null>> {
+    return await TAURI_INVOKE("reopen_privacy_settings", { permission });
+},
+/**
+ * Tauri command to check if Stereo Mix is enabled
+ */
+async checkStereoMixEnabled() : Promise<Envelope<boolean>> {
+    return await TAURI_INVOKE("check_stereo_mix_enabled");
+},
+/**
+ * Tauri command to automatically enable Stereo Mix
+ */
+async enableStereoMix(requestId: string | null) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("enable_stereo_mix", { requestId });
+},
+/**
+ * Tauri command to open recording devices manually
+ */
+async openRecordingDevices() : Promise<Envelope<null>> {
+    return await TAURI_INVOKE("open_recording_devices");
+},
+/**
+ * Tauri command to get system Stereo Mix capabilities
+ */
+async getStereoMixCapabilities() : Promise<Envelope<JsonValue>> {
+    return await TAURI_INVOKE("get_stereo_mix_capabilities");
+},
+/**
+ * Tauri command to get manual enablement instructions
+ */
+async getStereoMixInstructions() : Promise<Envelope<string[]>> {
+    return await TAURI_INVOKE("get_stereo_mix_instructions");
+},
+async activateStealthMode() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("activate_stealth_mode");
+},
+async deactivateStealthMode() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("deactivate_stealth_mode");
+},
+async getStealthStatus() : Promise<Envelope<StealthStatus>> {
+    return await TAURI_INVOKE("get_stealth_status");
+},
+async testStealthHotkey(hotkeyName: string) : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("test_stealth_hotkey", { hotkeyName });
+},
+async enableTaskManagerStealth() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("enable_task_manager_stealth");
+},
+async disableTaskManagerStealth() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("disable_task_manager_stealth");
+},
+async applyAdvancedStealth() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("apply_advanced_stealth");
+},
+async getTaskManagerStealthStatus() : Promise<Envelope<JsonValue>> {
+    return await TAURI_INVOKE("get_task_manager_stealth_status");
+},
+async hideFromTaskbar() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("hide_from_taskbar");
+},
+async showInTaskbar() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("show_in_taskbar");
+},
+async toggleTaskbarVisibility() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("toggle_taskbar_visibility");
+},
+async getTaskbarStatus() : Promise<Envelope<JsonValue>> {
+    return await TAURI_INVOKE("get_taskbar_status");
+},
+async isHiddenFromTaskbar() : Promise<Envelope<boolean>> {
+    return await TAURI_INVOKE("is_hidden_from_taskbar");
+},
+async activateRealStealth() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("activate_real_stealth");
+},
+async deactivateRealStealth() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("deactivate_real_stealth");
+},
+async getRealStealthStatus() : Promise<Envelope<JsonValue>> {
+    return await TAURI_INVOKE("get_real_stealth_status");
+},
+async activateDllInjectionStealth() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("activate_dll_injection_stealth");
+},
+async deactivateDllInjectionStealth() : Promise<Envelope<string>> {
+    return await TAURI_INVOKE("deactivate_dll_injection_stealth");
+},
+async getDllInjectionStealthStatus() : Promise<Envelope<JsonValue>> {
+    return await TAURI_INVOKE("get_dll_injection_stealth_status");
+}
+}
+
+/** user-defined events **/
+
+
+
+/** user-defined constants **/
+
+
+
+/** user-defined types **/
+
+/**
+ * Result of text extraction from accessibility APIs
+ */
+export type AccessibilityTextResult = { 
+/**
+ * Extracted text content
+ */
+text: string; 
+/**
+ * Source application name
+ */
+source_app: string; 
+/**
+ * Window title
+ */
+window_title: string; 
+/**
+ * Confidence score (0.0 - 1.0)
+ */
+confidence: number; 
+/**
+ * Whether this looks like a question
+ */
+is_potential_question: boolean; 
+/**
+ * Timestamp of extraction
+ */
+timestamp: number; 
+/**
+ * Extraction method used
+ */
+extraction_method: string; 
+/**
+ * Window class name
+ */
+window_class: string; 
+/**
+ * Process ID
+ */
+process_id: number; 
+/**
+ * Text length in characters
+ */
+text_length: number }
+/**
+ * The signed-in account, without any token
+ */
+export type Account = { user_id: string | null; email: string | null; name: string | null; signed_in_at: string }
+export type AccountProfile = { id: string; label: string; 
+/**
+ * Set when someone signs in within the profile
+ */
+user_id: string | null; email: string | null; created_at: string; last_used_at: string | null }
+export type AccountSummary = { profile: AccountProfile; active: boolean; 
+/**
+ * Whether the profile has a signed-in account
+ */
+signed_in: boolean; data_dir: string }
+export type ActiveMeeting = { app: MeetingApp; started_at: string; window_title: string | null }
+export type AiAnalysisResult = { generated_question: string; analysis: string; confidence: number }
+export type AiResponseData = { message_type: string; text: string | null; error: string | null }
+/**
+ * Payload of `ai-stream-complete`: the finished Markdown answer, or the analysis result when the
+ * answer came from the accessibility-based screen analysis
+ */
+export type AiStreamComplete = string | AiAnalysisResult
+/**
+ * Payload of `ai-stream-token`; `token` repeats `text` for older listeners
+ */
+export type AiStreamToken = { text: string; token: string; 
+/**
+ * Unix milliseconds
+ */
+timestamp: number }
+export type AnalyzeScreenPayload = { screen_content: string; model: string; company: string | null; position: string | null; job_description: string | null }
+export type AnalyzeScreenWithAiPayload = { model: string; provider: string; company: string | null; position: string | null; job_description: string | null; system_prompt: string | null }
+export type AnswerEvaluation = { answer_id: string; question_id: string; 
+/**
+ * Topic of the question, for trends across sessions
+ */
+topic?: QuestionTopic | null; scores: CriterionScore[]; 
+/**
+ * Weighted average of the criterion scores, on the rubric's scale
+ */
+overall: number; scale_max: number; comment: string; evaluated_at: string }
+export type AnswerLabels = ({ tags: string[]; favorite: boolean; updated_at: string | null }) & { answer_id: string }
+export type AnswerLanguageSettings = { 
+/**
+ * Language answers are written in (ISO 639-1 code)
+ */
+output_language: string; 
+/**
+ * Translate questions that are not in the output language before answering
+ */
+translate_questions: boolean; 
+/**
+ * The interviewer's language; detected from transcripts when not set
+ */
+interviewer_language: string | null }
+export type AnswerLanguageStatus = { settings: AnswerLanguageSettings; 
+/**
+ * Language detected from recent interviewer transcripts
+ */
+detected_language: string | null; 
+/**
+ * Codes `set_answer_language` accepts, with their names
+ */
+supported: ([string, string])[] }
+export type AnswerPacing = { question_id: string; question_text: string; started_at: string; 
+/**
+ * Time from the question until the next one (or session end)
+ */
+duration_seconds: number; 
+/**
+ * Time my microphone detected speech during that window
+ */
+speaking_seconds: number; words: number; words_per_minute: number | null; filler_count: number; fillers: Partial<{ [key in string]: number }> }
+export type ApiVersion = { api_version: number; 
+/**
+ * Version of the app itself, from Cargo.toml
+ */
+app_version: string; 
+/**
+ * Commands that still work but go away in a later API version
+ */
+deprecated: DeprecatedCommand[] }
+export type AppConsents = { remembered: string[]; this_session: string[]; pending: string[] }
+/**
+ * Serialized form of `AppError`, which only exists for the TypeScript bindings
+ */
+export type AppError = { code: ErrorCode; message: string; retryable: boolean; subsystem: string; remediation: string | null }
+export type AppearancePrefs = { dark: boolean; high_contrast: boolean; reduced_motion: boolean; reduced_transparency: boolean }
+export type AudioCapabilities = { os: string; 
+/**
+ * The capture backend in use, e.g. "wasapi", "pulseaudio", "coreaudio"
+ */
+backend: string; features: AudioFeatureSupport[] }
+export type AudioConfigPayload = { sample_rate: number; channels: number; buffer_size: number }
+/**
+ * Payload of `audio-debug-dump-complete`
+ */
+export type AudioDebugDumpComplete = { 
+/**
+ * Directory holding the WAV files
+ */
+path: string }
+/**
+ * Payload of `audio-devices-changed`
+ */
+export type AudioDeviceSelection = { input: RoleSelection; loopback: RoleSelection }
+export type AudioEndpoint = { id: string; name: string; role: DeviceRole; is_default: boolean }
+export type AudioFeature = 
+/**
+ * Recording what the speakers play
+ */
+"loopback_capture" | 
+/**
+ * Recording a single application's audio instead of the whole mix
+ */
+"per_app_capture" | 
+/**
+ * Acoustic echo cancellation on the microphone stream
+ */
+"echo_cancellation" | 
+/**
+ * Exclusive-mode access to the microphone
+ */
+"exclusive_mode"
+export type AudioFeatureSupport = { feature: AudioFeature; available: boolean; 
+/**
+ * Why the feature is missing, or a note on how it is provided
+ */
+reason: string | null }
+/**
+ * Payload of `audio-level`, sent about once a second while system audio is captured
+ */
+export type AudioLevel = { rms: number; peak: number; speech: boolean; 
+/**
+ * Unix milliseconds
+ */
+timestamp: number }
+export type AuditEntry = { timestamp: string; provider: string; purpose: string; category: PayloadCategory; bytes: number; session_id: string | null }
+export type AuditFilter = { provider: string | null; purpose: string | null; category: PayloadCategory | null; session_id: string | null; 
+/**
+ * RFC 3339 timestamp; only entries at or after it are returned
+ */
+since: string | null; limit: number | null }
+/**
+ * Result of the auth commands and payload of `auth-state`
+ */
+export type AuthStatus = { signed_in: boolean; account: Account | null; 
+/**
+ * Expiry of the access token held in memory, if any
+ */
+access_token_expires_at: string | null; 
+/**
+ * A browser sign-in is waiting for its callback
+ */
+login_pending: boolean }
+export type AuthStatus = 
+/**
+ * The temp token is valid
+ */
+"active" | 
+/**
+ * Refreshing failed; retrying until the token expires
+ */
+"refresh_failed" | 
+/**
+ * The token expired; `reauthenticate_session` needs a new one
+ */
+"expired"
+export type AutoAnalysisSettings = { enabled: boolean; 
+/**
+ * Minimum seconds between two automatic runs
+ */
+cooldown_secs: number; 
+/**
+ * Characters of new text that count as a significant change
+ */
+min_new_chars: number; provider: string; model: string }
+/**
+ * Payload of `auto-analysis-triggered`
+ */
+export type AutoAnalysisTriggered = { 
+/**
+ * Progress and `cancel_request` use this ID
+ */
+request_id: string; source_app: string; window_title: string; new_chars: number; 
+/**
+ * First characters of the new text
+ */
+preview: string }
+export type BankQuestion = { id: string; question: string; tags?: string[]; difficulty: Difficulty | null; best_answer: string | null; source: QuestionSource; times_asked?: number; created_at: string; updated_at: string; rehearsal: RehearsalState }
+export type CalendarConnection = { provider: CalendarProvider; auth_url: string }
+export type CalendarProvider = "google" | "outlook"
+export type CalendarSettings = { enabled: boolean; providers: CalendarProvider[]; 
+/**
+ * Minutes before start that `interview-upcoming` fires
+ */
+reminder_minutes: number; 
+/**
+ * Pre-create a backend session for each detected interview
+ */
+auto_create_sessions: boolean; 
+/**
+ * Also treat events with a meeting link but no interview wording as interviews
+ */
+include_all_meetings: boolean }
+/**
+ * Candidate details; anything left out falls back to the interview context
+ */
+export type CandidateProfile = { name: string | null; resume: string | null; experience_level: string | null; 
+/**
+ * Anything else worth mentioning (achievements, motivation)
+ */
+notes: string | null }
+export type CanonicalQuestion = { id: string; text: string; first_seen: string; 
+/**
+ * Every arrival of the question, the canonical one first
+ */
+sightings: QuestionSighting[]; 
+/**
+ * Session question ID once it was saved
+ */
+saved_question_id: string | null; answered: boolean }
+export type Capability = 
+/**
+ * Loopback capture of what the speakers play
+ */
+"system_audio_capture" | "microphone_capture" | 
+/**
+ * Reading question text out of other applications' windows
+ */
+"accessibility_reader" | 
+/**
+ * Enabling the Stereo Mix recording device
+ */
+"stereo_mix" | 
+/**
+ * Hiding MockMate's windows from screen capture and screen sharing
+ */
+"capture_protection" | "screenshot" | "session_recording" | 
+/**
+ * Task Manager / taskbar hiding
+ */
+"stealth" | 
+/**
+ * OS credential store for integration tokens
+ */
+"credential_store"
+export type CaptureMemoryStatus = { memory_limit_mb: number; captured_samples: number; spilled_samples: number }
+export type CaptureMode = 
+/**
+ * The microphone is always forwarded
+ */
+"always" | 
+/**
+ * Forwarded only while the hotkey is held
+ */
+"push_to_talk" | 
+/**
+ * Forwarded except while the hotkey is held
+ */
+"push_to_mute"
+export type CategoryUsage = { category: DataCategory; path: string; bytes: number; files: number; oldest_modified: string | null; retention_days: number | null }
+/**
+ * Configured channel names; the first one of each target is the default
+ */
+export type ChatChannels = { slack: string[]; discord: string[] }
+export type ChatTarget = "slack" | "discord"
+export type CleanupResult = { files_deleted: number; bytes_freed: number; 
+/**
+ * Files that could not be removed (e.g. the log file currently being written)
+ */
+errors: string[] }
+export type CodingSettings = { 
+/**
+ * Route detected coding problems to the structured prompt
+ */
+enabled: boolean; 
+/**
+ * Language solutions are written in unless a request names another
+ */
+language: string }
+export type CodingSolution = { id: string; session_id: string | null; 
+/**
+ * Where the problem came from, e.g. "transcript" or the accessibility source
+ */
+source: string; problem: string; language: string; approach: string; time_complexity: string; space_complexity: string; code: string; test_cases: TestCase[]; created_at: string }
+export type ComponentHealth = { component: string; status: HealthState; detail: string; latency_ms: number | null }
+export type ConsentRequest = { app: string; window_title: string; requested_at: string }
+export type ContextPreview = { budget_tokens: number; used_tokens: number; company: string | null; position: string | null; sections: ContextSection[]; 
+/**
+ * The system prompt the provider would be sent with this context
+ */
+system_prompt: string }
+export type ContextSection = { 
+/**
+ * "job_description", "resume", "recent_transcript" or "prior_qa"
+ */
+name: string; content: string; 
+/**
+ * Estimated tokens of `content`
+ */
+tokens: number; 
+/**
+ * Whether part of the source was left out to fit the budget
+ */
+truncated: boolean }
+export type CrashReport = { timestamp: string; app_version: string; os: string; arch: string; thread: string; 
+/**
+ * True when the panic happened on a tokio runtime thread (e.g. a spawned task)
+ */
+in_async_task: boolean; message: string; location: string | null; backtrace: string; recent_logs: LogEntry[]; file_path?: string | null }
+/**
+ * Payload of `credit-balance`
+ */
+export type CreditBalance = { session_id: string; credits: number; 
+/**
+ * Balance before this refresh, when one was known
+ */
+previous_credits: number | null; 
+/**
+ * "manual", "purchase_poll" or "deep_link"
+ */
+source: string; refreshed_at: string }
+/**
+ * Payload of `credit-purchase`
+ */
+export type CreditPurchase = { session_id: string; 
+/**
+ * "opened", "completed" or "cancelled"
+ */
+status: string; checkout_url: string | null }
+export type CriterionScore = { criterion: string; score: number; comment: string }
+export type DataCategory = "recordings" | "transcripts" | "audio_debug" | "crash_reports" | 
+/**
+ * Rotated by the logging module itself; only counted and purged here
+ */
+"logs" | 
+/**
+ * Outbound-data audit log; kept until purged
+ */
+"audit_log" | 
+/**
+ * Raw system audio spilled to disk by long captures; only files whose capture is gone are removed
+ */
+"capture_spill"
+export type DatabaseDiagnostic = { database_connected: boolean; connection_error: string | null; tables_exist: boolean; sample_data_count: number | null; test_query_result: string | null }
+/**
+ * Payload of `deepgram-status`
+ */
+export type DeepgramStatus = { 
+/**
+ * "connected", "disconnected" or "stopped"
+ */
+status: string; model?: string | null; 
+/**
+ * Unix milliseconds
+ */
+timestamp: number }
+export type DeepgramTranscriptionResult = { text: string; is_final: boolean; confidence: number; timestamp: string }
+/**
+ * Payload of `deprecated-command`
+ */
+export type DeprecatedCommand = { command: string; replacement: string; 
+/**
+ * API version that no longer has the command
+ */
+removed_in: number }
+export type DeviceRole = 
+/**
+ * The microphone
+ */
+"input" | 
+/**
+ * The output device whose playback is captured as system audio
+ */
+"loopback"
+export type DiagramFormat = "mermaid" | "ascii"
+export type Difficulty = "easy" | "medium" | "hard"
+export type DisplayInfo = { index: number; name: string; x: number; y: number; width: number; height: number; scale_factor: number; is_primary: boolean }
+export type DocumentKind = "thank_you_email" | "cover_letter"
+export type EffectivePolicy = { local_only: EffectiveSetting<boolean>; clipboard_monitoring: EffectiveSetting<boolean>; backend_url: EffectiveSetting<string>; 
+/**
+ * Policy locations that set at least one value, highest precedence first
+ */
+sources: string[]; 
+/**
+ * Policy that was found but could not be read; it is ignored
+ */
+problems: string[] }
+export type EffectiveSetting<T> = { value: T; source: SettingSource; 
+/**
+ * Set by policy, so the app refuses to change it
+ */
+locked: boolean }
+export type Envelope<T> = { ok: boolean; data: T | null; error: AppError | null; 
+/**
+ * Appears in the backend log next to a failure, so a UI error can be matched to its log line
+ */
+request_id: string; duration_ms: number }
+export type ErrorCode = "not_found" | "invalid_input" | 
+/**
+ * The feature does not exist on this OS or in this mode
+ */
+"unsupported" | 
+/**
+ * The OS or the user refused access (microphone, screen recording, files)
+ */
+"permission_denied" | 
+/**
+ * Local-only mode forbids the cloud request
+ */
+"privacy_blocked" | 
+/**
+ * Sign-in is missing or expired
+ */
+"unauthorized" | "network" | "timeout" | 
+/**
+ * A service or device is busy, not configured or not running
+ */
+"unavailable" | 
+/**
+ * Stopped by `cancel_request`
+ */
+"cancelled" | 
+/**
+ * The command needs an activated session and there is none (or it is another one)
+ */
+"no_active_session" | "internal"
+/**
+ * Payload of `transcription-error` and `websocket-error`
+ */
+export type ErrorPayload = { error: string }
+/**
+ * An emitted event as reported by `list_events`
+ */
+export type EventDescriptor = { name: string; description: string; 
+/**
+ * JSON schema of the payload; the `null` type for events that carry none
+ */
+payload_schema: JsonValue }
+export type FeatureSupport = { capability: Capability; available: boolean; fallback: string | null }
+export type Flashcard = { id: string; source: FlashcardSource; question: string; my_answer: string | null; model_answer: string; created_at: string; schedule: ReviewSchedule }
+export type FlashcardSource = 
+/**
+ * Backend interview session id
+ */
+{ kind: "session"; id: string } | 
+/**
+ * Local practice session id
+ */
+{ kind: "practice"; id: string }
+export type GenerateAnswerPayload = { question: string; model: string; provider: string; company: string | null; position: string | null; job_description: string | null; 
+/**
+ * Where the question came from ("transcript", "manual", "accessibility"), for deduplication
+ */
+source?: string | null }
+export type GeneratedDocument = { kind: DocumentKind; session_id: string | null; markdown: string; saved_path: string | null; generated_at: string }
+export type Glossary = { profile: string; entries: GlossaryEntry[] }
+export type GlossaryEntry = { id: string; term: string; 
+/**
+ * What the term means, for the AI's context
+ */
+definition?: string | null; 
+/**
+ * How answers should say it, e.g. "Kestrel (our payments platform)"
+ */
+preferred_phrasing?: string | null; 
+/**
+ * Ways transcription tends to mishear the term; replaced with the term
+ */
+aliases?: string[]; created_at: string; updated_at: string }
+/**
+ * New or edited glossary entry; on update, only provided fields change
+ */
+export type GlossaryEntryInput = { term: string | null; definition: string | null; preferred_phrasing: string | null; aliases: string[] | null }
+export type HealthReport = { 
+/**
+ * Worst status among components that are expected to be running
+ */
+overall: HealthState; components: ComponentHealth[]; checked_at: string }
+export type HealthState = 
+/**
+ * Component is running and responding
+ */
+"ok" | 
+/**
+ * Component works but something is off (slow, missing optional config)
+ */
+"degraded" | 
+/**
+ * Component is expected to work but failed its check
+ */
+"down" | 
+/**
+ * Component is not running right now (e.g. capture not started yet)
+ */
+"inactive"
+export type ImportSummary = { added: number; updated: number; skipped: number }
+export type ImportedJobDescription = { url: string; title: string | null; company: string | null; location: string | null; description: string; keywords: string[]; 
+/**
+ * "json_ld" when structured data was found, otherwise "html"
+ */
+extraction: string }
+export type InterviewAnswer = { id: string; question_id: string; session_id: string; answer_text: string | null; response_time: number | null; ai_feedback: string | null; ai_score: number | null; answered_at: string; created_at: string }
+export type InterviewConfig = { job_title: string; company_name: string | null; difficulty: string }
+export type InterviewContextPayload = { company: string | null; position: string | null; job_description: string | null; 
+/**
+ * Custom system prompt for the active session; an empty string clears it
+ */
+system_prompt?: string | null }
+/**
+ * One entry of a `save_interview_items` batch
+ */
+export type InterviewItem = { kind: "question"; client_ref?: string | null; question_number: number; question_text: string; category: string; difficulty_level: string; expected_duration: number } | { kind: "answer"; question_id: string; answer_text: string; response_time?: number | null; ai_feedback?: string | null; ai_score?: number | null } | { kind: "transcript_segment"; source: string; speaker?: string | null; text: string; timestamp: string }
+export type InterviewQuestion = { id: string; session_id: string; question_number: number; question_text: string; category: string; 
+/**
+ * Behavioral / coding / system design / domain, classified when the question is saved
+ */
+topic?: QuestionTopic | null; difficulty_level: string; expected_duration: number; asked_at: string; created_at: string }
+/**
+ * Target role; anything left out falls back to the interview context
+ */
+export type JobTarget = { title: string | null; company: string | null; description: string | null }
+export type JsonValue = null | boolean | number | string | JsonValue[] | Partial<{ [key in string]: JsonValue }>
+export type LabelFilter = { 
+/**
+ * Sessions, answers, or both when unset
+ */
+kind: LabeledKind | null; 
+/**
+ * Items must carry every listed tag
+ */
+tags?: string[]; 
+/**
+ * Items must carry at least one of these tags
+ */
+any_tags?: string[]; favorite: boolean | null; 
+/**
+ * Case-insensitive substring match on the session title
+ */
+search: string | null; 
+/**
+ * Only items labeled at or after this time
+ */
+since: string | null }
+export type LabeledItem = ({ tags: string[]; favorite: boolean; updated_at: string | null }) & { kind: LabeledKind; session_id: string; answer_id: string | null; title: string | null }
+export type LabeledKind = "session" | "answer"
+export type LiveServerSettings = { enabled: boolean; port: number; 
+/**
+ * Listen on every interface so other machines on the network can connect
+ */
+allow_lan: boolean; 
+/**
+ * Clients must present this token
+ */
+token: string }
+export type LiveServerStatus = { running: boolean; 
+/**
+ * e.g. "ws://127.0.0.1:18480"
+ */
+address: string | null; clients: number; messages_sent: number; last_error: string | null }
+/**
+ * A single captured log record
+ */
+export type LogEntry = { timestamp: string; level: string; target: string; message: string }
+export type MarkKind = "session_started" | "session_ended" | "recording_started" | "recording_stopped" | "timer"
+export type MarkdownExport = { session_note: string; question_notes: string[] }
+export type MatchKind = "id" | "name" | 
+/**
+ * Closest name; the stored ID is updated to the match
+ */
+"similar_name"
+export type MeetingApp = "teams" | "zoom" | "google_meet"
+export type MeetingDetectionStatus = { enabled: boolean; auto_start_transcription: boolean; auto_start_session: boolean; 
+/**
+ * False where call activity cannot be read (macOS)
+ */
+supported: boolean; active: ActiveMeeting[] }
+/**
+ * Payload of `meeting-started` and `meeting-ended`
+ */
+export type MeetingEvent = { app: MeetingApp; started_at: string; 
+/**
+ * Set on `meeting-ended`
+ */
+ended_at: string | null; window_title: string | null; 
+/**
+ * The frontend should turn on system-audio transcription
+ */
+auto_start_transcription: boolean; 
+/**
+ * The frontend should start the connected session and its timer
+ */
+auto_start_session: boolean }
+/**
+ * Percentile summary for one metric, in milliseconds
+ */
+export type MetricSummary = { name: string; count: number; mean_ms: number; p50_ms: number; p90_ms: number; p95_ms: number; p99_ms: number; max_ms: number }
+export type MicSelftestReport = { passed: boolean; 
+/**
+ * Seconds of audio actually recorded
+ */
+duration_secs: number; 
+/**
+ * Level of the loudest tenth of the clip, i.e. my speech, in dBFS
+ */
+level_dbfs: number; 
+/**
+ * Level of the quietest tenth of the clip, in dBFS
+ */
+noise_floor_dbfs: number; snr_db: number; peak_dbfs: number; 
+/**
+ * Share of samples at full scale, 0-1
+ */
+clipping_ratio: number; played_back: boolean; 
+/**
+ * What to change, empty when the test passed cleanly
+ */
+recommendations: string[] }
+export type ModelInfo = { id: string; name: string; provider: string; icon: string }
+export type MonitorInfo = { index: number; x: number; y: number; width: number; height: number; primary: boolean }
+/**
+ * Serialized form of `PhysicalSize<u32>`, for the TypeScript bindings
+ */
+export type MonitorSize = { width: number; height: number }
+export type NetworkEntry = { timestamp: string; method: string; host: string; 
+/**
+ * Path with content-bearing segments replaced by ":redacted"
+ */
+path: string; 
+/**
+ * HTTP status, or None when the request failed before a response
+ */
+status: number | null; 
+/**
+ * Time until response headers were received
+ */
+duration_ms: number; 
+/**
+ * Consecutive failed requests to the same host immediately before this one
+ */
+retry_count: number; error: string | null }
+export type NotionExport = { page_id: string; url: string | null; blocks: number }
+export type NotionStatus = { connected: boolean; workspace_bot: string | null }
+export type OfflineGraceStatus = { grace_hours: number; 
+/**
+ * The session could be let in offline right now
+ */
+grant: OfflineGrant | null; 
+/**
+ * Why it could not, when a session was given
+ */
+unavailable_reason: string | null }
+/**
+ * A session let in without the backend
+ */
+export type OfflineGrant = { session_id: string; user_id: string | null; 
+/**
+ * Balance when the entitlement was issued
+ */
+credits_available: number | null; 
+/**
+ * When the backend last confirmed the session
+ */
+issued_at: string; 
+/**
+ * End of the grace window
+ */
+usable_until: string }
+export type OfflineTranscriptionStatus = { 
+/**
+ * The Vosk library and a model are installed
+ */
+available: boolean; active: boolean; library_path: string | null; model_path: string | null; reason: string | null; since: string | null }
+export type Operation = { key: string; command: string; session_id: string; status: OperationStatus; started_at: string; attempts: number; acknowledged_at: string | null; 
+/**
+ * What the command returned, replayed to retries
+ */
+response: JsonValue | null }
+export type OperationStatus = 
+/**
+ * Sent, but no response has been acknowledged yet
+ */
+"pending" | "succeeded"
+/**
+ * Payload of `pacing-feedback`
+ */
+export type PacingFeedback = { question_id: string; elapsed_seconds: number; speaking_seconds: number; words_per_minute: number | null; filler_count: number; 
+/**
+ * Set when there is something worth telling me right now
+ */
+message: string | null }
+export type PacingReport = { answers: AnswerPacing[]; total_speaking_seconds: number; average_words_per_minute: number | null; total_fillers: number; 
+/**
+ * Fillers per 100 words across the session
+ */
+filler_rate: number | null; longest_answer_seconds: number }
+/**
+ * One page of a paged query
+ */
+export type Page<T> = { items: T[]; 
+/**
+ * Matching items across all pages
+ */
+total: number; offset: number; has_more: boolean }
+export type PayloadCategory = 
+/**
+ * Prompts, transcripts, answers
+ */
+"text" | 
+/**
+ * Raw PCM streamed for transcription
+ */
+"audio" | 
+/**
+ * Screenshots sent for analysis
+ */
+"image" | 
+/**
+ * Ids, status updates and counters without user content
+ */
+"metadata"
+/**
+ * OS permissions MockMate depends on
+ */
+export type Permission = "microphone" | "screen_recording" | 
+/**
+ * Reading other applications' windows (macOS Accessibility, the AT-SPI bus on Linux)
+ */
+"accessibility"
+export type PermissionReport = { 
+/**
+ * `std::env::consts::OS`
+ */
+os: string; permissions: PermissionStatus[]; 
+/**
+ * Every permission is usable
+ */
+ready: boolean }
+/**
+ * Payload of the `permission-revoked` event
+ */
+export type PermissionRevokedEvent = { 
+/**
+ * "microphone", "camera", ...
+ */
+permission: string; 
+/**
+ * Where the denial was detected, e.g. "capture_start" or "capture_loop"
+ */
+source: string; detail: string; 
+/**
+ * Subsystems that were stopped because they depend on the permission
+ */
+paused: string[]; timestamp: number }
+export type PermissionState = "granted" | "denied" | 
+/**
+ * Not decided yet; the OS asks on first use (or, for the Linux screen cast portal, on every use)
+ */
+"not_determined" | 
+/**
+ * This OS does not gate the feature
+ */
+"not_required" | 
+/**
+ * The service behind the permission is missing (no AT-SPI bus, no desktop portal)
+ */
+"unavailable"
+export type PermissionStatus = { permission: Permission; state: PermissionState; usable: boolean; needed_for: string; 
+/**
+ * Set whenever the user can do something about the state
+ */
+remediation: Remediation | null }
+export type PlatformCapabilities = { 
+/**
+ * `std::env::consts::OS`, e.g. "windows", "macos", "linux"
+ */
+os: string; arch: string; features: FeatureSupport[] }
+export type PortableStatus = { enabled: boolean; data_dir: string; 
+/**
+ * Whether mockmate:// links open sessions; when false the session ID has to be typed in
+ */
+protocol_links: boolean }
+/**
+ * How windows can be placed on the current display server
+ */
+export type PositioningStrategy = 
+/**
+ * Global screen coordinates: Windows, macOS and X11 (XWayland included)
+ */
+"absolute" | 
+/**
+ * Wayland has no global coordinates for clients. The compositor places top-level windows,
+ * moves are ignored, and secondary windows are anchored as transient children of the main one
+ */
+"compositor_relative"
+export type PracticeConfig = { 
+/**
+ * "openai" or "pollinations"
+ */
+provider: string; model: string; 
+/**
+ * Falls back to the current interview context when omitted
+ */
+company: string | null; position: string | null; job_description: string | null; 
+/**
+ * e.g. "junior", "mid", "senior"
+ */
+difficulty: string | null; question_count: number | null; 
+/**
+ * Follow-ups the interviewer may ask per planned question
+ */
+max_follow_ups: number | null; 
+/**
+ * Profile whose per-topic performance drives adaptive planning
+ */
+profile?: string | null; 
+/**
+ * Weight topics and difficulty by past performance (on by default)
+ */
+adaptive?: boolean | null }
+export type PracticeEvaluation = { score: number; feedback: string }
+export type PracticeProfile = { name: string; topics: Partial<{ [key in QuestionTopic]: TopicStats }>; updated_at: string | null }
+export type PracticeReport = { session_id: string; company: string | null; position: string | null; 
+/**
+ * Practice profile the session counted towards; reports from before profiles belong to the default
+ */
+profile?: string | null; started_at: string; completed_at: string; 
+/**
+ * Average of the turn scores, 0-10
+ */
+overall_score: number; summary: string; strengths: string[]; improvements: string[]; turns: PracticeTurn[] }
+export type PracticeSession = { id: string; config: PracticeConfig; state: PracticeState; planned_questions: string[]; 
+/**
+ * Topic of each planned question, when the interviewer labelled it
+ */
+planned_topics?: (QuestionTopic | null)[]; 
+/**
+ * Index of the planned question currently being discussed
+ */
+question_index: number; follow_ups_asked: number; turns: PracticeTurn[]; 
+/**
+ * Final transcripts heard since the current question was asked
+ */
+heard: string; started_at: string }
+export type PracticeState = "awaiting_answer" | "evaluating" | "completed"
+/**
+ * Result of answering the current question
+ */
+export type PracticeStep = { evaluation: PracticeEvaluation; next_question: PracticeTurn | null; completed: boolean }
+export type PracticeTurn = { kind: TurnKind; question: string; topic?: QuestionTopic | null; answer: string | null; 
+/**
+ * 1-10
+ */
+score: number | null; feedback: string | null }
+/**
+ * Payload of `practice-update`
+ */
+export type PracticeUpdate = { session_id: string; state: PracticeState; turn: PracticeTurn | null }
+export type ProtectionState = 
+/**
+ * Screen capture and screen shares see nothing where the window is
+ */
+"protected" | "unprotected" | 
+/**
+ * This OS cannot hide windows from capture
+ */
+"unsupported" | 
+/**
+ * Protection was requested but the OS call failed (e.g. Windows older than 10 2004)
+ */
+"failed"
+export type ProviderInfo = { id: string; name: string; description: string }
+export type ProviderWarmUp = { provider: string; ok: boolean; elapsed_ms: number; error: string | null }
+/**
+ * Payload of `ptt-state`
+ */
+export type PttState = { mode: CaptureMode; hotkey: string; 
+/**
+ * The hotkey or the UI button is held
+ */
+held: boolean; 
+/**
+ * Microphone audio is reaching transcription
+ */
+forwarding: boolean }
+/**
+ * Payload of `question-deduplicated`
+ */
+export type QuestionDeduplicated = { canonical_id: string; canonical_text: string; duplicate_text: string; source: string; similarity: number }
+/**
+ * New or edited question from the UI or an import file
+ */
+export type QuestionInput = { question: string | null; tags: string[] | null; difficulty: Difficulty | null; best_answer: string | null }
+export type QuestionPayload = { session_id: string; question: string }
+export type QuestionQuery = { 
+/**
+ * Questions must carry every listed tag
+ */
+tags: string[] | null; difficulty: Difficulty | null; source: QuestionSource | null; 
+/**
+ * Questions tagged with this topic (saved questions are tagged when categorized)
+ */
+topic: QuestionTopic | null; 
+/**
+ * Case-insensitive substring match on the question and best answer
+ */
+search: string | null; limit: number | null }
+export type QuestionSighting = { text: string; 
+/**
+ * "transcript", "accessibility", "manual", ...
+ */
+source: string; seen_at: string; 
+/**
+ * 1.0 for the canonical question itself
+ */
+similarity: number }
+export type QuestionSource = 
+/**
+ * Added or imported by the user
+ */
+"curated" | 
+/**
+ * Saved automatically when it came up in an interview session
+ */
+"asked"
+export type QuestionTopic = "behavioral" | "coding" | "system_design" | 
+/**
+ * Role- or industry-specific knowledge
+ */
+"domain"
+/**
+ * Payload of `question-translated`
+ */
+export type QuestionTranslation = { original: string; translated: string; source_language: string; target_language: string }
+export type ReconciledQuestion = { question_id: string; question_text: string; asked_at: string; 
+/**
+ * Transcript speaker who asked the question; None when no turn matched
+ */
+asked_by: string | null; transcript_text: string | null; transcript_offset_ms: number | null; match_score: number; 
+/**
+ * Speaker who replied (normally the candidate) and what they said
+ */
+answered_by: string | null; spoken_answer: string | null }
+export type RecordingResult = { session_id: string | null; path: string; duration_ms: number; video_frames: number; audio_samples: number }
+export type RecordingSettings = { 
+/**
+ * Record automatically whenever a session is activated
+ */
+enabled: boolean; target: RecordingTarget; fps: number; include_microphone: boolean }
+export type RecordingStatus = { recording: boolean; session_id: string | null; path: string | null; started_at: string | null; elapsed_ms: number }
+export type RecordingTarget = 
+/**
+ * None means the primary monitor
+ */
+{ kind: "monitor"; index: number | null } | { kind: "window"; hwnd: number }
+export type RehearsalState = { 
+/**
+ * Leitner box, 0 = rehearse tomorrow
+ */
+box_index: number; due_at: string; last_reviewed_at: string | null; reviews: number; lapses: number }
+/**
+ * Release description served by the backend for a channel
+ */
+export type ReleaseManifest = { version: string; channel: UpdateChannel; notes: string | null; pub_date: string | null; 
+/**
+ * Installer download URL (.msi or NSIS .exe)
+ */
+url: string; 
+/**
+ * Base64 Ed25519 signature of the installer bytes
+ */
+signature: string; 
+/**
+ * Share of installs (0-100) the release is currently offered to
+ */
+rollout_percentage?: number; 
+/**
+ * Installs older than this get the release regardless of the rollout
+ */
+min_supported_version: string | null }
+export type Remediation = { instructions: string; 
+/**
+ * Settings URL for the page to change (ms-settings: on Windows, x-apple.systempreferences: on macOS)
+ */
+deep_link: string | null }
+export type ReportCard = { profile: string; generated_at: string; sessions: number; scored_answers: number; average_score: number | null; score_trend: Trend; topics: TopicCard[]; words_per_minute: Trend; filler_rate: Trend; 
+/**
+ * Newest first
+ */
+recent_sessions: SessionPoint[] }
+/**
+ * Payload of `progress`, reported by long-running commands under their request ID
+ */
+export type RequestProgress = { request_id: string; command: string; 
+/**
+ * "started", a command-specific stage, then "completed", "failed" or "cancelled"
+ */
+stage: string; 
+/**
+ * 0-100 when the command can estimate it
+ */
+percent: number | null; 
+/**
+ * True on the final event
+ */
+done: boolean }
+/**
+ * Payload of `ai-response-update`
+ */
+export type ResponseUpdate = { 
+/**
+ * "stream", "stream-token", "complete" or "error"
+ */
+message_type: string; text: string | null; error: string | null }
+export type RestApiSettings = { enabled: boolean; port: number; 
+/**
+ * Sent by clients as `Authorization: Bearer <token>`
+ */
+token: string }
+export type RestApiStatus = { running: boolean; 
+/**
+ * e.g. "http://127.0.0.1:18481"
+ */
+address: string | null; requests_served: number; last_error: string | null }
+/**
+ * Days to keep each kind of data; `None` keeps it until purged
+ */
+export type RetentionPolicy = { recordings_days: number | null; transcripts_days: number | null; audio_debug_days: number | null; crash_reports_days: number | null }
+export type ReviewSchedule = { ease_factor: number; interval_days: number; repetitions: number; due_at: string; last_grade: number | null; last_reviewed_at: string | null }
+export type RoleSelection = { role: DeviceRole; 
+/**
+ * What was chosen; none means the OS default
+ */
+preferred: StoredDevice | null; 
+/**
+ * Endpoint capture opens; none means the OS default
+ */
+resolved: AudioEndpoint | null; matched_by: MatchKind | null }
+export type Rubric = { criteria: RubricCriterion[]; 
+/**
+ * Scores run from 1 to this value
+ */
+scale_max: number; 
+/**
+ * Score answers automatically as they are saved
+ */
+auto_evaluate: boolean; 
+/**
+ * Provider and model used for automatic evaluation
+ */
+provider: string; model: string }
+export type RubricCriterion = { 
+/**
+ * Stable identifier used in stored scores, e.g. "clarity"
+ */
+key: string; name: string; 
+/**
+ * What a strong answer looks like for this criterion; shown to the model
+ */
+description: string; weight?: number }
+export type SavedFilter = { id: string; name: string; filter: LabelFilter; created_at: string }
+/**
+ * A captured image as base64 PNG
+ */
+export type Screenshot = { 
+/**
+ * Base64 encoded PNG
+ */
+screenshot: string; width: number; height: number; 
+/**
+ * "monitor", "window" or "region"
+ */
+source: string }
+export type ScreenshotResponse = { screenshot: string; width: number; height: number }
+export type ScriptInfo = { name: string; 
+/**
+ * Hook functions the script defines
+ */
+hooks: string[]; 
+/**
+ * Compile error, if the script failed to load
+ */
+error: string | null }
+/**
+ * Payload of `script-notification`
+ */
+export type ScriptNotification = { title: string; body: string }
+/**
+ * Payload of `script-event`, raised by a user script's `emit_ui`
+ */
+export type ScriptUiEvent = { name: string; payload: JsonValue }
+export type ScriptingStatus = { enabled: boolean; scripts_dir: string; scripts: ScriptInfo[]; commands: string[] }
+export type Section = "overview" | "diagram" | "components" | "talking_points"
+/**
+ * Payload of `system-design-section`
+ */
+export type SectionChunk = { section: Section; text: string }
+export type SelftestCheck = { name: string; passed: boolean; detail: string }
+export type SelftestReport = { os: string; 
+/**
+ * Architecture this build was compiled for
+ */
+arch: string; 
+/**
+ * Architecture of the machine, when the OS reports it
+ */
+native_arch: string | null; 
+/**
+ * True when an x64 build runs under emulation on an ARM64 machine
+ */
+emulated: boolean; passed: boolean; checks: SelftestCheck[] }
+export type Session = { id: string; user_id: string; job_title: string; job_description: string; difficulty: string; session_type: string; status: string; resume_content: string | null; created_at: string; desktop_connected_at: string | null; session_started_at: string | null; interview_duration: number | null; credits_used: number | null }
+export type SessionActivationResponse = { success: boolean; message: string; session: SessionData | null; remaining_credits: number | null; 
+/**
+ * Signed by the backend; kept for offline grace
+ */
+entitlement?: SignedEntitlement | null; 
+/**
+ * Let in from the cached entitlement because the backend could not be reached
+ */
+offline?: boolean }
+/**
+ * Payload of `session-auth` and result of the session auth commands
+ */
+export type SessionAuthStatus = { session_id: string; status: AuthStatus; user_id: string | null; 
+/**
+ * None when the token's lifetime is unknown; it is then never refreshed
+ */
+expires_at: string | null; authenticated_at: string; 
+/**
+ * "authenticated", "refreshed", "reauthenticated", or why refreshing failed
+ */
+reason: string }
+export type SessionConnectionPayload = { session_id: string; token: string; user_id: string; 
+/**
+ * Custom system prompt applied to every generation in the session; an empty string clears it
+ */
+system_prompt?: string | null }
+export type SessionData = { id: string; job_title: string; job_description: string | null; difficulty_level: string; interview_type: string; estimated_duration_minutes: number; status: string; created_at: string; desktop_connected: boolean | null }
+export type SessionInfo = { id: string; job_title: string; user_name: string; difficulty: string; credits_available: number; status: string }
+/**
+ * Paging, ordering and filters for session question/answer queries. Every field is optional;
+ * the default returns the whole session, oldest first
+ */
+export type SessionItemQuery = { 
+/**
+ * No limit when unset
+ */
+limit: number | null; offset: number; order: SortOrder; 
+/**
+ * Questions only: the category the question was saved with
+ */
+category: string | null; 
+/**
+ * Questions only
+ */
+topic: QuestionTopic | null; 
+/**
+ * Only items at or after this time
+ */
+since: string | null; 
+/**
+ * Only items before this time
+ */
+until: string | null; 
+/**
+ * Questions only: true for answered questions, false for unanswered ones
+ */
+answered: boolean | null }
+export type SessionKind = "practice" | "interview"
+export type SessionLabels = ({ tags: string[]; favorite: boolean; updated_at: string | null }) & { session_id: string; title: string | null; answers: AnswerLabels[] }
+/**
+ * Payload of `session-launch`. A launch from the web app's deep link carries `token` and
+ * `user_id`; a temp-token launch that is not authenticated automatically carries `temp_token`
+ * and `auto_connect` for the frontend to finish
+ */
+export type SessionLaunch = { 
+/**
+ * "session-launch" on temp-token launches
+ */
+type?: string | null; session_id: string; token?: string | null; user_id?: string | null; temp_token?: string | null; auto_connect?: boolean | null; launched_at: string }
+export type SessionPoint = { session_id: string; kind: SessionKind; date: string; title: string | null; 
+/**
+ * Average answer score on a 0-10 scale, when any answer was scored
+ */
+score: number | null; answers: number; topics: Partial<{ [key in QuestionTopic]: number }>; words_per_minute: number | null; filler_rate: number | null }
+export type SessionReport = { session: Session; user: User; questions: InterviewQuestion[]; answers: InterviewAnswer[]; total_questions: number; total_answers: number; average_response_time: number; average_score: number; 
+/**
+ * Talk time, pace and filler words per answer, when measured locally
+ */
+pacing?: PacingReport | null; 
+/**
+ * Rubric scores and comments per answer, when evaluated
+ */
+evaluations?: AnswerEvaluation[] }
+export type SessionSummary = { session_id: string; title: string; 
+/**
+ * 0-10 when the session was scored
+ */
+overall_score: number | null; questions: number; summary: string; strengths: string[]; improvements: string[]; generated_at: string }
+export type SessionTimeline = { session_id: string; 
+/**
+ * Time `offset_ms` is measured from
+ */
+origin: string | null; 
+/**
+ * Whether `origin` is the start of a recording of this session
+ */
+recording_aligned: boolean; 
+/**
+ * Milliseconds from the origin to the last event
+ */
+duration_ms: number; 
+/**
+ * False when questions and answers could not be loaded from the database
+ */
+database_available: boolean; events: TimelineEvent[] }
+export type SessionValidationResult = { valid: boolean; message: string }
+export type SessionWithUser = { session_id: string; session_name: string; company_name: string | null; job_title: string; job_description: string | null; status: string; desktop_connected: boolean; created_at: string; started_at: string | null; user_details: UserInfo; interview_config: InterviewConfig; credits_available: number }
+export type SettingSource = "policy" | "user" | "default"
+/**
+ * Payload of `ai-response-geometry`
+ */
+export type SettledGeometry = { width: number; height: number; x: number; y: number; 
+/**
+ * Requests merged into this resize
+ */
+merged_requests: number }
+/**
+ * Entitlement as the backend sends it
+ */
+export type SignedEntitlement = { 
+/**
+ * Base64 JSON claims
+ */
+payload: string; 
+/**
+ * Base64 Ed25519 signature over the decoded payload
+ */
+signature: string }
+/**
+ * Sort direction of a paged query, by timestamp
+ */
+export type SortOrder = "asc" | "desc"
+export type SpeakerStats = { speaker: string; talk_time_ms: number; turns: number; is_candidate: boolean }
+export type StarAnswer = { question: string; story_id: string; story_title: string; 
+/**
+ * Spoken-style answer in Markdown with Situation / Task / Action / Result sections
+ */
+answer: string; 
+/**
+ * Other stories that would also fit, best first
+ */
+alternatives: StoryMatch[] }
+export type StartupPhase = { name: string; 
+/**
+ * Ran in the background after setup returned
+ */
+deferred: boolean; 
+/**
+ * Offset from process start when the phase began
+ */
+started_at_ms: number; duration_ms: number; error: string | null }
+export type StartupReport = { 
+/**
+ * Process start -> Tauri setup hook entered (runtime + webview bootstrap)
+ */
+process_to_setup_ms: number | null; 
+/**
+ * Time spent inside the setup hook (blocks the first frame)
+ */
+setup_duration_ms: number | null; 
+/**
+ * Process start -> setup hook returned
+ */
+cold_start_ms: number | null; 
+/**
+ * Process start -> last deferred service finished (None while some are still running)
+ */
+deferred_complete_ms: number | null; pending_deferred: number; phases: StartupPhase[] }
+/**
+ * Stealth hotkey event structure
+ */
+export type StealthHotkeyEvent = { action: string; hotkey_id: number; timestamp: string }
+/**
+ * Stealth status information
+ */
+export type StealthStatus = { active: boolean; registered_hotkeys: string[]; hotkey_mappings: Partial<{ [key in string]: string }> }
+export type StorageUsage = { total_bytes: number; categories: CategoryUsage[] }
+/**
+ * Payload of `store-ai-question`
+ */
+export type StoreAiQuestion = { questionText: string; questionNumber: number; category: string; difficultyLevel: string; source: string; metadata: StoreAiQuestionMetadata }
+export type StoreAiQuestionMetadata = { timestamp: string; aiProvider: string; aiModel: string; confidence: number; analysisType: string; 
+/**
+ * Minutes
+ */
+expectedDuration: number; sourceDescription: string; extractedTextLength: number }
+export type StoredDevice = { id: string; name: string }
+export type Story = { id: string; title: string; situation: string; task: string; action: string; result: string; 
+/**
+ * Themes the story demonstrates, e.g. "conflict", "leadership", "failure"
+ */
+tags?: string[]; times_used?: number; created_at: string; updated_at: string }
+/**
+ * New or edited story; on update, only provided fields change
+ */
+export type StoryInput = { title: string | null; situation: string | null; task: string | null; action: string | null; result: string | null; tags: string[] | null }
+export type StoryMatch = { story: Story; 
+/**
+ * 0-1; tag hits count more than words from the story body
+ */
+score: number }
+export type SystemDesignAnswer = { question: string; diagram_format: DiagramFormat | null; overview: string; 
+/**
+ * Diagram source without the code fence
+ */
+diagram: string; components: string; talking_points: string }
+export type SystemDesignSettings = { 
+/**
+ * Answer detected system design questions in this mode
+ */
+enabled: boolean; diagram_format: DiagramFormat }
+export type TagCount = { tag: string; count: number }
+export type TeamsMeetingInfo = { id: string; subject: string | null; start: string | null; end: string | null; join_url: string }
+export type TeamsMeetingReport = { session_id: string; meeting: TeamsMeetingInfo; 
+/**
+ * Display name of the signed-in Microsoft account
+ */
+candidate_name: string | null; transcript_available: boolean; recordings: TeamsRecording[]; speakers: SpeakerStats[]; questions: ReconciledQuestion[]; transcript: TranscriptTurn[]; report: SessionReport; imported_at: string }
+export type TeamsRecording = { id: string; created_at: string | null; content_url: string | null }
+/**
+ * Exactly what is sent to the backend
+ */
+export type TelemetryBatch = { install_id: string; app_version: string; os: string; period_start: string; period_end: string; feature_counts: Partial<{ [key in string]: number }>; error_counts: Partial<{ [key in string]: number }> }
+export type TelemetryPreview = { enabled: boolean; endpoint: string; pending_batch: TelemetryBatch }
+/**
+ * Payload of `teleprompter-state`
+ */
+export type TeleprompterState = { 
+/**
+ * Scroll speed in pixels per second
+ */
+speed: number; font_size: number; 
+/**
+ * Index into `list_displays`; `None` picks the first secondary display
+ */
+display: number | null }
+export type TempTokenAuthPayload = { session_id: string; temp_token: string }
+export type TempTokenAuthResponse = { success: boolean; message: string; user_id: string | null; session: SessionData | null; remaining_credits: number | null; 
+/**
+ * When the temp token stops working (RFC 3339), if the backend reports it
+ */
+expires_at?: string | null; 
+/**
+ * Seconds the temp token stays valid, if the backend reports it
+ */
+expires_in?: number | null; 
+/**
+ * Signed by the backend; kept for offline grace
+ */
+entitlement?: SignedEntitlement | null; 
+/**
+ * Let in from the cached entitlement because the backend could not be reached
+ */
+offline?: boolean }
+/**
+ * Payload of `temp-token-auth-result`
+ */
+export type TempTokenAuthResult = { 
+/**
+ * "temp-token-auth-success", "temp-token-auth-failed" or "temp-token-auth-error"
+ */
+type: string; session_id: string; user_id?: string | null; session?: SessionData | null; remaining_credits?: number | null; error?: string | null; auto_authenticated: boolean; launched_at: string }
+export type TestCase = { input: string; expected: string; explanation?: string }
+export type TimelineEvent = { at: string; 
+/**
+ * Milliseconds from the timeline origin; negative for events before it
+ */
+offset_ms: number; kind: TimelineEventKind; 
+/**
+ * Question or answer text, transcript segment, evaluation summary or mark label
+ */
+text: string; 
+/**
+ * Set for marks
+ */
+mark: MarkKind | null; 
+/**
+ * Transcript source ("system_audio", "zoom_captions") or speaker
+ */
+source: string | null; question_id: string | null; answer_id: string | null; 
+/**
+ * Answer score or overall rubric score
+ */
+score: number | null }
+export type TimelineEventKind = "question" | "answer" | "transcript" | "analysis" | "mark"
+export type TopicCard = { topic: QuestionTopic; 
+/**
+ * Questions asked on this topic, scored or not
+ */
+questions: number; average_score: number | null; trend: Trend }
+export type TopicStats = { 
+/**
+ * Moving average of scores, 1-10
+ */
+rating: number; 
+/**
+ * Difficulty questions are asked at, 1 (easiest) to 5
+ */
+level: number; answered: number; last_score: number | null }
+export type TranscriptLine = { timestamp: string; 
+/**
+ * Where the text came from: "system_audio" or "zoom_captions"
+ */
+source: string; speaker: string | null; text: string }
+/**
+ * Consecutive transcript cues from the same speaker
+ */
+export type TranscriptTurn = { speaker: string | null; start_ms: number; end_ms: number; text: string }
+/**
+ * Payload of `transcription-fallback`
+ */
+export type TranscriptionFallback = { 
+/**
+ * True while Vosk is transcribing instead of Deepgram
+ */
+active: boolean; engine: string; 
+/**
+ * Why the fallback started, or why it ended
+ */
+reason: string; 
+/**
+ * Local transcripts are noticeably less accurate than Deepgram's
+ */
+reduced_accuracy: boolean; 
+/**
+ * Unix milliseconds
+ */
+timestamp: number }
+export type TranscriptionPreset = "lowest_latency" | "balanced" | "highest_accuracy"
+/**
+ * Payload of `transcription-status`
+ */
+export type TranscriptionStatus = { 
+/**
+ * "starting", "streaming", "partial", "stopping", "disconnected" or "error"
+ */
+status: string; message: string; model?: string | null; 
+/**
+ * Unix milliseconds
+ */
+timestamp: number }
+/**
+ * What a preset sets
+ */
+export type TranscriptionTuning = { preset: TranscriptionPreset; 
+/**
+ * Samples per audio chunk sent for transcription (44.1kHz)
+ */
+chunk_samples: number; 
+/**
+ * Silence in milliseconds after which Deepgram finalizes a segment
+ */
+endpointing_ms: number; 
+/**
+ * Stream partial transcripts while the interviewer is speaking
+ */
+interim_results: boolean; 
+/**
+ * Gap in milliseconds after which Deepgram reports the end of an utterance
+ */
+utterance_end_ms: number | null; 
+/**
+ * Deepgram formatting of numbers, dates and punctuation
+ */
+smart_format: boolean; 
+/**
+ * Folder names under the Vosk installation, in order of preference
+ */
+local_models: string[] }
+export type Trend = { weekly: WeekPoint[]; 
+/**
+ * Change of the weekly average per week
+ */
+slope_per_week: number | null; direction: TrendDirection }
+export type TrendDirection = "improving" | "declining" | "steady" | 
+/**
+ * Fewer than two weeks with data
+ */
+"not_enough_data"
+export type TurnKind = "question" | "follow_up"
+export type UpcomingInterview = { event_id: string; provider: CalendarProvider; title: string; company: string | null; position: string | null; meeting_url: string; start: string; end: string | null; organizer: string | null; session_id: string | null; notified: boolean }
+export type UpdateChannel = "stable" | "beta"
+export type UpdateCheck = { current_version: string; channel: UpdateChannel; 
+/**
+ * Newer release offered to this install, if any
+ */
+update: ReleaseManifest | null; 
+/**
+ * The current version is below the release's minimum supported version
+ */
+mandatory: boolean; 
+/**
+ * The verified installer is already downloaded
+ */
+ready: boolean }
+/**
+ * Payload of `update-ready`
+ */
+export type UpdateReady = { version: string; notes: string | null; mandatory: boolean }
+export type User = { id: string; email: string; first_name: string; last_name: string | null; credits: number; created_at: string; last_active: string | null }
+export type UserInfo = { name: string; email: string; avatar_url: string | null }
+export type WakeAction = "show_ai_window" | 
+/**
+ * Same as the AI answer hotkey (Ctrl+Shift+Z)
+ */
+"answer_last_question"
+/**
+ * Payload of `wake-word-detected`
+ */
+export type WakeWordDetected = { phrase: string; 
+/**
+ * 0-1 similarity to the closest sample
+ */
+score: number; actions: WakeAction[] }
+export type WakeWordStatus = { enabled: boolean; 
+/**
+ * The spotter is running on the microphone right now
+ */
+listening: boolean; 
+/**
+ * Only shown to the user; detection matches the recorded samples
+ */
+phrase: string; 
+/**
+ * 0-1; higher triggers more easily
+ */
+sensitivity: number; actions: WakeAction[]; samples: number }
+export type WarmUpReport = { providers: ProviderWarmUp[]; total_ms: number }
+export type WebhookDelivery = { delivery_id: string; endpoint_id: string; url: string; event: WebhookEvent; attempts: number; status_code: number | null; error: string | null; success: boolean; created_at: string; completed_at: string }
+export type WebhookEndpoint = { id?: string; url: string; 
+/**
+ * HMAC key; generated when saved empty
+ */
+secret?: string; 
+/**
+ * Events to deliver; empty means all
+ */
+events?: WebhookEvent[]; enabled?: boolean }
+export type WebhookEvent = "session_started" | "question_detected" | "answer_generated" | "session_finalized" | 
+/**
+ * Sent only by `test_webhook`
+ */
+"test"
+export type WeekPoint = { 
+/**
+ * Monday of the week
+ */
+week_start: string; average: number; samples: number }
+export type WindowConfiguration = { width: number; height: number; x: number; y: number; scale_factor: number; monitor_name: string; monitor_size: MonitorSize }
+export type WindowProtection = { label: string; 
+/**
+ * Whether protection was last asked for; None when it never was for this window
+ */
+requested: boolean | null; state: ProtectionState; detail: string | null }
+/**
+ * Payload of `zoom-caption`
+ */
+export type ZoomCaption = { text: string; speaker: string | null; is_self: boolean; is_final: boolean; timestamp: string }
+export type ZoomCaptionSettings = { enabled: boolean; 
+/**
+ * WebSocket URL of the caption bridge
+ */
+endpoint: string; 
+/**
+ * Keep reconnecting while enabled (e.g. between meetings)
+ */
+auto_reconnect: boolean; 
+/**
+ * Treat the user's own captions as questions too (useful for testing)
+ */
+include_own_speech: boolean }
+export type ZoomCaptionStatus = { connected: boolean; endpoint: string | null; meeting_id: string | null; meeting_topic: string | null; 
+/**
+ * False when Zoom refused caption access (not host and no permission granted)
+ */
+caption_access: boolean | null; captions_received: number; questions_detected: number; last_caption_at: string | null; last_error: string | null }
+
+/** tauri-specta globals **/
+
+import {
+	invoke as TAURI_INVOKE,
+	Channel as TAURI_CHANNEL,
+} from "@tauri-apps/api/core";
+import * as TAURI_API_EVENT from "@tauri-apps/api/event";
+import { type WebviewWindow as __WebviewWindow__ } from "@tauri-apps/api/webviewWindow";
+
+type __EventObj__<T> = {
+	listen: (
+		cb: TAURI_API_EVENT.EventCallback<T>,
+	) => ReturnType<typeof TAURI_API_EVENT.listen<T>>;
+	once: (
+		cb: TAURI_API_EVENT.EventCallback<T>,
+	) => ReturnType<typeof TAURI_API_EVENT.once<T>>;
+	emit: null extends T
+		? (payload?: T) => ReturnType<typeof TAURI_API_EVENT.emit>
+		: (payload: T) => ReturnType<typeof TAURI_API_EVENT.emit>;
+};
+
+export type Result<T, E> =
+	| { status: "ok"; data: T }
+	| { status: "error"; error: E };
+
+function __makeEvents__<T extends Record<string, any>>(
+	mappings: Record<keyof T, string>,
+) {
+	return new Proxy(
+		{} as unknown as {
+			[K in keyof T]: __EventObj__<T[K]> & {
+				(handle: __WebviewWindow__): __EventObj__<T[K]>;
+			};
+		},
+		{
+			get: (_, event) => {
+				const name = mappings[event as keyof T];
+
+				return new Proxy((() => {}) as any, {
+					apply: (_, __, [window]: [__WebviewWindow__]) => ({
+						listen: (arg: any) => window.listen(name, arg),
+						once: (arg: any) => window.once(name, arg),
+						emit: (arg: any) => window.emit(name, arg),
+					}),
+					get: (_, command: keyof __EventObj__<any>) => {
+						switch (command) {
+							case "listen":
+								return (arg: any) => TAURI_API_EVENT.listen(name, arg);
+							case "once":
+								return (arg: any) => TAURI_API_EVENT.once(name, arg);
+							case "emit":
+								return (arg: any) => TAURI_API_EVENT.emit(name, arg);
+						}
+					},
+				});
+			},
+		},
+	);
+}
+
+/** backend events **/
+
+export const EVENTS = {
+	/** An AI answer started streaming */
+	AI_STREAM_START: "ai-stream-start",
+	/** A chunk of a streaming AI answer (~30ms of tokens) */
+	AI_STREAM_TOKEN: "ai-stream-token",
+	/** An AI answer finished */
+	AI_STREAM_COMPLETE: "ai-stream-complete",
+	/** An AI answer failed; the payload is the error message */
+	AI_STREAM_ERROR: "ai-stream-error",
+	/** Stream, token, completion or error update for the AI response window */
+	AI_RESPONSE_UPDATE: "ai-response-update",
+	/** The AI response window settled at a new size and position */
+	AI_RESPONSE_GEOMETRY: "ai-response-geometry",
+	/** Typed coding solution for the response window, next to its Markdown rendering */
+	CODING_SOLUTION: "coding-solution",
+	/** Typed STAR answer for the response window, next to its Markdown rendering */
+	STAR_ANSWER: "star-answer",
+	/** One streamed piece of a system design answer section */
+	SYSTEM_DESIGN_SECTION: "system-design-section",
+	/** The finished, typed system design answer */
+	SYSTEM_DESIGN_ANSWER: "system-design-answer",
+	/** A question generated from screen analysis, for the frontend to save */
+	STORE_AI_QUESTION: "store-ai-question",
+	/** A question was read from another app's window */
+	ACCESSIBILITY_QUESTION_DETECTED: "accessibility-question-detected",
+	/** Base64 PCM chunk of system audio */
+	AUDIO_CHUNK: "audio-chunk",
+	/** System audio level for visualization */
+	AUDIO_LEVEL: "audio-level",
+	/** Speech started in system audio */
+	SPEECH_START: "speech-start",
+	/** Base64 PCM of a system audio speech segment */
+	SPEECH_DETECTED: "speech-detected",
+	/** Base64 PCM chunk of microphone audio */
+	MIC_AUDIO_CHUNK: "mic-audio-chunk",
+	/** Speech started on the microphone */
+	MIC_SPEECH_START: "mic-speech-start",
+	/** Base64 PCM of a microphone speech segment */
+	MIC_SPEECH_DETECTED: "mic-speech-detected",
+	/** Free-form system audio capture diagnostics */
+	PLUELY_AUDIO_DEBUG: "pluely-audio-debug",
+	/** Free-form microphone capture diagnostics */
+	PLUELY_MICROPHONE_DEBUG: "pluely-microphone-debug",
+	/** Deepgram streaming connected, disconnected or stopped */
+	DEEPGRAM_STATUS: "deepgram-status",
+	/** Progress of the combined capture and transcription pipeline */
+	TRANSCRIPTION_STATUS: "transcription-status",
+	/** Interim or final transcript of system audio */
+	TRANSCRIPTION_RESULT: "transcription-result",
+	/** The transcription connection failed */
+	TRANSCRIPTION_ERROR: "transcription-error",
+	/** An audio debug dump finished writing */
+	AUDIO_DEBUG_DUMP_COMPLETE: "audio-debug-dump-complete",
+	/** A caption received from the Zoom caption bridge */
+	ZOOM_CAPTION: "zoom-caption",
+	/** Zoom caption bridge connection status */
+	ZOOM_CAPTION_STATUS: "zoom-caption-status",
+	/** The app was launched for a session from a deep link */
+	SESSION_LAUNCH: "session-launch",
+	/** Outcome of authenticating a deep-link launch with its temporary token */
+	TEMP_TOKEN_AUTH_RESULT: "temp-token-auth-result",
+	/** Session recording started or stopped */
+	SESSION_RECORDING_STATE: "session-recording-state",
+	/** Speaking pace feedback on the current answer */
+	PACING_FEEDBACK: "pacing-feedback",
+	/** A practice session changed state or moved to a new turn */
+	PRACTICE_UPDATE: "practice-update",
+	/** A calendar interview is about to start */
+	INTERVIEW_UPCOMING: "interview-upcoming",
+	/** AI and transcription providers were warmed up for a session */
+	PROVIDERS_WARMED_UP: "providers-warmed-up",
+	/** A stealth hotkey was pressed */
+	STEALTH_HOTKEY: "stealth-hotkey",
+	/** An OS permission was revoked and dependent capture paused */
+	PERMISSION_REVOKED: "permission-revoked",
+	/** Reading text from an app needs the user's consent */
+	CONSENT_REQUIRED: "consent-required",
+	/** OS dark mode, contrast, motion or transparency preferences changed */
+	OS_APPEARANCE_CHANGED: "os-appearance-changed",
+	/** An update was downloaded and is ready to install */
+	UPDATE_READY: "update-ready",
+	/** Periodic latency percentiles per metric */
+	METRICS_SNAPSHOT: "metrics-snapshot",
+	/** A backend log record */
+	LOG_EVENT: "log-event",
+	/** A user script asked to show a notification */
+	SCRIPT_NOTIFICATION: "script-notification",
+	/** A user script emitted a custom UI event */
+	SCRIPT_EVENT: "script-event",
+	/** A JSON message from the backend WebSocket */
+	WEBSOCKET_MESSAGE: "websocket-message",
+	/** The backend WebSocket failed */
+	WEBSOCKET_ERROR: "websocket-error",
+	/** The backend WebSocket closed; the payload is an empty object */
+	WEBSOCKET_CLOSED: "websocket-closed",
+	/** Progress of a long-running command, which `cancel_request` can stop */
+	PROGRESS: "progress",
+	/** A deprecated command alias was called */
+	DEPRECATED_COMMAND: "deprecated-command",
+	/** A preferred microphone or loopback device was connected, disconnected or reconnected under a new ID */
+	AUDIO_DEVICES_CHANGED: "audio-devices-changed",
+	/** The push-to-talk gate opened or closed, or the capture mode changed */
+	PTT_STATE: "ptt-state",
+	/** The wake phrase was heard on the microphone */
+	WAKE_WORD_DETECTED: "wake-word-detected",
+	/** A Teams, Zoom or Meet call became active */
+	MEETING_STARTED: "meeting-started",
+	/** A detected call ended */
+	MEETING_ENDED: "meeting-ended",
+	/** A large block of new text appeared in the monitored window and is being analyzed */
+	AUTO_ANALYSIS_TRIGGERED: "auto-analysis-triggered",
+	/** Teleprompter mode was turned on or off, or its speed, pause or font size changed */
+	TELEPROMPTER_STATE: "teleprompter-state",
+	/** A question asked in another language was translated into the answer language */
+	QUESTION_TRANSLATED: "question-translated",
+	/** A question matched one seen moments ago and reuses its answer instead of a new AI call */
+	QUESTION_DEDUPLICATED: "question-deduplicated",
+	/** Transcription switched to the offline Vosk fallback (reduced accuracy) or back to Deepgram */
+	TRANSCRIPTION_FALLBACK: "transcription-fallback",
+	/** The credit balance of the session's user was re-read */
+	CREDIT_BALANCE: "credit-balance",
+	/** A credit checkout was opened, completed or cancelled */
+	CREDIT_PURCHASE: "credit-purchase",
+	/** A session's temp token was authenticated or refreshed, failed to refresh, or expired */
+	SESSION_AUTH: "session-auth",
+	/** Desktop sign-in started, completed or ended */
+	AUTH_STATE: "auth-state",
+} as const;
+
+export type EventPayloads = {
+	"ai-stream-start": null;
+	"ai-stream-token": AiStreamToken;
+	"ai-stream-complete": AiStreamComplete;
+	"ai-stream-error": string;
+	"ai-response-update": ResponseUpdate;
+	"ai-response-geometry": SettledGeometry;
+	"coding-solution": CodingSolution;
+	"star-answer": StarAnswer;
+	"system-design-section": SectionChunk;
+	"system-design-answer": SystemDesignAnswer;
+	"store-ai-question": StoreAiQuestion;
+	"accessibility-question-detected": AccessibilityTextResult;
+	"audio-chunk": string;
+	"audio-level": AudioLevel;
+	"speech-start": null;
+	"speech-detected": string;
+	"mic-audio-chunk": string;
+	"mic-speech-start": null;
+	"mic-speech-detected": string;
+	"pluely-audio-debug": JsonValue;
+	"pluely-microphone-debug": JsonValue;
+	"deepgram-status": DeepgramStatus;
+	"transcription-status": TranscriptionStatus;
+	"transcription-result": DeepgramTranscriptionResult;
+	"transcription-error": ErrorPayload;
+	"audio-debug-dump-complete": AudioDebugDumpComplete;
+	"zoom-caption": ZoomCaption;
+	"zoom-caption-status": ZoomCaptionStatus;
+	"session-launch": SessionLaunch;
+	"temp-token-auth-result": TempTokenAuthResult;
+	"session-recording-state": RecordingStatus;
+	"pacing-feedback": PacingFeedback;
+	"practice-update": PracticeUpdate;
+	"interview-upcoming": UpcomingInterview;
+	"providers-warmed-up": WarmUpReport;
+	"stealth-hotkey": StealthHotkeyEvent;
+	"permission-revoked": PermissionRevokedEvent;
+	"consent-required": ConsentRequest;
+	"os-appearance-changed": AppearancePrefs;
+	"update-ready": UpdateReady;
+	"metrics-snapshot": MetricSummary[];
+	"log-event": LogEntry;
+	"script-notification": ScriptNotification;
+	"script-event": ScriptUiEvent;
+	"websocket-message": JsonValue;
+	"websocket-error": ErrorPayload;
+	"websocket-closed": JsonValue;
+	"progress": RequestProgress;
+	"deprecated-command": DeprecatedCommand;
+	"audio-devices-changed": AudioDeviceSelection;
+	"ptt-state": PttState;
+	"wake-word-detected": WakeWordDetected;
+	"meeting-started": MeetingEvent;
+	"meeting-ended": MeetingEvent;
+	"auto-analysis-triggered": AutoAnalysisTriggered;
+	"teleprompter-state": TeleprompterState;
+	"question-translated": QuestionTranslation;
+	"question-deduplicated": QuestionDeduplicated;
+	"transcription-fallback": TranscriptionFallback;
+	"credit-balance": CreditBalance;
+	"credit-purchase": CreditPurchase;
+	"session-auth": SessionAuthStatus;
+	"auth-state": AuthStatus;
+};
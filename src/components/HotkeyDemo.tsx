@@ -1,5 +1,5 @@
 import React, { useEffect, useState } from 'react';
-import { invoke } from '@tauri-apps/api/core';
+import { invoke } from '@/lib/ipc';
 import { listen } from '@tauri-apps/api/event';
 
 interface StealthHotkeyEvent {
@@ -1,10 +1,16 @@
 import { useState, useEffect } from 'react';
-import { invoke } from '@tauri-apps/api/core';
+import { commands } from '@/bindings';
+import { call } from '@/lib/ipc';
 
 interface PermissionManagerProps {
   onPermissionsGranted?: () => void;
 }
 
+const checkMicrophone = async () => {
+  const report = await call(commands.checkPermissions());
+  return report.permissions.find((status) => status.permission === 'microphone');
+};
+
 export function PermissionManager({ onPermissionsGranted }: PermissionManagerProps) {
   const [isChecking, setIsChecking] = useState(true);
   const [needsPermissions, setNeedsPermissions] = useState(false);
@@ -18,9 +24,9 @@ export function PermissionManager({ onPermissionsGranted }: PermissionManagerPro
   const checkPermissions = async () => {
     try {
       setIsChecking(true);
-      const hasPermissions = await invoke<boolean>('check_permissions');
+      const microphone = await checkMicrophone();
       
-      if (hasPermissions) {
+      if (microphone?.usable ?? true) {
         console.log('✅ Audio permissions already granted');
         onPermissionsGranted?.();
       } else {
@@ -41,21 +47,21 @@ export function PermissionManager({ onPermissionsGranted }: PermissionManagerPro
       setPermissionError(null);
       
       // Initialize first run permissions
-      await invoke('initialize_first_run');
+      await call(commands.initializeFirstRun());
       
       // Request microphone permissions
-      await invoke('request_permissions');
+      await call(commands.requestPermissions('microphone'));
       
       // Wait a moment for user to grant permissions
       setTimeout(async () => {
-        const hasPermissions = await invoke<boolean>('check_permissions');
-        if (hasPermissions) {
+        const microphone = await checkMicrophone();
+        if (microphone?.usable ?? true) {
           console.log('✅ Permissions granted successfully');
           setNeedsPermissions(false);
           onPermissionsGranted?.();
         } else {
           console.log('⚠️ Permissions still not granted');
-          setPermissionError('Please enable microphone access in Windows Settings');
+          setPermissionError(microphone?.remediation?.instructions ?? 'Please enable microphone access in your system settings');
         }
         setIsRequestingPermissions(false);
       }, 3000);
@@ -1,5 +1,5 @@
 import React, { useState, useEffect } from 'react';
-import { invoke } from '@tauri-apps/api/core';
+import { invoke } from '@/lib/ipc';
 import { listen } from '@tauri-apps/api/event';
 import { useStealthMode } from '../hooks/useStealthMode';
 
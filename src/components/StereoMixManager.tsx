@@ -1,10 +1,27 @@
 import { useState, useEffect } from 'react';
-import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { commands } from '@/bindings';
+import { call, type RequestProgress } from '@/lib/ipc';
 
+/** What each `enable_stereo_mix` progress stage means to the user */
+const ENABLE_STAGES: Record<string, string> = {
+  checking: 'Looking for Stereo Mix...',
+  requesting_elevation: 'Waiting for administrator approval...',
+  enabling: 'Enabling Stereo Mix...',
+  unmuting: 'Unmuting Stereo Mix...',
+  verifying: 'Checking that Stereo Mix records...',
+};
+
+/** What `get_stereo_mix_capabilities` returns; the command is typed as plain JSON */
 interface StereoMixCapabilities {
   stereo_mix_available: boolean;
   alternative_devices: string[];
   requires_manual_enable: boolean;
+  /** WASAPI loopback of the default output works, so Stereo Mix is not needed */
+  loopback_available: boolean;
+  loopback_error: string | null;
+  stereo_mix_required: boolean;
+  preferred_source: 'wasapi_loopback' | 'stereo_mix';
   system_info: {
     os: string;
     arch: string;
@@ -42,13 +59,20 @@ export function StereoMixManager({
 
       // Check current system capabilities
       console.log('🔍 Checking Stereo Mix capabilities...');
-      const caps = await invoke<StereoMixCapabilities>('get_stereo_mix_capabilities');
+      const caps = await call(commands.getStereoMixCapabilities()) as unknown as StereoMixCapabilities;
       setCapabilities(caps);
       
       console.log('📊 Stereo Mix capabilities:', caps);
 
+      // System audio is captured through loopback; Stereo Mix is only a fallback
+      if (!caps.stereo_mix_required) {
+        console.log('✅ WASAPI loopback available, Stereo Mix not needed');
+        onStereoMixReady?.(true);
+        return;
+      }
+
       // Check if Stereo Mix is currently enabled
-      const isEnabled = await invoke<boolean>('check_stereo_mix_enabled');
+      const isEnabled = await call(commands.checkStereoMixEnabled());
       setStereoMixEnabled(isEnabled);
       
       console.log('🎵 Stereo Mix currently enabled:', isEnabled);
@@ -78,7 +102,18 @@ export function StereoMixManager({
       setEnableMessage('Attempting to enable Stereo Mix automatically...');
       
       console.log('🔧 Starting automatic Stereo Mix enablement...');
-      const result = await invoke<string>('enable_stereo_mix');
+      const requestId = crypto.randomUUID();
+      const unlisten = await listen<RequestProgress>('progress', ({ payload }) => {
+        if (payload.request_id === requestId && ENABLE_STAGES[payload.stage]) {
+          setEnableMessage(ENABLE_STAGES[payload.stage]);
+        }
+      });
+      let result: string;
+      try {
+        result = await call(commands.enableStereoMix(requestId));
+      } finally {
+        unlisten();
+      }
       
       console.log('📝 Auto-enable result:', result);
       setEnableMessage(result);
@@ -86,7 +121,7 @@ export function StereoMixManager({
       // Wait a moment and check if it worked
       await new Promise(resolve => setTimeout(resolve, 2000));
       
-      const isEnabled = await invoke<boolean>('check_stereo_mix_enabled');
+      const isEnabled = await call(commands.checkStereoMixEnabled());
       setStereoMixEnabled(isEnabled);
       
       if (isEnabled) {
@@ -120,7 +155,7 @@ export function StereoMixManager({
 
   const loadManualInstructions = async () => {
     try {
-      const instructionList = await invoke<string[]>('get_stereo_mix_instructions');
+      const instructionList = await call(commands.getStereoMixInstructions());
       setInstructions(instructionList);
     } catch (error) {
       console.error('Failed to load manual instructions:', error);
@@ -129,7 +164,7 @@ export function StereoMixManager({
 
   const openRecordingDevices = async () => {
     try {
-      await invoke('open_recording_devices');
+      await call(commands.openRecordingDevices());
       console.log('📂 Opened Windows Recording devices');
     } catch (error) {
       console.error('❌ Failed to open Recording devices:', error);
@@ -140,7 +175,7 @@ export function StereoMixManager({
   const recheckStereoMix = async () => {
     try {
       setIsChecking(true);
-      const isEnabled = await invoke<boolean>('check_stereo_mix_enabled');
+      const isEnabled = await call(commands.checkStereoMixEnabled());
       setStereoMixEnabled(isEnabled);
       
       if (isEnabled) {
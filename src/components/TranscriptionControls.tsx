@@ -1,5 +1,5 @@
 import { useState } from 'react';
-import { invoke } from '@tauri-apps/api/core';
+import { invoke } from '@/lib/ipc';
 import { Button } from '@/components/ui/button';
 
 interface TranscriptionControlsProps {
@@ -30,7 +30,7 @@ export default function TranscriptionControls({
       onStatusChange(true);
     } catch (error) {
       console.error('❌ Failed to start transcription:', error);
-      setLastError(error as string);
+      setLastError(String(error));
     } finally {
       setIsStarting(false);
     }
@@ -49,7 +49,7 @@ export default function TranscriptionControls({
       onStatusChange(false);
     } catch (error) {
       console.error('❌ Failed to stop transcription:', error);
-      setLastError(error as string);
+      setLastError(String(error));
     } finally {
       setIsStopping(false);
     }
@@ -3,7 +3,7 @@
 
 import { useState, useEffect, useRef, useCallback } from 'react';
 import { createClient, LiveTranscriptionEvents } from '@deepgram/sdk';
-import { invoke } from '@tauri-apps/api/core';
+import { invoke } from '@/lib/ipc';
 import { listen } from '@tauri-apps/api/event';
 
 interface DeepgramConfig {
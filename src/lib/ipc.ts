@@ -0,0 +1,120 @@
+import { invoke as tauriInvoke, type InvokeArgs } from '@tauri-apps/api/core';
+
+/** IPC API version this frontend was built against; `get_api_version` reports the backend's */
+export const API_VERSION = 2;
+
+export type ErrorCode =
+  | 'not_found'
+  | 'invalid_input'
+  | 'unsupported'
+  | 'permission_denied'
+  | 'privacy_blocked'
+  | 'unauthorized'
+  | 'network'
+  | 'timeout'
+  | 'unavailable'
+  | 'cancelled'
+  | 'no_active_session'
+  | 'internal';
+
+/** Shape of the `AppError` every backend command rejects with */
+export interface AppErrorPayload {
+  code: ErrorCode;
+  message: string;
+  retryable: boolean;
+  subsystem: string;
+  remediation: string | null;
+}
+
+/** What every backend command resolves (ok) or rejects (not ok) with */
+export interface Envelope<T> {
+  ok: boolean;
+  data: T | null;
+  error: AppErrorPayload | null;
+  request_id: string;
+  duration_ms: number;
+}
+
+/** A failed command; `String(error)` and `${error}` still give the plain message */
+export class CommandError extends Error {
+  readonly code: ErrorCode;
+  readonly retryable: boolean;
+  readonly subsystem: string;
+  readonly remediation: string | null;
+  /** Matches the backend log line for this failure */
+  readonly requestId: string;
+  readonly durationMs: number;
+
+  constructor(payload: AppErrorPayload, requestId: string, durationMs: number) {
+    super(payload.message);
+    this.name = 'CommandError';
+    this.code = payload.code;
+    this.retryable = payload.retryable;
+    this.subsystem = payload.subsystem;
+    this.remediation = payload.remediation;
+    this.requestId = requestId;
+    this.durationMs = durationMs;
+  }
+
+  toString(): string {
+    return this.message;
+  }
+}
+
+function isEnvelope(value: unknown): value is Envelope<unknown> {
+  return typeof value === 'object' && value !== null && 'ok' in value && 'request_id' in value;
+}
+
+function toCommandError(error: unknown): unknown {
+  // Rejections that never reached the command (unknown command, bad arguments) are plain strings
+  if (isEnvelope(error) && error.error) {
+    return new CommandError(error.error, error.request_id, error.duration_ms);
+  }
+  return error;
+}
+
+/** Like `invoke` from @tauri-apps/api, full envelope included */
+export async function invokeEnvelope<T>(cmd: string, args?: InvokeArgs): Promise<Envelope<T>> {
+  try {
+    return await tauriInvoke<Envelope<T>>(cmd, args);
+  } catch (error) {
+    throw toCommandError(error);
+  }
+}
+
+/** Data of a typed command from `@/bindings`, e.g. `await call(commands.getPacingReport(sessionId))` */
+export async function call<T>(pending: Promise<Envelope<T>>): Promise<T> {
+  try {
+    return (await pending).data as T;
+  } catch (error) {
+    throw toCommandError(error);
+  }
+}
+
+/** `invoke` from @tauri-apps/api that resolves with the command's data and rejects with a `CommandError` */
+export async function invoke<T>(cmd: string, args?: InvokeArgs): Promise<T> {
+  const envelope = await invokeEnvelope<T>(cmd, args);
+  return envelope.data as T;
+}
+
+/** Payload of the `progress` event long-running commands emit under their request ID */
+export interface RequestProgress {
+  request_id: string;
+  command: string;
+  stage: string;
+  percent: number | null;
+  done: boolean;
+}
+
+/** Stop a long-running command started with this `requestId`; false when it is not running */
+export function cancelRequest(requestId: string): Promise<boolean> {
+  return invoke<boolean>('cancel_request', { requestId });
+}
+
+/**
+ * Key for a credit-spending command (activation, connection); reuse it when retrying the same
+ * action so the backend charges once, and make a new one for the next action
+ */
+export function newIdempotencyKey(): string {
+  return crypto.randomUUID();
+}